@@ -0,0 +1,82 @@
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{Benchmark, TimestampsResult};
+use cubecl::linalg::tensor::TensorHandle;
+use cubecl::prelude::*;
+use cubecl::{calculate_cube_count_elemwise, future};
+
+use super::Workload;
+use crate::Work;
+
+#[cube(launch_unchecked)]
+fn elementwise_kernel<F: Float>(lhs: &Tensor<F>, rhs: &Tensor<F>, output: &mut Tensor<F>) {
+    if ABSOLUTE_POS < output.len() {
+        let lhs = lhs[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = lhs * rhs[ABSOLUTE_POS] + F::exp(lhs);
+    }
+}
+
+/// A binary elementwise kernel mixing arithmetic and a transcendental function.
+#[derive(new)]
+pub struct ElementwiseBench<R: Runtime, E> {
+    len: usize,
+    vectorization: u8,
+    client: ComputeClient<R::Server, R::Channel>,
+    #[new(default)]
+    _e: PhantomData<E>,
+}
+
+impl<R: Runtime, E: Float> Benchmark for ElementwiseBench<R, E> {
+    type Args = (TensorHandle<R, E>, TensorHandle<R, E>, TensorHandle<R, E>);
+
+    fn prepare(&self) -> Self::Args {
+        let lhs = TensorHandle::zeros(&self.client, vec![self.len]);
+        let rhs = TensorHandle::zeros(&self.client, vec![self.len]);
+        let output = TensorHandle::empty(&self.client, vec![self.len]);
+
+        (lhs, rhs, output)
+    }
+
+    fn execute(&self, (lhs, rhs, output): Self::Args) {
+        let cube_dim = CubeDim::default();
+        let cube_count =
+            calculate_cube_count_elemwise(self.len / self.vectorization as usize, cube_dim);
+
+        unsafe {
+            elementwise_kernel::launch_unchecked::<E, R>(
+                &self.client,
+                cube_count,
+                cube_dim,
+                lhs.as_arg(self.vectorization),
+                rhs.as_arg(self.vectorization),
+                output.as_arg(self.vectorization),
+            )
+        }
+    }
+
+    fn num_samples(&self) -> usize {
+        50
+    }
+
+    fn name(&self) -> String {
+        format!("elementwise-{}", E::as_elem()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.len]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
+
+impl<R: Runtime, E: Float> Workload for ElementwiseBench<R, E> {
+    fn work(&self) -> Work {
+        Work::Bytes((3 * self.len * core::mem::size_of::<E>()) as u64)
+    }
+}
@@ -0,0 +1,63 @@
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{Benchmark, TimestampsResult};
+use cubecl::future;
+use cubecl::linalg::{matmul, tensor::TensorHandle};
+use cubecl::prelude::*;
+
+use super::Workload;
+use crate::Work;
+
+/// A batched matrix multiplication with the strategy selected for the device.
+#[derive(new)]
+pub struct MatmulBench<R: Runtime, E> {
+    b: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+    client: ComputeClient<R::Server, R::Channel>,
+    #[new(default)]
+    _e: PhantomData<E>,
+}
+
+impl<R: Runtime, E: Float> Benchmark for MatmulBench<R, E> {
+    type Args = (TensorHandle<R, E>, TensorHandle<R, E>, TensorHandle<R, E>);
+
+    fn prepare(&self) -> Self::Args {
+        let lhs = TensorHandle::zeros(&self.client, vec![self.b, self.m, self.k]);
+        let rhs = TensorHandle::zeros(&self.client, vec![self.b, self.k, self.n]);
+        let out = TensorHandle::empty(&self.client, vec![self.b, self.m, self.n]);
+
+        (lhs, rhs, out)
+    }
+
+    fn execute(&self, (lhs, rhs, out): Self::Args) {
+        matmul::launch_ref::<R, E>(&self.client, lhs.as_ref(), rhs.as_ref(), out.as_ref());
+    }
+
+    fn num_samples(&self) -> usize {
+        10
+    }
+
+    fn name(&self) -> String {
+        format!("matmul-{}", E::as_elem()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.b, self.m, self.k], vec![self.b, self.k, self.n]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
+
+impl<R: Runtime, E: Float> Workload for MatmulBench<R, E> {
+    fn work(&self) -> Work {
+        Work::Flops((2 * self.b * self.m * self.k * self.n) as u64)
+    }
+}
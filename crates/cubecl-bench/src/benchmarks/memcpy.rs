@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{Benchmark, TimestampsResult};
+use cubecl::linalg::tensor::TensorHandle;
+use cubecl::prelude::*;
+use cubecl::{calculate_cube_count_elemwise, future};
+
+use super::Workload;
+use crate::Work;
+
+#[cube(launch_unchecked)]
+fn copy_kernel<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+    }
+}
+
+/// Copy a buffer to another, measuring the memory bandwidth.
+#[derive(new)]
+pub struct MemcpyBench<R: Runtime, E> {
+    len: usize,
+    vectorization: u8,
+    client: ComputeClient<R::Server, R::Channel>,
+    #[new(default)]
+    _e: PhantomData<E>,
+}
+
+impl<R: Runtime, E: Float> Benchmark for MemcpyBench<R, E> {
+    type Args = (TensorHandle<R, E>, TensorHandle<R, E>);
+
+    fn prepare(&self) -> Self::Args {
+        let input = TensorHandle::zeros(&self.client, vec![self.len]);
+        let output = TensorHandle::empty(&self.client, vec![self.len]);
+
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Args) {
+        let cube_dim = CubeDim::default();
+        let cube_count =
+            calculate_cube_count_elemwise(self.len / self.vectorization as usize, cube_dim);
+
+        unsafe {
+            copy_kernel::launch_unchecked::<E, R>(
+                &self.client,
+                cube_count,
+                cube_dim,
+                input.as_arg(self.vectorization),
+                output.as_arg(self.vectorization),
+            )
+        }
+    }
+
+    fn num_samples(&self) -> usize {
+        50
+    }
+
+    fn name(&self) -> String {
+        format!("memcpy-{}", E::as_elem()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.len]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
+
+impl<R: Runtime, E: Float> Workload for MemcpyBench<R, E> {
+    fn work(&self) -> Work {
+        Work::Bytes((2 * self.len * core::mem::size_of::<E>()) as u64)
+    }
+}
@@ -0,0 +1,102 @@
+mod elementwise;
+mod matmul;
+mod memcpy;
+mod reduction;
+
+pub use elementwise::*;
+pub use matmul::*;
+pub use memcpy::*;
+pub use reduction::*;
+
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::prelude::*;
+
+use crate::{Record, Work};
+
+/// Options of a [suite](run_suite) run.
+#[derive(Debug, Clone, Default)]
+pub struct SuiteOptions {
+    /// Only run the benchmarks whose name contains the filter.
+    pub filter: Option<String>,
+    /// How the durations are measured.
+    pub timing: TimingMethod,
+}
+
+/// Run the standard benchmarks on the runtime.
+pub fn run_suite<R: Runtime>(device: &R::Device, options: &SuiteOptions) -> Vec<Record> {
+    let client = R::client(device);
+    if let TimingMethod::DeviceOnly = options.timing {
+        client.enable_timestamps();
+    }
+
+    let selected = |name: &str| {
+        options
+            .filter
+            .iter()
+            .all(|filter| name.contains(filter.as_str()))
+    };
+    let mut records = Vec::new();
+
+    for len in [1 << 20, 1 << 24] {
+        if selected("memcpy") {
+            let bench = MemcpyBench::<R, f32>::new(len, 4, client.clone());
+            records.push(measure::<R, _>(&bench, options.timing));
+        }
+        if selected("elementwise") {
+            let bench = ElementwiseBench::<R, f32>::new(len, 4, client.clone());
+            records.push(measure::<R, _>(&bench, options.timing));
+        }
+        if selected("reduction") {
+            let bench = ReductionBench::<R, f32>::new(len, client.clone());
+            records.push(measure::<R, _>(&bench, options.timing));
+        }
+    }
+
+    if selected("matmul") {
+        for (b, m, k, n) in [
+            (1, 256, 256, 256),
+            (1, 1024, 1024, 1024),
+            (1, 2048, 2048, 2048),
+            (8, 512, 512, 512),
+        ] {
+            let bench = MatmulBench::<R, f32>::new(b, m, k, n, client.clone());
+            records.push(measure::<R, _>(&bench, options.timing));
+        }
+    }
+
+    records
+}
+
+/// A benchmark whose amount of work is known, to report its throughput.
+pub trait Workload: Benchmark {
+    fn work(&self) -> Work;
+}
+
+fn measure<R: Runtime, B: Workload>(benchmark: &B, timing: TimingMethod) -> Record {
+    let durations = benchmark.run(timing);
+    let computed = BenchmarkComputations::new(&durations);
+
+    Record {
+        benchmark: benchmark.name(),
+        shape: benchmark
+            .shapes()
+            .iter()
+            .map(|shape| {
+                shape
+                    .iter()
+                    .map(|dim| dim.to_string())
+                    .collect::<Vec<_>>()
+                    .join("x")
+            })
+            .collect::<Vec<_>>()
+            .join("+"),
+        runtime: R::name().to_string(),
+        timing,
+        samples: durations.durations.len(),
+        mean: computed.mean,
+        median: computed.median,
+        min: computed.min,
+        max: computed.max,
+        work: Some(benchmark.work()),
+    }
+}
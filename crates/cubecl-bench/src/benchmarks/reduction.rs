@@ -0,0 +1,117 @@
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{Benchmark, TimestampsResult};
+use cubecl::future;
+use cubecl::linalg::tensor::TensorHandle;
+use cubecl::prelude::*;
+
+use super::Workload;
+use crate::Work;
+
+const CUBE_DIM: u32 = 256;
+/// The number of values summed by each unit before the values of the cube are reduced.
+const VALUES_PER_UNIT: u32 = 4;
+
+/// Sum the values of each cube in shared memory, writing one partial sum per cube.
+#[cube(launch_unchecked)]
+fn sum_cube_kernel<F: Float>(
+    input: &Tensor<F>,
+    output: &mut Tensor<F>,
+    #[comptime] cube_dim: u32,
+    #[comptime] values_per_unit: u32,
+) {
+    let mut shared = SharedMemory::<F>::new(cube_dim);
+    let start = CUBE_POS * cube_dim * values_per_unit + UNIT_POS;
+
+    let mut sum = F::new(0.0);
+    #[unroll]
+    for i in 0..values_per_unit {
+        let index = start + i * cube_dim;
+        if index < input.len() {
+            sum += input[index];
+        }
+    }
+    shared[UNIT_POS] = sum;
+    sync_units();
+
+    let mut stride = cube_dim / 2;
+    while stride > 0 {
+        if UNIT_POS < stride {
+            let other = shared[UNIT_POS + stride];
+            shared[UNIT_POS] += other;
+        }
+        sync_units();
+        stride /= 2;
+    }
+
+    if UNIT_POS == 0 {
+        output[CUBE_POS] = shared[0];
+    }
+}
+
+/// Sum a buffer down to one value per cube, measuring a memory bound reduction with
+/// synchronization.
+#[derive(new)]
+pub struct ReductionBench<R: Runtime, E> {
+    len: usize,
+    client: ComputeClient<R::Server, R::Channel>,
+    #[new(default)]
+    _e: PhantomData<E>,
+}
+
+impl<R: Runtime, E: Float> ReductionBench<R, E> {
+    fn num_cubes(&self) -> u32 {
+        (self.len as u32).div_ceil(CUBE_DIM * VALUES_PER_UNIT)
+    }
+}
+
+impl<R: Runtime, E: Float> Benchmark for ReductionBench<R, E> {
+    type Args = (TensorHandle<R, E>, TensorHandle<R, E>);
+
+    fn prepare(&self) -> Self::Args {
+        let input = TensorHandle::zeros(&self.client, vec![self.len]);
+        let output = TensorHandle::empty(&self.client, vec![self.num_cubes() as usize]);
+
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Args) {
+        unsafe {
+            sum_cube_kernel::launch_unchecked::<E, R>(
+                &self.client,
+                CubeCount::Static(self.num_cubes(), 1, 1),
+                CubeDim::new(CUBE_DIM, 1, 1),
+                input.as_arg(1),
+                output.as_arg(1),
+                CUBE_DIM,
+                VALUES_PER_UNIT,
+            )
+        }
+    }
+
+    fn num_samples(&self) -> usize {
+        50
+    }
+
+    fn name(&self) -> String {
+        format!("reduction-{}", E::as_elem()).to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.len]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn sync_elapsed(&self) -> TimestampsResult {
+        future::block_on(self.client.sync_elapsed())
+    }
+}
+
+impl<R: Runtime, E: Float> Workload for ReductionBench<R, E> {
+    fn work(&self) -> Work {
+        Work::Bytes((self.len * core::mem::size_of::<E>()) as u64)
+    }
+}
@@ -0,0 +1,19 @@
+//! Standard benchmarks run on each enabled runtime, with reports comparing the runtimes and
+//! releases.
+//!
+//! Run them with the runtimes to compare, writing a markdown or CSV report:
+//!
+//! ```sh
+//! cargo run -p cubecl-bench --release --features wgpu,cuda -- --format csv --output bench.csv
+//! ```
+//!
+//! Passing a previous CSV report with `--baseline` shows the change of each median duration.
+
+#[macro_use]
+extern crate derive_new;
+
+mod benchmarks;
+mod report;
+
+pub use benchmarks::*;
+pub use report::*;
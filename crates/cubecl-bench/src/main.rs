@@ -0,0 +1,157 @@
+use cubecl::benchmark::TimingMethod;
+use cubecl_bench::{Report, SuiteOptions};
+use std::fmt;
+
+const USAGE: &str = "Usage: cubecl-bench [--format markdown|csv] [--output PATH] \
+                     [--baseline CSV_PATH] [--filter NAME] [--timing device|full]";
+
+/// An error ending the command.
+enum Error {
+    /// The arguments are invalid.
+    Args(String),
+    /// The baseline report can't be read.
+    Baseline { path: String, reason: String },
+    /// The report can't be written.
+    Output { path: String, reason: String },
+    /// No runtime feature is enabled.
+    NoRuntime,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Args(reason) => write!(f, "{reason}\n{USAGE}"),
+            Error::Baseline { path, reason } => write!(f, "Invalid baseline {path}: {reason}"),
+            Error::Output { path, reason } => {
+                write!(f, "Can't write the report to {path}: {reason}")
+            }
+            Error::NoRuntime => f.write_str(
+                "No benchmark was run, enable the features of the runtimes to benchmark",
+            ),
+        }
+    }
+}
+
+// The error returned by `main` is printed with its debug representation.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[derive(Default)]
+struct Args {
+    csv: bool,
+    output: Option<String>,
+    baseline: Option<String>,
+    options: SuiteOptions,
+}
+
+fn parse_args() -> Result<Args, Error> {
+    let mut args = Args {
+        options: SuiteOptions {
+            timing: TimingMethod::DeviceOnly,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| Error::Args(format!("Missing value for {arg}")))
+        };
+        match arg.as_str() {
+            "--format" => {
+                args.csv = match value()?.as_str() {
+                    "csv" => true,
+                    "markdown" => false,
+                    format => return Err(Error::Args(format!("Unknown format {format}"))),
+                }
+            }
+            "--output" => args.output = Some(value()?),
+            "--baseline" => args.baseline = Some(value()?),
+            "--filter" => args.options.filter = Some(value()?),
+            "--timing" => {
+                args.options.timing = match value()?.as_str() {
+                    "device" => TimingMethod::DeviceOnly,
+                    "full" => TimingMethod::Full,
+                    timing => return Err(Error::Args(format!("Unknown timing {timing}"))),
+                }
+            }
+            _ => return Err(Error::Args(format!("Unknown argument {arg}"))),
+        }
+    }
+
+    Ok(args)
+}
+
+fn read_baseline(path: &str) -> Result<Report, Error> {
+    let error = |reason: String| Error::Baseline {
+        path: path.to_string(),
+        reason,
+    };
+    let csv = std::fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+
+    Report::from_csv(&csv).map_err(error)
+}
+
+#[allow(unused_mut)]
+fn main() -> Result<(), Error> {
+    let args = parse_args()?;
+    let baseline = args.baseline.as_deref().map(read_baseline).transpose()?;
+
+    let mut records = Vec::new();
+
+    #[cfg(feature = "wgpu")]
+    records.extend(cubecl_bench::run_suite::<cubecl::wgpu::WgpuRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "wgpu-spirv")]
+    records.extend(cubecl_bench::run_suite::<
+        cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::VkSpirvCompiler>,
+    >(&Default::default(), &args.options));
+    #[cfg(feature = "cuda")]
+    records.extend(cubecl_bench::run_suite::<cubecl::cuda::CudaRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "hip")]
+    records.extend(cubecl_bench::run_suite::<cubecl::hip::HipRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "opencl")]
+    records.extend(cubecl_bench::run_suite::<cubecl::opencl::OpenClRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "vulkan")]
+    records.extend(cubecl_bench::run_suite::<cubecl::vulkan::VulkanRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+
+    if records.is_empty() {
+        return Err(Error::NoRuntime);
+    }
+
+    let report = Report::new(records);
+    let output = match args.csv {
+        true => report.to_csv(),
+        false => report.to_markdown(baseline.as_ref()),
+    };
+
+    match args.output {
+        Some(path) => std::fs::write(&path, output).map_err(|err| Error::Output {
+            reason: err.to_string(),
+            path,
+        }),
+        None => {
+            print!("{output}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use core::fmt::Write;
+use core::time::Duration;
+
+use cubecl::benchmark::TimingMethod;
+
+/// The amount of work done by a benchmark, to compute its throughput.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Work {
+    /// Bytes read and written in global memory.
+    Bytes(u64),
+    /// Floating point operations.
+    Flops(u64),
+}
+
+impl Work {
+    /// The throughput of the work done in the duration, e.g. `12.3 GB/s`.
+    pub fn throughput(&self, duration: Duration) -> String {
+        let seconds = duration.as_secs_f64();
+        match self {
+            Work::Bytes(bytes) => format!("{:.1} GB/s", *bytes as f64 / seconds / 1e9),
+            Work::Flops(flops) => format!("{:.1} GFLOP/s", *flops as f64 / seconds / 1e9),
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            Work::Bytes(_) => "bytes",
+            Work::Flops(_) => "flops",
+        }
+    }
+
+    fn amount(&self) -> u64 {
+        match self {
+            Work::Bytes(amount) | Work::Flops(amount) => *amount,
+        }
+    }
+}
+
+/// The result of a benchmark on a runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Name of the benchmark, including its element type.
+    pub benchmark: String,
+    /// Shapes of the inputs, e.g. `1x256x256+1x256x256`.
+    pub shape: String,
+    /// Name of the runtime.
+    pub runtime: String,
+    /// How the durations were measured.
+    pub timing: TimingMethod,
+    /// Number of durations measured.
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// Work done by an execution.
+    pub work: Option<Work>,
+}
+
+/// The results of a suite run, identified by the version and commit they were measured on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub version: String,
+    pub git_hash: String,
+    pub records: Vec<Record>,
+}
+
+const CSV_HEADER: &str = "version,git_hash,benchmark,shape,runtime,timing,samples,mean_ns,\
+                          median_ns,min_ns,max_ns,work,work_unit";
+
+impl Report {
+    /// Create a report of the records measured on the current commit.
+    pub fn new(records: Vec<Record>) -> Self {
+        let git_hash = std::process::Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|hash| hash.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash,
+            records,
+        }
+    }
+
+    /// Write the report as CSV, one line per record.
+    ///
+    /// Each line repeats the version and commit, so that the reports of several releases can be
+    /// concatenated and compared.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+
+        for record in self.records.iter() {
+            let (work, unit) = match record.work {
+                Some(work) => (work.amount().to_string(), work.unit()),
+                None => (String::new(), ""),
+            };
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                self.version,
+                self.git_hash,
+                record.benchmark,
+                record.shape,
+                record.runtime,
+                record.timing,
+                record.samples,
+                record.mean.as_nanos(),
+                record.median.as_nanos(),
+                record.min.as_nanos(),
+                record.max.as_nanos(),
+                work,
+                unit,
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    /// Read a report written by [to_csv](Self::to_csv).
+    pub fn from_csv(csv: &str) -> Result<Self, String> {
+        let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+        match lines.next() {
+            Some(header) if header.trim() == CSV_HEADER => {}
+            _ => return Err("Missing benchmark report header".to_string()),
+        }
+
+        let mut report = Report {
+            version: String::new(),
+            git_hash: String::new(),
+            records: Vec::new(),
+        };
+
+        for (line_number, line) in lines.enumerate() {
+            let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+            let [version, git_hash, benchmark, shape, runtime, timing, samples, mean, median, min, max, work, unit] =
+                fields[..]
+            else {
+                return Err(format!(
+                    "Line {}: expected 13 fields, found {}",
+                    line_number + 2,
+                    fields.len()
+                ));
+            };
+            let error = |field: &str| format!("Line {}: invalid {field}", line_number + 2);
+            let duration = |field: &str, value: &str| {
+                value
+                    .parse()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| error(field))
+            };
+
+            report.version = version.to_string();
+            report.git_hash = git_hash.to_string();
+            report.records.push(Record {
+                benchmark: benchmark.to_string(),
+                shape: shape.to_string(),
+                runtime: runtime.to_string(),
+                timing: match timing {
+                    "full" => TimingMethod::Full,
+                    "device_only" => TimingMethod::DeviceOnly,
+                    _ => return Err(error("timing")),
+                },
+                samples: samples.parse().map_err(|_| error("samples"))?,
+                mean: duration("mean", mean)?,
+                median: duration("median", median)?,
+                min: duration("min", min)?,
+                max: duration("max", max)?,
+                work: match unit {
+                    "" => None,
+                    unit => {
+                        let amount = work.parse().map_err(|_| error("work"))?;
+                        match unit {
+                            "bytes" => Some(Work::Bytes(amount)),
+                            "flops" => Some(Work::Flops(amount)),
+                            _ => return Err(error("work unit")),
+                        }
+                    }
+                },
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Write the report as a markdown table, with a row per benchmark and a column per runtime.
+    ///
+    /// Each cell shows the median duration and the throughput. With a baseline, the change of
+    /// the median from the baseline record of the same benchmark, shape and runtime is added, a
+    /// positive change being slower.
+    pub fn to_markdown(&self, baseline: Option<&Report>) -> String {
+        let mut runtimes: Vec<&str> = Vec::new();
+        let mut rows: Vec<(&str, &str)> = Vec::new();
+        for record in self.records.iter() {
+            if !runtimes.contains(&record.runtime.as_str()) {
+                runtimes.push(&record.runtime);
+            }
+            if !rows.contains(&(&record.benchmark, &record.shape)) {
+                rows.push((&record.benchmark, &record.shape));
+            }
+        }
+
+        let mut markdown = format!(
+            "# CubeCL Benchmarks\n\nVersion {} ({}), median durations",
+            self.version, self.git_hash
+        );
+        if let Some(baseline) = baseline {
+            write!(
+                markdown,
+                " compared to version {} ({})",
+                baseline.version, baseline.git_hash
+            )
+            .unwrap();
+        }
+        markdown.push_str(".\n\n| Benchmark | Shape |");
+        for runtime in runtimes.iter() {
+            write!(markdown, " `{runtime}` |").unwrap();
+        }
+        markdown.push_str("\n|---|---|");
+        markdown.push_str(&"---|".repeat(runtimes.len()));
+        markdown.push('\n');
+
+        for (benchmark, shape) in rows {
+            write!(markdown, "| {benchmark} | {shape} |").unwrap();
+            for runtime in runtimes.iter() {
+                let cell = self
+                    .find(benchmark, shape, runtime)
+                    .map(|record| {
+                        let previous =
+                            baseline.and_then(|baseline| baseline.find(benchmark, shape, runtime));
+                        cell(record, previous)
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                write!(markdown, " {cell} |").unwrap();
+            }
+            markdown.push('\n');
+        }
+
+        markdown
+    }
+
+    fn find(&self, benchmark: &str, shape: &str, runtime: &str) -> Option<&Record> {
+        self.records.iter().find(|record| {
+            record.benchmark == benchmark && record.shape == shape && record.runtime == runtime
+        })
+    }
+}
+
+fn cell(record: &Record, baseline: Option<&Record>) -> String {
+    let mut cell = format!("{:.3?}", record.median);
+    if let Some(work) = record.work {
+        write!(cell, ", {}", work.throughput(record.median)).unwrap();
+    }
+    if let Some(baseline) = baseline {
+        let change = record.median.as_secs_f64() / baseline.median.as_secs_f64() * 100.0 - 100.0;
+        write!(cell, " ({change:+.1}%)").unwrap();
+    }
+    cell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(runtime: &str, median_ms: u64) -> Record {
+        Record {
+            benchmark: "memcpy-f32".to_string(),
+            shape: "1048576".to_string(),
+            runtime: runtime.to_string(),
+            timing: TimingMethod::DeviceOnly,
+            samples: 50,
+            mean: Duration::from_millis(median_ms),
+            median: Duration::from_millis(median_ms),
+            min: Duration::from_millis(median_ms - 1),
+            max: Duration::from_millis(median_ms + 1),
+            work: Some(Work::Bytes(8_000_000)),
+        }
+    }
+
+    fn report(version: &str, records: Vec<Record>) -> Report {
+        Report {
+            version: version.to_string(),
+            git_hash: "abc1234".to_string(),
+            records,
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let mut without_work = record("wgpu<wgsl>", 4);
+        without_work.work = None;
+        without_work.timing = TimingMethod::Full;
+        let report = report("0.2.0", vec![record("cuda", 2), without_work]);
+
+        assert_eq!(Report::from_csv(&report.to_csv()), Ok(report));
+    }
+
+    #[test]
+    fn csv_rejects_missing_fields() {
+        let csv = format!("{CSV_HEADER}\n0.2.0,abc1234,memcpy-f32\n");
+
+        assert_eq!(
+            Report::from_csv(&csv),
+            Err("Line 2: expected 13 fields, found 3".to_string())
+        );
+    }
+
+    #[test]
+    fn markdown_compares_runtimes_and_baseline() {
+        let baseline = report("0.1.0", vec![record("cuda", 4)]);
+        let report = report("0.2.0", vec![record("cuda", 2), record("wgpu<wgsl>", 4)]);
+
+        let markdown = report.to_markdown(Some(&baseline));
+
+        assert!(markdown.contains("compared to version 0.1.0"));
+        assert!(markdown.contains("| Benchmark | Shape | `cuda` | `wgpu<wgsl>` |"));
+        assert!(markdown
+            .contains("| memcpy-f32 | 1048576 | 2.000ms, 4.0 GB/s (-50.0%) | 4.000ms, 2.0 GB/s |"));
+    }
+}
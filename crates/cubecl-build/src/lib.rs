@@ -0,0 +1,234 @@
+//! # CubeCL Build
+//!
+//! Compile kernels ahead of time in build scripts and embed them in the binary, so they are
+//! launched without being expanded and compiled at runtime.
+//!
+//! The build script registers the kernels, usually as [artifacts](KernelArtifact) expanded
+//! earlier or on a build server, and compiles them for the compiler of the target runtime:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     KernelBuild::<WgslCompiler>::new()
+//!         .artifact(KernelArtifact::from_json(include_str!("kernels/add.json")).unwrap())
+//!         .build();
+//! }
+//! ```
+//!
+//! The generated file declares the `EMBEDDED_KERNELS` static, whose kernels are preferred at
+//! launch with [launch_embedded](cubecl_core::prelude::KernelLauncher::launch_embedded):
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/embedded_kernels.rs"));
+//!
+//! launcher.launch_embedded(cube_count, kernel, EMBEDDED_KERNELS.get("add"), &client);
+//! ```
+
+use std::{
+    fmt::Write as _,
+    io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use cubecl_core::{
+    compute::{CubeTask, KernelTask},
+    ir::KernelArtifact,
+    CompilationOptions, Compiler, ExecutionMode, Kernel,
+};
+
+/// The name of the file generated in the output directory.
+pub const EMBEDDED_KERNELS_FILE: &str = "embedded_kernels.rs";
+
+/// The kernels compiled ahead of time for the compiler `C`.
+pub struct KernelBuild<C: Compiler> {
+    kernels: Vec<KernelArtifact>,
+    mode: ExecutionMode,
+    options: CompilationOptions,
+    crate_path: String,
+    _compiler: PhantomData<C>,
+}
+
+impl<C: Compiler> Default for KernelBuild<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Compiler> KernelBuild<C> {
+    /// Create a build without kernels, compiling them in [checked](ExecutionMode::Checked) mode.
+    pub fn new() -> Self {
+        Self {
+            kernels: Vec::new(),
+            mode: ExecutionMode::Checked,
+            options: CompilationOptions::default(),
+            crate_path: "cubecl_core".to_string(),
+            _compiler: PhantomData,
+        }
+    }
+
+    /// Register a kernel, expanding it now.
+    pub fn kernel<K: Kernel>(self, name: impl Into<String>, kernel: &K) -> Self {
+        self.artifact(KernelArtifact::of(name, kernel))
+    }
+
+    /// Register an artifact, embedded under its name.
+    ///
+    /// # Panics
+    ///
+    /// If another kernel has the same name.
+    pub fn artifact(mut self, artifact: KernelArtifact) -> Self {
+        assert!(
+            self.kernels
+                .iter()
+                .all(|kernel| kernel.name != artifact.name),
+            "The kernel {} is registered twice",
+            artifact.name
+        );
+        self.kernels.push(artifact);
+        self
+    }
+
+    /// Set the [execution mode](ExecutionMode) the kernels are compiled with.
+    pub fn mode(mut self, mode: ExecutionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the [options](CompilationOptions) the kernels are compiled with, e.g. to lower the
+    /// subcube operations for devices without subgroups.
+    pub fn options(mut self, options: CompilationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the path of the crate exporting the embedded kernel types in the generated code,
+    /// `cubecl_core` by default. Crates depending on `cubecl` only should set it to `cubecl`.
+    pub fn crate_path(mut self, path: impl Into<String>) -> Self {
+        self.crate_path = path.into();
+        self
+    }
+
+    /// Compile the kernels into the output directory of the build script.
+    ///
+    /// # Panics
+    ///
+    /// If it's not called from a build script, or if the files can't be written.
+    pub fn build(self) {
+        let out_dir = std::env::var("OUT_DIR").expect("Kernels should be built in a build script");
+
+        self.write(Path::new(&out_dir))
+            .expect("Failed to write the embedded kernels");
+    }
+
+    /// Compile the kernels into the given directory, writing one source file per kernel and the
+    /// [generated file](EMBEDDED_KERNELS_FILE) embedding them, whose path is returned.
+    pub fn write(self, dir: &Path) -> io::Result<PathBuf> {
+        let dir = dir.join("kernels");
+        std::fs::create_dir_all(&dir)?;
+
+        let crate_path = &self.crate_path;
+        let compiler = core::any::type_name::<C>();
+        let mut entries = String::new();
+
+        for (index, artifact) in self.kernels.into_iter().enumerate() {
+            let name = artifact.name.clone();
+            let compiled =
+                KernelTask::<C, KernelArtifact>::new(artifact).compile(&self.options, self.mode);
+
+            // The index keeps the file names unique when the sanitized names collide.
+            let path = dir.join(format!("{index}_{}.kernel", sanitize(&name)));
+            std::fs::write(&path, &compiled.source)?;
+
+            let cube_dim = compiled.cube_dim;
+            writeln!(
+                entries,
+                "    {crate_path}::compute::EmbeddedKernel {{
+        name: {name:?},
+        compiler: {compiler:?},
+        source: include_str!({path:?}),
+        cube_dim: {crate_path}::ir::CubeDim {{ x: {}, y: {}, z: {} }},
+        shared_mem_bytes: {},
+    }},",
+                cube_dim.x, cube_dim.y, cube_dim.z, compiled.shared_mem_bytes,
+            )
+            .unwrap();
+        }
+
+        let generated = format!(
+            "/// The kernels compiled ahead of time by the build script.
+pub static EMBEDDED_KERNELS: {crate_path}::compute::EmbeddedKernels =
+    {crate_path}::compute::EmbeddedKernels::new(&[
+{entries}]);
+"
+        );
+        let path = dir
+            .parent()
+            .expect("The kernels are in a subdirectory")
+            .join(EMBEDDED_KERNELS_FILE);
+        std::fs::write(&path, generated)?;
+
+        Ok(path)
+    }
+}
+
+/// The name of the source file of a kernel, which can't contain path separators.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        ir::{Elem, FloatKind, Item, Operator, UnaryOperator},
+        prelude::KernelBuilder,
+        KernelSettings,
+    };
+    use cubecl_cpp::CudaCompiler;
+
+    fn artifact(name: &str) -> KernelArtifact {
+        let mut builder = KernelBuilder::default();
+        let input = builder.input_array(Item::new(Elem::Float(FloatKind::F32)));
+        let output = builder.output_array(Item::new(Elem::Float(FloatKind::F32)));
+        builder.context.register(Operator::Assign(UnaryOperator {
+            input: *input,
+            out: *output,
+        }));
+
+        KernelArtifact::new(name, builder.build(KernelSettings::default()))
+    }
+
+    #[test]
+    fn kernels_are_written_and_embedded() {
+        let dir = std::env::temp_dir().join(format!("cubecl-build-{}", std::process::id()));
+
+        let path = KernelBuild::<CudaCompiler>::new()
+            .artifact(artifact("copy"))
+            .artifact(artifact("copy/f32"))
+            .write(&dir)
+            .unwrap();
+
+        let generated = std::fs::read_to_string(path).unwrap();
+        assert!(generated.contains(r#"name: "copy","#));
+        assert!(generated.contains(r#"name: "copy/f32","#));
+        assert!(dir.join("kernels/1_copy_f32.kernel").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn kernel_names_are_unique() {
+        let _ = KernelBuild::<CudaCompiler>::new()
+            .artifact(artifact("copy"))
+            .artifact(artifact("copy"));
+    }
+}
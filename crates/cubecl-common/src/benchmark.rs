@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use super::stub::Duration;
 
 /// How a benchmark's execution times are measured.
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimingMethod {
     /// Time measurements come from full timing of execution + sync
     /// calls.
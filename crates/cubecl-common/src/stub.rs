@@ -158,3 +158,8 @@ impl<T: core::fmt::Debug> SyncOnceCell<T> {
 pub use core::time::Duration;
 #[cfg(target_family = "wasm")]
 pub use web_time::Duration;
+
+#[cfg(all(not(target_family = "wasm"), feature = "std"))]
+pub use std::time::Instant;
+#[cfg(target_family = "wasm")]
+pub use web_time::Instant;
@@ -0,0 +1,49 @@
+//! The interface between CubeCL and its compiler backends, for the crates adding a backend
+//! outside of this repository, e.g. DirectX or SYCL.
+//!
+//! A backend implements three traits:
+//!
+//! - [Compiler] translates the [kernel definitions](KernelDefinition), once the
+//!   [passes](crate::ir::PassManager) are applied, into a [representation](CompilerRepresentation)
+//!   rendered as the source of the target language.
+//! - [ComputeServer] owns the device: it allocates the buffers through a [MemoryManagement] over
+//!   its [storage](ComputeStorage), and compiles then dispatches the [CubeTask] of each launch.
+//! - [Runtime] ties the compiler and the server together, and gives the [ComputeClient] of each
+//!   device, usually cached in a static [ComputeRuntime].
+//!
+//! The `backend_template` example is a complete backend storing its buffers in host memory, to
+//! be copied as a starting point.
+//!
+//! # Stability
+//!
+//! The items of this module follow semantic versioning, unlike the rest of `cubecl-core` and
+//! `cubecl-runtime` whose internals change with every release:
+//!
+//! - New methods of the traits always have a default implementation outside of major releases,
+//!   keeping the behavior of the backends that don't override them.
+//! - New [features](Feature) and [IR](crate::ir) operations may be added at any time; a backend
+//!   only receives them once it registers the features they depend on in its
+//!   [device properties](DeviceProperties).
+//! - Items are only removed or changed in major releases.
+//!
+//! Backends depending only on this module and on the IR they compile keep building across the
+//! minor releases of CubeCL.
+
+pub use crate::codegen::{CompilationOptions, Compiler, CompilerRepresentation};
+pub use crate::compute::{CompiledKernel, CubeTask};
+pub use crate::ir::{Elem, HybridAllocator, KernelDefinition, LocalAllocator};
+pub use crate::{ExecutionMode, Feature, KernelId, Runtime};
+
+pub use cubecl_runtime::channel::{ComputeChannel, MutexComputeChannel};
+pub use cubecl_runtime::client::ComputeClient;
+pub use cubecl_runtime::memory_management::{
+    MemoryConfiguration, MemoryDeviceProperties, MemoryManagement, MemoryUsage, OutOfMemoryError,
+    OutOfMemoryHook,
+};
+pub use cubecl_runtime::server::{Binding, ComputeServer, CubeCount, Handle, Priority, Stream};
+pub use cubecl_runtime::storage::{
+    BindingResource, BytesStorage, ComputeStorage, StorageHandle, StorageId, StorageUtilization,
+};
+pub use cubecl_runtime::{
+    ComputeRuntime, DeviceProperties, HardwareProperties, TimestampsError, TimestampsResult,
+};
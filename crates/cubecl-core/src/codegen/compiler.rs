@@ -14,10 +14,53 @@ pub trait Compiler: Sync + Send + 'static + Clone + Default + core::fmt::Debug {
     type Representation: CompilerRepresentation;
 
     /// Compiles the [kernel definition](KernelDefinition) into the compiler's representation.
-    fn compile(kernel: KernelDefinition, mode: ExecutionMode) -> Self::Representation;
+    fn compile(
+        kernel: KernelDefinition,
+        options: &CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Self::Representation;
     /// The size of the given element in bytes.
     fn elem_size(elem: Elem) -> usize;
     fn local_allocator() -> impl LocalAllocator;
-    /// The maximal size of a shared memory, in bytes
-    fn max_shared_memory_size() -> usize;
+    /// The maximal size of a shared memory, in bytes, common to the devices of the compiler.
+    ///
+    /// It's only a lower bound of the limit of the device, which is reported by the
+    /// [hardware properties](cubecl_runtime::HardwareProperties::max_shared_memory_size) of its
+    /// client instead.
+    #[deprecated(
+        note = "use the `max_shared_memory_size` of the hardware properties of the client, which reports the limit of the device"
+    )]
+    fn max_shared_memory_size() -> usize {
+        32768
+    }
+    /// Whether the scalar arguments of a kernel are packed into a single uniform buffer following
+    /// the [uniform layout](crate::UniformLayout), instead of one storage buffer per element type.
+    fn packed_scalars() -> bool {
+        false
+    }
+    /// Whether the compiler can emit [device functions](crate::ir::FunctionDefinition), which
+    /// are otherwise inlined into the kernel body.
+    fn device_functions() -> bool {
+        false
+    }
+    /// Whether the compiler supports [matrix operations](crate::ir::Operator::MatrixMul) on
+    /// `size`x`size` matrices natively. They are otherwise unrolled into scalar operations.
+    fn matrix_ops(_size: u32) -> bool {
+        false
+    }
+}
+
+/// The capabilities of the device a kernel is compiled for, unknown when the kernel is defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompilationOptions {
+    /// Whether the device supports [subcube operations](crate::ir::Subcube). Otherwise they are
+    /// [lowered](crate::ir::LowerSubcube) to shared memory, the whole cube acting as a
+    /// single subcube.
+    pub subcube: bool,
+}
+
+impl Default for CompilationOptions {
+    fn default() -> Self {
+        Self { subcube: true }
+    }
 }
@@ -3,8 +3,8 @@ use std::num::NonZero;
 use super::Compiler;
 use crate::{
     ir::{
-        Binding, CubeDim, Elem, Item, KernelDefinition, Location, ReadingStrategy, Scope, Variable,
-        Vectorization, Visibility,
+        Binding, CubeDim, DivisionPolicy, Elem, FastMath, FunctionDefinition, Item,
+        KernelDefinition, Location, ReadingStrategy, Scope, Variable, Vectorization, Visibility,
     },
     Runtime,
 };
@@ -25,6 +25,7 @@ pub struct KernelExpansion {
     pub inputs: Vec<InputInfo>,
     pub outputs: Vec<OutputInfo>,
     pub scope: Scope,
+    pub functions: Vec<FunctionDefinition>,
 }
 
 /// Simply indicate the output that can be replaced by the input.
@@ -54,6 +55,22 @@ pub struct KernelSettings {
     vectorization_partial: Vec<VectorizationPartial>,
     pub cube_dim: CubeDim,
     pub reading_strategy: Vec<(u16, ReadingStrategy)>,
+    /// The number of bytes of dynamic shared memory, chosen at launch.
+    ///
+    /// It isn't part of the compilation key, since backends with native support for dynamic
+    /// shared memory don't need to recompile the kernel, while the others add it to the
+    /// [kernel id](crate::KernelId) themselves.
+    pub dynamic_shared_memory: u32,
+    /// Whether the shared memory is zero-initialized before the kernel runs, instead of holding
+    /// whatever the previous cube left there. It's always the case in
+    /// [checked](crate::ExecutionMode::Checked) mode.
+    ///
+    /// Only the wgpu runtime supports it for now.
+    pub zero_initialize_shared_memory: bool,
+    /// The floating point semantics of the kernel.
+    pub fast_math: FastMath,
+    /// The result of divisions by zero in checked mode.
+    pub division: DivisionPolicy,
 }
 
 impl core::fmt::Display for KernelSettings {
@@ -78,6 +95,9 @@ impl core::fmt::Display for KernelSettings {
         // * Cube Dim X: x
         // * Cube Dim Y: y
         // * Cube Dim Z: z
+        // * Zero-initialized shared memory: s
+        // * Relaxed fast math: f
+        // * Division by zero returning zero: d
         f.write_str("m")?;
         for mapping in self.mappings.iter() {
             f.write_fmt(format_args!(
@@ -110,7 +130,21 @@ impl core::fmt::Display for KernelSettings {
         f.write_fmt(format_args!(
             "x{}y{}z{}",
             self.cube_dim.x, self.cube_dim.y, self.cube_dim.x
-        ))
+        ))?;
+
+        if self.zero_initialize_shared_memory {
+            f.write_str("s")?;
+        }
+
+        if let FastMath::Relaxed = self.fast_math {
+            f.write_str("f")?;
+        }
+
+        if let DivisionPolicy::Zero = self.division {
+            f.write_str("d")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -192,6 +226,30 @@ impl KernelSettings {
         self.cube_dim = cube_dim;
         self
     }
+
+    /// Set the number of bytes of dynamic shared memory.
+    pub fn dynamic_shared_memory(mut self, bytes: u32) -> Self {
+        self.dynamic_shared_memory = bytes;
+        self
+    }
+
+    /// Zero-initialize the shared memory before the kernel runs.
+    pub fn zero_initialize_shared_memory(mut self, zero_initialize: bool) -> Self {
+        self.zero_initialize_shared_memory = zero_initialize;
+        self
+    }
+
+    /// Set the floating point semantics of the kernel.
+    pub fn fast_math(mut self, fast_math: FastMath) -> Self {
+        self.fast_math = fast_math;
+        self
+    }
+
+    /// Set the result of divisions by zero in checked mode.
+    pub fn division(mut self, division: DivisionPolicy) -> Self {
+        self.division = division;
+        self
+    }
 }
 
 #[allow(dead_code)]
@@ -211,8 +269,15 @@ fn is_contiguous(strides: &[usize]) -> bool {
 /// Information related to an input.
 #[derive(Clone, Debug)]
 pub enum InputInfo {
-    Array { item: Item, visibility: Visibility },
-    Scalar { elem: Elem, size: usize },
+    Array {
+        item: Item,
+        visibility: Visibility,
+        rank: Option<usize>,
+    },
+    Scalar {
+        elem: Elem,
+        size: usize,
+    },
 }
 
 impl InputInfo {
@@ -220,10 +285,7 @@ impl InputInfo {
     #[allow(dead_code)]
     pub fn item(&self) -> Item {
         match self {
-            InputInfo::Array {
-                item,
-                visibility: _,
-            } => *item,
+            InputInfo::Array { item, .. } => *item,
             InputInfo::Scalar { elem, size: _ } => Item::new(*elem),
         }
     }
@@ -245,7 +307,7 @@ impl OutputInfo {
                 local: _,
                 position: _,
             } => *item,
-            OutputInfo::Array { item } => *item,
+            OutputInfo::Array { item, .. } => *item,
         }
     }
 }
@@ -271,7 +333,7 @@ pub enum OutputInfo {
     /// Simply register the output, but don't automatically add a write to it.
     ///
     /// Useful when a procedure writes to the output using operations.
-    Array { item: Item },
+    Array { item: Item, rank: Option<usize> },
 }
 
 impl OutputInfo {
@@ -289,7 +351,7 @@ impl OutputInfo {
                 local: _,
                 position: _,
             } => bool_elem(item.elem()),
-            OutputInfo::Array { item } => bool_elem(item.elem()),
+            OutputInfo::Array { item, .. } => bool_elem(item.elem()),
         };
         <R::Compiler as Compiler>::elem_size(elem)
     }
@@ -321,6 +383,7 @@ impl KernelIntegrator {
                 item: Item::new(Elem::UInt),
                 visibility: Visibility::Read,
                 location: Location::Storage,
+                rank: None,
                 size: None, // We avoid putting the length here since it will force a new kernel
                             // for each tensor rank.
             },
@@ -335,7 +398,12 @@ impl KernelIntegrator {
             outputs,
             named,
             cube_dim: settings.cube_dim,
+            dynamic_shared_memory: settings.dynamic_shared_memory,
+            zero_initialize_shared_memory: settings.zero_initialize_shared_memory,
+            fast_math: settings.fast_math,
+            division: settings.division,
             body: self.expansion.scope,
+            functions: self.expansion.functions,
         }
     }
 
@@ -346,12 +414,17 @@ impl KernelIntegrator {
 
         for input in self.expansion.inputs.drain(..) {
             match input {
-                InputInfo::Array { item, visibility } => {
+                InputInfo::Array {
+                    item,
+                    visibility,
+                    rank,
+                } => {
                     self.input_bindings.push(Binding {
                         item: bool_item(item),
                         visibility,
                         location: Location::Storage,
                         size: None,
+                        rank,
                     });
                 }
                 InputInfo::Scalar { elem, size } => {
@@ -364,6 +437,7 @@ impl KernelIntegrator {
                             visibility: Visibility::Read,
                             location: Location::Storage,
                             size: Some(size),
+                            rank: None,
                         },
                     ));
                 }
@@ -397,6 +471,7 @@ impl KernelIntegrator {
                         visibility: Visibility::ReadWrite,
                         location: Location::Storage,
                         size: None,
+                        rank: None,
                     });
                     self.expansion.scope.write_global(
                         Variable::Local {
@@ -431,7 +506,7 @@ impl KernelIntegrator {
                         position,
                     );
                 }
-                OutputInfo::Array { item } => {
+                OutputInfo::Array { item, rank } => {
                     let elem_adapted = bool_item(item);
 
                     self.output_bindings.push(Binding {
@@ -439,6 +514,7 @@ impl KernelIntegrator {
                         visibility: Visibility::ReadWrite,
                         location: Location::Storage,
                         size: None,
+                        rank,
                     });
 
                     index += 1;
@@ -475,7 +551,7 @@ impl KernelIntegrator {
                 );
                 return;
             }
-            OutputInfo::Array { item: _ } => panic!("Can't register an inplace operation for an array that isn't using a defined writing strategy."),
+            OutputInfo::Array { .. } => panic!("Can't register an inplace operation for an array that isn't using a defined writing strategy."),
         };
 
         let item = match self.input_bindings.get_mut(mapping.pos_input) {
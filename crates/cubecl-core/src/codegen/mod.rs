@@ -2,7 +2,9 @@ mod execution;
 mod integrator;
 
 mod compiler;
+mod uniform;
 
 pub use compiler::*;
 pub use execution::*;
 pub use integrator::*;
+pub use uniform::*;
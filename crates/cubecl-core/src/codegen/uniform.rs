@@ -0,0 +1,101 @@
+use crate::ir::Elem;
+
+/// The alignment of a struct in the uniform address space, in bytes.
+pub const UNIFORM_STRUCT_ALIGNMENT: usize = 16;
+
+/// Layout of the scalar arguments of a kernel packed into a single uniform buffer.
+///
+/// Scalars are grouped per element type, in the same order they are registered, and each scalar
+/// is aligned on its own size. The total size is rounded up to [UNIFORM_STRUCT_ALIGNMENT] so that
+/// the buffer can be bound as a uniform struct.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UniformLayout {
+    fields: Vec<UniformField>,
+    size: usize,
+}
+
+/// A single scalar of a [uniform layout](UniformLayout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformField {
+    /// The element type of the scalar.
+    pub elem: Elem,
+    /// The position of the scalar among the scalars of the same element type.
+    pub index: usize,
+    /// The offset of the scalar in the buffer, in bytes.
+    pub offset: usize,
+}
+
+impl UniformLayout {
+    /// Compute the layout of the given scalar groups, each being an element type with the number
+    /// of scalars of that type.
+    pub fn new(groups: impl IntoIterator<Item = (Elem, usize)>) -> Self {
+        let mut fields = Vec::new();
+        let mut offset: usize = 0;
+
+        for (elem, count) in groups {
+            let size = elem.size();
+
+            for index in 0..count {
+                offset = offset.next_multiple_of(size);
+                fields.push(UniformField {
+                    elem,
+                    index,
+                    offset,
+                });
+                offset += size;
+            }
+        }
+
+        Self {
+            fields,
+            size: offset.next_multiple_of(UNIFORM_STRUCT_ALIGNMENT),
+        }
+    }
+
+    /// The scalars of the layout, in order.
+    pub fn fields(&self) -> &[UniformField] {
+        &self.fields
+    }
+
+    /// The size of the buffer in bytes, including the trailing padding.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the layout doesn't contain any scalar.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{FloatKind, IntKind};
+
+    #[test]
+    pub fn uniform_layout_aligns_each_group() {
+        let layout = UniformLayout::new([
+            (Elem::Float(FloatKind::F16), 3),
+            (Elem::UInt, 2),
+            (Elem::Int(IntKind::I64), 1),
+        ]);
+
+        let offsets = layout
+            .fields()
+            .iter()
+            .map(|field| field.offset)
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets, vec![0, 2, 4, 8, 12, 16]);
+        assert_eq!(layout.size(), 32);
+    }
+
+    #[test]
+    pub fn uniform_layout_empty() {
+        let layout = UniformLayout::new([]);
+
+        assert!(layout.is_empty());
+        assert_eq!(layout.size(), 0);
+    }
+}
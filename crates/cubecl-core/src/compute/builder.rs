@@ -41,7 +41,7 @@ impl KernelBuilder {
 
     /// Register an output array and return the [element](ExpandElement) to be used for kernel expansion.
     pub fn output_tensor(&mut self, item: Item) -> ExpandElement {
-        self.outputs.push(OutputInfo::Array { item });
+        self.outputs.push(OutputInfo::Array { item, rank: None });
         let variable = self.context.output(self.num_output, item);
         self.num_output += 1;
 
@@ -53,6 +53,7 @@ impl KernelBuilder {
         self.inputs.push(InputInfo::Array {
             item,
             visibility: Visibility::Read,
+            rank: None,
         });
         let variable = self.context.input(self.num_input, item);
         self.num_input += 1;
@@ -61,7 +62,10 @@ impl KernelBuilder {
 
     /// Register an output array and return the [element](ExpandElement) to be used for kernel expansion.
     pub fn output_array(&mut self, item: Item) -> ExpandElement {
-        self.outputs.push(OutputInfo::Array { item });
+        self.outputs.push(OutputInfo::Array {
+            item,
+            rank: Some(1),
+        });
         let variable = self.context.output(self.num_output, item);
         self.num_output += 1;
 
@@ -75,7 +79,10 @@ impl KernelBuilder {
             .get_mut(position as usize)
             .expect("Position valid");
 
-        if let InputInfo::Array { visibility, item } = input {
+        if let InputInfo::Array {
+            visibility, item, ..
+        } = input
+        {
             *visibility = Visibility::ReadWrite;
             let variable = self.context.input(position, *item);
             return variable;
@@ -89,6 +96,7 @@ impl KernelBuilder {
         self.inputs.push(InputInfo::Array {
             item,
             visibility: Visibility::Read,
+            rank: Some(1),
         });
         let variable = self.context.input(self.num_input, item);
         self.num_input += 1;
@@ -97,7 +105,10 @@ impl KernelBuilder {
 
     /// Build the [kernel definition](KernelDefinition).
     pub fn build(self, settings: KernelSettings) -> KernelDefinition {
+        let functions = self.context.functions.take();
+
         KernelIntegrator::new(KernelExpansion {
+            functions,
             scope: self.context.into_scope(),
             inputs: self.inputs,
             outputs: self.outputs,
@@ -106,8 +117,11 @@ impl KernelBuilder {
     }
 
     pub fn with_local_allocator(allocator: impl LocalAllocator + 'static) -> Self {
+        let mut context = CubeContext::root(allocator);
+        context.debug_info = debug_info_enabled();
+
         Self {
-            context: CubeContext::root(allocator),
+            context,
             inputs: Vec::new(),
             outputs: Vec::new(),
             indices: HashMap::new(),
@@ -122,3 +136,9 @@ impl Default for KernelBuilder {
         Self::with_local_allocator(ReusingAllocator::default())
     }
 }
+
+/// Whether source locations should be registered, set with the `CUBECL_DEBUG_INFO` environment
+/// variable.
+fn debug_info_enabled() -> bool {
+    std::env::var("CUBECL_DEBUG_INFO").is_ok_and(|value| value != "0")
+}
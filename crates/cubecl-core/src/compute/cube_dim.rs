@@ -0,0 +1,280 @@
+use core::fmt::Debug;
+
+use alloc::sync::Arc;
+use cubecl_runtime::{
+    client::ComputeClient,
+    tune::{AutotuneOperation, AutotuneOperationSet, LocalTuner, SearchSpace, TunePoint},
+    HardwareProperties,
+};
+
+use crate::{compute::KernelStatistics, ir::CubeDim, tune_device_id, Runtime};
+
+/// The number of units per cube used when the device doesn't constrain the choice, which
+/// performs well on most hardware.
+const PREFERRED_UNITS_PER_CUBE: u32 = 256;
+
+/// The subcube size assumed when the device doesn't report one.
+const DEFAULT_SUBCUBE_SIZE: u32 = 32;
+
+/// The cube dims benchmarked by [autotuned](CubeDim::autotuned), cached per device and key.
+static TUNER: LocalTuner<String, String> = LocalTuner::new("cubecl-core-cube-dim");
+
+impl CubeDim {
+    /// A one-dimensional cube dim suited to the device, maximizing the theoretical occupancy of a
+    /// kernel whose resource usage is unknown.
+    pub fn suggested(properties: &HardwareProperties) -> Self {
+        Self::suggested_for(None, properties)
+    }
+
+    /// A one-dimensional cube dim suited to the device, maximizing the theoretical occupancy of a
+    /// kernel with the given [statistics](KernelStatistics), when the backend reports them.
+    ///
+    /// Among the cube dims with the same occupancy, the closest to 256 units is selected.
+    pub fn suggested_for(
+        statistics: Option<&KernelStatistics>,
+        properties: &HardwareProperties,
+    ) -> Self {
+        let registers = statistics.map(|statistics| statistics.registers);
+        let space = search_space(properties, registers);
+        let points = space.points();
+
+        let units = space
+            .benchmark_order(&points)
+            .first()
+            .map(|index| points[*index].get("units"))
+            .unwrap_or(Ord::min(
+                PREFERRED_UNITS_PER_CUBE,
+                properties.max_units_per_cube,
+            ));
+
+        CubeDim::new(units, 1, 1)
+    }
+
+    /// Benchmark the launch with the candidate one-dimensional cube dims, most promising first,
+    /// and launch it with the fastest one, which is returned.
+    ///
+    /// The fastest cube dim is cached for the device and the given key, which should identify
+    /// the kernel and the problem size. Following calls with the same key only launch once.
+    pub fn autotuned<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        key: String,
+        launch: impl Fn(CubeDim) + Send + Sync + 'static,
+    ) -> Self {
+        let space = search_space(client.properties().hardware_properties(), None);
+        let set = CubeDimOperationSet {
+            key,
+            points: space.points(),
+            space,
+            launch: Arc::new(launch),
+        };
+
+        TUNER.execute(&tune_device_id::<R>(client), client, Box::new(set))
+    }
+}
+
+/// The candidate one-dimensional cube dims, ranked by their theoretical occupancy.
+fn search_space(properties: &HardwareProperties, registers: Option<u32>) -> SearchSpace {
+    let subcube_size = properties.subcube_size.unwrap_or(DEFAULT_SUBCUBE_SIZE);
+    let max_units = properties.max_units_per_cube;
+    let properties = properties.clone();
+
+    SearchSpace::new()
+        .param("units", [32, 64, 128, 256, 512, 1024])
+        .constraint(move |point| {
+            let units = point.get("units");
+            units <= max_units && units % subcube_size == 0
+        })
+        .prior(move |point| {
+            let units = point.get("units");
+            let distance = units.abs_diff(PREFERRED_UNITS_PER_CUBE) as f32;
+
+            // The distance to the preferred size only breaks the ties between occupancies.
+            occupancy(&properties, units, registers) - distance / 1_000_000.0
+        })
+        .early_stopping(2)
+}
+
+/// The ratio of units resident on a multiprocessor to the maximum it supports, when launching
+/// cubes of `units` units each using `registers` registers.
+///
+/// Devices that don't report their multiprocessor limits are only limited by the cube size.
+fn occupancy(properties: &HardwareProperties, units: u32, registers: Option<u32>) -> f32 {
+    match (
+        properties.max_units_per_multiprocessor,
+        resident_cubes(properties, units, registers),
+    ) {
+        (Some(max_units), Some(cubes)) => (cubes * units) as f32 / max_units as f32,
+        _ => 1.0,
+    }
+}
+
+/// The number of cubes of `units` units each using `registers` registers resident on a
+/// multiprocessor at once, when the device reports its multiprocessor limits.
+pub(crate) fn resident_cubes(
+    properties: &HardwareProperties,
+    units: u32,
+    registers: Option<u32>,
+) -> Option<u32> {
+    let max_units = properties.max_units_per_multiprocessor?;
+    let subcube_size = properties.subcube_size.unwrap_or(DEFAULT_SUBCUBE_SIZE);
+    // Registers and units are allocated per subcube.
+    let allocated_units = units.div_ceil(subcube_size) * subcube_size;
+
+    let mut cubes = max_units / allocated_units;
+    if let Some(max_cubes) = properties.max_cubes_per_multiprocessor {
+        cubes = Ord::min(cubes, max_cubes);
+    }
+    if let (Some(available), Some(registers)) = (properties.registers_per_multiprocessor, registers)
+    {
+        cubes = Ord::min(cubes, available / Ord::max(registers * allocated_units, 1));
+    }
+
+    Some(cubes)
+}
+
+struct CubeDimOperationSet {
+    key: String,
+    space: SearchSpace,
+    points: Vec<TunePoint>,
+    launch: Arc<dyn Fn(CubeDim) + Send + Sync>,
+}
+
+impl CubeDimOperationSet {
+    fn operation(&self, index: usize) -> Box<dyn AutotuneOperation<CubeDim>> {
+        Box::new(CubeDimOperation {
+            cube_dim: CubeDim::new(self.points[index].get("units"), 1, 1),
+            launch: self.launch.clone(),
+        })
+    }
+}
+
+impl AutotuneOperationSet<String, CubeDim> for CubeDimOperationSet {
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation<CubeDim>>> {
+        (0..self.points.len())
+            .map(|index| self.operation(index))
+            .collect()
+    }
+
+    fn fastest(self: Box<Self>, fastest_index: usize) -> Box<dyn AutotuneOperation<CubeDim>> {
+        self.operation(fastest_index)
+    }
+
+    fn benchmark_order(&self, _key: &String, _num_candidates: usize) -> Vec<usize> {
+        self.space.benchmark_order(&self.points)
+    }
+
+    fn early_stopping(&self) -> Option<usize> {
+        self.space.patience()
+    }
+}
+
+struct CubeDimOperation {
+    cube_dim: CubeDim,
+    launch: Arc<dyn Fn(CubeDim) + Send + Sync>,
+}
+
+impl Debug for CubeDimOperation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CubeDimOperation")
+            .field("cube_dim", &self.cube_dim)
+            .finish()
+    }
+}
+
+impl AutotuneOperation<CubeDim> for CubeDimOperation {
+    fn execute(self: Box<Self>) -> CubeDim {
+        (self.launch)(self.cube_dim);
+        self.cube_dim
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation<CubeDim>> {
+        Box::new(Self {
+            cube_dim: self.cube_dim,
+            launch: self.launch.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubeCount;
+
+    fn properties() -> HardwareProperties {
+        HardwareProperties {
+            max_shared_memory_size: 49152,
+            max_cube_dim: (1024, 1024, 64),
+            max_units_per_cube: 1024,
+            max_cube_count: (u32::MAX, u16::MAX as u32, u16::MAX as u32),
+            max_bindings: 512,
+            max_buffer_size: u32::MAX as u64,
+            subcube_size: Some(32),
+            num_multiprocessors: Some(80),
+            max_units_per_multiprocessor: Some(1536),
+            max_cubes_per_multiprocessor: Some(16),
+            registers_per_multiprocessor: Some(65536),
+        }
+    }
+
+    #[test]
+    fn suggested_cube_dim_maximizes_occupancy() {
+        // 1024 units leave a third of the multiprocessor idle, and 64 hit the cube limit.
+        assert_eq!(CubeDim::suggested(&properties()), CubeDim::new(256, 1, 1));
+    }
+
+    #[test]
+    fn suggested_cube_dim_accounts_for_registers() {
+        let statistics = KernelStatistics::new(96, 0, 0.0);
+
+        // The registers fit 682 units, of which cubes of 64 or 128 units use the most.
+        assert_eq!(
+            CubeDim::suggested_for(Some(&statistics), &properties()),
+            CubeDim::new(128, 1, 1)
+        );
+    }
+
+    #[test]
+    fn suggested_cube_dim_respects_the_device_limits() {
+        let properties = HardwareProperties {
+            max_units_per_cube: 128,
+            max_units_per_multiprocessor: None,
+            ..properties()
+        };
+
+        assert_eq!(CubeDim::suggested(&properties), CubeDim::new(128, 1, 1));
+    }
+
+    fn persistent_cube_count(num_elems: usize, properties: &HardwareProperties) -> (u32, u32, u32) {
+        match crate::calculate_cube_count_persistent(num_elems, CubeDim::new(256, 1, 1), properties)
+        {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            CubeCount::Dynamic(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn persistent_cube_count_fills_the_device() {
+        // Each of the 80 multiprocessors keeps 6 cubes of 256 units resident.
+        assert_eq!(persistent_cube_count(1 << 24, &properties()), (480, 1, 1));
+        // Small problems don't launch idle cubes.
+        assert_eq!(persistent_cube_count(1000, &properties()), (4, 1, 1));
+        assert_eq!(persistent_cube_count(0, &properties()), (1, 1, 1));
+    }
+
+    #[test]
+    fn persistent_cube_count_without_multiprocessors() {
+        let properties = HardwareProperties {
+            num_multiprocessors: None,
+            max_cube_count: (1024, 1024, 1024),
+            ..properties()
+        };
+
+        // Every element gets a unit, up to the dispatch limit.
+        assert_eq!(persistent_cube_count(1 << 16, &properties), (256, 1, 1));
+        assert_eq!(persistent_cube_count(1 << 24, &properties), (1024, 1, 1));
+    }
+}
@@ -0,0 +1,107 @@
+/// A table of kernel instantiations selected at runtime by name.
+///
+/// Kernels generic over a user trait are instantiated once per implementor, each instantiation
+/// being a distinct kernel with its own [kernel id](crate::KernelId). The table maps names to the
+/// launch functions of those instantiations, so that library crates can expose extension points
+/// (such as elementwise operations or epilogues) that users fill with their own implementors.
+///
+/// ```rust, ignore
+/// type BinaryLaunch<R> = for<'a> fn(
+///     &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+///     CubeCount,
+///     CubeDim,
+///     ArrayArg<'a, R>,
+///     ArrayArg<'a, R>,
+///     ArrayArg<'a, R>,
+/// );
+///
+/// let table = DispatchTable::<BinaryLaunch<R>>::new()
+///     .register("add", kernel_binary_op::launch::<AddOp, R>)
+///     .register("mul", kernel_binary_op::launch::<MulOp, R>);
+///
+/// let launch = table.get("add").unwrap();
+/// launch(&client, cube_count, cube_dim, lhs, rhs, output);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DispatchTable<F> {
+    entries: Vec<(String, F)>,
+}
+
+impl<F> Default for DispatchTable<F> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<F: Copy> DispatchTable<F> {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the instantiation under the given name, replacing any previous one.
+    pub fn register(mut self, name: impl Into<String>, instantiation: F) -> Self {
+        self.insert(name, instantiation);
+        self
+    }
+
+    /// Insert the instantiation under the given name, replacing any previous one.
+    pub fn insert(&mut self, name: impl Into<String>, instantiation: F) {
+        let name = name.into();
+
+        match self.entries.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, entry)) => *entry = instantiation,
+            None => self.entries.push((name, instantiation)),
+        }
+    }
+
+    /// Get the instantiation registered under the given name.
+    pub fn get(&self, name: &str) -> Option<F> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, instantiation)| *instantiation)
+    }
+
+    /// The names of all registered instantiations, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn double(value: u32) -> u32 {
+        value * 2
+    }
+
+    fn square(value: u32) -> u32 {
+        value * value
+    }
+
+    #[test]
+    fn dispatch_table_selects_by_name() {
+        let table = DispatchTable::<fn(u32) -> u32>::new()
+            .register("double", double)
+            .register("square", square);
+
+        assert_eq!(table.get("double").map(|f| f(3)), Some(6));
+        assert_eq!(table.get("square").map(|f| f(3)), Some(9));
+        assert!(table.get("cube").is_none());
+    }
+
+    #[test]
+    fn dispatch_table_replaces_existing_names() {
+        let table = DispatchTable::<fn(u32) -> u32>::new()
+            .register("op", double)
+            .register("other", double)
+            .register("op", square);
+
+        assert_eq!(table.get("op").map(|f| f(3)), Some(9));
+        assert_eq!(table.names().collect::<Vec<_>>(), vec!["op", "other"]);
+    }
+}
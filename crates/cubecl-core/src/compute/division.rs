@@ -0,0 +1,29 @@
+use crate::{ir::KernelDefinition, Kernel, KernelId};
+
+/// A kernel [counting its divisions by zero](KernelDefinition::count_division_by_zero) in an
+/// extra atomic output.
+#[derive(new)]
+pub(crate) struct DivisionCountingKernel<K: Kernel> {
+    kernel: K,
+}
+
+impl<K: Kernel> Kernel for DivisionCountingKernel<K> {
+    fn define(&self) -> KernelDefinition {
+        let mut definition = self.kernel.define();
+        definition.count_division_by_zero();
+
+        definition
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>().info(self.kernel.id())
+    }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.kernel.dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.kernel.zero_initialize_shared_memory()
+    }
+}
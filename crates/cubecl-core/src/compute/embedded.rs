@@ -0,0 +1,78 @@
+use cubecl_runtime::ExecutionMode;
+
+use crate::{
+    compute::CompiledKernel, compute::CubeTask, ir::CubeDim, CompilationOptions, Compiler, KernelId,
+};
+
+/// A kernel compiled ahead of time, usually by a build script, whose source is embedded in the
+/// binary.
+///
+/// Launching it skips the expansion and the compilation of the kernel, but the source is still
+/// loaded by the server, so only compilers whose servers read the textual source are supported,
+/// such as WGSL and C++.
+///
+/// The kernel keeps the [execution mode](ExecutionMode) it was compiled with.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedKernel {
+    /// The name of the kernel, unique among the embedded kernels.
+    pub name: &'static str,
+    /// The type name of the compiler that produced the source.
+    pub compiler: &'static str,
+    /// The compiled source.
+    pub source: &'static str,
+    /// The cube dim the kernel was compiled for.
+    pub cube_dim: CubeDim,
+    /// The number of bytes of static shared memory used by the kernel.
+    pub shared_mem_bytes: usize,
+}
+
+/// The kernels embedded in a binary, generated by a build script and looked up by name.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedKernels {
+    kernels: &'static [EmbeddedKernel],
+}
+
+impl EmbeddedKernels {
+    /// Create a collection of embedded kernels.
+    pub const fn new(kernels: &'static [EmbeddedKernel]) -> Self {
+        Self { kernels }
+    }
+
+    /// The embedded kernel with the given name, if it was compiled ahead of time.
+    pub fn get(&self, name: &str) -> Option<&'static EmbeddedKernel> {
+        self.kernels.iter().find(|kernel| kernel.name == name)
+    }
+
+    /// All the embedded kernels.
+    pub fn kernels(&self) -> &'static [EmbeddedKernel] {
+        self.kernels
+    }
+}
+
+impl<C: Compiler> CubeTask<C> for EmbeddedKernel {
+    fn compile(&self, _options: &CompilationOptions, _mode: ExecutionMode) -> CompiledKernel<C> {
+        assert_eq!(
+            self.compiler,
+            core::any::type_name::<C>(),
+            "The kernel {} was compiled ahead of time for another compiler",
+            self.name
+        );
+
+        CompiledKernel {
+            name: Some(self.name),
+            source: self.source.to_string(),
+            repr: None,
+            cube_dim: self.cube_dim,
+            shared_mem_bytes: self.shared_mem_bytes,
+            debug_info: None,
+        }
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>().info(self.name)
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
@@ -1,6 +1,14 @@
-use std::{fmt::Display, marker::PhantomData};
+use std::{
+    cell::RefCell,
+    fmt::{Display, Write},
+    marker::PhantomData,
+};
 
-use crate::{codegen::CompilerRepresentation, ir::CubeDim, Compiler, Kernel, KernelId};
+use crate::{
+    codegen::{CompilationOptions, CompilerRepresentation},
+    ir::{CubeDim, KernelCost, PassManager},
+    Compiler, Kernel, KernelId,
+};
 use alloc::sync::Arc;
 use cubecl_runtime::ExecutionMode;
 
@@ -19,6 +27,22 @@ pub struct CompiledKernel<C: Compiler> {
     pub debug_info: Option<DebugInformation>,
 }
 
+/// Render the source code of a kernel.
+///
+/// The source is written into a buffer reused by the kernels compiled on the same thread, so it
+/// doesn't grow through repeated reallocations, and only the final copy is allocated.
+pub fn render_source(repr: &impl Display) -> String {
+    thread_local! {
+        static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+    }
+
+    BUFFER.with_borrow_mut(|buffer| {
+        buffer.clear();
+        write!(buffer, "{repr}").expect("Formatting the source shouldn't fail");
+        buffer.as_str().into()
+    })
+}
+
 /// Extra debugging information about the compiled kernel.
 #[derive(new)]
 pub struct DebugInformation {
@@ -26,6 +50,50 @@ pub struct DebugInformation {
     pub lang_tag: &'static str,
     /// The compilation id.
     pub id: KernelId,
+    /// The resources used by the kernel, when the backend reports them.
+    #[new(default)]
+    pub statistics: Option<KernelStatistics>,
+    /// The work estimated from the IR of the kernel, unknown for precompiled kernels.
+    pub cost: Option<KernelCost>,
+}
+
+/// The resources used by a compiled kernel on the device.
+#[derive(new, Debug, Clone, Copy, PartialEq)]
+pub struct KernelStatistics {
+    /// The number of registers used by each unit.
+    pub registers: u32,
+    /// The number of bytes of shared memory allocated statically.
+    pub static_shared_memory: usize,
+    /// The ratio of active warps on a multiprocessor to the maximum it supports, for the cube
+    /// dim of the kernel.
+    pub occupancy: f32,
+}
+
+impl Display for KernelStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "registers: {}, static shared memory: {} bytes, occupancy: {:.0} %",
+            self.registers,
+            self.static_shared_memory,
+            self.occupancy * 100.0
+        ))
+    }
+}
+
+impl<C: Compiler> CompiledKernel<C> {
+    /// The Rust source location of the given line (starting at 1) of the generated source.
+    ///
+    /// Only available when the kernel was expanded with debug info enabled through the
+    /// `CUBECL_DEBUG_INFO` environment variable, in which case compilers emit the location of
+    /// each statement as a `// file.rs:line` comment before its instructions.
+    pub fn source_location(&self, line: usize) -> Option<&str> {
+        self.source
+            .lines()
+            .take(line)
+            .filter_map(|line| line.trim().strip_prefix("// "))
+            .filter(|comment| comment.contains(".rs:"))
+            .last()
+    }
 }
 
 impl Display for KernelId {
@@ -48,7 +116,9 @@ impl<C: Compiler> Display for CompiledKernel<C> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("\n[START_KERNEL_COMPILATION]")?;
 
-        if let Some(name) = self.name {
+        if let Some(info) = &self.debug_info {
+            f.write_fmt(format_args!("\nname: {}", info.id.name()))?;
+        } else if let Some(name) = self.name {
             if name.len() <= 32 {
                 f.write_fmt(format_args!("\nname: {name}"))?;
             } else {
@@ -73,6 +143,14 @@ shared_memory: {} bytes",
                     true
                 )
             ))?;
+
+            if let Some(statistics) = &info.statistics {
+                f.write_fmt(format_args!("\nresources: {statistics}"))?;
+            }
+
+            if let Some(cost) = &info.cost {
+                f.write_fmt(format_args!("\ncost: {cost}"))?;
+            }
         }
 
         f.write_fmt(format_args!(
@@ -171,11 +249,25 @@ fn format_str(kernel_id: &str, markers: &[(char, char)], include_space: bool) ->
 pub trait CubeTask<C: Compiler>: Send + Sync {
     /// Identifier for the kernel, used for caching kernel compilation.
     fn id(&self) -> KernelId;
-    /// Compile the kernel into source
-    fn compile(&self, mode: ExecutionMode) -> CompiledKernel<C>;
+    /// Compile the kernel into source, for a device with the capabilities of the options.
+    fn compile(&self, options: &CompilationOptions, mode: ExecutionMode) -> CompiledKernel<C>;
     fn name(&self) -> &'static str {
         core::any::type_name::<Self>()
     }
+    /// The number of bytes of dynamic shared memory to allocate at launch.
+    fn dynamic_shared_memory(&self) -> u32 {
+        0
+    }
+    /// Whether the shared memory must be zero-initialized before the kernel runs.
+    fn zero_initialize_shared_memory(&self) -> bool {
+        false
+    }
+    /// The [work](KernelCost) of each unit of the kernel, estimated from its IR.
+    ///
+    /// Autotune can use it to skip the configurations that are bound by memory bandwidth.
+    fn cost(&self) -> Option<KernelCost> {
+        None
+    }
 }
 
 /// Wraps a [kernel](Kernel) to create a [cube task](CubeTask).
@@ -186,15 +278,26 @@ pub struct KernelTask<C: Compiler, K: Kernel> {
 }
 
 impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
-    fn compile(&self, mode: ExecutionMode) -> CompiledKernel<C> {
-        let gpu_ir = self.kernel_definition.define();
+    fn compile(&self, options: &CompilationOptions, mode: ExecutionMode) -> CompiledKernel<C> {
+        let _span = cubecl_runtime::trace_span!("compile", kernel = %self.id().name());
+        let mut gpu_ir = self.kernel_definition.define();
+        for missing in gpu_ir.body.missing_barriers() {
+            log::warn!("Data race in {}: {missing}", core::any::type_name::<K>());
+        }
+        for non_uniform in gpu_ir.body.non_uniform_operations(&gpu_ir.functions) {
+            log::warn!(
+                "Non-uniform control flow in {}: {non_uniform}",
+                core::any::type_name::<K>()
+            );
+        }
+        gpu_ir.apply_passes(&PassManager::global(), options, mode);
         let cube_dim = gpu_ir.cube_dim;
-        let lower_level_ir = C::compile(gpu_ir, mode);
+        let lower_level_ir = C::compile(gpu_ir, options, mode);
         let shared_mem_bytes = lower_level_ir.shared_memory_size();
 
         CompiledKernel {
             name: Some(core::any::type_name::<K>()),
-            source: lower_level_ir.to_string(),
+            source: render_source(&lower_level_ir),
             repr: Some(lower_level_ir),
             cube_dim,
             shared_mem_bytes,
@@ -209,24 +312,49 @@ impl<C: Compiler, K: Kernel> CubeTask<C> for KernelTask<C, K> {
     fn name(&self) -> &'static str {
         core::any::type_name::<K>()
     }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.kernel_definition.dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.kernel_definition.zero_initialize_shared_memory()
+    }
+
+    fn cost(&self) -> Option<KernelCost> {
+        Some(self.kernel_definition.define().cost())
+    }
 }
 
 impl<C: Compiler> CubeTask<C> for Arc<dyn CubeTask<C>> {
-    fn compile(&self, mode: ExecutionMode) -> CompiledKernel<C> {
-        self.as_ref().compile(mode)
+    fn compile(&self, options: &CompilationOptions, mode: ExecutionMode) -> CompiledKernel<C> {
+        self.as_ref().compile(options, mode)
     }
 
     fn id(&self) -> KernelId {
         self.as_ref().id()
     }
+
     fn name(&self) -> &'static str {
         self.as_ref().name()
     }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.as_ref().dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.as_ref().zero_initialize_shared_memory()
+    }
+
+    fn cost(&self) -> Option<KernelCost> {
+        self.as_ref().cost()
+    }
 }
 
 impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
-    fn compile(&self, mode: ExecutionMode) -> CompiledKernel<C> {
-        self.as_ref().compile(mode)
+    fn compile(&self, options: &CompilationOptions, mode: ExecutionMode) -> CompiledKernel<C> {
+        self.as_ref().compile(options, mode)
     }
 
     fn id(&self) -> KernelId {
@@ -236,4 +364,16 @@ impl<C: Compiler> CubeTask<C> for Box<dyn CubeTask<C>> {
     fn name(&self) -> &'static str {
         self.as_ref().name()
     }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.as_ref().dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.as_ref().zero_initialize_shared_memory()
+    }
+
+    fn cost(&self) -> Option<KernelCost> {
+        self.as_ref().cost()
+    }
 }
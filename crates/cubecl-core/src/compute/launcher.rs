@@ -1,13 +1,21 @@
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
-
-use crate::compute::KernelTask;
-use crate::ir::{Elem, FloatKind, IntKind};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::compute::{
+    find_races, race_detection_enabled, DataRace, DivisionCountingKernel, EmbeddedKernel,
+    InstrumentedKernel, KernelTask, SpecializationCache, SpecializedKernel, TensorShape,
+    DEFAULT_RECORDED_ACCESSES, RECORD_WORDS,
+};
+use crate::ir::{self, CoopMma, Elem, FloatKind, IntKind, KernelDefinition, Operation, Variable};
 use crate::prelude::ArrayHandleRef;
-use crate::KernelSettings;
 use crate::{calculate_num_elems_dyn_rank, frontend::TensorHandleRef, Kernel, Runtime};
+use crate::{Compiler, CubeElement, Feature, KernelSettings, MmaConfig, UniformLayout};
 use bytemuck::NoUninit;
 use cubecl_runtime::client::ComputeClient;
 use cubecl_runtime::server::{Binding, CubeCount};
+use cubecl_runtime::DeviceProperties;
 use num_traits::ToPrimitive;
 
 /// Prepare a kernel for [launch](KernelLauncher::launch).
@@ -21,19 +29,128 @@ pub struct KernelLauncher<R: Runtime> {
     scalar_i64: ScalarState<i64>,
     scalar_i32: ScalarState<i32>,
     scalar_order: Vec<Elem>,
+    tensor_specs: Vec<TensorSpec>,
     pub settings: KernelSettings,
     runtime: PhantomData<R>,
 }
 
+/// The element type and rank of a registered tensor, used to validate a
+/// [checked launch](KernelLauncher::launch_checked).
+struct TensorSpec {
+    elem: Option<Elem>,
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+}
+
+/// An error returned by a [checked launch](KernelLauncher::launch_checked) when the launch
+/// arguments don't match the [kernel definition](KernelDefinition).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchError {
+    /// The number of tensors doesn't match the number of global buffers of the kernel.
+    BindingCount {
+        /// The number of buffers of the kernel.
+        expected: usize,
+        /// The number of tensors registered.
+        actual: usize,
+    },
+    /// The element type of a tensor doesn't match the one of the kernel buffer.
+    ElemMismatch {
+        /// The position of the tensor, inputs first followed by outputs.
+        position: usize,
+        /// The element type of the kernel buffer.
+        expected: Elem,
+        /// The element type of the tensor.
+        actual: Elem,
+    },
+    /// The shape and the strides of a tensor don't have the same rank.
+    StridesMismatch {
+        /// The position of the tensor, inputs first followed by outputs.
+        position: usize,
+        /// The rank of the shape.
+        shape: usize,
+        /// The rank of the strides.
+        strides: usize,
+    },
+    /// The rank of an array doesn't match the one of the kernel buffer.
+    RankMismatch {
+        /// The position of the tensor, inputs first followed by outputs.
+        position: usize,
+        /// The rank of the kernel buffer.
+        expected: usize,
+        /// The rank of the tensor.
+        actual: usize,
+    },
+    /// The kernel executes a [cmma configuration](MmaConfig) the device doesn't support.
+    UnsupportedCmma(MmaConfig),
+    /// The dynamic shared memory of the launch can't hold a single element of the dynamic shared
+    /// memory declared by the kernel.
+    DynamicSharedMemory {
+        /// The number of bytes of dynamic shared memory of the launch.
+        bytes: u32,
+        /// The size in bytes of an element of the dynamic shared memory.
+        item_size: u32,
+    },
+}
+
+impl core::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LaunchError::BindingCount { expected, actual } => write!(
+                f,
+                "The kernel expects {expected} tensors, but {actual} were provided"
+            ),
+            LaunchError::ElemMismatch {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The tensor at position {position} holds {actual} elements, but the kernel expects {expected}"
+            ),
+            LaunchError::StridesMismatch {
+                position,
+                shape,
+                strides,
+            } => write!(
+                f,
+                "The tensor at position {position} has a shape of rank {shape}, but strides of rank {strides}"
+            ),
+            LaunchError::RankMismatch {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "The tensor at position {position} has rank {actual}, but the kernel expects {expected}"
+            ),
+            LaunchError::UnsupportedCmma(config) => write!(
+                f,
+                "The kernel executes cmma operations {config}, which the device doesn't support"
+            ),
+            LaunchError::DynamicSharedMemory { bytes, item_size } => write!(
+                f,
+                "The dynamic shared memory of {bytes} bytes can't hold a single element of {item_size} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
 impl<R: Runtime> KernelLauncher<R> {
     /// Register a tensor to be launched.
     pub fn register_tensor(&mut self, tensor: &TensorHandleRef<'_, R>) {
+        self.tensor_specs.push(TensorSpec {
+            elem: tensor.elem,
+            shape: tensor.shape.to_vec(),
+            strides: tensor.strides.to_vec(),
+        });
         self.tensors.push(tensor);
     }
 
     /// Register an array to be launched.
     pub fn register_array(&mut self, array: &ArrayHandleRef<'_, R>) {
-        self.tensors.push(&array.as_tensor());
+        self.register_tensor(&array.as_tensor());
     }
 
     /// Register a u32 scalar to be launched.
@@ -79,12 +196,57 @@ impl<R: Runtime> KernelLauncher<R> {
     }
 
     /// Launch the kernel.
+    ///
+    /// When the `CUBECL_RACE_DETECTION` environment variable is set, the kernel is launched with
+    /// [race detection](Self::launch_race_detection) and the races found are logged as warnings.
+    ///
+    /// # Panics
+    ///
+    /// If the kernel executes a [cmma configuration](MmaConfig) the device doesn't support, or if
+    /// the dynamic shared memory can't hold a single element.
     pub fn launch<K: Kernel>(
         self,
         cube_count: CubeCount,
         kernel: K,
         client: &ComputeClient<R::Server, R::Channel>,
     ) {
+        if let Err(err) = validate_kernel::<R, _>(&kernel, client) {
+            panic!("{err}");
+        }
+        self.launch_validated(cube_count, kernel, client);
+    }
+
+    /// Launch the kernel after validating the registered tensors against its
+    /// [definition](KernelDefinition).
+    ///
+    /// The element type is only validated for tensors created with a known element type, such as
+    /// [TensorArg::from_raw_parts_typed](crate::prelude::TensorArg::from_raw_parts_typed).
+    /// The definition of a kernel is only expanded on its first launch, and the parts of it
+    /// validated are cached by its id afterward.
+    pub fn launch_checked<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), LaunchError> {
+        let layout = validate_kernel::<R, _>(&kernel, client)?;
+        self.validate_bindings(&layout.bindings)?;
+        self.launch_validated(cube_count, kernel, client);
+
+        Ok(())
+    }
+
+    fn launch_validated<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) {
+        if race_detection_enabled() {
+            return self.launch_logging_races(cube_count, kernel, client);
+        }
+
+        let _span = cubecl_runtime::trace_span!("launch", kernel = %kernel.id().name());
         let bindings = self.into_bindings(client);
 
         let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
@@ -92,17 +254,87 @@ impl<R: Runtime> KernelLauncher<R> {
         client.execute(kernel, cube_count, bindings);
     }
 
+    /// Validate the registered tensors and the dynamic shared memory against the
+    /// [kernel definition](KernelDefinition).
+    pub fn validate(&self, definition: &KernelDefinition) -> Result<(), LaunchError> {
+        let layout = KernelLayout::new(definition);
+        layout.validate_dynamic_shared_memory(definition.dynamic_shared_memory)?;
+
+        self.validate_bindings(&layout.bindings)
+    }
+
+    /// Validate the registered tensors against the input and output bindings of a kernel.
+    fn validate_bindings(&self, bindings: &[ir::Binding]) -> Result<(), LaunchError> {
+        let expected = bindings.len();
+
+        if expected != self.tensor_specs.len() {
+            return Err(LaunchError::BindingCount {
+                expected,
+                actual: self.tensor_specs.len(),
+            });
+        }
+
+        for (position, (binding, spec)) in bindings.iter().zip(self.tensor_specs.iter()).enumerate()
+        {
+            if spec.shape.len() != spec.strides.len() {
+                return Err(LaunchError::StridesMismatch {
+                    position,
+                    shape: spec.shape.len(),
+                    strides: spec.strides.len(),
+                });
+            }
+
+            // Only arrays have a rank known by the kernel, tensors are of any rank.
+            if let Some(expected) = binding.rank {
+                if expected != spec.shape.len() {
+                    return Err(LaunchError::RankMismatch {
+                        position,
+                        expected,
+                        actual: spec.shape.len(),
+                    });
+                }
+            }
+
+            if let Some(actual) = spec.elem {
+                let expected = binding.item.elem();
+
+                if storage_elem(expected) != storage_elem(actual) {
+                    return Err(LaunchError::ElemMismatch {
+                        position,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Launch the kernel without check bounds.
     ///
     /// # Safety
     ///
     /// Out-of-bounds reads and writes can happen.
+    ///
+    /// # Panics
+    ///
+    /// If the kernel executes a [cmma configuration](MmaConfig) the device doesn't support, or if
+    /// the dynamic shared memory can't hold a single element.
     pub unsafe fn launch_unchecked<K: Kernel>(
         self,
         cube_count: CubeCount,
         kernel: K,
         client: &ComputeClient<R::Server, R::Channel>,
     ) {
+        if let Err(err) = validate_kernel::<R, _>(&kernel, client) {
+            panic!("{err}");
+        }
+        if race_detection_enabled() {
+            return self.launch_logging_races(cube_count, kernel, client);
+        }
+
+        let _span = cubecl_runtime::trace_span!("launch", kernel = %kernel.id().name());
         let bindings = self.into_bindings(client);
 
         let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
@@ -110,6 +342,151 @@ impl<R: Runtime> KernelLauncher<R> {
         client.execute_unchecked(kernel, cube_count, bindings);
     }
 
+    /// Launch the kernel with its accesses to global and shared memory recorded, and return the
+    /// [data races](DataRace) between its units found in the first `max_accesses` ones.
+    ///
+    /// Each access appends a record to a buffer read back once the kernel completes, which makes
+    /// the launch much slower than a normal one. It's only meant to validate new kernels.
+    pub fn launch_race_detection<K: Kernel>(
+        mut self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+        max_accesses: u32,
+    ) -> Vec<DataRace> {
+        assert!(max_accesses > 0, "At least one access should be recorded");
+
+        let num_words = max_accesses as usize * RECORD_WORDS;
+        let counter = client.create(bytemuck::bytes_of(&0u32));
+        let records = client.empty(num_words * core::mem::size_of::<u32>());
+        unsafe {
+            self.register_array(&ArrayHandleRef::from_raw_parts(&counter, 1));
+            self.register_array(&ArrayHandleRef::from_raw_parts(&records, num_words));
+        }
+
+        let bindings = self.into_bindings(client);
+        let kernel = InstrumentedKernel::new(kernel, max_accesses);
+        let kernel = Box::new(KernelTask::<R::Compiler, _>::new(kernel));
+        client.execute(kernel, cube_count, bindings);
+
+        let data = client.read_many(&[counter.binding(), records.binding()]);
+        let num_accesses = u32::from_bytes(&data[0])[0];
+        if num_accesses > max_accesses {
+            log::warn!(
+                "Race detection only recorded {max_accesses} of the {num_accesses} accesses of \
+                 the kernel"
+            );
+        }
+
+        let records = u32::from_bytes(&data[1]);
+        let num_words = num_accesses.min(max_accesses) as usize * RECORD_WORDS;
+        find_races(&records[..num_words])
+    }
+
+    /// Launch the kernel with its divisions and remainders by zero returning zero, whatever its
+    /// [policy](crate::ir::DivisionPolicy), and return the number of divisions by zero it executed.
+    ///
+    /// Each division by zero is counted with an atomic operation, which makes the launch slower
+    /// than a normal one. It's only meant to diagnose kernels returning unexpected values.
+    pub fn launch_division_check<K: Kernel>(
+        mut self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> u32 {
+        let counter = client.create(bytemuck::bytes_of(&0u32));
+        unsafe {
+            self.register_array(&ArrayHandleRef::from_raw_parts(&counter, 1));
+        }
+
+        let bindings = self.into_bindings(client);
+        let kernel = DivisionCountingKernel::new(kernel);
+        let kernel = Box::new(KernelTask::<R::Compiler, _>::new(kernel));
+        client.execute(kernel, cube_count, bindings);
+
+        u32::from_bytes(&client.read(counter.binding()))[0]
+    }
+
+    fn launch_logging_races<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) {
+        let name = core::any::type_name::<K>();
+        let races =
+            self.launch_race_detection(cube_count, kernel, client, DEFAULT_RECORDED_ACCESSES);
+
+        for race in races {
+            log::warn!("Data race in {name}: {race}");
+        }
+    }
+
+    /// Launch a variant of the kernel [specialized](SpecializationCache) for the shapes and
+    /// strides of the registered tensors.
+    pub fn launch_specialized<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+        specializations: &SpecializationCache,
+    ) {
+        let kernel = self.specialize(kernel, client, specializations);
+        self.launch(cube_count, kernel, client);
+    }
+
+    /// Launch a variant of the kernel [specialized](SpecializationCache) for the shapes and
+    /// strides of the registered tensors, without check bounds.
+    ///
+    /// # Safety
+    ///
+    /// Out-of-bounds reads and writes can happen.
+    pub unsafe fn launch_specialized_unchecked<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+        specializations: &SpecializationCache,
+    ) {
+        let kernel = self.specialize(kernel, client, specializations);
+        self.launch_unchecked(cube_count, kernel, client);
+    }
+
+    /// Launch the [embedded](EmbeddedKernel) variant of the kernel when it was compiled ahead of
+    /// time, otherwise expand and compile the kernel.
+    pub fn launch_embedded<K: Kernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        embedded: Option<&'static EmbeddedKernel>,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) {
+        match embedded {
+            Some(embedded) => {
+                let bindings = self.into_bindings(client);
+                client.execute(Box::new(*embedded), cube_count, bindings);
+            }
+            None => self.launch(cube_count, kernel, client),
+        }
+    }
+
+    fn specialize<K: Kernel>(
+        &self,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+        specializations: &SpecializationCache,
+    ) -> SpecializedKernel<K> {
+        let shapes = self
+            .tensor_specs
+            .iter()
+            .map(|spec| TensorShape::new(spec.shape.clone(), spec.strides.clone()))
+            .collect();
+        let kernel = SpecializedKernel::new(kernel, shapes);
+        specializations.register::<R>(kernel.id(), client);
+
+        kernel
+    }
+
     /// We need to create the bindings in the same order they are defined in the compilation step.
     ///
     /// The function [crate::KernelIntegrator::integrate] stars by registering the input tensors followed
@@ -119,33 +496,70 @@ impl<R: Runtime> KernelLauncher<R> {
     fn into_bindings(mut self, client: &ComputeClient<R::Server, R::Channel>) -> Vec<Binding> {
         let mut bindings = Vec::new();
 
-        self.tensors.register(client, &mut bindings);
-
-        for elem in self.scalar_order.drain(..) {
-            match elem {
-                Elem::Float(kind) => match kind {
-                    FloatKind::F16 => self.scalar_f16.register::<R>(client, &mut bindings),
-                    FloatKind::BF16 => self.scalar_bf16.register::<R>(client, &mut bindings),
-                    FloatKind::F32 => self.scalar_f32.register::<R>(client, &mut bindings),
-                    FloatKind::F64 => self.scalar_f64.register::<R>(client, &mut bindings),
-                },
-                Elem::Int(kind) => match kind {
-                    IntKind::I32 => self.scalar_i32.register::<R>(client, &mut bindings),
-                    IntKind::I64 => self.scalar_i64.register::<R>(client, &mut bindings),
-                },
-                Elem::AtomicInt(kind) => match kind {
-                    IntKind::I32 => self.scalar_i32.register::<R>(client, &mut bindings),
-                    IntKind::I64 => self.scalar_i64.register::<R>(client, &mut bindings),
-                },
-                Elem::UInt => self.scalar_u32.register::<R>(client, &mut bindings),
-                Elem::AtomicUInt => self.scalar_u32.register::<R>(client, &mut bindings),
-                Elem::Bool => panic!("Bool can't be passed as bindings."),
+        core::mem::replace(&mut self.tensors, TensorState::Empty).register(client, &mut bindings);
+
+        let scalar_order = core::mem::take(&mut self.scalar_order);
+
+        if <R::Compiler as Compiler>::packed_scalars() {
+            self.register_packed_scalars(&scalar_order, client, &mut bindings);
+        } else {
+            for elem in scalar_order {
+                let data = self.scalar_data(elem);
+                bindings.push(client.create(data).binding());
             }
         }
 
         bindings
     }
 
+    /// Pack all scalars into a single buffer following the [uniform layout](UniformLayout) the
+    /// compiler uses to declare them.
+    fn register_packed_scalars(
+        &self,
+        scalar_order: &[Elem],
+        client: &ComputeClient<R::Server, R::Channel>,
+        bindings: &mut Vec<Binding>,
+    ) {
+        let layout = UniformLayout::new(
+            scalar_order
+                .iter()
+                .map(|elem| (*elem, self.scalar_data(*elem).len() / elem.size())),
+        );
+
+        if layout.is_empty() {
+            return;
+        }
+
+        let mut data = vec![0; layout.size()];
+
+        for field in layout.fields() {
+            let size = field.elem.size();
+            let start = field.index * size;
+            let value = &self.scalar_data(field.elem)[start..start + size];
+
+            data[field.offset..field.offset + size].copy_from_slice(value);
+        }
+
+        bindings.push(client.create(&data).binding());
+    }
+
+    fn scalar_data(&self, elem: Elem) -> &[u8] {
+        match elem {
+            Elem::Float(kind) => match kind {
+                FloatKind::F16 => self.scalar_f16.data(),
+                FloatKind::BF16 => self.scalar_bf16.data(),
+                FloatKind::F32 => self.scalar_f32.data(),
+                FloatKind::F64 => self.scalar_f64.data(),
+            },
+            Elem::Int(kind) | Elem::AtomicInt(kind) => match kind {
+                IntKind::I32 => self.scalar_i32.data(),
+                IntKind::I64 => self.scalar_i64.data(),
+            },
+            Elem::UInt | Elem::AtomicUInt => self.scalar_u32.data(),
+            Elem::Bool => panic!("Bool can't be passed as bindings."),
+        }
+    }
+
     fn register_scalar(&mut self, elem: Elem) {
         if !self.scalar_order.contains(&elem) {
             self.scalar_order.push(elem);
@@ -153,6 +567,156 @@ impl<R: Runtime> KernelLauncher<R> {
     }
 }
 
+/// The parts of a kernel definition validated at launch.
+struct KernelLayout {
+    /// The input and output bindings.
+    bindings: Vec<ir::Binding>,
+    /// The item type of the dynamic shared memory, if the kernel declares one.
+    dynamic_shared_memory: Option<ir::Item>,
+}
+
+impl KernelLayout {
+    fn new(definition: &KernelDefinition) -> Self {
+        Self {
+            bindings: definition
+                .inputs
+                .iter()
+                .chain(definition.outputs.iter())
+                .cloned()
+                .collect(),
+            dynamic_shared_memory: definition.body.dynamic_shared_memory(),
+        }
+    }
+
+    /// Validate that the `bytes` of dynamic shared memory of a launch hold at least one element,
+    /// which the compilers need to size the memory.
+    fn validate_dynamic_shared_memory(&self, bytes: u32) -> Result<(), LaunchError> {
+        let Some(item) = self.dynamic_shared_memory else {
+            return Ok(());
+        };
+        let vectorization = item.vectorization.map_or(1, |factor| factor.get() as usize);
+        let item_size = (item.elem.size() * vectorization) as u32;
+
+        match bytes < item_size {
+            true => Err(LaunchError::DynamicSharedMemory { bytes, item_size }),
+            false => Ok(()),
+        }
+    }
+}
+
+/// The layout of each kernel launched by the process, by hash of its id, since the definition is
+/// as slow to expand as the kernel is to compile.
+static LAYOUTS: RwLock<BTreeMap<u64, Arc<KernelLayout>>> = RwLock::new(BTreeMap::new());
+
+/// The layout of the kernel, only expanding its `definition` on its first launch.
+fn kernel_layout<'a, K: Kernel>(
+    kernel: &K,
+    definition: impl FnOnce() -> &'a KernelDefinition,
+) -> Arc<KernelLayout> {
+    let id = kernel_hash(kernel);
+    if let Some(layout) = LAYOUTS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&id)
+    {
+        return layout.clone();
+    }
+
+    let layout = Arc::new(KernelLayout::new(definition()));
+    LAYOUTS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(id, layout.clone());
+
+    layout
+}
+
+fn kernel_hash<K: Kernel>(kernel: &K) -> u64 {
+    let mut hasher = std::hash::DefaultHasher::new();
+    kernel.id().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Validate the dynamic shared memory of the launch against the [layout](KernelLayout) of the
+/// kernel, and [its cmma configurations](validate_cmma) on its first launch with the client,
+/// returning the layout.
+fn validate_kernel<R: Runtime, K: Kernel>(
+    kernel: &K,
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Result<Arc<KernelLayout>, LaunchError> {
+    let mut definition = None;
+    let layout = kernel_layout(kernel, || definition.insert(kernel.define()));
+    layout.validate_dynamic_shared_memory(kernel.dynamic_shared_memory())?;
+    client.validate_once(kernel_hash(kernel), || {
+        validate_cmma(
+            definition.get_or_insert_with(|| kernel.define()),
+            client.properties(),
+        )
+    })?;
+
+    Ok(layout)
+}
+
+/// Validate the [cmma configurations](MmaConfig) executed by the kernel against the ones
+/// supported by the device, which would otherwise fail to compile.
+fn validate_cmma(
+    definition: &KernelDefinition,
+    properties: &DeviceProperties<Feature>,
+) -> Result<(), LaunchError> {
+    let mut unsupported = None;
+    let scopes = core::iter::once(&definition.body)
+        .chain(definition.functions.iter().map(|function| &function.body));
+
+    for scope in scopes {
+        scope.visit_operations(&mut |operation| {
+            let Operation::CoopMma(CoopMma::Execute {
+                mat_a,
+                mat_b,
+                mat_c,
+                ..
+            }) = operation
+            else {
+                return;
+            };
+            let (
+                Variable::Matrix { mat: a, .. },
+                Variable::Matrix { mat: b, .. },
+                Variable::Matrix { mat: c, .. },
+            ) = (mat_a, mat_b, mat_c)
+            else {
+                return;
+            };
+            let config = MmaConfig {
+                a: a.elem,
+                b: b.elem,
+                c: c.elem,
+                m: a.m,
+                k: a.k,
+                n: a.n,
+            };
+
+            if unsupported.is_none() && !config.is_supported(properties) {
+                unsupported = Some(config);
+            }
+        });
+    }
+
+    match unsupported {
+        Some(config) => Err(LaunchError::UnsupportedCmma(config)),
+        None => Ok(()),
+    }
+}
+
+/// The element type as stored in a global buffer, where atomics and booleans share the
+/// representation of their integer counterpart.
+fn storage_elem(elem: Elem) -> Elem {
+    match elem {
+        Elem::AtomicInt(kind) => Elem::Int(kind),
+        Elem::AtomicUInt | Elem::Bool => Elem::UInt,
+        elem => elem,
+    }
+}
+
 /// Handles the tensor state.
 pub enum TensorState<R: Runtime> {
     /// No tensor is registered yet.
@@ -301,17 +865,10 @@ impl<T: NoUninit> ScalarState<T> {
         }
     }
 
-    fn register<R: Runtime>(
-        &self,
-        client: &ComputeClient<R::Server, R::Channel>,
-        bindings: &mut Vec<Binding>,
-    ) {
+    fn data(&self) -> &[u8] {
         match self {
-            ScalarState::Empty => (),
-            ScalarState::Some(values) => {
-                let handle = client.create(bytemuck::cast_slice(values));
-                bindings.push(handle.binding());
-            }
+            ScalarState::Empty => &[],
+            ScalarState::Some(values) => bytemuck::cast_slice(values),
         }
     }
 }
@@ -328,6 +885,7 @@ impl<R: Runtime> Default for KernelLauncher<R> {
             scalar_i64: ScalarState::Empty,
             scalar_i32: ScalarState::Empty,
             scalar_order: Vec::new(),
+            tensor_specs: Vec::new(),
             settings: Default::default(),
             runtime: PhantomData,
         }
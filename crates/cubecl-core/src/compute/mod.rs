@@ -1,7 +1,22 @@
 mod builder;
+mod cube_dim;
+mod dispatch;
+mod division;
+mod embedded;
 mod kernel;
 mod launcher;
+mod race;
+mod scope;
+mod specialization;
+
+pub(crate) use cube_dim::resident_cubes;
+pub(crate) use division::DivisionCountingKernel;
 
 pub use builder::*;
+pub use dispatch::*;
+pub use embedded::*;
 pub use kernel::*;
 pub use launcher::*;
+pub use race::*;
+pub use scope::*;
+pub use specialization::*;
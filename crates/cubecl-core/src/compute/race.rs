@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::{
+    ir::{
+        cpa, BinaryOperator, Binding, Branch, Elem, Item, KernelDefinition, Location, Operation,
+        Operator, Scope, Synchronization, Variable, Visibility,
+    },
+    Kernel, KernelId,
+};
+
+/// The number of `u32` words recorded for each access: the memory, the index, the cube, the unit
+/// and the number of [sync_units](Synchronization::SyncUnits) executed before the access.
+pub(crate) const RECORD_WORDS: usize = 5;
+
+/// The number of accesses recorded when race detection is enabled with the
+/// `CUBECL_RACE_DETECTION` environment variable.
+pub(crate) const DEFAULT_RECORDED_ACCESSES: u32 = 1 << 20;
+
+/// Whether kernels should be launched with [race detection](crate::compute::KernelLauncher::launch_race_detection),
+/// set with the `CUBECL_RACE_DETECTION` environment variable.
+pub(crate) fn race_detection_enabled() -> bool {
+    std::env::var("CUBECL_RACE_DETECTION").is_ok_and(|value| value != "0")
+}
+
+/// The memory accessed by a [data race](DataRace).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RaceMemory {
+    /// The input array with the given id.
+    Input(u16),
+    /// The output array with the given id.
+    Output(u16),
+    /// The shared memory with the given id.
+    Shared(u16),
+}
+
+impl Display for RaceMemory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RaceMemory::Input(id) => write!(f, "input({id})"),
+            RaceMemory::Output(id) => write!(f, "output({id})"),
+            RaceMemory::Shared(id) => write!(f, "shared({id})"),
+        }
+    }
+}
+
+/// An access to memory recorded during a launch with race detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceAccess {
+    /// The position of the cube of the unit.
+    pub cube: u32,
+    /// The position of the unit in its cube.
+    pub unit: u32,
+    /// Whether the access is a write.
+    pub write: bool,
+}
+
+impl Display for RaceAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.write { "written" } else { "read" };
+        write!(f, "{kind} by unit {} of cube {}", self.unit, self.cube)
+    }
+}
+
+/// Two accesses to the same value by different units, at least one of them being a write, with no
+/// [sync_units](Synchronization::SyncUnits) ordering them.
+///
+/// Units of different cubes are never synchronized, so their accesses to global memory always
+/// race.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataRace {
+    pub memory: RaceMemory,
+    /// The index of the value in the memory, in lines.
+    pub index: u32,
+    pub first: RaceAccess,
+    pub second: RaceAccess,
+}
+
+impl Display for DataRace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}[{}]` is {} and {} without synchronization",
+            self.memory, self.index, self.first, self.second
+        )
+    }
+}
+
+/// A kernel recording its accesses to global and shared memory into two extra outputs: an atomic
+/// counter of the accesses followed by the [records](RECORD_WORDS) of the first `capacity` ones.
+///
+/// Only the body of the kernel is instrumented, not the device functions it calls. Atomic arrays
+/// aren't recorded, and bulk copies are recorded as an access to their first value.
+#[derive(new)]
+pub(crate) struct InstrumentedKernel<K: Kernel> {
+    kernel: K,
+    capacity: u32,
+}
+
+impl<K: Kernel> Kernel for InstrumentedKernel<K> {
+    fn define(&self) -> KernelDefinition {
+        let mut definition = self.kernel.define();
+        instrument(&mut definition, self.capacity);
+
+        definition
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>().info((self.kernel.id(), self.capacity))
+    }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.kernel.dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.kernel.zero_initialize_shared_memory()
+    }
+}
+
+/// Where an access is recorded, each memory being identified by its id, its kind and whether it's
+/// written in the first word of a record.
+const INPUT: u32 = 0;
+const OUTPUT: u32 = 1;
+const SHARED: u32 = 2;
+
+fn instrument(definition: &mut KernelDefinition, capacity: u32) {
+    let num_outputs = definition.outputs.len() as u16;
+    let counter = Variable::GlobalOutputArray {
+        id: num_outputs,
+        item: Item::new(Elem::AtomicUInt),
+    };
+    let records = Variable::GlobalOutputArray {
+        id: num_outputs + 1,
+        item: Item::new(Elem::UInt),
+    };
+    for item in [counter.item(), records.item()] {
+        definition.outputs.push(Binding {
+            location: Location::Storage,
+            visibility: Visibility::ReadWrite,
+            item,
+            size: None,
+            rank: Some(1),
+        });
+    }
+
+    let scope = &mut definition.body;
+    let mut instrumentation = Instrumentation {
+        counter,
+        records,
+        capacity,
+        epoch: scope.create_local(Elem::UInt),
+        slot: scope.create_local(Elem::UInt),
+        position: scope.create_local(Elem::UInt),
+        in_bounds: scope.create_local(Elem::Bool),
+        slices: Vec::new(),
+    };
+
+    let operations = core::mem::take(&mut scope.operations);
+    let epoch = instrumentation.epoch;
+    cpa!(scope, epoch = cast(0u32));
+    scope.operations.extend(operations);
+
+    instrumentation.scope(scope);
+}
+
+struct Instrumentation {
+    counter: Variable,
+    records: Variable,
+    capacity: u32,
+    /// The number of `sync_units` executed by the unit.
+    epoch: Variable,
+    slot: Variable,
+    position: Variable,
+    in_bounds: Variable,
+    /// The memory and the offset of each slice of global or shared memory.
+    slices: Vec<(Variable, u32, Variable)>,
+}
+
+impl Instrumentation {
+    fn scope(&mut self, scope: &mut Scope) {
+        let operations = core::mem::take(&mut scope.operations);
+
+        for mut operation in operations {
+            match &mut operation {
+                Operation::Branch(branch) => match branch {
+                    Branch::If(branch) => self.scope(&mut branch.scope),
+                    Branch::IfElse(branch) => {
+                        self.scope(&mut branch.scope_if);
+                        self.scope(&mut branch.scope_else);
+                    }
+                    Branch::Switch(switch) => {
+                        self.scope(&mut switch.scope_default);
+                        for (_, case) in switch.cases.iter_mut() {
+                            self.scope(case);
+                        }
+                    }
+                    Branch::RangeLoop(range_loop) => self.scope(&mut range_loop.scope),
+                    Branch::Loop(body) => self.scope(&mut body.scope),
+                    _ => {}
+                },
+                Operation::Synchronization(Synchronization::SyncUnits) => {
+                    scope.register(operation);
+                    let epoch = self.epoch;
+                    cpa!(scope, epoch = epoch + 1u32);
+                    continue;
+                }
+                Operation::Operator(Operator::Slice(op)) => {
+                    if let Some((memory, offset)) = self.memory(op.input) {
+                        let start = op.start;
+                        let slice_offset = scope.create_local(Elem::UInt);
+                        cpa!(scope, slice_offset = offset + start);
+                        self.slices.push((op.out, memory, slice_offset));
+                    }
+                }
+                Operation::Operator(operator) => {
+                    for (array, index, write) in accesses(operator) {
+                        if let Some((memory, offset)) = self.memory(array) {
+                            self.record(scope, memory | write as u32, offset, index);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            scope.register(operation);
+        }
+    }
+
+    /// Append a record of the access to the records, when there is still room for it.
+    fn record(&self, scope: &mut Scope, memory: u32, offset: Variable, index: Variable) {
+        let (counter, records, slot, position, in_bounds, epoch) = (
+            self.counter,
+            self.records,
+            self.slot,
+            self.position,
+            self.in_bounds,
+            self.epoch,
+        );
+        let capacity: Variable = self.capacity.into();
+
+        let pointer = scope.create_local_undeclared(Item::new(Elem::AtomicUInt));
+        cpa!(scope, pointer = counter[0u32]);
+        scope.register(Operator::AtomicAdd(BinaryOperator {
+            lhs: pointer,
+            rhs: 1u32.into(),
+            out: slot,
+        }));
+        cpa!(scope, position = cast(index));
+        cpa!(scope, position = position + offset);
+        cpa!(scope, in_bounds = slot < capacity);
+
+        cpa!(scope, if(in_bounds).then(|scope| {
+            let words: [Variable; RECORD_WORDS] = [
+                memory.into(),
+                position,
+                Variable::CubePos,
+                Variable::UnitPos,
+                epoch,
+            ];
+            let address = scope.create_local(Elem::UInt);
+            cpa!(scope, address = slot * RECORD_WORDS as u32);
+
+            for word in words {
+                cpa!(scope, records[address] = word);
+                cpa!(scope, address = address + 1u32);
+            }
+        }));
+    }
+
+    /// The identifier and the offset of the global or shared memory the variable refers to.
+    fn memory(&self, variable: Variable) -> Option<(u32, Variable)> {
+        let (id, kind, item) = match variable {
+            Variable::GlobalInputArray { id, item } => (id, INPUT, item),
+            Variable::GlobalOutputArray { id, item } => (id, OUTPUT, item),
+            Variable::SharedMemory { id, item, .. } => (id, SHARED, item),
+            Variable::Slice { .. } => {
+                return self
+                    .slices
+                    .iter()
+                    .rev()
+                    .find(|(slice, ..)| *slice == variable)
+                    .map(|(_, memory, offset)| (*memory, *offset));
+            }
+            _ => return None,
+        };
+
+        if item.elem.is_atomic() {
+            return None;
+        }
+        Some(((id as u32) << 3 | kind << 1, 0u32.into()))
+    }
+}
+
+/// The arrays accessed by the operator, with the index accessed and whether it's a write.
+fn accesses(operator: &Operator) -> Vec<(Variable, Variable, bool)> {
+    match operator {
+        Operator::Index(op) | Operator::UncheckedIndex(op) => vec![(op.lhs, op.rhs, false)],
+        Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op) => {
+            vec![(op.out, op.lhs, true)]
+        }
+        Operator::Copy(op) => vec![(op.input, op.in_index, false), (op.out, op.out_index, true)],
+        Operator::CopyBulk(op) | Operator::CopyBulkAsync(op) => {
+            vec![(op.input, op.in_index, false), (op.out, op.out_index, true)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// A recorded access.
+#[derive(Clone, Copy)]
+struct Record {
+    access: RaceAccess,
+    epoch: u32,
+}
+
+/// Find the races between the recorded accesses, at most one for each value.
+pub(crate) fn find_races(records: &[u32]) -> Vec<DataRace> {
+    let mut values = BTreeMap::<(RaceMemory, u32), Vec<Record>>::new();
+
+    for record in records.chunks_exact(RECORD_WORDS) {
+        let id = (record[0] >> 3) as u16;
+        let memory = match (record[0] >> 1) & 3 {
+            INPUT => RaceMemory::Input(id),
+            OUTPUT => RaceMemory::Output(id),
+            _ => RaceMemory::Shared(id),
+        };
+        let access = RaceAccess {
+            cube: record[2],
+            unit: record[3],
+            write: record[0] & 1 == 1,
+        };
+        values.entry((memory, record[1])).or_default().push(Record {
+            access,
+            epoch: record[4],
+        });
+    }
+
+    values
+        .into_iter()
+        .filter_map(|((memory, index), records)| {
+            let writes = records.iter().filter(|record| record.access.write);
+            let (first, second) = writes
+                .flat_map(|write| records.iter().map(move |other| (*write, *other)))
+                .find(|(write, other)| races(memory, write, other))?;
+
+            Some(DataRace {
+                memory,
+                index,
+                first: first.access,
+                second: second.access,
+            })
+        })
+        .collect()
+}
+
+/// Whether a write races with another access to the same value.
+fn races(memory: RaceMemory, write: &Record, other: &Record) -> bool {
+    let (a, b) = (write.access, other.access);
+    let same_cube = a.cube == b.cube;
+
+    if same_cube && a.unit == b.unit {
+        return false;
+    }
+
+    match memory {
+        RaceMemory::Shared(_) => same_cube && write.epoch == other.epoch,
+        RaceMemory::Input(_) | RaceMemory::Output(_) => !same_cube || write.epoch == other.epoch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(memory: u32, index: u32, cube: u32, unit: u32, epoch: u32) -> [u32; RECORD_WORDS] {
+        [memory, index, cube, unit, epoch]
+    }
+
+    const SHARED_READ: u32 = SHARED << 1;
+    const SHARED_WRITE: u32 = SHARED << 1 | 1;
+    const OUTPUT_WRITE: u32 = 1 << 3 | OUTPUT << 1 | 1;
+
+    #[test]
+    fn shared_write_read_by_another_unit_races() {
+        let records = [
+            record(SHARED_WRITE, 4, 0, 1, 0),
+            record(SHARED_READ, 4, 0, 2, 0),
+        ]
+        .concat();
+
+        let races = find_races(&records);
+
+        assert_eq!(
+            races,
+            vec![DataRace {
+                memory: RaceMemory::Shared(0),
+                index: 4,
+                first: RaceAccess {
+                    cube: 0,
+                    unit: 1,
+                    write: true
+                },
+                second: RaceAccess {
+                    cube: 0,
+                    unit: 2,
+                    write: false
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn synchronized_or_same_unit_accesses_dont_race() {
+        let records = [
+            record(SHARED_WRITE, 4, 0, 1, 0),
+            record(SHARED_READ, 4, 0, 1, 0),
+            record(SHARED_READ, 4, 0, 2, 1),
+            // Shared memory isn't shared between cubes.
+            record(SHARED_READ, 4, 1, 2, 0),
+        ]
+        .concat();
+
+        assert!(find_races(&records).is_empty());
+    }
+
+    #[test]
+    fn global_writes_of_different_cubes_race() {
+        let records = [
+            record(OUTPUT_WRITE, 0, 0, 0, 0),
+            record(OUTPUT_WRITE, 0, 1, 0, 1),
+        ]
+        .concat();
+
+        let races = find_races(&records);
+
+        assert_eq!(races.len(), 1);
+        assert_eq!(races[0].memory, RaceMemory::Output(1));
+    }
+}
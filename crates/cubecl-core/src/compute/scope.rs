@@ -0,0 +1,245 @@
+use core::marker::PhantomData;
+
+use cubecl_runtime::{client::ComputeClient, server::Handle};
+
+use crate::{
+    calculate_cube_count_elemwise,
+    frontend::{ArrayArg, CubePrimitive, TensorArg},
+    ir::CubeDim,
+    prelude::CubeCount,
+    tensor_line_size, Runtime,
+};
+
+/// Prepare the arguments of kernels without unsafe code, inferring their vectorization from
+/// their shape and strides.
+///
+/// The scope is given to the closure, which launches the kernels with their `launch`
+/// function. The shape and the strides of every argument are checked against the size of its
+/// handle, so an argument with a wrong shape is rejected with an [ArgumentOutOfBounds] instead
+/// of letting the kernel read or write out of its buffer. Backends checking the bounds of the
+/// accesses with the lengths of the arguments rely on that check.
+///
+/// The buffers are borrowed for the lifetime of the scope, shared by the inputs and exclusively
+/// by the outputs, so a buffer can't be written by a kernel while another argument reads it.
+/// Writing in place has to go through [TensorArg::alias].
+///
+/// # Example
+///
+/// ```ignore
+/// launch_scope::<R, _>(&client, |scope| {
+///     let input = scope.input::<f32>(&input, &shape, &strides).unwrap();
+///     let output = scope.output::<f32>(&mut output, &shape, &strides).unwrap();
+///
+///     kernel::launch::<f32, R>(
+///         scope.client(),
+///         scope.cube_count(&output),
+///         scope.cube_dim(),
+///         input,
+///         output,
+///     );
+/// });
+/// ```
+pub fn launch_scope<'a, R: Runtime, Out>(
+    client: &'a ComputeClient<R::Server, R::Channel>,
+    launch: impl FnOnce(&LaunchScope<'a, R>) -> Out,
+) -> Out {
+    let scope = LaunchScope {
+        client,
+        cube_dim: CubeDim::suggested(client.properties().hardware_properties()),
+        runtime: PhantomData,
+    };
+
+    launch(&scope)
+}
+
+/// An argument of a [launch scope](launch_scope) spanning more bytes than its handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentOutOfBounds {
+    /// The number of bytes spanned by the argument.
+    pub required: u64,
+    /// The size of the handle, `None` when it wasn't created by a client and is unknown.
+    pub available: Option<u64>,
+}
+
+impl core::fmt::Display for ArgumentOutOfBounds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.available {
+            Some(available) => write!(
+                f,
+                "The argument spans {} bytes, but its handle only has {available}",
+                self.required
+            ),
+            None => write!(
+                f,
+                "The argument spans {} bytes, but the size of its handle is unknown",
+                self.required
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArgumentOutOfBounds {}
+
+/// The arguments of kernels launched in a [launch scope](launch_scope).
+pub struct LaunchScope<'a, R: Runtime> {
+    client: &'a ComputeClient<R::Server, R::Channel>,
+    cube_dim: CubeDim,
+    runtime: PhantomData<R>,
+}
+
+impl<'a, R: Runtime> LaunchScope<'a, R> {
+    /// The client the kernels are launched on.
+    pub fn client(&self) -> &'a ComputeClient<R::Server, R::Channel> {
+        self.client
+    }
+
+    /// The [cube dim](CubeDim::suggested) suited to the device.
+    pub fn cube_dim(&self) -> CubeDim {
+        self.cube_dim
+    }
+
+    /// The cube count covering every line of the given tensor with one unit, using the
+    /// [cube dim](Self::cube_dim) of the scope.
+    ///
+    /// # Panics
+    ///
+    /// If the tensor is an alias, which doesn't hold its shape.
+    pub fn cube_count(&self, tensor: &TensorArg<'a, R>) -> CubeCount {
+        let TensorArg::Handle {
+            handle,
+            vectorization_factor,
+        } = tensor
+        else {
+            panic!("The cube count can't be inferred from an aliased tensor");
+        };
+        let num_elems: usize = handle.shape.iter().product();
+
+        calculate_cube_count_elemwise(num_elems / *vectorization_factor as usize, self.cube_dim)
+    }
+
+    /// A tensor of elements `E` read by the kernel.
+    ///
+    /// # Errors
+    ///
+    /// If the shape and the strides span more bytes than the handle.
+    ///
+    /// # Panics
+    ///
+    /// If the shape and the strides don't have the same rank.
+    pub fn input<E: CubePrimitive>(
+        &self,
+        handle: &'a Handle,
+        shape: &'a [usize],
+        strides: &'a [usize],
+    ) -> Result<TensorArg<'a, R>, ArgumentOutOfBounds> {
+        self.tensor::<E>(handle, shape, strides)
+    }
+
+    /// A tensor of elements `E` written by the kernel.
+    ///
+    /// # Errors
+    ///
+    /// If the shape and the strides span more bytes than the handle.
+    ///
+    /// # Panics
+    ///
+    /// If the shape and the strides don't have the same rank.
+    pub fn output<E: CubePrimitive>(
+        &self,
+        handle: &'a mut Handle,
+        shape: &'a [usize],
+        strides: &'a [usize],
+    ) -> Result<TensorArg<'a, R>, ArgumentOutOfBounds> {
+        self.tensor::<E>(handle, shape, strides)
+    }
+
+    /// An array of `len` elements `E` read by the kernel.
+    ///
+    /// # Errors
+    ///
+    /// If the `len` elements span more bytes than the handle.
+    pub fn input_array<E: CubePrimitive>(
+        &self,
+        handle: &'a Handle,
+        len: usize,
+    ) -> Result<ArrayArg<'a, R>, ArgumentOutOfBounds> {
+        self.array::<E>(handle, len)
+    }
+
+    /// An array of `len` elements `E` written by the kernel.
+    ///
+    /// # Errors
+    ///
+    /// If the `len` elements span more bytes than the handle.
+    pub fn output_array<E: CubePrimitive>(
+        &self,
+        handle: &'a mut Handle,
+        len: usize,
+    ) -> Result<ArrayArg<'a, R>, ArgumentOutOfBounds> {
+        self.array::<E>(handle, len)
+    }
+
+    fn tensor<E: CubePrimitive>(
+        &self,
+        handle: &'a Handle,
+        shape: &'a [usize],
+        strides: &'a [usize],
+    ) -> Result<TensorArg<'a, R>, ArgumentOutOfBounds> {
+        assert_eq!(
+            shape.len(),
+            strides.len(),
+            "The shape and the strides of a tensor must have the same rank"
+        );
+        // The last element is at the sum of the strides of the last index of every dimension.
+        let num_elems = match shape.contains(&0) {
+            true => 0,
+            false => {
+                shape
+                    .iter()
+                    .zip(strides)
+                    .map(|(dim, stride)| (*dim as u64 - 1) * *stride as u64)
+                    .sum::<u64>()
+                    + 1
+            }
+        };
+        check_bounds::<E>(handle, num_elems)?;
+
+        let line_size = match shape.len() {
+            0 => 1,
+            rank => tensor_line_size(R::supported_line_sizes(), shape, strides, rank - 1),
+        };
+
+        // Safety: the shape and the strides were checked against the size of the handle.
+        Ok(unsafe { TensorArg::from_raw_parts_typed::<E>(handle, strides, shape, line_size) })
+    }
+
+    fn array<E: CubePrimitive>(
+        &self,
+        handle: &'a Handle,
+        len: usize,
+    ) -> Result<ArrayArg<'a, R>, ArgumentOutOfBounds> {
+        check_bounds::<E>(handle, len as u64)?;
+
+        let line_size = tensor_line_size(R::supported_line_sizes(), &[len], &[1], 0);
+
+        // Safety: the length was checked against the size of the handle.
+        Ok(unsafe { ArrayArg::from_raw_parts_typed::<E>(handle, len, line_size) })
+    }
+}
+
+fn check_bounds<E: CubePrimitive>(
+    handle: &Handle,
+    num_elems: u64,
+) -> Result<(), ArgumentOutOfBounds> {
+    let required = num_elems * E::as_elem().size() as u64;
+    let available = handle.size();
+
+    match available {
+        Some(available) if required <= available => Ok(()),
+        _ => Err(ArgumentOutOfBounds {
+            required,
+            available,
+        }),
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Mutex;
+
+use cubecl_runtime::{client::ComputeClient, ExecutionMode};
+
+use crate::{
+    compute::{CompiledKernel, CubeTask},
+    ir::{
+        Branch, ConstantScalarValue, KernelDefinition, Metadata, Operation, Operator, Scope,
+        UnaryOperator, Variable,
+    },
+    CompilationOptions, Compiler, Kernel, KernelId, Runtime,
+};
+
+/// The shape-specialized variants of the kernels launched with
+/// [launch_specialized](crate::compute::KernelLauncher::launch_specialized), bounded to a
+/// number of variants.
+///
+/// A specialized variant reads the shapes and strides of its tensors as constants instead of
+/// reading them from the metadata buffer, which lets the compiler simplify the indexing. That
+/// pays off for fixed-shape workloads, but every new shape compiles a new variant. When the
+/// cache is full, the least recently launched variant is evicted from the server, and compiled
+/// again if it's launched later.
+///
+/// A cache should only be used with a single client.
+#[derive(Debug)]
+pub struct SpecializationCache {
+    capacity: usize,
+    /// The ids of the cached variants, from the least to the most recently launched.
+    variants: Mutex<Vec<KernelId>>,
+}
+
+impl SpecializationCache {
+    /// Create a cache holding at most `capacity` specialized variants.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "The specialization cache can't be empty");
+
+        Self {
+            capacity,
+            variants: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The number of cached variants.
+    pub fn len(&self) -> usize {
+        self.variants.lock().unwrap().len()
+    }
+
+    /// Whether no variant is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Mark the variant as the most recently launched, evicting the least recently launched one
+    /// from the client when the cache is full.
+    pub(crate) fn register<R: Runtime>(
+        &self,
+        id: KernelId,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) {
+        let evicted = self.touch(id);
+
+        if let Some(id) = evicted {
+            client.evict(Box::new(EvictedKernel { id }));
+        }
+    }
+
+    fn touch(&self, id: KernelId) -> Option<KernelId> {
+        let mut variants = self.variants.lock().unwrap();
+
+        if let Some(index) = variants.iter().position(|variant| *variant == id) {
+            let id = variants.remove(index);
+            variants.push(id);
+            return None;
+        }
+
+        let evicted = (variants.len() == self.capacity).then(|| variants.remove(0));
+        variants.push(id);
+
+        evicted
+    }
+}
+
+/// The shape and strides of a tensor, as registered at launch.
+#[derive(new, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TensorShape {
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+}
+
+/// A kernel reading the shapes and strides of its tensors as constants.
+#[derive(new)]
+pub(crate) struct SpecializedKernel<K: Kernel> {
+    kernel: K,
+    /// The tensors of the launch, inputs first followed by outputs.
+    tensors: Vec<TensorShape>,
+}
+
+impl<K: Kernel> Kernel for SpecializedKernel<K> {
+    fn define(&self) -> KernelDefinition {
+        let mut definition = self.kernel.define();
+        let num_inputs = definition.inputs.len();
+        specialize_scope(&mut definition.body, &self.tensors, num_inputs);
+
+        definition
+    }
+
+    fn id(&self) -> KernelId {
+        self.kernel.id().specialize(self.tensors.clone())
+    }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.kernel.dynamic_shared_memory()
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.kernel.zero_initialize_shared_memory()
+    }
+}
+
+/// Only used to find the compiled variants to evict.
+struct EvictedKernel {
+    id: KernelId,
+}
+
+impl<C: Compiler> CubeTask<C> for EvictedKernel {
+    fn id(&self) -> KernelId {
+        self.id.clone()
+    }
+
+    fn compile(&self, _options: &CompilationOptions, _mode: ExecutionMode) -> CompiledKernel<C> {
+        unreachable!("Evicted kernels are never compiled")
+    }
+}
+
+/// Replace the metadata reads of the scope and its children by the values of the tensors.
+fn specialize_scope(scope: &mut Scope, tensors: &[TensorShape], num_inputs: usize) {
+    for operation in scope.operations.iter_mut() {
+        match operation {
+            Operation::Metadata(metadata) => {
+                if let Some(specialized) = specialize_metadata(metadata, tensors, num_inputs) {
+                    *operation = specialized;
+                }
+            }
+            Operation::Branch(branch) => match branch {
+                Branch::If(op) => specialize_scope(&mut op.scope, tensors, num_inputs),
+                Branch::IfElse(op) => {
+                    specialize_scope(&mut op.scope_if, tensors, num_inputs);
+                    specialize_scope(&mut op.scope_else, tensors, num_inputs);
+                }
+                Branch::Switch(op) => {
+                    specialize_scope(&mut op.scope_default, tensors, num_inputs);
+                    for (_, scope) in op.cases.iter_mut() {
+                        specialize_scope(scope, tensors, num_inputs);
+                    }
+                }
+                Branch::RangeLoop(op) => specialize_scope(&mut op.scope, tensors, num_inputs),
+                Branch::Loop(op) => specialize_scope(&mut op.scope, tensors, num_inputs),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// The assignment of the value read by the metadata operation, when it's known.
+///
+/// The length of arrays depends on their vectorization, so it's still read at runtime.
+fn specialize_metadata(
+    metadata: &Metadata,
+    tensors: &[TensorShape],
+    num_inputs: usize,
+) -> Option<Operation> {
+    let (dim, var, out, is_stride) = match metadata {
+        Metadata::Shape { dim, var, out } => (dim, var, out, false),
+        Metadata::Stride { dim, var, out } => (dim, var, out, true),
+        Metadata::Length { .. } => return None,
+    };
+    let position = match var {
+        Variable::GlobalInputArray { id, .. } => *id as usize,
+        Variable::GlobalOutputArray { id, .. } => num_inputs + *id as usize,
+        _ => return None,
+    };
+    let dim = dim.as_const()?.try_as_usize()?;
+    let tensor = tensors.get(position)?;
+    let value = match is_stride {
+        true => tensor.strides.get(dim)?,
+        false => tensor.shape.get(dim)?,
+    };
+
+    Some(Operation::Operator(Operator::Assign(UnaryOperator {
+        input: Variable::ConstantScalar(ConstantScalarValue::UInt(*value as u64)),
+        out: *out,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Elem, Item};
+
+    struct Dummy;
+
+    fn tensors() -> Vec<TensorShape> {
+        vec![
+            TensorShape::new(vec![4, 8], vec![8, 1]),
+            TensorShape::new(vec![8, 4], vec![1, 8]),
+        ]
+    }
+
+    #[test]
+    fn metadata_of_known_dims_is_replaced_by_constants() {
+        let mut scope = Scope::root();
+        let out = scope.create_local(Item::new(Elem::UInt));
+        scope.register(Metadata::Stride {
+            dim: 1u32.into(),
+            var: Variable::GlobalOutputArray {
+                id: 0,
+                item: Item::new(Elem::Float(crate::ir::FloatKind::F32)),
+            },
+            out,
+        });
+
+        specialize_scope(&mut scope, &tensors(), 1);
+
+        assert_eq!(
+            scope.operations[0],
+            Operation::Operator(Operator::Assign(UnaryOperator {
+                input: Variable::ConstantScalar(ConstantScalarValue::UInt(8)),
+                out,
+            }))
+        );
+    }
+
+    #[test]
+    fn metadata_of_runtime_dims_is_kept() {
+        let mut scope = Scope::root();
+        let dim = scope.create_local(Item::new(Elem::UInt));
+        let out = scope.create_local(Item::new(Elem::UInt));
+        let shape = Metadata::Shape {
+            dim,
+            var: Variable::GlobalInputArray {
+                id: 0,
+                item: Item::new(Elem::Float(crate::ir::FloatKind::F32)),
+            },
+            out,
+        };
+        scope.register(shape.clone());
+
+        specialize_scope(&mut scope, &tensors(), 1);
+
+        assert_eq!(scope.operations[0], Operation::Metadata(shape));
+    }
+
+    #[test]
+    fn cache_evicts_the_least_recently_launched_variant() {
+        let cache = SpecializationCache::new(2);
+        let variant = |shape: usize| KernelId::new::<Dummy>().specialize(shape);
+
+        assert_eq!(cache.touch(variant(1)), None);
+        assert_eq!(cache.touch(variant(2)), None);
+        assert_eq!(cache.touch(variant(1)), None);
+        assert_eq!(cache.touch(variant(3)), Some(variant(2)));
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -1,9 +1,12 @@
+use core::cell::RefCell;
+
 use num_traits::NumCast;
 
-use crate::ir::{Branch, If, IfElse, Item, Loop, RangeLoop};
+use crate::ir::{Branch, Elem, If, IfElse, Item, Loop, RangeLoop};
 use crate::{
     frontend::{CubeContext, ExpandElement},
     ir::Switch,
+    unexpanded,
 };
 
 use super::{assign, CubePrimitive, CubeType, ExpandElementTyped, Int, Numeric};
@@ -249,6 +252,44 @@ pub mod range_stepped {
     }
 }
 
+/// Grid-stride range, the positions up to `end` handled by the current unit when every unit
+/// launched steps over the others. Equivalent to:
+///
+/// ```ignore
+/// range_stepped(ABSOLUTE_POS, end, CUBE_COUNT * CUBE_DIM)
+/// ```
+///
+/// Lets a kernel launched with fewer units than positions, e.g. with
+/// [calculate_cube_count_persistent](crate::calculate_cube_count_persistent), cover all of them.
+#[allow(unused_variables)]
+pub fn grid_stride_range(end: u32) -> core::ops::Range<u32> {
+    unexpanded!()
+}
+
+pub mod grid_stride_range {
+    use crate::{
+        frontend::{mul, ABSOLUTE_POS, CUBE_COUNT, CUBE_DIM},
+        prelude::{CubeContext, ExpandElementTyped},
+    };
+
+    use super::SteppedRangeExpand;
+
+    pub fn expand(
+        context: &mut CubeContext,
+        end: ExpandElementTyped<u32>,
+    ) -> SteppedRangeExpand<u32> {
+        let units = CUBE_COUNT::expand(context);
+        let cube_dim = CUBE_DIM::expand(context);
+
+        SteppedRangeExpand {
+            start: ABSOLUTE_POS::expand(context),
+            end,
+            step: mul::expand(context, units, cube_dim),
+            inclusive: false,
+        }
+    }
+}
+
 pub fn for_expand<I: Numeric>(
     context: &mut CubeContext,
     range: impl Iterable<I>,
@@ -517,6 +558,10 @@ pub fn break_expand(context: &mut CubeContext) {
     context.register(Branch::Break);
 }
 
+pub fn continue_expand(context: &mut CubeContext) {
+    context.register(Branch::Continue);
+}
+
 pub fn return_expand(context: &mut CubeContext) {
     context.register(Branch::Return);
 }
@@ -530,3 +575,58 @@ pub fn loop_expand(context: &mut CubeContext, mut block: impl FnMut(&mut CubeCon
         scope: inside_loop.into_scope(),
     })));
 }
+
+/// The value of a cube function returning early with `return value`.
+///
+/// The body of the function is expanded in a loop running once, so returning assigns the value
+/// and breaks out of it. When a return is nested in another loop or in a switch, a flag is set
+/// as well so that the enclosing loops can break in turn.
+pub struct ReturnExpand<C: CubePrimitive> {
+    value: RefCell<Option<ExpandElementTyped<C>>>,
+    flag: Option<ExpandElementTyped<bool>>,
+}
+
+impl<C: CubePrimitive> ReturnExpand<C> {
+    pub fn new(context: &mut CubeContext, nested: bool) -> Self {
+        let flag = nested.then(|| {
+            let flag: ExpandElementTyped<bool> =
+                context.create_local_variable(Item::new(Elem::Bool)).into();
+            assign::expand(context, false.into(), flag.clone());
+            flag
+        });
+
+        Self {
+            value: RefCell::new(None),
+            flag,
+        }
+    }
+
+    /// Return the value from the function.
+    pub fn expand_return(&self, context: &mut CubeContext, value: ExpandElementTyped<C>) {
+        // The variable is created with the item of the first value, which may be vectorized.
+        let out = self
+            .value
+            .borrow_mut()
+            .get_or_insert_with(|| context.create_local_variable(value.expand.item()).into())
+            .clone();
+        assign::expand(context, value, out);
+
+        if let Some(flag) = &self.flag {
+            assign::expand(context, true.into(), flag.clone());
+        }
+        break_expand(context);
+    }
+
+    /// Break out of the enclosing loop when a nested loop or switch returned.
+    pub fn expand_propagate(&self, context: &mut CubeContext) {
+        if let Some(flag) = &self.flag {
+            if_expand(context, flag.expand.clone(), break_expand);
+        }
+    }
+
+    pub fn finish(self) -> ExpandElementTyped<C> {
+        self.value
+            .into_inner()
+            .expect("The function should return a value")
+    }
+}
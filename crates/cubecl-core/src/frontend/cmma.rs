@@ -64,6 +64,9 @@ pub use ir::{MatrixIdent, MatrixLayout};
 ///
 /// They can either be in a [row major](MatrixLayout::RowMajor) or a
 /// [column major](MatrixLayout::ColMajor) format.
+///
+/// The `f32` [A](MatrixIdent::A) and [B](MatrixIdent::B) matrices are executed in TF32 on CUDA,
+/// trading the precision of the inputs for tensor core throughput.
 #[derive(Copy, Clone)]
 pub struct Matrix<C: CubeType> {
     _c: PhantomData<C>,
@@ -107,7 +110,9 @@ impl<C: CubePrimitive> Matrix<C> {
     /// * [MatrixIdent::B] Shape => (K, N)
     /// * [MatrixIdent::Accumulator] Shape => (M, N)
     ///
-    /// Not all shapes are supported, and the permitted shapes depend on the element type.
+    /// Not all shapes are supported, and the permitted shapes depend on the element type. The
+    /// configurations supported by a device are listed by [MmaConfig::supported](crate::MmaConfig::supported),
+    /// and [launches](crate::prelude::KernelLauncher::launch) reject the others.
     ///
     /// Refer to [nvidia documentation](https://docs.nvidia.com/cuda/cuda-c-programming-guide/index.html#element-types-and-matrix-sizes).
     #[allow(unused_variables)]
@@ -131,7 +136,9 @@ impl<C: CubePrimitive> Matrix<C> {
     /// * [MatrixIdent::B] Shape => (K, N)
     /// * [MatrixIdent::Accumulator] Shape => (M, N)
     ///
-    /// Not all shapes are supported, and the permitted shapes depend on the element type.
+    /// Not all shapes are supported, and the permitted shapes depend on the element type. The
+    /// configurations supported by a device are listed by [MmaConfig::supported](crate::MmaConfig::supported),
+    /// and [launches](crate::prelude::KernelLauncher::launch) reject the others.
     ///
     /// Refer to [nvidia documentation](https://docs.nvidia.com/cuda/cuda-c-programming-guide/index.html#element-types-and-matrix-sizes).
     #[allow(unused_variables)]
@@ -156,7 +163,9 @@ impl<C: CubePrimitive> Matrix<C> {
     /// * [MatrixIdent::B] Shape => (K, N)
     /// * [MatrixIdent::Accumulator] Shape => (M, N)
     ///
-    /// Not all shapes are supported, and the permitted shapes depend on the element type.
+    /// Not all shapes are supported, and the permitted shapes depend on the element type. The
+    /// configurations supported by a device are listed by [MmaConfig::supported](crate::MmaConfig::supported),
+    /// and [launches](crate::prelude::KernelLauncher::launch) reject the others.
     ///
     /// Refer to [nvidia documentation](https://docs.nvidia.com/cuda/cuda-c-programming-guide/index.html#element-types-and-matrix-sizes).
     #[allow(unused_variables)]
@@ -182,9 +191,15 @@ impl<C: CubePrimitive> Matrix<C> {
     ) -> MatrixExpand {
         let elem = context.create_matrix(ir::Matrix {
             ident,
-            m: m.constant().unwrap().as_u32() as u8,
-            n: n.constant().unwrap().as_u32() as u8,
-            k: k.constant().unwrap().as_u32() as u8,
+            m: m.constant()
+                .expect("The shape of a matrix must be known at compile time")
+                .as_u32() as u8,
+            n: n.constant()
+                .expect("The shape of a matrix must be known at compile time")
+                .as_u32() as u8,
+            k: k.constant()
+                .expect("The shape of a matrix must be known at compile time")
+                .as_u32() as u8,
             elem: C::as_elem(),
             layout,
         });
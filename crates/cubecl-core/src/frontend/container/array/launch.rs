@@ -2,7 +2,7 @@ use std::{marker::PhantomData, num::NonZero};
 
 use crate::{
     compute::{KernelBuilder, KernelLauncher},
-    ir::{Item, Vectorization},
+    ir::{Elem, Item, Vectorization},
     prelude::{
         ArgSettings, CubePrimitive, ExpandElementTyped, LaunchArg, LaunchArgExpand, TensorHandleRef,
     },
@@ -21,6 +21,7 @@ pub struct ArrayCompilationArg {
 pub struct ArrayHandleRef<'a, R: Runtime> {
     pub handle: &'a cubecl_runtime::server::Handle,
     pub(crate) length: [usize; 1],
+    pub(crate) elem: Option<Elem>,
     runtime: PhantomData<R>,
 }
 
@@ -93,6 +94,27 @@ impl<'a, R: Runtime> ArrayArg<'a, R> {
     }
 }
 
+impl<'a, R: Runtime> ArrayArg<'a, R> {
+    /// Create a new array argument holding elements of type `E`.
+    ///
+    /// The element type is validated against the kernel when using a
+    /// [checked launch](KernelLauncher::launch_checked).
+    ///
+    /// # Safety
+    ///
+    /// Specifying the wrong length may lead to out-of-bounds reads and writes.
+    pub unsafe fn from_raw_parts_typed<E: CubePrimitive>(
+        handle: &'a cubecl_runtime::server::Handle,
+        length: usize,
+        vectorization_factor: u8,
+    ) -> Self {
+        ArrayArg::Handle {
+            handle: ArrayHandleRef::from_raw_parts(handle, length).with_elem(E::as_elem()),
+            vectorization_factor,
+        }
+    }
+}
+
 impl<'a, R: Runtime> ArrayHandleRef<'a, R> {
     /// Create a new array handle reference.
     ///
@@ -106,10 +128,17 @@ impl<'a, R: Runtime> ArrayHandleRef<'a, R> {
         Self {
             handle,
             length: [length],
+            elem: None,
             runtime: PhantomData,
         }
     }
 
+    /// Specify the element type stored in the buffer.
+    pub fn with_elem(mut self, elem: Elem) -> Self {
+        self.elem = Some(elem);
+        self
+    }
+
     /// Return the handle as a tensor instead of an array.
     pub fn as_tensor(&self) -> TensorHandleRef<'_, R> {
         let shape = &self.length;
@@ -118,6 +147,7 @@ impl<'a, R: Runtime> ArrayHandleRef<'a, R> {
             handle: self.handle,
             strides: &[1],
             shape,
+            elem: self.elem,
             runtime: PhantomData,
         }
     }
@@ -166,6 +166,101 @@ mod size {
     }
 }
 
+/// Module that contains the implementation details of the swizzle functions.
+mod swizzle {
+    use super::*;
+    use crate::ir::{Operator, SwizzleOperator};
+
+    impl<P: CubePrimitive> Line<P> {
+        /// Create a new line from the given components of this line, in order.
+        ///
+        /// Components are known at comptime and may be repeated. The size of the new line is the
+        /// number of components, and a single component gives a line of size 1.
+        ///
+        /// ```rust, ignore
+        /// // Same as `line.zyx` in WGSL.
+        /// let reversed = line.swizzle([2, 1, 0]);
+        /// let first = line.swizzle([0]);
+        /// ```
+        #[allow(unused_variables)]
+        pub fn swizzle<const N: usize>(&self, components: [u32; N]) -> Self {
+            unexpanded!()
+        }
+
+        /// Write the elements of the value to the given components of this line, in order.
+        ///
+        /// The value must either have one element per component or be of size 1, in which case
+        /// it's written to every component.
+        ///
+        /// ```rust, ignore
+        /// // Same as `line.x = value.y; line.z = value.x;`.
+        /// line.set_swizzle([0, 2], value.swizzle([1, 0]));
+        /// ```
+        #[allow(unused_variables)]
+        pub fn set_swizzle<const N: usize>(&mut self, components: [u32; N], value: Self) {
+            unexpanded!()
+        }
+    }
+
+    impl<P: CubePrimitive> ExpandElementTyped<Line<P>> {
+        /// Expand method of [swizzle](Line::swizzle).
+        pub fn __expand_swizzle_method<const N: usize>(
+            self,
+            context: &mut CubeContext,
+            components: [u32; N],
+        ) -> Self {
+            check_components(self.size(), &components);
+
+            let vectorization = NonZero::new(N as u8).filter(|size| size.get() > 1);
+            let output =
+                context.create_local_binding(Item::vectorized(P::as_elem(), vectorization));
+
+            context.register(Operator::Swizzle(SwizzleOperator {
+                input: *self.expand,
+                components: components.to_vec(),
+                out: *output,
+            }));
+
+            output.into()
+        }
+
+        /// Expand method of [set_swizzle](Line::set_swizzle).
+        pub fn __expand_set_swizzle_method<const N: usize>(
+            self,
+            context: &mut CubeContext,
+            components: [u32; N],
+            value: Self,
+        ) {
+            check_components(self.size(), &components);
+            assert!(
+                (1..N).all(|i| !components[..i].contains(&components[i])),
+                "Can't write the same component twice in {components:?}"
+            );
+            assert!(
+                value.size() == 1 || value.size() as usize == N,
+                "Can't write a line of size {} to {N} components",
+                value.size()
+            );
+
+            context.register(Operator::SwizzleAssign(SwizzleOperator {
+                input: *value.expand,
+                components: components.to_vec(),
+                out: *self.expand,
+            }));
+        }
+    }
+
+    fn check_components(size: u32, components: &[u32]) {
+        assert!(
+            !components.is_empty(),
+            "A swizzle needs at least one component"
+        );
+        if let Some(component) = components.iter().find(|component| **component >= size) {
+            panic!("Component {component} is out of bounds for a line of size {size}");
+        }
+    }
+}
+
 impl<P: CubePrimitive> CubeType for Line<P> {
     type ExpandType = ExpandElementTyped<Self>;
 }
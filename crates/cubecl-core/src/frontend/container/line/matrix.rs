@@ -0,0 +1,198 @@
+use std::num::NonZero;
+
+use crate::frontend::{binary_expand, CubeContext, ExpandElement, ExpandElementTyped, Float};
+use crate::ir::{BinaryOperator, Item, LineInitOperator, Operator, SwizzleOperator, UnaryOperator};
+use crate::unexpanded;
+
+use super::Line;
+
+impl<F: Float> Line<F> {
+    /// Multiply this matrix by a matrix or a vector.
+    ///
+    /// Lines of 4, 9 or 16 elements can be used as 2x2, 3x3 or 4x4 matrices stored in
+    /// column-major order. The right hand side is either a matrix of the same size or a vector
+    /// with one element per row, and the output has the same size as the right hand side.
+    ///
+    /// ```rust, ignore
+    /// let transformed = transform.matrix_mul(position);
+    /// ```
+    #[allow(unused_variables)]
+    pub fn matrix_mul(self, rhs: Self) -> Self {
+        unexpanded!()
+    }
+
+    /// Transpose this matrix.
+    pub fn transpose(self) -> Self {
+        unexpanded!()
+    }
+
+    /// The determinant of this matrix.
+    pub fn determinant(self) -> F {
+        unexpanded!()
+    }
+}
+
+impl<F: Float> ExpandElementTyped<Line<F>> {
+    /// Expand method of [matrix_mul](Line::matrix_mul).
+    pub fn __expand_matrix_mul_method(self, context: &mut CubeContext, rhs: Self) -> Self {
+        let n = matrix_size(self.size());
+        let rhs_size = rhs.size();
+        assert!(
+            rhs_size == n * n || rhs_size == n,
+            "Can't multiply a {n}x{n} matrix by a line of size {rhs_size}"
+        );
+
+        if (context.matrix_ops)(n) {
+            let out = context.create_local_binding(line_item::<F>(rhs_size));
+            context.register(Operator::MatrixMul(BinaryOperator {
+                lhs: *self.expand,
+                rhs: *rhs.expand,
+                out: *out,
+            }));
+            return out.into();
+        }
+
+        let lhs = components(context, &self.expand, n * n);
+        let rhs = components(context, &rhs.expand, rhs_size);
+        let columns = rhs_size / n;
+
+        let mut values = Vec::with_capacity(rhs_size as usize);
+        for column in 0..columns {
+            for row in 0..n {
+                let products = (0..n).map(|k| {
+                    let lhs = lhs[(k * n + row) as usize].clone();
+                    let rhs = rhs[(column * n + k) as usize].clone();
+                    binary_expand(context, lhs, rhs, Operator::Mul)
+                });
+                let products = products.collect::<Vec<_>>();
+                let sum = products
+                    .into_iter()
+                    .reduce(|sum, product| binary_expand(context, sum, product, Operator::Add))
+                    .unwrap();
+                values.push(sum);
+            }
+        }
+
+        init_line::<F>(context, values).into()
+    }
+
+    /// Expand method of [transpose](Line::transpose).
+    pub fn __expand_transpose_method(self, context: &mut CubeContext) -> Self {
+        let n = matrix_size(self.size());
+        let out = context.create_local_binding(line_item::<F>(n * n));
+
+        if (context.matrix_ops)(n) {
+            context.register(Operator::Transpose(UnaryOperator {
+                input: *self.expand,
+                out: *out,
+            }));
+        } else {
+            let components = (0..n)
+                .flat_map(|column| (0..n).map(move |row| row * n + column))
+                .collect();
+            context.register(Operator::Swizzle(SwizzleOperator {
+                input: *self.expand,
+                components,
+                out: *out,
+            }));
+        }
+
+        out.into()
+    }
+
+    /// Expand method of [determinant](Line::determinant).
+    pub fn __expand_determinant_method(self, context: &mut CubeContext) -> ExpandElementTyped<F> {
+        let n = matrix_size(self.size());
+
+        if (context.matrix_ops)(n) {
+            let out = context.create_local_binding(Item::new(F::as_elem()));
+            context.register(Operator::Determinant(UnaryOperator {
+                input: *self.expand,
+                out: *out,
+            }));
+            return out.into();
+        }
+
+        let elements = components(context, &self.expand, n * n);
+        let rows = (0..n).collect::<Vec<_>>();
+        let columns = (0..n).collect::<Vec<_>>();
+
+        determinant(context, &elements, n, &rows, &columns).into()
+    }
+}
+
+/// The number of rows and columns of a square matrix stored in a line of the given size.
+fn matrix_size(size: u32) -> u32 {
+    match size {
+        4 => 2,
+        9 => 3,
+        16 => 4,
+        _ => panic!("A line of size {size} isn't a matrix, only sizes 4, 9 and 16 are supported"),
+    }
+}
+
+fn line_item<F: Float>(size: u32) -> Item {
+    Item::vectorized(
+        F::as_elem(),
+        NonZero::new(size as u8).filter(|size| size.get() > 1),
+    )
+}
+
+/// Extract every element of a line into its own scalar.
+fn components(context: &mut CubeContext, line: &ExpandElement, size: u32) -> Vec<ExpandElement> {
+    (0..size)
+        .map(|component| {
+            let out = context.create_local_binding(Item::new(line.item().elem()));
+            context.register(Operator::Swizzle(SwizzleOperator {
+                input: **line,
+                components: vec![component],
+                out: *out,
+            }));
+            out
+        })
+        .collect()
+}
+
+fn init_line<F: Float>(context: &mut CubeContext, values: Vec<ExpandElement>) -> ExpandElement {
+    let out = context.create_local_binding(line_item::<F>(values.len() as u32));
+    context.register(Operator::InitLine(LineInitOperator {
+        inputs: values.iter().map(|value| **value).collect(),
+        out: *out,
+    }));
+    out
+}
+
+/// The determinant of the sub-matrix with the given rows and columns, using a cofactor expansion
+/// along its first column.
+fn determinant(
+    context: &mut CubeContext,
+    elements: &[ExpandElement],
+    n: u32,
+    rows: &[u32],
+    columns: &[u32],
+) -> ExpandElement {
+    let element = |row: u32, column: u32| elements[(column * n + row) as usize].clone();
+
+    if let [row] = rows {
+        return element(*row, columns[0]);
+    }
+
+    let mut result: Option<ExpandElement> = None;
+    for (i, row) in rows.iter().enumerate() {
+        let minor_rows = rows
+            .iter()
+            .filter(|other| *other != row)
+            .copied()
+            .collect::<Vec<_>>();
+        let minor = determinant(context, elements, n, &minor_rows, &columns[1..]);
+        let term = binary_expand(context, element(*row, columns[0]), minor, Operator::Mul);
+
+        result = Some(match result {
+            None => term,
+            Some(result) if i % 2 == 0 => binary_expand(context, result, term, Operator::Add),
+            Some(result) => binary_expand(context, result, term, Operator::Sub),
+        });
+    }
+
+    result.unwrap()
+}
@@ -1,4 +1,5 @@
 mod base;
+mod matrix;
 mod ops;
 
 pub use base::*;
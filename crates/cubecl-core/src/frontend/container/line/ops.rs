@@ -2,9 +2,10 @@ use num_traits::{NumCast, ToPrimitive};
 
 use crate::{
     frontend::{
-        Abs, Ceil, Clamp, Cos, CubeIndex, CubeIndexMut, CubePrimitive, Erf, Exp,
-        ExpandElementTyped, Floor, Log, Log1p, Max, Min, Powf, Recip, Remainder, Round, Sin, Sqrt,
-        Tanh,
+        Abs, BitManipulation, Ceil, Clamp, Cos, CubeIndex, CubeIndexMut, CubePrimitive, Erf, Erfc,
+        Exp, ExpandElementTyped, Expm1, Floor, Lgamma, Log, Log1p, Max, Min, Powf, Recip,
+        Remainder, Round, SaturatingAdd, SaturatingSub, Sin, Sqrt, Tanh, Tgamma, WrappingAdd,
+        WrappingMul, WrappingSub,
     },
     unexpanded,
 };
@@ -122,10 +123,20 @@ where
 impl<P: CubePrimitive + Abs> Abs for Line<P> {}
 impl<P: CubePrimitive + Max> Max for Line<P> {}
 impl<P: CubePrimitive + Min> Min for Line<P> {}
+impl<P: CubePrimitive + SaturatingAdd> SaturatingAdd for Line<P> {}
+impl<P: CubePrimitive + SaturatingSub> SaturatingSub for Line<P> {}
+impl<P: CubePrimitive + WrappingAdd> WrappingAdd for Line<P> {}
+impl<P: CubePrimitive + WrappingSub> WrappingSub for Line<P> {}
+impl<P: CubePrimitive + WrappingMul> WrappingMul for Line<P> {}
 impl<P: CubePrimitive + Clamp> Clamp for Line<P> {}
+impl<P: CubePrimitive + BitManipulation> BitManipulation for Line<P> {}
 impl<P: CubePrimitive + Log> Log for Line<P> {}
 impl<P: CubePrimitive + Log1p> Log1p for Line<P> {}
 impl<P: CubePrimitive + Erf> Erf for Line<P> {}
+impl<P: CubePrimitive + Erfc> Erfc for Line<P> {}
+impl<P: CubePrimitive + Expm1> Expm1 for Line<P> {}
+impl<P: CubePrimitive + Tgamma> Tgamma for Line<P> {}
+impl<P: CubePrimitive + Lgamma> Lgamma for Line<P> {}
 impl<P: CubePrimitive + Exp> Exp for Line<P> {}
 impl<P: CubePrimitive + Powf> Powf for Line<P> {}
 impl<P: CubePrimitive + Sqrt> Sqrt for Line<P> {}
@@ -2,11 +2,12 @@ use std::{marker::PhantomData, num::NonZero};
 
 use crate::{
     frontend::{
-        indexation::Index, CubeContext, CubePrimitive, CubeType, ExpandElementTyped, Init,
-        IntoRuntime,
+        indexation::Index, CubeContext, CubePrimitive, CubeType, ExpandElement, ExpandElementTyped,
+        Init, IntoRuntime, SizedContainer,
     },
-    ir::Item,
+    ir::{Item, Variable},
     prelude::Line,
+    unexpanded,
 };
 
 #[derive(Clone, Copy)]
@@ -85,6 +86,76 @@ impl<T: CubePrimitive + Clone> SharedMemory<T> {
         let var = context.create_shared(Item::new(T::as_elem()), size);
         ExpandElementTyped::new(var)
     }
+
+    /// Create a shared memory whose size is chosen at launch with
+    /// [KernelSettings::dynamic_shared_memory](crate::KernelSettings::dynamic_shared_memory), or
+    /// with the size parameter of the launch functions of `#[cube(launch, dynamic_shared_memory)]`.
+    pub fn new_dynamic() -> Self {
+        SharedMemory { _val: PhantomData }
+    }
+
+    pub fn __expand_new_dynamic(context: &mut CubeContext) -> <Self as CubeType>::ExpandType {
+        let var = context.create_shared_dynamic(Item::new(T::as_elem()));
+        ExpandElementTyped::new(var)
+    }
+}
+
+/// Module that contains the implementation details of the metadata functions.
+mod metadata {
+    use super::*;
+
+    impl<T: CubeType> SharedMemory<T> {
+        /// Obtain the length of the shared memory, which is known when compiling the kernel.
+        #[allow(clippy::len_without_is_empty)]
+        pub fn len(&self) -> u32 {
+            unexpanded!()
+        }
+
+        /// Iterate over the elements of the shared memory, as in `for value in shared.iter()`.
+        pub fn iter(&self) -> &Self {
+            unexpanded!()
+        }
+    }
+
+    impl<T: CubePrimitive> ExpandElementTyped<SharedMemory<T>> {
+        // Expand method of [len](SharedMemory::len).
+        pub fn __expand_len_method(self, _context: &mut CubeContext) -> ExpandElementTyped<u32> {
+            ExpandElementTyped::from_lit(length(&self.expand))
+        }
+
+        // Expand method of [iter](SharedMemory::iter).
+        pub fn __expand_iter_method(self, _context: &mut CubeContext) -> Self {
+            self
+        }
+    }
+}
+
+/// The length of the shared memory, which isn't known when compiling for dynamic shared memory.
+fn length(shared: &ExpandElement) -> u32 {
+    match **shared {
+        Variable::SharedMemory {
+            length,
+            dynamic: false,
+            ..
+        } => length,
+        _ => panic!("The length of dynamic shared memory isn't known when compiling the kernel"),
+    }
+}
+
+impl<T: CubePrimitive> SizedContainer for SharedMemory<T> {
+    type Item = T;
+
+    fn len(val: &ExpandElement, _context: &mut CubeContext) -> ExpandElement {
+        ExpandElementTyped::<u32>::from_lit(length(val)).expand
+    }
+}
+
+impl<T: CubeType> Iterator for &SharedMemory<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unexpanded!()
+    }
 }
 
 /// Module that contains the implementation details of the index functions.
@@ -92,7 +163,6 @@ mod indexation {
     use crate::{
         ir::{BinaryOperator, Operator},
         prelude::{CubeIndex, CubeIndexMut},
-        unexpanded,
     };
 
     use super::*;
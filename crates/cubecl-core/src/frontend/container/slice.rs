@@ -36,6 +36,11 @@ mod metadata {
             unexpanded!()
         }
 
+        /// Iterate over the elements of the slice, as in `for value in slice.iter()`.
+        pub fn iter(&self) -> &Self {
+            unexpanded!()
+        }
+
         /// Returns the same slice, but with lines of length 1.
         pub fn as_aligned(&self) -> Slice<'a, Line<E>>
         where
@@ -52,6 +57,11 @@ mod metadata {
             unexpanded!()
         }
 
+        /// Iterate over the elements of the slice, as in `for value in slice.iter()`.
+        pub fn iter(&self) -> &Self {
+            unexpanded!()
+        }
+
         /// Returns the same slice, but with lines of length 1.
         pub fn as_aligned(&self) -> SliceMut<'a, Line<E>>
         where
@@ -68,6 +78,11 @@ mod metadata {
             elem.__expand_len_method(context)
         }
 
+        // Expand method of [iter](Slice::iter).
+        pub fn __expand_iter_method(self, _context: &mut CubeContext) -> Self {
+            self
+        }
+
         // Expand method of [len](Slice::as_aligned).
         pub fn __expand_as_aligned_method(
             self,
@@ -87,6 +102,11 @@ mod metadata {
             elem.__expand_len_method(context)
         }
 
+        // Expand method of [iter](SliceMut::iter).
+        pub fn __expand_iter_method(self, _context: &mut CubeContext) -> Self {
+            self
+        }
+
         // Expand method of [len](SliceMut::as_aligned).
         pub fn __expand_as_aligned_method(
             self,
@@ -236,6 +256,25 @@ impl<'a, T: CubeType> Iterator for &Slice<'a, T> {
     }
 }
 
+impl<'a, C: CubeType<ExpandType = ExpandElementTyped<C>>> SizedContainer for SliceMut<'a, C> {
+    type Item = C;
+}
+
+impl<'a, T: CubeType> Iterator for SliceMut<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unexpanded!()
+    }
+}
+impl<'a, T: CubeType> Iterator for &SliceMut<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unexpanded!()
+    }
+}
+
 pub trait SliceOperator<E: CubeType>: CubeType<ExpandType = Self::Expand> {
     type Expand: SliceOperatorExpand<E>;
 
@@ -0,0 +1,262 @@
+use std::marker::PhantomData;
+use std::num::NonZero;
+
+use crate::frontend::{
+    CubeContext, CubeType, ExpandElement, ExpandElementBaseInit, ExpandElementTyped,
+};
+use crate::ir::{BinaryOperator, Elem, Item, Metadata, Operator, UnaryOperator, Variable};
+use crate::unexpanded;
+
+use super::{StructFieldInfo, StructLayout, STRUCT_WORD_SIZE};
+
+/// A [cube type](CubeType) that can be stored in a [struct array](StructArray).
+///
+/// Usually implemented with `#[derive(CubeType)]` and the `#[expand(buffer)]` attribute, where
+/// fields are 32-bit primitives or [lines](crate::prelude::Line) annotated with their size.
+///
+/// ```rust, ignore
+/// #[derive(CubeType, Clone, Copy)]
+/// #[expand(buffer)]
+/// pub struct Particle {
+///     #[expand(line_size = 3)]
+///     pos: Line<f32>,
+///     #[expand(line_size = 3)]
+///     vel: Line<f32>,
+/// }
+/// ```
+pub trait CubeStruct: CubeType + Send + Sync + 'static {
+    /// The fields of the struct, in declaration order.
+    fn fields() -> Vec<StructFieldInfo>;
+
+    /// Create the expanded struct from its expanded fields, in declaration order.
+    fn __expand_from_fields(fields: Vec<ExpandElement>) -> Self::ExpandType;
+
+    /// Split the expanded struct into its expanded fields, in declaration order.
+    fn __expand_into_fields(value: Self::ExpandType) -> Vec<ExpandElement>;
+
+    /// The memory layout of the struct in a global buffer.
+    fn layout() -> StructLayout {
+        StructLayout::new(Self::fields())
+    }
+}
+
+/// A contiguous array of [structs](CubeStruct) stored in a global buffer.
+///
+/// The structs follow the [layout](StructLayout) of a WGSL `array<T>` of the equivalent struct
+/// declaration, so the same host data can be shared with hand-written WGSL shaders. The buffer is
+/// accessed as 32-bit words, and reading or writing a struct is lowered to reading or writing each
+/// of its fields.
+///
+/// # Limitations
+///
+/// No struct type is declared in the generated shaders, WGSL included: the buffer is bound as an
+/// `array<u32>`, and each field is bitcasted from its words. This keeps the layout identical on
+/// every backend, where a C++ or SPIR-V struct would follow other alignment rules. As a
+/// consequence:
+///
+/// - Fields must be 32-bit primitives, or [lines](crate::prelude::Line) of 1 to 4 of them. Other
+///   fields are rejected with a panic when the kernel is expanded.
+/// - Comptime fields, nested structs and arrays can't be stored, the derive rejects comptime
+///   fields at compile time.
+/// - A struct isn't read or written at once, so concurrent accesses to the same struct can
+///   observe a mix of old and new fields.
+pub struct StructArray<T: CubeStruct> {
+    _val: PhantomData<T>,
+}
+
+impl<T: CubeStruct> CubeType for StructArray<T> {
+    type ExpandType = ExpandElementTyped<StructArray<T>>;
+}
+
+impl<T: CubeStruct> CubeType for &StructArray<T> {
+    type ExpandType = ExpandElementTyped<StructArray<T>>;
+}
+
+impl<T: CubeStruct> ExpandElementBaseInit for StructArray<T> {
+    fn init_elem(_context: &mut CubeContext, elem: ExpandElement) -> ExpandElement {
+        // The type can't be deeply cloned/copied.
+        elem
+    }
+}
+
+impl<T: CubeStruct> StructArray<T> {
+    /// Obtain the number of structs in the array.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u32 {
+        unexpanded!()
+    }
+
+    /// Read the struct at the given index.
+    #[allow(unused_variables)]
+    pub fn read(&self, index: u32) -> T {
+        unexpanded!()
+    }
+
+    /// Write the struct at the given index.
+    #[allow(unused_variables)]
+    pub fn write(&mut self, index: u32, value: T) {
+        unexpanded!()
+    }
+}
+
+impl<T: CubeStruct> ExpandElementTyped<StructArray<T>> {
+    /// Expand method of [len](StructArray::len).
+    pub fn __expand_len_method(self, context: &mut CubeContext) -> ExpandElementTyped<u32> {
+        let words = context.create_local_binding(Item::new(Elem::UInt));
+        context.register(Metadata::Length {
+            var: self.expand.into(),
+            out: words.clone().into(),
+        });
+
+        let stride = word_constant(T::layout().stride() / STRUCT_WORD_SIZE);
+        let out = context.create_local_binding(Item::new(Elem::UInt));
+        context.register(Operator::Div(BinaryOperator {
+            lhs: words.into(),
+            rhs: stride,
+            out: out.clone().into(),
+        }));
+
+        out.into()
+    }
+
+    /// Expand method of [read](StructArray::read).
+    pub fn __expand_read_method(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+    ) -> T::ExpandType {
+        let layout = T::layout();
+        let base = struct_base(context, index, &layout);
+        let buffer: Variable = *self.expand;
+
+        let fields = layout
+            .fields()
+            .iter()
+            .map(|field| {
+                let value = match field.info.line_size {
+                    1 => context.create_local_binding(Item::new(field.info.elem)),
+                    size => context.create_local_variable(Item::vectorized(
+                        field.info.elem,
+                        NonZero::new(size),
+                    )),
+                };
+
+                for i in 0..field.info.line_size as usize {
+                    let position = word_position(context, &base, field.offset, i);
+                    let word = context.create_local_binding(Item::new(Elem::UInt));
+                    context.register(Operator::Index(BinaryOperator {
+                        lhs: buffer,
+                        rhs: position.into(),
+                        out: word.clone().into(),
+                    }));
+
+                    let elem = bitcast(context, word, field.info.elem);
+
+                    if field.info.line_size == 1 {
+                        context.register(Operator::Assign(UnaryOperator {
+                            input: elem.into(),
+                            out: value.clone().into(),
+                        }));
+                    } else {
+                        context.register(Operator::IndexAssign(BinaryOperator {
+                            lhs: word_constant(i),
+                            rhs: elem.into(),
+                            out: value.clone().into(),
+                        }));
+                    }
+                }
+
+                value
+            })
+            .collect();
+
+        T::__expand_from_fields(fields)
+    }
+
+    /// Expand method of [write](StructArray::write).
+    pub fn __expand_write_method(
+        self,
+        context: &mut CubeContext,
+        index: ExpandElementTyped<u32>,
+        value: T::ExpandType,
+    ) {
+        let layout = T::layout();
+        let base = struct_base(context, index, &layout);
+        let buffer: Variable = *self.expand;
+        let values = T::__expand_into_fields(value);
+
+        for (field, value) in layout.fields().iter().zip(values) {
+            for i in 0..field.info.line_size as usize {
+                let elem = match field.info.line_size {
+                    1 => value.clone(),
+                    _ => {
+                        let elem = context.create_local_binding(Item::new(field.info.elem));
+                        context.register(Operator::Index(BinaryOperator {
+                            lhs: *value,
+                            rhs: word_constant(i),
+                            out: elem.clone().into(),
+                        }));
+                        elem
+                    }
+                };
+
+                let word = bitcast(context, elem, Elem::UInt);
+                let position = word_position(context, &base, field.offset, i);
+                context.register(Operator::IndexAssign(BinaryOperator {
+                    lhs: position.into(),
+                    rhs: word.into(),
+                    out: buffer,
+                }));
+            }
+        }
+    }
+}
+
+fn word_constant(value: usize) -> Variable {
+    Variable::ConstantScalar(crate::ir::ConstantScalarValue::UInt(value as u64))
+}
+
+/// The position of the first word of the struct at the given index.
+fn struct_base(
+    context: &mut CubeContext,
+    index: ExpandElementTyped<u32>,
+    layout: &StructLayout,
+) -> ExpandElement {
+    let base = context.create_local_binding(Item::new(Elem::UInt));
+    context.register(Operator::Mul(BinaryOperator {
+        lhs: index.expand.into(),
+        rhs: word_constant(layout.stride() / STRUCT_WORD_SIZE),
+        out: base.clone().into(),
+    }));
+    base
+}
+
+/// The position of the word holding the given element of a field.
+fn word_position(
+    context: &mut CubeContext,
+    base: &ExpandElement,
+    offset: usize,
+    elem_index: usize,
+) -> ExpandElement {
+    let position = context.create_local_binding(Item::new(Elem::UInt));
+    context.register(Operator::Add(BinaryOperator {
+        lhs: **base,
+        rhs: word_constant(offset / STRUCT_WORD_SIZE + elem_index),
+        out: position.clone().into(),
+    }));
+    position
+}
+
+/// Reinterpret a 32-bit scalar as the given element type.
+fn bitcast(context: &mut CubeContext, value: ExpandElement, elem: Elem) -> ExpandElement {
+    if value.item().elem() == elem {
+        return value;
+    }
+
+    let out = context.create_local_binding(Item::new(elem));
+    context.register(Operator::Bitcast(UnaryOperator {
+        input: *value,
+        out: out.clone().into(),
+    }));
+    out
+}
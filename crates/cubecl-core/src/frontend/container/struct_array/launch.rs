@@ -0,0 +1,86 @@
+use crate::{
+    compute::{KernelBuilder, KernelLauncher},
+    ir::{Elem, Item},
+    prelude::{ArgSettings, ArrayArg, ExpandElementTyped, LaunchArg, LaunchArgExpand},
+    Runtime,
+};
+
+use super::{CubeStruct, StructArray, STRUCT_WORD_SIZE};
+
+/// Compilation argument for a [struct array](StructArray).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct StructArrayCompilationArg {
+    inplace: Option<u16>,
+}
+
+/// Argument to be used for [struct arrays](StructArray) passed as arguments to kernels.
+pub struct StructArrayArg<'a, R: Runtime> {
+    array: ArrayArg<'a, R>,
+}
+
+impl<'a, R: Runtime> StructArrayArg<'a, R> {
+    /// Create a new struct array argument holding `length` structs.
+    ///
+    /// # Safety
+    ///
+    /// Specifying the wrong length may lead to out-of-bounds reads and writes.
+    pub unsafe fn from_raw_parts<T: CubeStruct>(
+        handle: &'a cubecl_runtime::server::Handle,
+        length: usize,
+    ) -> Self {
+        let words = length * T::layout().stride() / STRUCT_WORD_SIZE;
+
+        Self {
+            array: ArrayArg::from_raw_parts_typed::<u32>(handle, words, 1),
+        }
+    }
+
+    /// Create an alias argument.
+    pub fn alias(position: usize) -> Self {
+        Self {
+            array: ArrayArg::Alias {
+                input_pos: position,
+            },
+        }
+    }
+}
+
+impl<'a, R: Runtime> ArgSettings<R> for StructArrayArg<'a, R> {
+    fn register(&self, launcher: &mut KernelLauncher<R>) {
+        self.array.register(launcher)
+    }
+}
+
+impl<T: CubeStruct> LaunchArgExpand for StructArray<T> {
+    type CompilationArg = StructArrayCompilationArg;
+
+    fn expand(
+        _arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> ExpandElementTyped<StructArray<T>> {
+        builder.input_array(Item::new(Elem::UInt)).into()
+    }
+
+    fn expand_output(
+        arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> ExpandElementTyped<StructArray<T>> {
+        match arg.inplace {
+            Some(id) => builder.inplace_output(id).into(),
+            None => builder.output_array(Item::new(Elem::UInt)).into(),
+        }
+    }
+}
+
+impl<T: CubeStruct> LaunchArg for StructArray<T> {
+    type RuntimeArg<'a, R: Runtime> = StructArrayArg<'a, R>;
+
+    fn compilation_arg<R: Runtime>(runtime_arg: &Self::RuntimeArg<'_, R>) -> Self::CompilationArg {
+        match &runtime_arg.array {
+            ArrayArg::Handle { .. } => StructArrayCompilationArg { inplace: None },
+            ArrayArg::Alias { input_pos } => StructArrayCompilationArg {
+                inplace: Some(*input_pos as u16),
+            },
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::ir::Elem;
+
+/// The size of a word of a [struct array](super::StructArray), in bytes.
+pub const STRUCT_WORD_SIZE: usize = 4;
+
+/// A field of a [cube struct](super::CubeStruct), as declared.
+#[derive(new, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructFieldInfo {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The element type of the field.
+    pub elem: Elem,
+    /// The number of elements of the field, `1` for scalars.
+    pub line_size: u8,
+}
+
+/// A field of a [struct layout](StructLayout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructField {
+    /// The declared field.
+    pub info: StructFieldInfo,
+    /// The offset of the field in the struct, in bytes.
+    pub offset: usize,
+}
+
+/// Memory layout of a [cube struct](super::CubeStruct) stored in a global buffer.
+///
+/// The layout follows the WGSL rules for the storage address space, where scalars are aligned on
+/// 4 bytes, `vec2` on 8 bytes and `vec3` and `vec4` on 16 bytes. The struct is aligned on its
+/// largest field alignment, and its size is rounded up to that alignment so that consecutive
+/// structs stay aligned. Host data must be laid out the same way, padding included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructLayout {
+    fields: Vec<StructField>,
+    align: usize,
+    stride: usize,
+}
+
+impl StructLayout {
+    /// Compute the layout of the given fields.
+    ///
+    /// # Panics
+    ///
+    /// If a field doesn't have 32-bit elements or has more than 4 of them.
+    pub fn new(fields: impl IntoIterator<Item = StructFieldInfo>) -> Self {
+        let mut layout = Vec::new();
+        let mut offset: usize = 0;
+        let mut struct_align = STRUCT_WORD_SIZE;
+
+        for info in fields {
+            let (size, align) = Self::field_size_align(&info);
+
+            offset = offset.next_multiple_of(align);
+            struct_align = struct_align.max(align);
+            layout.push(StructField { info, offset });
+            offset += size;
+        }
+
+        Self {
+            fields: layout,
+            align: struct_align,
+            stride: offset.next_multiple_of(struct_align),
+        }
+    }
+
+    fn field_size_align(info: &StructFieldInfo) -> (usize, usize) {
+        assert_eq!(
+            info.elem.size(),
+            STRUCT_WORD_SIZE,
+            "Struct field {} must have 32-bit elements, got {}",
+            info.name,
+            info.elem
+        );
+
+        let size = info.line_size as usize * STRUCT_WORD_SIZE;
+        let align = match info.line_size {
+            1 => STRUCT_WORD_SIZE,
+            2 => 2 * STRUCT_WORD_SIZE,
+            3 | 4 => 4 * STRUCT_WORD_SIZE,
+            _ => panic!(
+                "Struct field {} must have between 1 and 4 elements, got {}",
+                info.name, info.line_size
+            ),
+        };
+
+        (size, align)
+    }
+
+    /// The fields of the struct, in declaration order.
+    pub fn fields(&self) -> &[StructField] {
+        &self.fields
+    }
+
+    /// The alignment of the struct, in bytes.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The distance between two consecutive structs in a buffer, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{FloatKind, IntKind};
+
+    #[test]
+    pub fn struct_layout_pads_vec3() {
+        let f32 = Elem::Float(FloatKind::F32);
+        let layout = StructLayout::new([
+            StructFieldInfo::new("pos", f32, 3),
+            StructFieldInfo::new("vel", f32, 3),
+            StructFieldInfo::new("mass", f32, 1),
+        ]);
+
+        let offsets = layout
+            .fields()
+            .iter()
+            .map(|field| field.offset)
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets, vec![0, 16, 28]);
+        assert_eq!(layout.align(), 16);
+        assert_eq!(layout.stride(), 32);
+    }
+
+    #[test]
+    pub fn struct_layout_scalars_only() {
+        let layout = StructLayout::new([
+            StructFieldInfo::new("id", Elem::UInt, 1),
+            StructFieldInfo::new("offset", Elem::Int(IntKind::I32), 1),
+            StructFieldInfo::new("uv", Elem::Float(FloatKind::F32), 2),
+        ]);
+
+        let offsets = layout
+            .fields()
+            .iter()
+            .map(|field| field.offset)
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets, vec![0, 4, 8]);
+        assert_eq!(layout.stride(), 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "must have 32-bit elements")]
+    pub fn struct_layout_rejects_64_bit_fields() {
+        StructLayout::new([StructFieldInfo::new("mass", Elem::Float(FloatKind::F64), 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must have between 1 and 4 elements")]
+    pub fn struct_layout_rejects_wide_lines() {
+        StructLayout::new([StructFieldInfo::new(
+            "weights",
+            Elem::Float(FloatKind::F32),
+            8,
+        )]);
+    }
+}
@@ -0,0 +1,7 @@
+mod base;
+mod launch;
+mod layout;
+
+pub use base::*;
+pub use launch::*;
+pub use layout::*;
@@ -2,7 +2,7 @@ use std::{marker::PhantomData, num::NonZero};
 
 use crate::{
     compute::{KernelBuilder, KernelLauncher},
-    ir::{Item, Vectorization},
+    ir::{Elem, Item, Vectorization},
     prelude::{ArgSettings, CubePrimitive, ExpandElementTyped, LaunchArg, LaunchArgExpand},
     Runtime,
 };
@@ -32,6 +32,11 @@ pub struct TensorHandleRef<'a, R: Runtime> {
     pub handle: &'a cubecl_runtime::server::Handle,
     pub strides: &'a [usize],
     pub shape: &'a [usize],
+    /// The element type stored in the buffer, if known.
+    ///
+    /// Used to validate the tensor against the kernel when using a
+    /// [checked launch](KernelLauncher::launch_checked).
+    pub elem: Option<Elem>,
     pub runtime: PhantomData<R>,
 }
 
@@ -39,8 +44,8 @@ impl<'a, R: Runtime> core::fmt::Debug for TensorHandleRef<'a, R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
-            "TensorHandleRef {{ strides: {:?}, shape: {:?} }}",
-            self.strides, self.shape
+            "TensorHandleRef {{ strides: {:?}, shape: {:?}, elem: {:?} }}",
+            self.strides, self.shape, self.elem
         )
     }
 }
@@ -117,6 +122,31 @@ impl<'a, R: Runtime> TensorArg<'a, R> {
         }
     }
 
+    /// Create a new tensor argument holding elements of type `E`, specified with its
+    /// vectorization factor.
+    ///
+    /// The element type is validated against the kernel when using a
+    /// [checked launch](KernelLauncher::launch_checked).
+    ///
+    /// # Safety
+    ///
+    /// If you provide wrong strides or shapes, it might create undefined behavior caused by
+    /// out-of-bound reads and writes.
+    pub unsafe fn from_raw_parts_typed<E: CubePrimitive>(
+        handle: &'a cubecl_runtime::server::Handle,
+        strides: &'a [usize],
+        shape: &'a [usize],
+        factor: u8,
+    ) -> Self {
+        unsafe {
+            Self::Handle {
+                handle: TensorHandleRef::from_raw_parts(handle, strides, shape)
+                    .with_elem(E::as_elem()),
+                vectorization_factor: factor,
+            }
+        }
+    }
+
     /// Create an alias argument.
     pub fn alias(position: usize) -> Self {
         Self::Alias {
@@ -157,7 +187,14 @@ impl<'a, R: Runtime> TensorHandleRef<'a, R> {
             handle,
             strides,
             shape,
+            elem: None,
             runtime: PhantomData,
         }
     }
+
+    /// Specify the element type stored in the buffer.
+    pub fn with_elem(mut self, elem: Elem) -> Self {
+        self.elem = Some(elem);
+        self
+    }
 }
@@ -1,12 +1,31 @@
-use crate::ir::{self, Elem, Item, Operation, ReusingAllocator, Scope, Variable};
+use crate::ir::{
+    self, Elem, FunctionDefinition, Item, Operation, ReusingAllocator, Scope, SourceLocation,
+    Variable,
+};
 use crate::{frontend::ExpandElement, ir::LocalAllocator};
 use alloc::rc::Rc;
 use core::cell::RefCell;
 
+/// The topology values read by a device function body, with the parameters receiving them.
+pub type Builtins = Rc<RefCell<Vec<(Variable, ExpandElement)>>>;
+
 pub struct CubeContext {
     pub root: Rc<RefCell<Scope>>,
     pub scope: Rc<RefCell<Scope>>,
     pub local_allocator: Rc<dyn LocalAllocator>,
+    /// The device functions expanded so far, shared by all contexts of a kernel.
+    pub functions: Rc<RefCell<Vec<FunctionDefinition>>>,
+    /// Whether functions marked with `#[cube(inline = never)]` are expanded into
+    /// [device functions](crate::frontend::call_expand) instead of being inlined.
+    pub device_functions: bool,
+    /// Whether [matrix operations](crate::ir::Operator::MatrixMul) on matrices of the size are
+    /// registered as is instead of being unrolled.
+    pub matrix_ops: fn(u32) -> bool,
+    /// Whether the source location of each expanded statement is registered.
+    pub debug_info: bool,
+    /// The topology values read by the body of the [device function](crate::frontend::call_expand)
+    /// being expanded. `None` in the kernel body.
+    pub builtins: Option<Builtins>,
 }
 
 impl Default for CubeContext {
@@ -28,6 +47,11 @@ impl CubeContext {
             local_allocator: Rc::new(allocator),
             scope,
             root,
+            functions: Default::default(),
+            device_functions: false,
+            matrix_ops: |_| false,
+            debug_info: false,
+            builtins: None,
         }
     }
 
@@ -35,6 +59,28 @@ impl CubeContext {
         self.scope.borrow_mut().register(op)
     }
 
+    /// Register the source location of the statement being expanded, when debug info is enabled.
+    ///
+    /// Consecutive statements on the same line only register their location once.
+    pub fn register_location(&mut self, file: &'static str, line: u32, column: u32) {
+        if !self.debug_info {
+            return;
+        }
+
+        let mut scope = self.scope.borrow_mut();
+        if let Some(Operation::Location(last)) = scope.operations.last() {
+            if last.file == file && last.line == line {
+                return;
+            }
+        }
+
+        scope.register(SourceLocation {
+            file: file.to_string(),
+            line,
+            column,
+        });
+    }
+
     pub fn child(&mut self) -> CubeContext {
         let scope = self.scope.borrow_mut().child();
 
@@ -42,7 +88,41 @@ impl CubeContext {
             scope: Rc::new(RefCell::new(scope)),
             root: self.root.clone(),
             local_allocator: self.local_allocator.clone(),
+            functions: self.functions.clone(),
+            device_functions: self.device_functions,
+            matrix_ops: self.matrix_ops,
+            debug_info: self.debug_info,
+            builtins: self.builtins.clone(),
+        }
+    }
+
+    /// Create the context of a device function body, with its own root scope.
+    pub fn function(&self) -> CubeContext {
+        let mut context = Self::root(ReusingAllocator::default());
+        context.functions = self.functions.clone();
+        context.device_functions = self.device_functions;
+        context.matrix_ops = self.matrix_ops;
+        context.debug_info = self.debug_info;
+        context.builtins = Some(Default::default());
+        context
+    }
+
+    /// The topology value, read in the body of a device function through an implicit parameter
+    /// that its calls fill with the value of the caller.
+    pub fn builtin(&mut self, variable: Variable) -> ExpandElement {
+        let Some(builtins) = self.builtins.clone() else {
+            return ExpandElement::Plain(variable);
+        };
+        let mut builtins = builtins.borrow_mut();
+
+        if let Some((_, param)) = builtins.iter().find(|(builtin, _)| *builtin == variable) {
+            return param.clone();
         }
+
+        // Declared in the root scope and never reused, so it holds the value for the whole body.
+        let param = ExpandElement::Plain(self.root.borrow_mut().create_local(variable.item()));
+        builtins.push((variable, param.clone()));
+        param
     }
 
     pub fn into_scope(self) -> Scope {
@@ -88,6 +168,10 @@ impl CubeContext {
         ExpandElement::Plain(self.root.borrow_mut().create_shared(item, size))
     }
 
+    pub fn create_shared_dynamic(&mut self, item: Item) -> ExpandElement {
+        ExpandElement::Plain(self.root.borrow_mut().create_shared_dynamic(item))
+    }
+
     pub fn create_local_array(&mut self, item: Item, size: u32) -> ExpandElement {
         ExpandElement::Plain(self.root.borrow_mut().create_local_array(item, size))
     }
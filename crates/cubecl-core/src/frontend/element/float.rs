@@ -25,6 +25,10 @@ pub trait Float:
     + Floor
     + Ceil
     + Erf
+    + Erfc
+    + Expm1
+    + Tgamma
+    + Lgamma
     + Recip
     + Magnitude
     + Normalize
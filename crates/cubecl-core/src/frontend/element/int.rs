@@ -1,6 +1,7 @@
 use crate::frontend::{
-    CubeContext, CubePrimitive, CubeType, ExpandElement, ExpandElementBaseInit, ExpandElementTyped,
-    Numeric,
+    BitManipulation, CubeContext, CubePrimitive, CubeType, ExpandElement, ExpandElementBaseInit,
+    ExpandElementTyped, Numeric, SaturatingAdd, SaturatingSub, WrappingAdd, WrappingMul,
+    WrappingSub,
 };
 use crate::ir::{Elem, IntKind};
 use crate::Runtime;
@@ -39,6 +40,12 @@ pub trait Int:
     + std::ops::ShrAssign<u32>
     + std::cmp::PartialOrd
     + std::cmp::PartialEq
+    + SaturatingAdd
+    + SaturatingSub
+    + WrappingAdd
+    + WrappingSub
+    + WrappingMul
+    + BitManipulation
 {
     const BITS: u32;
 
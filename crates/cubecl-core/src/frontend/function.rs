@@ -0,0 +1,129 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::frontend::{CubeContext, ExpandElement};
+use crate::ir::{FunctionCall, FunctionDefinition, Item, Operator, UnaryOperator, Variable};
+
+/// Expand a call to a function marked with `#[cube(inline = never)]`.
+///
+/// When the compiler supports [device functions](crate::Compiler::device_functions), the body is
+/// expanded once per instantiation into its own [function](FunctionDefinition) and each call
+/// site only registers a [call](FunctionCall). Otherwise, or when an argument isn't a plain
+/// value such as an array, the body is inlined like any other function.
+///
+/// Topology values such as `UNIT_POS` read by the body are passed by the calls as implicit
+/// arguments, so the function behaves the same whether it's inlined or not. Shared memories or
+/// local arrays can't be created in device functions.
+///
+/// Functions are only expanded into device functions when marked, whatever their size.
+///
+/// Instantiations are identified by the `path` of the function, including its module so
+/// functions of the same name in different modules are distinct, and by its `generics`.
+pub fn call_expand(
+    context: &mut CubeContext,
+    path: &str,
+    generics: &str,
+    args: Vec<ExpandElement>,
+    body: impl FnOnce(&mut CubeContext, Vec<ExpandElement>) -> Option<ExpandElement>,
+) -> Option<ExpandElement> {
+    if !context.device_functions || !args.iter().all(|arg| is_value(arg)) {
+        return body(context, args);
+    }
+
+    let items: Vec<Item> = args.iter().map(|arg| arg.item()).collect();
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    generics.hash(&mut hasher);
+    items.hash(&mut hasher);
+    let name = path.rsplit("::").next().unwrap_or(path);
+    let name = format!("{name}_{:016x}", hasher.finish());
+
+    let existing = context
+        .functions
+        .borrow()
+        .iter()
+        .find(|function| function.name == name)
+        .map(|function| {
+            let output = function.output.map(|output| output.item());
+            (output, function.builtins.clone())
+        });
+
+    let (output, builtins) = match existing {
+        Some(existing) => existing,
+        None => {
+            let function = expand_function(context, name.clone(), &items, body);
+            let output = function.output.map(|output| output.item());
+            let builtins = function.builtins.clone();
+            context.functions.borrow_mut().push(function);
+            (output, builtins)
+        }
+    };
+
+    // Read in the caller, the builtins are also forwarded when it's a device function itself.
+    let builtins: Vec<ExpandElement> = builtins
+        .into_iter()
+        .map(|builtin| context.builtin(builtin))
+        .collect();
+    let out = output.map(|item| context.create_local_binding(item));
+    context.register(FunctionCall {
+        name,
+        args: args
+            .iter()
+            .chain(builtins.iter())
+            .map(|arg| **arg)
+            .collect(),
+        out: out.as_ref().map(|out| **out),
+    });
+
+    out
+}
+
+fn expand_function(
+    context: &CubeContext,
+    name: String,
+    items: &[Item],
+    body: impl FnOnce(&mut CubeContext, Vec<ExpandElement>) -> Option<ExpandElement>,
+) -> FunctionDefinition {
+    let mut function = context.function();
+
+    let params: Vec<ExpandElement> = items
+        .iter()
+        .map(|item| function.create_local_variable(*item))
+        .collect();
+    let mut inputs: Vec<Variable> = params.iter().map(|param| **param).collect();
+
+    let output = body(&mut function, params).map(|value| {
+        let output = function.create_local_variable(value.item());
+        function.register(Operator::Assign(UnaryOperator {
+            input: *value,
+            out: *output,
+        }));
+        *output
+    });
+
+    let builtins = function
+        .builtins
+        .take()
+        .map(|builtins| builtins.take())
+        .unwrap_or_default();
+    inputs.extend(builtins.iter().map(|(_, param)| **param));
+
+    FunctionDefinition {
+        name,
+        inputs,
+        output,
+        builtins: builtins.into_iter().map(|(builtin, _)| builtin).collect(),
+        body: function.into_scope(),
+    }
+}
+
+/// Whether the variable can be passed by value to a device function.
+fn is_value(variable: &Variable) -> bool {
+    matches!(
+        variable,
+        Variable::Local { .. }
+            | Variable::LocalBinding { .. }
+            | Variable::ConstantScalar(_)
+            | Variable::GlobalScalar { .. }
+    )
+}
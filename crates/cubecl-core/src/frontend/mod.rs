@@ -7,6 +7,7 @@ mod const_expand;
 mod container;
 mod context;
 mod element;
+mod function;
 mod indexation;
 mod operation;
 mod subcube;
@@ -17,6 +18,7 @@ pub use const_expand::*;
 pub use container::*;
 pub use context::*;
 pub use element::*;
+pub use function::*;
 pub use indexation::*;
 pub use operation::*;
 pub use subcube::*;
@@ -297,6 +297,56 @@ impl_binary_func!(
     i64,
     u32
 );
+impl_binary_func!(
+    SaturatingAdd,
+    saturating_add,
+    __expand_saturating_add,
+    __expand_saturating_add_method,
+    Operator::SaturatingAdd,
+    i32,
+    i64,
+    u32
+);
+impl_binary_func!(
+    SaturatingSub,
+    saturating_sub,
+    __expand_saturating_sub,
+    __expand_saturating_sub_method,
+    Operator::SaturatingSub,
+    i32,
+    i64,
+    u32
+);
+impl_binary_func!(
+    WrappingAdd,
+    wrapping_add,
+    __expand_wrapping_add,
+    __expand_wrapping_add_method,
+    Operator::WrappingAdd,
+    i32,
+    i64,
+    u32
+);
+impl_binary_func!(
+    WrappingSub,
+    wrapping_sub,
+    __expand_wrapping_sub,
+    __expand_wrapping_sub_method,
+    Operator::WrappingSub,
+    i32,
+    i64,
+    u32
+);
+impl_binary_func!(
+    WrappingMul,
+    wrapping_mul,
+    __expand_wrapping_mul,
+    __expand_wrapping_mul_method,
+    Operator::WrappingMul,
+    i32,
+    i64,
+    u32
+);
 impl_binary_func_fixed_output_vectorization!(
     Dot,
     dot,
@@ -0,0 +1,102 @@
+use crate::{
+    ir::{BitfieldExtractOperator, BitfieldInsertOperator, Operator},
+    prelude::{CubeContext, CubePrimitive, ExpandElement, ExpandElementTyped},
+    unexpanded,
+};
+
+use super::{binary_expand, unary_expand};
+
+/// Bit manipulations mapped to native instructions when available, applied to each element of a
+/// line.
+pub trait BitManipulation: CubePrimitive + Sized {
+    /// Rotate the bits to the left by `amount`, modulo the bit width.
+    #[allow(unused_variables)]
+    fn rotate_left(value: Self, amount: u32) -> Self {
+        unexpanded!()
+    }
+
+    /// Rotate the bits to the right by `amount`, modulo the bit width.
+    #[allow(unused_variables)]
+    fn rotate_right(value: Self, amount: u32) -> Self {
+        unexpanded!()
+    }
+
+    /// Extract `count` bits starting at the bit `offset`, sign extended for signed integers.
+    /// The offset and count are clamped to the bit width.
+    #[allow(unused_variables)]
+    fn extract_bits(value: Self, offset: u32, count: u32) -> Self {
+        unexpanded!()
+    }
+
+    /// Replace `count` bits of `base` starting at the bit `offset` with the lowest bits of
+    /// `insert`. The offset and count are clamped to the bit width.
+    #[allow(unused_variables)]
+    fn insert_bits(base: Self, insert: Self, offset: u32, count: u32) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_rotate_left(
+        context: &mut CubeContext,
+        value: Self::ExpandType,
+        amount: ExpandElementTyped<u32>,
+    ) -> Self::ExpandType {
+        binary_expand(context, value.into(), amount.into(), Operator::RotateLeft).into()
+    }
+
+    fn __expand_rotate_right(
+        context: &mut CubeContext,
+        value: Self::ExpandType,
+        amount: ExpandElementTyped<u32>,
+    ) -> Self::ExpandType {
+        binary_expand(context, value.into(), amount.into(), Operator::RotateRight).into()
+    }
+
+    fn __expand_extract_bits(
+        context: &mut CubeContext,
+        value: Self::ExpandType,
+        offset: ExpandElementTyped<u32>,
+        count: ExpandElementTyped<u32>,
+    ) -> Self::ExpandType {
+        let value: ExpandElement = value.into();
+        let offset: ExpandElement = offset.into();
+        let count: ExpandElement = count.into();
+
+        unary_expand(context, value, |op| {
+            Operator::BitfieldExtract(BitfieldExtractOperator {
+                input: op.input,
+                offset: *offset,
+                count: *count,
+                out: op.out,
+            })
+        })
+        .into()
+    }
+
+    fn __expand_insert_bits(
+        context: &mut CubeContext,
+        base: Self::ExpandType,
+        insert: Self::ExpandType,
+        offset: ExpandElementTyped<u32>,
+        count: ExpandElementTyped<u32>,
+    ) -> Self::ExpandType {
+        let base: ExpandElement = base.into();
+        let insert: ExpandElement = insert.into();
+        let offset: ExpandElement = offset.into();
+        let count: ExpandElement = count.into();
+
+        unary_expand(context, base, |op| {
+            Operator::BitfieldInsert(BitfieldInsertOperator {
+                base: op.input,
+                insert: *insert,
+                offset: *offset,
+                count: *count,
+                out: op.out,
+            })
+        })
+        .into()
+    }
+}
+
+impl BitManipulation for i32 {}
+impl BitManipulation for i64 {}
+impl BitManipulation for u32 {}
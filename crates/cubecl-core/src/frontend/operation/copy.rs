@@ -46,3 +46,51 @@ pub mod copy_bulk {
         }));
     }
 }
+
+/// Bulk copy `length` elements between two array-likes, allowing the copy to complete
+/// asynchronously. Intended to stream global memory into shared memory while computing on a
+/// previously loaded buffer.
+///
+/// The copied elements can't be read until the copy is grouped with [`copy_async_commit()`] and
+/// awaited with [`copy_async_wait()`]. Backends without asynchronous copies perform it immediately.
+///
+/// # Example
+///
+/// ```ignore
+/// copy_bulk_async(input.slice(start, end), 0, shared.slice_mut(0, 16), 0, 16);
+/// copy_async_commit();
+/// copy_async_wait(0);
+/// sync_units();
+/// ```
+pub fn copy_bulk_async<C: CubePrimitive>(
+    _from: &Slice<C>,
+    _from_index: u32,
+    _to: &mut SliceMut<C>,
+    _to_index: u32,
+    _length: u32,
+) {
+}
+
+pub mod copy_bulk_async {
+    use crate::ir::{CopyBulkOperator, Operator};
+
+    use super::*;
+
+    /// The expand function for [`copy_bulk_async`]
+    pub fn expand<C: CubeType>(
+        context: &mut CubeContext,
+        from: ExpandElementTyped<Slice<C>>,
+        from_index: ExpandElementTyped<u32>,
+        to: ExpandElementTyped<SliceMut<C>>,
+        to_index: ExpandElementTyped<u32>,
+        length: u32,
+    ) {
+        context.register(Operator::CopyBulkAsync(CopyBulkOperator {
+            out: *to.expand,
+            out_index: to_index.expand.consume(),
+            input: from.expand.consume(),
+            in_index: from_index.expand.consume(),
+            len: length,
+        }));
+    }
+}
@@ -1,19 +1,23 @@
 mod assignation;
 mod base;
 mod binary;
+mod bits;
 mod branch;
 mod clamp;
 mod cmp;
 mod copy;
 mod fma;
+mod pack;
 mod unary;
 
 pub use assignation::*;
 pub use base::*;
 pub use binary::*;
+pub use bits::*;
 pub use branch::*;
 pub use clamp::*;
 pub use cmp::*;
 pub use copy::*;
 pub use fma::*;
+pub use pack::*;
 pub use unary::*;
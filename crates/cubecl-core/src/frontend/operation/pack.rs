@@ -0,0 +1,114 @@
+use crate::{
+    frontend::{CubeContext, ExpandElementTyped, Line},
+    ir::{Elem, FloatKind, Item, Operator, UnaryOperator},
+    unexpanded,
+};
+
+use super::base::unary_expand_fixed_output;
+
+/// Convert the two `f32` of a line to `f16`, rounding to nearest even, and pack their bits in a
+/// `u32`, the first element in the low half.
+///
+/// The conversion is computed on the device, so 16-bit data can be written by runtimes without
+/// a `f16` storage type, like wgpu.
+#[allow(unused_variables)]
+pub fn pack_f16(value: Line<f32>) -> u32 {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [pack_f16()].
+pub mod pack_f16 {
+    use super::*;
+
+    /// Expand method of [pack_f16()].
+    pub fn expand(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<Line<f32>>,
+    ) -> ExpandElementTyped<u32> {
+        pack_expand(context, value, Operator::PackF16)
+    }
+}
+
+/// Unpack the two `f16` of a `u32` to a line of two `f32`, the low half first.
+#[allow(unused_variables)]
+pub fn unpack_f16(value: u32) -> Line<f32> {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [unpack_f16()].
+pub mod unpack_f16 {
+    use super::*;
+
+    /// Expand method of [unpack_f16()].
+    pub fn expand(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Line<f32>> {
+        unpack_expand(context, value, Operator::UnpackF16)
+    }
+}
+
+/// Convert the two `f32` of a line to `bf16`, rounding to nearest even, and pack their bits in a
+/// `u32`, the first element in the low half.
+///
+/// NaNs stay NaNs, and values too large for `bf16` round to infinity.
+#[allow(unused_variables)]
+pub fn pack_bf16(value: Line<f32>) -> u32 {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [pack_bf16()].
+pub mod pack_bf16 {
+    use super::*;
+
+    /// Expand method of [pack_bf16()].
+    pub fn expand(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<Line<f32>>,
+    ) -> ExpandElementTyped<u32> {
+        pack_expand(context, value, Operator::PackBf16)
+    }
+}
+
+/// Unpack the two `bf16` of a `u32` to a line of two `f32`, the low half first.
+#[allow(unused_variables)]
+pub fn unpack_bf16(value: u32) -> Line<f32> {
+    unexpanded!()
+}
+
+/// Module containing the expand function for [unpack_bf16()].
+pub mod unpack_bf16 {
+    use super::*;
+
+    /// Expand method of [unpack_bf16()].
+    pub fn expand(
+        context: &mut CubeContext,
+        value: ExpandElementTyped<u32>,
+    ) -> ExpandElementTyped<Line<f32>> {
+        unpack_expand(context, value, Operator::UnpackBf16)
+    }
+}
+
+fn pack_expand(
+    context: &mut CubeContext,
+    value: ExpandElementTyped<Line<f32>>,
+    operator: fn(UnaryOperator) -> Operator,
+) -> ExpandElementTyped<u32> {
+    let vectorization = value.expand.item().vectorization.map(|it| it.get());
+    assert_eq!(
+        vectorization,
+        Some(2),
+        "Only lines of two elements can be packed in a u32"
+    );
+    let out_item = Item::new(Elem::UInt);
+    unary_expand_fixed_output(context, value.expand, out_item, operator).into()
+}
+
+fn unpack_expand(
+    context: &mut CubeContext,
+    value: ExpandElementTyped<u32>,
+    operator: fn(UnaryOperator) -> Operator,
+) -> ExpandElementTyped<Line<f32>> {
+    let out_item = Item::vectorized(Elem::Float(FloatKind::F32), core::num::NonZero::new(2));
+    unary_expand_fixed_output(context, value.expand, out_item, operator).into()
+}
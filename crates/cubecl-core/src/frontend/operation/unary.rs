@@ -148,6 +148,46 @@ impl_unary_func!(
     f64
 );
 impl_unary_func!(Erf, erf, __expand_erf, Operator::Erf, f16, bf16, f32, f64);
+impl_unary_func!(
+    Erfc,
+    erfc,
+    __expand_erfc,
+    Operator::Erfc,
+    f16,
+    bf16,
+    f32,
+    f64
+);
+impl_unary_func!(
+    Expm1,
+    expm1,
+    __expand_expm1,
+    Operator::Expm1,
+    f16,
+    bf16,
+    f32,
+    f64
+);
+impl_unary_func!(
+    Tgamma,
+    tgamma,
+    __expand_tgamma,
+    Operator::Tgamma,
+    f16,
+    bf16,
+    f32,
+    f64
+);
+impl_unary_func!(
+    Lgamma,
+    lgamma,
+    __expand_lgamma,
+    Operator::Lgamma,
+    f16,
+    bf16,
+    f32,
+    f64
+);
 impl_unary_func!(
     Recip,
     recip,
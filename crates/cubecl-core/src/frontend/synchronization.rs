@@ -33,3 +33,32 @@ pub mod sync_storage {
         context.register(Synchronization::SyncStorage)
     }
 }
+
+/// Group every [asynchronous copy](crate::frontend::copy_bulk_async()) issued by the unit since
+/// the last commit, so they can be awaited together with [`copy_async_wait()`].
+pub fn copy_async_commit() {}
+
+pub mod copy_async_commit {
+    use super::*;
+
+    pub fn expand(context: &mut CubeContext) {
+        context.register(Synchronization::CopyAsyncCommit)
+    }
+}
+
+/// Wait until at most `pending` committed groups of asynchronous copies of the unit are still in
+/// flight. Groups complete in commit order, so with double buffering `copy_async_wait(1)` waits for
+/// the older buffer only.
+///
+/// This only covers the copies of the current unit: call [`sync_units()`] afterward before reading
+/// elements copied by other units.
+#[allow(unused_variables)]
+pub fn copy_async_wait(pending: u32) {}
+
+pub mod copy_async_wait {
+    use super::*;
+
+    pub fn expand(context: &mut CubeContext, pending: u32) {
+        context.register(Synchronization::CopyAsyncWait { pending })
+    }
+}
@@ -12,11 +12,11 @@ macro_rules! constant {
         #[doc = $doc]
         pub mod $ident {
             use super::*;
-            use crate::frontend::{CubeContext, ExpandElement};
+            use crate::frontend::CubeContext;
 
             /// Expansion of the constant variable.
-            pub fn expand(_context: &mut CubeContext) -> ExpandElementTyped<u32> {
-                ExpandElementTyped::new(ExpandElement::Plain($var))
+            pub fn expand(context: &mut CubeContext) -> ExpandElementTyped<u32> {
+                ExpandElementTyped::new(context.builtin($var))
             }
         }
     };
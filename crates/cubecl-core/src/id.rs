@@ -7,8 +7,11 @@ use std::sync::Arc;
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 pub struct KernelId {
     pub(crate) type_id: core::any::TypeId,
+    pub(crate) type_name: &'static str,
     pub(crate) info: Option<Info>,
+    pub(crate) specialization: Option<Info>,
     pub(crate) mode: Option<ExecutionMode>,
+    pub(crate) dynamic_shared_memory: Option<u32>,
 }
 
 impl KernelId {
@@ -16,8 +19,11 @@ impl KernelId {
     pub fn new<T: 'static>() -> Self {
         Self {
             type_id: core::any::TypeId::of::<T>(),
+            type_name: core::any::type_name::<T>(),
             info: None,
+            specialization: None,
             mode: None,
+            dynamic_shared_memory: None,
         }
     }
 
@@ -33,10 +39,151 @@ impl KernelId {
         self
     }
 
+    /// Add the values the kernel is specialized for, such as the shapes of its tensors.
+    ///
+    /// Each specialization is compiled separately from the generic kernel.
+    pub fn specialize<I: 'static + PartialEq + Eq + Hash + core::fmt::Debug + Send + Sync>(
+        mut self,
+        specialization: I,
+    ) -> Self {
+        self.specialization = Some(Info::new(specialization));
+        self
+    }
+
     /// Set the [execution mode](ExecutionMode).
     pub fn mode(&mut self, mode: ExecutionMode) {
         self.mode = Some(mode);
     }
+
+    /// Set the number of bytes of dynamic shared memory.
+    ///
+    /// Only used by backends that bake the size of the dynamic shared memory into the compiled
+    /// kernel, which requires a different variant for each size.
+    pub fn dynamic_shared_memory(&mut self, bytes: u32) {
+        self.dynamic_shared_memory = Some(bytes);
+    }
+
+    /// A readable name of the kernel, stable across runs, made of its type and of the information
+    /// it was created with, e.g. `matmul_tiling2d_f32_16x16x1_64x64`.
+    ///
+    /// The name isn't unique: kernels of different modules may have the same type name, and
+    /// long information is truncated.
+    pub fn name(&self) -> String {
+        let mut name = mangle_type(self.type_name);
+        if let Some(info) = &self.info {
+            let info = mangle_info(&format!("{info:?}"));
+            if !info.is_empty() {
+                name.push('_');
+                name.push_str(&info);
+            }
+        }
+
+        if name.len() > MAX_NAME_LEN {
+            let mut end = MAX_NAME_LEN;
+            while !name.is_char_boundary(end) {
+                end -= 1;
+            }
+            name.truncate(end);
+        }
+        name
+    }
+
+    /// Whether both ids are the same kernel, compiled with possibly different
+    /// [execution modes](ExecutionMode) or sizes of dynamic shared memory.
+    pub fn is_variant_of(&self, other: &KernelId) -> bool {
+        self.type_id == other.type_id
+            && self.info == other.info
+            && self.specialization == other.specialization
+    }
+}
+
+/// The longest [kernel name](KernelId::name), short enough for labels and file names.
+const MAX_NAME_LEN: usize = 128;
+
+/// The snake case name of the type followed by its generic arguments, e.g.
+/// `matmul_tiling2d_f32` for `crate::matmul::MatmulTiling2d<f32, WgpuRuntime>`.
+///
+/// Runtimes are skipped, since all the kernels of a client share the same.
+fn mangle_type(type_name: &str) -> String {
+    let mut segments = Vec::new();
+    // Each path ends at a generic delimiter, its last segment naming the type.
+    for path in type_name.split(['<', '>', ',', '(', ')', '[', ']', ';', '&', ' ']) {
+        let Some(segment) = path.rsplit("::").next().filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        if segment.ends_with("Runtime") {
+            continue;
+        }
+        segments.push(snake_case(segment));
+    }
+    segments.join("_")
+}
+
+/// The values of the debug representation of the information, without the names of the types
+/// and fields. Consecutive numbers are joined by `x`, e.g. `16x16x1` for a cube dim.
+fn mangle_info(debug: &str) -> String {
+    let mut name = String::new();
+    let mut chars = debug.chars().peekable();
+    let mut previous_number = false;
+
+    while let Some(c) = chars.next() {
+        if !(c.is_alphanumeric() || c == '_' || c == '-') {
+            if c != ' ' && c != ',' && c != '.' {
+                // Numbers are only joined inside the same group.
+                previous_number = false;
+            }
+            continue;
+        }
+
+        let mut token = String::from(c);
+        while let Some(&next) = chars.peek() {
+            let decimal = next == '.' && token.chars().all(|c| c.is_ascii_digit() || c == '-');
+            if !(next.is_alphanumeric() || next == '_' || decimal) {
+                break;
+            }
+            token.push(next);
+            chars.next();
+        }
+
+        // Skip the spaces to know whether the token names a field or a type.
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+        match chars.peek() {
+            Some(':') => {
+                chars.next();
+                continue;
+            }
+            Some('{' | '(') => continue,
+            _ => {}
+        }
+
+        let number = token.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+        let token = match number {
+            true => token.replace('-', "m").replace('.', "p"),
+            false => snake_case(&token),
+        };
+        if !name.is_empty() {
+            name.push(if number && previous_number { 'x' } else { '_' });
+        }
+        name.push_str(&token);
+        previous_number = number;
+    }
+
+    name
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+    snake
 }
 
 /// Extra information
@@ -127,4 +274,27 @@ mod tests {
         assert!(set.contains(&value_1));
         assert!(!set.contains(&value_2));
     }
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Tiling {
+        block_size_m: u32,
+        block_size_n: u32,
+    }
+
+    struct MatmulTiling2d<F, R>(F, R);
+    struct WgpuRuntime;
+
+    #[test]
+    pub fn kernel_id_name() {
+        let id = KernelId::new::<MatmulTiling2d<f32, WgpuRuntime>>().info((
+            Tiling {
+                block_size_m: 64,
+                block_size_n: 64,
+            },
+            true,
+            -2i32,
+        ));
+
+        assert_eq!(id.name(), "matmul_tiling2d_f32_64x64_true_m2");
+    }
 }
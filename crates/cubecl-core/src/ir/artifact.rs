@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use super::KernelDefinition;
+use crate::{Kernel, KernelId};
+
+/// The version of the [artifact](KernelArtifact) format, increased on every change of the IR
+/// that breaks the compatibility of serialized kernels.
+pub const ARTIFACT_VERSION: u32 = 2;
+
+/// A [kernel definition](KernelDefinition) serialized once and compiled later, possibly on
+/// another machine.
+///
+/// Kernels can be expanded at build time or on a build server, stored as artifacts, and loaded
+/// on the target machines, where they are launched like any other [kernel](Kernel). Producers
+/// written in other languages only have to emit the JSON format of the IR.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelArtifact {
+    version: u32,
+    /// The name of the kernel, identifying it in the compilation caches.
+    pub name: String,
+    /// The definition of the kernel.
+    pub definition: KernelDefinition,
+}
+
+/// An error returned when [loading](KernelArtifact::from_json) an artifact.
+#[derive(Debug)]
+pub enum ArtifactError {
+    /// The artifact was serialized with an incompatible version of the format.
+    Version {
+        /// The version supported by this build.
+        expected: u32,
+        /// The version of the artifact.
+        actual: u32,
+    },
+    /// The artifact isn't valid JSON or doesn't describe a kernel.
+    Format(serde_json::Error),
+}
+
+impl core::fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ArtifactError::Version { expected, actual } => write!(
+                f,
+                "The artifact has version {actual}, but only version {expected} is supported"
+            ),
+            ArtifactError::Format(err) => write!(f, "The artifact is malformed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ArtifactError {}
+
+impl KernelArtifact {
+    /// Create an artifact of the current version.
+    pub fn new(name: impl Into<String>, definition: KernelDefinition) -> Self {
+        Self {
+            version: ARTIFACT_VERSION,
+            name: name.into(),
+            definition,
+        }
+    }
+
+    /// Create an artifact of the kernel, expanding it.
+    pub fn of<K: Kernel>(name: impl Into<String>, kernel: &K) -> Self {
+        Self::new(name, kernel.define())
+    }
+
+    /// Serialize the artifact to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("The IR should be serializable")
+    }
+
+    /// Load an artifact serialized with [to_json](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, ArtifactError> {
+        // Only the version is read first, so older artifacts report their version instead of
+        // failing on the fields that changed.
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+
+        let header: Header = serde_json::from_str(json).map_err(ArtifactError::Format)?;
+        if header.version != ARTIFACT_VERSION {
+            return Err(ArtifactError::Version {
+                expected: ARTIFACT_VERSION,
+                actual: header.version,
+            });
+        }
+
+        serde_json::from_str(json).map_err(ArtifactError::Format)
+    }
+}
+
+impl Kernel for KernelArtifact {
+    fn define(&self) -> KernelDefinition {
+        self.definition.clone()
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>().info(self.name.clone())
+    }
+
+    fn dynamic_shared_memory(&self) -> u32 {
+        self.definition.dynamic_shared_memory
+    }
+
+    fn zero_initialize_shared_memory(&self) -> bool {
+        self.definition.zero_initialize_shared_memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{Elem, FloatKind, Item},
+        prelude::KernelBuilder,
+        KernelSettings,
+    };
+
+    fn artifact() -> KernelArtifact {
+        let mut builder = KernelBuilder::default();
+        let input = builder.input_array(Item::new(Elem::Float(FloatKind::F32)));
+        let output = builder.output_array(Item::new(Elem::Float(FloatKind::F32)));
+        builder
+            .context
+            .register(crate::ir::Operator::Assign(crate::ir::UnaryOperator {
+                input: *input,
+                out: *output,
+            }));
+
+        KernelArtifact::new("copy", builder.build(KernelSettings::default()))
+    }
+
+    #[test]
+    fn artifact_is_loaded_unchanged() {
+        let artifact = artifact();
+
+        let loaded = KernelArtifact::from_json(&artifact.to_json()).unwrap();
+
+        assert_eq!(loaded.name, "copy");
+        assert_eq!(loaded.definition.body, artifact.definition.body);
+        assert_eq!(loaded.definition.inputs, artifact.definition.inputs);
+    }
+
+    #[test]
+    fn artifact_keeps_zero_initialized_shared_memory() {
+        let mut artifact = artifact();
+        artifact.definition.zero_initialize_shared_memory = true;
+
+        let loaded = KernelArtifact::from_json(&artifact.to_json()).unwrap();
+
+        assert!(loaded.zero_initialize_shared_memory());
+    }
+
+    #[test]
+    fn artifact_of_another_version_is_rejected() {
+        let json = artifact()
+            .to_json()
+            .replacen(r#""version":2"#, r#""version":0"#, 1);
+
+        let err = KernelArtifact::from_json(&json).unwrap_err();
+
+        assert!(matches!(
+            err,
+            ArtifactError::Version {
+                expected: 2,
+                actual: 0
+            }
+        ));
+    }
+}
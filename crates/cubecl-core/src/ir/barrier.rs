@@ -0,0 +1,292 @@
+use std::fmt::Display;
+
+use super::{Branch, CoopMma, Operation, Operator, Scope, Synchronization, Variable};
+
+/// A read of shared memory that may see a value written by another unit, since no
+/// [sync_units](Synchronization::SyncUnits) separates it from the write.
+///
+/// The analysis can't know which unit accesses which value, so a read is assumed to come from
+/// another unit when its index isn't the variable used by the write. Writing and reading back
+/// `shared[UNIT_POS]` is fine, while reading `shared[UNIT_POS + 1]` isn't.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingBarrier {
+    /// The operation writing to shared memory.
+    pub write: Operation,
+    /// The operation reading the written memory without synchronization.
+    pub read: Operation,
+}
+
+impl Display for MissingBarrier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` may read the shared memory written by `{}` in another unit, add a \
+             `sync_units()` in between",
+            self.read, self.write
+        )
+    }
+}
+
+impl Scope {
+    /// Find the shared memory reads that aren't separated from a write by a
+    /// [sync_units](Synchronization::SyncUnits).
+    ///
+    /// Each path through the branches is followed, and the body of synchronizing loops is
+    /// analyzed twice so that the writes at the end of an iteration reach the reads at the start
+    /// of the next one.
+    pub fn missing_barriers(&self) -> Vec<MissingBarrier> {
+        let mut analysis = BarrierAnalysis::default();
+        analysis.scope(self, Vec::new());
+        analysis.missing
+    }
+}
+
+/// An access to shared memory.
+#[derive(Clone, PartialEq)]
+struct Access {
+    memory: u16,
+    /// The variable indexed, either the shared memory or a slice of it.
+    base: Variable,
+    /// The index accessed, unknown for matrix loads.
+    index: Option<Variable>,
+}
+
+impl Access {
+    /// Whether the access may touch a value written by another unit through `write`.
+    ///
+    /// Only a unit indexing with the same variable is assumed to access its own value, since a
+    /// constant index is the same for all units.
+    fn conflicts_with(&self, write: &Access) -> bool {
+        let own = match self.index {
+            Some(Variable::ConstantScalar(_)) | None => false,
+            index => self.base == write.base && index == write.index,
+        };
+        self.memory == write.memory && !own
+    }
+}
+
+/// The writes not yet followed by a synchronization.
+type Pending = Vec<(Access, Operation)>;
+
+/// The pending writes at each exit of a scope.
+#[derive(Default)]
+struct Flow {
+    /// When the end of the scope is reached.
+    end: Option<Pending>,
+    breaks: Pending,
+    continues: Pending,
+}
+
+#[derive(Default)]
+struct BarrierAnalysis {
+    /// The shared memory id of each slice of shared memory.
+    slices: Vec<(Variable, u16)>,
+    missing: Vec<MissingBarrier>,
+}
+
+impl BarrierAnalysis {
+    fn scope(&mut self, scope: &Scope, mut pending: Pending) -> Flow {
+        let mut flow = Flow::default();
+
+        for operation in scope.operations.iter() {
+            match operation {
+                Operation::Synchronization(Synchronization::SyncUnits) => pending.clear(),
+                Operation::Branch(Branch::Break) => {
+                    merge(&mut flow.breaks, pending);
+                    return flow;
+                }
+                Operation::Branch(Branch::Continue) => {
+                    merge(&mut flow.continues, pending);
+                    return flow;
+                }
+                Operation::Branch(Branch::Return) => return flow,
+                Operation::Branch(Branch::If(branch)) => {
+                    let then = self.scope(&branch.scope, pending.clone());
+                    pending = self.join(&mut flow, [then], Some(pending));
+                }
+                Operation::Branch(Branch::IfElse(branch)) => {
+                    let then = self.scope(&branch.scope_if, pending.clone());
+                    let or_else = self.scope(&branch.scope_else, pending.clone());
+                    pending = self.join(&mut flow, [then, or_else], None);
+                }
+                Operation::Branch(Branch::Switch(switch)) => {
+                    let mut cases = vec![self.scope(&switch.scope_default, pending.clone())];
+                    for (_, case) in switch.cases.iter() {
+                        cases.push(self.scope(case, pending.clone()));
+                    }
+                    pending = self.join(&mut flow, cases, None);
+                }
+                Operation::Branch(Branch::RangeLoop(range_loop)) => {
+                    let (breaks, mut after) = self.body(&range_loop.scope, pending.clone());
+                    merge(&mut after, breaks);
+                    // The loop may not run at all.
+                    merge(&mut after, pending);
+                    pending = after;
+                }
+                Operation::Branch(Branch::Loop(body)) => {
+                    // A plain loop is only left with a `break`.
+                    let (breaks, _) = self.body(&body.scope, pending);
+                    pending = breaks;
+                }
+                operation => self.operation(operation, &mut pending),
+            }
+        }
+
+        flow.end = Some(pending);
+        flow
+    }
+
+    /// Analyze the body of a loop, returning the pending writes at its `break`s and at the end of
+    /// its iterations.
+    ///
+    /// A body synchronizing its units is analyzed twice, so that the writes after the last
+    /// synchronization reach the reads before the first one in the next iteration. Without
+    /// synchronization, the iterations of a unit usually access different values, such as in
+    /// loops striding over shared memory by `CUBE_DIM`, and aren't compared.
+    fn body(&mut self, scope: &Scope, pending: Pending) -> (Pending, Pending) {
+        let first = self.scope(scope, pending.clone());
+        let mut next = pending;
+        merge(&mut next, first.continues);
+        merge(&mut next, first.end.unwrap_or_default());
+
+        if !synchronizes(scope) {
+            return (first.breaks, next);
+        }
+
+        let second = self.scope(scope, next.clone());
+        let mut breaks = first.breaks;
+        merge(&mut breaks, second.breaks);
+        merge(&mut next, second.continues);
+        merge(&mut next, second.end.unwrap_or_default());
+
+        (breaks, next)
+    }
+
+    /// Merge the flows of the branches, `skipped` being the pending writes when no branch runs.
+    fn join(
+        &mut self,
+        flow: &mut Flow,
+        branches: impl IntoIterator<Item = Flow>,
+        skipped: Option<Pending>,
+    ) -> Pending {
+        let mut pending = skipped.unwrap_or_default();
+        for branch in branches {
+            merge(&mut flow.breaks, branch.breaks);
+            merge(&mut flow.continues, branch.continues);
+            if let Some(end) = branch.end {
+                merge(&mut pending, end);
+            }
+        }
+        pending
+    }
+
+    fn operation(&mut self, operation: &Operation, pending: &mut Pending) {
+        let (reads, writes) = self.accesses(operation);
+
+        for read in reads {
+            for (write, write_op) in pending.iter() {
+                if !read.conflicts_with(write) {
+                    continue;
+                }
+                let missing = MissingBarrier {
+                    write: write_op.clone(),
+                    read: operation.clone(),
+                };
+                if !self.missing.contains(&missing) {
+                    self.missing.push(missing);
+                }
+            }
+        }
+
+        for write in writes {
+            let write = (write, operation.clone());
+            if !pending.contains(&write) {
+                pending.push(write);
+            }
+        }
+    }
+
+    /// The shared memory read and written by the operation.
+    fn accesses(&mut self, operation: &Operation) -> (Vec<Access>, Vec<Access>) {
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+
+        match operation {
+            Operation::Operator(Operator::Index(op) | Operator::UncheckedIndex(op)) => {
+                reads.extend(self.access(op.lhs, Some(op.rhs)));
+            }
+            Operation::Operator(Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op)) => {
+                writes.extend(self.access(op.out, Some(op.lhs)));
+            }
+            Operation::Operator(Operator::Copy(op)) => {
+                reads.extend(self.access(op.input, Some(op.in_index)));
+                writes.extend(self.access(op.out, Some(op.out_index)));
+            }
+            Operation::Operator(Operator::CopyBulk(op) | Operator::CopyBulkAsync(op)) => {
+                reads.extend(self.access(op.input, Some(op.in_index)));
+                writes.extend(self.access(op.out, Some(op.out_index)));
+            }
+            Operation::Operator(Operator::Slice(op)) => {
+                if let Some(memory) = self.memory(op.input) {
+                    self.slices.push((op.out, memory));
+                }
+            }
+            // Matrix stores aren't tracked, since they are usually read back by the plane that
+            // stored them, which they synchronize.
+            Operation::CoopMma(CoopMma::Load { value, .. }) => {
+                reads.extend(self.access(*value, None));
+            }
+            _ => {}
+        }
+
+        (reads, writes)
+    }
+
+    fn access(&self, base: Variable, index: Option<Variable>) -> Option<Access> {
+        self.memory(base).map(|memory| Access {
+            memory,
+            base,
+            index,
+        })
+    }
+
+    /// The id of the shared memory the variable refers to, if any.
+    fn memory(&self, variable: Variable) -> Option<u16> {
+        match variable {
+            Variable::SharedMemory { id, .. } => Some(id),
+            Variable::Slice { .. } => self
+                .slices
+                .iter()
+                .rev()
+                .find(|(slice, _)| *slice == variable)
+                .map(|(_, memory)| *memory),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the scope or one of its children synchronizes the units.
+fn synchronizes(scope: &Scope) -> bool {
+    scope.operations.iter().any(|operation| match operation {
+        Operation::Synchronization(Synchronization::SyncUnits) => true,
+        Operation::Branch(Branch::If(branch)) => synchronizes(&branch.scope),
+        Operation::Branch(Branch::IfElse(branch)) => {
+            synchronizes(&branch.scope_if) || synchronizes(&branch.scope_else)
+        }
+        Operation::Branch(Branch::Switch(switch)) => {
+            synchronizes(&switch.scope_default)
+                || switch.cases.iter().any(|(_, case)| synchronizes(case))
+        }
+        Operation::Branch(Branch::RangeLoop(range_loop)) => synchronizes(&range_loop.scope),
+        Operation::Branch(Branch::Loop(body)) => synchronizes(&body.scope),
+        _ => false,
+    })
+}
+
+fn merge(pending: &mut Pending, other: Pending) {
+    for write in other {
+        if !pending.contains(&write) {
+            pending.push(write);
+        }
+    }
+}
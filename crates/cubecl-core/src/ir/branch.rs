@@ -23,6 +23,8 @@ pub enum Branch {
     Return,
     /// A break statement.
     Break,
+    /// A continue statement.
+    Continue,
 }
 
 impl Display for Branch {
@@ -56,6 +58,7 @@ impl Display for Branch {
             Branch::Loop(_) => write!(f, "loop{{}}"),
             Branch::Return => write!(f, "return"),
             Branch::Break => write!(f, "break"),
+            Branch::Continue => write!(f, "continue"),
         }
     }
 }
@@ -0,0 +1,334 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    Branch, CoopMma, Elem, KernelDefinition, MatrixIdent, Operation, Operator, Scope, Subcube,
+    Variable,
+};
+use crate::SUBCUBE_DIM_APPROX;
+
+/// The work done by each unit of a kernel, estimated from its IR.
+///
+/// The estimate is static: loops with constant bounds are counted for each of their iterations,
+/// other loops once, and the most expensive branch of a condition is assumed to be taken.
+/// Cooperative matrix operations are shared by the units of an approximate subcube.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KernelCost {
+    /// The number of bytes read from global memory.
+    pub bytes_read: u64,
+    /// The number of bytes written to global memory.
+    pub bytes_written: u64,
+    /// The number of floating point operations.
+    pub flops: u64,
+}
+
+impl KernelCost {
+    /// The number of bytes read or written in global memory.
+    pub fn bytes(&self) -> u64 {
+        self.bytes_read + self.bytes_written
+    }
+
+    /// The floating point operations per byte of global memory traffic, infinite when the
+    /// kernel doesn't access global memory.
+    pub fn arithmetic_intensity(&self) -> f64 {
+        self.flops as f64 / self.bytes() as f64
+    }
+
+    /// Whether the kernel is limited by the memory bandwidth rather than the arithmetic
+    /// throughput of a device, according to the roofline model.
+    ///
+    /// The device peak is given in floating point operations per second and its bandwidth in
+    /// bytes per second.
+    pub fn is_memory_bound(&self, peak_flops: f64, bandwidth: f64) -> bool {
+        self.arithmetic_intensity() < peak_flops / bandwidth
+    }
+
+    /// The roofline estimate of the floating point operations per second reached on a device,
+    /// bounded by its peak and by the bandwidth times the arithmetic intensity.
+    pub fn attainable_flops(&self, peak_flops: f64, bandwidth: f64) -> f64 {
+        f64::min(peak_flops, bandwidth * self.arithmetic_intensity())
+    }
+
+    fn add(&mut self, other: KernelCost) {
+        self.bytes_read = self.bytes_read.saturating_add(other.bytes_read);
+        self.bytes_written = self.bytes_written.saturating_add(other.bytes_written);
+        self.flops = self.flops.saturating_add(other.flops);
+    }
+
+    fn max(self, other: KernelCost) -> KernelCost {
+        KernelCost {
+            bytes_read: self.bytes_read.max(other.bytes_read),
+            bytes_written: self.bytes_written.max(other.bytes_written),
+            flops: self.flops.max(other.flops),
+        }
+    }
+
+    fn times(self, count: u64) -> KernelCost {
+        KernelCost {
+            bytes_read: self.bytes_read.saturating_mul(count),
+            bytes_written: self.bytes_written.saturating_mul(count),
+            flops: self.flops.saturating_mul(count),
+        }
+    }
+}
+
+impl Display for KernelCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes read, {} bytes written, {} flops per unit ({:.2} flops/byte)",
+            self.bytes_read,
+            self.bytes_written,
+            self.flops,
+            self.arithmetic_intensity()
+        )
+    }
+}
+
+impl KernelDefinition {
+    /// Estimate the [cost](KernelCost) of each unit of the kernel.
+    pub fn cost(&self) -> KernelCost {
+        let mut analysis = CostAnalysis::default();
+        // Functions are defined before their callers, so the cost of a call is always known.
+        for function in self.functions.iter() {
+            let cost = analysis.scope(&function.body);
+            analysis.functions.insert(function.name.clone(), cost);
+        }
+        analysis.scope(&self.body)
+    }
+}
+
+#[derive(Default)]
+struct CostAnalysis {
+    functions: HashMap<String, KernelCost>,
+    /// The slices of global memory.
+    global_slices: Vec<Variable>,
+}
+
+impl CostAnalysis {
+    fn scope(&mut self, scope: &Scope) -> KernelCost {
+        let mut cost = KernelCost::default();
+        for operation in scope.operations.iter() {
+            let operation = self.operation(operation);
+            cost.add(operation);
+        }
+        cost
+    }
+
+    fn operation(&mut self, operation: &Operation) -> KernelCost {
+        match operation {
+            Operation::Operator(operator) => self.operator(operator),
+            Operation::Branch(branch) => self.branch(branch),
+            Operation::Subcube(subcube) => match subcube {
+                Subcube::Sum(op) | Subcube::Prod(op) | Subcube::Min(op) | Subcube::Max(op) => {
+                    flops(op.input, 1)
+                }
+                _ => KernelCost::default(),
+            },
+            Operation::CoopMma(coop_mma) => self.coop_mma(coop_mma),
+            Operation::Call(call) => self.functions.get(&call.name).copied().unwrap_or_default(),
+            Operation::Metadata(_) | Operation::Synchronization(_) | Operation::Location(_) => {
+                KernelCost::default()
+            }
+        }
+    }
+
+    fn operator(&mut self, operator: &Operator) -> KernelCost {
+        match operator {
+            Operator::Index(op) | Operator::UncheckedIndex(op) if self.is_global(op.lhs) => {
+                read(op.out, 1)
+            }
+            Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op)
+                if self.is_global(op.out) =>
+            {
+                write(op.rhs, 1)
+            }
+            Operator::Copy(op) => {
+                let mut cost = KernelCost::default();
+                if self.is_global(op.input) {
+                    cost.add(read(op.input, 1));
+                }
+                if self.is_global(op.out) {
+                    cost.add(write(op.out, 1));
+                }
+                cost
+            }
+            Operator::CopyBulk(op) | Operator::CopyBulkAsync(op) => {
+                let mut cost = KernelCost::default();
+                if self.is_global(op.input) {
+                    cost.add(read(op.input, op.len as u64));
+                }
+                if self.is_global(op.out) {
+                    cost.add(write(op.out, op.len as u64));
+                }
+                cost
+            }
+            Operator::Slice(op) => {
+                if self.is_global(op.input) {
+                    self.global_slices.push(op.out);
+                }
+                KernelCost::default()
+            }
+            Operator::Add(op)
+            | Operator::Sub(op)
+            | Operator::Mul(op)
+            | Operator::Div(op)
+            | Operator::Powf(op)
+            | Operator::Modulo(op)
+            | Operator::Remainder(op)
+            | Operator::Max(op)
+            | Operator::Min(op)
+            | Operator::SaturatingAdd(op)
+            | Operator::SaturatingSub(op)
+            | Operator::WrappingAdd(op)
+            | Operator::WrappingSub(op)
+            | Operator::WrappingMul(op) => flops(op.out, 1),
+            Operator::Abs(op)
+            | Operator::Exp(op)
+            | Operator::Log(op)
+            | Operator::Log1p(op)
+            | Operator::Cos(op)
+            | Operator::Sin(op)
+            | Operator::Tanh(op)
+            | Operator::Sqrt(op)
+            | Operator::Round(op)
+            | Operator::Floor(op)
+            | Operator::Ceil(op)
+            | Operator::Erf(op)
+            | Operator::Erfc(op)
+            | Operator::Expm1(op)
+            | Operator::Tgamma(op)
+            | Operator::Lgamma(op)
+            | Operator::Recip(op)
+            | Operator::Neg(op)
+            | Operator::Determinant(op) => flops(op.out, 1),
+            Operator::Clamp(op) => flops(op.out, 2),
+            Operator::Fma(op) => flops(op.out, 2),
+            Operator::Dot(op) => flops(op.lhs, 2),
+            Operator::Magnitude(op) => flops(op.input, 2),
+            Operator::Normalize(op) => flops(op.input, 3),
+            Operator::MatrixMul(op) => {
+                // A square matrix of `n * n` elements times `rhs` elements.
+                let n = (op.lhs.vectorization_factor() as f64).sqrt() as u64;
+                flops(op.rhs, 2 * n)
+            }
+            _ => KernelCost::default(),
+        }
+    }
+
+    fn branch(&mut self, branch: &Branch) -> KernelCost {
+        match branch {
+            Branch::If(op) => self.scope(&op.scope),
+            Branch::IfElse(op) => {
+                let scope_if = self.scope(&op.scope_if);
+                scope_if.max(self.scope(&op.scope_else))
+            }
+            Branch::Switch(op) => {
+                let mut cost = self.scope(&op.scope_default);
+                for (_, case) in op.cases.iter() {
+                    cost = cost.max(self.scope(case));
+                }
+                cost
+            }
+            Branch::RangeLoop(op) => {
+                let iterations = constant(op.start)
+                    .zip(constant(op.end))
+                    .map(|(start, end)| {
+                        let end = end + op.inclusive as u64;
+                        let step = op.step.and_then(constant).unwrap_or(1).max(1);
+                        end.saturating_sub(start).div_ceil(step)
+                    })
+                    .unwrap_or(1);
+                self.scope(&op.scope).times(iterations)
+            }
+            Branch::Loop(op) => self.scope(&op.scope),
+            Branch::Select(_) | Branch::Return | Branch::Break | Branch::Continue => {
+                KernelCost::default()
+            }
+        }
+    }
+
+    fn coop_mma(&mut self, coop_mma: &CoopMma) -> KernelCost {
+        let units = SUBCUBE_DIM_APPROX as u64;
+        match coop_mma {
+            CoopMma::Load { mat, value, .. } if self.is_global(*value) => {
+                let (elems, elem) = matrix_elems(*mat);
+                read_bytes(elems * elem.size() as u64 / units)
+            }
+            CoopMma::Store { output, mat, .. } if self.is_global(*output) => {
+                let (elems, elem) = matrix_elems(*mat);
+                KernelCost {
+                    bytes_written: elems * elem.size() as u64 / units,
+                    ..Default::default()
+                }
+            }
+            CoopMma::Execute { mat_a, mat_b, .. } => match (mat_a, mat_b) {
+                (Variable::Matrix { mat: a, .. }, Variable::Matrix { mat: b, .. }) => KernelCost {
+                    flops: 2 * a.m as u64 * b.n as u64 * a.k as u64 / units,
+                    ..Default::default()
+                },
+                _ => KernelCost::default(),
+            },
+            _ => KernelCost::default(),
+        }
+    }
+
+    fn is_global(&self, variable: Variable) -> bool {
+        matches!(
+            variable,
+            Variable::GlobalInputArray { .. } | Variable::GlobalOutputArray { .. }
+        ) || self.global_slices.contains(&variable)
+    }
+}
+
+fn constant(variable: Variable) -> Option<u64> {
+    match variable {
+        Variable::ConstantScalar(value) => value.try_as_usize().map(|value| value as u64),
+        _ => None,
+    }
+}
+
+fn matrix_elems(mat: Variable) -> (u64, Elem) {
+    let Variable::Matrix { mat, .. } = mat else {
+        return (0, Elem::UInt);
+    };
+    let (rows, cols) = match mat.ident {
+        MatrixIdent::A => (mat.m, mat.k),
+        MatrixIdent::B => (mat.k, mat.n),
+        MatrixIdent::Accumulator => (mat.m, mat.n),
+    };
+    (rows as u64 * cols as u64, mat.elem)
+}
+
+fn read_bytes(bytes: u64) -> KernelCost {
+    KernelCost {
+        bytes_read: bytes,
+        ..Default::default()
+    }
+}
+
+fn read(variable: Variable, count: u64) -> KernelCost {
+    read_bytes(item_size(variable) * count)
+}
+
+fn write(variable: Variable, count: u64) -> KernelCost {
+    KernelCost {
+        bytes_written: item_size(variable) * count,
+        ..Default::default()
+    }
+}
+
+/// The floating point operations of `per_elem` operations on each element of the variable.
+fn flops(variable: Variable, per_elem: u64) -> KernelCost {
+    let flops = match variable.item().elem() {
+        Elem::Float(_) => variable.vectorization_factor() as u64 * per_elem,
+        _ => 0,
+    };
+    KernelCost {
+        flops,
+        ..Default::default()
+    }
+}
+
+fn item_size(variable: Variable) -> u64 {
+    variable.item().elem().size() as u64 * variable.vectorization_factor() as u64
+}
@@ -0,0 +1,21 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// The location in the Rust source of the statements expanded after it.
+///
+/// Only registered when debug info is enabled with the `CUBECL_DEBUG_INFO` environment variable,
+/// compilers emit it as a comment or as native debug info next to the generated instructions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
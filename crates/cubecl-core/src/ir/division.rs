@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    cpa, BinaryOperator, Binding, Branch, ConstantScalarValue, Elem, Item, KernelDefinition,
+    Location, Operation, Operator, Scope, Select, UnaryOperator, Variable, Visibility,
+};
+
+/// The result of a division by zero in [checked](crate::ExecutionMode::Checked) mode.
+///
+/// Without a policy, each backend has its own behavior: integer divisions by zero are undefined on
+/// CUDA and HIP, and return an implementation defined value on WGSL, while float divisions may not
+/// produce infinities on WGSL.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum DivisionPolicy {
+    /// Divisions are emitted as is, with the behavior of the backend.
+    #[default]
+    Native,
+    /// Divisions and remainders by zero return zero on every backend, for integers and floats,
+    /// even with an infinite or NaN numerator.
+    ///
+    /// Unchecked kernels aren't guarded. The divisions by zero of a launch are counted by
+    /// [launch_division_check](crate::prelude::KernelLauncher::launch_division_check).
+    Zero,
+}
+
+impl KernelDefinition {
+    /// Guard the divisions and remainders of the kernel according to its
+    /// [policy](DivisionPolicy).
+    ///
+    /// The divisor is selected to be one where it is zero, and the result to be zero there, so
+    /// vectorized divisions are guarded per element without branching. Divisions by a constant
+    /// other than zero aren't guarded.
+    pub fn guard_division(&mut self) {
+        if self.division == DivisionPolicy::Native {
+            return;
+        }
+
+        self.guard_scopes(None);
+    }
+
+    /// Guard the divisions and remainders of the kernel as with [DivisionPolicy::Zero], whatever
+    /// its policy and execution mode, and count the divisions by zero executed in a new atomic
+    /// output, the diagnostic flag of the launch.
+    ///
+    /// Only the divisions of the body are counted, the device functions it calls can't access
+    /// the output.
+    pub fn count_division_by_zero(&mut self) {
+        let counter = Variable::GlobalOutputArray {
+            id: self.outputs.len() as u16,
+            item: Item::new(Elem::AtomicUInt),
+        };
+        self.outputs.push(Binding {
+            location: Location::Storage,
+            visibility: Visibility::ReadWrite,
+            item: counter.item(),
+            size: None,
+            rank: Some(1),
+        });
+
+        self.guard_scopes(Some(counter));
+        // The divisions are already guarded.
+        self.division = DivisionPolicy::Native;
+    }
+
+    fn guard_scopes(&mut self, counter: Option<Variable>) {
+        guard_scope(&mut self.body, counter);
+        for function in self.functions.iter_mut() {
+            guard_scope(&mut function.body, None);
+        }
+    }
+}
+
+fn guard_scope(scope: &mut Scope, counter: Option<Variable>) {
+    for operation in core::mem::take(&mut scope.operations) {
+        match operation {
+            Operation::Operator(Operator::Div(op)) => guard(scope, op, Operator::Div, counter),
+            Operation::Operator(Operator::Modulo(op)) => {
+                guard(scope, op, Operator::Modulo, counter)
+            }
+            Operation::Operator(Operator::Remainder(op)) => {
+                guard(scope, op, Operator::Remainder, counter)
+            }
+            Operation::Branch(mut branch) => {
+                match &mut branch {
+                    Branch::If(op) => guard_scope(&mut op.scope, counter),
+                    Branch::IfElse(op) => {
+                        guard_scope(&mut op.scope_if, counter);
+                        guard_scope(&mut op.scope_else, counter);
+                    }
+                    Branch::Switch(op) => {
+                        for (_, case) in op.cases.iter_mut() {
+                            guard_scope(case, counter);
+                        }
+                        guard_scope(&mut op.scope_default, counter);
+                    }
+                    Branch::RangeLoop(op) => guard_scope(&mut op.scope, counter),
+                    Branch::Loop(op) => guard_scope(&mut op.scope, counter),
+                    Branch::Select(_) | Branch::Return | Branch::Break | Branch::Continue => {}
+                }
+                scope.register(branch);
+            }
+            operation => scope.register(operation),
+        }
+    }
+}
+
+fn guard(
+    scope: &mut Scope,
+    op: BinaryOperator,
+    divide: fn(BinaryOperator) -> Operator,
+    counter: Option<Variable>,
+) {
+    let divisor = op.rhs;
+    let elem = divisor.item().elem();
+    if divisor.as_const().is_some_and(|value| !value.is_zero()) {
+        scope.register(divide(op));
+        return;
+    }
+
+    let vectorization = divisor.item().vectorization;
+    let is_zero = scope.create_local(Item::vectorized(Elem::Bool, vectorization));
+    scope.register(Operator::Equal(BinaryOperator {
+        lhs: divisor,
+        rhs: constant(0, elem),
+        out: is_zero,
+    }));
+    if let Some(counter) = counter {
+        count(scope, is_zero, counter);
+    }
+
+    // Selecting the result instead of scaling it keeps infinite and NaN numerators from turning
+    // the zero into a NaN.
+    let safe_divisor = scope.create_local(divisor.item());
+    scope.register(Branch::Select(Select {
+        cond: is_zero,
+        then: constant(1, elem),
+        or_else: divisor,
+        out: safe_divisor,
+    }));
+    let quotient = scope.create_local(op.out.item());
+    scope.register(divide(BinaryOperator {
+        lhs: op.lhs,
+        rhs: safe_divisor,
+        out: quotient,
+    }));
+    scope.register(Branch::Select(Select {
+        cond: is_zero,
+        then: constant(0, op.out.item().elem()),
+        or_else: quotient,
+        out: op.out,
+    }));
+}
+
+/// Add the number of elements of the divisor that are zero to the counter.
+fn count(scope: &mut Scope, is_zero: Variable, counter: Variable) {
+    let vectorization = is_zero.item().vectorization;
+    let zeros = scope.create_local(Item::vectorized(Elem::UInt, vectorization));
+    scope.register(Operator::Assign(UnaryOperator {
+        input: is_zero,
+        out: zeros,
+    }));
+
+    let total = scope.create_local(Elem::UInt);
+    match vectorization {
+        Some(vectorization) => {
+            let lane = scope.create_local(Elem::UInt);
+            cpa!(scope, total = zeros[0u32]);
+            for index in 1..vectorization.get() as u32 {
+                cpa!(scope, lane = zeros[index]);
+                cpa!(scope, total = total + lane);
+            }
+        }
+        None => {
+            cpa!(scope, total = zeros);
+        }
+    }
+
+    let any = scope.create_local(Elem::Bool);
+    cpa!(scope, any = total != 0u32);
+    cpa!(scope, if(any).then(|scope| {
+        let pointer = scope.create_local_undeclared(Item::new(Elem::AtomicUInt));
+        let previous = scope.create_local(Elem::UInt);
+        cpa!(scope, pointer = counter[0u32]);
+        scope.register(Operator::AtomicAdd(BinaryOperator {
+            lhs: pointer,
+            rhs: total,
+            out: previous,
+        }));
+    }));
+}
+
+fn constant(value: u32, elem: Elem) -> Variable {
+    Variable::ConstantScalar(ConstantScalarValue::UInt(value as u64).cast_to(elem))
+}
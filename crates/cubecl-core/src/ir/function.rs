@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use super::{Scope, Variable};
+use serde::{Deserialize, Serialize};
+
+/// A device function that isn't inlined into the kernel body.
+///
+/// The inputs and the output are local variables of the function body. Compilers declare the
+/// inputs as parameters and return the output at the end of the body.
+///
+/// The topology values read by the body, listed in `builtins`, are received by the last inputs,
+/// which calls fill after the arguments.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub builtins: Vec<Variable>,
+    pub body: Scope,
+}
+
+/// A call to a [device function](FunctionDefinition).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct FunctionCall {
+    pub name: String,
+    pub args: Vec<Variable>,
+    pub out: Option<Variable>,
+}
+
+impl Display for FunctionCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(out) = &self.out {
+            write!(f, "{out} = ")?;
+        }
+
+        write!(f, "{}(", self.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{arg}")?;
+        }
+        f.write_str(")")
+    }
+}
@@ -1,4 +1,4 @@
-use super::{ConstantScalarValue, Scope, Variable};
+use super::{ConstantScalarValue, DivisionPolicy, FunctionDefinition, Scope, Variable};
 use crate::SUBCUBE_DIM_APPROX;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -11,7 +11,32 @@ pub struct KernelDefinition {
     pub outputs: Vec<Binding>,
     pub named: Vec<(String, Binding)>,
     pub cube_dim: CubeDim,
+    /// The number of bytes of dynamic shared memory chosen at launch.
+    pub dynamic_shared_memory: u32,
+    /// Whether the shared memory is zero-initialized before the kernel runs.
+    pub zero_initialize_shared_memory: bool,
+    /// The floating point semantics the kernel is compiled with.
+    pub fast_math: FastMath,
+    /// The result of divisions by zero in checked mode.
+    pub division: DivisionPolicy,
     pub body: Scope,
+    /// The device functions called by the body, callees before callers.
+    pub functions: Vec<FunctionDefinition>,
+}
+
+/// The floating point semantics of a kernel, trading accuracy for speed.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum FastMath {
+    /// Every backend follows the accuracy of its standard math functions.
+    #[default]
+    Strict,
+    /// The `f32` functions are computed with their approximate variants, such as an `exp2` based
+    /// `exp` and a division by a reciprocal, whose results are undefined for infinite, NaN and
+    /// denormal values.
+    ///
+    /// CUDA uses its intrinsics, like `__expf` and `__fdividef`, and WGSL replaces the extensions
+    /// handling the special cases of `powf` with its plain formula.
+    Relaxed,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -246,6 +271,8 @@ pub struct Binding {
     pub visibility: Visibility,
     pub item: Item,
     pub size: Option<usize>,
+    /// The rank expected for the data, or `None` when any rank is accepted.
+    pub rank: Option<usize>,
 }
 
 #[derive(new, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash)]
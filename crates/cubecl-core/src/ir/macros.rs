@@ -323,6 +323,30 @@ macro_rules! cpa {
             cpa!(unary $input, $out)
         ));
     };
+    // out = erfc(input)
+    ($scope:expr, $out:ident = erfc($input:expr)) => {
+        $scope.register($crate::ir::Operator::Erfc(
+            cpa!(unary $input, $out)
+        ));
+    };
+    // out = expm1(input)
+    ($scope:expr, $out:ident = expm1($input:expr)) => {
+        $scope.register($crate::ir::Operator::Expm1(
+            cpa!(unary $input, $out)
+        ));
+    };
+    // out = tgamma(input)
+    ($scope:expr, $out:ident = tgamma($input:expr)) => {
+        $scope.register($crate::ir::Operator::Tgamma(
+            cpa!(unary $input, $out)
+        ));
+    };
+    // out = lgamma(input)
+    ($scope:expr, $out:ident = lgamma($input:expr)) => {
+        $scope.register($crate::ir::Operator::Lgamma(
+            cpa!(unary $input, $out)
+        ));
+    };
     // out = input
     ($scope:expr, $out:ident = $input:ident) => {
         $scope.register($crate::ir::Operator::Assign(
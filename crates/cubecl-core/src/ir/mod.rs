@@ -1,23 +1,40 @@
+mod artifact;
+mod barrier;
 mod branch;
 mod cmma;
+mod cost;
+mod debug;
+mod division;
+mod function;
 mod kernel;
 mod local_allocator;
 mod macros;
 mod operation;
+mod pass;
+mod printer;
 mod processing;
 mod scope;
 mod subcube;
 mod synchronization;
+mod uniformity;
 mod variable;
 
+pub use artifact::*;
+pub use barrier::*;
 pub use branch::*;
 pub use cmma::*;
+pub use cost::*;
+pub use debug::*;
+pub use division::*;
+pub use function::*;
 pub use kernel::*;
 pub use local_allocator::*;
 pub use operation::*;
+pub use pass::*;
 pub use scope::*;
 pub use subcube::*;
 pub use synchronization::*;
+pub use uniformity::*;
 pub use variable::*;
 
 pub(crate) use macros::cpa;
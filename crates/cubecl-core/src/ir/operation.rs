@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use super::{Branch, CoopMma, Subcube, Synchronization, Variable};
+use super::{Branch, CoopMma, FunctionCall, SourceLocation, Subcube, Synchronization, Variable};
 use serde::{Deserialize, Serialize};
 
 /// All operations that can be used in a GPU compute shader.
@@ -20,6 +20,8 @@ pub enum Operation {
     Synchronization(Synchronization),
     Subcube(Subcube),
     CoopMma(CoopMma),
+    Call(FunctionCall),
+    Location(SourceLocation),
 }
 
 impl Display for Operation {
@@ -31,6 +33,8 @@ impl Display for Operation {
             Operation::Synchronization(synchronization) => write!(f, "{synchronization}"),
             Operation::Subcube(subcube) => write!(f, "{subcube}"),
             Operation::CoopMma(coop_mma) => write!(f, "{coop_mma}"),
+            Operation::Call(call) => write!(f, "{call}"),
+            Operation::Location(location) => write!(f, "// {location}"),
         }
     }
 }
@@ -45,6 +49,8 @@ impl Operation {
             Operation::Synchronization(_) => None,
             Operation::Subcube(subcube) => subcube.out(),
             Operation::CoopMma(_) => None,
+            Operation::Call(call) => call.out,
+            Operation::Location(_) => None,
         }
     }
 }
@@ -71,6 +77,10 @@ pub enum Operator {
     Floor(UnaryOperator),
     Ceil(UnaryOperator),
     Erf(UnaryOperator),
+    Erfc(UnaryOperator),
+    Expm1(UnaryOperator),
+    Tgamma(UnaryOperator),
+    Lgamma(UnaryOperator),
     Recip(UnaryOperator),
     Equal(BinaryOperator),
     NotEqual(BinaryOperator),
@@ -84,10 +94,15 @@ pub enum Operator {
     Index(BinaryOperator),
     Copy(CopyOperator),
     CopyBulk(CopyBulkOperator),
+    /// Bulk copy that may complete asynchronously. The copied values are only visible once the
+    /// copy is committed and awaited.
+    CopyBulkAsync(CopyBulkOperator),
     Slice(SliceOperator),
     UncheckedIndex(BinaryOperator),
     IndexAssign(BinaryOperator),
     InitLine(LineInitOperator),
+    Swizzle(SwizzleOperator),
+    SwizzleAssign(SwizzleOperator),
     UncheckedIndexAssign(BinaryOperator),
     And(BinaryOperator),
     Or(BinaryOperator),
@@ -95,13 +110,40 @@ pub enum Operator {
     Neg(UnaryOperator),
     Max(BinaryOperator),
     Min(BinaryOperator),
+    /// Integer addition clamped to the range of the type instead of overflowing.
+    SaturatingAdd(BinaryOperator),
+    /// Integer subtraction clamped to the range of the type instead of overflowing.
+    SaturatingSub(BinaryOperator),
+    /// Integer addition wrapping around on overflow, in two's complement for signed integers.
+    WrappingAdd(BinaryOperator),
+    /// Integer subtraction wrapping around on overflow, in two's complement for signed integers.
+    WrappingSub(BinaryOperator),
+    /// Integer multiplication wrapping around on overflow, in two's complement for signed
+    /// integers.
+    WrappingMul(BinaryOperator),
     BitwiseAnd(BinaryOperator),
     BitwiseOr(BinaryOperator),
     BitwiseXor(BinaryOperator),
     ShiftLeft(BinaryOperator),
     ShiftRight(BinaryOperator),
+    /// Rotate the bits of `lhs` to the left by the `u32` amount `rhs`, modulo the bit width.
+    RotateLeft(BinaryOperator),
+    /// Rotate the bits of `lhs` to the right by the `u32` amount `rhs`, modulo the bit width.
+    RotateRight(BinaryOperator),
+    BitfieldExtract(BitfieldExtractOperator),
+    BitfieldInsert(BitfieldInsertOperator),
     Remainder(BinaryOperator),
     Bitcast(UnaryOperator),
+    /// Convert a line of two `f32` to `f16`, rounding to nearest even, and pack their bits in a
+    /// `u32`, the first element in the low half.
+    PackF16(UnaryOperator),
+    /// Unpack the two `f16` of a `u32` to a line of two `f32`, the low half first.
+    UnpackF16(UnaryOperator),
+    /// Convert a line of two `f32` to `bf16`, rounding to nearest even, and pack their bits in a
+    /// `u32`, the first element in the low half.
+    PackBf16(UnaryOperator),
+    /// Unpack the two `bf16` of a `u32` to a line of two `f32`, the low half first.
+    UnpackBf16(UnaryOperator),
     AtomicLoad(UnaryOperator),
     AtomicStore(UnaryOperator),
     AtomicSwap(BinaryOperator),
@@ -116,6 +158,13 @@ pub enum Operator {
     Magnitude(UnaryOperator),
     Normalize(UnaryOperator),
     Dot(BinaryOperator),
+    /// Multiply a square matrix by a matrix or a vector. Matrices are stored column-major in
+    /// lines of 4, 9 or 16 elements.
+    MatrixMul(BinaryOperator),
+    /// Transpose a square matrix stored in a line.
+    Transpose(UnaryOperator),
+    /// The determinant of a square matrix stored in a line.
+    Determinant(UnaryOperator),
 }
 
 impl Operator {
@@ -139,11 +188,18 @@ impl Operator {
             | Operator::UncheckedIndexAssign(binary_operator)
             | Operator::Max(binary_operator)
             | Operator::Min(binary_operator)
+            | Operator::SaturatingAdd(binary_operator)
+            | Operator::SaturatingSub(binary_operator)
+            | Operator::WrappingAdd(binary_operator)
+            | Operator::WrappingSub(binary_operator)
+            | Operator::WrappingMul(binary_operator)
             | Operator::BitwiseAnd(binary_operator)
             | Operator::BitwiseOr(binary_operator)
             | Operator::BitwiseXor(binary_operator)
             | Operator::ShiftLeft(binary_operator)
             | Operator::ShiftRight(binary_operator)
+            | Operator::RotateLeft(binary_operator)
+            | Operator::RotateRight(binary_operator)
             | Operator::Remainder(binary_operator)
             | Operator::And(binary_operator)
             | Operator::Or(binary_operator)
@@ -155,7 +211,8 @@ impl Operator {
             | Operator::AtomicAnd(binary_operator)
             | Operator::AtomicOr(binary_operator)
             | Operator::AtomicXor(binary_operator)
-            | Operator::Dot(binary_operator) => binary_operator.out,
+            | Operator::Dot(binary_operator)
+            | Operator::MatrixMul(binary_operator) => binary_operator.out,
 
             Operator::Abs(unary_operator)
             | Operator::Exp(unary_operator)
@@ -169,21 +226,37 @@ impl Operator {
             | Operator::Floor(unary_operator)
             | Operator::Ceil(unary_operator)
             | Operator::Erf(unary_operator)
+            | Operator::Erfc(unary_operator)
+            | Operator::Expm1(unary_operator)
+            | Operator::Tgamma(unary_operator)
+            | Operator::Lgamma(unary_operator)
             | Operator::Recip(unary_operator)
             | Operator::Assign(unary_operator)
             | Operator::Not(unary_operator)
             | Operator::Neg(unary_operator)
             | Operator::Bitcast(unary_operator)
+            | Operator::PackF16(unary_operator)
+            | Operator::UnpackF16(unary_operator)
+            | Operator::PackBf16(unary_operator)
+            | Operator::UnpackBf16(unary_operator)
             | Operator::AtomicLoad(unary_operator)
             | Operator::AtomicStore(unary_operator)
             | Operator::Magnitude(unary_operator)
-            | Operator::Normalize(unary_operator) => unary_operator.out,
+            | Operator::Normalize(unary_operator)
+            | Operator::Transpose(unary_operator)
+            | Operator::Determinant(unary_operator) => unary_operator.out,
 
             Operator::Clamp(clamp_operator) => clamp_operator.out,
+            Operator::BitfieldExtract(bitfield_operator) => bitfield_operator.out,
+            Operator::BitfieldInsert(bitfield_operator) => bitfield_operator.out,
             Operator::Copy(copy_operator) => copy_operator.out,
             Operator::CopyBulk(copy_bulk_operator) => copy_bulk_operator.out,
+            Operator::CopyBulkAsync(copy_bulk_operator) => copy_bulk_operator.out,
             Operator::Slice(slice_operator) => slice_operator.out,
             Operator::InitLine(line_init_operator) => line_init_operator.out,
+            Operator::Swizzle(swizzle_operator) | Operator::SwizzleAssign(swizzle_operator) => {
+                swizzle_operator.out
+            }
             Operator::AtomicCompareAndSwap(op) => op.out,
             Operator::Fma(fma_operator) => fma_operator.out,
         };
@@ -212,6 +285,10 @@ impl Display for Operator {
             Operator::Floor(op) => write!(f, "{} = {}.floor()", op.out, op.input),
             Operator::Ceil(op) => write!(f, "{} = {}.ceil()", op.out, op.input),
             Operator::Erf(op) => write!(f, "{} = {}.erf()", op.out, op.input),
+            Operator::Erfc(op) => write!(f, "{} = {}.erfc()", op.out, op.input),
+            Operator::Expm1(op) => write!(f, "{} = {}.expm1()", op.out, op.input),
+            Operator::Tgamma(op) => write!(f, "{} = {}.tgamma()", op.out, op.input),
+            Operator::Lgamma(op) => write!(f, "{} = {}.lgamma()", op.out, op.input),
             Operator::Recip(op) => write!(f, "{} = {}.recip()", op.out, op.input),
             Operator::Equal(op) => write!(f, "{} = {} == {}", op.out, op.lhs, op.rhs),
             Operator::NotEqual(op) => write!(f, "{} = {} != {}", op.out, op.lhs, op.rhs),
@@ -237,6 +314,11 @@ impl Display for Operator {
                 "memcpy({}[{}], {}[{}], {})",
                 op.out, op.input, op.in_index, op.out_index, op.len
             ),
+            Operator::CopyBulkAsync(op) => write!(
+                f,
+                "memcpy_async({}[{}], {}[{}], {})",
+                op.out, op.out_index, op.input, op.in_index, op.len
+            ),
             Operator::Slice(op) => write!(f, "{} = {}[{}..{}]", op.out, op.input, op.start, op.end),
             Operator::UncheckedIndex(op) => {
                 write!(f, "{} = unchecked {}[{}]", op.out, op.lhs, op.rhs)
@@ -251,13 +333,48 @@ impl Display for Operator {
             Operator::Neg(op) => write!(f, "{} = -{}", op.out, op.input),
             Operator::Max(op) => write!(f, "{} = {}.max({})", op.out, op.lhs, op.rhs),
             Operator::Min(op) => write!(f, "{} = {}.min({})", op.out, op.lhs, op.rhs),
+            Operator::SaturatingAdd(op) => {
+                write!(f, "{} = {}.saturating_add({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::SaturatingSub(op) => {
+                write!(f, "{} = {}.saturating_sub({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::WrappingAdd(op) => {
+                write!(f, "{} = {}.wrapping_add({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::WrappingSub(op) => {
+                write!(f, "{} = {}.wrapping_sub({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::WrappingMul(op) => {
+                write!(f, "{} = {}.wrapping_mul({})", op.out, op.lhs, op.rhs)
+            }
             Operator::BitwiseAnd(op) => write!(f, "{} = {} & {}", op.out, op.lhs, op.rhs),
             Operator::BitwiseOr(op) => write!(f, "{} = {} | {}", op.out, op.lhs, op.rhs),
             Operator::BitwiseXor(op) => write!(f, "{} = {} ^ {}", op.out, op.lhs, op.rhs),
             Operator::ShiftLeft(op) => write!(f, "{} = {} << {}", op.out, op.lhs, op.rhs),
             Operator::ShiftRight(op) => write!(f, "{} = {} >> {}", op.out, op.lhs, op.rhs),
+            Operator::RotateLeft(op) => {
+                write!(f, "{} = {}.rotate_left({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::RotateRight(op) => {
+                write!(f, "{} = {}.rotate_right({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::BitfieldExtract(op) => write!(
+                f,
+                "{} = extract_bits({}, {}, {})",
+                op.out, op.input, op.offset, op.count
+            ),
+            Operator::BitfieldInsert(op) => write!(
+                f,
+                "{} = insert_bits({}, {}, {}, {})",
+                op.out, op.base, op.insert, op.offset, op.count
+            ),
             Operator::Remainder(op) => write!(f, "{} = {} rem {}", op.out, op.lhs, op.rhs),
             Operator::Bitcast(op) => write!(f, "{} = bitcast({})", op.out, op.input),
+            Operator::PackF16(op) => write!(f, "{} = pack_f16({})", op.out, op.input),
+            Operator::UnpackF16(op) => write!(f, "{} = unpack_f16({})", op.out, op.input),
+            Operator::PackBf16(op) => write!(f, "{} = pack_bf16({})", op.out, op.input),
+            Operator::UnpackBf16(op) => write!(f, "{} = unpack_bf16({})", op.out, op.input),
             Operator::AtomicLoad(op) => write!(f, "{} = atomic_load({})", op.out, op.input),
             Operator::AtomicStore(op) => write!(f, "atomic_store({}, {})", op.out, op.input),
             Operator::AtomicSwap(op) => {
@@ -278,6 +395,11 @@ impl Display for Operator {
             Operator::Magnitude(op) => write!(f, "{} = {}.length()", op.out, op.input),
             Operator::Normalize(op) => write!(f, "{} = {}.normalize()", op.out, op.input),
             Operator::Dot(op) => write!(f, "{} = {}.dot({})", op.out, op.lhs, op.rhs),
+            Operator::MatrixMul(op) => {
+                write!(f, "{} = {}.matrix_mul({})", op.out, op.lhs, op.rhs)
+            }
+            Operator::Transpose(op) => write!(f, "{} = {}.transpose()", op.out, op.input),
+            Operator::Determinant(op) => write!(f, "{} = {}.determinant()", op.out, op.input),
             Operator::InitLine(init) => {
                 let inits = init
                     .inputs
@@ -286,6 +408,14 @@ impl Display for Operator {
                     .collect::<Vec<_>>();
                 write!(f, "{} = vec({})", init.out, inits.join(", "))
             }
+            Operator::Swizzle(op) => {
+                let components = op.components();
+                write!(f, "{} = {}.swizzle({components})", op.out, op.input)
+            }
+            Operator::SwizzleAssign(op) => {
+                let components = op.components();
+                write!(f, "{}.swizzle({components}) = {}", op.out, op.input)
+            }
         }
     }
 }
@@ -361,6 +491,29 @@ pub struct LineInitOperator {
     pub inputs: Vec<Variable>,
 }
 
+/// Reads or writes the given components of a vectorized value.
+///
+/// A [swizzle](Operator::Swizzle) creates the output from the input components in order, so its
+/// vectorization is the number of components. A [swizzle assign](Operator::SwizzleAssign) writes
+/// each element of the input to the matching component of the output, broadcasting scalar inputs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct SwizzleOperator {
+    pub input: Variable,
+    pub components: Vec<u32>,
+    pub out: Variable,
+}
+
+impl SwizzleOperator {
+    fn components(&self) -> String {
+        self.components
+            .iter()
+            .map(|component| component.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(missing_docs)]
 pub struct CopyOperator {
@@ -389,6 +542,29 @@ pub struct ClampOperator {
     pub out: Variable,
 }
 
+/// Extract `count` bits of `input` starting at the bit `offset`, sign extended for signed
+/// integers. The `u32` offset and count are clamped to the bit width.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct BitfieldExtractOperator {
+    pub input: Variable,
+    pub offset: Variable,
+    pub count: Variable,
+    pub out: Variable,
+}
+
+/// Replace `count` bits of `base` starting at the bit `offset` with the lowest bits of `insert`.
+/// The `u32` offset and count are clamped to the bit width.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(missing_docs)]
+pub struct BitfieldInsertOperator {
+    pub base: Variable,
+    pub insert: Variable,
+    pub offset: Variable,
+    pub count: Variable,
+    pub out: Variable,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[allow(missing_docs)]
 pub struct SliceOperator {
@@ -448,6 +624,18 @@ impl From<Synchronization> for Operation {
     }
 }
 
+impl From<FunctionCall> for Operation {
+    fn from(value: FunctionCall) -> Self {
+        Self::Call(value)
+    }
+}
+
+impl From<SourceLocation> for Operation {
+    fn from(value: SourceLocation) -> Self {
+        Self::Location(value)
+    }
+}
+
 impl From<Metadata> for Operation {
     fn from(val: Metadata) -> Self {
         Operation::Metadata(val)
@@ -0,0 +1,191 @@
+use std::sync::RwLock;
+
+use alloc::sync::Arc;
+use cubecl_runtime::ExecutionMode;
+
+use crate::codegen::CompilationOptions;
+
+use super::KernelDefinition;
+
+/// The passes of the kernels compiled from now on, `None` until they are first changed.
+static GLOBAL: RwLock<Option<PassManager>> = RwLock::new(None);
+
+/// A transformation of the IR of a kernel, applied after its expansion and before its
+/// compilation by the backend.
+///
+/// Passes are registered in the [pass manager](PassManager) of the process, so downstream crates
+/// can add their own, e.g. a strength reduction specific to their domain.
+pub trait Pass: Send + Sync {
+    /// The name of the pass, used to place other passes relative to it.
+    fn name(&self) -> &str;
+
+    /// Transform the kernel, compiled with the options and mode.
+    fn apply(
+        &self,
+        kernel: &mut KernelDefinition,
+        options: &CompilationOptions,
+        mode: ExecutionMode,
+    );
+}
+
+/// [Lowers](KernelDefinition::lower_subcube) the subcube operations for devices without subcube
+/// support.
+#[derive(Debug, Clone, Copy)]
+pub struct LowerSubcube;
+
+impl Pass for LowerSubcube {
+    fn name(&self) -> &str {
+        "lower_subcube"
+    }
+
+    fn apply(
+        &self,
+        kernel: &mut KernelDefinition,
+        options: &CompilationOptions,
+        _mode: ExecutionMode,
+    ) {
+        if !options.subcube {
+            kernel.lower_subcube();
+        }
+    }
+}
+
+/// [Guards](KernelDefinition::guard_division) the divisions of checked kernels.
+#[derive(Debug, Clone, Copy)]
+pub struct GuardDivision;
+
+impl Pass for GuardDivision {
+    fn name(&self) -> &str {
+        "guard_division"
+    }
+
+    fn apply(
+        &self,
+        kernel: &mut KernelDefinition,
+        _options: &CompilationOptions,
+        mode: ExecutionMode,
+    ) {
+        if mode == ExecutionMode::Checked {
+            kernel.guard_division();
+        }
+    }
+}
+
+/// The ordered passes applied to the kernels before their compilation.
+///
+/// The [default](PassManager::default) passes are the ones every kernel needs,
+/// [LowerSubcube] then [GuardDivision]. Custom passes are inserted relative to them, in the
+/// [global](PassManager::global) manager used by the runtimes:
+///
+/// ```ignore
+/// PassManager::update_global(|passes| {
+///     passes.insert_before("guard_division", Arc::new(MyStrengthReduction));
+/// });
+/// ```
+///
+/// Compiled kernels are cached by their [id](crate::KernelId), so the passes should be registered
+/// before the first kernel is launched.
+#[derive(Clone)]
+pub struct PassManager {
+    passes: Vec<Arc<dyn Pass>>,
+}
+
+impl Default for PassManager {
+    fn default() -> Self {
+        Self {
+            passes: vec![Arc::new(LowerSubcube), Arc::new(GuardDivision)],
+        }
+    }
+}
+
+impl core::fmt::Debug for PassManager {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PassManager {
+    /// A manager without any pass, not even the default ones.
+    pub fn empty() -> Self {
+        Self { passes: Vec::new() }
+    }
+
+    /// The passes applied to the kernels compiled by the runtimes.
+    pub fn global() -> Self {
+        GLOBAL.read().unwrap().clone().unwrap_or_default()
+    }
+
+    /// Change the passes applied to the kernels compiled from now on by the runtimes.
+    pub fn update_global(update: impl FnOnce(&mut PassManager)) {
+        let mut global = GLOBAL.write().unwrap();
+        update(global.get_or_insert_with(PassManager::default));
+    }
+
+    /// The names of the passes, in the order they are applied.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.passes.iter().map(|pass| pass.name())
+    }
+
+    /// Add the pass after all the others.
+    pub fn push(&mut self, pass: Arc<dyn Pass>) {
+        self.passes.push(pass);
+    }
+
+    /// Add the pass just before the pass with the name.
+    ///
+    /// # Panics
+    ///
+    /// When there is no pass with the name.
+    pub fn insert_before(&mut self, name: &str, pass: Arc<dyn Pass>) {
+        let index = self.position(name);
+        self.passes.insert(index, pass);
+    }
+
+    /// Add the pass just after the pass with the name.
+    ///
+    /// # Panics
+    ///
+    /// When there is no pass with the name.
+    pub fn insert_after(&mut self, name: &str, pass: Arc<dyn Pass>) {
+        let index = self.position(name);
+        self.passes.insert(index + 1, pass);
+    }
+
+    /// Remove the pass with the name, returning it when there was one.
+    pub fn remove(&mut self, name: &str) -> Option<Arc<dyn Pass>> {
+        let index = self.passes.iter().position(|pass| pass.name() == name)?;
+        Some(self.passes.remove(index))
+    }
+
+    /// Apply the passes to the kernel, in order.
+    pub fn apply(
+        &self,
+        kernel: &mut KernelDefinition,
+        options: &CompilationOptions,
+        mode: ExecutionMode,
+    ) {
+        for pass in self.passes.iter() {
+            let _span = cubecl_runtime::trace_span!("pass", name = pass.name());
+            pass.apply(kernel, options, mode);
+        }
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.passes
+            .iter()
+            .position(|pass| pass.name() == name)
+            .unwrap_or_else(|| panic!("No pass named {name}, the passes are {self:?}"))
+    }
+}
+
+impl KernelDefinition {
+    /// Apply the [passes](PassManager) to the kernel before its compilation.
+    pub fn apply_passes(
+        &mut self,
+        passes: &PassManager,
+        options: &CompilationOptions,
+        mode: ExecutionMode,
+    ) {
+        passes.apply(self, options, mode);
+    }
+}
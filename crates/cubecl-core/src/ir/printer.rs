@@ -0,0 +1,286 @@
+use std::fmt::{Display, Write};
+
+use super::{Branch, Operation, RangeLoop, Scope, Variable};
+
+/// The number of spaces of each level of nesting.
+const INDENT: usize = 4;
+
+/// Renders the scope as readable code, with the nested scopes of the control flow indented.
+///
+/// The declared locals are listed first with their type, and the values defined by an operation,
+/// such as bindings and the SSA versions of locals, are prefixed with `let` and their type:
+///
+/// ```text
+/// let mut local(0, 1): f32
+/// let binding(1, 1): bool = input(0) > 0
+/// if binding(1, 1) {
+///     local(0, 1) = input(0) * 2
+/// }
+/// ```
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_scope(f, self, 0)
+    }
+}
+
+fn fmt_scope(f: &mut impl Write, scope: &Scope, indent: usize) -> std::fmt::Result {
+    for local in scope.locals.iter() {
+        writeln!(f, "{:indent$}let mut {local}: {}", "", local.item())?;
+    }
+    for (array, values) in scope.const_arrays.iter() {
+        write!(
+            f,
+            "{:indent$}let {array}: [{}; {}] = [",
+            "",
+            array.item(),
+            values.len()
+        )?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{value}")?;
+        }
+        f.write_str("]\n")?;
+    }
+
+    for operation in scope.operations.iter() {
+        let Operation::Branch(branch) = operation else {
+            writeln!(f, "{:indent$}{}", "", Statement(operation))?;
+            continue;
+        };
+
+        let nested = indent + INDENT;
+        match branch {
+            Branch::If(if_) => {
+                writeln!(f, "{:indent$}if {} {{", "", if_.cond)?;
+                fmt_scope(f, &if_.scope, nested)?;
+            }
+            Branch::IfElse(if_else) => {
+                writeln!(f, "{:indent$}if {} {{", "", if_else.cond)?;
+                fmt_scope(f, &if_else.scope_if, nested)?;
+                writeln!(f, "{:indent$}}} else {{", "")?;
+                fmt_scope(f, &if_else.scope_else, nested)?;
+            }
+            Branch::Switch(switch) => {
+                writeln!(f, "{:indent$}switch {} {{", "", switch.value)?;
+                for (value, case) in switch.cases.iter() {
+                    writeln!(f, "{:nested$}{value} => {{", "")?;
+                    fmt_scope(f, case, nested + INDENT)?;
+                    writeln!(f, "{:nested$}}}", "")?;
+                }
+                writeln!(f, "{:nested$}default => {{", "")?;
+                fmt_scope(f, &switch.scope_default, nested + INDENT)?;
+                writeln!(f, "{:nested$}}}", "")?;
+            }
+            Branch::RangeLoop(range_loop) => {
+                writeln!(f, "{:indent$}for {} {{", "", RangeHeader(range_loop))?;
+                fmt_scope(f, &range_loop.scope, nested)?;
+            }
+            Branch::Loop(loop_) => {
+                writeln!(f, "{:indent$}loop {{", "")?;
+                fmt_scope(f, &loop_.scope, nested)?;
+            }
+            Branch::Select(_) | Branch::Return | Branch::Break | Branch::Continue => {
+                writeln!(f, "{:indent$}{}", "", Statement(operation))?;
+                continue;
+            }
+        }
+        writeln!(f, "{:indent$}}}", "")?;
+    }
+
+    Ok(())
+}
+
+/// An operation, declaring the value it defines with its type.
+struct Statement<'a>(&'a Operation);
+
+impl Display for Statement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let defined = self.0.out().filter(|out| {
+            matches!(
+                out,
+                Variable::LocalBinding { .. } | Variable::Versioned { .. }
+            )
+        });
+        let Some(out) = defined else {
+            return write!(f, "{}", self.0);
+        };
+
+        let operation = self.0.to_string();
+        match operation.strip_prefix(&format!("{out} = ")) {
+            Some(value) => write!(f, "let {out}: {} = {value}", out.item()),
+            None => f.write_str(&operation),
+        }
+    }
+}
+
+/// The iterated range of a loop, e.g. `local(0, 2) in 0..16 step 2`.
+struct RangeHeader<'a>(&'a RangeLoop);
+
+impl Display for RangeHeader<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = self.0;
+        let dots = if range.inclusive { "..=" } else { ".." };
+        write!(f, "{} in {}{dots}{}", range.i, range.start, range.end)?;
+        if let Some(step) = &range.step {
+            write!(f, " step {step}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Scope {
+    /// Renders the control flow graph of the scope in the [Graphviz](https://graphviz.org) dot
+    /// language, e.g. to be viewed with `dot -Tsvg`.
+    ///
+    /// Each node is a block of operations executed in sequence, and the edges of the branches are
+    /// labeled with their condition. Loops are drawn with a dashed edge back to their header.
+    /// Breaks, continues and returns are shown in their block, without edges of their own.
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+        graph.body.push_str("digraph scope {\n");
+        graph
+            .body
+            .push_str("    node [shape=box, fontname=monospace];\n");
+
+        let entry = graph.node("entry".to_string());
+        let exits = graph.scope(self, vec![(entry, None)]);
+        let exit = graph.node("exit".to_string());
+        graph.edges(&exits, exit);
+
+        graph.body.push_str("}\n");
+        graph.body
+    }
+}
+
+/// The predecessors of the next block, with the label of their edge.
+type Exits = Vec<(usize, Option<String>)>;
+
+#[derive(Default)]
+struct DotGraph {
+    body: String,
+    nodes: usize,
+}
+
+impl DotGraph {
+    /// Add the blocks of the scope after the predecessors, returning the predecessors of the
+    /// block following the scope.
+    fn scope(&mut self, scope: &Scope, mut exits: Exits) -> Exits {
+        let mut block = String::new();
+        for local in scope.locals.iter() {
+            writeln!(block, "let mut {local}: {}", local.item()).unwrap();
+        }
+
+        for operation in scope.operations.iter() {
+            let branch = match operation {
+                Operation::Branch(
+                    branch @ (Branch::If(_)
+                    | Branch::IfElse(_)
+                    | Branch::Switch(_)
+                    | Branch::RangeLoop(_)
+                    | Branch::Loop(_)),
+                ) => branch,
+                operation => {
+                    writeln!(block, "{}", Statement(operation)).unwrap();
+                    continue;
+                }
+            };
+
+            match branch {
+                Branch::If(if_) => {
+                    writeln!(block, "if {}", if_.cond).unwrap();
+                    let node = self.block(&mut block, &exits);
+                    exits = self.scope(&if_.scope, vec![(node, Some("true".into()))]);
+                    exits.push((node, Some("false".into())));
+                }
+                Branch::IfElse(if_else) => {
+                    writeln!(block, "if {}", if_else.cond).unwrap();
+                    let node = self.block(&mut block, &exits);
+                    exits = self.scope(&if_else.scope_if, vec![(node, Some("true".into()))]);
+                    let exits_else =
+                        self.scope(&if_else.scope_else, vec![(node, Some("false".into()))]);
+                    exits.extend(exits_else);
+                }
+                Branch::Switch(switch) => {
+                    writeln!(block, "switch {}", switch.value).unwrap();
+                    let node = self.block(&mut block, &exits);
+                    exits = Vec::new();
+                    for (value, case) in switch.cases.iter() {
+                        exits.extend(self.scope(case, vec![(node, Some(value.to_string()))]));
+                    }
+                    let default = vec![(node, Some("default".into()))];
+                    exits.extend(self.scope(&switch.scope_default, default));
+                }
+                Branch::RangeLoop(range_loop) => {
+                    if !block.is_empty() {
+                        let node = self.block(&mut block, &exits);
+                        exits = vec![(node, None)];
+                    }
+                    writeln!(block, "for {}", RangeHeader(range_loop)).unwrap();
+                    let header = self.block(&mut block, &exits);
+                    let body = self.scope(&range_loop.scope, vec![(header, Some("body".into()))]);
+                    self.back_edges(&body, header);
+                    exits = vec![(header, Some("end".into()))];
+                }
+                Branch::Loop(loop_) => {
+                    if !block.is_empty() {
+                        let node = self.block(&mut block, &exits);
+                        exits = vec![(node, None)];
+                    }
+                    block.push_str("loop\n");
+                    let header = self.block(&mut block, &exits);
+                    let body = self.scope(&loop_.scope, vec![(header, None)]);
+                    self.back_edges(&body, header);
+                    exits = vec![(header, Some("break".into()))];
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if !block.is_empty() {
+            let node = self.block(&mut block, &exits);
+            exits = vec![(node, None)];
+        }
+
+        exits
+    }
+
+    /// Add the block of the buffered operations after the predecessors, emptying the buffer.
+    fn block(&mut self, block: &mut String, exits: &Exits) -> usize {
+        let node = self.node(core::mem::take(block));
+        self.edges(exits, node);
+        node
+    }
+
+    fn node(&mut self, label: String) -> usize {
+        let node = self.nodes;
+        self.nodes += 1;
+
+        // Left-justified lines, escaped for a quoted string.
+        let label = label
+            .trim_end_matches('\n')
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\l");
+        writeln!(self.body, "    n{node} [label=\"{label}\\l\"];").unwrap();
+        node
+    }
+
+    fn edges(&mut self, exits: &Exits, node: usize) {
+        for (exit, label) in exits.iter() {
+            match label {
+                Some(label) => {
+                    writeln!(self.body, "    n{exit} -> n{node} [label=\"{label}\"];").unwrap()
+                }
+                None => writeln!(self.body, "    n{exit} -> n{node};").unwrap(),
+            }
+        }
+    }
+
+    fn back_edges(&mut self, exits: &Exits, header: usize) {
+        for (exit, _) in exits.iter() {
+            writeln!(self.body, "    n{exit} -> n{header} [style=dashed];").unwrap();
+        }
+    }
+}
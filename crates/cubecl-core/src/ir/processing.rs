@@ -86,6 +86,18 @@ impl ScopeProcessing {
                 Operator::Erf(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
                 }
+                Operator::Erfc(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Expm1(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Tgamma(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
+                Operator::Lgamma(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
                 Operator::Recip(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
                 }
@@ -162,7 +174,12 @@ impl ScopeProcessing {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
-                Operator::Min(op) => {
+                Operator::Min(op)
+                | Operator::SaturatingAdd(op)
+                | Operator::SaturatingSub(op)
+                | Operator::WrappingAdd(op)
+                | Operator::WrappingSub(op)
+                | Operator::WrappingMul(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
@@ -186,11 +203,30 @@ impl ScopeProcessing {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
+                Operator::RotateLeft(op) | Operator::RotateRight(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
+                    sanitize_constant_scalar_ref_elem(&mut op.rhs, Elem::UInt);
+                }
+                Operator::BitfieldExtract(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                    sanitize_constant_scalar_ref_elem(&mut op.offset, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.count, Elem::UInt);
+                }
+                Operator::BitfieldInsert(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.base, &op.out);
+                    sanitize_constant_scalar_ref_var(&mut op.insert, &op.out);
+                    sanitize_constant_scalar_ref_elem(&mut op.offset, Elem::UInt);
+                    sanitize_constant_scalar_ref_elem(&mut op.count, Elem::UInt);
+                }
                 Operator::Remainder(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
                 Operator::Bitcast(_) => {}
+                Operator::PackF16(_) => {}
+                Operator::UnpackF16(_) => {}
+                Operator::PackBf16(_) => {}
+                Operator::UnpackBf16(_) => {}
                 Operator::AtomicLoad(_) => {}
                 Operator::AtomicStore(_) => {}
                 Operator::AtomicSwap(op) => {
@@ -231,15 +267,24 @@ impl ScopeProcessing {
                     sanitize_constant_scalar_ref_var(&mut op.lhs, &op.out);
                     sanitize_constant_scalar_ref_var(&mut op.rhs, &op.out);
                 }
+                Operator::MatrixMul(_) | Operator::Transpose(_) | Operator::Determinant(_) => {
+                    // Nothing to do
+                }
                 Operator::InitLine(_) => {
                     // TODO: Sanitize based on elem
                 }
+                Operator::Swizzle(_) => {
+                    // Nothing to do
+                }
+                Operator::SwizzleAssign(op) => {
+                    sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
+                }
                 Operator::Copy(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
                     sanitize_constant_scalar_ref_elem(&mut op.in_index, Elem::UInt);
                     sanitize_constant_scalar_ref_elem(&mut op.out_index, Elem::UInt);
                 }
-                Operator::CopyBulk(op) => {
+                Operator::CopyBulk(op) | Operator::CopyBulkAsync(op) => {
                     sanitize_constant_scalar_ref_var(&mut op.input, &op.out);
                     sanitize_constant_scalar_ref_elem(&mut op.in_index, Elem::UInt);
                     sanitize_constant_scalar_ref_elem(&mut op.out_index, Elem::UInt);
@@ -281,6 +326,12 @@ impl ScopeProcessing {
             Operation::Subcube(_) => {
                 // Nothing to do since no constant is possible.
             }
+            Operation::Call(_) => {
+                // Nothing to do since arguments keep their own type.
+            }
+            Operation::Location(_) => {
+                // Nothing to do.
+            }
             Operation::CoopMma(op) => match op {
                 CoopMma::Fill { mat, value } => {
                     sanitize_constant_scalar_ref_var(value, mat);
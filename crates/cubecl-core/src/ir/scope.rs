@@ -1,8 +1,8 @@
 use crate::ir::ConstantScalarValue;
 
 use super::{
-    cpa, processing::ScopeProcessing, Elem, Item, Matrix, Operation, Operator, UnaryOperator,
-    Variable,
+    cpa, processing::ScopeProcessing, Branch, Elem, Item, Matrix, Operation, Operator,
+    UnaryOperator, Variable,
 };
 use serde::{Deserialize, Serialize};
 
@@ -65,6 +65,33 @@ impl Scope {
         }
     }
 
+    /// Visit the operations of the scope and of its nested scopes, in order.
+    pub fn visit_operations(&self, visit: &mut impl FnMut(&Operation)) {
+        for operation in self.operations.iter() {
+            visit(operation);
+
+            let Operation::Branch(branch) = operation else {
+                continue;
+            };
+            match branch {
+                Branch::If(op) => op.scope.visit_operations(visit),
+                Branch::IfElse(op) => {
+                    op.scope_if.visit_operations(visit);
+                    op.scope_else.visit_operations(visit);
+                }
+                Branch::Switch(op) => {
+                    for (_, scope) in op.cases.iter() {
+                        scope.visit_operations(visit);
+                    }
+                    op.scope_default.visit_operations(visit);
+                }
+                Branch::RangeLoop(op) => op.scope.visit_operations(visit),
+                Branch::Loop(op) => op.scope.visit_operations(visit),
+                Branch::Select(_) | Branch::Return | Branch::Break | Branch::Continue => {}
+            }
+        }
+    }
+
     /// Create a variable initialized at zero.
     pub fn zero<I: Into<Item>>(&mut self, item: I) -> Variable {
         let local = self.create_local(item);
@@ -363,11 +390,49 @@ impl Scope {
             id: index,
             item,
             length: shared_memory_size,
+            dynamic: false,
         };
         self.shared_memories.push(shared_memory);
         shared_memory
     }
 
+    /// Create a shared variable of the given [item type](Item) whose size is chosen at launch.
+    ///
+    /// Only one dynamic shared memory can be declared per kernel, since backends map it to a single
+    /// memory region sized with [KernelSettings::dynamic_shared_memory](crate::KernelSettings::dynamic_shared_memory).
+    pub fn create_shared_dynamic<I: Into<Item>>(&mut self, item: I) -> Variable {
+        assert!(
+            !self
+                .shared_memories
+                .iter()
+                .any(|var| matches!(var, Variable::SharedMemory { dynamic: true, .. })),
+            "Only one dynamic shared memory can be declared per kernel"
+        );
+
+        let item = item.into();
+        let index = self.new_shared_index();
+        let shared_memory = Variable::SharedMemory {
+            id: index,
+            item,
+            length: 0,
+            dynamic: true,
+        };
+        self.shared_memories.push(shared_memory);
+        shared_memory
+    }
+
+    /// The [item type](Item) of the dynamic shared memory declared in the scope, if any.
+    pub fn dynamic_shared_memory(&self) -> Option<Item> {
+        self.shared_memories.iter().find_map(|var| match var {
+            Variable::SharedMemory {
+                item,
+                dynamic: true,
+                ..
+            } => Some(*item),
+            _ => None,
+        })
+    }
+
     /// Create a shared variable of the given [item type](Item).
     pub fn create_const_array<I: Into<Item>>(&mut self, item: I, data: Vec<Variable>) -> Variable {
         let item = item.into();
@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-use super::{BinaryOperator, InitOperator, UnaryOperator, Variable};
+use super::{
+    BinaryOperator, Branch, Elem, InitOperator, Item, KernelDefinition, Operation, Operator,
+    RangeLoop, Scope, Synchronization, UnaryOperator, Variable,
+};
 use serde::{Deserialize, Serialize};
 
 /// All subcube operations.
@@ -51,3 +54,165 @@ impl Display for Subcube {
         }
     }
 }
+
+impl KernelDefinition {
+    /// Lower the [subcube operations](Subcube) to shared memory, for devices without subcube
+    /// support.
+    ///
+    /// The whole cube acts as a single subcube: each unit writes its value to the shared memory,
+    /// and reads back the values of all the units after a barrier. Like barriers, the lowered
+    /// operations must be reached by all the units of the cube.
+    pub fn lower_subcube(&mut self) {
+        let mut items = Vec::new();
+        let mut collect = |operation: &Operation| {
+            let Operation::Subcube(subcube) = operation else {
+                return;
+            };
+            let input = match subcube {
+                Subcube::Elect(_) => return,
+                Subcube::Broadcast(op) => op.lhs,
+                Subcube::All(op)
+                | Subcube::Any(op)
+                | Subcube::Sum(op)
+                | Subcube::Prod(op)
+                | Subcube::Min(op)
+                | Subcube::Max(op) => op.input,
+            };
+            if !items.contains(&input.item()) {
+                items.push(input.item());
+            }
+        };
+        self.body.visit_operations(&mut collect);
+        for function in self.functions.iter() {
+            function.body.visit_operations(&mut collect);
+        }
+
+        // Shared memories are declared by the root scope, one per item reused by all operations.
+        let units = self.cube_dim.num_elems();
+        let lowering = SubcubeLowering {
+            units,
+            shared: items
+                .into_iter()
+                .map(|item| (item, self.body.create_shared(item, units)))
+                .collect(),
+        };
+
+        lowering.scope(&mut self.body);
+        for function in self.functions.iter_mut() {
+            lowering.scope(&mut function.body);
+        }
+    }
+}
+
+struct SubcubeLowering {
+    units: u32,
+    shared: Vec<(Item, Variable)>,
+}
+
+impl SubcubeLowering {
+    fn scope(&self, scope: &mut Scope) {
+        for operation in core::mem::take(&mut scope.operations) {
+            match operation {
+                Operation::Subcube(subcube) => self.subcube(scope, subcube),
+                Operation::Branch(mut branch) => {
+                    match &mut branch {
+                        Branch::If(op) => self.scope(&mut op.scope),
+                        Branch::IfElse(op) => {
+                            self.scope(&mut op.scope_if);
+                            self.scope(&mut op.scope_else);
+                        }
+                        Branch::Switch(op) => {
+                            for (_, case) in op.cases.iter_mut() {
+                                self.scope(case);
+                            }
+                            self.scope(&mut op.scope_default);
+                        }
+                        Branch::RangeLoop(op) => self.scope(&mut op.scope),
+                        Branch::Loop(op) => self.scope(&mut op.scope),
+                        Branch::Select(_) | Branch::Return | Branch::Break | Branch::Continue => {}
+                    }
+                    scope.register(branch);
+                }
+                operation => scope.register(operation),
+            }
+        }
+    }
+
+    fn subcube(&self, scope: &mut Scope, subcube: Subcube) {
+        let (op, combine): (_, fn(BinaryOperator) -> Operator) = match subcube {
+            Subcube::Elect(op) => {
+                scope.register(Operator::Equal(BinaryOperator {
+                    lhs: Variable::UnitPos,
+                    rhs: 0u32.into(),
+                    out: op.out,
+                }));
+                return;
+            }
+            Subcube::Broadcast(op) => {
+                let shared = self.share(scope, op.lhs);
+                scope.register(Operator::Index(BinaryOperator {
+                    lhs: shared,
+                    rhs: op.rhs,
+                    out: op.out,
+                }));
+                scope.register(Synchronization::SyncUnits);
+                return;
+            }
+            Subcube::All(op) => (op, Operator::And),
+            Subcube::Any(op) => (op, Operator::Or),
+            Subcube::Sum(op) => (op, Operator::Add),
+            Subcube::Prod(op) => (op, Operator::Mul),
+            Subcube::Min(op) => (op, Operator::Min),
+            Subcube::Max(op) => (op, Operator::Max),
+        };
+
+        let shared = self.share(scope, op.input);
+        scope.register(Operator::Index(BinaryOperator {
+            lhs: shared,
+            rhs: 0u32.into(),
+            out: op.out,
+        }));
+
+        let mut body = scope.child();
+        let i = body.create_local_undeclared(Item::new(Elem::UInt));
+        let value = body.create_local(op.input.item());
+        body.register(Operator::Index(BinaryOperator {
+            lhs: shared,
+            rhs: i,
+            out: value,
+        }));
+        body.register(combine(BinaryOperator {
+            lhs: op.out,
+            rhs: value,
+            out: op.out,
+        }));
+        scope.register(Branch::RangeLoop(Box::new(RangeLoop {
+            i,
+            start: 1u32.into(),
+            end: self.units.into(),
+            step: None,
+            inclusive: false,
+            scope: body,
+        })));
+
+        // The shared memory is overwritten by the next operation only once all units read it.
+        scope.register(Synchronization::SyncUnits);
+    }
+
+    /// Write the value of each unit to the shared memory of its item, visible to all the units
+    /// once returned.
+    fn share(&self, scope: &mut Scope, value: Variable) -> Variable {
+        let (_, shared) = self
+            .shared
+            .iter()
+            .find(|(item, _)| *item == value.item())
+            .unwrap();
+        scope.register(Operator::IndexAssign(BinaryOperator {
+            lhs: Variable::UnitPos,
+            rhs: value,
+            out: *shared,
+        }));
+        scope.register(Synchronization::SyncUnits);
+        *shared
+    }
+}
@@ -9,6 +9,10 @@ pub enum Synchronization {
     // Synchronizize units in a cube.
     SyncUnits,
     SyncStorage,
+    // Group all pending asynchronous copies of the unit.
+    CopyAsyncCommit,
+    // Wait until at most `pending` groups of asynchronous copies are still in flight.
+    CopyAsyncWait { pending: u32 },
 }
 
 impl Display for Synchronization {
@@ -16,6 +20,10 @@ impl Display for Synchronization {
         match self {
             Synchronization::SyncUnits => write!(f, "sync_units()"),
             Synchronization::SyncStorage => write!(f, "sync_storage()"),
+            Synchronization::CopyAsyncCommit => write!(f, "copy_async_commit()"),
+            Synchronization::CopyAsyncWait { pending } => {
+                write!(f, "copy_async_wait({pending})")
+            }
         }
     }
 }
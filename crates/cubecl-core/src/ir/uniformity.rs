@@ -0,0 +1,524 @@
+use std::fmt::Display;
+
+use super::{
+    Branch, CopyBulkOperator, CopyOperator, FunctionDefinition, Metadata, Operation, Operator,
+    Scope, SourceLocation, Subcube, Synchronization, Variable,
+};
+
+/// The units sharing the same value of a variable, or taking the same path through the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Uniformity {
+    /// The same for all units of a cube.
+    Cube,
+    /// The same for all units of a subcube, but may differ between subcubes.
+    Subcube,
+    /// May differ for each unit.
+    Unit,
+}
+
+impl Display for Uniformity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Uniformity::Cube => f.write_str("cube"),
+            Uniformity::Subcube => f.write_str("subcube"),
+            Uniformity::Unit => f.write_str("unit"),
+        }
+    }
+}
+
+/// An operation that all units of a cube or of a subcube must execute together, but that is
+/// reached through control flow depending on values that differ between them.
+///
+/// Backends either reject such kernels or produce undefined behavior, such as a
+/// [sync_units](Synchronization::SyncUnits) waiting forever for units that never reach it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonUniformOperation {
+    /// The barrier, subcube operation or call to a function using them.
+    pub operation: Operation,
+    /// The units that must execute the operation together.
+    pub required: Uniformity,
+    /// The uniformity of the control flow reaching the operation.
+    pub control: Uniformity,
+    /// The location of the operation in the cube source, when debug info is enabled.
+    pub location: Option<SourceLocation>,
+}
+
+impl Display for NonUniformOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.operation)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {location}")?;
+        }
+        write!(
+            f,
+            " must be executed by all units of the {}, but the control flow reaching it isn't \
+             uniform across the {}",
+            self.required, self.required
+        )
+    }
+}
+
+impl Scope {
+    /// Find the barriers and subcube operations reached through non-uniform control flow, such
+    /// as a branch on `UNIT_POS`.
+    ///
+    /// Calls to the given device functions are reported when the function itself uses a barrier
+    /// or a subcube operation. The body of each function is checked assuming uniform arguments.
+    ///
+    /// Subcubes are assumed to be made of whole rows of the cube, so `UNIT_POS_Y` and
+    /// `UNIT_POS_Z` are uniform across a subcube when `CUBE_DIM_X` is a multiple of
+    /// `SUBCUBE_DIM`.
+    pub fn non_uniform_operations(
+        &self,
+        functions: &[FunctionDefinition],
+    ) -> Vec<NonUniformOperation> {
+        let mut analysis = UniformityAnalysis::default();
+
+        for function in functions {
+            analysis.values.clear();
+            analysis.scope(&function.body, Uniformity::Cube);
+            let summary = FunctionSummary {
+                required: analysis.required.take(),
+                output: function.output.map(|out| analysis.value(out)),
+            };
+            analysis.functions.push((function.name.clone(), summary));
+        }
+
+        analysis.values.clear();
+        analysis.scope(self, Uniformity::Cube);
+        analysis.non_uniform
+    }
+}
+
+/// The uniformity a function requires from its callers, and the one of its output given
+/// uniform arguments.
+struct FunctionSummary {
+    required: Option<Uniformity>,
+    output: Option<Uniformity>,
+}
+
+/// The uniformity of the variables assigned so far, or of their elements for arrays.
+type Values = Vec<(Variable, Uniformity)>;
+
+/// How the units may diverge when leaving a scope early.
+#[derive(Default)]
+struct Exits {
+    /// The control flow uniformity at the `break`s and `continue`s.
+    jumps: Option<Uniformity>,
+    /// The values at the `break`s and `continue`s.
+    jumped: Values,
+    /// The control flow uniformity at the `return`s.
+    returns: Option<Uniformity>,
+}
+
+#[derive(Default)]
+struct UniformityAnalysis {
+    values: Values,
+    functions: Vec<(String, FunctionSummary)>,
+    /// The strongest uniformity required by the operations of the current function.
+    required: Option<Uniformity>,
+    location: Option<SourceLocation>,
+    non_uniform: Vec<NonUniformOperation>,
+}
+
+impl UniformityAnalysis {
+    fn scope(&mut self, scope: &Scope, mut control: Uniformity) -> Exits {
+        let mut exits = Exits::default();
+
+        for operation in scope.operations.iter() {
+            let nested = match operation {
+                Operation::Location(location) => {
+                    self.location = Some(location.clone());
+                    continue;
+                }
+                Operation::Branch(Branch::Break | Branch::Continue) => {
+                    exits.jumps = exits.jumps.max(Some(control));
+                    merge(&mut exits.jumped, self.values.clone());
+                    return exits;
+                }
+                Operation::Branch(Branch::Return) => {
+                    exits.returns = exits.returns.max(Some(control));
+                    return exits;
+                }
+                Operation::Branch(Branch::If(branch)) => {
+                    let control = control.max(self.value(branch.cond));
+                    let skipped = self.values.clone();
+                    let exits = self.scope(&branch.scope, control);
+                    merge(&mut self.values, skipped);
+                    exits
+                }
+                Operation::Branch(Branch::IfElse(branch)) => {
+                    let control = control.max(self.value(branch.cond));
+                    let values = self.values.clone();
+                    let then = self.scope(&branch.scope_if, control);
+                    let then_values = core::mem::replace(&mut self.values, values);
+                    let or_else = self.scope(&branch.scope_else, control);
+                    merge(&mut self.values, then_values);
+                    join(then, or_else)
+                }
+                Operation::Branch(Branch::Switch(switch)) => {
+                    let control = control.max(self.value(switch.value));
+                    let values = self.values.clone();
+                    let cases = switch.cases.iter().map(|(_, case)| case);
+                    let mut exits = Exits::default();
+                    let mut after = Values::new();
+
+                    for case in core::iter::once(&switch.scope_default).chain(cases) {
+                        self.values.clone_from(&values);
+                        exits = join(exits, self.scope(case, control));
+                        merge(&mut after, core::mem::take(&mut self.values));
+                    }
+                    self.values = after;
+                    exits
+                }
+                Operation::Branch(Branch::RangeLoop(range_loop)) => {
+                    let mut control = control
+                        .max(self.value(range_loop.start))
+                        .max(self.value(range_loop.end));
+                    if let Some(step) = range_loop.step {
+                        control = control.max(self.value(step));
+                    }
+                    self.assign(range_loop.i, control);
+                    self.body(&range_loop.scope, control)
+                }
+                Operation::Branch(Branch::Loop(body)) => self.body(&body.scope, control),
+                operation => {
+                    self.operation(operation, control);
+                    Exits::default()
+                }
+            };
+
+            // The units leaving a nested scope early don't run the rest of the scope.
+            control = control.max(nested.jumps.max(nested.returns).unwrap_or(control));
+            exits = join(exits, nested);
+        }
+
+        exits
+    }
+
+    /// Analyze the body of a loop until the uniformity of its variables stops changing, since
+    /// the values assigned in an iteration are used by the next one.
+    ///
+    /// The units may leave the loop in different iterations, so the loop doesn't stay uniform
+    /// when a `break` or a `continue` depends on non-uniform values.
+    fn body(&mut self, scope: &Scope, mut control: Uniformity) -> Exits {
+        let mut entry = self.values.clone();
+
+        loop {
+            let exits = self.scope(scope, control);
+            let next = control.max(exits.jumps.unwrap_or(control));
+
+            // An iteration starts with the values before the loop, or with the ones at the end
+            // of the previous iteration. The loop is left with the same values, or with the ones
+            // at a `break`.
+            let end = core::mem::replace(&mut self.values, entry.clone());
+            merge(&mut self.values, end);
+            merge(&mut self.values, exits.jumped);
+
+            if next == control && entry == self.values {
+                return Exits {
+                    returns: exits.returns,
+                    ..Default::default()
+                };
+            }
+            entry.clone_from(&self.values);
+            control = next;
+        }
+    }
+
+    fn operation(&mut self, operation: &Operation, control: Uniformity) {
+        match operation {
+            Operation::Synchronization(
+                Synchronization::SyncUnits | Synchronization::SyncStorage,
+            ) => {
+                self.require(operation, Uniformity::Cube, control);
+            }
+            Operation::Subcube(subcube) => {
+                self.require(operation, Uniformity::Subcube, control);
+                self.subcube(subcube, control);
+            }
+            Operation::Call(call) => {
+                let summary = self
+                    .functions
+                    .iter()
+                    .find(|(name, _)| *name == call.name)
+                    .map(|(_, summary)| (summary.required, summary.output));
+                let (required, output) = summary.unwrap_or((None, Some(Uniformity::Unit)));
+
+                if let Some(required) = required {
+                    self.require(operation, required, control);
+                }
+                if let Some(out) = call.out {
+                    let args = self.max(&call.args);
+                    let value = output.unwrap_or(Uniformity::Unit).max(args);
+                    self.assign(out, value.max(control));
+                }
+            }
+            Operation::Operator(operator) => self.operator(operator, control),
+            Operation::Metadata(
+                Metadata::Stride { dim, var, out } | Metadata::Shape { dim, var, out },
+            ) => {
+                let value = self.value(*dim).max(self.value(*var));
+                self.assign(*out, value.max(control));
+            }
+            Operation::Metadata(Metadata::Length { var, out }) => {
+                let value = self.value(*var);
+                self.assign(*out, value.max(control));
+            }
+            Operation::Branch(Branch::Select(select)) => {
+                let value = self.max(&[select.cond, select.then, select.or_else]);
+                self.assign(select.out, value.max(control));
+            }
+            _ => {}
+        }
+    }
+
+    fn subcube(&mut self, subcube: &Subcube, control: Uniformity) {
+        let (input, out) = match subcube {
+            Subcube::Elect(op) => {
+                self.assign(op.out, Uniformity::Unit);
+                return;
+            }
+            Subcube::Broadcast(op) => (self.value(op.lhs).max(self.value(op.rhs)), op.out),
+            Subcube::All(op)
+            | Subcube::Any(op)
+            | Subcube::Sum(op)
+            | Subcube::Prod(op)
+            | Subcube::Min(op)
+            | Subcube::Max(op) => (self.value(op.input), op.out),
+        };
+
+        // The result is shared by the subcube even when the input differs for each unit.
+        let value = input.min(Uniformity::Subcube);
+        self.assign(out, value.max(control));
+    }
+
+    fn operator(&mut self, operator: &Operator, control: Uniformity) {
+        let (out, value) = match operator {
+            Operator::Index(op) | Operator::UncheckedIndex(op) => {
+                let value = self.content(op.lhs).max(self.value(op.rhs));
+                (op.out, value)
+            }
+            Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op) => {
+                let value = self.max(&[op.lhs, op.rhs]);
+                return self.assign_elements(op.out, value.max(control));
+            }
+            Operator::Copy(CopyOperator {
+                out,
+                out_index,
+                input,
+                in_index,
+            })
+            | Operator::CopyBulk(CopyBulkOperator {
+                out,
+                out_index,
+                input,
+                in_index,
+                ..
+            })
+            | Operator::CopyBulkAsync(CopyBulkOperator {
+                out,
+                out_index,
+                input,
+                in_index,
+                ..
+            }) => {
+                let value = self
+                    .content(*input)
+                    .max(self.value(*in_index))
+                    .max(self.value(*out_index));
+                return self.assign_elements(*out, value.max(control));
+            }
+            Operator::Slice(op) => {
+                let value = self
+                    .content(op.input)
+                    .max(self.value(op.start))
+                    .max(self.value(op.end));
+                (op.out, value)
+            }
+            // The previous value of an atomic may come from any unit.
+            Operator::AtomicLoad(op) => (op.out, Uniformity::Unit),
+            Operator::AtomicSwap(op)
+            | Operator::AtomicAdd(op)
+            | Operator::AtomicSub(op)
+            | Operator::AtomicMax(op)
+            | Operator::AtomicMin(op)
+            | Operator::AtomicAnd(op)
+            | Operator::AtomicOr(op)
+            | Operator::AtomicXor(op) => (op.out, Uniformity::Unit),
+            Operator::AtomicCompareAndSwap(op) => (op.out, Uniformity::Unit),
+            Operator::AtomicStore(op) => return self.assign_elements(op.out, Uniformity::Unit),
+            Operator::Fma(op) => (op.out, self.max(&[op.a, op.b, op.c])),
+            Operator::Clamp(op) => (op.out, self.max(&[op.input, op.min_value, op.max_value])),
+            Operator::BitfieldExtract(op) => (op.out, self.max(&[op.input, op.offset, op.count])),
+            Operator::BitfieldInsert(op) => {
+                (op.out, self.max(&[op.base, op.insert, op.offset, op.count]))
+            }
+            Operator::InitLine(op) => (op.out, self.max(&op.inputs)),
+            Operator::Swizzle(op) => (op.out, self.value(op.input)),
+            Operator::SwizzleAssign(op) => {
+                let value = self.value(op.input);
+                return self.assign_elements(op.out, value.max(control));
+            }
+            Operator::Add(op)
+            | Operator::Sub(op)
+            | Operator::Mul(op)
+            | Operator::Div(op)
+            | Operator::Powf(op)
+            | Operator::Equal(op)
+            | Operator::NotEqual(op)
+            | Operator::Lower(op)
+            | Operator::Greater(op)
+            | Operator::LowerEqual(op)
+            | Operator::GreaterEqual(op)
+            | Operator::Modulo(op)
+            | Operator::And(op)
+            | Operator::Or(op)
+            | Operator::Max(op)
+            | Operator::Min(op)
+            | Operator::SaturatingAdd(op)
+            | Operator::SaturatingSub(op)
+            | Operator::WrappingAdd(op)
+            | Operator::WrappingSub(op)
+            | Operator::WrappingMul(op)
+            | Operator::BitwiseAnd(op)
+            | Operator::BitwiseOr(op)
+            | Operator::BitwiseXor(op)
+            | Operator::ShiftLeft(op)
+            | Operator::ShiftRight(op)
+            | Operator::RotateLeft(op)
+            | Operator::RotateRight(op)
+            | Operator::Remainder(op)
+            | Operator::Dot(op)
+            | Operator::MatrixMul(op) => (op.out, self.max(&[op.lhs, op.rhs])),
+            Operator::Abs(op)
+            | Operator::Exp(op)
+            | Operator::Log(op)
+            | Operator::Log1p(op)
+            | Operator::Cos(op)
+            | Operator::Sin(op)
+            | Operator::Tanh(op)
+            | Operator::Sqrt(op)
+            | Operator::Round(op)
+            | Operator::Floor(op)
+            | Operator::Ceil(op)
+            | Operator::Erf(op)
+            | Operator::Erfc(op)
+            | Operator::Expm1(op)
+            | Operator::Tgamma(op)
+            | Operator::Lgamma(op)
+            | Operator::Recip(op)
+            | Operator::Assign(op)
+            | Operator::Not(op)
+            | Operator::Neg(op)
+            | Operator::Bitcast(op)
+            | Operator::PackF16(op)
+            | Operator::UnpackF16(op)
+            | Operator::PackBf16(op)
+            | Operator::UnpackBf16(op)
+            | Operator::Magnitude(op)
+            | Operator::Normalize(op)
+            | Operator::Transpose(op)
+            | Operator::Determinant(op) => (op.out, self.value(op.input)),
+        };
+
+        self.assign(out, value.max(control));
+    }
+
+    /// Report the operation when it isn't reached uniformly enough.
+    fn require(&mut self, operation: &Operation, required: Uniformity, control: Uniformity) {
+        self.required = Some(self.required.map_or(required, |other| other.min(required)));
+
+        if control <= required {
+            return;
+        }
+        let non_uniform = NonUniformOperation {
+            operation: operation.clone(),
+            required,
+            control,
+            location: self.location.clone(),
+        };
+        if !self.non_uniform.contains(&non_uniform) {
+            self.non_uniform.push(non_uniform);
+        }
+    }
+
+    fn assign(&mut self, variable: Variable, value: Uniformity) {
+        match self.values.iter_mut().find(|(var, _)| *var == variable) {
+            Some((_, previous)) => *previous = value,
+            None => self.values.push((variable, value)),
+        }
+    }
+
+    /// Assign some elements of an array or of a line, keeping the uniformity of the others.
+    fn assign_elements(&mut self, array: Variable, value: Uniformity) {
+        merge(&mut self.values, vec![(array, value)]);
+    }
+
+    /// The uniformity of the value of a variable.
+    fn value(&self, variable: Variable) -> Uniformity {
+        match variable {
+            Variable::UnitPos
+            | Variable::UnitPosX
+            | Variable::AbsolutePos
+            | Variable::AbsolutePosX => Uniformity::Unit,
+            // Subcubes are made of whole rows of units along the x axis.
+            Variable::UnitPosY
+            | Variable::UnitPosZ
+            | Variable::AbsolutePosY
+            | Variable::AbsolutePosZ => Uniformity::Subcube,
+            Variable::Local { .. }
+            | Variable::Versioned { .. }
+            | Variable::LocalBinding { .. }
+            | Variable::LocalArray { .. }
+            | Variable::Slice { .. }
+            | Variable::Matrix { .. } => self.assigned(variable),
+            _ => Uniformity::Cube,
+        }
+    }
+
+    /// The uniformity of the elements of an array.
+    fn content(&self, array: Variable) -> Uniformity {
+        match array {
+            // Shared memory and outputs may be written by any unit.
+            Variable::SharedMemory { .. } | Variable::GlobalOutputArray { .. } => Uniformity::Unit,
+            Variable::GlobalInputArray { .. } | Variable::ConstantArray { .. } => Uniformity::Cube,
+            array => self.value(array),
+        }
+    }
+
+    fn assigned(&self, variable: Variable) -> Uniformity {
+        self.values
+            .iter()
+            .find(|(var, _)| *var == variable)
+            .map(|(_, value)| *value)
+            .unwrap_or(Uniformity::Cube)
+    }
+
+    fn max(&self, variables: &[Variable]) -> Uniformity {
+        variables
+            .iter()
+            .map(|variable| self.value(*variable))
+            .max()
+            .unwrap_or(Uniformity::Cube)
+    }
+}
+
+fn join(mut first: Exits, second: Exits) -> Exits {
+    merge(&mut first.jumped, second.jumped);
+    Exits {
+        jumps: first.jumps.max(second.jumps),
+        jumped: first.jumped,
+        returns: first.returns.max(second.returns),
+    }
+}
+
+/// Merge the values of two paths, keeping the least uniform value of each variable.
+fn merge(values: &mut Values, other: Values) {
+    for (variable, value) in other {
+        match values.iter_mut().find(|(var, _)| *var == variable) {
+            Some((_, previous)) => *previous = value.max(*previous),
+            None => values.push((variable, value)),
+        }
+    }
+}
@@ -46,6 +46,7 @@ pub enum Variable {
         id: u16,
         item: Item,
         length: u32,
+        dynamic: bool,
     },
     LocalArray {
         id: u16,
@@ -9,12 +9,14 @@ pub mod frontend;
 /// Some future utilities that work across environments.
 pub use cubecl_common::future;
 
-pub use cubecl_runtime::memory_management::MemoryConfiguration;
+pub use cubecl_runtime::cancellation::{CancellationToken, Cancelled};
+pub use cubecl_runtime::memory_management::{MemoryConfiguration, StagingConfiguration};
 pub use frontend::cmma;
 
 /// Cube Language Internal Representation.
 pub mod ir;
 
+pub mod backend;
 pub mod codegen;
 pub mod compute;
 pub mod prelude;
@@ -33,6 +35,8 @@ pub use cubecl_runtime::benchmark;
 pub const SUBCUBE_DIM_APPROX: usize = 16;
 
 use crate::ir::KernelDefinition;
+use cubecl_runtime::client::ComputeClient;
+use cubecl_runtime::HardwareProperties;
 use frontend::LaunchArg;
 
 pub use prelude::CubeCount;
@@ -49,6 +53,14 @@ pub trait Kernel: Send + Sync + 'static + Sized {
     fn id(&self) -> KernelId {
         KernelId::new::<Self>()
     }
+    /// The number of bytes of dynamic shared memory to allocate at launch.
+    fn dynamic_shared_memory(&self) -> u32 {
+        0
+    }
+    /// Whether the shared memory must be zero-initialized before the kernel runs.
+    fn zero_initialize_shared_memory(&self) -> bool {
+        false
+    }
 }
 
 /// Calculate the number of cubes required to execute an operation where one cube unit is
@@ -62,6 +74,45 @@ pub fn calculate_cube_count_elemwise(num_elems: usize, cube_dim: CubeDim) -> Cub
     CubeCount::Static(cube_count_x as u32, cube_count_y as u32, 1)
 }
 
+/// Calculate the number of cubes of a persistent kernel, where the cubes stay resident and loop
+/// over the `num_elems` elements, e.g. with a [grid_stride_range](frontend::grid_stride_range).
+///
+/// At most as many cubes as the device keeps resident at once are launched, which saves the
+/// dispatch of many short-lived cubes. Devices that don't report their multiprocessors launch
+/// one cube per `cube_dim.num_elems()` elements, like [calculate_cube_count_elemwise].
+pub fn calculate_cube_count_persistent(
+    num_elems: usize,
+    cube_dim: CubeDim,
+    properties: &HardwareProperties,
+) -> CubeCount {
+    let units = cube_dim.num_elems();
+    let mut cube_count = num_elems.div_ceil(units as usize).max(1) as u64;
+
+    let resident = compute::resident_cubes(properties, units, None);
+    if let (Some(multiprocessors), Some(resident)) = (properties.num_multiprocessors, resident) {
+        let persistent = multiprocessors as u64 * resident.max(1) as u64;
+        cube_count = cube_count.min(persistent);
+    }
+
+    CubeCount::Static(
+        cube_count.min(properties.max_cube_count.0 as u64) as u32,
+        1,
+        1,
+    )
+}
+
+/// The id under which autotune results are cached for the device of the client.
+///
+/// Devices of the same runtime with the same [hardware properties](HardwareProperties) share
+/// their results.
+pub fn tune_device_id<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> String {
+    format!(
+        "{}-{:?}",
+        R::name(),
+        client.properties().hardware_properties()
+    )
+}
+
 pub fn tensor_vectorization_factor(
     factors: &[u8],
     shape: &[usize],
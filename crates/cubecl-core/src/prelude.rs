@@ -1,7 +1,10 @@
 pub use crate::{cube, CubeLaunch, CubeType, Kernel, RuntimeArg};
 
 pub use crate::codegen::{KernelExpansion, KernelIntegrator, KernelSettings};
-pub use crate::compute::{CompiledKernel, CubeTask, KernelBuilder, KernelLauncher, KernelTask};
+pub use crate::compute::{
+    launch_scope, ArgumentOutOfBounds, CompiledKernel, CubeTask, DataRace, DispatchTable,
+    KernelBuilder, KernelLauncher, KernelTask, LaunchError, LaunchScope, SpecializationCache,
+};
 pub use crate::frontend::cmma;
 pub use crate::frontend::{branch::*, synchronization::*, vectorization_of};
 pub use crate::ir::{CubeDim, KernelDefinition};
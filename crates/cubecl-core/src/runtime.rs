@@ -1,5 +1,7 @@
 use crate::{codegen::Compiler, compute::CubeTask, ir::Elem};
-use cubecl_runtime::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
+use cubecl_runtime::{
+    channel::ComputeChannel, client::ComputeClient, server::ComputeServer, DeviceProperties,
+};
 
 pub use cubecl_runtime::channel;
 pub use cubecl_runtime::client;
@@ -47,5 +49,65 @@ pub enum Feature {
         k: u8,
         n: u8,
     },
+    /// The number of units cooperating on each cmma operation, which must all execute it.
+    CmmaWarpSize(i32),
+    /// Bulk copies from global to shared memory are asynchronous, bypassing the registers.
+    AsyncCopy,
+    /// Square matrices of the size, stored in lines, support the
+    /// [matrix operations](crate::prelude::Line::matrix_mul).
+    Matrix(u8),
     Type(Elem),
 }
+
+/// The shape and element types of a [cooperative matrix-multiply and accumulate](crate::cmma)
+/// `D = A * B + C`, where `A` is `m x k`, `B` is `k x n` and `C` is `m x n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MmaConfig {
+    pub a: Elem,
+    pub b: Elem,
+    pub c: Elem,
+    pub m: u8,
+    pub k: u8,
+    pub n: u8,
+}
+
+impl MmaConfig {
+    /// The configurations supported by the device, from which kernels can select their tile
+    /// shape.
+    pub fn supported(properties: &DeviceProperties<Feature>) -> Vec<Self> {
+        properties
+            .features()
+            .filter_map(|feature| match *feature {
+                Feature::Cmma { a, b, c, m, k, n } => Some(MmaConfig { a, b, c, m, k, n }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the device supports the configuration.
+    pub fn is_supported(&self, properties: &DeviceProperties<Feature>) -> bool {
+        properties.feature_enabled(self.feature())
+    }
+
+    /// The [feature](Feature) registered by the runtimes supporting the configuration.
+    pub fn feature(&self) -> Feature {
+        Feature::Cmma {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            m: self.m,
+            k: self.k,
+            n: self.n,
+        }
+    }
+}
+
+impl core::fmt::Display for MmaConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "m{}n{}k{} {}x{}+{}",
+            self.m, self.n, self.k, self.a, self.b, self.c
+        )
+    }
+}
@@ -113,6 +113,134 @@ test_binary_impl!(
     ]
 );
 
+#[cube(launch)]
+pub fn kernel_saturating<I: Int>(
+    lhs: &Array<Line<I>>,
+    rhs: &Array<Line<I>>,
+    sum: &mut Array<Line<I>>,
+    difference: &mut Array<Line<I>>,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        sum[ABSOLUTE_POS] = Line::saturating_add(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+        difference[ABSOLUTE_POS] = Line::saturating_sub(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_wrapping<I: Int>(
+    lhs: &Array<I>,
+    rhs: &Array<I>,
+    sum: &mut Array<I>,
+    difference: &mut Array<I>,
+    product: &mut Array<I>,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        sum[ABSOLUTE_POS] = I::wrapping_add(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+        difference[ABSOLUTE_POS] = I::wrapping_sub(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+        product[ABSOLUTE_POS] = I::wrapping_mul(lhs[ABSOLUTE_POS], rhs[ABSOLUTE_POS]);
+    }
+}
+
+fn launch_saturating<R: Runtime, I: Int + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &[I],
+    rhs: &[I],
+) -> (Vec<I>, Vec<I>) {
+    let lhs_handle = client.create(I::as_bytes(lhs));
+    let rhs_handle = client.create(I::as_bytes(rhs));
+    let sum = client.empty(core::mem::size_of_val(lhs));
+    let difference = client.empty(core::mem::size_of_val(lhs));
+
+    kernel_saturating::launch::<I, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(lhs.len() as u32 / 4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs_handle, lhs.len(), 4) },
+        unsafe { ArrayArg::from_raw_parts(&rhs_handle, rhs.len(), 4) },
+        unsafe { ArrayArg::from_raw_parts(&sum, lhs.len(), 4) },
+        unsafe { ArrayArg::from_raw_parts(&difference, lhs.len(), 4) },
+    );
+
+    (
+        I::from_bytes(&client.read(sum.binding())).to_vec(),
+        I::from_bytes(&client.read(difference.binding())).to_vec(),
+    )
+}
+
+pub fn test_saturating_i32<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = [i32::MAX, i32::MIN, i32::MAX - 1, -5, 7, i32::MIN + 1, 0, -1];
+    let rhs = [1, -1, 1, 3, i32::MIN, i32::MAX, i32::MIN, i32::MAX];
+
+    let (sum, difference) = launch_saturating::<R, i32>(&client, &lhs, &rhs);
+
+    let expected_sum: Vec<_> = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(a, b)| a.saturating_add(b))
+        .collect();
+    let expected_difference: Vec<_> = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(a, b)| a.saturating_sub(b))
+        .collect();
+    assert_eq!(sum, expected_sum);
+    assert_eq!(difference, expected_difference);
+}
+
+pub fn test_saturating_u32<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = [u32::MAX, 0, u32::MAX - 1, 5, 7, 1, u32::MAX, 3];
+    let rhs = [1, 1, 1, 3, u32::MAX, 2, u32::MAX, 0];
+
+    let (sum, difference) = launch_saturating::<R, u32>(&client, &lhs, &rhs);
+
+    let expected_sum: Vec<_> = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(a, b)| a.saturating_add(b))
+        .collect();
+    let expected_difference: Vec<_> = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(a, b)| a.saturating_sub(b))
+        .collect();
+    assert_eq!(sum, expected_sum);
+    assert_eq!(difference, expected_difference);
+}
+
+pub fn test_wrapping_i32<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = [i32::MAX, i32::MIN, 65536, -7];
+    let rhs = [1, 1, 65536, 3];
+    let lhs_handle = client.create(i32::as_bytes(&lhs));
+    let rhs_handle = client.create(i32::as_bytes(&rhs));
+    let sum = client.empty(core::mem::size_of_val(&lhs));
+    let difference = client.empty(core::mem::size_of_val(&lhs));
+    let product = client.empty(core::mem::size_of_val(&lhs));
+
+    kernel_wrapping::launch::<i32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(lhs.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs_handle, lhs.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs_handle, rhs.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&sum, lhs.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&difference, lhs.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&product, lhs.len(), 1) },
+    );
+
+    assert_eq!(
+        i32::from_bytes(&client.read(sum.binding())),
+        [i32::MIN, i32::MIN + 1, 131072, -4]
+    );
+    assert_eq!(
+        i32::from_bytes(&client.read(difference.binding())),
+        [i32::MAX - 1, i32::MAX, 0, -10]
+    );
+    assert_eq!(
+        i32::from_bytes(&client.read(product.binding())),
+        [i32::MAX, i32::MIN, 0, -21]
+    );
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_binary {
@@ -131,6 +259,9 @@ macro_rules! testgen_binary {
             }
 
             add_test!(test_dot);
+            add_test!(test_saturating_i32);
+            add_test!(test_saturating_u32);
+            add_test!(test_wrapping_i32);
         }
     };
 }
@@ -0,0 +1,175 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_rotate<I: Int>(
+    input: &Array<Line<I>>,
+    left: &mut Array<Line<I>>,
+    right: &mut Array<Line<I>>,
+    #[comptime] amount: u32,
+) {
+    if ABSOLUTE_POS < input.len() {
+        left[ABSOLUTE_POS] = Line::rotate_left(input[ABSOLUTE_POS], amount);
+        right[ABSOLUTE_POS] = Line::rotate_right(input[ABSOLUTE_POS], amount);
+    }
+}
+
+fn launch_rotate<R: Runtime, I: Int + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &[I],
+    amount: u32,
+) -> (Vec<I>, Vec<I>) {
+    let input_handle = client.create(I::as_bytes(input));
+    let left = client.empty(core::mem::size_of_val(input));
+    let right = client.empty(core::mem::size_of_val(input));
+
+    kernel_rotate::launch::<I, R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(input.len() as u32 / 4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, input.len(), 4) },
+        unsafe { ArrayArg::from_raw_parts(&left, input.len(), 4) },
+        unsafe { ArrayArg::from_raw_parts(&right, input.len(), 4) },
+        amount,
+    );
+
+    (
+        I::from_bytes(&client.read(left.binding())).to_vec(),
+        I::from_bytes(&client.read(right.binding())).to_vec(),
+    )
+}
+
+pub fn test_rotate<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let uints = [0x12345678, 0x80000001, 0, u32::MAX];
+    let ints = [0x12345678, i32::MIN, -2, 1];
+
+    // Amounts of at least the bit width wrap around.
+    for amount in [0, 8, 31, 36] {
+        let (left, right) = launch_rotate::<R, u32>(&client, &uints, amount);
+        let expected_left: Vec<_> = uints.iter().map(|x| x.rotate_left(amount)).collect();
+        let expected_right: Vec<_> = uints.iter().map(|x| x.rotate_right(amount)).collect();
+        assert_eq!(left, expected_left, "rotate_left by {amount}");
+        assert_eq!(right, expected_right, "rotate_right by {amount}");
+
+        let (left, right) = launch_rotate::<R, i32>(&client, &ints, amount);
+        let expected_left: Vec<_> = ints.iter().map(|x| x.rotate_left(amount)).collect();
+        let expected_right: Vec<_> = ints.iter().map(|x| x.rotate_right(amount)).collect();
+        assert_eq!(left, expected_left, "rotate_left by {amount}");
+        assert_eq!(right, expected_right, "rotate_right by {amount}");
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_extract_bits<I: Int>(
+    input: &Array<I>,
+    offsets: &Array<u32>,
+    counts: &Array<u32>,
+    output: &mut Array<I>,
+) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = I::extract_bits(
+            input[ABSOLUTE_POS],
+            offsets[ABSOLUTE_POS],
+            counts[ABSOLUTE_POS],
+        );
+    }
+}
+
+pub fn test_extract_bits<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = [0x12345678, 0x12345678, 0x000000f0, -1, 0x7fffffff];
+    let offsets = [4, 0, 4, 30, 28];
+    let counts = [8, 32, 4, 8, 0];
+    let input_handle = client.create(i32::as_bytes(&input));
+    let offsets_handle = client.create(u32::as_bytes(&offsets));
+    let counts_handle = client.create(u32::as_bytes(&counts));
+    let output = client.empty(core::mem::size_of_val(&input));
+
+    kernel_extract_bits::launch::<i32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(input.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, input.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&offsets_handle, offsets.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&counts_handle, counts.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, input.len(), 1) },
+    );
+
+    // Signed fields are sign extended, and fields past the top bit are truncated.
+    assert_eq!(
+        i32::from_bytes(&client.read(output.binding())),
+        [0x67, 0x12345678, -1, -1, 0]
+    );
+}
+
+#[cube(launch)]
+pub fn kernel_insert_bits<I: Int>(
+    base: &Array<I>,
+    insert: &Array<I>,
+    offsets: &Array<u32>,
+    counts: &Array<u32>,
+    output: &mut Array<I>,
+) {
+    if ABSOLUTE_POS < base.len() {
+        output[ABSOLUTE_POS] = I::insert_bits(
+            base[ABSOLUTE_POS],
+            insert[ABSOLUTE_POS],
+            offsets[ABSOLUTE_POS],
+            counts[ABSOLUTE_POS],
+        );
+    }
+}
+
+pub fn test_insert_bits<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let base = [0x12345678, 0xffffffff, 0x12345678, 0, 0xabcdef01];
+    let insert = [0xab, 0, 0xffffffff, 0x87654321, 0xff];
+    let offsets = [8, 4, 28, 0, 16];
+    let counts = [8, 8, 8, 32, 0];
+    let base_handle = client.create(u32::as_bytes(&base));
+    let insert_handle = client.create(u32::as_bytes(&insert));
+    let offsets_handle = client.create(u32::as_bytes(&offsets));
+    let counts_handle = client.create(u32::as_bytes(&counts));
+    let output = client.empty(core::mem::size_of_val(&base));
+
+    kernel_insert_bits::launch::<u32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(base.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&base_handle, base.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&insert_handle, insert.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&offsets_handle, offsets.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&counts_handle, counts.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, base.len(), 1) },
+    );
+
+    assert_eq!(
+        u32::from_bytes(&client.read(output.binding())),
+        [0x1234ab78, 0xfffff00f, 0xf2345678, 0x87654321, 0xabcdef01]
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_bitwise {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_rotate() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::bitwise::test_rotate::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_extract_bits() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::bitwise::test_extract_bits::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_insert_bits() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::bitwise::test_insert_bits::<TestRuntime>(client);
+        }
+    };
+}
@@ -50,6 +50,32 @@ pub fn kernel_select(output: &mut Array<f32>, cond: u32) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_for_continue(output: &mut Array<f32>) {
+    let mut sum = 0.0f32;
+    for i in 0..output.len() {
+        if i % 2 == 0 {
+            continue;
+        }
+        sum += output[i];
+    }
+    output[0] = sum;
+}
+
+#[cube(launch)]
+pub fn kernel_while_continue(output: &mut Array<f32>) {
+    let mut sum = 0.0f32;
+    let mut i = 0;
+    while i < output.len() {
+        i += 1;
+        if i % 2 == 0 {
+            continue;
+        }
+        sum += output[i - 1];
+    }
+    output[0] = sum;
+}
+
 pub fn test_switch_statement<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
 
@@ -151,6 +177,38 @@ pub fn test_select<R: Runtime>(client: ComputeClient<R::Server, R::Channel>, con
     }
 }
 
+pub fn test_for_continue<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+
+    kernel_for_continue::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(1, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 4, 1) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 6.0);
+}
+
+pub fn test_while_continue<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+
+    kernel_while_continue::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(1, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 4, 1) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 4.0);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_branch {
@@ -192,5 +250,17 @@ macro_rules! testgen_branch {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::branch::test_select::<TestRuntime>(client, false);
         }
+
+        #[test]
+        fn test_for_continue() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_for_continue::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_while_continue() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::branch::test_while_continue::<TestRuntime>(client);
+        }
     };
 }
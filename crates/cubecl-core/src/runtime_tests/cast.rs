@@ -0,0 +1,191 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_cast_float_to_int(input: &Array<f32>, ints: &mut Array<i32>, uints: &mut Array<u32>) {
+    if UNIT_POS < input.len() {
+        let value = input[UNIT_POS];
+        ints[UNIT_POS] = i32::cast_from(value);
+        uints[UNIT_POS] = u32::cast_from(f32::abs(value));
+    }
+}
+
+pub fn test_cast_float_to_int<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = [2.7, -2.7, 0.5, 100.0];
+    let input_handle = client.create(f32::as_bytes(&input));
+    let ints = client.empty(input.len() * core::mem::size_of::<i32>());
+    let uints = client.empty(input.len() * core::mem::size_of::<u32>());
+
+    kernel_cast_float_to_int::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(input.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, input.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&ints, input.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&uints, input.len(), 1) },
+    );
+
+    // Conversions to integers truncate toward zero.
+    assert_eq!(
+        i32::from_bytes(&client.read(ints.binding())),
+        [2, -2, 0, 100]
+    );
+    assert_eq!(
+        u32::from_bytes(&client.read(uints.binding())),
+        [2, 2, 0, 100]
+    );
+}
+
+#[cube(launch)]
+pub fn kernel_cast_int_to_float(ints: &Array<i32>, uints: &Array<u32>, output: &mut Array<f32>) {
+    if UNIT_POS < ints.len() {
+        output[UNIT_POS] = f32::cast_from(ints[UNIT_POS]);
+        output[UNIT_POS + ints.len()] = f32::cast_from(uints[UNIT_POS]);
+    }
+}
+
+pub fn test_cast_int_to_float<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let ints = [16777217, 16777219, -16777219, 7];
+    let uints = [4294967295, 16777217, 16777219, 7];
+    let ints_handle = client.create(i32::as_bytes(&ints));
+    let uints_handle = client.create(u32::as_bytes(&uints));
+    let output = client.empty(2 * ints.len() * core::mem::size_of::<f32>());
+
+    kernel_cast_int_to_float::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(ints.len() as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&ints_handle, ints.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&uints_handle, uints.len(), 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 2 * ints.len(), 1) },
+    );
+
+    // Integers without an exact `f32` round to nearest even.
+    assert_eq!(
+        f32::from_bytes(&client.read(output.binding())),
+        [
+            16777216.0,
+            16777220.0,
+            -16777220.0,
+            7.0,
+            4294967296.0,
+            16777216.0,
+            16777220.0,
+            7.0
+        ]
+    );
+}
+
+#[cube(launch)]
+pub fn kernel_pack_half(
+    input: &Array<Line<f32>>,
+    packed: &mut Array<u32>,
+    unpacked: &mut Array<Line<f32>>,
+    #[comptime] bf16: bool,
+) {
+    if UNIT_POS < packed.len() {
+        let bits = if bf16 {
+            pack_bf16(input[UNIT_POS])
+        } else {
+            pack_f16(input[UNIT_POS])
+        };
+        packed[UNIT_POS] = bits;
+        unpacked[UNIT_POS] = if bf16 {
+            unpack_bf16(bits)
+        } else {
+            unpack_f16(bits)
+        };
+    }
+}
+
+fn pack_half<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &[f32],
+    bf16: bool,
+) -> (Vec<u32>, Vec<f32>) {
+    let num_packed = input.len() / 2;
+    let input_handle = client.create(f32::as_bytes(input));
+    let packed = client.empty(num_packed * core::mem::size_of::<u32>());
+    let unpacked = client.empty(core::mem::size_of_val(input));
+
+    kernel_pack_half::launch::<R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(num_packed as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, input.len(), 2) },
+        unsafe { ArrayArg::from_raw_parts(&packed, num_packed, 1) },
+        unsafe { ArrayArg::from_raw_parts(&unpacked, input.len(), 2) },
+        bf16,
+    );
+
+    (
+        u32::from_bytes(&client.read(packed.binding())).to_vec(),
+        f32::from_bytes(&client.read(unpacked.binding())).to_vec(),
+    )
+}
+
+pub fn test_pack_f16<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = [
+        1.0,
+        -2.5,
+        1.0 + 2f32.powi(-12),
+        1.0 + 2f32.powi(-11) + 2f32.powi(-13),
+    ];
+
+    let (packed, unpacked) = pack_half::<R>(&client, &input, false);
+
+    assert_eq!(packed, [0xc1003c00, 0x3c013c00]);
+    assert_eq!(unpacked, [1.0, -2.5, 1.0, 1.0 + 2f32.powi(-10)]);
+}
+
+pub fn test_pack_bf16<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = [
+        1.0,
+        -2.5,
+        1.0 + 2f32.powi(-8),
+        1.0 + 3.0 * 2f32.powi(-8),
+        f32::NAN,
+        f32::MAX,
+    ];
+
+    let (packed, unpacked) = pack_half::<R>(&client, &input, true);
+
+    // Ties round to even, NaNs stay NaNs and values past the largest `bf16` round to infinity.
+    assert_eq!(packed, [0xc0203f80, 0x3f823f80, 0x7f807fc0]);
+    assert_eq!(&unpacked[..4], [1.0, -2.5, 1.0, 1.0 + 2f32.powi(-6)]);
+    assert!(unpacked[4].is_nan());
+    assert_eq!(unpacked[5], f32::INFINITY);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_cast {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_cast_float_to_int() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_cast_float_to_int::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_cast_int_to_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_cast_int_to_float::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_pack_f16() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_pack_f16::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_pack_bf16() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cast::test_pack_bf16::<TestRuntime>(client);
+        }
+    };
+}
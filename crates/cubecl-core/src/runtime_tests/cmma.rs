@@ -1,6 +1,6 @@
 use crate as cubecl;
 
-use crate::Feature;
+use crate::{Feature, MmaConfig};
 use cubecl::{
     ir::{Elem, FloatKind},
     prelude::*,
@@ -103,6 +103,122 @@ pub fn test_simple_1<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     assert_eq!(expected, actual);
 }
 
+#[cube(launch)]
+pub fn kernel_shape(
+    lhs: &Array<f16>,
+    rhs: &Array<f16>,
+    out: &mut Array<f32>,
+    #[comptime] m: u32,
+    #[comptime] n: u32,
+    #[comptime] k: u32,
+) {
+    let a = cmma::Matrix::<f16>::from_slice(
+        cmma::MatrixIdent::A,
+        m,
+        n,
+        k,
+        cmma::MatrixLayout::RowMajor,
+        lhs.as_slice(),
+        k,
+    );
+    let b = cmma::Matrix::<f16>::from_slice(
+        cmma::MatrixIdent::B,
+        m,
+        n,
+        k,
+        cmma::MatrixLayout::ColMajor,
+        rhs.as_slice(),
+        k,
+    );
+    let c = cmma::Matrix::<f32>::from_value(
+        cmma::MatrixIdent::Accumulator,
+        m,
+        n,
+        k,
+        cmma::MatrixLayout::Undefined,
+        0.0,
+    );
+
+    cmma::execute::<f16, f16, f32, f32>(&a, &b, &c, &c);
+
+    cmma::store(out.as_slice_mut(), &c, n, cmma::MatrixLayout::RowMajor);
+}
+
+fn launch_shape<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    size: u32,
+) -> Result<(), LaunchError> {
+    let len = (size * size) as usize;
+    let lhs = client.create(f16::as_bytes(&vec![f16::from_f32(1.0); len]));
+    let rhs = client.create(f16::as_bytes(&vec![f16::from_f32(1.0); len]));
+    let out = client.empty(core::mem::size_of::<f32>() * len);
+
+    kernel_shape::launch_checked::<R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts_typed::<f16>(&lhs, len, 1) },
+        unsafe { ArrayArg::from_raw_parts_typed::<f16>(&rhs, len, 1) },
+        unsafe { ArrayArg::from_raw_parts_typed::<f32>(&out, len, 1) },
+        size,
+        size,
+        size,
+    )
+}
+
+pub fn test_shape_accepted<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let config = MmaConfig {
+        a: Elem::Float(FloatKind::F16),
+        b: Elem::Float(FloatKind::F16),
+        c: Elem::Float(FloatKind::F32),
+        m: 16,
+        k: 16,
+        n: 16,
+    };
+    if !config.is_supported(client.properties()) {
+        // We can't execute the test, skip.
+        return;
+    }
+
+    assert_eq!(launch_shape::<R>(&client, 16), Ok(()));
+}
+
+/// No device supports `m3n3k3` tiles, which must be rejected before the kernel is compiled.
+pub fn test_shape_rejected<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let config = MmaConfig {
+        a: Elem::Float(FloatKind::F16),
+        b: Elem::Float(FloatKind::F16),
+        c: Elem::Float(FloatKind::F32),
+        m: 3,
+        k: 3,
+        n: 3,
+    };
+
+    assert_eq!(
+        launch_shape::<R>(&client, 3),
+        Err(LaunchError::UnsupportedCmma(config))
+    );
+}
+
+/// Launches without the checks of the arguments validate the cmma configurations as well.
+pub fn test_shape_rejected_launch<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(f16::as_bytes(&[f16::from_f32(1.0); 9]));
+    let rhs = client.create(f16::as_bytes(&[f16::from_f32(1.0); 9]));
+    let out = client.empty(core::mem::size_of::<f32>() * 9);
+
+    kernel_shape::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 9, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 9, 1) },
+        unsafe { ArrayArg::from_raw_parts(&out, 9, 1) },
+        3,
+        3,
+        3,
+    );
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_cmma {
@@ -114,5 +230,24 @@ macro_rules! testgen_cmma {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::cmma::test_simple_1::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_cmma_shape_accepted() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cmma::test_shape_accepted::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_cmma_shape_rejected() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cmma::test_shape_rejected::<TestRuntime>(client);
+        }
+
+        #[test]
+        #[should_panic(expected = "which the device doesn't support")]
+        fn test_cmma_shape_rejected_launch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::cmma::test_shape_rejected_launch::<TestRuntime>(client);
+        }
     };
 }
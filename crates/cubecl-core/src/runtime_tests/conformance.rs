@@ -0,0 +1,843 @@
+//! Check that the IR operators have the same semantics on every runtime, over edge-case inputs.
+//!
+//! Each operator runs over every edge case, or every pair of them, and its results are compared
+//! with a reference on the CPU following the semantics agreed upon by all backends:
+//!
+//! - Float arithmetic, comparisons and rounding follow IEEE 754, including infinities and `NaN`.
+//!   A `NaN` result matches any `NaN`, and the sign of zero isn't checked.
+//! - Denormals may be flushed to zero, both as inputs and as results.
+//! - `max` and `min` with a `NaN` operand may return either `NaN` or the other operand.
+//! - Division, square root and transcendental functions are only checked within a tolerance on the
+//!   inputs where backends specify their accuracy, which excludes most special values.
+//! - Integer `+`, `-` and `*` on signed integers are only checked when they don't overflow, which
+//!   is undefined on CUDA. The wrapping operators are checked everywhere.
+//! - Integer division and remainder by zero, and of the minimum by `-1`, aren't checked.
+//! - Shifts are only checked for amounts smaller than the bit width.
+//!
+//! Every divergence is collected before failing, so a single run reports all the results on which
+//! a runtime disagrees with the others.
+use core::fmt::{Debug, Display};
+
+use crate as cubecl;
+
+use cubecl::prelude::*;
+use cubecl_test::Tolerance;
+
+/// The float edge cases, including denormals, extremes and special values.
+pub const FLOAT_CASES: [f32; 17] = [
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    0.5,
+    1.5,
+    -2.5,
+    3.0,
+    100.0,
+    f32::MIN_POSITIVE,
+    1e-40,
+    -1e-40,
+    f32::MAX,
+    f32::MIN,
+    f32::INFINITY,
+    f32::NEG_INFINITY,
+    f32::NAN,
+];
+
+/// The signed integer edge cases.
+pub const INT_CASES: [i32; 9] = [0, 1, -1, 2, 7, -7, 31, i32::MAX, i32::MIN];
+
+/// The unsigned integer edge cases.
+pub const UINT_CASES: [u32; 7] = [0, 1, 2, 7, 31, 0x8000_0000, u32::MAX];
+
+/// A result of a kernel outside of the semantics agreed upon by the runtimes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub operator: String,
+    pub inputs: String,
+    pub actual: String,
+    pub accepted: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}({}) = {}, accepted: {}",
+            self.operator, self.inputs, self.actual, self.accepted
+        )
+    }
+}
+
+/// Panic with a report of the divergences, if there are any.
+pub fn assert_conforms(divergences: &[Divergence]) {
+    if divergences.is_empty() {
+        return;
+    }
+
+    let report = divergences
+        .iter()
+        .map(|divergence| format!("  {divergence}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    panic!(
+        "{} results diverge from the agreed semantics:\n{report}",
+        divergences.len()
+    );
+}
+
+#[derive(CubeType, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FloatUnaryOp {
+    Abs,
+    Neg,
+    Floor,
+    Ceil,
+    Round,
+    Sqrt,
+    Recip,
+    Exp,
+    Log,
+    Sin,
+    Cos,
+    Tanh,
+}
+
+#[derive(CubeType, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FloatBinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Modulo,
+    Rem,
+    Max,
+    Min,
+    Powf,
+}
+
+#[derive(CubeType, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum ComparisonOp {
+    Equal,
+    NotEqual,
+    Lower,
+    LowerEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(CubeType, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum IntBinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Modulo,
+    Rem,
+    Max,
+    Min,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    ShiftLeft,
+    ShiftRight,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+}
+
+impl FloatUnaryOp {
+    pub const ALL: [Self; 12] = [
+        Self::Abs,
+        Self::Neg,
+        Self::Floor,
+        Self::Ceil,
+        Self::Round,
+        Self::Sqrt,
+        Self::Recip,
+        Self::Exp,
+        Self::Log,
+        Self::Sin,
+        Self::Cos,
+        Self::Tanh,
+    ];
+
+    /// The accepted result for the input, or [None] when the input isn't checked.
+    pub fn reference(&self, x: f32) -> Option<f32> {
+        let finite = x.is_finite();
+        match self {
+            Self::Abs => Some(x.abs()),
+            Self::Neg => Some(-x),
+            Self::Floor => Some(x.floor()),
+            Self::Ceil => Some(x.ceil()),
+            Self::Round => Some(x.round_ties_even()),
+            Self::Sqrt => (x >= 0.0).then(|| x.sqrt()),
+            Self::Recip => (x.is_normal() && x.abs() <= 2f32.powi(126)).then(|| x.recip()),
+            Self::Exp => (x.abs() <= 80.0).then(|| x.exp()),
+            Self::Log => (finite && x >= f32::MIN_POSITIVE).then(|| x.ln()),
+            Self::Sin => (x.abs() <= 100.0).then(|| x.sin()),
+            Self::Cos => (x.abs() <= 100.0).then(|| x.cos()),
+            Self::Tanh => finite.then(|| x.tanh()),
+        }
+    }
+
+    pub fn tolerance(&self) -> Tolerance {
+        match self {
+            Self::Abs | Self::Neg | Self::Floor | Self::Ceil | Self::Round => Tolerance::exact(),
+            Self::Sqrt | Self::Recip => Tolerance::relative(1e-6),
+            Self::Exp | Self::Log | Self::Sin | Self::Cos | Self::Tanh => Tolerance::default(),
+        }
+    }
+}
+
+impl FloatBinaryOp {
+    pub const ALL: [Self; 9] = [
+        Self::Add,
+        Self::Sub,
+        Self::Mul,
+        Self::Div,
+        Self::Modulo,
+        Self::Rem,
+        Self::Max,
+        Self::Min,
+        Self::Powf,
+    ];
+
+    /// The accepted results for the inputs, empty when the inputs aren't checked.
+    pub fn reference(&self, x: f32, y: f32) -> Vec<f32> {
+        // The remainders are computed from the quotient on some backends, which is only exact
+        // while it fits the mantissa.
+        let exact_quotient = x.is_finite() && y.is_normal() && (x / y).abs() < 2f32.powi(23);
+        match self {
+            Self::Add => vec![x + y],
+            Self::Sub => vec![x - y],
+            Self::Mul => vec![x * y],
+            Self::Div if y.is_normal() && y.abs() <= 2f32.powi(126) => vec![x / y],
+            Self::Modulo if exact_quotient => vec![x % y],
+            Self::Rem if exact_quotient => vec![x - y * (x / y).floor()],
+            Self::Max | Self::Min if x.is_nan() || y.is_nan() => vec![f32::NAN, x.min(y)],
+            Self::Max => vec![x.max(y)],
+            Self::Min => vec![x.min(y)],
+            Self::Powf if x.is_normal() && x > 0.0 && y.abs() <= 10.0 => {
+                let result = x.powf(y);
+                if result.is_normal() {
+                    vec![result]
+                } else {
+                    vec![]
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    pub fn tolerance(&self) -> Tolerance {
+        match self {
+            Self::Add | Self::Sub | Self::Mul | Self::Max | Self::Min => Tolerance::exact(),
+            Self::Div => Tolerance::relative(1e-6),
+            Self::Modulo | Self::Rem | Self::Powf => Tolerance::default(),
+        }
+    }
+}
+
+impl ComparisonOp {
+    pub const ALL: [Self; 6] = [
+        Self::Equal,
+        Self::NotEqual,
+        Self::Lower,
+        Self::LowerEqual,
+        Self::Greater,
+        Self::GreaterEqual,
+    ];
+
+    pub fn reference<T: PartialOrd>(&self, x: T, y: T) -> bool {
+        match self {
+            Self::Equal => x == y,
+            Self::NotEqual => x != y,
+            Self::Lower => x < y,
+            Self::LowerEqual => x <= y,
+            Self::Greater => x > y,
+            Self::GreaterEqual => x >= y,
+        }
+    }
+}
+
+impl IntBinaryOp {
+    pub const ALL: [Self; 16] = [
+        Self::Add,
+        Self::Sub,
+        Self::Mul,
+        Self::Div,
+        Self::Modulo,
+        Self::Rem,
+        Self::Max,
+        Self::Min,
+        Self::WrappingAdd,
+        Self::WrappingSub,
+        Self::WrappingMul,
+        Self::ShiftLeft,
+        Self::ShiftRight,
+        Self::BitwiseAnd,
+        Self::BitwiseOr,
+        Self::BitwiseXor,
+    ];
+
+    /// The accepted result for 32-bit integers, or [None] when the inputs aren't checked.
+    ///
+    /// The values are widened to `i64`, and `signed` tells how the results wrap around.
+    pub fn reference(&self, x: i64, y: i64, signed: bool) -> Option<i64> {
+        let wrap = |value: i64| match signed {
+            true => value as i32 as i64,
+            false => value as u32 as i64,
+        };
+        let exact = |value: i64| (!signed || wrap(value) == value).then_some(wrap(value));
+        let divisible = y != 0 && !(signed && x == i32::MIN as i64 && y == -1);
+
+        match self {
+            Self::Add => exact(x + y),
+            Self::Sub => exact(x - y),
+            Self::Mul => exact(x * y),
+            Self::Div => divisible.then(|| x / y),
+            Self::Modulo => divisible.then(|| x % y),
+            Self::Rem => divisible.then(|| ((x % y) + y) % y),
+            Self::Max => Some(Ord::max(x, y)),
+            Self::Min => Some(Ord::min(x, y)),
+            Self::WrappingAdd => Some(wrap(x + y)),
+            Self::WrappingSub => Some(wrap(x - y)),
+            Self::WrappingMul => Some(wrap(x.wrapping_mul(y))),
+            Self::ShiftLeft => (0..32).contains(&y).then(|| wrap(x << y)),
+            Self::ShiftRight => (0..32).contains(&y).then(|| x >> y),
+            Self::BitwiseAnd => Some(x & y),
+            Self::BitwiseOr => Some(wrap(x | y)),
+            Self::BitwiseXor => Some(wrap(x ^ y)),
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_float_unary<F: Float + core::ops::Neg<Output = F>>(
+    input: &Array<F>,
+    output: &mut Array<F>,
+    #[comptime] op: FloatUnaryOp,
+) {
+    if ABSOLUTE_POS < input.len() {
+        let x = input[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = match op {
+            FloatUnaryOp::Abs => F::abs(x),
+            FloatUnaryOp::Neg => -x,
+            FloatUnaryOp::Floor => F::floor(x),
+            FloatUnaryOp::Ceil => F::ceil(x),
+            FloatUnaryOp::Round => F::round(x),
+            FloatUnaryOp::Sqrt => F::sqrt(x),
+            FloatUnaryOp::Recip => F::recip(x),
+            FloatUnaryOp::Exp => F::exp(x),
+            FloatUnaryOp::Log => F::log(x),
+            FloatUnaryOp::Sin => F::sin(x),
+            FloatUnaryOp::Cos => F::cos(x),
+            FloatUnaryOp::Tanh => F::tanh(x),
+        };
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_float_binary<F: Float + core::ops::Rem<Output = F>>(
+    lhs: &Array<F>,
+    rhs: &Array<F>,
+    output: &mut Array<F>,
+    #[comptime] op: FloatBinaryOp,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        let x = lhs[ABSOLUTE_POS];
+        let y = rhs[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = match op {
+            FloatBinaryOp::Add => x + y,
+            FloatBinaryOp::Sub => x - y,
+            FloatBinaryOp::Mul => x * y,
+            FloatBinaryOp::Div => x / y,
+            FloatBinaryOp::Modulo => x % y,
+            FloatBinaryOp::Rem => Remainder::rem(x, y),
+            FloatBinaryOp::Max => F::max(x, y),
+            FloatBinaryOp::Min => F::min(x, y),
+            FloatBinaryOp::Powf => F::powf(x, y),
+        };
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_comparison<N: Numeric>(
+    lhs: &Array<N>,
+    rhs: &Array<N>,
+    output: &mut Array<u32>,
+    #[comptime] op: ComparisonOp,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        let x = lhs[ABSOLUTE_POS];
+        let y = rhs[ABSOLUTE_POS];
+        let result = match op {
+            ComparisonOp::Equal => x == y,
+            ComparisonOp::NotEqual => x != y,
+            ComparisonOp::Lower => x < y,
+            ComparisonOp::LowerEqual => x <= y,
+            ComparisonOp::Greater => x > y,
+            ComparisonOp::GreaterEqual => x >= y,
+        };
+        output[ABSOLUTE_POS] = select(result, 1, 0);
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_int_binary<I: Int>(
+    lhs: &Array<I>,
+    rhs: &Array<I>,
+    output: &mut Array<I>,
+    #[comptime] op: IntBinaryOp,
+) {
+    if ABSOLUTE_POS < lhs.len() {
+        let x = lhs[ABSOLUTE_POS];
+        let y = rhs[ABSOLUTE_POS];
+        output[ABSOLUTE_POS] = match op {
+            IntBinaryOp::Add => x + y,
+            IntBinaryOp::Sub => x - y,
+            IntBinaryOp::Mul => x * y,
+            IntBinaryOp::Div => x / y,
+            IntBinaryOp::Modulo => x % y,
+            IntBinaryOp::Rem => Remainder::rem(x, y),
+            IntBinaryOp::Max => I::max(x, y),
+            IntBinaryOp::Min => I::min(x, y),
+            IntBinaryOp::WrappingAdd => I::wrapping_add(x, y),
+            IntBinaryOp::WrappingSub => I::wrapping_sub(x, y),
+            IntBinaryOp::WrappingMul => I::wrapping_mul(x, y),
+            IntBinaryOp::ShiftLeft => x << y,
+            IntBinaryOp::ShiftRight => x >> y,
+            IntBinaryOp::BitwiseAnd => x & y,
+            IntBinaryOp::BitwiseOr => x | y,
+            IntBinaryOp::BitwiseXor => x ^ y,
+        };
+    }
+}
+
+/// Every pair of the cases, as the left and right operands.
+fn pairs<T: Copy>(cases: &[T]) -> (Vec<T>, Vec<T>) {
+    cases
+        .iter()
+        .flat_map(|x| cases.iter().map(move |y| (*x, *y)))
+        .unzip()
+}
+
+/// Flush a denormal to a zero of the same sign.
+fn flush(x: f32) -> f32 {
+    if x.is_subnormal() {
+        0.0f32.copysign(x)
+    } else {
+        x
+    }
+}
+
+/// The accepted results, with or without flushing the denormals of the inputs and results.
+fn accepted_float(inputs: &[f32], reference: impl Fn(&[f32]) -> Vec<f32>) -> Vec<f32> {
+    let flushed = inputs.iter().copied().map(flush).collect::<Vec<_>>();
+    let mut accepted = reference(inputs);
+    if flushed != inputs {
+        accepted.extend(reference(&flushed));
+    }
+    let flushed_results = accepted.iter().copied().map(flush).collect::<Vec<_>>();
+    accepted.extend(flushed_results);
+    accepted.sort_by(f32::total_cmp);
+    accepted.dedup_by(|a, b| a.to_bits() == b.to_bits());
+    accepted
+}
+
+/// Compare the float results with the references of their inputs.
+fn check_float(
+    operator: impl Debug,
+    inputs: &[Vec<f32>],
+    actual: &[f32],
+    tolerance: Tolerance,
+    reference: impl Fn(&[f32]) -> Vec<f32>,
+) -> Vec<Divergence> {
+    actual
+        .iter()
+        .enumerate()
+        .filter_map(|(i, actual)| {
+            let inputs = inputs.iter().map(|input| input[i]).collect::<Vec<_>>();
+            let accepted = accepted_float(&inputs, &reference);
+            let conforms = accepted.is_empty()
+                || accepted
+                    .iter()
+                    .any(|expected| tolerance.accepts(*actual as f64, *expected as f64));
+
+            (!conforms).then(|| Divergence {
+                operator: format!("{operator:?}"),
+                inputs: format_values(&inputs),
+                actual: format!("{actual:?}"),
+                accepted: format_values(&accepted),
+            })
+        })
+        .collect()
+}
+
+fn format_values<T: Debug>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|value| format!("{value:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn conformance_float_unary<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<Divergence> {
+    let len = FLOAT_CASES.len();
+    let input = client.create(f32::as_bytes(&FLOAT_CASES));
+
+    FloatUnaryOp::ALL
+        .iter()
+        .flat_map(|op| {
+            let output = client.empty(len * core::mem::size_of::<f32>());
+            unsafe {
+                kernel_float_unary::launch_unchecked::<f32, R>(
+                    client,
+                    CubeCount::Static(1, 1, 1),
+                    CubeDim::new(len as u32, 1, 1),
+                    ArrayArg::from_raw_parts(&input, len, 1),
+                    ArrayArg::from_raw_parts(&output, len, 1),
+                    *op,
+                )
+            };
+
+            let actual = client.read(output.binding());
+            check_float(
+                op,
+                &[FLOAT_CASES.to_vec()],
+                f32::from_bytes(&actual),
+                op.tolerance(),
+                |inputs| op.reference(inputs[0]).into_iter().collect(),
+            )
+        })
+        .collect()
+}
+
+pub fn conformance_float_binary<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<Divergence> {
+    let (lhs, rhs) = pairs(&FLOAT_CASES);
+    let len = lhs.len();
+    let lhs_handle = client.create(f32::as_bytes(&lhs));
+    let rhs_handle = client.create(f32::as_bytes(&rhs));
+
+    FloatBinaryOp::ALL
+        .iter()
+        .flat_map(|op| {
+            let output = client.empty(len * core::mem::size_of::<f32>());
+            unsafe {
+                kernel_float_binary::launch_unchecked::<f32, R>(
+                    client,
+                    CubeCount::Static(len.div_ceil(64) as u32, 1, 1),
+                    CubeDim::new(64, 1, 1),
+                    ArrayArg::from_raw_parts(&lhs_handle, len, 1),
+                    ArrayArg::from_raw_parts(&rhs_handle, len, 1),
+                    ArrayArg::from_raw_parts(&output, len, 1),
+                    *op,
+                )
+            };
+
+            let actual = client.read(output.binding());
+            check_float(
+                op,
+                &[lhs.clone(), rhs.clone()],
+                f32::from_bytes(&actual),
+                op.tolerance(),
+                |inputs| op.reference(inputs[0], inputs[1]),
+            )
+        })
+        .collect()
+}
+
+/// Compare the floats and the integers of both signedness.
+pub fn conformance_comparison<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+
+    for op in ComparisonOp::ALL {
+        let (lhs, rhs) = pairs(&FLOAT_CASES);
+        let actual = launch_comparison::<R, f32>(client, &lhs, &rhs, op);
+        for (i, actual) in actual.into_iter().enumerate() {
+            let inputs = [lhs[i], rhs[i]];
+            let accepted = [op.reference(inputs[0], inputs[1]), {
+                let [x, y] = inputs.map(flush);
+                op.reference(x, y)
+            }];
+            if !accepted.contains(&actual) {
+                divergences.push(Divergence {
+                    operator: format!("{op:?}"),
+                    inputs: format_values(&inputs),
+                    actual: format!("{actual}"),
+                    accepted: format_values(&accepted),
+                });
+            }
+        }
+
+        let (lhs, rhs) = pairs(&INT_CASES);
+        divergences.extend(check_comparison(
+            op,
+            &lhs,
+            &rhs,
+            launch_comparison::<R, i32>(client, &lhs, &rhs, op),
+        ));
+        let (lhs, rhs) = pairs(&UINT_CASES);
+        divergences.extend(check_comparison(
+            op,
+            &lhs,
+            &rhs,
+            launch_comparison::<R, u32>(client, &lhs, &rhs, op),
+        ));
+    }
+
+    divergences
+}
+
+fn launch_comparison<R: Runtime, N: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &[N],
+    rhs: &[N],
+    op: ComparisonOp,
+) -> Vec<bool> {
+    let len = lhs.len();
+    let lhs = client.create(N::as_bytes(lhs));
+    let rhs = client.create(N::as_bytes(rhs));
+    let output = client.empty(len * core::mem::size_of::<u32>());
+
+    unsafe {
+        kernel_comparison::launch_unchecked::<N, R>(
+            client,
+            CubeCount::Static(len.div_ceil(64) as u32, 1, 1),
+            CubeDim::new(64, 1, 1),
+            ArrayArg::from_raw_parts(&lhs, len, 1),
+            ArrayArg::from_raw_parts(&rhs, len, 1),
+            ArrayArg::from_raw_parts(&output, len, 1),
+            op,
+        )
+    };
+
+    u32::from_bytes(&client.read(output.binding()))
+        .iter()
+        .map(|result| *result != 0)
+        .collect()
+}
+
+fn check_comparison<T: PartialOrd + Debug + Copy>(
+    op: ComparisonOp,
+    lhs: &[T],
+    rhs: &[T],
+    actual: Vec<bool>,
+) -> Vec<Divergence> {
+    actual
+        .into_iter()
+        .enumerate()
+        .filter(|(i, actual)| *actual != op.reference(lhs[*i], rhs[*i]))
+        .map(|(i, actual)| Divergence {
+            operator: format!("{op:?}"),
+            inputs: format_values(&[lhs[i], rhs[i]]),
+            actual: format!("{actual}"),
+            accepted: format!("{}", op.reference(lhs[i], rhs[i])),
+        })
+        .collect()
+}
+
+/// Check the integer operators on both signed and unsigned integers.
+pub fn conformance_int_binary<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+
+    for op in IntBinaryOp::ALL {
+        let (lhs, rhs) = pairs(&INT_CASES);
+        let actual = launch_int_binary::<R, i32>(client, &lhs, &rhs, op);
+        divergences.extend(check_int(op, &lhs, &rhs, &actual, true));
+
+        let (lhs, rhs) = pairs(&UINT_CASES);
+        let actual = launch_int_binary::<R, u32>(client, &lhs, &rhs, op);
+        divergences.extend(check_int(op, &lhs, &rhs, &actual, false));
+    }
+
+    divergences
+}
+
+fn launch_int_binary<R: Runtime, I: Int + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &[I],
+    rhs: &[I],
+    op: IntBinaryOp,
+) -> Vec<I> {
+    let len = lhs.len();
+    let output = client.empty(core::mem::size_of_val(lhs));
+    let lhs = client.create(I::as_bytes(lhs));
+    let rhs = client.create(I::as_bytes(rhs));
+
+    unsafe {
+        kernel_int_binary::launch_unchecked::<I, R>(
+            client,
+            CubeCount::Static(len.div_ceil(64) as u32, 1, 1),
+            CubeDim::new(64, 1, 1),
+            ArrayArg::from_raw_parts(&lhs, len, 1),
+            ArrayArg::from_raw_parts(&rhs, len, 1),
+            ArrayArg::from_raw_parts(&output, len, 1),
+            op,
+        )
+    };
+
+    I::from_bytes(&client.read(output.binding())).to_vec()
+}
+
+fn check_int<I: Into<i64> + Copy + Debug>(
+    op: IntBinaryOp,
+    lhs: &[I],
+    rhs: &[I],
+    actual: &[I],
+    signed: bool,
+) -> Vec<Divergence> {
+    actual
+        .iter()
+        .enumerate()
+        .filter_map(|(i, actual)| {
+            let expected = op.reference(lhs[i].into(), rhs[i].into(), signed)?;
+            let actual: i64 = (*actual).into();
+            (actual != expected).then(|| Divergence {
+                operator: format!("{op:?}"),
+                inputs: format_values(&[lhs[i], rhs[i]]),
+                actual: format!("{actual}"),
+                accepted: format!("{expected}"),
+            })
+        })
+        .collect()
+}
+
+pub fn test_conformance_float_unary<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    assert_conforms(&conformance_float_unary::<R>(&client));
+}
+
+pub fn test_conformance_float_binary<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    assert_conforms(&conformance_float_binary::<R>(&client));
+}
+
+pub fn test_conformance_comparison<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    assert_conforms(&conformance_comparison::<R>(&client));
+}
+
+pub fn test_conformance_int_binary<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    assert_conforms(&conformance_int_binary::<R>(&client));
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_conformance {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_conformance_float_unary() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::conformance::test_conformance_float_unary::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_conformance_float_binary() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::conformance::test_conformance_float_binary::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_conformance_comparison() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::conformance::test_conformance_comparison::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_conformance_int_binary() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::conformance::test_conformance_int_binary::<TestRuntime>(
+                client,
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denormals_may_be_flushed() {
+        let accepted = accepted_float(&[1e-40, 1.0], |inputs| vec![inputs[0] + inputs[1]]);
+
+        assert!(accepted.contains(&1.0));
+        assert!(check_float(
+            FloatBinaryOp::Add,
+            &[vec![1e-40], vec![1e-40]],
+            &[0.0],
+            Tolerance::exact(),
+            |inputs| FloatBinaryOp::Add.reference(inputs[0], inputs[1]),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn max_with_nan_accepts_either_operand() {
+        for actual in [f32::NAN, 1.0] {
+            let divergences = check_float(
+                FloatBinaryOp::Max,
+                &[vec![f32::NAN], vec![1.0]],
+                &[actual],
+                Tolerance::exact(),
+                |inputs| FloatBinaryOp::Max.reference(inputs[0], inputs[1]),
+            );
+            assert!(divergences.is_empty(), "{divergences:?}");
+        }
+    }
+
+    #[test]
+    fn divergences_are_reported() {
+        let divergences = check_float(
+            FloatUnaryOp::Floor,
+            &[vec![-2.5, 1.5]],
+            &[-2.0, 1.0],
+            Tolerance::exact(),
+            |inputs| {
+                FloatUnaryOp::Floor
+                    .reference(inputs[0])
+                    .into_iter()
+                    .collect()
+            },
+        );
+
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(
+            divergences[0].to_string(),
+            "Floor(-2.5) = -2.0, accepted: -3.0"
+        );
+    }
+
+    #[test]
+    fn int_reference_skips_undefined_results() {
+        let reference = |op: IntBinaryOp, x: i32, y: i32| op.reference(x as i64, y as i64, true);
+
+        assert_eq!(reference(IntBinaryOp::Add, i32::MAX, 1), None);
+        assert_eq!(
+            reference(IntBinaryOp::WrappingAdd, i32::MAX, 1),
+            Some(i32::MIN as i64)
+        );
+        assert_eq!(reference(IntBinaryOp::Div, i32::MIN, -1), None);
+        assert_eq!(reference(IntBinaryOp::Rem, -7, 2), Some(1));
+        assert_eq!(reference(IntBinaryOp::Modulo, -7, 2), Some(-1));
+        assert_eq!(reference(IntBinaryOp::ShiftLeft, 1, 32), None);
+        assert_eq!(
+            IntBinaryOp::Add.reference(u32::MAX as i64, 1, false),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(inline = never)]
+pub fn polynomial(x: f32, factor: f32) -> f32 {
+    x * x * factor + x
+}
+
+#[cube(inline = never)]
+pub fn add_unit_pos(x: f32) -> f32 {
+    x + f32::cast_from(UNIT_POS)
+}
+
+#[cube(launch)]
+pub fn kernel_device_function(output: &mut Array<f32>) {
+    if UNIT_POS < output.len() {
+        let value = output[UNIT_POS];
+        output[UNIT_POS] = polynomial(value, 2.0) + polynomial(value, 3.0);
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_device_function_topology(output: &mut Array<f32>) {
+    if UNIT_POS < output.len() {
+        output[UNIT_POS] = add_unit_pos(output[UNIT_POS]);
+    }
+}
+
+pub fn test_device_function<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[1.0, 2.0, 3.0]));
+
+    kernel_device_function::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(3, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 3, 1) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[7.0, 24.0, 51.0]);
+}
+
+pub fn test_device_function_topology<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[1.0, 2.0, 3.0]));
+
+    kernel_device_function_topology::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(3, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 3, 1) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 3.0, 5.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_device_function {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_device_function() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::device_function::test_device_function::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_device_function_topology() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::device_function::test_device_function_topology::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,83 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube]
+pub trait BinaryOp {
+    fn apply<F: Float>(a: F, b: F) -> F;
+}
+
+pub struct AddOp;
+pub struct MulOp;
+
+#[cube]
+impl BinaryOp for AddOp {
+    fn apply<F: Float>(a: F, b: F) -> F {
+        a + b
+    }
+}
+
+#[cube]
+impl BinaryOp for MulOp {
+    fn apply<F: Float>(a: F, b: F) -> F {
+        a * b
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_binary_op<O: BinaryOp>(lhs: &Array<f32>, rhs: &Array<f32>, output: &mut Array<f32>) {
+    output[UNIT_POS] = O::apply::<f32>(lhs[UNIT_POS], rhs[UNIT_POS]);
+}
+
+type BinaryLaunch<R> = for<'a> fn(
+    &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+    CubeCount,
+    CubeDim,
+    ArrayArg<'a, R>,
+    ArrayArg<'a, R>,
+    ArrayArg<'a, R>,
+);
+
+pub fn test_dispatch_table<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let table = DispatchTable::<BinaryLaunch<R>>::new()
+        .register("add", kernel_binary_op::launch::<AddOp, R>)
+        .register("mul", kernel_binary_op::launch::<MulOp, R>);
+
+    let lhs = client.create(f32::as_bytes(&[2.0, 3.0]));
+    let rhs = client.create(f32::as_bytes(&[4.0, 5.0]));
+
+    for (name, expected) in [("add", [6.0, 8.0]), ("mul", [8.0, 15.0])] {
+        let output = client.empty(2 * core::mem::size_of::<f32>());
+        let launch = table.get(name).unwrap();
+
+        launch(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(2, 1, 1),
+            unsafe { ArrayArg::from_raw_parts(&lhs, 2, 1) },
+            unsafe { ArrayArg::from_raw_parts(&rhs, 2, 1) },
+            unsafe { ArrayArg::from_raw_parts(&output, 2, 1) },
+        );
+
+        let actual = client.read(output.binding());
+        let actual = f32::from_bytes(&actual);
+
+        assert_eq!(actual, expected);
+    }
+
+    assert!(table.get("sub").is_none());
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_dispatch {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_dispatch_table() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::dispatch::test_dispatch_table::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,109 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch, safe_division)]
+pub fn kernel_safe_division<N: Numeric>(lhs: &Array<N>, rhs: &Array<N>, output: &mut Array<N>) {
+    output[UNIT_POS] = lhs[UNIT_POS] / rhs[UNIT_POS];
+}
+
+#[cube(launch, safe_division)]
+pub fn kernel_safe_modulo(lhs: &Array<i32>, rhs: &Array<i32>, output: &mut Array<i32>) {
+    output[UNIT_POS] = lhs[UNIT_POS] % rhs[UNIT_POS];
+}
+
+/// Divisions by zero return zero, even for infinite and NaN-producing numerators.
+pub fn test_safe_division_float<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(f32::as_bytes(&[1.0, f32::INFINITY, 0.0, 6.0]));
+    let rhs = client.create(f32::as_bytes(&[0.0, 0.0, 0.0, 2.0]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    kernel_safe_division::launch::<f32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, [0.0, 0.0, 0.0, 3.0]);
+}
+
+pub fn test_safe_division_int<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(i32::as_bytes(&[7, -7, 0, 7]));
+    let rhs = client.create(i32::as_bytes(&[0, 0, 0, 2]));
+    let quotients = client.empty(4 * core::mem::size_of::<i32>());
+    let remainders = client.empty(4 * core::mem::size_of::<i32>());
+
+    kernel_safe_division::launch::<i32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&quotients, 4, 1) },
+    );
+    kernel_safe_modulo::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&remainders, 4, 1) },
+    );
+
+    let actual = client.read(quotients.binding());
+    assert_eq!(i32::from_bytes(&actual), [0, 0, 0, 3]);
+    let actual = client.read(remainders.binding());
+    assert_eq!(i32::from_bytes(&actual), [0, 0, 0, 1]);
+}
+
+/// The divisions by zero are counted per element of vectorized divisions.
+pub fn test_division_check<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(f32::as_bytes(&[1.0, f32::INFINITY, 0.0, 6.0]));
+    let rhs = client.create(f32::as_bytes(&[0.0, 0.0, 0.0, 2.0]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    let count = kernel_safe_division::launch_division_check::<f32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(1, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&lhs, 4, 4) },
+        unsafe { ArrayArg::from_raw_parts(&rhs, 4, 4) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 4) },
+    );
+
+    assert_eq!(count, 3);
+    let actual = client.read(output.binding());
+    assert_eq!(f32::from_bytes(&actual), [0.0, 0.0, 0.0, 3.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_division {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_safe_division_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::division::test_safe_division_float::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_safe_division_int() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::division::test_safe_division_int::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_division_check() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::division::test_division_check::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,89 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+use cubecl_runtime::server::Handle;
+use cubecl_test::{GoldenTest, Tolerance};
+
+#[cube(launch_unchecked)]
+pub fn kernel_multiply_add<F: Float>(lhs: &Array<F>, rhs: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] * rhs[ABSOLUTE_POS] + F::new(1.0);
+    }
+}
+
+#[cube(launch_unchecked)]
+pub fn kernel_off_by_one(input: &Array<u32>, output: &mut Array<u32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + 1;
+    }
+}
+
+fn launch_multiply_add<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    inputs: &[Handle],
+    outputs: &[Handle],
+) {
+    unsafe {
+        kernel_multiply_add::launch_unchecked::<f32, R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(32, 1, 1),
+            ArrayArg::from_raw_parts(&inputs[0], 32, 1),
+            ArrayArg::from_raw_parts(&inputs[1], 32, 1),
+            ArrayArg::from_raw_parts(&outputs[0], 32, 1),
+        )
+    };
+}
+
+pub fn test_golden_float<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    GoldenTest::<f32>::new()
+        .input(32, -10.0..10.0)
+        .input(32, -10.0..10.0)
+        .output(32)
+        .run(&client, launch_multiply_add::<R>, |inputs, outputs| {
+            for (i, output) in outputs[0].iter_mut().enumerate() {
+                *output = inputs[0][i] * inputs[1][i] + 1.0;
+            }
+        });
+}
+
+pub fn test_golden_mismatch<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    GoldenTest::<u32>::new()
+        .input(32, 0.0..100.0)
+        .output(32)
+        .tolerance(Tolerance::exact())
+        .run(
+            &client,
+            |client, inputs, outputs| unsafe {
+                kernel_off_by_one::launch_unchecked::<R>(
+                    client,
+                    CubeCount::Static(1, 1, 1),
+                    CubeDim::new(32, 1, 1),
+                    ArrayArg::from_raw_parts(&inputs[0], 32, 1),
+                    ArrayArg::from_raw_parts(&outputs[0], 32, 1),
+                )
+            },
+            |inputs, outputs| outputs[0].copy_from_slice(&inputs[0]),
+        );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_golden {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_golden_float() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::golden::test_golden_float::<TestRuntime>(client);
+        }
+
+        #[test]
+        #[should_panic(expected = "Values differ more than the tolerance")]
+        fn test_golden_mismatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::golden::test_golden_mismatch::<TestRuntime>(client);
+        }
+    };
+}
@@ -1,4 +1,5 @@
 use crate as cubecl;
+use cubecl::ir::Item;
 use cubecl::prelude::*;
 
 #[cube(launch)]
@@ -15,6 +16,41 @@ pub fn kernel_without_generics(output: &mut Array<f32>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_add<F: Float>(lhs: &Tensor<F>, rhs: &Tensor<F>, output: &mut Tensor<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] + rhs[ABSOLUTE_POS];
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_layout<F: Float>(input: &Tensor<F>, output: &mut Array<F>) {
+    if UNIT_POS == 0 {
+        output[0] = F::cast_from(input.shape(1));
+        output[1] = F::cast_from(input.stride(0));
+    }
+}
+
+#[derive(CubeLaunch)]
+pub struct ScaleArgs<F: Float> {
+    input: Tensor<F>,
+    bias: Array<F>,
+    scale: F,
+    #[expand(comptime)]
+    negate: bool,
+}
+
+#[cube(launch)]
+pub fn kernel_struct_arg<F: Float>(args: &ScaleArgs<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < output.len() {
+        let mut value = args.input[ABSOLUTE_POS] * args.scale + args.bias[ABSOLUTE_POS];
+        if comptime!(args.negate) {
+            value = F::new(0.0) - value;
+        }
+        output[ABSOLUTE_POS] = value;
+    }
+}
+
 pub fn test_kernel_with_generics<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
 
@@ -47,6 +83,293 @@ pub fn test_kernel_without_generics<R: Runtime>(client: ComputeClient<R::Server,
     assert_eq!(actual[0], 5.0);
 }
 
+pub fn test_kernel_create_async<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let (handle, fence) = client.create_async(f32::as_bytes(&[0.0, 1.0]));
+
+    // The kernel has to wait on the upload, otherwise the upload overwrites its output.
+    kernel_without_generics::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&handle, 2, 1) },
+    );
+
+    cubecl_common::reader::read_sync(client.wait_fence(fence));
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[5.0, 1.0]);
+}
+
+pub fn test_kernel_read_write_range<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0]));
+
+    kernel_without_generics::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&handle, 4, 1) },
+    );
+    // The write is ordered after the kernel.
+    client.write_range(&handle, 8, f32::as_bytes(&[7.0]));
+
+    let actual = client.read_range(&handle, 0, 12);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[5.0, 1.0, 7.0]);
+}
+
+pub fn test_kernel_fill<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0]));
+    let zeros = client.create(f32::as_bytes(&[1.0, 2.0]));
+
+    kernel_without_generics::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&handle, 4, 1) },
+    );
+    // The fill is ordered after the kernel.
+    client.fill(&handle, f32::as_bytes(&[7.0]));
+    client.fill(&zeros, &[0]);
+
+    let actual = client.read(handle.binding());
+    assert_eq!(f32::from_bytes(&actual), &[7.0, 7.0, 7.0, 7.0]);
+    let actual = client.read(zeros.binding());
+    assert_eq!(f32::from_bytes(&actual), &[0.0, 0.0]);
+}
+
+pub fn test_kernel_grow<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
+
+    let handle = client.grow(handle, 4 * core::mem::size_of::<f32>());
+    client.write_range(&handle, 8, f32::as_bytes(&[2.0, 3.0]));
+    kernel_without_generics::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&handle, 4, 1) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[5.0, 1.0, 2.0, 3.0]);
+}
+
+pub fn test_kernel_stream<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
+    let stream = client.create_stream();
+    let client_stream = client.clone().with_stream(stream);
+
+    kernel_without_generics::launch::<R>(
+        &client_stream,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&handle, 2, 1) },
+    );
+    client.wait(client_stream.event());
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 5.0);
+}
+
+pub fn test_kernel_launch_checked<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
+
+    kernel_without_generics::launch_checked::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts_typed::<f32>(&handle, 2, 1) },
+    )
+    .unwrap();
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual[0], 5.0);
+}
+
+pub fn test_kernel_launch_checked_elem_mismatch<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(u32::as_bytes(&[0, 1]));
+
+    let result = kernel_without_generics::launch_checked::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts_typed::<u32>(&handle, 2, 1) },
+    );
+
+    assert_eq!(
+        result,
+        Err(LaunchError::ElemMismatch {
+            position: 0,
+            expected: f32::as_elem(),
+            actual: u32::as_elem(),
+        })
+    );
+}
+
+pub fn test_kernel_launch_checked_rank_mismatch<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(f32::as_bytes(&[0.0; 4]));
+    let mut builder = KernelBuilder::default();
+    builder.output_array(Item::new(f32::as_elem()));
+    let definition = builder.build(KernelSettings::default());
+
+    let mut launcher = KernelLauncher::<R>::default();
+    launcher
+        .register_tensor(&unsafe { TensorHandleRef::from_raw_parts(&handle, &[2, 1], &[2, 2]) });
+
+    assert_eq!(
+        launcher.validate(&definition),
+        Err(LaunchError::RankMismatch {
+            position: 0,
+            expected: 1,
+            actual: 2,
+        })
+    );
+}
+
+pub fn test_kernel_launch_checked_strides_mismatch<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(f32::as_bytes(&[0.0; 4]));
+    let mut builder = KernelBuilder::default();
+    builder.input_tensor(Item::new(f32::as_elem()));
+    let definition = builder.build(KernelSettings::default());
+
+    let mut launcher = KernelLauncher::<R>::default();
+    launcher.register_tensor(&unsafe { TensorHandleRef::from_raw_parts(&handle, &[1], &[2, 2]) });
+
+    assert_eq!(
+        launcher.validate(&definition),
+        Err(LaunchError::StridesMismatch {
+            position: 0,
+            shape: 2,
+            strides: 1,
+        })
+    );
+}
+
+pub fn test_kernel_launch_scope<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let lhs = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]));
+    let rhs = client.create(f32::as_bytes(&[1.0; 8]));
+    let mut output = client.empty(8 * core::mem::size_of::<f32>());
+    let shape = [2, 4];
+    let strides = [4, 1];
+
+    launch_scope::<R, _>(&client, |scope| {
+        let lhs = scope.input::<f32>(&lhs, &shape, &strides).unwrap();
+        let rhs = scope.input::<f32>(&rhs, &shape, &strides).unwrap();
+        let output = scope.output::<f32>(&mut output, &shape, &strides).unwrap();
+
+        kernel_add::launch::<f32, R>(
+            scope.client(),
+            scope.cube_count(&output),
+            scope.cube_dim(),
+            lhs,
+            rhs,
+            output,
+        );
+    });
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+}
+
+pub fn test_kernel_launch_scope_out_of_bounds<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(f32::as_bytes(&[0.0; 8]));
+    let shape = [2, 4];
+    let strides = [8, 1];
+
+    launch_scope::<R, _>(&client, |scope| {
+        assert!(scope.input::<f32>(&input, &[2, 4], &[4, 1]).is_ok());
+        assert!(scope.input::<f32>(&input, &[0, 16], &[16, 1]).is_ok());
+        assert_eq!(
+            scope.input::<f32>(&input, &shape, &strides).err(),
+            Some(ArgumentOutOfBounds {
+                required: 12 * 4,
+                available: Some(8 * 4),
+            })
+        );
+        assert!(scope.input_array::<f32>(&input, 8).is_ok());
+        assert_eq!(
+            scope.input_array::<f32>(&input, 9).err(),
+            Some(ArgumentOutOfBounds {
+                required: 9 * 4,
+                available: Some(8 * 4),
+            })
+        );
+    });
+}
+
+pub fn test_kernel_launch_specialized<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[0.0; 8]));
+    let specializations = SpecializationCache::new(1);
+
+    let launch = |shape: [usize; 2], strides: [usize; 2]| {
+        let output = client.empty(2 * core::mem::size_of::<f32>());
+
+        kernel_layout::launch_specialized::<f32, R>(
+            &client,
+            &specializations,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::default(),
+            unsafe { TensorArg::from_raw_parts(&input, &strides, &shape, 1) },
+            unsafe { ArrayArg::from_raw_parts(&output, 2, 1) },
+        );
+
+        let actual = client.read(output.binding());
+        f32::from_bytes(&actual).to_vec()
+    };
+
+    assert_eq!(launch([2, 4], [4, 1]), [4.0, 4.0]);
+    // The second shape evicts the variant of the first one, which is compiled again.
+    assert_eq!(launch([4, 2], [2, 1]), [2.0, 2.0]);
+    assert_eq!(launch([2, 4], [4, 1]), [4.0, 4.0]);
+    assert_eq!(specializations.len(), 1);
+}
+
+pub fn test_kernel_struct_arg<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0]));
+    let bias = client.create(f32::as_bytes(&[1.0; 4]));
+
+    let launch = |negate: bool| {
+        let output = client.empty(4 * core::mem::size_of::<f32>());
+
+        kernel_struct_arg::launch::<f32, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(4, 1, 1),
+            ScaleArgsLaunch::new(
+                unsafe { TensorArg::from_raw_parts(&input, &[1], &[4], 1) },
+                unsafe { ArrayArg::from_raw_parts(&bias, 4, 1) },
+                ScalarArg::new(2.0),
+                negate,
+            ),
+            unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+        );
+
+        let actual = client.read(output.binding());
+        f32::from_bytes(&actual).to_vec()
+    };
+
+    assert_eq!(launch(false), [1.0, 3.0, 5.0, 7.0]);
+    // The comptime field selects another kernel variant.
+    assert_eq!(launch(true), [-1.0, -3.0, -5.0, -7.0]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_launch {
@@ -64,5 +387,93 @@ macro_rules! testgen_launch {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::launch::test_kernel_without_generics::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_launch_create_async() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_create_async::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_read_write_range() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_read_write_range::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_fill() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_fill::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_grow() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_grow::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_stream() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_stream::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_checked() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_checked::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_scope() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_scope::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_scope_out_of_bounds() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_scope_out_of_bounds::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_launch_specialized() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_specialized::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_launch_struct_arg() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_struct_arg::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_launch_checked_elem_mismatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_checked_elem_mismatch::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_rank_mismatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_checked_rank_mismatch::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_strides_mismatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_launch_checked_strides_mismatch::<
+                TestRuntime,
+            >(client);
+        }
     };
 }
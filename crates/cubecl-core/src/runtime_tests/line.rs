@@ -0,0 +1,98 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_line_wide(input: &Array<Line<f32>>, output: &mut Array<Line<f32>>) {
+    if UNIT_POS < output.len() {
+        let mut line = input[UNIT_POS] + input[UNIT_POS];
+        line[5] = input[UNIT_POS][1];
+        output[UNIT_POS] = line;
+    }
+}
+
+pub fn test_line_wide<R: Runtime>(client: ComputeClient<R::Server, R::Channel>, line_size: u8) {
+    if !R::supported_line_sizes().contains(&line_size) {
+        return;
+    }
+
+    let num_lines = 2;
+    let len = num_lines * line_size as usize;
+    let input: Vec<f32> = (0..len).map(|i| i as f32).collect();
+    let expected: Vec<f32> = (0..len)
+        .map(|i| match i % line_size as usize {
+            5 => (i - 4) as f32,
+            _ => 2.0 * i as f32,
+        })
+        .collect();
+
+    let input_handle = client.create(f32::as_bytes(&input));
+    let output_handle = client.empty(len * core::mem::size_of::<f32>());
+
+    kernel_line_wide::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(num_lines as u32, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, len, line_size) },
+        unsafe { ArrayArg::from_raw_parts(&output_handle, len, line_size) },
+    );
+
+    let actual = client.read(output_handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, expected.as_slice());
+}
+
+#[cube(launch)]
+pub fn kernel_line_swizzle(input: &Array<Line<f32>>, output: &mut Array<Line<f32>>) {
+    if UNIT_POS == 0 {
+        let line = input[0];
+        let mut reversed = line.swizzle([3, 2, 1, 0]);
+        reversed.set_swizzle([0, 2], line.swizzle([1, 1]));
+        output[0] = reversed;
+    }
+}
+
+pub fn test_line_swizzle<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input_handle = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0]));
+    let output_handle = client.empty(4 * core::mem::size_of::<f32>());
+
+    kernel_line_swizzle::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&input_handle, 4, 4) },
+        unsafe { ArrayArg::from_raw_parts(&output_handle, 4, 4) },
+    );
+
+    let actual = client.read(output_handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 2.0, 1.0, 0.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_line {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_line_size_8() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_wide::<TestRuntime>(client, 8);
+        }
+
+        #[test]
+        fn test_line_size_16() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_wide::<TestRuntime>(client, 16);
+        }
+
+        #[test]
+        fn test_line_swizzle() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::line::test_line_swizzle::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,114 @@
+use crate as cubecl;
+
+use crate::Feature;
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_matrix(
+    matrix: &Array<Line<f32>>,
+    vector: &Array<Line<f32>>,
+    product: &mut Array<Line<f32>>,
+    transformed: &mut Array<Line<f32>>,
+    determinant: &mut Array<f32>,
+) {
+    if UNIT_POS == 0 {
+        let m = matrix[0];
+        product[0] = m.matrix_mul(m.transpose());
+        transformed[0] = m.matrix_mul(vector[0]);
+        determinant[0] = m.determinant();
+    }
+}
+
+pub fn test_matrix<R: Runtime>(client: ComputeClient<R::Server, R::Channel>, n: usize) {
+    if !client
+        .properties()
+        .feature_enabled(Feature::Matrix(n as u8))
+    {
+        return;
+    }
+
+    // Matrices stored column-major, whose determinants aren't just the product of a diagonal.
+    let (matrix, expected_determinant) = match n {
+        2 => (vec![1.0, 3.0, 2.0, 4.0], -2.0),
+        3 => (vec![2.0, 0.0, 1.0, 1.0, -1.0, 2.0, 3.0, 4.0, 0.0], -9.0),
+        _ => (
+            vec![
+                1.0, 3.0, 0.0, 2.0, 2.0, 1.0, 1.0, 0.0, 0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 2.0, 1.0,
+            ],
+            -14.0,
+        ),
+    };
+    let vector: Vec<f32> = (1..=n).map(|i| i as f32).collect();
+    let transposed: Vec<f32> = (0..n)
+        .flat_map(|column| (0..n).map(move |row| (row, column)))
+        .map(|(row, column)| matrix[row * n + column])
+        .collect();
+
+    let matrix_handle = client.create(f32::as_bytes(&matrix));
+    let vector_handle = client.create(f32::as_bytes(&vector));
+    let product_handle = client.empty(n * n * core::mem::size_of::<f32>());
+    let transformed_handle = client.empty(n * core::mem::size_of::<f32>());
+    let determinant_handle = client.empty(core::mem::size_of::<f32>());
+
+    kernel_matrix::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts(&matrix_handle, n * n, (n * n) as u8) },
+        unsafe { ArrayArg::from_raw_parts(&vector_handle, n, n as u8) },
+        unsafe { ArrayArg::from_raw_parts(&product_handle, n * n, (n * n) as u8) },
+        unsafe { ArrayArg::from_raw_parts(&transformed_handle, n, n as u8) },
+        unsafe { ArrayArg::from_raw_parts(&determinant_handle, 1, 1) },
+    );
+
+    let product = client.read(product_handle.binding());
+    let transformed = client.read(transformed_handle.binding());
+    let determinant = client.read(determinant_handle.binding());
+
+    assert_eq!(
+        f32::from_bytes(&product),
+        matrix_mul(&matrix, &transposed, n)
+    );
+    assert_eq!(
+        f32::from_bytes(&transformed),
+        matrix_mul(&matrix, &vector, n)
+    );
+    let determinant = f32::from_bytes(&determinant)[0];
+    assert!(
+        (determinant - expected_determinant).abs() < 1e-4,
+        "Expected a determinant of {expected_determinant}, got {determinant}"
+    );
+}
+
+fn matrix_mul(lhs: &[f32], rhs: &[f32], n: usize) -> Vec<f32> {
+    (0..rhs.len() / n)
+        .flat_map(|column| (0..n).map(move |row| (row, column)))
+        .map(|(row, column)| (0..n).map(|k| lhs[k * n + row] * rhs[column * n + k]).sum())
+        .collect()
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_matrix {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_matrix_2x2() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::matrix::test_matrix::<TestRuntime>(client, 2);
+        }
+
+        #[test]
+        fn test_matrix_3x3() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::matrix::test_matrix::<TestRuntime>(client, 3);
+        }
+
+        #[test]
+        fn test_matrix_4x4() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::matrix::test_matrix::<TestRuntime>(client, 4);
+        }
+    };
+}
@@ -1,14 +1,26 @@
 pub mod assign;
 pub mod binary;
+pub mod bitwise;
 pub mod branch;
+pub mod cast;
 pub mod cmma;
+pub mod conformance;
 pub mod const_match;
 pub mod constants;
+pub mod device_function;
 pub mod different_rank;
+pub mod division;
+pub mod dispatch;
+pub mod golden;
 pub mod launch;
+pub mod line;
+pub mod matrix;
 pub mod metadata;
+pub mod race_detection;
 pub mod sequence;
+pub mod shared_memory;
 pub mod slice;
+pub mod struct_array;
 pub mod subcube;
 pub mod topology;
 pub mod unary;
@@ -23,7 +35,9 @@ macro_rules! testgen_all {
         cubecl_core::testgen_launch!();
         cubecl_core::testgen_cmma!();
         cubecl_core::testgen_slice!();
+        cubecl_core::testgen_shared_memory!();
         cubecl_core::testgen_assign!();
+        cubecl_core::testgen_cast!();
         cubecl_core::testgen_branch!();
         cubecl_core::testgen_constants!();
         cubecl_core::testgen_topology!();
@@ -31,7 +45,17 @@ macro_rules! testgen_all {
         cubecl_core::testgen_sequence!();
         cubecl_core::testgen_unary!();
         cubecl_core::testgen_binary!();
+        cubecl_core::testgen_bitwise!();
         cubecl_core::testgen_different_rank!();
+        cubecl_core::testgen_dispatch!();
+        cubecl_core::testgen_device_function!();
+        cubecl_core::testgen_division!();
         cubecl_core::testgen_const_match!();
+        cubecl_core::testgen_struct_array!();
+        cubecl_core::testgen_line!();
+        cubecl_core::testgen_matrix!();
+        cubecl_core::testgen_race_detection!();
+        cubecl_core::testgen_golden!();
+        cubecl_core::testgen_conformance!();
     };
 }
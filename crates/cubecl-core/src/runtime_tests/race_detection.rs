@@ -0,0 +1,74 @@
+use crate as cubecl;
+use cubecl::prelude::*;
+
+use crate::compute::RaceMemory;
+
+#[cube(launch)]
+pub fn kernel_write_same_value(output: &mut Array<f32>) {
+    output[0] = f32::cast_from(UNIT_POS);
+}
+
+#[cube(launch)]
+pub fn kernel_exchange_shared(output: &mut Array<f32>) {
+    let mut shared = SharedMemory::<f32>::new(2);
+    shared[UNIT_POS] = f32::cast_from(UNIT_POS);
+    sync_units();
+    output[UNIT_POS] = shared[1 - UNIT_POS];
+}
+
+pub fn test_race_detection<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 0.0]));
+
+    let races = kernel_write_same_value::launch_race_detection::<R>(
+        &client,
+        64,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(2, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 2, 1) },
+    );
+
+    assert_eq!(races.len(), 1);
+    assert_eq!(races[0].memory, RaceMemory::Output(0));
+    assert_eq!(races[0].index, 0);
+}
+
+pub fn test_race_detection_synchronized<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[0.0, 0.0]));
+
+    let races = kernel_exchange_shared::launch_race_detection::<R>(
+        &client,
+        64,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(2, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&handle, 2, 1) },
+    );
+
+    assert!(races.is_empty());
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 0.0]);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_race_detection {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_race_detection() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::race_detection::test_race_detection::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_race_detection_synchronized() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::race_detection::test_race_detection_synchronized::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,127 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_shared_memory_size(output: &mut Array<f32>, #[comptime] size: u32) {
+    let mut shared = SharedMemory::<f32>::new(size);
+    let last = shared.len() - 1;
+
+    if UNIT_POS == 0 {
+        shared[last] = 5.0;
+    }
+    sync_units();
+
+    if UNIT_POS == 0 {
+        output[0] = shared[last];
+    }
+}
+
+#[cube(launch, dynamic_shared_memory)]
+pub fn kernel_dynamic_shared_memory(input: &Array<f32>, output: &mut Array<f32>) {
+    let mut shared = SharedMemory::<f32>::new_dynamic();
+
+    shared[UNIT_POS] = input[UNIT_POS];
+    sync_units();
+
+    output[UNIT_POS] = shared[UNIT_POS ^ 1];
+}
+
+/// Kernels must launch with as much shared memory as the device reports, without opting in to a
+/// larger limit.
+pub fn test_shared_memory_max_size<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let max_size = client
+        .properties()
+        .hardware_properties()
+        .max_shared_memory_size;
+    let size = (max_size / core::mem::size_of::<f32>()) as u32;
+    let output = client.empty(core::mem::size_of::<f32>());
+
+    kernel_shared_memory_size::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(1, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&output, 1, 1) },
+        size,
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[5.0]);
+}
+
+pub fn test_dynamic_shared_memory<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    kernel_dynamic_shared_memory::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        4 * core::mem::size_of::<f32>() as u32,
+        unsafe { ArrayArg::from_raw_parts(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[2.0, 1.0, 4.0, 3.0]);
+}
+
+pub fn test_dynamic_shared_memory_too_small<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    let result = kernel_dynamic_shared_memory::launch_checked::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        2,
+        unsafe { ArrayArg::from_raw_parts_typed::<f32>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts_typed::<f32>(&output, 4, 1) },
+    );
+
+    assert_eq!(
+        result,
+        Err(LaunchError::DynamicSharedMemory {
+            bytes: 2,
+            item_size: 4,
+        })
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_shared_memory {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_shared_memory_max_size() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_shared_memory_max_size::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_dynamic_shared_memory() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_dynamic_shared_memory::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_dynamic_shared_memory_too_small() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::shared_memory::test_dynamic_shared_memory_too_small::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
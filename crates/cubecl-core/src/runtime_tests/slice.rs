@@ -48,6 +48,51 @@ pub fn slice_mut_len(output: &mut Array<u32>) {
     }
 }
 
+#[cube(launch)]
+pub fn slice_copy_bulk_to_shared(input: &Array<f32>, output: &mut Array<f32>) {
+    let mut shared = SharedMemory::<f32>::new(4);
+    if UNIT_POS == 0 {
+        copy_bulk(input.slice(1, 5), 0, shared.slice_mut(0, 4), 0, 4u32);
+    }
+    sync_units();
+
+    if UNIT_POS == 0 {
+        for i in 0..4u32 {
+            output[i] = shared[i];
+        }
+    }
+}
+
+#[cube(launch)]
+pub fn slice_copy_bulk_async_double_buffered(input: &Array<f32>, output: &mut Array<f32>) {
+    let mut shared = SharedMemory::<f32>::new(8);
+    copy_bulk_async(
+        input.slice(0, 4),
+        UNIT_POS,
+        shared.slice_mut(0, 4),
+        UNIT_POS,
+        1u32,
+    );
+    copy_async_commit();
+    copy_bulk_async(
+        input.slice(4, 8),
+        UNIT_POS,
+        shared.slice_mut(4, 8),
+        UNIT_POS,
+        1u32,
+    );
+    copy_async_commit();
+
+    // Only the first buffer is ready, the second one may still be in flight.
+    copy_async_wait(1u32);
+    sync_units();
+    output[UNIT_POS] = shared[3 - UNIT_POS];
+
+    copy_async_wait(0u32);
+    sync_units();
+    output[UNIT_POS + 4] = shared[7 - UNIT_POS];
+}
+
 pub fn test_slice_select<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0, 4.0]));
     let output = client.empty(core::mem::size_of::<f32>());
@@ -146,6 +191,48 @@ pub fn test_slice_mut_len<R: Runtime>(client: ComputeClient<R::Server, R::Channe
     assert_eq!(actual[0], 2);
 }
 
+pub fn test_slice_copy_bulk_to_shared<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(core::mem::size_of::<f32>() * 4);
+
+    unsafe {
+        slice_copy_bulk_to_shared::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(1, 1, 1),
+            ArrayArg::from_raw_parts(&input, 5, 1),
+            ArrayArg::from_raw_parts(&output, 4, 1),
+        )
+    };
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[1.0, 2.0, 3.0, 4.0]);
+}
+
+pub fn test_slice_copy_bulk_async_double_buffered<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]));
+    let output = client.empty(core::mem::size_of::<f32>() * 8);
+
+    unsafe {
+        slice_copy_bulk_async_double_buffered::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(4, 1, 1),
+            ArrayArg::from_raw_parts(&input, 8, 1),
+            ArrayArg::from_raw_parts(&output, 8, 1),
+        )
+    };
+
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, &[3.0, 2.0, 1.0, 0.0, 7.0, 6.0, 5.0, 4.0]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_slice {
@@ -181,5 +268,21 @@ macro_rules! testgen_slice {
             let client = TestRuntime::client(&Default::default());
             cubecl_core::runtime_tests::slice::test_slice_mut_len::<TestRuntime>(client);
         }
+
+        #[test]
+        fn test_slice_copy_bulk_to_shared() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::slice::test_slice_copy_bulk_to_shared::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_slice_copy_bulk_async_double_buffered() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::slice::test_slice_copy_bulk_async_double_buffered::<
+                TestRuntime,
+            >(client);
+        }
     };
 }
@@ -0,0 +1,75 @@
+use crate as cubecl;
+
+use cubecl::prelude::*;
+
+#[derive(CubeType, Clone, Copy)]
+#[expand(buffer)]
+pub struct Particle {
+    #[expand(line_size = 3)]
+    pos: Line<f32>,
+    #[expand(line_size = 3)]
+    vel: Line<f32>,
+    id: u32,
+}
+
+#[cube(launch)]
+pub fn kernel_struct_array_step(particles: &mut StructArray<Particle>) {
+    if UNIT_POS < particles.len() {
+        let mut particle = particles.read(UNIT_POS);
+        particle.pos += particle.vel;
+        particle.id += 1;
+        particles.write(UNIT_POS, particle);
+    }
+}
+
+pub fn test_struct_array<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // Each particle is 8 words: `pos`, padding, `vel` and `id`.
+    let data = [
+        1.0f32,
+        2.0,
+        3.0,
+        0.0,
+        0.5,
+        0.5,
+        0.5,
+        f32::from_bits(7), //
+        4.0,
+        5.0,
+        6.0,
+        0.0,
+        1.0,
+        -1.0,
+        2.0,
+        f32::from_bits(9),
+    ];
+    let handle = client.create(f32::as_bytes(&data));
+
+    kernel_struct_array_step::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(2, 1, 1),
+        unsafe { StructArrayArg::from_raw_parts::<Particle>(&handle, 2) },
+    );
+
+    let actual = client.read(handle.binding());
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(&actual[0..3], &[1.5, 2.5, 3.5]);
+    assert_eq!(actual[7].to_bits(), 8);
+    assert_eq!(&actual[8..11], &[5.0, 4.0, 8.0]);
+    assert_eq!(actual[15].to_bits(), 10);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_struct_array {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_struct_array() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::struct_array::test_struct_array::<TestRuntime>(client);
+        }
+    };
+}
@@ -34,6 +34,41 @@ pub fn test_kernel_topology_absolute_pos<R: Runtime>(client: ComputeClient<R::Se
     assert_eq!(actual, &expect);
 }
 
+#[cube(launch)]
+pub fn kernel_grid_stride(output: &mut Array<u32>) {
+    for i in grid_stride_range(output.len()) {
+        output[i] += i;
+    }
+}
+
+pub fn test_kernel_topology_grid_stride<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let length = 10_000;
+    let cube_dim = CubeDim::new(64, 1, 1);
+    let properties = client.properties().hardware_properties().clone();
+
+    // Fewer units than positions, over one or many axis, must still cover each of them once.
+    for cube_count in [
+        cubecl::calculate_cube_count_persistent(length, cube_dim, &properties),
+        CubeCount::Static(3, 2, 1),
+        CubeCount::Static(1, 1, 1),
+    ] {
+        let handle = client.create(u32::as_bytes(&vec![0; length]));
+
+        unsafe {
+            kernel_grid_stride::launch::<R>(
+                &client,
+                cube_count.clone(),
+                cube_dim,
+                ArrayArg::from_raw_parts(&handle, length, 1),
+            )
+        };
+
+        let actual = client.read(handle.binding());
+        let expect: Vec<u32> = (0..length as u32).collect();
+        assert_eq!(u32::from_bytes(&actual), &expect, "{cube_count:?}");
+    }
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_topology {
@@ -47,5 +82,13 @@ macro_rules! testgen_topology {
                 client,
             );
         }
+
+        #[test]
+        fn test_topology_grid_stride() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::topology::test_kernel_topology_grid_stride::<TestRuntime>(
+                client,
+            );
+        }
     };
 }
@@ -30,8 +30,83 @@ expected: {:?}",
     }
 }
 
+/// Like [assert_equals_approx], with an epsilon relative to the magnitude of the expected values.
+pub(crate) fn assert_equals_approx_relative<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: Handle,
+    expected: &[f32],
+    epsilon: f32,
+) {
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            (a - e).abs() <= epsilon * e.abs(),
+            "Values differ more than the relative epsilon: actual={}, expected={}, epsilon={}
+index: {}
+actual: {:?}
+expected: {:?}",
+            a,
+            e,
+            epsilon,
+            i,
+            actual,
+            expected
+        );
+    }
+}
+
 macro_rules! test_unary_impl {
     (
+        $test_name:ident,
+        $float_type:ident,
+        $unary_func:expr,
+        [$({
+            input_vectorization: $input_vectorization:expr,
+            out_vectorization: $out_vectorization:expr,
+            input: $input:expr,
+            expected: $expected:expr
+        }),*]) => {
+        test_unary_impl!(
+            @assert assert_equals_approx(0.001),
+            $test_name,
+            $float_type,
+            $unary_func,
+            [$({
+                input_vectorization: $input_vectorization,
+                out_vectorization: $out_vectorization,
+                input: $input,
+                expected: $expected
+            }),*]
+        );
+    };
+    (
+        relative_epsilon: $epsilon:expr,
+        $test_name:ident,
+        $float_type:ident,
+        $unary_func:expr,
+        [$({
+            input_vectorization: $input_vectorization:expr,
+            out_vectorization: $out_vectorization:expr,
+            input: $input:expr,
+            expected: $expected:expr
+        }),*]) => {
+        test_unary_impl!(
+            @assert assert_equals_approx_relative($epsilon),
+            $test_name,
+            $float_type,
+            $unary_func,
+            [$({
+                input_vectorization: $input_vectorization,
+                out_vectorization: $out_vectorization,
+                input: $input,
+                expected: $expected
+            }),*]
+        );
+    };
+    (
+        @assert $assert:ident($epsilon:expr),
         $test_name:ident,
         $float_type:ident,
         $unary_func:expr,
@@ -65,7 +140,7 @@ macro_rules! test_unary_impl {
                     )
                 };
 
-                assert_equals_approx::<R>(&client, output_handle, &$expected, 0.001);
+                $assert::<R>(&client, output_handle, &$expected, $epsilon);
             }
             )*
         }
@@ -142,6 +217,91 @@ test_unary_impl!(
     ]
 );
 
+// The expected values of the special functions are given by libm.
+
+test_unary_impl!(
+    relative_epsilon: 1e-4,
+    test_erfc,
+    F,
+    F::erfc,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: [-2., -0.5, 0., 0.5],
+            expected: [1.9953223, 1.5205, 1., 0.47950012]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: [1., 2., 3., 4.],
+            expected: [0.1572992, 4.677735e-3, 2.2090497e-5, 1.5417258e-8]
+        }
+    ]
+);
+
+test_unary_impl!(
+    relative_epsilon: 1e-4,
+    test_expm1,
+    F,
+    F::expm1,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: [-1., -1e-3, -1e-6, 0.],
+            expected: [-0.63212055, -9.995002e-4, -9.999995e-7, 0.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: [1e-6, 1e-3, 0.5, 2.],
+            expected: [1.0000005e-6, 1.0005002e-3, 0.6487213, 6.389056]
+        }
+    ]
+);
+
+test_unary_impl!(
+    relative_epsilon: 1e-4,
+    test_tgamma,
+    F,
+    F::tgamma,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: [-2.5, -0.5, 0.5, 1.],
+            expected: [-0.9453087, -3.5449077, 1.7724539, 1.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: [2., 3.5, 5., 10.],
+            expected: [1., 3.323351, 24., 362880.]
+        }
+    ]
+);
+
+test_unary_impl!(
+    test_lgamma,
+    F,
+    F::lgamma,
+    [
+        {
+            input_vectorization: 1,
+            out_vectorization: 1,
+            input: [-2.5, -0.5, 0.5, 1.],
+            expected: [-0.05624372, 1.2655121, 0.5723649, 0.]
+        },
+        {
+            input_vectorization: 4,
+            out_vectorization: 4,
+            input: [2., 3.5, 5., 10.],
+            expected: [0., 1.2009736, 3.1780539, 12.801827]
+        }
+    ]
+);
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_unary {
@@ -161,6 +321,10 @@ macro_rules! testgen_unary {
 
             add_test!(test_normalize);
             add_test!(test_magnitude);
+            add_test!(test_erfc);
+            add_test!(test_expm1);
+            add_test!(test_tgamma);
+            add_test!(test_lgamma);
         }
     };
 }
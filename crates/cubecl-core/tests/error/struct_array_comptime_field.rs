@@ -0,0 +1,12 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[derive(CubeType, Clone, Copy)]
+#[expand(buffer)]
+pub struct Particle {
+    mass: f32,
+    #[expand(comptime)]
+    kind: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn while_let(value: u32) {
+    while let 1 = value {
+        break;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,94 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn read_neighbor(value: f32) {
+    let mut shared = SharedMemory::<f32>::new(32);
+    shared[UNIT_POS] = value;
+    let _ = shared[(UNIT_POS + 1) % 32];
+}
+
+#[cube]
+pub fn read_neighbor_synced(value: f32) {
+    let mut shared = SharedMemory::<f32>::new(32);
+    shared[UNIT_POS] = value;
+    sync_units();
+    let _ = shared[(UNIT_POS + 1) % 32];
+}
+
+#[cube]
+pub fn read_own(value: f32) {
+    let mut shared = SharedMemory::<f32>::new(32);
+    shared[UNIT_POS] = value;
+    shared[UNIT_POS] += value;
+}
+
+#[cube]
+pub fn write_in_branch(value: f32) {
+    let mut shared = SharedMemory::<f32>::new(32);
+    if UNIT_POS == 0 {
+        shared[0] = value;
+    }
+    let _ = shared[0];
+}
+
+#[cube]
+pub fn write_across_iterations(value: f32) {
+    let mut shared = SharedMemory::<f32>::new(32);
+    for _ in 0..4 {
+        let _ = shared[(UNIT_POS + 1) % 32];
+        sync_units();
+        shared[UNIT_POS] = value;
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Item, Operation, Operator};
+
+    fn missing_barriers(expand: impl FnOnce(&mut CubeContext, ExpandElementTyped<f32>)) -> usize {
+        let mut context = CubeContext::default();
+        let value = context.create_local_binding(Item::new(Elem::Float(FloatKind::F32)));
+        expand(&mut context, value.into());
+
+        context.into_scope().missing_barriers().len()
+    }
+
+    #[test]
+    fn cube_missing_barrier_test() {
+        let mut context = CubeContext::default();
+        let value = context.create_local_binding(Item::new(Elem::Float(FloatKind::F32)));
+        read_neighbor::expand(&mut context, value.into());
+        let missing = context.into_scope().missing_barriers();
+
+        assert_eq!(missing.len(), 1);
+        assert!(matches!(
+            missing[0].write,
+            Operation::Operator(Operator::IndexAssign(_))
+        ));
+        assert!(matches!(
+            missing[0].read,
+            Operation::Operator(Operator::Index(_))
+        ));
+    }
+
+    #[test]
+    fn cube_barrier_test() {
+        assert_eq!(missing_barriers(read_neighbor_synced::expand), 0);
+    }
+
+    #[test]
+    fn cube_same_index_test() {
+        assert_eq!(missing_barriers(read_own::expand), 0);
+    }
+
+    #[test]
+    fn cube_missing_barrier_branch_test() {
+        assert_eq!(missing_barriers(write_in_branch::expand), 1);
+    }
+
+    #[test]
+    fn cube_missing_barrier_loop_test() {
+        assert_eq!(missing_barriers(write_across_iterations::expand), 1);
+    }
+}
@@ -0,0 +1,79 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube(launch)]
+pub fn const_generic_kernel<F: Float, const TILE: u32>(output: &mut Array<F>) {
+    let mut shared = SharedMemory::<F>::new(TILE);
+    for i in 0..TILE {
+        shared[i] = F::cast_from(i);
+    }
+    if UNIT_POS < TILE {
+        output[UNIT_POS] = shared[UNIT_POS] * F::cast_from(TILE);
+    }
+}
+
+#[cube]
+pub fn const_generic_unroll<const N: u32>(lhs: u32) {
+    let mut sum = lhs;
+    #[unroll]
+    for _ in 0..N {
+        sum *= lhs;
+    }
+    let _ = sum;
+}
+
+#[cube]
+pub fn comptime_unroll(lhs: u32, #[comptime] n: u32) {
+    let mut sum = lhs;
+    #[unroll]
+    for _ in 0..n {
+        sum *= lhs;
+    }
+    let _ = sum;
+}
+
+#[cube(inline = never)]
+pub fn callee_const_generic<const N: u32>(x: u32) -> u32 {
+    x * N
+}
+
+#[cube]
+pub fn caller_const_generics(x: u32) {
+    let _ = callee_const_generic::<2>(x) + callee_const_generic::<4>(x);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, Item};
+
+    #[test]
+    fn cube_const_generic_is_comptime_test() {
+        let mut context = CubeContext::default();
+        let lhs = context.create_local_binding(Item::new(Elem::UInt));
+        const_generic_unroll::expand::<3>(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        let mut context = CubeContext::default();
+        let lhs = context.create_local_binding(Item::new(Elem::UInt));
+        comptime_unroll::expand(&mut context, lhs.into(), 3);
+        let expected = context.into_scope();
+
+        assert_eq!(
+            format!("{:?}", scope.operations),
+            format!("{:?}", expected.operations)
+        );
+    }
+
+    #[test]
+    fn cube_const_generic_device_functions_test() {
+        let mut context = CubeContext {
+            device_functions: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(Elem::UInt));
+
+        caller_const_generics::expand(&mut context, x.into());
+
+        assert_eq!(context.functions.borrow().len(), 2);
+    }
+}
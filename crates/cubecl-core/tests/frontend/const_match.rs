@@ -0,0 +1,127 @@
+// Some bindings are uppercase like constants, to test that they are bindings anyway.
+#![allow(non_snake_case)]
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum Activation {
+    Identity,
+    Double,
+    Scale(u32),
+}
+
+#[cube]
+pub fn activation<F: Float>(x: F, #[comptime] act: Activation) -> F {
+    match act {
+        Activation::Identity => x,
+        Activation::Double => x + x,
+        Activation::Scale(factor) if factor <= 1 => x,
+        Activation::Scale(factor) => x * F::cast_from(factor),
+    }
+}
+
+#[cube]
+pub fn activation_shadowing<F: Float>(factor: F, #[comptime] act: Activation) -> F {
+    match act {
+        Activation::Scale(factor) => F::cast_from(factor),
+        _ => factor,
+    }
+}
+
+#[cube]
+pub fn activation_uppercase_binding<F: Float>(N: F, #[comptime] act: Activation) -> F {
+    match act {
+        Activation::Scale(N) => F::cast_from(N),
+        _ => N,
+    }
+}
+
+const LIMIT: u32 = 4;
+
+#[cube]
+pub fn const_path_pattern<F: Float>(x: F, #[comptime] value: u32) -> F {
+    match value {
+        self::LIMIT => x + x,
+        _ => x,
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Item, Scope};
+
+    type ElemType = f32;
+
+    fn expand(act: Activation) -> Scope {
+        let mut context = CubeContext::default();
+        let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        activation::expand::<ElemType>(&mut context, x.into(), act);
+        context.into_scope()
+    }
+
+    #[test]
+    fn cube_const_match_specializes_each_variant() {
+        let identity = expand(Activation::Identity);
+        let double = expand(Activation::Double);
+        let scale = expand(Activation::Scale(3));
+
+        assert!(identity.operations.is_empty());
+        assert_eq!(format!("{:?}", double.operations).matches("Add").count(), 1);
+        assert_eq!(format!("{:?}", scale.operations).matches("Mul").count(), 1);
+    }
+
+    #[test]
+    fn cube_const_match_guard() {
+        let scale_one = expand(Activation::Scale(1));
+
+        assert!(scale_one.operations.is_empty());
+    }
+
+    #[test]
+    fn cube_const_match_binding_shadows_runtime_variable() {
+        let mut context = CubeContext::default();
+        let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        activation_shadowing::expand::<ElemType>(&mut context, x.into(), Activation::Scale(2));
+        let scope = context.into_scope();
+
+        // The cast reads the comptime binding, not the runtime parameter with the same name.
+        assert_eq!(scope.operations.len(), 1);
+        assert!(format!("{:?}", scope.operations).contains("input: ConstantScalar(UInt(2))"));
+    }
+
+    #[test]
+    fn cube_const_match_uppercase_binding_shadows_runtime_variable() {
+        let mut context = CubeContext::default();
+        let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        activation_uppercase_binding::expand::<ElemType>(
+            &mut context,
+            x.into(),
+            Activation::Scale(2),
+        );
+        let scope = context.into_scope();
+
+        assert_eq!(scope.operations.len(), 1);
+        assert!(format!("{:?}", scope.operations).contains("input: ConstantScalar(UInt(2))"));
+    }
+
+    #[test]
+    fn cube_const_match_path_is_a_constant() {
+        let expand = |value| {
+            let mut context = CubeContext::default();
+            let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+            const_path_pattern::expand::<ElemType>(&mut context, x.into(), value);
+            context.into_scope()
+        };
+
+        assert_eq!(
+            format!("{:?}", expand(4).operations).matches("Add").count(),
+            1
+        );
+        assert!(expand(3).operations.is_empty());
+    }
+}
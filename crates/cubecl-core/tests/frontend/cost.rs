@@ -0,0 +1,92 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn elementwise_add(lhs: &Array<f32>, rhs: &Array<f32>, output: &mut Array<f32>) {
+    output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] + rhs[ABSOLUTE_POS];
+}
+
+#[cube]
+pub fn unrolled_sum(lhs: &Array<f32>, rhs: &Array<f32>, output: &mut Array<f32>) {
+    let mut sum = 0.0;
+    for i in 0..4 {
+        sum += lhs[i] * rhs[i];
+    }
+    output[ABSOLUTE_POS] = sum;
+}
+
+#[cube]
+pub fn branch_on_position(lhs: &Array<f32>, rhs: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS == 0 {
+        output[0] = lhs[0];
+    } else {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] * rhs[ABSOLUTE_POS] + rhs[0];
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Item, KernelCost};
+
+    type Expand = fn(
+        &mut CubeContext,
+        ExpandElementTyped<Array<f32>>,
+        ExpandElementTyped<Array<f32>>,
+        ExpandElementTyped<Array<f32>>,
+    );
+
+    fn cost(expand: Expand) -> KernelCost {
+        let item = Item::new(Elem::Float(FloatKind::F32));
+        let mut builder = KernelBuilder::default();
+        let lhs = builder.input_array(item);
+        let rhs = builder.input_array(item);
+        let output = builder.output_array(item);
+        expand(&mut builder.context, lhs.into(), rhs.into(), output.into());
+
+        builder.build(KernelSettings::default()).cost()
+    }
+
+    #[test]
+    fn cube_cost_elementwise_test() {
+        let cost = cost(elementwise_add::expand);
+
+        assert_eq!(
+            cost,
+            KernelCost {
+                bytes_read: 8,
+                bytes_written: 4,
+                flops: 1,
+            }
+        );
+        assert_eq!(cost.arithmetic_intensity(), 1.0 / 12.0);
+        assert!(cost.is_memory_bound(10e12, 1e12));
+    }
+
+    #[test]
+    fn cube_cost_counts_loop_iterations_test() {
+        let cost = cost(unrolled_sum::expand);
+
+        assert_eq!(
+            cost,
+            KernelCost {
+                bytes_read: 32,
+                bytes_written: 4,
+                flops: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn cube_cost_takes_most_expensive_branch_test() {
+        let cost = cost(branch_on_position::expand);
+
+        assert_eq!(
+            cost,
+            KernelCost {
+                bytes_read: 12,
+                bytes_written: 4,
+                flops: 2,
+            }
+        );
+    }
+}
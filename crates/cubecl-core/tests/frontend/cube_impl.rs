@@ -78,3 +78,31 @@ impl<C: Numeric> ComplexType<C, f32> {
         lhs * f32::cast_from(rhs)
     }
 }
+
+mod public {
+    use super::*;
+
+    #[derive(CubeType)]
+    pub struct PublicType {
+        pub a: u32,
+    }
+
+    #[cube]
+    impl PublicType {
+        pub fn new(a: u32) -> Self {
+            PublicType { a }
+        }
+
+        pub fn double(&self) -> u32 {
+            self.a * 2
+        }
+    }
+}
+
+/// The public methods of a cube impl can be called from other modules.
+#[cube]
+#[allow(dead_code)]
+fn call_public_methods(a: u32) -> u32 {
+    let value = public::PublicType::new(a);
+    value.double()
+}
@@ -0,0 +1,52 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn two_statements(x: u32) -> u32 {
+    let y = x + 1;
+    y * 2
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, Item, Operation, SourceLocation};
+
+    #[test]
+    fn cube_debug_info_registers_statement_locations_test() {
+        let mut context = CubeContext {
+            debug_info: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(Elem::UInt));
+
+        two_statements::expand(&mut context, x.into());
+        let scope = context.into_scope();
+
+        let locations: Vec<SourceLocation> = scope
+            .operations
+            .into_iter()
+            .filter_map(|op| match op {
+                Operation::Location(location) => Some(location),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].file, file!());
+        assert_eq!(locations[0].line, 6);
+    }
+
+    #[test]
+    fn cube_debug_info_disabled_by_default_test() {
+        let mut context = CubeContext::default();
+        let x = context.create_local_binding(Item::new(Elem::UInt));
+
+        two_statements::expand(&mut context, x.into());
+        let scope = context.into_scope();
+
+        assert!(!scope
+            .operations
+            .iter()
+            .any(|op| matches!(op, Operation::Location(_))));
+    }
+}
@@ -0,0 +1,177 @@
+use cubecl_core as cubecl;
+use cubecl_core::{
+    cube,
+    frontend::{Numeric, UNIT_POS},
+};
+
+#[cube(inline = never)]
+pub fn callee_never_inlined<T: Numeric>(x: T) -> T {
+    x * T::from_int(8)
+}
+
+#[cube]
+pub fn caller_twice<T: Numeric>(x: T) {
+    let _ = callee_never_inlined::<T>(x) + callee_never_inlined::<T>(x);
+}
+
+pub mod first {
+    use super::*;
+
+    #[cube(inline = never)]
+    pub fn same_name<T: Numeric>(x: T) -> T {
+        x + T::from_int(1)
+    }
+}
+
+pub mod second {
+    use super::*;
+
+    #[cube(inline = never)]
+    pub fn same_name<T: Numeric>(x: T) -> T {
+        x * T::from_int(2)
+    }
+}
+
+#[cube]
+pub fn caller_same_names<T: Numeric>(x: T) {
+    let _ = first::same_name::<T>(x) + second::same_name::<T>(x);
+}
+
+#[cube(inline = never)]
+pub fn reads_unit_pos(x: u32) -> u32 {
+    x + UNIT_POS
+}
+
+#[cube]
+pub fn caller_unit_pos(x: u32) {
+    let _ = reads_unit_pos(x);
+}
+
+#[cube(inline = never)]
+pub fn forwards_unit_pos(x: u32) -> u32 {
+    reads_unit_pos(x) * 2
+}
+
+#[cube]
+pub fn caller_forwards_unit_pos(x: u32) {
+    let _ = forwards_unit_pos(x);
+}
+
+#[cube]
+pub fn no_call<T: Numeric>(x: T) {
+    let _ = x * T::from_int(8) + x * T::from_int(8);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        frontend::{CubeContext, CubePrimitive},
+        ir::{Item, Operation, Variable},
+    };
+
+    type ElemType = f32;
+
+    #[test]
+    fn cube_device_function_defined_once_test() {
+        let mut context = CubeContext {
+            device_functions: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        caller_twice::expand::<ElemType>(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+        let scope = context.into_scope();
+
+        let calls = scope
+            .operations
+            .iter()
+            .filter(|op| matches!(op, Operation::Call(_)))
+            .count();
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].inputs.len(), 1);
+        assert!(functions[0].output.is_some());
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn cube_device_function_same_name_in_sibling_modules_test() {
+        let mut context = CubeContext {
+            device_functions: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        caller_same_names::expand::<ElemType>(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+
+        assert_eq!(functions.len(), 2);
+        assert_ne!(functions[0].name, functions[1].name);
+        assert_ne!(
+            format!("{:?}", functions[0].body.operations),
+            format!("{:?}", functions[1].body.operations)
+        );
+    }
+
+    #[test]
+    fn cube_device_function_topology_as_implicit_argument_test() {
+        let mut context = CubeContext {
+            device_functions: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(u32::as_elem()));
+
+        caller_unit_pos::expand(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+        let scope = context.into_scope();
+
+        let call = scope.operations.iter().find_map(|op| match op {
+            Operation::Call(call) => Some(call.clone()),
+            _ => None,
+        });
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].builtins, vec![Variable::UnitPos]);
+        assert_eq!(functions[0].inputs.len(), 2);
+        assert_eq!(call.unwrap().args[1], Variable::UnitPos);
+    }
+
+    #[test]
+    fn cube_device_function_topology_forwarded_by_callers_test() {
+        let mut context = CubeContext {
+            device_functions: true,
+            ..Default::default()
+        };
+        let x = context.create_local_binding(Item::new(u32::as_elem()));
+
+        caller_forwards_unit_pos::expand(&mut context, x.into());
+        let functions = context.functions.borrow().clone();
+
+        // The inner function is defined first, while the outer one is expanded.
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].builtins, vec![Variable::UnitPos]);
+        assert_eq!(functions[1].builtins, vec![Variable::UnitPos]);
+        assert_eq!(functions[1].inputs.len(), 2);
+    }
+
+    #[test]
+    fn cube_device_function_inlined_when_unsupported_test() {
+        let mut caller_context = CubeContext::default();
+        let x = caller_context.create_local_binding(Item::new(ElemType::as_elem()));
+        caller_twice::expand::<ElemType>(&mut caller_context, x.into());
+        let functions = caller_context.functions.borrow().len();
+        let caller_scope = caller_context.into_scope();
+
+        let mut no_call_context = CubeContext::default();
+        let x = no_call_context.create_local_binding(Item::new(ElemType::as_elem()));
+        no_call::expand::<ElemType>(&mut no_call_context, x.into());
+        let no_call_scope = no_call_context.into_scope();
+
+        assert_eq!(functions, 0);
+        assert_eq!(
+            format!("{:?}", caller_scope.operations),
+            format!("{:?}", no_call_scope.operations)
+        );
+    }
+}
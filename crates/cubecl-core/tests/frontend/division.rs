@@ -0,0 +1,77 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn divisions(lhs: u32, rhs: u32) {
+    let _ = lhs / rhs;
+    let _ = lhs % rhs;
+    let _ = lhs / 2;
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Branch, DivisionPolicy, Elem, Operation, Operator};
+
+    fn guarded(division: DivisionPolicy) -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        let lhs = builder.scalar(Elem::UInt);
+        let rhs = builder.scalar(Elem::UInt);
+        divisions::expand(&mut builder.context, lhs.into(), rhs.into());
+
+        let mut kernel = builder.build(KernelSettings::default().division(division));
+        kernel.guard_division();
+        kernel
+    }
+
+    fn count(kernel: &KernelDefinition, filter: impl Fn(&Operator) -> bool) -> usize {
+        let mut count = 0;
+        kernel.body.visit_operations(&mut |operation| {
+            if matches!(operation, Operation::Operator(operator) if filter(operator)) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    fn count_selects(kernel: &KernelDefinition) -> usize {
+        let mut count = 0;
+        kernel.body.visit_operations(&mut |operation| {
+            if matches!(operation, Operation::Branch(Branch::Select(_))) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    #[test]
+    fn cube_division_native_policy_test() {
+        let kernel = guarded(DivisionPolicy::Native);
+
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::Equal(_))), 0);
+    }
+
+    #[test]
+    fn cube_division_zero_policy_test() {
+        let kernel = guarded(DivisionPolicy::Zero);
+
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::Div(_))), 2);
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::Modulo(_))), 1);
+        // The division by a constant isn't guarded.
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::Equal(_))), 2);
+        // The divisor and the result are selected, instead of scaling the result by a mask.
+        assert_eq!(count_selects(&kernel), 4);
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::Mul(_))), 0);
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::AtomicAdd(_))), 0);
+    }
+
+    #[test]
+    fn cube_division_count_test() {
+        let mut kernel = guarded(DivisionPolicy::Native);
+        kernel.count_division_by_zero();
+
+        assert_eq!(kernel.outputs.len(), 1);
+        assert_eq!(kernel.division, DivisionPolicy::Native);
+        assert_eq!(count_selects(&kernel), 4);
+        assert_eq!(count(&kernel, |op| matches!(op, Operator::AtomicAdd(_))), 2);
+    }
+}
@@ -28,6 +28,26 @@ pub fn loop_with_return<I: Int>(lhs: I) {
     }
 }
 
+#[cube]
+pub fn manual_loop_continue<I: Int>(lhs: I) {
+    loop {
+        if lhs == I::from_int(0) {
+            continue;
+        }
+        let _ = lhs % I::from_int(1);
+    }
+}
+
+#[cube]
+pub fn while_let_comptime<I: Int>(lhs: I, #[comptime] divisor: Option<i64>) {
+    while let Some(divisor) = divisor {
+        if lhs == I::from_int(0) {
+            break;
+        }
+        let _ = lhs % I::from_int(divisor);
+    }
+}
+
 mod tests {
     use super::*;
     use cubecl_core::{
@@ -80,6 +100,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cube_loop_continue_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        manual_loop_continue::expand::<ElemType>(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        assert_eq!(
+            format!("{:?}", scope.operations),
+            inline_macro_ref_loop_branch(Branch::Continue)
+        );
+    }
+
+    #[test]
+    fn cube_while_let_some_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        while_let_comptime::expand::<ElemType>(&mut context, lhs.into(), Some(1));
+        let scope = context.into_scope();
+
+        assert_eq!(
+            format!("{:?}", scope.operations),
+            inline_macro_ref_loop(false)
+        );
+    }
+
+    #[test]
+    fn cube_while_let_none_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        while_let_comptime::expand::<ElemType>(&mut context, lhs.into(), None);
+        let scope = context.into_scope();
+
+        assert_eq!(format!("{:?}", scope.operations), inline_macro_ref_break());
+    }
+
     fn inline_macro_ref_while() -> String {
         let mut context = CubeContext::default();
         let item = Item::new(ElemType::as_elem());
@@ -107,6 +169,13 @@ mod tests {
     }
 
     fn inline_macro_ref_loop(is_return: bool) -> String {
+        match is_return {
+            true => inline_macro_ref_loop_branch(Branch::Return),
+            false => inline_macro_ref_loop_branch(Branch::Break),
+        }
+    }
+
+    fn inline_macro_ref_loop_branch(branch: Branch) -> String {
         let mut context = CubeContext::default();
         let item = Item::new(ElemType::as_elem());
         let lhs = context.create_local_binding(item);
@@ -121,10 +190,7 @@ mod tests {
             loop(|scope| {
                 cpa!(scope, cond = lhs == 0);
                 cpa!(scope, if(cond).then(|scope|{
-                    match is_return {
-                        true => scope.register(Branch::Return),
-                        false => scope.register(Branch::Break)
-                    }
+                    scope.register(branch.clone())
                 }));
                 // Must not mutate `lhs` because it is used in every iteration
                 cpa!(scope, y = lhs % 1i32);
@@ -133,4 +199,21 @@ mod tests {
 
         format!("{:?}", scope.operations)
     }
+
+    fn inline_macro_ref_break() -> String {
+        let mut context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+        context.create_local_binding(item);
+
+        let mut scope = context.into_scope();
+
+        cpa!(
+            &mut scope,
+            loop(|scope| {
+                scope.register(Branch::Break);
+            })
+        );
+
+        format!("{:?}", scope.operations)
+    }
 }
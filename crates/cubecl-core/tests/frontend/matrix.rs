@@ -0,0 +1,79 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn matrix_ops(matrix: Line<f32>, vector: Line<f32>) -> f32 {
+    let transformed = matrix.transpose().matrix_mul(vector);
+    transformed[0] + matrix.determinant()
+}
+
+mod tests {
+    use std::num::NonZero;
+
+    use super::*;
+    use cubecl_core::ir::{Item, Operation, Operator};
+
+    type ElemType = f32;
+
+    fn line(context: &mut CubeContext, size: u8) -> ExpandElementTyped<Line<ElemType>> {
+        context
+            .create_local_binding(Item::vectorized(ElemType::as_elem(), NonZero::new(size)))
+            .into()
+    }
+
+    #[test]
+    fn cube_matrix_unrolled_test() {
+        let mut context = CubeContext::default();
+        let matrix = line(&mut context, 9);
+        let vector = line(&mut context, 3);
+
+        matrix_ops::expand(&mut context, matrix, vector);
+        let scope = context.into_scope();
+
+        assert!(!scope.operations.iter().any(|op| matches!(
+            op,
+            Operation::Operator(
+                Operator::MatrixMul(_) | Operator::Transpose(_) | Operator::Determinant(_)
+            )
+        )));
+        assert!(scope
+            .operations
+            .iter()
+            .any(|op| matches!(op, Operation::Operator(Operator::InitLine(_)))));
+    }
+
+    #[test]
+    fn cube_matrix_native_test() {
+        let mut context = CubeContext {
+            matrix_ops: |size| size == 2,
+            ..Default::default()
+        };
+        let matrix = line(&mut context, 4);
+        let vector = line(&mut context, 2);
+
+        matrix_ops::expand(&mut context, matrix, vector);
+        let scope = context.into_scope();
+
+        let count = |f: fn(&Operator) -> bool| {
+            scope
+                .operations
+                .iter()
+                .filter(|op| matches!(op, Operation::Operator(op) if f(op)))
+                .count()
+        };
+
+        assert_eq!(count(|op| matches!(op, Operator::MatrixMul(_))), 1);
+        assert_eq!(count(|op| matches!(op, Operator::Transpose(_))), 1);
+        assert_eq!(count(|op| matches!(op, Operator::Determinant(_))), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cube_matrix_invalid_size_fails() {
+        let mut context = CubeContext::default();
+        let matrix = line(&mut context, 8);
+        let vector = line(&mut context, 2);
+
+        matrix_ops::expand(&mut context, matrix, vector);
+    }
+}
@@ -1,11 +1,18 @@
 mod array;
 mod assign;
+mod barrier;
 mod cast_elem;
 mod cast_kind;
 mod comptime;
+mod const_generic;
+mod const_match;
 mod constants;
+mod cost;
 mod cube_impl;
 mod cube_trait;
+mod debug_info;
+mod device_function;
+mod division;
 mod enum_type;
 mod for_loop;
 mod function_call;
@@ -14,15 +21,24 @@ mod r#if;
 mod intrinsics;
 mod literal;
 mod r#loop;
+mod matrix;
 mod module_import;
 mod ops;
 mod parenthesis;
+mod pass;
+mod printer;
 mod redeclare;
+mod r#return;
 mod reuse;
 mod shared_memory;
+mod slice;
 mod r#struct;
+mod struct_array;
+mod subcube;
+mod swizzle;
 mod tensor;
 mod topology;
 mod r#trait;
 mod tuple;
+mod uniformity;
 mod vectorization;
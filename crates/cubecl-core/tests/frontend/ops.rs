@@ -86,6 +86,26 @@ pub fn erf_op<F: Float>(a: F) -> F {
     F::erf(a)
 }
 
+#[cube]
+pub fn erfc_op<F: Float>(a: F) -> F {
+    F::erfc(a)
+}
+
+#[cube]
+pub fn expm1_op<F: Float>(a: F) -> F {
+    F::expm1(a)
+}
+
+#[cube]
+pub fn tgamma_op<F: Float>(a: F) -> F {
+    F::tgamma(a)
+}
+
+#[cube]
+pub fn lgamma_op<F: Float>(a: F) -> F {
+    F::lgamma(a)
+}
+
 #[cube]
 pub fn recip_op<F: Float>(a: F) -> F {
     F::recip(a)
@@ -326,6 +346,10 @@ mod tests {
     );
     unary_test!(cube_can_sqrt, sqrt_op::expand::<f32>, "Sqrt");
     unary_test!(cube_can_erf, erf_op::expand::<f32>, "Erf");
+    unary_test!(cube_can_erfc, erfc_op::expand::<f32>, "Erfc");
+    unary_test!(cube_can_expm1, expm1_op::expand::<f32>, "Expm1");
+    unary_test!(cube_can_tgamma, tgamma_op::expand::<f32>, "Tgamma");
+    unary_test!(cube_can_lgamma, lgamma_op::expand::<f32>, "Lgamma");
     unary_test!(cube_can_recip, recip_op::expand::<f32>, "Recip");
     unary_test!(cube_can_round, round_op::expand::<f32>, "Round");
     unary_test!(cube_can_floor, floor_op::expand::<f32>, "Floor");
@@ -0,0 +1,138 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn double_sum(value: f32) {
+    let _ = value * 2.0;
+    let _ = subcube_sum(value);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        ir::{
+            BinaryOperator, ConstantScalarValue, Elem, FloatKind, KernelDefinition, Operation,
+            Operator, Pass, PassManager,
+        },
+        CompilationOptions, ExecutionMode,
+    };
+    use std::sync::Arc;
+
+    /// Replaces the multiplications by two with additions.
+    struct MulToAdd;
+
+    impl Pass for MulToAdd {
+        fn name(&self) -> &str {
+            "mul_to_add"
+        }
+
+        fn apply(&self, kernel: &mut KernelDefinition, _: &CompilationOptions, _: ExecutionMode) {
+            for operation in kernel.body.operations.iter_mut() {
+                let Operation::Operator(Operator::Mul(op)) = operation else {
+                    continue;
+                };
+                if op.rhs.as_const() == Some(ConstantScalarValue::Float(2.0, FloatKind::F32)) {
+                    *operation = Operator::Add(BinaryOperator {
+                        lhs: op.lhs,
+                        rhs: op.lhs,
+                        out: op.out,
+                    })
+                    .into();
+                }
+            }
+        }
+    }
+
+    struct Noop(&'static str);
+
+    impl Pass for Noop {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn apply(&self, _: &mut KernelDefinition, _: &CompilationOptions, _: ExecutionMode) {}
+    }
+
+    fn kernel() -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        let value = builder.scalar(Elem::Float(FloatKind::F32));
+        double_sum::expand(&mut builder.context, value.into());
+
+        builder.build(KernelSettings::default().cube_dim(CubeDim::new(32, 1, 1)))
+    }
+
+    fn count(kernel: &KernelDefinition, filter: impl Fn(&Operation) -> bool) -> usize {
+        let mut count = 0;
+        kernel.body.visit_operations(&mut |operation| {
+            if filter(operation) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    #[test]
+    fn default_passes_lower_the_kernel() {
+        let mut kernel = kernel();
+        let options = CompilationOptions { subcube: false };
+
+        kernel.apply_passes(&PassManager::default(), &options, ExecutionMode::Checked);
+
+        assert_eq!(count(&kernel, |op| matches!(op, Operation::Subcube(_))), 0);
+    }
+
+    #[test]
+    fn custom_pass_transforms_the_kernel() {
+        let mut kernel = kernel();
+        let mut passes = PassManager::default();
+        passes.insert_before("guard_division", Arc::new(MulToAdd));
+
+        kernel.apply_passes(
+            &passes,
+            &CompilationOptions::default(),
+            ExecutionMode::Checked,
+        );
+
+        let mul = |op: &Operation| matches!(op, Operation::Operator(Operator::Mul(_)));
+        let add = |op: &Operation| matches!(op, Operation::Operator(Operator::Add(_)));
+        assert_eq!(count(&kernel, mul), 0);
+        assert_eq!(count(&kernel, add), 1);
+        // The subcube operations are supported by the device.
+        assert_eq!(count(&kernel, |op| matches!(op, Operation::Subcube(_))), 1);
+    }
+
+    #[test]
+    fn passes_are_placed_relative_to_each_other() {
+        let mut passes = PassManager::default();
+        passes.insert_after("lower_subcube", Arc::new(Noop("after")));
+        passes.insert_before("lower_subcube", Arc::new(Noop("before")));
+        passes.push(Arc::new(Noop("last")));
+
+        assert_eq!(
+            passes.names().collect::<Vec<_>>(),
+            ["before", "lower_subcube", "after", "guard_division", "last"]
+        );
+
+        assert!(passes.remove("after").is_some());
+        assert!(passes.remove("after").is_none());
+        assert_eq!(
+            passes.names().collect::<Vec<_>>(),
+            ["before", "lower_subcube", "guard_division", "last"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "No pass named unknown")]
+    fn inserting_relative_to_an_unknown_pass_panics() {
+        PassManager::empty().insert_after("unknown", Arc::new(Noop("pass")));
+    }
+
+    #[test]
+    fn global_passes_are_updated() {
+        PassManager::update_global(|passes| passes.push(Arc::new(Noop("registered"))));
+
+        assert!(PassManager::global()
+            .names()
+            .any(|name| name == "registered"));
+    }
+}
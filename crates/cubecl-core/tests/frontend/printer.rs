@@ -0,0 +1,62 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn accumulate(value: f32) {
+    let mut sum = f32::new(0.0);
+    for _ in 0..4 {
+        if value > f32::new(0.0) {
+            sum += value;
+        } else {
+            sum -= value;
+        }
+    }
+    let _ = sum * f32::new(2.0);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Item, Scope};
+
+    fn scope() -> Scope {
+        let mut context = CubeContext::default();
+        let value = context.create_local_binding(Item::new(Elem::Float(FloatKind::F32)));
+        accumulate::expand(&mut context, value.into());
+
+        context.into_scope()
+    }
+
+    #[test]
+    fn display_nests_the_control_flow() {
+        let expected = "let mut local(0, 0): f32
+let mut local(1, 0): f32
+let mut local(2, 0): bool
+local(1, 0) = 0f32
+for local(0, 1) in 0i32..4i32 {
+    local(2, 0) = local(0, 0) > 0f32
+    if local(2, 0) {
+        local(1, 0) = local(1, 0) + local(0, 0)
+    } else {
+        local(1, 0) = local(1, 0) - local(0, 0)
+    }
+}
+local(1, 0) = local(1, 0) * 2f32
+";
+
+        assert_eq!(scope().to_string(), expected);
+    }
+
+    #[test]
+    fn dot_links_the_blocks_of_the_branches() {
+        let dot = scope().to_dot();
+
+        assert!(dot.starts_with("digraph scope {\n"));
+        assert!(dot.contains("n2 -> n3 [label=\"body\"];"));
+        assert!(dot.contains("n3 -> n4 [label=\"true\"];"));
+        assert!(dot.contains("n3 -> n5 [label=\"false\"];"));
+        assert!(dot.contains("n4 -> n2 [style=dashed];"));
+        assert!(dot.contains("n5 -> n2 [style=dashed];"));
+        assert!(dot.contains("n2 -> n6 [label=\"end\"];"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
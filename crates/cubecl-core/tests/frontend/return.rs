@@ -0,0 +1,210 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn early_return<I: Int>(lhs: I) -> I {
+    if lhs == I::from_int(0) {
+        return I::from_int(1);
+    }
+    lhs * I::from_int(2)
+}
+
+#[cube]
+pub fn return_in_loop<I: Int>(lhs: I) -> I {
+    for i in 0..10u32 {
+        if lhs == I::cast_from(i) {
+            return I::cast_from(i);
+        }
+    }
+    lhs
+}
+
+#[cube]
+#[allow(clippy::needless_return)]
+pub fn return_in_match(lhs: u32) -> u32 {
+    match lhs {
+        0 => return 4,
+        1 => {
+            return 5;
+        }
+        _ => {}
+    }
+    return lhs;
+}
+
+#[cube]
+#[allow(clippy::needless_return)]
+pub fn return_in_branches(lhs: u32) -> u32 {
+    if lhs > 2 {
+        return lhs;
+    } else {
+        return 2;
+    }
+}
+
+#[cube]
+pub fn call_early_return(lhs: u32) {
+    let _ = early_return::<u32>(lhs) + return_in_loop::<u32>(lhs);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::{
+        cpa,
+        ir::{Branch, Elem, Item, Operation, Variable},
+    };
+    use pretty_assertions::assert_eq;
+
+    type ElemType = i32;
+
+    #[test]
+    fn cube_early_return_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        early_return::expand::<ElemType>(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        assert_eq!(format!("{:?}", scope.operations), inline_macro_ref_early());
+    }
+
+    #[test]
+    fn cube_return_in_loop_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(ElemType::as_elem()));
+
+        return_in_loop::expand::<ElemType>(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        assert_eq!(format!("{:?}", scope.operations), inline_macro_ref_loop());
+    }
+
+    #[test]
+    fn cube_return_in_match_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(Elem::UInt));
+
+        return_in_match::expand(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        // The cases break out of the switch, so the enclosing loop must break in turn.
+        let Operation::Branch(Branch::Loop(function)) = &scope.operations[1] else {
+            panic!("The function body should be expanded in a loop");
+        };
+        assert!(matches!(
+            function.scope.operations[..2],
+            [
+                Operation::Branch(Branch::Switch(_)),
+                Operation::Branch(Branch::If(_))
+            ]
+        ));
+    }
+
+    #[test]
+    fn cube_return_in_branches_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(Elem::UInt));
+
+        return_in_branches::expand(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        // Returns outside of loops don't need a flag.
+        assert!(matches!(
+            scope.operations[..],
+            [Operation::Branch(Branch::Loop(_))]
+        ));
+    }
+
+    #[test]
+    fn cube_call_early_return_test() {
+        let mut context = CubeContext::default();
+
+        let lhs = context.create_local_binding(Item::new(Elem::UInt));
+
+        call_early_return::expand(&mut context, lhs.into());
+        let scope = context.into_scope();
+
+        let loops = scope
+            .operations
+            .iter()
+            .filter(|op| matches!(op, Operation::Branch(Branch::Loop(_))))
+            .count();
+        assert_eq!(loops, 2);
+    }
+
+    fn inline_macro_ref_early() -> String {
+        let mut context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+        let lhs = context.create_local_binding(item);
+
+        let mut scope = context.into_scope();
+        let cond = scope.create_local(Item::new(Elem::Bool));
+        let out = scope.create_local(item);
+        let y = scope.create_local(item);
+        let lhs: Variable = lhs.into();
+        let one: Variable = 1i32.into();
+
+        cpa!(
+            &mut scope,
+            loop(|scope| {
+                cpa!(scope, cond = lhs == 0);
+                cpa!(scope, if(cond).then(|scope|{
+                    cpa!(scope, out = one);
+                    scope.register(Branch::Break);
+                }));
+                cpa!(scope, y = lhs * 2i32);
+                cpa!(scope, out = y);
+                scope.register(Branch::Break);
+            })
+        );
+
+        format!("{:?}", scope.operations)
+    }
+
+    fn inline_macro_ref_loop() -> String {
+        let mut context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+        let lhs = context.create_local_binding(item);
+
+        let mut scope = context.into_scope();
+        let returned = scope.create_local(Item::new(Elem::Bool));
+        let y = scope.create_local(item);
+        let cond = scope.create_local(Item::new(Elem::Bool));
+        let out = scope.create_local(item);
+        let lhs: Variable = lhs.into();
+        let not_returned: Variable = false.into();
+        let has_returned: Variable = true.into();
+
+        cpa!(scope, returned = not_returned);
+        cpa!(
+            &mut scope,
+            loop(|scope| {
+                cpa!(
+                    scope,
+                    range(0u32, 10u32).for_each(|i, scope| {
+                        cpa!(scope, y = cast(i));
+                        cpa!(scope, cond = lhs == y);
+                        cpa!(scope, if(cond).then(|scope|{
+                            cpa!(scope, y = cast(i));
+                            cpa!(scope, out = y);
+                            cpa!(scope, returned = has_returned);
+                            scope.register(Branch::Break);
+                        }));
+                    })
+                );
+                cpa!(scope, if(returned).then(|scope|{
+                    scope.register(Branch::Break);
+                }));
+                cpa!(scope, out = lhs);
+                cpa!(scope, returned = has_returned);
+                scope.register(Branch::Break);
+            })
+        );
+
+        format!("{:?}", scope.operations)
+    }
+}
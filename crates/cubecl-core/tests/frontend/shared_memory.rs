@@ -8,6 +8,12 @@ pub fn shared_memory_read_write<T: Numeric>(#[comptime] sm_size: u32) {
     let _ = shared[0];
 }
 
+#[cube]
+pub fn shared_memory_dynamic<T: Numeric>() {
+    let mut shared = SharedMemory::<T>::new_dynamic();
+    shared[0] = T::from_int(3);
+}
+
 mod tests {
     use super::*;
     use cubecl_core::{
@@ -28,6 +34,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cube_support_dynamic_shared_memory() {
+        let mut context = CubeContext::default();
+
+        shared_memory_dynamic::expand::<ElemType>(&mut context);
+        assert_eq!(
+            format!("{:?}", context.into_scope().operations),
+            inline_macro_ref_dynamic()
+        );
+    }
+
+    fn inline_macro_ref_dynamic() -> String {
+        let context = CubeContext::default();
+        let item = Item::new(ElemType::as_elem());
+
+        let mut scope = context.into_scope();
+        let pos: Variable = 0u32.into();
+
+        let shared = scope.create_shared_dynamic(item);
+        cpa!(scope, shared[pos] = 3.0_f32);
+
+        format!("{:?}", scope.operations)
+    }
+
     fn inline_macro_ref() -> String {
         let context = CubeContext::default();
         let item = Item::new(ElemType::as_elem());
@@ -0,0 +1,126 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn slice_range(input: &Array<f32>, start: u32) {
+    let head = &input[start..8];
+    let tail = &input[start..];
+    let _ = head[0] + tail[0];
+}
+
+#[cube]
+pub fn slice_method(input: &Array<f32>, start: u32) {
+    let head = input.slice(start, 8);
+    let len = input.len();
+    let tail = input.slice(start, len);
+    let _ = head[0] + tail[0];
+}
+
+#[cube]
+pub fn shared_memory_slice_range() {
+    let mut shared = SharedMemory::<f32>::new(16);
+    let slice = &mut shared[..=3];
+    slice[1] += 2.0;
+    let rest = &shared[4..];
+    let _ = rest[0];
+}
+
+#[cube]
+pub fn shared_memory_slice_method() {
+    let mut shared = SharedMemory::<f32>::new(16);
+    let slice = shared.slice_mut(0, 4);
+    slice[1] += 2.0;
+    let rest = shared.slice(4, 16);
+    let _ = rest[0];
+}
+
+#[cube]
+pub fn shared_memory_iter() {
+    let shared = SharedMemory::<f32>::new(16);
+    let mut sum = 0.0;
+    for value in shared.iter() {
+        sum += value;
+    }
+    let _ = sum;
+}
+
+#[cube]
+pub fn slice_iter(input: &Array<f32>) {
+    let mut sum = 0.0;
+    for value in input[2..6].iter() {
+        sum += value;
+    }
+    let _ = sum;
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Branch, Elem, FloatKind, Item, Operation, Operator, Variable};
+
+    fn range_loop_end(operations: &[Operation]) -> Option<Variable> {
+        operations.iter().find_map(|op| match op {
+            Operation::Branch(Branch::RangeLoop(range_loop)) => Some(range_loop.end),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn cube_slice_range_test() {
+        let mut context = CubeContext::default();
+        let input = context.input(0, Item::new(Elem::Float(FloatKind::F32)));
+        let start = context.create_local_binding(Item::new(Elem::UInt));
+        slice_range::expand(&mut context, input.into(), start.into());
+        let scope = context.into_scope();
+
+        let mut context = CubeContext::default();
+        let input = context.input(0, Item::new(Elem::Float(FloatKind::F32)));
+        let start = context.create_local_binding(Item::new(Elem::UInt));
+        slice_method::expand(&mut context, input.into(), start.into());
+        let expected = context.into_scope();
+
+        assert_eq!(
+            format!("{:?}", scope.operations),
+            format!("{:?}", expected.operations)
+        );
+    }
+
+    #[test]
+    fn cube_shared_memory_slice_range_test() {
+        let mut context = CubeContext::default();
+        shared_memory_slice_range::expand(&mut context);
+        let scope = context.into_scope();
+
+        let mut context = CubeContext::default();
+        shared_memory_slice_method::expand(&mut context);
+        let expected = context.into_scope();
+
+        assert_eq!(
+            format!("{:?}", scope.operations),
+            format!("{:?}", expected.operations)
+        );
+    }
+
+    #[test]
+    fn cube_shared_memory_iter_test() {
+        let mut context = CubeContext::default();
+        shared_memory_iter::expand(&mut context);
+        let scope = context.into_scope();
+
+        // The length of the shared memory is known when compiling.
+        assert_eq!(range_loop_end(&scope.operations), Some(16u32.into()));
+    }
+
+    #[test]
+    fn cube_slice_iter_test() {
+        let mut context = CubeContext::default();
+        let input = context.input(0, Item::new(Elem::Float(FloatKind::F32)));
+        slice_iter::expand(&mut context, input.into());
+        let scope = context.into_scope();
+
+        assert!(range_loop_end(&scope.operations).is_some());
+        assert!(scope
+            .operations
+            .iter()
+            .any(|op| matches!(op, Operation::Operator(Operator::Slice(_)))));
+    }
+}
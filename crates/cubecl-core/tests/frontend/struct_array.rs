@@ -0,0 +1,58 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Clone, Copy)]
+#[expand(buffer)]
+pub struct Particle {
+    #[expand(line_size = 3)]
+    pos: Line<f32>,
+    #[expand(line_size = 3)]
+    vel: Line<f32>,
+    id: u32,
+}
+
+#[cube]
+pub fn struct_array_step(particles: &mut StructArray<Particle>) {
+    let mut particle = particles.read(UNIT_POS);
+    particle.pos += particle.vel;
+    particles.write(UNIT_POS, particle);
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Item};
+
+    #[test]
+    fn cube_struct_fields() {
+        let f32 = Elem::Float(FloatKind::F32);
+
+        assert_eq!(
+            Particle::fields(),
+            vec![
+                StructFieldInfo::new("pos", f32, 3),
+                StructFieldInfo::new("vel", f32, 3),
+                StructFieldInfo::new("id", Elem::UInt, 1),
+            ]
+        );
+        assert_eq!(Particle::layout().stride(), 32);
+    }
+
+    #[test]
+    fn cube_struct_array_read_write() {
+        let mut context = CubeContext::default();
+        let particles = context.output(0, Item::new(Elem::UInt));
+
+        struct_array_step::expand(&mut context, particles.into());
+        let scope = context.into_scope();
+
+        // The 6 float elements are bitcasted when read, then again when written.
+        let count = |pattern: &str| {
+            scope
+                .operations
+                .iter()
+                .filter(|op| format!("{op}").contains(pattern))
+                .count()
+        };
+        assert_eq!(count("bitcast"), 12);
+    }
+}
@@ -0,0 +1,72 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn subcube_reductions(value: f32) {
+    let _ = subcube_sum(value);
+    let _ = subcube_max(value);
+    if UNIT_POS == 0 {
+        let _ = subcube_any(value > 0.0);
+    }
+}
+
+#[cube]
+pub fn subcube_elect_and_broadcast(value: f32) {
+    if subcube_elect() {
+        let _ = subcube_broadcast(value, 0);
+    }
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Operation, Synchronization};
+
+    fn lowered(expand: impl FnOnce(&mut CubeContext, ExpandElementTyped<f32>)) -> KernelDefinition {
+        let mut builder = KernelBuilder::default();
+        let value = builder.scalar(Elem::Float(FloatKind::F32));
+        expand(&mut builder.context, value.into());
+
+        let mut kernel = builder.build(KernelSettings::default().cube_dim(CubeDim::new(32, 1, 1)));
+        kernel.lower_subcube();
+        kernel
+    }
+
+    fn count(kernel: &KernelDefinition, filter: impl Fn(&Operation) -> bool) -> usize {
+        let mut count = 0;
+        kernel.body.visit_operations(&mut |operation| {
+            if filter(operation) {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    #[test]
+    fn cube_lower_subcube_reductions_test() {
+        let kernel = lowered(subcube_reductions::expand);
+
+        assert_eq!(count(&kernel, |op| matches!(op, Operation::Subcube(_))), 0);
+        // A barrier after writing the shared memory and another after reading it.
+        assert_eq!(
+            count(&kernel, |op| matches!(
+                op,
+                Operation::Synchronization(Synchronization::SyncUnits)
+            )),
+            6
+        );
+    }
+
+    #[test]
+    fn cube_lower_subcube_elect_and_broadcast_test() {
+        let kernel = lowered(subcube_elect_and_broadcast::expand);
+
+        assert_eq!(count(&kernel, |op| matches!(op, Operation::Subcube(_))), 0);
+        assert_eq!(
+            count(&kernel, |op| matches!(
+                op,
+                Operation::Synchronization(Synchronization::SyncUnits)
+            )),
+            2
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn swizzle_reverse(line: Line<f32>) -> Line<f32> {
+    line.swizzle([2, 1, 0])
+}
+
+#[cube]
+pub fn swizzle_assign(line: Line<f32>, value: Line<f32>) {
+    let mut out = line;
+    out.set_swizzle([3, 0], value);
+}
+
+mod tests {
+    use std::num::NonZero;
+
+    use super::*;
+    use cubecl_core::ir::{Item, Operation, Operator};
+
+    type ElemType = f32;
+
+    fn line(context: &mut CubeContext, size: u8) -> ExpandElementTyped<Line<ElemType>> {
+        context
+            .create_local_binding(Item::vectorized(ElemType::as_elem(), NonZero::new(size)))
+            .into()
+    }
+
+    #[test]
+    fn cube_swizzle_test() {
+        let mut context = CubeContext::default();
+        let input = line(&mut context, 4);
+
+        let out = swizzle_reverse::expand(&mut context, input);
+        let scope = context.into_scope();
+
+        let swizzle = scope.operations.iter().find_map(|op| match op {
+            Operation::Operator(Operator::Swizzle(op)) => Some(op),
+            _ => None,
+        });
+
+        assert_eq!(swizzle.unwrap().components, vec![2, 1, 0]);
+        assert_eq!(out.size(), 3);
+    }
+
+    #[test]
+    fn cube_swizzle_assign_test() {
+        let mut context = CubeContext::default();
+        let input = line(&mut context, 4);
+        let value = line(&mut context, 2);
+
+        swizzle_assign::expand(&mut context, input, value);
+        let scope = context.into_scope();
+
+        let swizzle = scope.operations.iter().find_map(|op| match op {
+            Operation::Operator(Operator::SwizzleAssign(op)) => Some(op),
+            _ => None,
+        });
+
+        assert_eq!(swizzle.unwrap().components, vec![3, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cube_swizzle_out_of_bounds_fails() {
+        let mut context = CubeContext::default();
+        let input = line(&mut context, 2);
+
+        swizzle_reverse::expand(&mut context, input);
+    }
+}
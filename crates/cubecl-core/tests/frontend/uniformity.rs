@@ -0,0 +1,115 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[cube]
+pub fn sync_in_unit_branch(value: f32) {
+    if UNIT_POS == 0 {
+        let _ = value + 1.0;
+        sync_units();
+    }
+}
+
+#[cube]
+pub fn sync_in_cube_branch(value: f32) {
+    if CUBE_POS == 0 {
+        let _ = value + 1.0;
+        sync_units();
+    }
+}
+
+#[cube]
+pub fn subcube_sum_in_row_branch(value: f32) {
+    if UNIT_POS_Y == 0 {
+        let _ = subcube_sum(value);
+    }
+}
+
+#[cube]
+pub fn subcube_sum_in_unit_branch(value: f32) {
+    if UNIT_POS_X == 0 {
+        let _ = subcube_sum(value);
+    }
+}
+
+#[cube]
+pub fn sync_after_unit_return(value: f32) {
+    if UNIT_POS == 0 {
+        return;
+    }
+    sync_units();
+    let _ = value + 1.0;
+}
+
+#[cube]
+pub fn sync_in_unit_loop(value: f32) {
+    let mut count = 0;
+    loop {
+        if count > 4 {
+            break;
+        }
+        sync_units();
+        count = UNIT_POS + 1;
+    }
+    let _ = value + 1.0;
+}
+
+mod tests {
+    use super::*;
+    use cubecl_core::ir::{Elem, FloatKind, Item, Operation, Synchronization, Uniformity};
+
+    fn non_uniform(expand: impl FnOnce(&mut CubeContext, ExpandElementTyped<f32>)) -> usize {
+        let mut context = CubeContext::default();
+        let value = context.create_local_binding(Item::new(Elem::Float(FloatKind::F32)));
+        expand(&mut context, value.into());
+
+        context.into_scope().non_uniform_operations(&[]).len()
+    }
+
+    #[test]
+    fn cube_non_uniform_barrier_test() {
+        let mut context = CubeContext {
+            debug_info: true,
+            ..Default::default()
+        };
+        let value = context.create_local_binding(Item::new(Elem::Float(FloatKind::F32)));
+        sync_in_unit_branch::expand(&mut context, value.into());
+        let non_uniform = context.into_scope().non_uniform_operations(&[]);
+
+        assert_eq!(non_uniform.len(), 1);
+        assert_eq!(
+            non_uniform[0].operation,
+            Operation::Synchronization(Synchronization::SyncUnits)
+        );
+        assert_eq!(non_uniform[0].required, Uniformity::Cube);
+        assert_eq!(non_uniform[0].control, Uniformity::Unit);
+
+        let location = non_uniform[0].location.as_ref().unwrap();
+        assert_eq!(location.file, file!());
+        assert_eq!(location.line, 8);
+    }
+
+    #[test]
+    fn cube_uniform_barrier_test() {
+        assert_eq!(non_uniform(sync_in_cube_branch::expand), 0);
+    }
+
+    #[test]
+    fn cube_subcube_uniform_test() {
+        assert_eq!(non_uniform(subcube_sum_in_row_branch::expand), 0);
+    }
+
+    #[test]
+    fn cube_subcube_non_uniform_test() {
+        assert_eq!(non_uniform(subcube_sum_in_unit_branch::expand), 1);
+    }
+
+    #[test]
+    fn cube_non_uniform_return_test() {
+        assert_eq!(non_uniform(sync_after_unit_return::expand), 1);
+    }
+
+    #[test]
+    fn cube_non_uniform_loop_test() {
+        assert_eq!(non_uniform(sync_in_unit_loop::expand), 1);
+    }
+}
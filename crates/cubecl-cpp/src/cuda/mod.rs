@@ -8,7 +8,7 @@ impl Dialect for Cuda {
         f.write_str("#include <cuda_fp16.h>\n")
     }
     fn include_bf16(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("#include <cuda_bfp16.h>\n")
+        f.write_str("#include <cuda_bf16.h>\n")
     }
     fn include_wmma(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("#include <mma.h>\n")
@@ -22,4 +22,16 @@ impl Dialect for Cuda {
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("__nv_bfloat162")
     }
+    fn async_copy() -> bool {
+        true
+    }
+    fn fast_math_intrinsics() -> bool {
+        true
+    }
+    fn packed_math() -> bool {
+        true
+    }
+    fn include_pipeline(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("#include <cuda_pipeline.h>\n")
+    }
 }
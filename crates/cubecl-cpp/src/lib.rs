@@ -18,3 +18,9 @@ mod cuda;
 
 #[cfg(feature = "cuda")]
 pub type CudaCompiler = shared::CppCompiler<cuda::Cuda>;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+
+#[cfg(feature = "opencl")]
+pub type OpenClCompiler = shared::CppCompiler<opencl::OpenCl>;
@@ -0,0 +1,115 @@
+use crate::shared::{Dialect, Elem, Item, SharedMemory, Variable};
+
+/// OpenCL C, the kernels are written in the CUDA flavor of the other dialects and the builtins
+/// that differ are mapped with macros in the preamble.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OpenCl;
+
+impl Dialect for OpenCl {
+    fn include_f16(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+    fn include_bf16(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+    fn include_wmma(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+    fn include_runtime(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+    // The runtime doesn't register bf16 as a supported type, kernels using it anyway fail to build
+    // with this error instead of panicking while they are compiled to OpenCL C.
+    fn bfloat16_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\n#error \"bf16 isn't supported by OpenCL\"\n")
+    }
+    fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\n#error \"bf16 isn't supported by OpenCL\"\n")
+    }
+
+    fn preamble(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "#define __device__
+#define __align__(n) __attribute__((aligned(n)))
+#define __shared__ __local
+#define __syncthreads() barrier(CLK_LOCAL_MEM_FENCE | CLK_GLOBAL_MEM_FENCE)
+#define __threadfence() mem_fence(CLK_GLOBAL_MEM_FENCE)
+#define threadIdx ((uint3)((uint)get_local_id(0), (uint)get_local_id(1), (uint)get_local_id(2)))
+#define blockIdx ((uint3)((uint)get_group_id(0), (uint)get_group_id(1), (uint)get_group_id(2)))
+#define blockDim ((uint3)((uint)get_local_size(0), (uint)get_local_size(1), (uint)get_local_size(2)))
+#define gridDim ((uint3)((uint)get_num_groups(0), (uint)get_num_groups(1), (uint)get_num_groups(2)))
+#define make_int3(x, y, z) ((int3)((int)(x), (int)(y), (int)(z)))
+#define warpSize get_max_sub_group_size()
+#define atomicAdd atomic_add
+#define atomicSub atomic_sub
+#define atomicMax atomic_max
+#define atomicMin atomic_min
+#define atomicAnd atomic_and
+#define atomicOr atomic_or
+#define atomicXor atomic_xor
+#define atomicExch atomic_xchg
+#define atomicCAS atomic_cmpxchg
+#define __float_as_int as_int
+#define __float_as_uint as_uint
+#define __int_as_float as_float
+#define __uint_as_float as_float
+#define powf pow
+",
+        )
+    }
+
+    // `kernel` is a keyword of OpenCL C.
+    fn kernel_declaration(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("__kernel void kernel_main(")
+    }
+
+    fn binding(f: &mut std::fmt::Formatter<'_>, item: &Item<Self>, name: &str) -> std::fmt::Result {
+        write!(f, "__global {item}* {name}")
+    }
+
+    // Dynamic local memory can only be allocated as a kernel argument.
+    fn dynamic_shared_memory_binding(
+        f: &mut std::fmt::Formatter<'_>,
+        shared: &SharedMemory<Self>,
+    ) -> std::fmt::Result {
+        write!(f, "__local {}* shared_memory_{}", shared.item, shared.index)
+    }
+
+    fn shared_memory(
+        f: &mut std::fmt::Formatter<'_>,
+        shared: &SharedMemory<Self>,
+    ) -> std::fmt::Result {
+        match shared.dynamic {
+            true => Ok(()),
+            false => writeln!(
+                f,
+                "__local {} shared_memory_{}[{}];",
+                shared.item, shared.index, shared.size
+            ),
+        }
+    }
+
+    fn cast(
+        f: &mut std::fmt::Formatter<'_>,
+        elem: &Elem<Self>,
+        value: &dyn std::fmt::Display,
+    ) -> std::fmt::Result {
+        write!(f, "({elem})({value})")
+    }
+
+    // Structs are initialized with compound literals.
+    fn compose(f: &mut std::fmt::Formatter<'_>, item: &Item<Self>) -> std::fmt::Result {
+        write!(f, "({item})")
+    }
+
+    // Slices don't track where they point, so slices of shared memory aren't supported.
+    fn address_space(f: &mut std::fmt::Formatter<'_>, var: &Variable<Self>) -> std::fmt::Result {
+        match var {
+            Variable::GlobalInputArray(..)
+            | Variable::GlobalOutputArray(..)
+            | Variable::Slice { .. } => f.write_str("__global "),
+            Variable::SharedMemory(..) => f.write_str("__local "),
+            _ => Ok(()),
+        }
+    }
+}
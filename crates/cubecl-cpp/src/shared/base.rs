@@ -1,12 +1,16 @@
 use std::hash::Hash;
-use std::{collections::HashSet, fmt::Debug, num::NonZero};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    num::NonZero,
+};
 
 use cubecl_core::{
     cpa,
     ir::{
         self as gpu, ConstantScalarValue, Elem, Item, Metadata, ReusingAllocator, Scope, Variable,
     },
-    Compiler, Feature,
+    CompilationOptions, Compiler, Feature,
 };
 use cubecl_runtime::{DeviceProperties, ExecutionMode};
 
@@ -22,6 +26,95 @@ pub trait Dialect: Default + Clone + Copy + Debug + Send + Sync + Eq + Hash + 's
     fn include_runtime(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn bfloat16_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn bfloat162_type_name(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+    /// Whether bulk copies from global to shared memory are emitted as asynchronous copies on
+    /// the architectures supporting them, declared by [include_pipeline](Self::include_pipeline).
+    fn async_copy() -> bool {
+        false
+    }
+    fn include_pipeline(_f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+
+    /// Whether the `f32` functions of kernels compiled with [relaxed](gpu::FastMath::Relaxed) fast
+    /// math are emitted with the approximate intrinsics, like `__expf` and `__fdividef`.
+    fn fast_math_intrinsics() -> bool {
+        false
+    }
+
+    /// Whether the arithmetic on pairs of `f16` is emitted with the intrinsics of the packed
+    /// `half2` type, computing both halves in a single instruction.
+    fn packed_math() -> bool {
+        false
+    }
+
+    /// Definitions written at the top of every kernel, before the vectorized types.
+    fn preamble(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("typedef unsigned int uint;\n")
+    }
+
+    /// The declaration of the kernel entry point, up to its opening parenthesis.
+    fn kernel_declaration(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("extern \"C\" __global__ void kernel(")
+    }
+
+    /// A kernel parameter bound to a global array.
+    fn binding(
+        f: &mut std::fmt::Formatter<'_>,
+        item: &super::Item<Self>,
+        name: &str,
+    ) -> std::fmt::Result {
+        write!(f, "{item} {name}[]")
+    }
+
+    /// A kernel parameter for a dynamic shared memory, nothing when it's declared in the body.
+    fn dynamic_shared_memory_binding(
+        _f: &mut std::fmt::Formatter<'_>,
+        _shared: &super::SharedMemory<Self>,
+    ) -> std::fmt::Result {
+        Ok(())
+    }
+
+    /// Declare a shared memory at the start of the kernel body.
+    fn shared_memory(
+        f: &mut std::fmt::Formatter<'_>,
+        shared: &super::SharedMemory<Self>,
+    ) -> std::fmt::Result {
+        match shared.dynamic {
+            true => writeln!(
+                f,
+                "extern __shared__ {} shared_memory_{}[];",
+                shared.item, shared.index
+            ),
+            false => writeln!(
+                f,
+                "__shared__ {} shared_memory_{}[{}];",
+                shared.item, shared.index, shared.size
+            ),
+        }
+    }
+
+    /// Convert a value to the given element type.
+    fn cast(
+        f: &mut std::fmt::Formatter<'_>,
+        elem: &super::Elem<Self>,
+        value: &dyn std::fmt::Display,
+    ) -> std::fmt::Result {
+        write!(f, "{elem}({value})")
+    }
+
+    /// The type written before the braced components when initializing a vectorized item.
+    fn compose(f: &mut std::fmt::Formatter<'_>, item: &super::Item<Self>) -> std::fmt::Result {
+        write!(f, "{item}")
+    }
+
+    /// The address space qualifier of a pointer into the given variable.
+    fn address_space(
+        _f: &mut std::fmt::Formatter<'_>,
+        _var: &super::Variable<Self>,
+    ) -> std::fmt::Result {
+        Ok(())
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -35,13 +128,17 @@ pub struct CppCompiler<D: Dialect> {
     wmma: bool,
     bf16: bool,
     f16: bool,
+    pipeline: bool,
     shape: bool,
     stride: bool,
     num_inputs: usize,
     num_outputs: usize,
     items: HashSet<super::Item<D>>,
+    /// The array each slice was taken from, by slice id and depth.
+    slices: HashMap<(u16, u8), gpu::Variable>,
     strategy: ExecutionMode,
     settings: VariableSettings,
+    fast_math: bool,
 }
 
 impl<D: Dialect> Compiler for CppCompiler<D> {
@@ -49,6 +146,7 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
 
     fn compile(
         kernel: cubecl_core::ir::KernelDefinition,
+        _options: &CompilationOptions,
         strategy: ExecutionMode,
     ) -> Self::Representation {
         let compiler = Self {
@@ -71,13 +169,23 @@ impl<D: Dialect> Compiler for CppCompiler<D> {
     fn local_allocator() -> impl gpu::LocalAllocator {
         ReusingAllocator::default()
     }
+
+    fn device_functions() -> bool {
+        true
+    }
 }
 
 impl<D: Dialect> CppCompiler<D> {
     fn compile_ir(mut self, mut value: gpu::KernelDefinition) -> super::ComputeKernel<D> {
         self.num_inputs = value.inputs.len();
         self.num_outputs = value.outputs.len();
+        self.fast_math = value.fast_math == gpu::FastMath::Relaxed && D::fast_math_intrinsics();
 
+        let functions = value
+            .functions
+            .into_iter()
+            .map(|function| self.compile_function(function))
+            .collect();
         let instructions = self.compile_scope(&mut value.body);
         let inputs = value
             .inputs
@@ -113,13 +221,36 @@ impl<D: Dialect> CppCompiler<D> {
             named,
             cube_dim: value.cube_dim,
             body,
+            functions,
             wmma_activated: self.wmma,
             bf16: self.bf16,
             f16: self.f16,
+            pipeline: self.pipeline,
             items: self.items,
         }
     }
 
+    fn compile_function(&mut self, mut value: gpu::FunctionDefinition) -> super::Function<D> {
+        let mut instructions = self.compile_scope(&mut value.body);
+        let num_declarations = instructions
+            .iter()
+            .take_while(|instruction| matches!(instruction, Instruction::DeclareVariable { .. }))
+            .count();
+        let declarations = instructions.drain(..num_declarations).collect();
+
+        super::Function {
+            name: value.name,
+            inputs: value
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: value.output.map(|output| self.compile_variable(output)),
+            declarations,
+            instructions,
+        }
+    }
+
     fn compile_scope(&mut self, scope: &mut gpu::Scope) -> Vec<Instruction<D>> {
         let mut instructions = Vec::new();
 
@@ -167,9 +298,35 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Operation::Operator(op) => self.compile_instruction(op, instructions, scope),
             gpu::Operation::Metadata(op) => instructions.push(self.compile_metadata(op)),
             gpu::Operation::Branch(val) => self.compile_branch(instructions, val),
+            gpu::Operation::Call(call) => instructions.push(Instruction::Call {
+                name: call.name,
+                args: call
+                    .args
+                    .into_iter()
+                    .map(|arg| self.compile_variable(arg))
+                    .collect(),
+                out: call.out.map(|out| self.compile_variable(out)),
+            }),
+            gpu::Operation::Location(location) => instructions.push(Instruction::Comment {
+                content: location.to_string(),
+            }),
             gpu::Operation::Synchronization(val) => match val {
                 gpu::Synchronization::SyncUnits => instructions.push(Instruction::SyncThreads),
                 gpu::Synchronization::SyncStorage => instructions.push(Instruction::SyncThreads),
+                // Copies are only asynchronous when the dialect supports it, and are otherwise
+                // already complete.
+                gpu::Synchronization::CopyAsyncCommit => {
+                    if D::async_copy() {
+                        self.pipeline = true;
+                        instructions.push(Instruction::PipelineCommit)
+                    }
+                }
+                gpu::Synchronization::CopyAsyncWait { pending } => {
+                    if D::async_copy() {
+                        self.pipeline = true;
+                        instructions.push(Instruction::PipelineWait { pending })
+                    }
+                }
             },
             gpu::Operation::Subcube(op) => {
                 self.wrap_size_checked = true;
@@ -349,6 +506,7 @@ impl<D: Dialect> CppCompiler<D> {
             }),
             gpu::Branch::Return => instructions.push(Instruction::Return),
             gpu::Branch::Break => instructions.push(Instruction::Break),
+            gpu::Branch::Continue => instructions.push(Instruction::Continue),
             gpu::Branch::RangeLoop(mut range_loop) => instructions.push(Instruction::RangeLoop {
                 i: self.compile_variable(range_loop.i),
                 start: self.compile_variable(range_loop.start),
@@ -363,6 +521,12 @@ impl<D: Dialect> CppCompiler<D> {
         };
     }
 
+    /// Whether the operation writing to `out` uses the approximate intrinsics, only available for
+    /// `f32`.
+    fn is_fast_math(&self, out: gpu::Variable) -> bool {
+        self.fast_math && out.item().elem() == gpu::Elem::Float(gpu::FloatKind::F32)
+    }
+
     fn compile_instruction(
         &mut self,
         value: gpu::Operator,
@@ -372,17 +536,25 @@ impl<D: Dialect> CppCompiler<D> {
         match value {
             gpu::Operator::Add(op) => instructions.push(Instruction::Add(self.compile_binary(op))),
             gpu::Operator::Mul(op) => instructions.push(Instruction::Mul(self.compile_binary(op))),
+            gpu::Operator::Div(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastDiv(self.compile_binary(op)))
+            }
             gpu::Operator::Div(op) => instructions.push(Instruction::Div(self.compile_binary(op))),
             gpu::Operator::Sub(op) => instructions.push(Instruction::Sub(self.compile_binary(op))),
             gpu::Operator::Assign(op) => {
                 instructions.push(Instruction::Assign(self.compile_unary(op)))
             }
-            gpu::Operator::Slice(op) => instructions.push(Instruction::Slice {
-                input: self.compile_variable(op.input),
-                start: self.compile_variable(op.start),
-                end: self.compile_variable(op.end),
-                out: self.compile_variable(op.out),
-            }),
+            gpu::Operator::Slice(op) => {
+                if let gpu::Variable::Slice { id, depth, .. } = op.out {
+                    self.slices.insert((id, depth), self.memory_of(op.input));
+                }
+                instructions.push(Instruction::Slice {
+                    input: self.compile_variable(op.input),
+                    start: self.compile_variable(op.start),
+                    end: self.compile_variable(op.end),
+                    out: self.compile_variable(op.out),
+                })
+            }
             gpu::Operator::Index(op) => {
                 if matches!(self.strategy, ExecutionMode::Checked) && has_length(&op.lhs) {
                     let lhs = op.lhs;
@@ -446,23 +618,63 @@ impl<D: Dialect> CppCompiler<D> {
                 instructions.push(Instruction::GreaterEqual(self.compile_binary(op)))
             }
             gpu::Operator::Abs(op) => instructions.push(Instruction::Abs(self.compile_unary(op))),
+            gpu::Operator::Exp(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastExp(self.compile_unary(op)))
+            }
             gpu::Operator::Exp(op) => instructions.push(Instruction::Exp(self.compile_unary(op))),
+            gpu::Operator::Log(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastLog(self.compile_unary(op)))
+            }
             gpu::Operator::Log(op) => instructions.push(Instruction::Log(self.compile_unary(op))),
             gpu::Operator::Log1p(op) => {
                 instructions.push(Instruction::Log1p(self.compile_unary(op)))
             }
+            gpu::Operator::Cos(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastCos(self.compile_unary(op)))
+            }
             gpu::Operator::Cos(op) => instructions.push(Instruction::Cos(self.compile_unary(op))),
+            gpu::Operator::Sin(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastSin(self.compile_unary(op)))
+            }
             gpu::Operator::Sin(op) => instructions.push(Instruction::Sin(self.compile_unary(op))),
             gpu::Operator::Tanh(op) => instructions.push(Instruction::Tanh(self.compile_unary(op))),
+            gpu::Operator::Powf(op) if self.is_fast_math(op.out) => {
+                instructions.push(Instruction::FastPowf(self.compile_binary(op)))
+            }
             gpu::Operator::Powf(op) => {
                 instructions.push(Instruction::Powf(self.compile_binary(op)))
             }
             gpu::Operator::Sqrt(op) => instructions.push(Instruction::Sqrt(self.compile_unary(op))),
             gpu::Operator::Erf(op) => instructions.push(Instruction::Erf(self.compile_unary(op))),
+            gpu::Operator::Erfc(op) => instructions.push(Instruction::Erfc(self.compile_unary(op))),
+            gpu::Operator::Expm1(op) => {
+                instructions.push(Instruction::Expm1(self.compile_unary(op)))
+            }
+            gpu::Operator::Tgamma(op) => {
+                instructions.push(Instruction::Tgamma(self.compile_unary(op)))
+            }
+            gpu::Operator::Lgamma(op) => {
+                instructions.push(Instruction::Lgamma(self.compile_unary(op)))
+            }
             gpu::Operator::And(op) => instructions.push(Instruction::And(self.compile_binary(op))),
             gpu::Operator::Or(op) => instructions.push(Instruction::Or(self.compile_binary(op))),
             gpu::Operator::Not(op) => instructions.push(Instruction::Not(self.compile_unary(op))),
             gpu::Operator::Max(op) => instructions.push(Instruction::Max(self.compile_binary(op))),
+            gpu::Operator::SaturatingAdd(op) => {
+                instructions.push(Instruction::SaturatingAdd(self.compile_binary(op)))
+            }
+            gpu::Operator::SaturatingSub(op) => {
+                instructions.push(Instruction::SaturatingSub(self.compile_binary(op)))
+            }
+            gpu::Operator::WrappingAdd(op) => {
+                instructions.push(Instruction::WrappingAdd(self.compile_binary(op)))
+            }
+            gpu::Operator::WrappingSub(op) => {
+                instructions.push(Instruction::WrappingSub(self.compile_binary(op)))
+            }
+            gpu::Operator::WrappingMul(op) => {
+                instructions.push(Instruction::WrappingMul(self.compile_binary(op)))
+            }
             gpu::Operator::Min(op) => instructions.push(Instruction::Min(self.compile_binary(op))),
             gpu::Operator::NotEqual(op) => {
                 instructions.push(Instruction::NotEqual(self.compile_binary(op)))
@@ -482,6 +694,25 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Operator::ShiftRight(op) => {
                 instructions.push(Instruction::ShiftRight(self.compile_binary(op)))
             }
+            gpu::Operator::RotateLeft(op) => {
+                instructions.push(Instruction::RotateLeft(self.compile_binary(op)))
+            }
+            gpu::Operator::RotateRight(op) => {
+                instructions.push(Instruction::RotateRight(self.compile_binary(op)))
+            }
+            gpu::Operator::BitfieldExtract(op) => instructions.push(Instruction::ExtractBits {
+                input: self.compile_variable(op.input),
+                offset: self.compile_variable(op.offset),
+                count: self.compile_variable(op.count),
+                out: self.compile_variable(op.out),
+            }),
+            gpu::Operator::BitfieldInsert(op) => instructions.push(Instruction::InsertBits {
+                base: self.compile_variable(op.base),
+                insert: self.compile_variable(op.insert),
+                offset: self.compile_variable(op.offset),
+                count: self.compile_variable(op.count),
+                out: self.compile_variable(op.out),
+            }),
             gpu::Operator::Clamp(op) => instructions.push(Instruction::Clamp {
                 input: self.compile_variable(op.input),
                 min_value: self.compile_variable(op.min_value),
@@ -525,6 +756,20 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Operator::Bitcast(op) => {
                 instructions.push(Instruction::Bitcast(self.compile_unary(op)))
             }
+            gpu::Operator::PackF16(op) => {
+                self.f16 = true;
+                instructions.push(Instruction::PackF16(self.compile_unary(op)))
+            }
+            gpu::Operator::UnpackF16(op) => {
+                self.f16 = true;
+                instructions.push(Instruction::UnpackF16(self.compile_unary(op)))
+            }
+            gpu::Operator::PackBf16(op) => {
+                instructions.push(Instruction::PackBf16(self.compile_unary(op)))
+            }
+            gpu::Operator::UnpackBf16(op) => {
+                instructions.push(Instruction::UnpackBf16(self.compile_unary(op)))
+            }
             gpu::Operator::AtomicLoad(op) => {
                 instructions.push(Instruction::AtomicLoad(self.compile_unary(op)))
             }
@@ -579,22 +824,96 @@ impl<D: Dialect> CppCompiler<D> {
                     .collect(),
                 out: self.compile_variable(op.out),
             }),
-            gpu::Operator::Copy(op) => instructions.push(Instruction::Copy {
+            gpu::Operator::MatrixMul(_)
+            | gpu::Operator::Transpose(_)
+            | gpu::Operator::Determinant(_) => {
+                unreachable!("Matrix operations are unrolled for C++ targets")
+            }
+            gpu::Operator::Swizzle(op) => instructions.push(Instruction::Swizzle {
                 input: self.compile_variable(op.input),
-                in_index: self.compile_variable(op.in_index),
+                components: op.components,
                 out: self.compile_variable(op.out),
-                out_index: self.compile_variable(op.out_index),
             }),
-            gpu::Operator::CopyBulk(op) => instructions.push(Instruction::CopyBulk {
+            gpu::Operator::SwizzleAssign(op) => instructions.push(Instruction::SwizzleAssign {
+                input: self.compile_variable(op.input),
+                components: op.components,
+                out: self.compile_variable(op.out),
+            }),
+            gpu::Operator::Copy(op) => instructions.push(Instruction::Copy {
                 input: self.compile_variable(op.input),
                 in_index: self.compile_variable(op.in_index),
                 out: self.compile_variable(op.out),
                 out_index: self.compile_variable(op.out_index),
-                len: op.len,
             }),
+            gpu::Operator::CopyBulk(op) => {
+                if self.can_copy_async(&op) {
+                    // Awaited right away, so it's visible to the unit like a regular copy.
+                    self.pipeline = true;
+                    instructions.push(self.compile_copy_bulk(op, true));
+                    instructions.push(Instruction::PipelineCommit);
+                    instructions.push(Instruction::PipelineWait { pending: 0 });
+                } else {
+                    instructions.push(self.compile_copy_bulk(op, false));
+                }
+            }
+            gpu::Operator::CopyBulkAsync(op) => {
+                let is_async = self.can_copy_async(&op);
+                self.pipeline |= is_async;
+                instructions.push(self.compile_copy_bulk(op, is_async));
+            }
         };
     }
 
+    /// Whether a bulk copy can be lowered to asynchronous copies, which only go from global to
+    /// shared memory.
+    fn can_copy_async(&mut self, op: &gpu::CopyBulkOperator) -> bool {
+        let from_global = matches!(
+            self.memory_of(op.input),
+            gpu::Variable::GlobalInputArray { .. } | gpu::Variable::GlobalOutputArray { .. }
+        );
+        let to_shared = matches!(self.memory_of(op.out), gpu::Variable::SharedMemory { .. });
+        let item = self.compile_item(op.input.item());
+        // Asynchronous copies move 4, 8 or 16 bytes at once.
+        let copy_size = item.elem.size() * item.vectorization;
+
+        D::async_copy() && from_global && to_shared && matches!(copy_size, 4 | 8 | 16)
+    }
+
+    fn compile_copy_bulk(&mut self, op: gpu::CopyBulkOperator, is_async: bool) -> Instruction<D> {
+        let input = self.compile_variable(op.input);
+        let in_index = self.compile_variable(op.in_index);
+        let out = self.compile_variable(op.out);
+        let out_index = self.compile_variable(op.out_index);
+
+        if is_async {
+            Instruction::CopyBulkAsync {
+                input,
+                in_index,
+                out,
+                out_index,
+                len: op.len,
+            }
+        } else {
+            Instruction::CopyBulk {
+                input,
+                in_index,
+                out,
+                out_index,
+                len: op.len,
+            }
+        }
+    }
+
+    /// The memory a variable points to, following slices to the array they were taken from.
+    fn memory_of(&self, var: gpu::Variable) -> gpu::Variable {
+        match var {
+            gpu::Variable::Slice { id, depth, .. } => {
+                self.slices.get(&(id, depth)).copied().unwrap_or(var)
+            }
+            _ => var,
+        }
+    }
+
     fn compile_binary(&mut self, value: gpu::BinaryOperator) -> super::BinaryInstruction<D> {
         super::BinaryInstruction {
             lhs: self.compile_variable(value.lhs),
@@ -646,11 +965,19 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Variable::ConstantScalar(value) => {
                 super::Variable::ConstantScalar(value, self.compile_elem(value.elem()))
             }
-            gpu::Variable::SharedMemory { id, item, length } => {
+            gpu::Variable::SharedMemory {
+                id,
+                item,
+                length,
+                dynamic,
+            } => {
                 let item = self.compile_item(item);
                 if !self.shared_memories.iter().any(|s| s.index == id) {
-                    self.shared_memories
-                        .push(super::SharedMemory::new(id, item, length));
+                    let shared = match dynamic {
+                        true => super::SharedMemory::new_dynamic(id, item),
+                        false => super::SharedMemory::new(id, item, length),
+                    };
+                    self.shared_memories.push(shared);
                 }
                 super::Variable::SharedMemory(id, item, length)
             }
@@ -862,4 +1189,8 @@ pub fn register_supported_types(props: &mut DeviceProperties<Feature>) {
     for ty in supported_types {
         props.register_feature(Feature::Type(ty));
     }
+    // Lines of any size are stored in structs, so every matrix size is supported.
+    for size in [2, 3, 4] {
+        props.register_feature(Feature::Matrix(size));
+    }
 }
@@ -46,15 +46,14 @@ pub trait Binary<D: Dialect> {
         let item_out_original = out.item();
         let item_out_optimized = out_optimized.item();
 
-        let index = match optimized.optimization_factor {
-            Some(factor) => item_out_optimized.vectorization / factor,
-            None => item_out_optimized.vectorization,
-        };
+        // The optimized item already accounts for the packed elements, each of its components
+        // must be written.
+        let index = item_out_optimized.vectorization;
 
         let mut write_op =
             |lhs: &Variable<D>, rhs: &Variable<D>, out: &Variable<D>, item_out: Item<D>| {
                 let out = out.fmt_left();
-                writeln!(f, "{out} = {item_out}{{")?;
+                writeln!(f, "{out} = {}{{", item_out.compose())?;
                 for i in 0..index {
                     let lhsi = lhs.index(i);
                     let rhsi = rhs.index(i);
@@ -102,6 +101,29 @@ macro_rules! operator {
     };
 }
 
+/// An operator with an intrinsic computing both halves of a packed pair at once, used on the
+/// dialects supporting [packed math](Dialect::packed_math).
+macro_rules! packed_operator {
+    ($name:ident, $op:expr, $packed:expr) => {
+        pub struct $name;
+
+        impl<D: Dialect> Binary<D> for $name {
+            fn format_scalar<Lhs: Display, Rhs: Display>(
+                f: &mut std::fmt::Formatter<'_>,
+                lhs: Lhs,
+                rhs: Rhs,
+                item: Item<D>,
+            ) -> std::fmt::Result {
+                if D::packed_math() && item.elem == Elem::F162 {
+                    write!(f, "{}({lhs}, {rhs})", $packed)
+                } else {
+                    write!(f, "{lhs} {} {rhs}", $op)
+                }
+            }
+        }
+    };
+}
+
 macro_rules! function {
     ($name:ident, $op:expr) => {
         pub struct $name;
@@ -119,11 +141,10 @@ macro_rules! function {
     };
 }
 
-operator!(Add, "+");
-operator!(Sub, "-");
-operator!(Div, "/");
-operator!(Mul, "*");
-operator!(Modulo, "%");
+packed_operator!(Add, "+", "__hadd2");
+packed_operator!(Sub, "-", "__hsub2");
+packed_operator!(Div, "/", "__h2div");
+packed_operator!(Mul, "*", "__hmul2");
 operator!(Equal, "==");
 operator!(NotEqual, "!=");
 operator!(Lower, "<");
@@ -139,9 +160,129 @@ operator!(Or, "||");
 operator!(And, "&&");
 
 function!(Powf, "powf");
+function!(FastPowf, "__powf");
+function!(FastDiv, "__fdividef");
 function!(Max, "max");
 function!(Min, "min");
 
+/// Integer arithmetic wrapping around on overflow, computed on unsigned integers since signed
+/// overflow is undefined behavior.
+macro_rules! wrapping_operator {
+    ($name:ident, $op:expr) => {
+        pub struct $name;
+
+        impl<D: Dialect> Binary<D> for $name {
+            fn format_scalar<Lhs: Display, Rhs: Display>(
+                f: &mut std::fmt::Formatter<'_>,
+                lhs: Lhs,
+                rhs: Rhs,
+                item: Item<D>,
+            ) -> std::fmt::Result {
+                match item.elem {
+                    Elem::I32 => write!(f, "int(uint({lhs}) {} uint({rhs}))", $op),
+                    _ => write!(f, "{lhs} {} {rhs}", $op),
+                }
+            }
+        }
+    };
+}
+
+wrapping_operator!(WrappingAdd, "+");
+wrapping_operator!(WrappingSub, "-");
+wrapping_operator!(WrappingMul, "*");
+
+/// The remainder of the truncated division, with the sign of the dividend. C++ only defines `%`
+/// on integers, so floats use `fmod`.
+pub struct Modulo;
+
+impl<D: Dialect> Binary<D> for Modulo {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        match item.elem {
+            Elem::F32 => write!(f, "fmod({lhs}, {rhs})"),
+            Elem::F16 | Elem::BF16 => {
+                write!(f, "{}(fmod(float({lhs}), float({rhs})))", item.elem)
+            }
+            _ => write!(f, "{lhs} % {rhs}"),
+        }
+    }
+}
+
+pub struct SaturatingAdd;
+pub struct SaturatingSub;
+pub struct RotateLeft;
+pub struct RotateRight;
+
+impl<D: Dialect> Binary<D> for SaturatingAdd {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        // Compare with the bounds offset by `rhs` so the overflowing sum is never computed.
+        match item.elem {
+            Elem::I32 => write!(
+                f,
+                "({rhs} > 0 ? ({lhs} > {max} - {rhs} ? {max} : {lhs} + {rhs}) : ({lhs} < {min} - {rhs} ? {min} : {lhs} + {rhs}))",
+                min = I32_MIN,
+                max = I32_MAX,
+            ),
+            _ => write!(f, "({lhs} > {U32_MAX} - {rhs} ? {U32_MAX} : {lhs} + {rhs})"),
+        }
+    }
+}
+
+impl<D: Dialect> Binary<D> for SaturatingSub {
+    fn format_scalar<Lhs: Display, Rhs: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        lhs: Lhs,
+        rhs: Rhs,
+        item: Item<D>,
+    ) -> std::fmt::Result {
+        match item.elem {
+            Elem::I32 => write!(
+                f,
+                "({rhs} > 0 ? ({lhs} < {min} + {rhs} ? {min} : {lhs} - {rhs}) : ({lhs} > {max} + {rhs} ? {max} : {lhs} - {rhs}))",
+                min = I32_MIN,
+                max = I32_MAX,
+            ),
+            _ => write!(f, "({lhs} < {rhs} ? 0u : {lhs} - {rhs})"),
+        }
+    }
+}
+
+/// Rotations are funnel shifts of the value with itself, which take the amount modulo 32.
+macro_rules! rotate {
+    ($name:ident, $funnel:expr) => {
+        impl<D: Dialect> Binary<D> for $name {
+            fn format_scalar<Lhs: Display, Rhs: Display>(
+                f: &mut std::fmt::Formatter<'_>,
+                lhs: Lhs,
+                rhs: Rhs,
+                item: Item<D>,
+            ) -> std::fmt::Result {
+                match item.elem {
+                    Elem::I32 => write!(f, "int({}(uint({lhs}), uint({lhs}), {rhs}))", $funnel),
+                    _ => write!(f, "{}({lhs}, {lhs}, {rhs})", $funnel),
+                }
+            }
+        }
+    };
+}
+
+rotate!(RotateLeft, "__funnelshift_l");
+rotate!(RotateRight, "__funnelshift_r");
+
+// `-2147483648` would be parsed as the negation of a literal too large for an `int`.
+const I32_MIN: &str = "(-2147483647 - 1)";
+const I32_MAX: &str = "2147483647";
+const U32_MAX: &str = "0xffffffffu";
+
 pub struct IndexAssign;
 pub struct Index;
 
@@ -159,10 +300,10 @@ impl<D: Dialect> Binary<D> for IndexAssign {
         let item_rhs = rhs.item();
 
         let format_vec = |f: &mut Formatter<'_>, cast: bool| {
-            writeln!(f, "{item_out}{{")?;
+            writeln!(f, "{}{{", item_out.compose())?;
             for i in 0..item_out.vectorization {
                 if cast {
-                    writeln!(f, "{}({}),", item_out.elem, rhs.index(i))?;
+                    writeln!(f, "{},", item_out.elem.cast(rhs.index(i)))?;
                 } else {
                     writeln!(f, "{},", rhs.index(i))?;
                 }
@@ -178,10 +319,10 @@ impl<D: Dialect> Binary<D> for IndexAssign {
             if item_out.vectorization > 1 {
                 format_vec(f, true)?;
             } else {
-                write!(f, "{}({rhs})", item_out.elem)?;
+                write!(f, "{}", item_out.elem.cast(rhs))?;
             }
             Ok(())
-        } else if rhs.is_const() && item_rhs.vectorization > 1 {
+        } else if rhs.is_const() && item_rhs.vectorization > 1 && item_rhs.optimized() != item_rhs {
             // Reinterpret cast in case rhs is optimized
             write!(f, "reinterpret_cast<{item_out} const&>({rhs})")
         } else {
@@ -245,7 +386,7 @@ impl<D: Dialect> Binary<D> for Index {
 
         let item_out = out.item();
         if let Elem::Atomic(inner) = item_out.elem {
-            write!(f, "{inner}* {out} = &{lhs}[{rhs}];")
+            write!(f, "{}{inner}* {out} = &{lhs}[{rhs}];", lhs.address_space())
         } else {
             let out = out.fmt_left();
             write!(f, "{out} = ")?;
@@ -267,9 +408,10 @@ impl<D: Dialect> Binary<D> for Index {
         let item_lhs = lhs.item();
 
         let format_vec = |f: &mut Formatter<'_>| {
-            writeln!(f, "{item_out}{{")?;
+            writeln!(f, "{}{{", item_out.compose())?;
             for i in 0..item_out.vectorization {
-                write!(f, "{}({lhs}[{rhs}].i_{i}),", item_out.elem)?;
+                let value = format!("{lhs}[{rhs}].i_{i}");
+                write!(f, "{},", item_out.elem.cast(value))?;
             }
             f.write_str("}")?;
 
@@ -280,7 +422,7 @@ impl<D: Dialect> Binary<D> for Index {
             if item_out.vectorization > 1 {
                 format_vec(f)
             } else {
-                write!(f, "{}({lhs}[{rhs}])", item_out.elem)
+                write!(f, "{}", item_out.elem.cast(format!("{lhs}[{rhs}]")))
             }
         } else {
             write!(f, "{lhs}[{rhs}]")
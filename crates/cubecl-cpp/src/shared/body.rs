@@ -100,11 +100,7 @@ impl<D: Dialect> Display for Body<D> {
         }
 
         for shared in self.shared_memories.iter() {
-            writeln!(
-                f,
-                "__shared__ {} shared_memory_{}[{}];",
-                shared.item, shared.index, shared.size
-            )?;
+            D::shared_memory(f, shared)?;
         }
 
         for const_array in self.const_arrays.iter() {
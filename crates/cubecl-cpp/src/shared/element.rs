@@ -216,21 +216,23 @@ impl<D: Dialect> Display for Variable<D> {
             // precision related problems.
             Variable::ConstantScalar(number, elem) => match number {
                 ConstantScalarValue::Int(val, kind) => match kind {
-                    gpu::IntKind::I32 => write!(f, "{elem}({})", *val as i32),
-                    gpu::IntKind::I64 => write!(f, "{elem}({})", *val),
+                    gpu::IntKind::I32 => write!(f, "{}", elem.cast(*val as i32)),
+                    gpu::IntKind::I64 => write!(f, "{}", elem.cast(*val)),
                 },
                 ConstantScalarValue::Float(val, kind) => match kind {
                     gpu::FloatKind::F16 => {
-                        write!(f, "{elem}({:?})", half::f16::from_f64(*val))
+                        let val = format!("{:?}", half::f16::from_f64(*val));
+                        write!(f, "{}", elem.cast(val))
                     }
                     gpu::FloatKind::BF16 => {
-                        write!(f, "{elem}({:?})", half::bf16::from_f64(*val))
+                        let val = format!("{:?}", half::bf16::from_f64(*val));
+                        write!(f, "{}", elem.cast(val))
                     }
-                    gpu::FloatKind::F32 => write!(f, "{elem}({:?})", *val as f32),
-                    gpu::FloatKind::F64 => write!(f, "{elem}({:?})", *val),
+                    gpu::FloatKind::F32 => write!(f, "{}", elem.cast(format!("{:?}", *val as f32))),
+                    gpu::FloatKind::F64 => write!(f, "{}", elem.cast(format!("{:?}", *val))),
                 },
                 ConstantScalarValue::UInt(val) => {
-                    write!(f, "{elem}({})", *val as u32)
+                    write!(f, "{}", elem.cast(*val as u32))
                 }
                 ConstantScalarValue::Bool(val) => write!(f, "{}", val),
             },
@@ -479,6 +481,10 @@ impl<D: Dialect> Item<D> {
         &self.elem
     }
 
+    pub fn compose(&self) -> Compose<D> {
+        Compose { item: *self }
+    }
+
     pub fn de_optimized(&self) -> Self {
         match self.elem {
             Elem::F162 => Item::new(Elem::F16, self.vectorization * 2),
@@ -527,7 +533,51 @@ impl<D: Dialect> Item<D> {
     }
 }
 
+/// A value converted to an element type, formatted with the syntax of the dialect.
+pub struct Cast<D: Dialect, V: Display> {
+    elem: Elem<D>,
+    value: V,
+}
+
+impl<D: Dialect, V: Display> Display for Cast<D, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        D::cast(f, &self.elem, &self.value)
+    }
+}
+
+/// The type of a braced initializer for a vectorized item, e.g. `float_4` in `float_4{...}`.
+pub struct Compose<D: Dialect> {
+    item: Item<D>,
+}
+
+impl<D: Dialect> Display for Compose<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        D::compose(f, &self.item)
+    }
+}
+
+/// The address space qualifier of a pointer into a variable, if the dialect requires one.
+pub struct AddressSpace<D: Dialect> {
+    var: Variable<D>,
+}
+
+impl<D: Dialect> Display for AddressSpace<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        D::address_space(f, &self.var)
+    }
+}
+
+impl<D: Dialect> Variable<D> {
+    pub fn address_space(&self) -> AddressSpace<D> {
+        AddressSpace { var: *self }
+    }
+}
+
 impl<D: Dialect> Elem<D> {
+    pub fn cast<V: Display>(&self, value: V) -> Cast<D, V> {
+        Cast { elem: *self, value }
+    }
+
     pub fn size(&self) -> usize {
         match self {
             Self::F16 => core::mem::size_of::<f16>(),
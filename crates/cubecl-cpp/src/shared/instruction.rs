@@ -1,7 +1,7 @@
 use crate::shared::FmtLeft;
 
 use super::{
-    binary::*, unary::*, Component, Dialect, Elem, Variable, WarpInstruction, WmmaInstruction,
+    binary::*, unary::*, Component, Dialect, Elem, Item, Variable, WarpInstruction, WmmaInstruction,
 };
 use std::{fmt::Display, marker::PhantomData};
 
@@ -66,6 +66,16 @@ pub enum Instruction<D: Dialect> {
         inputs: Vec<Variable<D>>,
         out: Variable<D>,
     },
+    Swizzle {
+        input: Variable<D>,
+        components: Vec<u32>,
+        out: Variable<D>,
+    },
+    SwizzleAssign {
+        input: Variable<D>,
+        components: Vec<u32>,
+        out: Variable<D>,
+    },
     Loop {
         instructions: Vec<Self>,
     },
@@ -97,6 +107,15 @@ pub enum Instruction<D: Dialect> {
     },
     Return,
     Break,
+    Continue,
+    Call {
+        name: String,
+        args: Vec<Variable<D>>,
+        out: Option<Variable<D>>,
+    },
+    Comment {
+        content: String,
+    },
     Stride {
         dim: Variable<D>,
         position: usize,
@@ -114,6 +133,17 @@ pub enum Instruction<D: Dialect> {
     LowerEqual(BinaryInstruction<D>),
     GreaterEqual(BinaryInstruction<D>),
     Erf(UnaryInstruction<D>),
+    Erfc(UnaryInstruction<D>),
+    Expm1(UnaryInstruction<D>),
+    Tgamma(UnaryInstruction<D>),
+    Lgamma(UnaryInstruction<D>),
+    /// The approximate `f32` intrinsics used with [relaxed](cubecl_core::ir::FastMath::Relaxed) fast math.
+    FastExp(UnaryInstruction<D>),
+    FastLog(UnaryInstruction<D>),
+    FastCos(UnaryInstruction<D>),
+    FastSin(UnaryInstruction<D>),
+    FastPowf(BinaryInstruction<D>),
+    FastDiv(BinaryInstruction<D>),
     BitwiseOr(BinaryInstruction<D>),
     BitwiseAnd(BinaryInstruction<D>),
     BitwiseXor(BinaryInstruction<D>),
@@ -130,6 +160,11 @@ pub enum Instruction<D: Dialect> {
     Sqrt(UnaryInstruction<D>),
     Min(BinaryInstruction<D>),
     Max(BinaryInstruction<D>),
+    SaturatingAdd(BinaryInstruction<D>),
+    SaturatingSub(BinaryInstruction<D>),
+    WrappingAdd(BinaryInstruction<D>),
+    WrappingSub(BinaryInstruction<D>),
+    WrappingMul(BinaryInstruction<D>),
     Not(UnaryInstruction<D>),
     Or(BinaryInstruction<D>),
     And(BinaryInstruction<D>),
@@ -139,6 +174,21 @@ pub enum Instruction<D: Dialect> {
         max_value: Variable<D>,
         out: Variable<D>,
     },
+    RotateLeft(BinaryInstruction<D>),
+    RotateRight(BinaryInstruction<D>),
+    ExtractBits {
+        input: Variable<D>,
+        offset: Variable<D>,
+        count: Variable<D>,
+        out: Variable<D>,
+    },
+    InsertBits {
+        base: Variable<D>,
+        insert: Variable<D>,
+        offset: Variable<D>,
+        count: Variable<D>,
+        out: Variable<D>,
+    },
     SyncThreads,
     ThreadFence,
     Round(UnaryInstruction<D>),
@@ -147,6 +197,10 @@ pub enum Instruction<D: Dialect> {
     Wrap(WarpInstruction<D>),
     Wmma(WmmaInstruction<D>),
     Bitcast(UnaryInstruction<D>),
+    PackF16(UnaryInstruction<D>),
+    UnpackF16(UnaryInstruction<D>),
+    PackBf16(UnaryInstruction<D>),
+    UnpackBf16(UnaryInstruction<D>),
     AtomicLoad(UnaryInstruction<D>),
     AtomicStore(UnaryInstruction<D>),
     AtomicSwap(BinaryInstruction<D>),
@@ -180,6 +234,20 @@ pub enum Instruction<D: Dialect> {
         out_index: Variable<D>,
         len: u32,
     },
+    /// A bulk copy from global to shared memory, asynchronous on the architectures supporting it.
+    CopyBulkAsync {
+        input: Variable<D>,
+        in_index: Variable<D>,
+        out: Variable<D>,
+        out_index: Variable<D>,
+        len: u32,
+    },
+    /// Group the pending asynchronous copies.
+    PipelineCommit,
+    /// Wait until at most `pending` groups of asynchronous copies are in flight.
+    PipelineWait {
+        pending: u32,
+    },
 }
 
 impl<D: Dialect> Display for Instruction<D> {
@@ -187,6 +255,22 @@ impl<D: Dialect> Display for Instruction<D> {
         match self {
             Instruction::Return => f.write_str("return;"),
             Instruction::Break => f.write_str("break;"),
+            Instruction::Continue => f.write_str("continue;"),
+            Instruction::Comment { content } => writeln!(f, "// {content}"),
+            Instruction::Call { name, args, out } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match out {
+                    Some(out) => {
+                        let out = out.fmt_left();
+                        writeln!(f, "{out} = {name}({args});")
+                    }
+                    None => writeln!(f, "{name}({args});"),
+                }
+            }
             Instruction::DeclareVariable { var } => match var {
                 Variable::WmmaFragment { frag, .. } => writeln!(f, "{frag} {var};"),
                 _ => {
@@ -203,7 +287,8 @@ impl<D: Dialect> Display for Instruction<D> {
             } => {
                 let item = out.item();
                 writeln!(f, "const uint {out}_length = {end} - {start};")?;
-                writeln!(f, "{item} *{out} = {input} + {start};")
+                let space = input.address_space();
+                writeln!(f, "{space}{item} *{out} = {input} + {start};")
             }
             Instruction::Mul(it) => Mul::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Div(it) => Div::format(f, &it.lhs, &it.rhs, &it.out),
@@ -218,15 +303,17 @@ impl<D: Dialect> Display for Instruction<D> {
             Instruction::CheckedIndex { len, lhs, rhs, out } => {
                 let item_out = out.item();
                 if let Elem::Atomic(inner) = item_out.elem {
-                    write!(f, "{inner}* {out} = &{lhs}[{rhs}];")
+                    write!(f, "{}{inner}* {out} = &{lhs}[{rhs}];", lhs.address_space())
                 } else {
                     let out = out.fmt_left();
                     write!(f, "{out} = ({rhs} < {len}) ? ")?;
                     Index::format_scalar(f, *lhs, *rhs, item_out)?;
+                    let zero = item_out.elem.cast(0);
                     if item_out.vectorization == 1 {
-                        writeln!(f, " : {item_out}(0);")
+                        writeln!(f, " : {zero};")
                     } else {
-                        writeln!(f, " : {item_out}{{}};")
+                        let zeros = vec![zero.to_string(); item_out.vectorization];
+                        writeln!(f, " : {}{{{}}};", item_out.compose(), zeros.join(","))
                     }
                 }
             }
@@ -251,6 +338,37 @@ impl<D: Dialect> Display for Instruction<D> {
                 }
                 Ok(())
             }
+            Instruction::CopyBulkAsync {
+                input,
+                in_index,
+                out,
+                out_index,
+                len,
+            } => {
+                // The copy bypasses the registers from Ampere onward.
+                let item = input.item();
+                f.write_str("#if __CUDA_ARCH__ >= 800\n")?;
+                for i in 0..*len {
+                    writeln!(
+                        f,
+                        "__pipeline_memcpy_async(&{out}[{out_index} + {i}], &{input}[{in_index} + {i}], sizeof({item}));"
+                    )?;
+                }
+                f.write_str("#else\n")?;
+                for i in 0..*len {
+                    writeln!(f, "{out}[{out_index} + {i}] = {input}[{in_index} + {i}];")?;
+                }
+                f.write_str("#endif\n")
+            }
+            Instruction::PipelineCommit => {
+                f.write_str("#if __CUDA_ARCH__ >= 800\n__pipeline_commit();\n#endif\n")
+            }
+            Instruction::PipelineWait { pending } => {
+                write!(
+                    f,
+                    "#if __CUDA_ARCH__ >= 800\n__pipeline_wait_prior({pending});\n#endif\n"
+                )
+            }
             Instruction::Assign(it) => Assign::format(f, &it.input, &it.out),
             Instruction::RangeLoop {
                 i,
@@ -328,7 +446,7 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
 
                 if vf > 1 {
-                    writeln!(f, "{out} = {item_out} {{")?;
+                    writeln!(f, "{out} = {} {{", item_out.compose())?;
                     for i in 0..vf {
                         let theni = then.index(i);
                         let or_elsei = or_else.index(i);
@@ -384,6 +502,16 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
             Instruction::LowerEqual(it) => LowerEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::GreaterEqual(it) => GreaterEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Erf(it) => Erf::format(f, &it.input, &it.out),
+            Instruction::Erfc(it) => Erfc::format(f, &it.input, &it.out),
+            Instruction::Expm1(it) => Expm1::format(f, &it.input, &it.out),
+            Instruction::Tgamma(it) => Tgamma::format(f, &it.input, &it.out),
+            Instruction::Lgamma(it) => Lgamma::format(f, &it.input, &it.out),
+            Instruction::FastExp(it) => FastExp::format(f, &it.input, &it.out),
+            Instruction::FastLog(it) => FastLog::format(f, &it.input, &it.out),
+            Instruction::FastCos(it) => FastCos::format(f, &it.input, &it.out),
+            Instruction::FastSin(it) => FastSin::format(f, &it.input, &it.out),
+            Instruction::FastPowf(it) => FastPowf::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::FastDiv(it) => FastDiv::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Abs(it) => Abs::format(f, &it.input, &it.out),
             Instruction::Exp(it) => Exp::format(f, &it.input, &it.out),
             Instruction::Log(it) => Log::format(f, &it.input, &it.out),
@@ -394,6 +522,11 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
             Instruction::Powf(it) => Powf::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Sqrt(it) => Sqrt::format(f, &it.input, &it.out),
             Instruction::Max(it) => Max::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::SaturatingAdd(it) => SaturatingAdd::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::SaturatingSub(it) => SaturatingSub::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::WrappingAdd(it) => WrappingAdd::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::WrappingSub(it) => WrappingSub::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::WrappingMul(it) => WrappingMul::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Min(it) => Min::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Not(it) => Not::format(f, &it.input, &it.out),
             Instruction::Or(it) => Or::format(f, &it.lhs, &it.rhs, &it.out),
@@ -404,6 +537,21 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                 max_value,
                 out,
             } => Clamp::format(f, input, min_value, max_value, out),
+            Instruction::RotateLeft(it) => RotateLeft::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::RotateRight(it) => RotateRight::format(f, &it.lhs, &it.rhs, &it.out),
+            Instruction::ExtractBits {
+                input,
+                offset,
+                count,
+                out,
+            } => Bitfield::format_extract(f, input, offset, count, out),
+            Instruction::InsertBits {
+                base,
+                insert,
+                offset,
+                count,
+                out,
+            } => Bitfield::format_insert(f, base, insert, offset, count, out),
             Instruction::SyncThreads => f.write_str("__syncthreads();\n"),
             Instruction::ThreadFence => f.write_str("__threadfence();\n"),
             Instruction::Round(it) => Round::format(f, &it.input, &it.out),
@@ -483,6 +631,49 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                     _ => panic!("Unsupported type for bitcasting"),
                 }
             }
+            Instruction::PackF16(UnaryInstruction { input, out }) => {
+                let (low, high) = (input.index(0), input.index(1));
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = uint(__half_as_ushort(__float2half_rn({low}))) | (uint(__half_as_ushort(__float2half_rn({high}))) << 16);"
+                )
+            }
+            Instruction::UnpackF16(UnaryInstruction { input, out }) => {
+                let item = out.item();
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = {}{{__half2float(__ushort_as_half(ushort({input} & 0xffff))), __half2float(__ushort_as_half(ushort({input} >> 16)))}};",
+                    item.compose()
+                )
+            }
+            Instruction::PackBf16(UnaryInstruction { input, out }) => {
+                // Round to nearest even by adding half of the dropped bits, with the parity of the
+                // kept bits breaking the ties. NaNs are kept quiet, since the rounding could carry
+                // them to infinity.
+                for i in 0..2 {
+                    let bits = format!("{out}_bf16_{i}");
+                    let input_i = input.index(i);
+                    writeln!(f, "uint {bits} = __float_as_uint({input_i});")?;
+                    writeln!(
+                        f,
+                        "{bits} = ({bits} & 0x7fffffff) > 0x7f800000 ? ({bits} >> 16) | 0x40 : ({bits} + 0x7fff + (({bits} >> 16) & 1)) >> 16;"
+                    )?;
+                }
+                let bits = format!("{out}_bf16");
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {bits}_0 | ({bits}_1 << 16);")
+            }
+            Instruction::UnpackBf16(UnaryInstruction { input, out }) => {
+                let item = out.item();
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = {}{{__uint_as_float({input} << 16), __uint_as_float({input} & 0xffff0000)}};",
+                    item.compose()
+                )
+            }
             Instruction::AtomicCAS {
                 input,
                 cmp,
@@ -547,7 +738,40 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
                     .map(|input| format!("{input}"))
                     .collect::<Vec<_>>();
                 let out = out.fmt_left();
-                writeln!(f, "{out} = {item}{{{}}};", inputs.join(","))
+                writeln!(f, "{out} = {}{{{}}};", item.compose(), inputs.join(","))
+            }
+            Instruction::Swizzle {
+                input,
+                components,
+                out,
+            } => {
+                let item = out.item();
+                let out = out.fmt_left();
+                match components.as_slice() {
+                    [component] => writeln!(f, "{out} = {};", input.index(*component as usize)),
+                    _ => {
+                        let inputs = components
+                            .iter()
+                            .map(|component| format!("{}", input.index(*component as usize)))
+                            .collect::<Vec<_>>();
+                        writeln!(f, "{out} = {}{{{}}};", item.compose(), inputs.join(","))
+                    }
+                }
+            }
+            Instruction::SwizzleAssign {
+                input,
+                components,
+                out,
+            } => {
+                for (i, component) in components.iter().enumerate() {
+                    writeln!(
+                        f,
+                        "{} = {};",
+                        out.index(*component as usize),
+                        input.index(i)
+                    )?;
+                }
+                Ok(())
             }
         }
     }
@@ -565,6 +789,14 @@ impl<D: Dialect> Fma<D> {
         c: &Variable<D>,
         out: &Variable<D>,
     ) -> core::fmt::Result {
+        if D::packed_math() {
+            let optimized = Variable::optimized_args([*a, *b, *c, *out]);
+            let [a, b, c, out_optimized] = optimized.args;
+            if out_optimized.elem() == Elem::F162 {
+                return Self::format_packed(f, &a, &b, &c, &out_optimized, out);
+            }
+        }
+
         let out_item = out.item();
         let num = out_item.vectorization;
 
@@ -572,7 +804,7 @@ impl<D: Dialect> Fma<D> {
         if num == 1 {
             writeln!(f, "{out} = fma({a}, {b}, {c});")
         } else {
-            writeln!(f, "{out} = {out_item}{{")?;
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
 
             for i in 0..num {
                 let ai = a.index(i);
@@ -584,6 +816,34 @@ impl<D: Dialect> Fma<D> {
             f.write_str("};\n")
         }
     }
+
+    /// Compute the pairs of the packed arguments with `__hfma2`, reinterpreting the result as the
+    /// original output item.
+    fn format_packed(
+        f: &mut core::fmt::Formatter<'_>,
+        a: &Variable<D>,
+        b: &Variable<D>,
+        c: &Variable<D>,
+        out_optimized: &Variable<D>,
+        out: &Variable<D>,
+    ) -> core::fmt::Result {
+        let item_optimized = out_optimized.item();
+        let out_tmp = Variable::tmp(item_optimized);
+
+        writeln!(f, "{} = {}{{", out_tmp.fmt_left(), item_optimized.compose())?;
+        for i in 0..item_optimized.vectorization {
+            let ai = a.index(i);
+            let bi = b.index(i);
+            let ci = c.index(i);
+
+            writeln!(f, "__hfma2({ai}, {bi}, {ci}),")?;
+        }
+        f.write_str("};\n")?;
+
+        let item_out = out.item();
+        let out = out.fmt_left();
+        writeln!(f, "{out} = reinterpret_cast<{item_out}&>({out_tmp});")
+    }
 }
 
 struct Clamp<D: Dialect> {
@@ -609,7 +869,7 @@ impl<D: Dialect> Clamp<D> {
         if num == 1 {
             writeln!(f, "{out} = max({min_value}, min({max_value}, {input}));")
         } else {
-            writeln!(f, "{out} = {out_item}{{")?;
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
             for i in 0..num {
                 let inputi = input.index(i);
                 let mini = min_value.index(i);
@@ -623,6 +883,100 @@ impl<D: Dialect> Clamp<D> {
     }
 }
 
+struct Bitfield<D: Dialect> {
+    dialect: PhantomData<D>,
+}
+
+impl<D: Dialect> Bitfield<D> {
+    /// Clamp the offset and count to the bit width like WGSL, declaring them in temporaries since
+    /// they are used by every element.
+    fn format_range(
+        f: &mut core::fmt::Formatter<'_>,
+        offset: &Variable<D>,
+        count: &Variable<D>,
+    ) -> Result<(Variable<D>, Variable<D>), core::fmt::Error> {
+        let offset_tmp = Variable::tmp(Item::scalar(Elem::U32));
+        let count_tmp = Variable::tmp(Item::scalar(Elem::U32));
+        writeln!(f, "{} = min(uint({offset}), 32u);", offset_tmp.fmt_left())?;
+        writeln!(
+            f,
+            "{} = min(uint({count}), 32u - {offset_tmp});",
+            count_tmp.fmt_left()
+        )?;
+        Ok((offset_tmp, count_tmp))
+    }
+
+    fn format_extract(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<D>,
+        offset: &Variable<D>,
+        count: &Variable<D>,
+        out: &Variable<D>,
+    ) -> core::fmt::Result {
+        let (offset, count) = Self::format_range(f, offset, count)?;
+        let out_item = out.item();
+        // Signed values are sign extended by shifting the field to the top bits first.
+        let extract = |input: &dyn Display| {
+            match out_item.elem {
+            Elem::I32 => format!(
+                "({count} == 0u ? 0 : int(uint({input}) << (32u - {offset} - {count})) >> (32u - {count}))"
+            ),
+            _ => format!(
+                "({count} == 0u ? 0u : ({input} >> {offset}) & (0xffffffffu >> (32u - {count})))"
+            ),
+        }
+        };
+
+        let num = out_item.vectorization;
+        let out = out.fmt_left();
+        if num == 1 {
+            writeln!(f, "{out} = {};", extract(input))
+        } else {
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
+            for i in 0..num {
+                writeln!(f, "{},", extract(&input.index(i)))?;
+            }
+            f.write_str("};\n")
+        }
+    }
+
+    fn format_insert(
+        f: &mut core::fmt::Formatter<'_>,
+        base: &Variable<D>,
+        insert: &Variable<D>,
+        offset: &Variable<D>,
+        count: &Variable<D>,
+        out: &Variable<D>,
+    ) -> core::fmt::Result {
+        let (offset, count) = Self::format_range(f, offset, count)?;
+        let mask = Variable::<D>::tmp(Item::scalar(Elem::U32));
+        writeln!(
+            f,
+            "{} = {count} == 0u ? 0u : (0xffffffffu >> (32u - {count})) << {offset};",
+            mask.fmt_left()
+        )?;
+        let out_item = out.item();
+        let elem = out_item.elem;
+        let insert_bits = |base: &dyn Display, insert: &dyn Display| {
+            format!(
+                "({count} == 0u ? {base} : {elem}((uint({base}) & ~{mask}) | ((uint({insert}) << {offset}) & {mask})))"
+            )
+        };
+
+        let num = out_item.vectorization;
+        let out = out.fmt_left();
+        if num == 1 {
+            writeln!(f, "{out} = {};", insert_bits(base, insert))
+        } else {
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
+            for i in 0..num {
+                writeln!(f, "{},", insert_bits(&base.index(i), &insert.index(i)))?;
+            }
+            f.write_str("};\n")
+        }
+    }
+}
+
 struct Remainder<D: Dialect> {
     dialect: PhantomData<D>,
 }
@@ -642,18 +996,30 @@ impl<D: Dialect> Remainder<D> {
 
         let out = out.fmt_left();
         if num == 1 {
-            writeln!(f, "{out} = {lhs} - {rhs} * floor({lhs} / {rhs});")
+            writeln!(f, "{out} = {};", Self::scalar(lhs, rhs, out_item.elem))
         } else {
-            writeln!(f, "{out} = {out_item}{{")?;
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
             for i in 0..num {
                 let lhsi = lhs.index(i);
                 let rhsi = rhs.index(i);
 
-                writeln!(f, "{lhsi} - {rhsi} * floor({lhsi} / {rhsi}),")?;
+                writeln!(f, "{},", Self::scalar(lhsi, rhsi, out_item.elem))?;
             }
             f.write_str("};\n")
         }
     }
+
+    /// The remainder of the floored division, with the sign of the divisor. Integers adjust the
+    /// truncated remainder of `%`, since `floor` of an integer division doesn't round it down.
+    fn scalar(lhs: impl Display, rhs: impl Display, elem: Elem<D>) -> String {
+        match elem {
+            Elem::I32 => format!(
+                "({lhs} % {rhs} != 0 && (({lhs} % {rhs} < 0) != ({rhs} < 0)) ? {lhs} % {rhs} + {rhs} : {lhs} % {rhs})"
+            ),
+            Elem::U32 => format!("{lhs} % {rhs}"),
+            _ => format!("{lhs} - {rhs} * floor({lhs} / {rhs})"),
+        }
+    }
 }
 
 struct Magnitude<D: Dialect> {
@@ -714,7 +1080,7 @@ impl<D: Dialect> Normalize<D> {
         if num == 1 {
             writeln!(f, "{out} = {input} / {norm};")
         } else {
-            write!(f, "{out} = {out_item}{{")?;
+            write!(f, "{out} = {}{{", out_item.compose())?;
             for i in 0..num {
                 let input_i = input.index(i);
 
@@ -760,7 +1126,7 @@ struct EnsureBoolArg<'a, V: Display, D: Dialect> {
 impl<'a, V: Display, D: Dialect> Display for EnsureBoolArg<'a, V, D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.elem != &Elem::Bool {
-            write!(f, "bool({})", self.var)
+            write!(f, "{}", Elem::<D>::Bool.cast(self.var))
         } else {
             write!(f, "{}", self.var)
         }
@@ -1,4 +1,4 @@
-use super::{Body, Dialect, Item, Variable};
+use super::{Body, Component, Dialect, Instruction, Item, Variable};
 use cubecl_core::{ir::CubeDim, CompilerRepresentation};
 use std::{collections::HashSet, fmt::Display};
 
@@ -13,6 +13,7 @@ pub struct SharedMemory<D: Dialect> {
     pub index: u16,
     pub item: Item<D>,
     pub size: u32,
+    pub dynamic: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -44,7 +45,64 @@ impl<D: Dialect> LocalArray<D> {
 
 impl<D: Dialect> SharedMemory<D> {
     pub fn new(index: u16, item: Item<D>, size: u32) -> Self {
-        Self { index, item, size }
+        Self {
+            index,
+            item,
+            size,
+            dynamic: false,
+        }
+    }
+
+    /// A shared memory sized at launch, which doesn't count in the static shared memory size.
+    pub fn new_dynamic(index: u16, item: Item<D>) -> Self {
+        Self {
+            index,
+            item,
+            size: 0,
+            dynamic: true,
+        }
+    }
+}
+
+/// A `__device__` function, whose parameters are copied into the locals used by its body.
+#[derive(Debug, Clone)]
+pub struct Function<D: Dialect> {
+    pub name: String,
+    pub inputs: Vec<Variable<D>>,
+    pub output: Option<Variable<D>>,
+    pub declarations: Vec<Instruction<D>>,
+    pub instructions: Vec<Instruction<D>>,
+}
+
+impl<D: Dialect> Display for Function<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.output {
+            Some(output) => write!(f, "__device__ {} ", output.item())?,
+            None => f.write_str("__device__ void ")?,
+        }
+        write!(f, "{}(", self.name)?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} p_{i}", input.item())?;
+        }
+        f.write_str(") {\n")?;
+
+        for declaration in self.declarations.iter() {
+            write!(f, "{declaration}")?;
+        }
+        for (i, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "{input} = p_{i};")?;
+        }
+        for instruction in self.instructions.iter() {
+            write!(f, "{instruction}")?;
+        }
+        if let Some(output) = &self.output {
+            writeln!(f, "return {output};")?;
+        }
+
+        f.write_str("}\n")
     }
 }
 
@@ -55,9 +113,11 @@ pub struct ComputeKernel<D: Dialect> {
     pub named: Vec<(String, Binding<D>)>,
     pub cube_dim: CubeDim,
     pub body: Body<D>,
+    pub functions: Vec<Function<D>>,
     pub wmma_activated: bool,
     pub bf16: bool,
     pub f16: bool,
+    pub pipeline: bool,
     pub items: HashSet<super::Item<D>>,
 }
 
@@ -88,17 +148,27 @@ impl<D: Dialect> Display for ComputeKernel<D> {
             D::include_f16(f)?;
         }
 
-        f.write_str("typedef unsigned int uint;\n")?;
+        if self.pipeline {
+            D::include_pipeline(f)?;
+        }
+
+        D::preamble(f)?;
 
         for item in self.items.iter() {
             let elem = item.elem;
             let size = item.vectorization;
             let alignment = elem.size() * size;
+            // Alignments must be powers of two, so odd sizes such as 3x3 matrices are only aligned
+            // to their elements.
+            let alignment = match alignment.is_power_of_two() {
+                true => alignment,
+                false => elem.size(),
+            };
             if size > 1 {
                 write!(
                     f,
                     "
-struct __align__({alignment}) {item} {{"
+typedef struct __align__({alignment}) {item} {{"
                 )?;
 
                 for i in 0..size {
@@ -109,40 +179,48 @@ struct __align__({alignment}) {item} {{"
                     )?;
                 }
 
-                f.write_str("\n};\n")?;
+                write!(f, "\n}} {item};\n")?;
             }
         }
 
-        write!(
-            f,
-            "
+        for function in self.functions.iter() {
+            write!(f, "\n{function}")?;
+        }
 
-extern \"C\" __global__ void kernel(
-",
-        )?;
+        f.write_str("\n\n")?;
+        D::kernel_declaration(f)?;
+        f.write_str("\n")?;
 
-        let num_bindings = self.inputs.len() + self.outputs.len() + self.named.len();
-        let mut binding_index = 0;
-        for (index, binding) in self.inputs.iter().enumerate() {
-            binding_index += 1;
-            write!(f, "{} input_{}[]", binding.item, index)?;
-            if binding_index < num_bindings {
-                f.write_str(",")?;
+        let mut first = true;
+        let mut separator = |f: &mut std::fmt::Formatter<'_>| match first {
+            true => {
+                first = false;
+                Ok(())
             }
+            false => f.write_str(","),
+        };
+        for (index, binding) in self.inputs.iter().enumerate() {
+            separator(f)?;
+            D::binding(f, &binding.item, &format!("input_{index}"))?;
         }
         for (index, binding) in self.outputs.iter().enumerate() {
-            binding_index += 1;
-            write!(f, "{} output_{}[]", binding.item, index)?;
-            if binding_index < num_bindings {
-                f.write_str(",")?;
-            }
+            separator(f)?;
+            D::binding(f, &binding.item, &format!("output_{index}"))?;
         }
         for (name, binding) in self.named.iter() {
-            binding_index += 1;
-            write!(f, "{} {}[]", binding.item, name)?;
-
-            if binding_index < num_bindings {
-                f.write_str(",")?;
+            separator(f)?;
+            D::binding(f, &binding.item, name)?;
+        }
+        for shared in self
+            .body
+            .shared_memories
+            .iter()
+            .filter(|shared| shared.dynamic)
+        {
+            let parameter = DynamicSharedMemoryBinding(shared).to_string();
+            if !parameter.is_empty() {
+                separator(f)?;
+                f.write_str(&parameter)?;
             }
         }
 
@@ -154,3 +232,11 @@ extern \"C\" __global__ void kernel(
         Ok(())
     }
 }
+
+struct DynamicSharedMemoryBinding<'a, D: Dialect>(&'a SharedMemory<D>);
+
+impl<D: Dialect> Display for DynamicSharedMemoryBinding<'_, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        D::dynamic_shared_memory_binding(f, self.0)
+    }
+}
@@ -77,18 +77,31 @@ impl Display for FragmentIdent {
     }
 }
 
+impl<D: Dialect> Fragment<D> {
+    /// Whether the fragment is a f32 input of the multiplication, which tensor cores execute
+    /// in TF32.
+    pub fn is_tf32(&self) -> bool {
+        self.ident != FragmentIdent::Accumulator && self.elem == Elem::F32
+    }
+}
+
 impl<D: Dialect> Display for Fragment<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let elem = match self.is_tf32() {
+            true => "nvcuda::wmma::precision::tf32".to_string(),
+            false => self.elem.to_string(),
+        };
+
         match self.layout {
             Some(layout) => write!(
                 f,
                 "nvcuda::wmma::fragment<{}, {}, {}, {}, {}, {}>",
-                self.ident, self.m, self.n, self.k, self.elem, layout
+                self.ident, self.m, self.n, self.k, elem, layout
             ),
             None => write!(
                 f,
                 "nvcuda::wmma::fragment<{}, {}, {}, {}, {}>",
-                self.ident, self.m, self.n, self.k, self.elem,
+                self.ident, self.m, self.n, self.k, elem,
             ),
         }
     }
@@ -105,10 +118,13 @@ impl<D: Dialect> Display for WmmaInstruction<D> {
                 value,
                 stride,
                 layout: None,
-            } => writeln!(
-                f,
-                "nvcuda::wmma::load_matrix_sync({frag}, {value}, {stride});"
-            ),
+            } => {
+                writeln!(
+                    f,
+                    "nvcuda::wmma::load_matrix_sync({frag}, {value}, {stride});"
+                )?;
+                round_tf32(f, frag)
+            }
             WmmaInstruction::Load {
                 frag,
                 value,
@@ -122,7 +138,8 @@ impl<D: Dialect> Display for WmmaInstruction<D> {
                 writeln!(
                     f,
                     "nvcuda::wmma::load_matrix_sync({frag}, {value}, {stride}, {layout});"
-                )
+                )?;
+                round_tf32(f, frag)
             }
             WmmaInstruction::Execute {
                 frag_a,
@@ -152,3 +169,15 @@ impl<D: Dialect> Display for WmmaInstruction<D> {
         }
     }
 }
+
+/// Round the f32 values loaded in a TF32 fragment, which would otherwise be truncated by the
+/// tensor cores.
+fn round_tf32<D: Dialect>(f: &mut std::fmt::Formatter<'_>, frag: &Variable<D>) -> std::fmt::Result {
+    match frag {
+        Variable::WmmaFragment { frag: fragment, .. } if fragment.is_tf32() => writeln!(
+            f,
+            "for (int t = 0; t < {frag}.num_elements; t++) {{ {frag}.x[t] = nvcuda::wmma::__float_to_tf32({frag}.x[t]); }}"
+        ),
+        _ => Ok(()),
+    }
+}
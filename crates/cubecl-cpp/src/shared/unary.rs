@@ -34,7 +34,7 @@ pub trait Unary<D: Dialect> {
         let mut write_op = |index, elem, input: &Variable<D>, out: &Variable<D>| {
             let out_item = out.item();
             let out = out.fmt_left();
-            writeln!(f, "{out} = {out_item}{{")?;
+            writeln!(f, "{out} = {}{{", out_item.compose())?;
 
             for i in 0..index {
                 let inputi = input.index(i);
@@ -157,7 +157,49 @@ function!(Round, "rint");
 
 function!(Tanh, "tanh", false);
 function!(Erf, "erf", false);
-function!(Abs, "abs", false);
+function!(Erfc, "erfc", false);
+function!(Expm1, "expm1", false);
+function!(Tgamma, "tgamma", false);
+function!(Lgamma, "lgamma", false);
+
+function!(FastExp, "__expf", false);
+function!(FastLog, "__logf", false);
+function!(FastCos, "__cosf", false);
+function!(FastSin, "__sinf", false);
+
+pub struct Abs;
+
+impl<D: Dialect> FunctionFmt<D> for Abs {
+    fn base_function_name() -> &'static str {
+        "abs"
+    }
+
+    // C only defines `abs` for integers, so floats use `fabs`.
+    fn function_name(elem: Elem<D>) -> String {
+        match elem {
+            Elem::I32 | Elem::U32 => "abs".into(),
+            _ => "fabs".into(),
+        }
+    }
+
+    fn half_support() -> bool {
+        false
+    }
+}
+
+impl<D: Dialect> Unary<D> for Abs {
+    fn format_scalar<Input: Display>(
+        f: &mut std::fmt::Formatter<'_>,
+        input: Input,
+        elem: Elem<D>,
+    ) -> std::fmt::Result {
+        Self::format_unary(f, input, elem)
+    }
+
+    fn can_optimize() -> bool {
+        false
+    }
+}
 
 pub struct Not;
 
@@ -203,7 +245,7 @@ impl<D: Dialect> Unary<D> for Assign {
     {
         // Cast only when necessary.
         if elem != input.elem() {
-            write!(f, "{elem}({input})")
+            write!(f, "{}", elem.cast(input))
         } else {
             write!(f, "{input}")
         }
@@ -1,8 +1,14 @@
+mod module_cache;
 mod server;
+mod staging;
 mod storage;
+mod virtual_memory;
 
+pub use module_cache::*;
 pub use server::*;
+pub use staging::*;
 pub use storage::*;
+pub use virtual_memory::*;
 
 #[allow(clippy::uninit_vec)]
 pub fn uninit_vec<I>(len: usize) -> Vec<I> {
@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+
+/// Configures where compiled kernels are looked up before compiling them with NVRTC.
+#[derive(Debug, Clone)]
+pub struct CompilationConfiguration {
+    /// The directory caching the PTX compiled by NVRTC across runs, `~/.cache/cubecl/cuda` by
+    /// default. Without a directory, every kernel is compiled on its first launch.
+    pub cache_dir: Option<PathBuf>,
+    /// A directory of cubins compiled ahead of time with nvcc, preferred over the PTX when they
+    /// exist.
+    ///
+    /// The CUDA source of each kernel is written to the cache directory next to its PTX, and the
+    /// cubin must have the same name, e.g.
+    /// `nvcc -cubin -arch=sm_89 <name>-<hash>-sm_89.cu -o <cubin_dir>/<name>-<hash>-sm_89.cubin`.
+    pub cubin_dir: Option<PathBuf>,
+}
+
+impl Default for CompilationConfiguration {
+    fn default() -> Self {
+        Self {
+            cache_dir: dirs::home_dir().map(|home| home.join(".cache").join("cubecl").join("cuda")),
+            cubin_dir: None,
+        }
+    }
+}
+
+/// The compiled kernels stored on disk, keyed by their [name](cubecl_core::KernelId::name), the
+/// hash of their source and the compute capability they were compiled for.
+#[derive(Debug)]
+pub struct ModuleCache {
+    config: CompilationConfiguration,
+}
+
+impl ModuleCache {
+    pub fn new(config: CompilationConfiguration) -> Self {
+        Self { config }
+    }
+
+    /// The key of the kernel `name` compiled from `source` for the compute capability `arch`.
+    ///
+    /// Only the hash identifies the kernel, the name keeps the cached files readable.
+    pub fn key(name: &str, source: &str, arch: u32) -> String {
+        format!("{name}-{:x}-sm_{arch}", md5::compute(source))
+    }
+
+    /// The cubin or the PTX of the kernel, when one was stored.
+    pub fn load(&self, key: &str) -> Option<Vec<u8>> {
+        let cubin = self
+            .config
+            .cubin_dir
+            .as_ref()
+            .and_then(|dir| std::fs::read(dir.join(format!("{key}.cubin"))).ok());
+
+        cubin.or_else(|| {
+            let dir = self.config.cache_dir.as_ref()?;
+            let mut ptx = std::fs::read(dir.join(format!("{key}.ptx"))).ok()?;
+            // The PTX is loaded as a null-terminated string.
+            if ptx.last() != Some(&0) {
+                ptx.push(0);
+            }
+            Some(ptx)
+        })
+    }
+
+    /// Store the PTX compiled by NVRTC, along with the source it was compiled from.
+    pub fn store(&self, key: &str, source: &str, ptx: &[u8]) {
+        let Some(dir) = &self.config.cache_dir else {
+            return;
+        };
+
+        let stored = std::fs::create_dir_all(dir)
+            .and_then(|_| write_atomically(&dir.join(format!("{key}.cu")), source.as_bytes()))
+            .and_then(|_| write_atomically(&dir.join(format!("{key}.ptx")), ptx));
+
+        // Failing to cache only costs a compilation on the next run.
+        if let Err(err) = stored {
+            log::warn!(
+                "Failed to cache the kernel {key} in {}: {err}",
+                dir.display()
+            );
+        }
+    }
+}
+
+/// Write the file under a temporary name before renaming it, so processes compiling the same
+/// kernel concurrently never read a partial file.
+fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".tmp{}", std::process::id()));
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> (ModuleCache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("cubecl-cuda-{name}-{}", std::process::id()));
+        let config = CompilationConfiguration {
+            cache_dir: Some(dir.join("ptx")),
+            cubin_dir: Some(dir.join("cubin")),
+        };
+
+        (ModuleCache::new(config), dir)
+    }
+
+    #[test]
+    fn stored_ptx_is_loaded_null_terminated() {
+        let (cache, dir) = cache("ptx");
+        let key = ModuleCache::key("copy", "source", 89);
+
+        assert_eq!(cache.load(&key), None);
+        cache.store(&key, "source", b"ptx");
+
+        assert_eq!(cache.load(&key), Some(b"ptx\0".to_vec()));
+        assert!(dir.join("ptx").join(format!("{key}.cu")).exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cubin_is_preferred_over_ptx() {
+        let (cache, dir) = cache("cubin");
+        let key = ModuleCache::key("copy", "source", 89);
+        cache.store(&key, "source", b"ptx");
+        std::fs::create_dir_all(dir.join("cubin")).unwrap();
+        std::fs::write(dir.join("cubin").join(format!("{key}.cubin")), b"cubin").unwrap();
+
+        assert_eq!(cache.load(&key), Some(b"cubin".to_vec()));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn key_depends_on_the_compute_capability() {
+        assert_ne!(
+            ModuleCache::key("copy", "source", 80),
+            ModuleCache::key("copy", "source", 89)
+        );
+    }
+}
@@ -1,22 +1,27 @@
 use cubecl_cpp::{formatter::format_cpp, CudaCompiler};
 
+use super::module_cache::ModuleCache;
+use super::staging::{CudaStaging, PinnedBuffer};
 use super::storage::CudaStorage;
-use super::{uninit_vec, CudaResource};
-use cubecl_core::compute::DebugInformation;
+use super::{uninit_vec, Binding, CudaResource};
+use cubecl_core::compute::{DebugInformation, KernelStatistics};
 use cubecl_core::ir::CubeDim;
 use cubecl_core::Feature;
-use cubecl_core::{prelude::*, KernelId};
+use cubecl_core::{prelude::*, CompilationOptions, KernelId};
 use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
-use cubecl_runtime::memory_management::MemoryUsage;
-use cubecl_runtime::storage::BindingResource;
+use cubecl_runtime::memory_management::{MemoryUsage, OutOfMemoryError};
+use cubecl_runtime::overrides::KernelOverrides;
+use cubecl_runtime::storage::{BindingResource, StorageId};
 use cubecl_runtime::{
     memory_management::MemoryManagement,
-    server::{self, ComputeServer},
+    server::{self, ComputeServer, DeviceLost},
 };
 use cubecl_runtime::{ExecutionMode, TimestampsError, TimestampsResult};
 use cudarc::driver::sys::CUctx_st;
 use cudarc::driver::sys::CUfunc_st;
-use std::collections::HashMap;
+use cudarc::driver::sys::CUmod_st;
+use cudarc::driver::sys::{CUevent, CUevent_flags, CUevent_wait_flags, CUstream};
+use std::collections::{HashMap, HashSet};
 use std::ffi::CStr;
 use std::ffi::CString;
 use std::future::Future;
@@ -27,18 +32,55 @@ use std::time::Instant;
 pub struct CudaServer {
     ctx: CudaContext,
     logger: DebugLogger,
+    recovery: Option<ContextRecovery>,
+}
+
+/// Creates the context again once its device is lost.
+struct ContextRecovery(Box<dyn Fn() -> Result<CudaContext, DeviceLost> + Send>);
+
+impl core::fmt::Debug for ContextRecovery {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ContextRecovery")
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct CudaContext {
     context: *mut CUctx_st,
-    stream: cudarc::driver::sys::CUstream,
+    /// The streams kernels are executed on, the first one being the default stream.
+    streams: Vec<CudaStream>,
+    /// The stream of the asynchronous uploads, running concurrently with the kernels.
+    transfer_stream: CUstream,
+    /// The events recorded since the last sync.
+    fences: HashMap<u64, CUevent>,
+    fence_count: u64,
+    /// The fence of the last upload.
+    upload_fence: Option<u64>,
+    /// The stream that last used each storage since the last sync.
+    used_storage: HashMap<StorageId, usize>,
+    staging: CudaStaging,
+    module_cache: ModuleCache,
     memory_management: MemoryManagement<CudaStorage>,
     module_names: HashMap<KernelId, CompiledKernel>,
+    /// The sources overridden during development.
+    overrides: Option<KernelOverrides>,
+    /// The buffers of the launches, reused so launching a compiled kernel doesn't allocate.
+    launch_storage: Vec<StorageId>,
+    launch_resources: Vec<CudaResource>,
+    launch_args: Vec<Binding>,
     timestamps: KernelTimestamps,
+    /// The error of the streams once the device is lost.
+    lost: Option<DeviceLost>,
     pub(crate) arch: u32,
 }
 
+#[derive(Debug)]
+struct CudaStream {
+    stream: CUstream,
+    /// The last upload fence the stream waited on.
+    upload_fence: Option<u64>,
+}
+
 #[derive(Debug)]
 enum KernelTimestamps {
     Inferred { start_time: Instant },
@@ -65,7 +107,34 @@ impl KernelTimestamps {
 struct CompiledKernel {
     cube_dim: CubeDim,
     shared_mem_bytes: usize,
+    module: *mut CUmod_st,
     func: *mut CUfunc_st,
+    /// The largest shared memory size the function was allowed to launch with, zero until a
+    /// launch needs more than the default.
+    max_shared_memory_size: u32,
+}
+
+/// The error of a kernel launch rejected by the driver.
+#[derive(Debug)]
+enum LaunchError {
+    /// The function can't use the requested amount of shared memory.
+    SharedMemory {
+        bytes: u32,
+        error: cudarc::driver::DriverError,
+    },
+    Driver(cudarc::driver::DriverError),
+}
+
+impl core::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LaunchError::SharedMemory { bytes, error } => write!(
+                f,
+                "The kernel can't use {bytes} bytes of shared memory: {error}"
+            ),
+            LaunchError::Driver(error) => write!(f, "The kernel launch failed: {error}"),
+        }
+    }
 }
 
 unsafe impl Send for CudaServer {}
@@ -77,51 +146,220 @@ impl CudaServer {
     }
 
     fn read_sync(&mut self, binding: server::Binding) -> Vec<u8> {
-        let ctx = self.get_context();
-        let resource = ctx.memory_management.get_resource(
-            binding.memory,
-            binding.offset_start,
-            binding.offset_end,
-        );
+        self.read_many_sync(vec![binding]).remove(0)
+    }
 
-        let mut data = uninit_vec(resource.size() as usize);
+    /// Copy every binding to the host before waiting once on the stream.
+    fn read_many_sync(&mut self, bindings: Vec<server::Binding>) -> Vec<Vec<u8>> {
+        let ctx = self.get_context();
+        let storage = bindings
+            .iter()
+            .map(|binding| ctx.memory_management.get(binding.memory.clone()).id)
+            .collect::<Vec<_>>();
+        ctx.wait_dependencies(0, &storage);
+        let stream = ctx.streams[0].stream;
 
-        unsafe {
-            cudarc::driver::result::memcpy_dtoh_async(&mut data, resource.ptr, ctx.stream).unwrap();
-        };
+        let staged = bindings
+            .into_iter()
+            .map(|binding| {
+                let resource = ctx.memory_management.get_resource(
+                    binding.memory,
+                    binding.offset_start,
+                    binding.offset_end,
+                );
+                let size = resource.size() as usize;
+
+                match ctx.staging.reserve(size) {
+                    Some(mut buffer) => {
+                        unsafe {
+                            cudarc::driver::result::memcpy_dtoh_async(
+                                buffer.slice(size),
+                                resource.ptr,
+                                stream,
+                            )
+                            .unwrap();
+                        };
+                        StagedRead::Pinned(buffer, size)
+                    }
+                    None => {
+                        let mut data = uninit_vec(size);
+                        unsafe {
+                            cudarc::driver::result::memcpy_dtoh_async(
+                                &mut data,
+                                resource.ptr,
+                                stream,
+                            )
+                            .unwrap();
+                        };
+                        StagedRead::Pageable(data)
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
 
         ctx.sync();
+        if let Some(lost) = &ctx.lost {
+            panic!("{lost}");
+        }
 
-        data
+        staged
+            .into_iter()
+            .map(|read| match read {
+                StagedRead::Pinned(mut buffer, size) => {
+                    let data = buffer.slice(size).to_vec();
+                    ctx.staging.release(buffer);
+                    data
+                }
+                StagedRead::Pageable(data) => data,
+            })
+            .collect()
     }
 }
 
+enum StagedRead {
+    Pinned(PinnedBuffer, usize),
+    Pageable(Vec<u8>),
+}
+
 impl ComputeServer for CudaServer {
     type Kernel = Box<dyn CubeTask<CudaCompiler>>;
     type Storage = CudaStorage;
     type Feature = Feature;
 
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.id().name()
+    }
+
+    fn device_lost(&mut self) -> Option<DeviceLost> {
+        self.ctx.lost.clone()
+    }
+
+    fn recover(&mut self) -> Result<(), DeviceLost> {
+        let Some(lost) = self.ctx.lost.clone() else {
+            return Ok(());
+        };
+        let Some(recovery) = &self.recovery else {
+            return Err(lost);
+        };
+
+        log::warn!("{lost}, resetting the device");
+        let ctx = (recovery.0)()?;
+        // Freeing the resources of the lost context fails, the reset of the device released them.
+        core::mem::forget(core::mem::replace(&mut self.ctx, ctx));
+        if self.logger.profile_level().is_some() {
+            self.ctx.timestamps.enable();
+        }
+        Ok(())
+    }
+
     fn read(&mut self, binding: server::Binding) -> impl Future<Output = Vec<u8>> + 'static {
         let value = self.read_sync(binding);
         async { value }
     }
 
+    fn read_many(
+        &mut self,
+        bindings: Vec<server::Binding>,
+    ) -> impl Future<Output = Vec<Vec<u8>>> + 'static {
+        let values = self.read_many_sync(bindings);
+        async { values }
+    }
+
+    /// Uploads are always done on the transfer stream, so the kernels of every stream are ordered
+    /// after them the same way.
     fn create(&mut self, data: &[u8]) -> server::Handle {
+        self.create_async(data).0
+    }
+
+    fn create_async(&mut self, data: &[u8]) -> (server::Handle, server::Fence) {
         let handle = self.empty(data.len());
+        let fence = self.get_context().upload(handle.clone().binding(), data);
+
+        (handle, fence)
+    }
+
+    fn binding_size(&mut self, binding: &server::Binding) -> u64 {
         let ctx = self.get_context();
+        let storage = ctx.memory_management.get(binding.memory.clone());
+        storage.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
 
-        let binding = handle.clone().binding();
-        let resource = ctx.memory_management.get_resource(
-            binding.memory,
-            binding.offset_start,
-            binding.offset_end,
-        );
+    fn write(&mut self, binding: server::Binding, data: &[u8]) {
+        // Kernels executed next wait on the upload fence.
+        self.get_context().upload(binding, data);
+    }
+
+    fn clear(&mut self, binding: server::Binding) {
+        // Kernels executed next wait on the fence of the clear.
+        let size = self.binding_size(&binding);
+        self.get_context().fill(binding, size, &[0]);
+    }
+
+    fn fill(&mut self, binding: server::Binding, pattern: &[u8]) {
+        // Kernels executed next wait on the fence of the fill.
+        let size = self.binding_size(&binding);
+        self.get_context().fill(binding, size, pattern);
+    }
+
+    fn wait_fence(&mut self, fence: server::Fence) -> impl Future<Output = ()> + 'static {
+        let ctx = self.get_context();
+        // Fences are only removed once synced, so a missing fence is already signaled.
+        if let Some(event) = ctx.fences.get(&fence.id) {
+            unsafe {
+                cudarc::driver::sys::lib()
+                    .cuEventSynchronize(*event)
+                    .result()
+                    .unwrap();
+            }
+        }
+        async {}
+    }
+
+    fn create_stream(&mut self) -> server::Stream {
+        let ctx = self.get_context();
+        let stream = cudarc::driver::result::stream::create(
+            cudarc::driver::result::stream::StreamKind::NonBlocking,
+        )
+        .unwrap();
+        ctx.push_stream(stream)
+    }
 
+    /// The priorities of the device range from the least one, given to the default stream and
+    /// to the background streams, to the greatest one, given to the interactive streams.
+    fn create_stream_with_priority(&mut self, priority: server::Priority) -> server::Stream {
+        let ctx = self.get_context();
+        let (mut least, mut greatest) = (0, 0);
+        let mut stream = core::ptr::null_mut();
         unsafe {
-            cudarc::driver::result::memcpy_htod_async(resource.ptr, data, ctx.stream).unwrap();
+            let lib = cudarc::driver::sys::lib();
+            lib.cuCtxGetStreamPriorityRange(&mut least, &mut greatest)
+                .result()
+                .unwrap();
+            let priority = match priority {
+                server::Priority::Interactive => greatest,
+                server::Priority::Normal | server::Priority::Background => least,
+            };
+            lib.cuStreamCreateWithPriority(
+                &mut stream,
+                cudarc::driver::sys::CUstream_flags::CU_STREAM_NON_BLOCKING as u32,
+                priority,
+            )
+            .result()
+            .unwrap();
         }
+        ctx.push_stream(stream)
+    }
 
-        handle
+    fn record_event(&mut self, stream: server::Stream) -> server::Fence {
+        let ctx = self.get_context();
+        let stream = ctx.streams[ctx.stream_index(stream)].stream;
+        ctx.record_event(stream)
+    }
+
+    fn wait_event(&mut self, stream: server::Stream, fence: server::Fence) {
+        let ctx = self.get_context();
+        let stream = ctx.streams[ctx.stream_index(stream)].stream;
+        ctx.wait_event(stream, fence);
     }
 
     fn empty(&mut self, size: usize) -> server::Handle {
@@ -130,15 +368,65 @@ impl ComputeServer for CudaServer {
         server::Handle::new(handle, None, None)
     }
 
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle, OutOfMemoryError> {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.try_reserve(size as u64, None)?;
+        Ok(server::Handle::new(handle, None, None))
+    }
+
+    /// Growable resources map physical memory in a reserved range of virtual addresses, so they
+    /// keep their address when growing. Other resources are copied once to a growable resource.
+    fn grow(&mut self, handle: server::Handle, size: usize) -> Option<server::Handle> {
+        let ctx = self.get_context();
+        let binding = handle.clone().binding();
+        if ctx
+            .memory_management
+            .grow(binding.memory.clone(), size as u64)
+        {
+            return Some(server::Handle::new(handle.memory, None, None));
+        }
+
+        let memory = ctx.memory_management.reserve_growable(size as u64)?;
+        let grown = server::Handle::new(memory, None, None);
+        let grown_binding = grown.clone().binding();
+        let storage = [
+            ctx.memory_management.get(binding.memory.clone()).id,
+            ctx.memory_management.get(grown_binding.memory.clone()).id,
+        ];
+        ctx.wait_dependencies(0, &storage);
+
+        let source = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        let destination = ctx
+            .memory_management
+            .get_resource(grown_binding.memory, None, None);
+        unsafe {
+            cudarc::driver::result::memcpy_dtod_async(
+                destination.ptr,
+                source.ptr,
+                Ord::min(source.size(), size as u64) as usize,
+                ctx.streams[0].stream,
+            )
+            .unwrap();
+        }
+
+        Some(grown)
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
         count: CubeCount,
         bindings: Vec<server::Binding>,
         mode: ExecutionMode,
+        stream: server::Stream,
     ) {
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
+        let dynamic_shared_memory = kernel.dynamic_shared_memory();
 
         let profile_level = self.logger.profile_level();
         let profile_info = if profile_level.is_some() {
@@ -165,36 +453,42 @@ impl ComputeServer for CudaServer {
 
         let (ctx, logger) = self.get_context_with_logger();
 
+        ctx.reload_overrides();
         if !ctx.module_names.contains_key(&kernel_id) {
             ctx.compile_kernel(&kernel_id, kernel, logger, mode);
         }
 
-        let resources = bindings
-            .into_iter()
-            .map(|binding| {
-                ctx.memory_management.get_resource(
-                    binding.memory,
-                    binding.offset_start,
-                    binding.offset_end,
-                )
-            })
-            .collect::<Vec<_>>();
+        let stream = ctx.stream_index(stream);
+        let mut storage = core::mem::take(&mut ctx.launch_storage);
+        storage.extend(
+            bindings
+                .iter()
+                .map(|binding| ctx.memory_management.get(binding.memory.clone()).id),
+        );
+        ctx.wait_dependencies(stream, &storage);
+        storage.clear();
+        ctx.launch_storage = storage;
+
+        let mut resources = core::mem::take(&mut ctx.launch_resources);
+        resources.extend(bindings.into_iter().map(|binding| {
+            ctx.memory_management.get_resource(
+                binding.memory,
+                binding.offset_start,
+                binding.offset_end,
+            )
+        }));
 
         if let Some(level) = profile_level {
             ctx.sync();
             let start = std::time::SystemTime::now();
-            ctx.execute_task(kernel_id, count, resources);
+            let result =
+                ctx.execute_task(kernel_id, count, dynamic_shared_memory, &resources, stream);
             ctx.sync();
+            result.unwrap_or_else(|err| panic!("{err}"));
 
             let (name, kernel_id) = profile_info.unwrap();
             let info = match level {
-                ProfileLevel::Basic | ProfileLevel::Medium => {
-                    if let Some(val) = name.split("<").next() {
-                        val.split("::").last().unwrap_or(name).to_string()
-                    } else {
-                        name.to_string()
-                    }
-                }
+                ProfileLevel::Basic | ProfileLevel::Medium => kernel_id.name(),
                 ProfileLevel::Full => {
                     format!("{name}: {kernel_id} CubeCount {count:?}")
                 }
@@ -203,8 +497,27 @@ impl ComputeServer for CudaServer {
             self.logger
                 .register_profiled(info, start.elapsed().unwrap());
         } else {
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, dynamic_shared_memory, &resources, stream)
+                .unwrap_or_else(|err| panic!("{err}"));
         }
+
+        let ctx = self.get_context();
+        resources.clear();
+        ctx.launch_resources = resources;
+    }
+
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let kernel_id = kernel.id();
+        let ctx = self.get_context();
+        // The kernels in flight may still use the modules.
+        ctx.sync();
+        ctx.module_names.retain(|id, compiled| {
+            if !id.is_variant_of(&kernel_id) {
+                return true;
+            }
+            unsafe { cudarc::driver::result::module::unload(compiled.module).unwrap() };
+            false
+        });
     }
 
     fn flush(&mut self) {}
@@ -263,9 +576,22 @@ impl ComputeServer for CudaServer {
 }
 
 impl CudaContext {
+    /// Add a stream kernels are executed on.
+    fn push_stream(&mut self, stream: CUstream) -> server::Stream {
+        self.streams.push(CudaStream {
+            stream,
+            upload_fence: None,
+        });
+
+        server::Stream::new(self.streams.len() as u64 - 1)
+    }
+
     pub fn new(
         memory_management: MemoryManagement<CudaStorage>,
-        stream: cudarc::driver::sys::CUstream,
+        stream: CUstream,
+        transfer_stream: CUstream,
+        staging: CudaStaging,
+        module_cache: ModuleCache,
         context: *mut CUctx_st,
         arch: u32,
     ) -> Self {
@@ -273,16 +599,276 @@ impl CudaContext {
             context,
             memory_management,
             module_names: HashMap::new(),
-            stream,
+            overrides: KernelOverrides::from_env("cu"),
+            launch_storage: Vec::new(),
+            launch_resources: Vec::new(),
+            launch_args: Vec::new(),
+            streams: vec![CudaStream {
+                stream,
+                upload_fence: None,
+            }],
+            transfer_stream,
+            fences: HashMap::new(),
+            fence_count: 0,
+            upload_fence: None,
+            used_storage: HashMap::new(),
+            staging,
+            module_cache,
             arch,
             timestamps: KernelTimestamps::Disabled,
+            lost: None,
         }
     }
 
+    /// Unload the modules of the kernels whose override changed, so they're compiled again.
+    fn reload_overrides(&mut self) {
+        let Some(overrides) = &mut self.overrides else {
+            return;
+        };
+        let changed = overrides.changed();
+        if changed.is_empty() {
+            return;
+        }
+
+        // The kernels in flight may still use the modules.
+        self.sync();
+        self.module_names.retain(|id, compiled| {
+            if !changed.contains(&id.name()) {
+                return true;
+            }
+            unsafe { cudarc::driver::result::module::unload(compiled.module).unwrap() };
+            false
+        });
+    }
+
+    /// Wait for every stream, recording the error of the device when it is lost.
     fn sync(&mut self) {
+        if let Err(lost) = self.try_sync() {
+            log::error!("{lost}");
+            self.lost = Some(lost);
+        }
+    }
+
+    fn try_sync(&mut self) -> Result<(), DeviceLost> {
+        let lost = |err: cudarc::driver::DriverError| DeviceLost {
+            reason: err.to_string(),
+        };
         unsafe {
-            cudarc::driver::result::stream::synchronize(self.stream).unwrap();
+            for stream in self.streams.iter_mut() {
+                cudarc::driver::result::stream::synchronize(stream.stream).map_err(lost)?;
+                stream.upload_fence = None;
+            }
+            cudarc::driver::result::stream::synchronize(self.transfer_stream).map_err(lost)?;
+            self.staging.release_completed(|_| true);
+
+            for (_, event) in self.fences.drain() {
+                cudarc::driver::result::event::destroy(event).unwrap();
+            }
         };
+        self.upload_fence = None;
+        self.used_storage.clear();
+
+        // Nothing uses the memory anymore, so the unused allocations can be released.
+        self.memory_management.cleanup();
+        self.memory_management.storage().perform_deallocations();
+        Ok(())
+    }
+
+    fn stream_index(&self, stream: server::Stream) -> usize {
+        let index = stream.id as usize;
+        assert!(index < self.streams.len(), "Unknown CUDA stream {index}");
+        index
+    }
+
+    /// Record a fence signaled once the work submitted to the stream so far is completed.
+    fn record_event(&mut self, stream: CUstream) -> server::Fence {
+        let event =
+            cudarc::driver::result::event::create(CUevent_flags::CU_EVENT_DISABLE_TIMING).unwrap();
+        unsafe {
+            cudarc::driver::result::event::record(event, stream).unwrap();
+        }
+
+        let id = self.fence_count;
+        self.fence_count += 1;
+        self.fences.insert(id, event);
+        server::Fence::new(id)
+    }
+
+    /// Record a fence signaled once the uploads submitted to the transfer stream are completed.
+    fn record_fence(&mut self) -> server::Fence {
+        let fence = self.record_event(self.transfer_stream);
+        self.upload_fence = Some(fence.id);
+        fence
+    }
+
+    /// Copy `data` at the start of the binding on the transfer stream, returning the fence
+    /// signaled once the copy is completed.
+    fn upload(&mut self, binding: server::Binding, data: &[u8]) -> server::Fence {
+        let storage = self.memory_management.get(binding.memory.clone());
+        // The memory can be reused from a resource the submitted kernels still work on.
+        if let Some(index) = self.used_storage.get(&storage.id) {
+            let stream = self.streams[*index].stream;
+            self.wait_stream(self.transfer_stream, stream);
+        }
+        let resource = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+
+        self.release_staging();
+        match self.staging.reserve(data.len()) {
+            Some(mut buffer) => {
+                let staging = buffer.slice(data.len());
+                staging.copy_from_slice(data);
+                unsafe {
+                    cudarc::driver::result::memcpy_htod_async(
+                        resource.ptr,
+                        staging,
+                        self.transfer_stream,
+                    )
+                    .unwrap();
+                }
+
+                let fence = self.record_fence();
+                self.staging.release_after(fence.id, buffer);
+                fence
+            }
+            None => {
+                unsafe {
+                    cudarc::driver::result::memcpy_htod_async(
+                        resource.ptr,
+                        data,
+                        self.transfer_stream,
+                    )
+                    .unwrap();
+                }
+
+                self.record_fence()
+            }
+        }
+    }
+
+    /// Repeat the pattern over the bytes of the binding on the transfer stream, like an
+    /// [upload](Self::upload), returning the fence signaled once they are filled.
+    ///
+    /// Patterns of 1, 2 or 4 bytes repeated a whole number of times are set with a memset, the
+    /// others are uploaded.
+    fn fill(&mut self, binding: server::Binding, size: u64, pattern: &[u8]) -> server::Fence {
+        let width = pattern.len() as u64;
+        if !matches!(width, 1 | 2 | 4) || size % width != 0 {
+            let data = pattern
+                .iter()
+                .copied()
+                .cycle()
+                .take(size as usize)
+                .collect::<Vec<_>>();
+            return self.upload(binding, &data);
+        }
+
+        let storage = self.memory_management.get(binding.memory.clone());
+        if let Some(index) = self.used_storage.get(&storage.id) {
+            let stream = self.streams[*index].stream;
+            self.wait_stream(self.transfer_stream, stream);
+        }
+        let resource = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+
+        let count = (size / width) as usize;
+        unsafe {
+            let lib = cudarc::driver::sys::lib();
+            match *pattern {
+                [byte] => lib.cuMemsetD8Async(resource.ptr, byte, count, self.transfer_stream),
+                [a, b] => lib.cuMemsetD16Async(
+                    resource.ptr,
+                    u16::from_ne_bytes([a, b]),
+                    count,
+                    self.transfer_stream,
+                ),
+                [a, b, c, d] => lib.cuMemsetD32Async(
+                    resource.ptr,
+                    u32::from_ne_bytes([a, b, c, d]),
+                    count,
+                    self.transfer_stream,
+                ),
+                _ => unreachable!(),
+            }
+            .result()
+            .unwrap();
+        }
+
+        self.record_fence()
+    }
+
+    /// Make the staging buffers of the completed uploads available again.
+    fn release_staging(&mut self) {
+        let fences = &self.fences;
+        self.staging
+            .release_completed(|fence| match fences.get(&fence) {
+                Some(event) => unsafe {
+                    cudarc::driver::sys::lib().cuEventQuery(*event)
+                        == cudarc::driver::sys::CUresult::CUDA_SUCCESS
+                },
+                // Fences are only removed once synced.
+                None => true,
+            });
+    }
+
+    /// Make the work submitted next to the stream wait on the `fence`.
+    fn wait_event(&self, stream: CUstream, fence: server::Fence) {
+        // Fences are only removed once synced, so a missing fence is already signaled.
+        if let Some(event) = self.fences.get(&fence.id) {
+            unsafe {
+                cudarc::driver::result::stream::wait_event(
+                    stream,
+                    *event,
+                    CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    /// Make the work submitted next to the `waiting` stream wait on the work submitted to
+    /// `stream` so far.
+    fn wait_stream(&self, waiting: CUstream, stream: CUstream) {
+        let event =
+            cudarc::driver::result::event::create(CUevent_flags::CU_EVENT_DISABLE_TIMING).unwrap();
+        unsafe {
+            cudarc::driver::result::event::record(event, stream).unwrap();
+            cudarc::driver::result::stream::wait_event(
+                waiting,
+                event,
+                CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+            .unwrap();
+            // The event is released once completed.
+            cudarc::driver::result::event::destroy(event).unwrap();
+        }
+    }
+
+    /// Make the work submitted next to the stream at `index` wait on the uploads, and on the other
+    /// streams that used the `storage` since the last sync.
+    fn wait_dependencies(&mut self, index: usize, storage: &[StorageId]) {
+        let stream = self.streams[index].stream;
+        if let Some(fence) = self.upload_fence {
+            if self.streams[index].upload_fence != Some(fence) {
+                self.wait_event(stream, server::Fence::new(fence));
+                self.streams[index].upload_fence = Some(fence);
+            }
+        }
+
+        let others = storage
+            .iter()
+            .filter_map(|id| self.used_storage.insert(*id, index))
+            .filter(|other| *other != index)
+            .collect::<HashSet<_>>();
+        for other in others {
+            self.wait_stream(stream, self.streams[other].stream);
+        }
     }
 
     fn compile_kernel(
@@ -292,10 +878,25 @@ impl CudaContext {
         logger: &mut DebugLogger,
         mode: ExecutionMode,
     ) {
-        let mut kernel_compiled = kernel.compile(mode);
+        let _span = cubecl_runtime::trace_span!("pipeline", kernel = %kernel_id.name());
+        let mut kernel_compiled = kernel.compile(&CompilationOptions::default(), mode);
+        if let Some(source) = self
+            .overrides
+            .as_mut()
+            .and_then(|overrides| overrides.source(&kernel_id.name(), &kernel_compiled.source))
+        {
+            kernel_compiled.source = source;
+        }
+        // The key is computed before formatting, so logging doesn't change it.
+        let key = ModuleCache::key(&kernel_id.name(), &kernel_compiled.source, self.arch);
+        let source = kernel_compiled.source.clone();
 
         if logger.is_activated() {
-            kernel_compiled.debug_info = Some(DebugInformation::new("cpp", kernel_id.clone()));
+            kernel_compiled.debug_info = Some(DebugInformation::new(
+                "cpp",
+                kernel_id.clone(),
+                kernel.cost(),
+            ));
 
             if let Ok(formatted) = format_cpp(&kernel_compiled.source) {
                 kernel_compiled.source = formatted;
@@ -304,16 +905,57 @@ impl CudaContext {
 
         let shared_mem_bytes = kernel_compiled.shared_mem_bytes;
         let cube_dim = kernel_compiled.cube_dim;
+
+        let image = match self.module_cache.load(&key) {
+            Some(image) => image,
+            None => {
+                let ptx = self.compile_ptx(&kernel_compiled);
+                self.module_cache.store(&key, &source, &ptx);
+                ptx
+            }
+        };
+
+        let func_name = CString::new("kernel".to_string()).unwrap();
+        let (module, func) = unsafe {
+            let module =
+                cudarc::driver::result::module::load_data(image.as_ptr() as *const _).unwrap();
+            let func = cudarc::driver::result::module::get_function(module, func_name).unwrap();
+            (module, func)
+        };
+
+        // The resource usage is only known once the module is loaded, so the kernel is logged
+        // afterward.
+        if let Some(info) = &mut kernel_compiled.debug_info {
+            info.statistics = Some(kernel_statistics(func, cube_dim));
+        }
+        logger.debug(kernel_compiled);
+
+        self.module_names.insert(
+            kernel_id.clone(),
+            CompiledKernel {
+                cube_dim,
+                shared_mem_bytes,
+                module,
+                func,
+                max_shared_memory_size: 0,
+            },
+        );
+    }
+
+    /// Compile the kernel to PTX with NVRTC, returning the null-terminated PTX.
+    fn compile_ptx(
+        &self,
+        kernel_compiled: &cubecl_core::compute::CompiledKernel<CudaCompiler>,
+    ) -> Vec<u8> {
         let arch = format!("--gpu-architecture=sm_{}", self.arch);
 
         let include_path = include_path();
         let include_option = format!("--include-path={}", include_path.to_str().unwrap());
         let options = &[arch.as_str(), include_option.as_str()];
 
-        let kernel_compiled = logger.debug(kernel_compiled);
-
         let ptx = unsafe {
-            let program = cudarc::nvrtc::result::create_program(kernel_compiled.source).unwrap();
+            let program =
+                cudarc::nvrtc::result::create_program(kernel_compiled.source.clone()).unwrap();
             if cudarc::nvrtc::result::compile_program(program, options).is_err() {
                 let log_raw = cudarc::nvrtc::result::get_program_log(program).unwrap();
                 let log_ptr = log_raw.as_ptr();
@@ -322,55 +964,69 @@ impl CudaContext {
                 for line in log.split('\n') {
                     if !line.is_empty() {
                         message += format!("\n    {line}").as_str();
+                        let location =
+                            error_line(line).and_then(|line| kernel_compiled.source_location(line));
+                        if let Some(location) = location {
+                            message += format!(" (at {location})").as_str();
+                        }
                     }
                 }
-                let source = kernel.compile(mode).source;
+                let source = &kernel_compiled.source;
                 panic!("{message}\n[Source]  \n{source}");
             };
             cudarc::nvrtc::result::get_ptx(program).unwrap()
         };
 
-        let func_name = CString::new("kernel".to_string()).unwrap();
-        let func = unsafe {
-            let module =
-                cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _).unwrap();
-            cudarc::driver::result::module::get_function(module, func_name).unwrap()
-        };
-
-        self.module_names.insert(
-            kernel_id.clone(),
-            CompiledKernel {
-                cube_dim,
-                shared_mem_bytes,
-                func,
-            },
-        );
+        ptx.into_iter().map(|c| c as u8).collect()
     }
 
     fn execute_task(
         &mut self,
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
-        resources: Vec<CudaResource>,
-    ) {
-        let mut bindings = resources
-            .iter()
-            .map(|memory| memory.as_binding())
-            .collect::<Vec<_>>();
-
-        let kernel = self.module_names.get(&kernel_id).unwrap();
+        dynamic_shared_memory: u32,
+        resources: &[CudaResource],
+        stream: usize,
+    ) -> Result<(), LaunchError> {
+        let kernel = self.module_names.get_mut(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
-        unsafe {
+        let shared_memory_size = kernel.shared_mem_bytes as u32 + dynamic_shared_memory;
+
+        // Required to use more than 48KB of shared memory. The attribute only has to be raised
+        // once for the largest size the function launches with.
+        if dynamic_shared_memory > 0 && shared_memory_size > kernel.max_shared_memory_size {
+            unsafe {
+                cudarc::driver::result::function::set_function_attribute(
+                    kernel.func,
+                    cudarc::driver::sys::CUfunction_attribute::CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES,
+                    shared_memory_size as i32,
+                )
+            }
+            .map_err(|error| LaunchError::SharedMemory {
+                bytes: shared_memory_size,
+                error,
+            })?;
+            kernel.max_shared_memory_size = shared_memory_size;
+        }
+
+        let mut bindings = core::mem::take(&mut self.launch_args);
+        bindings.extend(resources.iter().map(|memory| memory.as_binding()));
+
+        let result = unsafe {
             cudarc::driver::result::launch_kernel(
                 kernel.func,
                 dispatch_count,
                 (cube_dim.x, cube_dim.y, cube_dim.z),
-                kernel.shared_mem_bytes as u32,
-                self.stream,
+                shared_memory_size,
+                self.streams[stream].stream,
                 &mut bindings,
             )
-            .unwrap();
         };
+
+        bindings.clear();
+        self.launch_args = bindings;
+
+        result.map_err(LaunchError::Driver)
     }
 
     fn memory_usage(&self) -> MemoryUsage {
@@ -385,7 +1041,21 @@ impl CudaServer {
         if logger.profile_level().is_some() {
             ctx.timestamps.enable();
         }
-        Self { ctx, logger }
+        Self {
+            ctx,
+            logger,
+            recovery: None,
+        }
+    }
+
+    /// Let the server [recover](ComputeServer::recover) from the loss of its device with the
+    /// context created by `recovery`.
+    pub(crate) fn with_recovery(
+        mut self,
+        recovery: impl Fn() -> Result<CudaContext, DeviceLost> + Send + 'static,
+    ) -> Self {
+        self.recovery = Some(ContextRecovery(Box::new(recovery)));
+        self
     }
 
     fn get_context(&mut self) -> &mut CudaContext {
@@ -393,6 +1063,10 @@ impl CudaServer {
     }
 
     fn get_context_with_logger(&mut self) -> (&mut CudaContext, &mut DebugLogger) {
+        // The operations of a lost device fail, so they stop here with the error of the loss.
+        if let Some(lost) = &self.ctx.lost {
+            panic!("{lost}");
+        }
         unsafe {
             cudarc::driver::result::ctx::set_current(self.ctx.context).unwrap();
         };
@@ -400,6 +1074,57 @@ impl CudaServer {
     }
 }
 
+/// The line of the source targeted by an NVRTC log line, formatted as `program(line): ...`.
+/// Query the resources used by the loaded kernel and the occupancy they allow for its cube dim.
+fn kernel_statistics(func: *mut CUfunc_st, cube_dim: CubeDim) -> KernelStatistics {
+    use cudarc::driver::sys::{lib, CUdevice_attribute, CUfunction_attribute};
+
+    unsafe {
+        let func_attribute = |attribute| {
+            let mut value = 0;
+            lib()
+                .cuFuncGetAttribute(&mut value, attribute, func)
+                .result()
+                .unwrap();
+            value
+        };
+        let registers = func_attribute(CUfunction_attribute::CU_FUNC_ATTRIBUTE_NUM_REGS);
+        let static_shared_memory =
+            func_attribute(CUfunction_attribute::CU_FUNC_ATTRIBUTE_SHARED_SIZE_BYTES);
+
+        let mut device = 0;
+        lib().cuCtxGetDevice(&mut device).result().unwrap();
+        let device_attribute = |attribute| {
+            cudarc::driver::result::device::get_attribute(device, attribute).unwrap() as u32
+        };
+        let warp_size = device_attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_WARP_SIZE);
+        let max_units = device_attribute(
+            CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_MULTIPROCESSOR,
+        );
+
+        let units_per_cube = cube_dim.num_elems();
+        let active_cubes = cudarc::driver::result::occupancy::max_active_block_per_multiprocessor(
+            func,
+            units_per_cube as i32,
+            0,
+        )
+        .unwrap() as u32;
+        let active_warps = active_cubes * units_per_cube.div_ceil(warp_size);
+
+        KernelStatistics::new(
+            registers as u32,
+            static_shared_memory as usize,
+            active_warps as f32 / (max_units / warp_size) as f32,
+        )
+    }
+}
+
+fn error_line(log: &str) -> Option<usize> {
+    let (_, rest) = log.split_once('(')?;
+    let (line, _) = rest.split_once(')')?;
+    line.parse().ok()
+}
+
 fn include_path() -> PathBuf {
     let mut path = cuda_path().expect("
         CUDA installation not found.
@@ -0,0 +1,97 @@
+use cubecl_runtime::memory_management::StagingConfiguration;
+
+/// Host memory staging the transfers between the host and the device.
+#[derive(Debug)]
+pub struct CudaStaging {
+    config: StagingConfiguration,
+    free: Vec<PinnedBuffer>,
+    /// The buffers used by uploads, with the fence signaled once the upload is completed.
+    in_flight: Vec<(u64, PinnedBuffer)>,
+}
+
+/// A page-locked host buffer, which the device can access directly.
+#[derive(Debug)]
+pub struct PinnedBuffer {
+    ptr: *mut u8,
+    size: usize,
+}
+
+impl PinnedBuffer {
+    fn new(size: usize) -> Self {
+        let mut ptr = core::ptr::null_mut();
+        unsafe {
+            cudarc::driver::sys::lib()
+                .cuMemHostAlloc(&mut ptr, size, 0)
+                .result()
+                .expect("Failed to allocate pinned host memory");
+        }
+
+        Self {
+            ptr: ptr as *mut u8,
+            size,
+        }
+    }
+
+    /// The first `size` bytes of the buffer.
+    pub fn slice(&mut self, size: usize) -> &mut [u8] {
+        assert!(size <= self.size);
+        unsafe { core::slice::from_raw_parts_mut(self.ptr, size) }
+    }
+}
+
+impl Drop for PinnedBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            cudarc::driver::sys::lib()
+                .cuMemFreeHost(self.ptr as *mut _)
+                .result()
+                .unwrap();
+        }
+    }
+}
+
+impl CudaStaging {
+    pub fn new(config: StagingConfiguration) -> Self {
+        Self {
+            config,
+            free: Vec::new(),
+            in_flight: Vec::new(),
+        }
+    }
+
+    /// Get a pinned buffer of at least `size` bytes, or `None` when transfers go through pageable
+    /// memory.
+    pub fn reserve(&mut self, size: usize) -> Option<PinnedBuffer> {
+        let StagingConfiguration::Pinned { chunk_size } = self.config else {
+            return None;
+        };
+
+        match self.free.iter().position(|buffer| buffer.size >= size) {
+            Some(index) => Some(self.free.swap_remove(index)),
+            None => Some(PinnedBuffer::new(Ord::max(size, chunk_size as usize))),
+        }
+    }
+
+    /// Make the buffer available for the next transfers.
+    pub fn release(&mut self, buffer: PinnedBuffer) {
+        self.free.push(buffer);
+    }
+
+    /// Make the buffer available once the upload signaling the `fence` is completed.
+    pub fn release_after(&mut self, fence: u64, buffer: PinnedBuffer) {
+        self.in_flight.push((fence, buffer));
+    }
+
+    /// Release the buffers of the uploads for which `completed` returns true.
+    pub fn release_completed(&mut self, completed: impl Fn(u64) -> bool) {
+        let mut index = 0;
+        while index < self.in_flight.len() {
+            if completed(self.in_flight[index].0) {
+                let (_, buffer) = self.in_flight.swap_remove(index);
+                self.free.push(buffer);
+            } else {
+                index += 1;
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use cubecl_runtime::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
-use cudarc::driver::sys::CUstream;
+use cudarc::driver::{
+    sys::{CUdevice, CUdeviceptr, CUmemAttach_flags, CUmem_advise, CUresult, CUstream},
+    DriverError,
+};
 use std::collections::HashMap;
 
-use super::uninit_vec;
+use super::{uninit_vec, VirtualMemory};
 
 /// Buffer storage for cuda.
 pub struct CudaStorage {
@@ -10,6 +13,20 @@ pub struct CudaStorage {
     deallocations: Vec<StorageId>,
     stream: cudarc::driver::sys::CUstream,
     ptr_bindings: PtrBindings,
+    /// The device and the size of the address range reserved by growable allocations, when the
+    /// device supports virtual memory management.
+    virtual_memory: Option<(CUdevice, u64)>,
+    growable: HashMap<StorageId, VirtualMemory>,
+    managed: Option<ManagedMemory>,
+}
+
+/// Allocations in unified memory, migrated by the driver between the host and the device.
+#[derive(Clone, Copy)]
+struct ManagedMemory {
+    device: CUdevice,
+    /// Whether the device accesses managed memory concurrently with the host, which is required
+    /// to prefetch it.
+    prefetch: bool,
 }
 
 struct PtrBindings {
@@ -60,19 +77,69 @@ impl CudaStorage {
             deallocations: Vec::new(),
             stream,
             ptr_bindings: PtrBindings::new(),
+            virtual_memory: None,
+            growable: HashMap::new(),
+            managed: None,
         }
     }
 
+    /// Allocate the buffers in managed memory, so their total size can exceed the memory of the
+    /// device.
+    ///
+    /// The pages are migrated on demand between the host and the device, which is preferred as
+    /// their location. When `prefetch` is enabled, the bound ranges are also migrated to the device
+    /// ahead of the kernels using them, avoiding page faults while they run.
+    pub fn with_managed_memory(mut self, device: CUdevice, prefetch: bool) -> Self {
+        self.managed = Some(ManagedMemory { device, prefetch });
+        self
+    }
+
+    /// Support growable allocations on the device, each reserving `reserved` bytes of virtual
+    /// addresses to grow into.
+    pub fn with_virtual_memory(mut self, device: CUdevice, reserved: u64) -> Self {
+        self.virtual_memory = Some((device, reserved));
+        self
+    }
+
     /// Actually deallocates buffers tagged to be deallocated.
+    ///
+    /// Growable and managed allocations are released right away, so the work using them has to
+    /// be completed.
     pub fn perform_deallocations(&mut self) {
         for id in self.deallocations.drain(..) {
             if let Some(ptr) = self.memory.remove(&id) {
+                if self.growable.remove(&id).is_some() {
+                    continue;
+                }
                 unsafe {
-                    cudarc::driver::result::free_async(ptr, self.stream).unwrap();
+                    match self.managed {
+                        Some(_) => cudarc::driver::result::free_sync(ptr).unwrap(),
+                        None => cudarc::driver::result::free_async(ptr, self.stream).unwrap(),
+                    }
                 }
             }
         }
     }
+
+    unsafe fn malloc(&self, size: u64) -> Result<CUdeviceptr, DriverError> {
+        let Some(managed) = self.managed else {
+            return cudarc::driver::result::malloc_async(self.stream, size as usize);
+        };
+        let ptr = cudarc::driver::result::malloc_managed(
+            size as usize,
+            CUmemAttach_flags::CU_MEM_ATTACH_GLOBAL,
+        )?;
+        // Keep the pages on the device until its memory is oversubscribed.
+        cudarc::driver::sys::lib()
+            .cuMemAdvise(
+                ptr,
+                size as usize,
+                CUmem_advise::CU_MEM_ADVISE_SET_PREFERRED_LOCATION,
+                managed.device,
+            )
+            .result()?;
+        Ok(ptr)
+    }
 }
 
 /// The memory resource that can be allocated for wgpu.
@@ -118,6 +185,21 @@ impl ComputeStorage for CudaStorage {
 
         let offset = handle.offset();
         let size = handle.size();
+        if let Some(ManagedMemory {
+            device,
+            prefetch: true,
+        }) = self.managed
+        {
+            // Only a hint, the pages are still migrated on demand when it fails.
+            unsafe {
+                cudarc::driver::sys::lib().cuMemPrefetchAsync(
+                    ptr + offset,
+                    size as usize,
+                    device,
+                    self.stream,
+                );
+            }
+        }
         let ptr = self.ptr_bindings.register(ptr + offset);
 
         CudaResource::new(
@@ -129,14 +211,60 @@ impl ComputeStorage for CudaStorage {
     }
 
     fn alloc(&mut self, size: u64) -> StorageHandle {
+        self.try_alloc(size)
+            .unwrap_or_else(|| panic!("Out of memory when allocating {size} bytes"))
+    }
+
+    /// The pending deallocations are performed before failing, so their memory can be reused.
+    fn try_alloc(&mut self, size: u64) -> Option<StorageHandle> {
+        let ptr = match unsafe { self.malloc(size) } {
+            Ok(ptr) => ptr,
+            Err(DriverError(CUresult::CUDA_ERROR_OUT_OF_MEMORY)) => {
+                if self.deallocations.is_empty() {
+                    return None;
+                }
+                // Growable allocations are unmapped right away, so the device has to be done
+                // with them.
+                unsafe {
+                    cudarc::driver::sys::lib()
+                        .cuCtxSynchronize()
+                        .result()
+                        .unwrap();
+                }
+                self.perform_deallocations();
+                return self.try_alloc(size);
+            }
+            Err(err) => panic!("Failed to allocate {size} bytes: {err:?}"),
+        };
+
         let id = StorageId::new();
-        let ptr =
-            unsafe { cudarc::driver::result::malloc_async(self.stream, size as usize).unwrap() };
         self.memory.insert(id, ptr);
-        StorageHandle::new(id, StorageUtilization { offset: 0, size })
+        Some(StorageHandle::new(
+            id,
+            StorageUtilization { offset: 0, size },
+        ))
     }
 
     fn dealloc(&mut self, id: StorageId) {
         self.deallocations.push(id);
     }
+
+    fn alloc_growable(&mut self, size: u64) -> Option<StorageHandle> {
+        let (device, reserved) = self.virtual_memory?;
+        let id = StorageId::new();
+        let mut memory = VirtualMemory::new(device, Ord::max(reserved, size));
+        memory.grow(size);
+        self.memory.insert(id, memory.ptr());
+        self.growable.insert(id, memory);
+        Some(StorageHandle::new(
+            id,
+            StorageUtilization { offset: 0, size },
+        ))
+    }
+
+    fn grow(&mut self, id: StorageId, size: u64) -> bool {
+        self.growable
+            .get_mut(&id)
+            .is_some_and(|memory| memory.grow(size))
+    }
 }
@@ -0,0 +1,137 @@
+use cudarc::driver::sys::{
+    CUdevice, CUdeviceptr, CUmemAccessDesc, CUmemAccess_flags, CUmemAllocationGranularity_flags,
+    CUmemAllocationHandleType, CUmemAllocationProp, CUmemAllocationType,
+    CUmemGenericAllocationHandle, CUmemLocation, CUmemLocationType,
+};
+
+/// Device memory growing in place, by mapping physical memory at the end of a reserved range of
+/// virtual addresses.
+///
+/// The address never changes, so growing doesn't need to reallocate and copy the content.
+#[derive(Debug)]
+pub struct VirtualMemory {
+    ptr: CUdeviceptr,
+    reserved: usize,
+    granularity: usize,
+    device: CUdevice,
+    /// The physical allocations, mapped one after the other from the start of the range.
+    chunks: Vec<(CUmemGenericAllocationHandle, usize)>,
+    mapped: usize,
+}
+
+impl VirtualMemory {
+    /// Reserve `reserved` bytes of virtual addresses on the device, without mapping any physical
+    /// memory yet.
+    pub fn new(device: CUdevice, reserved: u64) -> Self {
+        let granularity = unsafe {
+            let mut granularity = 0;
+            cudarc::driver::sys::lib()
+                .cuMemGetAllocationGranularity(
+                    &mut granularity,
+                    &allocation_prop(device),
+                    CUmemAllocationGranularity_flags::CU_MEM_ALLOC_GRANULARITY_MINIMUM,
+                )
+                .result()
+                .expect("Virtual memory management should be supported by the device");
+            granularity
+        };
+        let reserved = (reserved as usize).div_ceil(granularity) * granularity;
+
+        let mut ptr = 0;
+        unsafe {
+            cudarc::driver::sys::lib()
+                .cuMemAddressReserve(&mut ptr, reserved, granularity, 0, 0)
+                .result()
+                .expect("Failed to reserve virtual addresses");
+        }
+
+        Self {
+            ptr,
+            reserved,
+            granularity,
+            device,
+            chunks: Vec::new(),
+            mapped: 0,
+        }
+    }
+
+    /// The address of the memory.
+    pub fn ptr(&self) -> CUdeviceptr {
+        self.ptr
+    }
+
+    /// Map physical memory at the end of the accessible range until it covers `size` bytes.
+    ///
+    /// Returns false when `size` exceeds the reserved range of addresses.
+    pub fn grow(&mut self, size: u64) -> bool {
+        let size = size as usize;
+        if size <= self.mapped {
+            return true;
+        }
+        if size > self.reserved {
+            return false;
+        }
+
+        let chunk_size = (size - self.mapped).div_ceil(self.granularity) * self.granularity;
+        let ptr = self.ptr + self.mapped as u64;
+        let access = CUmemAccessDesc {
+            location: device_location(self.device),
+            flags: CUmemAccess_flags::CU_MEM_ACCESS_FLAGS_PROT_READWRITE,
+        };
+
+        unsafe {
+            let lib = cudarc::driver::sys::lib();
+            let mut handle = 0;
+            lib.cuMemCreate(&mut handle, chunk_size, &allocation_prop(self.device), 0)
+                .result()
+                .expect("Failed to allocate physical memory");
+            lib.cuMemMap(ptr, chunk_size, 0, handle, 0)
+                .result()
+                .unwrap();
+            lib.cuMemSetAccess(ptr, chunk_size, &access, 1)
+                .result()
+                .unwrap();
+            self.chunks.push((handle, chunk_size));
+        }
+        self.mapped += chunk_size;
+
+        true
+    }
+}
+
+/// The work using the memory has to be completed before it is dropped.
+impl Drop for VirtualMemory {
+    fn drop(&mut self) {
+        let mut offset = 0;
+        unsafe {
+            let lib = cudarc::driver::sys::lib();
+            for (handle, size) in self.chunks.drain(..) {
+                lib.cuMemUnmap(self.ptr + offset as u64, size)
+                    .result()
+                    .unwrap();
+                lib.cuMemRelease(handle).result().unwrap();
+                offset += size;
+            }
+            lib.cuMemAddressFree(self.ptr, self.reserved)
+                .result()
+                .unwrap();
+        }
+    }
+}
+
+fn device_location(device: CUdevice) -> CUmemLocation {
+    CUmemLocation {
+        type_: CUmemLocationType::CU_MEM_LOCATION_TYPE_DEVICE,
+        id: device,
+    }
+}
+
+fn allocation_prop(device: CUdevice) -> CUmemAllocationProp {
+    CUmemAllocationProp {
+        type_: CUmemAllocationType::CU_MEM_ALLOCATION_TYPE_PINNED,
+        requestedHandleTypes: CUmemAllocationHandleType::CU_MEM_HANDLE_TYPE_NONE,
+        location: device_location(device),
+        win32HandleMetaData: core::ptr::null_mut(),
+        allocFlags: Default::default(),
+    }
+}
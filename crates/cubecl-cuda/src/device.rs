@@ -8,3 +8,24 @@ impl core::fmt::Debug for CudaDevice {
         write!(f, "Cuda({})", self.index)
     }
 }
+
+impl CudaDevice {
+    /// The number of CUDA devices on the machine, zero when the driver isn't installed.
+    ///
+    /// Unlike creating a client, this never panics, so it can be used to check whether CUDA is
+    /// available before selecting a runtime.
+    pub fn count() -> usize {
+        // The driver is loaded lazily by cudarc, which panics when the library is missing.
+        let loadable = ["cuda", "nvcuda"].into_iter().any(|name| unsafe {
+            libloading::Library::new(libloading::library_filename(name)).is_ok()
+        });
+        if !loadable {
+            return 0;
+        }
+
+        match cudarc::driver::result::init() {
+            Ok(()) => cudarc::driver::result::device::get_count().unwrap_or(0) as usize,
+            Err(_) => 0,
+        }
+    }
+}
@@ -14,5 +14,8 @@ mod tests {
     pub type TestRuntime = crate::CudaRuntime;
 
     cubecl_core::testgen_all!();
+    cubecl_fft::testgen_all!();
+    cubecl_image::testgen_all!();
     cubecl_linalg::testgen_all!();
+    cubecl_std::testgen_all!();
 }
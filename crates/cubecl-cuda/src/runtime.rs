@@ -2,27 +2,40 @@ use std::mem::MaybeUninit;
 
 use cubecl_core::{
     ir::{Elem, FloatKind},
-    Feature, MemoryConfiguration, Runtime,
+    Feature, MemoryConfiguration, Runtime, StagingConfiguration,
 };
 use cubecl_runtime::{
     channel::MutexComputeChannel,
     client::ComputeClient,
     memory_management::{MemoryDeviceProperties, MemoryManagement},
+    server::DeviceLost,
     storage::ComputeStorage,
-    ComputeRuntime, DeviceProperties,
+    ComputeRuntime, DeviceProperties, HardwareProperties,
 };
 
 use crate::{
-    compute::{CudaContext, CudaServer, CudaStorage},
+    compute::{
+        CompilationConfiguration, CudaContext, CudaServer, CudaStaging, CudaStorage, ModuleCache,
+    },
     device::CudaDevice,
 };
 use cubecl_cpp::{register_supported_types, CudaCompiler};
 
 /// The values that control how a WGPU Runtime will perform its calculations.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// Configures the host memory staging the transfers.
+    pub staging_config: StagingConfiguration,
+    /// Configures where compiled kernels are cached and looked up.
+    pub compilation_config: CompilationConfiguration,
+    /// Allocates the buffers in unified memory, so they can exceed the memory of the device.
+    ///
+    /// The driver then evicts the pages of the least recently used buffers to the host instead of
+    /// failing with an out of memory error, at the cost of migrating them back when they are used.
+    /// Growable allocations aren't supported in this mode.
+    pub managed_memory: bool,
 }
 
 #[derive(Debug)]
@@ -35,6 +48,40 @@ static RUNTIME: ComputeRuntime<CudaDevice, Server, Channel> = ComputeRuntime::ne
 
 fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<Server, Channel> {
     // To get the supported WMMA features, and memory properties, we have to initialize the server immediately.
+    let (cuda_ctx, mem_properties, hardware_props) = create_context(device, &options);
+    let device = device.clone();
+    let mut server = CudaServer::new(cuda_ctx).with_recovery(move || {
+        // The primary context keeps the error of the lost device until it is reset.
+        unsafe {
+            let device_ptr =
+                cudarc::driver::result::device::get(device.index as i32).map_err(|err| {
+                    DeviceLost {
+                        reason: err.to_string(),
+                    }
+                })?;
+            cudarc::driver::sys::lib()
+                .cuDevicePrimaryCtxReset_v2(device_ptr)
+                .result()
+                .map_err(|err| DeviceLost {
+                    reason: err.to_string(),
+                })?;
+        }
+        Ok(create_context(&device, &options).0)
+    });
+    let mut device_props =
+        DeviceProperties::new(&[Feature::Subcube], mem_properties, hardware_props);
+    register_supported_types(&mut device_props);
+    register_wmma_features(&mut device_props, server.arch_version());
+    register_arch_features(&mut device_props, server.arch_version());
+
+    ComputeClient::new(MutexComputeChannel::new(server), device_props)
+}
+
+/// Create the context of the device along with its memory and hardware properties.
+fn create_context(
+    device: &CudaDevice,
+    options: &RuntimeOptions,
+) -> (CudaContext, MemoryDeviceProperties, HardwareProperties) {
     cudarc::driver::result::init().unwrap();
     let device_ptr = cudarc::driver::result::device::get(device.index as i32).unwrap();
     let arch = unsafe {
@@ -61,12 +108,82 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
         cudarc::driver::result::stream::StreamKind::NonBlocking,
     )
     .unwrap();
+    let transfer_stream = cudarc::driver::result::stream::create(
+        cudarc::driver::result::stream::StreamKind::NonBlocking,
+    )
+    .unwrap();
     let max_memory = unsafe {
         let mut bytes = MaybeUninit::uninit();
         cudarc::driver::sys::lib().cuDeviceTotalMem_v2(bytes.as_mut_ptr(), device_ptr);
         bytes.assume_init() as u64
     };
-    let storage = CudaStorage::new(stream);
+    let hardware_props = unsafe {
+        use cudarc::driver::{result::device::get_attribute, sys::CUdevice_attribute};
+
+        let attribute = |attribute| get_attribute(device_ptr, attribute).unwrap() as u32;
+
+        HardwareProperties {
+            // Kernels declare their shared memory statically, which can't exceed the default
+            // limit of a block: only dynamic shared memory can opt in to the larger one.
+            max_shared_memory_size: attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_SHARED_MEMORY_PER_BLOCK,
+            ) as usize,
+            max_cube_dim: (
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_X),
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_Y),
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_BLOCK_DIM_Z),
+            ),
+            max_units_per_cube: attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_BLOCK,
+            ),
+            max_cube_count: (
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_X),
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_Y),
+                attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_GRID_DIM_Z),
+            ),
+            // Kernel parameters are limited to 4KB, and each binding is passed as a pointer.
+            max_bindings: (4096 / core::mem::size_of::<u64>()) as u32,
+            // Allocations are only bounded by the memory of the device, but kernels index their
+            // bindings with 32-bit integers.
+            max_buffer_size: u32::MAX as u64,
+            subcube_size: Some(attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_WARP_SIZE)),
+            num_multiprocessors: Some(attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT,
+            )),
+            max_units_per_multiprocessor: Some(attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_THREADS_PER_MULTIPROCESSOR,
+            )),
+            max_cubes_per_multiprocessor: Some(attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_BLOCKS_PER_MULTIPROCESSOR,
+            )),
+            registers_per_multiprocessor: Some(attribute(
+                CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MAX_REGISTERS_PER_MULTIPROCESSOR,
+            )),
+        }
+    };
+    let virtual_memory = unsafe {
+        cudarc::driver::result::device::get_attribute(
+            device_ptr,
+            cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_VIRTUAL_MEMORY_MANAGEMENT_SUPPORTED,
+        )
+        .unwrap()
+    } != 0;
+    let storage = if options.managed_memory {
+        let concurrent_access = unsafe {
+            cudarc::driver::result::device::get_attribute(
+                device_ptr,
+                cudarc::driver::sys::CUdevice_attribute::CU_DEVICE_ATTRIBUTE_CONCURRENT_MANAGED_ACCESS,
+            )
+            .unwrap()
+        } != 0;
+        CudaStorage::new(stream).with_managed_memory(device_ptr, concurrent_access)
+    } else if virtual_memory {
+        // Addresses are plentiful, so every growable allocation can reserve enough of them to
+        // grow to the size of the device memory.
+        CudaStorage::new(stream).with_virtual_memory(device_ptr, max_memory)
+    } else {
+        CudaStorage::new(stream)
+    };
     let mem_properties = MemoryDeviceProperties {
         max_page_size: max_memory / 4,
         alignment: CudaStorage::ALIGNMENT,
@@ -75,15 +192,19 @@ fn create_client(device: &CudaDevice, options: RuntimeOptions) -> ComputeClient<
     let memory_management = MemoryManagement::from_configuration(
         storage,
         mem_properties.clone(),
-        options.memory_config,
+        options.memory_config.clone(),
+    );
+    let cuda_ctx = CudaContext::new(
+        memory_management,
+        stream,
+        transfer_stream,
+        CudaStaging::new(options.staging_config),
+        ModuleCache::new(options.compilation_config.clone()),
+        ctx,
+        arch,
     );
-    let cuda_ctx = CudaContext::new(memory_management, stream, ctx, arch);
-    let mut server = CudaServer::new(cuda_ctx);
-    let mut device_props = DeviceProperties::new(&[Feature::Subcube], mem_properties);
-    register_supported_types(&mut device_props);
-    register_wmma_features(&mut device_props, server.arch_version());
 
-    ComputeClient::new(MutexComputeChannel::new(server), device_props)
+    (cuda_ctx, mem_properties, hardware_props)
 }
 
 impl Runtime for CudaRuntime {
@@ -108,7 +229,15 @@ impl Runtime for CudaRuntime {
     }
 
     fn supported_line_sizes() -> &'static [u8] {
-        &[8, 4, 2]
+        &[16, 8, 4, 2]
+    }
+}
+
+/// Register the features emitted by the compiler depending on the compute capability.
+fn register_arch_features(properties: &mut DeviceProperties<Feature>, arch: u32) {
+    // The bulk copies from global to shared memory use `cp.async` from Ampere onward.
+    if arch >= 80 {
+        properties.register_feature(Feature::AsyncCopy);
     }
 }
 
@@ -121,8 +250,10 @@ fn register_wmma_features(properties: &mut DeviceProperties<Feature>, arch: u32)
     }
 
     if wmma {
+        properties.register_feature(Feature::CmmaWarpSize(32));
+
         // Types fully supported.
-        for (a, b, c) in [
+        let mut types = vec![
             (
                 Elem::Float(FloatKind::F16),
                 Elem::Float(FloatKind::F16),
@@ -133,12 +264,17 @@ fn register_wmma_features(properties: &mut DeviceProperties<Feature>, arch: u32)
                 Elem::Float(FloatKind::F16),
                 Elem::Float(FloatKind::F32),
             ),
-            (
+        ];
+        // The bf16 fragments need Ampere.
+        if arch >= 80 {
+            types.push((
                 Elem::Float(FloatKind::BF16),
                 Elem::Float(FloatKind::BF16),
                 Elem::Float(FloatKind::F32),
-            ),
-        ] {
+            ));
+        }
+
+        for (a, b, c) in types {
             properties.register_feature(Feature::Cmma {
                 a,
                 b,
@@ -164,5 +300,17 @@ fn register_wmma_features(properties: &mut DeviceProperties<Feature>, arch: u32)
                 n: 32,
             });
         }
+
+        // The f32 fragments are executed in TF32, which needs Ampere and a k of 8.
+        if arch >= 80 {
+            properties.register_feature(Feature::Cmma {
+                a: Elem::Float(FloatKind::F32),
+                b: Elem::Float(FloatKind::F32),
+                c: Elem::Float(FloatKind::F32),
+                m: 16,
+                k: 8,
+                n: 16,
+            });
+        }
     }
 }
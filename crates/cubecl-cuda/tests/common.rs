@@ -3,7 +3,7 @@ use std::{io::Write, process::Command};
 use cubecl_core::{
     client::ComputeClient,
     prelude::{ArrayArg, TensorArg},
-    server, Compiler, ExecutionMode, Kernel, Runtime,
+    server, CompilationOptions, Compiler, ExecutionMode, Kernel, Runtime,
 };
 use cubecl_cuda::{CudaDevice, CudaRuntime};
 
@@ -38,6 +38,7 @@ pub fn array(tensor: &Handle) -> ArrayArg<'_, CudaRuntime> {
 pub fn compile(kernel: impl Kernel) -> String {
     let kernel = <<CudaRuntime as Runtime>::Compiler as Compiler>::compile(
         kernel.define(),
+        &CompilationOptions::default(),
         ExecutionMode::Checked,
     )
     .to_string();
@@ -1,8 +1,12 @@
 use common::*;
 use cubecl_core as cubecl;
-use cubecl_core::{prelude::*, CubeCount, CubeDim};
+use cubecl_core::ir::Item;
+use cubecl_core::{prelude::*, CompilationOptions, Compiler, CubeCount, CubeDim, ExecutionMode};
+use cubecl_cpp::CudaCompiler;
 use cubecl_cuda::CudaRuntime;
+use half::f16;
 use pretty_assertions::assert_eq;
+use std::num::NonZero;
 
 mod common;
 
@@ -142,3 +146,47 @@ pub fn constant_array() {
     let expected = include_str!("constant_array.cu").replace("\r\n", "\n");
     assert_eq!(compile(kernel), expected);
 }
+
+#[cube]
+fn packed_half_kernel(lhs: &Array<Line<f16>>, rhs: &Array<Line<f16>>, out: &mut Array<Line<f16>>) {
+    out[0] = lhs[0] + rhs[0];
+    out[1] = lhs[1] * rhs[1];
+    out[2] = lhs[2] - rhs[2];
+    out[3] = lhs[3] / rhs[3];
+}
+
+fn compile_packed_half(vectorization: u8) -> String {
+    let mut builder = KernelBuilder::default();
+    let item = Item::vectorized(f16::as_elem(), NonZero::new(vectorization));
+    let lhs = builder.input_array(item);
+    let rhs = builder.input_array(item);
+    let out = builder.output_array(item);
+    packed_half_kernel::expand(&mut builder.context, lhs.into(), rhs.into(), out.into());
+
+    let definition = builder.build(KernelSettings::default());
+    <CudaCompiler as Compiler>::compile(
+        definition,
+        &CompilationOptions::default(),
+        ExecutionMode::Checked,
+    )
+    .to_string()
+}
+
+#[test]
+pub fn packed_half_components() {
+    for vectorization in [2, 4, 8] {
+        let kernel = compile_packed_half(vectorization);
+        let components = kernel.matches("__hadd2(").count();
+
+        assert_eq!(components, vectorization as usize / 2);
+    }
+}
+
+#[test]
+pub fn packed_half_intrinsics() {
+    let kernel = compile_packed_half(2);
+
+    for intrinsic in ["__hadd2(", "__hmul2(", "__hsub2(", "__h2div("] {
+        assert_eq!(kernel.matches(intrinsic).count(), 1, "{intrinsic}");
+    }
+}
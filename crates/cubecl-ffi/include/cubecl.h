@@ -0,0 +1,85 @@
+/* C declarations of the cubecl-ffi library. */
+
+#ifndef CUBECL_H
+#define CUBECL_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum CubeclStatus {
+    CUBECL_OK = 0,
+    CUBECL_NULL_POINTER = 1,
+    CUBECL_UNSUPPORTED_RUNTIME = 2,
+    CUBECL_UNKNOWN_KERNEL = 3,
+    CUBECL_INVALID_ARGUMENT = 4,
+    CUBECL_SIZE_MISMATCH = 5,
+    CUBECL_PANIC = 6,
+    CUBECL_OUT_OF_BOUNDS = 7,
+} CubeclStatus;
+
+typedef enum CubeclRuntime {
+    CUBECL_RUNTIME_WGPU = 0,
+    CUBECL_RUNTIME_CUDA = 1,
+} CubeclRuntime;
+
+typedef enum CubeclArgKind {
+    CUBECL_ARG_ARRAY = 0,
+    CUBECL_ARG_U32 = 1,
+    CUBECL_ARG_I32 = 2,
+    CUBECL_ARG_F32 = 3,
+} CubeclArgKind;
+
+typedef struct CubeclClient CubeclClient;
+typedef struct CubeclBuffer CubeclBuffer;
+
+typedef struct CubeclDim {
+    uint32_t x;
+    uint32_t y;
+    uint32_t z;
+} CubeclDim;
+
+typedef union CubeclScalar {
+    uint32_t u32;
+    int32_t i32;
+    float f32;
+} CubeclScalar;
+
+typedef struct CubeclArg {
+    CubeclArgKind kind;
+    /* The buffer, number of elements and elements per line of an array. */
+    const CubeclBuffer *buffer;
+    size_t len;
+    uint8_t line_size;
+    /* The value of a scalar. */
+    CubeclScalar scalar;
+} CubeclArg;
+
+/* Clients, the default device of the runtime when `device` is negative. */
+CubeclStatus cubecl_client_new(CubeclRuntime runtime, int32_t device, CubeclClient **client);
+void cubecl_client_free(CubeclClient *client);
+
+/* Buffers, in bytes. */
+CubeclStatus cubecl_buffer_create(const CubeclClient *client, const uint8_t *data, size_t size,
+                                  CubeclBuffer **buffer);
+CubeclStatus cubecl_buffer_empty(const CubeclClient *client, size_t size, CubeclBuffer **buffer);
+CubeclStatus cubecl_buffer_read(const CubeclClient *client, const CubeclBuffer *buffer,
+                                uint8_t *data, size_t size);
+void cubecl_buffer_free(CubeclBuffer *buffer);
+
+/* Kernels, registered by name in Rust. */
+CubeclStatus cubecl_launch(const CubeclClient *client, const char *name, CubeclDim cube_count,
+                           CubeclDim cube_dim, const CubeclArg *args, size_t num_args);
+CubeclStatus cubecl_sync(const CubeclClient *client);
+
+/* The message of the last failing call of the thread, or NULL. */
+const char *cubecl_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CUBECL_H */
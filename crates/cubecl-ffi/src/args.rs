@@ -0,0 +1,218 @@
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::{CubeclBuffer, FfiError};
+
+/// Three dimensions, of a cube count or a cube dim.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CubeclDim {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+/// The kind of a kernel argument.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeclArgKind {
+    /// An array stored in a buffer.
+    Array = 0,
+    /// A `u32` scalar.
+    U32 = 1,
+    /// An `i32` scalar.
+    I32 = 2,
+    /// An `f32` scalar.
+    F32 = 3,
+}
+
+/// The value of a scalar argument, read according to the [kind](CubeclArgKind) of the argument.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union CubeclScalar {
+    pub u32: u32,
+    pub i32: i32,
+    pub f32: f32,
+}
+
+/// An argument of a kernel launched over the C ABI.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CubeclArg {
+    pub kind: CubeclArgKind,
+    /// The buffer of an array, ignored for scalars.
+    pub buffer: *const CubeclBuffer,
+    /// The number of elements of an array, ignored for scalars.
+    pub len: usize,
+    /// The number of elements per line of an array, ignored for scalars.
+    pub line_size: u8,
+    /// The value of a scalar, ignored for arrays.
+    pub scalar: CubeclScalar,
+}
+
+/// An argument of a kernel, read from its [C representation](CubeclArg).
+#[derive(Debug, Clone, Copy)]
+pub enum Arg<'a> {
+    Array {
+        handle: &'a Handle,
+        /// The size of the buffer in bytes.
+        size: usize,
+        len: usize,
+        line_size: u8,
+    },
+    U32(u32),
+    I32(i32),
+    F32(f32),
+}
+
+/// The launch parameters given to a [registered kernel](crate::register_kernel).
+#[derive(Debug)]
+pub struct LaunchArgs<'a> {
+    cube_count: CubeclDim,
+    cube_dim: CubeclDim,
+    args: Vec<Arg<'a>>,
+}
+
+impl<'a> LaunchArgs<'a> {
+    /// Launch parameters with the arguments.
+    pub fn new(cube_count: CubeclDim, cube_dim: CubeclDim, args: Vec<Arg<'a>>) -> Self {
+        Self {
+            cube_count,
+            cube_dim,
+            args,
+        }
+    }
+
+    /// Read the launch parameters from their C representation.
+    ///
+    /// # Safety
+    ///
+    /// The arguments must point to `num_args` valid arguments, whose buffers stay valid for `'a`.
+    pub(crate) unsafe fn from_raw(
+        cube_count: CubeclDim,
+        cube_dim: CubeclDim,
+        args: *const CubeclArg,
+        num_args: usize,
+    ) -> Result<Self, FfiError> {
+        if num_args == 0 {
+            return Ok(Self::new(cube_count, cube_dim, Vec::new()));
+        }
+        if args.is_null() {
+            return Err(FfiError::NullPointer("args"));
+        }
+
+        let args = core::slice::from_raw_parts(args, num_args)
+            .iter()
+            .enumerate()
+            .map(|(position, arg)| {
+                Ok(match arg.kind {
+                    CubeclArgKind::Array => {
+                        let buffer = arg.buffer.as_ref().ok_or(FfiError::InvalidArgument {
+                            position,
+                            expected: "an array with a buffer",
+                        })?;
+                        Arg::Array {
+                            handle: &buffer.handle,
+                            size: buffer.size,
+                            len: arg.len,
+                            line_size: arg.line_size.max(1),
+                        }
+                    }
+                    CubeclArgKind::U32 => Arg::U32(arg.scalar.u32),
+                    CubeclArgKind::I32 => Arg::I32(arg.scalar.i32),
+                    CubeclArgKind::F32 => Arg::F32(arg.scalar.f32),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::new(cube_count, cube_dim, args))
+    }
+
+    /// The number of cubes to launch.
+    pub fn cube_count(&self) -> CubeCount {
+        let CubeclDim { x, y, z } = self.cube_count;
+        CubeCount::Static(x, y, z)
+    }
+
+    /// The number of units of each cube.
+    pub fn cube_dim(&self) -> CubeDim {
+        let CubeclDim { x, y, z } = self.cube_dim;
+        CubeDim::new(x, y, z)
+    }
+
+    /// The arguments, in the order they were given.
+    pub fn args(&self) -> &[Arg<'a>] {
+        &self.args
+    }
+
+    /// The array argument of elements `E` at the position.
+    ///
+    /// The length of the array is given by the host, so it is checked against the size of the
+    /// buffer to keep the kernel from reading or writing out of its bounds.
+    pub fn array<E: CubePrimitive, R: Runtime>(
+        &self,
+        position: usize,
+    ) -> Result<ArrayArg<'a, R>, FfiError> {
+        match self.args.get(position) {
+            Some(&Arg::Array {
+                handle,
+                size,
+                len,
+                line_size,
+            }) => {
+                // The length counts elements, whatever the size of the lines.
+                let required = len.checked_mul(E::as_elem().size());
+                match required {
+                    Some(required) if required <= size => {}
+                    _ => {
+                        return Err(FfiError::OutOfBounds {
+                            position,
+                            required,
+                            available: size,
+                        })
+                    }
+                }
+
+                // Safety: the array was checked to be within its buffer.
+                Ok(unsafe { ArrayArg::from_raw_parts(handle, len, line_size) })
+            }
+            _ => Err(FfiError::InvalidArgument {
+                position,
+                expected: "an array",
+            }),
+        }
+    }
+
+    /// The `u32` scalar argument at the position.
+    pub fn u32(&self, position: usize) -> Result<ScalarArg<u32>, FfiError> {
+        match self.args.get(position) {
+            Some(&Arg::U32(value)) => Ok(ScalarArg::new(value)),
+            _ => Err(FfiError::InvalidArgument {
+                position,
+                expected: "a u32",
+            }),
+        }
+    }
+
+    /// The `i32` scalar argument at the position.
+    pub fn i32(&self, position: usize) -> Result<ScalarArg<i32>, FfiError> {
+        match self.args.get(position) {
+            Some(&Arg::I32(value)) => Ok(ScalarArg::new(value)),
+            _ => Err(FfiError::InvalidArgument {
+                position,
+                expected: "an i32",
+            }),
+        }
+    }
+
+    /// The `f32` scalar argument at the position.
+    pub fn f32(&self, position: usize) -> Result<ScalarArg<f32>, FfiError> {
+        match self.args.get(position) {
+            Some(&Arg::F32(value)) => Ok(ScalarArg::new(value)),
+            _ => Err(FfiError::InvalidArgument {
+                position,
+                expected: "an f32",
+            }),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use cubecl_core::future;
+use cubecl_core::prelude::*;
+use cubecl_core::server::Handle;
+
+use crate::registry::launcher;
+use crate::{CubeclRuntime, FfiError, LaunchArgs};
+
+/// A client of a runtime, whichever runtime it is, owned by the host.
+pub struct CubeclClient {
+    inner: Box<dyn ErasedClient>,
+}
+
+/// A buffer allocated by a [client](CubeclClient), owned by the host.
+///
+/// The memory is released when the buffer is freed and the kernels using it are done.
+pub struct CubeclBuffer {
+    pub(crate) handle: Handle,
+    /// The size of the allocation in bytes, bounding the arrays launched with the buffer.
+    pub(crate) size: usize,
+}
+
+impl CubeclBuffer {
+    /// The handle of the buffer, to launch kernels with it.
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    /// The size of the buffer in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl CubeclClient {
+    /// The client of the device.
+    pub fn new<R: Runtime>(device: &R::Device) -> Self {
+        Self::from_client::<R>(R::client(device))
+    }
+
+    #[cfg_attr(not(any(feature = "wgpu", feature = "cuda")), allow(unused_variables))]
+    /// The client of a device of the runtime, the default device when `device` is negative.
+    pub fn for_runtime(runtime: CubeclRuntime, device: i32) -> Result<Self, FfiError> {
+        match runtime {
+            #[cfg(feature = "wgpu")]
+            CubeclRuntime::Wgpu => {
+                use cubecl_wgpu::{WgpuDevice, WgpuRuntime};
+                let device = match usize::try_from(device) {
+                    Ok(index) => WgpuDevice::DiscreteGpu(index),
+                    Err(_) => WgpuDevice::default(),
+                };
+                Ok(Self::new::<WgpuRuntime>(&device))
+            }
+            #[cfg(not(feature = "wgpu"))]
+            CubeclRuntime::Wgpu => Err(FfiError::UnsupportedRuntime("wgpu")),
+            #[cfg(feature = "cuda")]
+            CubeclRuntime::Cuda => {
+                use cubecl_cuda::{CudaDevice, CudaRuntime};
+                let index = usize::try_from(device).unwrap_or(0);
+                Ok(Self::new::<CudaRuntime>(&CudaDevice::new(index)))
+            }
+            #[cfg(not(feature = "cuda"))]
+            CubeclRuntime::Cuda => Err(FfiError::UnsupportedRuntime("cuda")),
+        }
+    }
+
+    /// Give the client to the host, e.g. to share the device with Rust code.
+    pub fn from_client<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) -> Self {
+        Self {
+            inner: Box::new(RuntimeClient::<R>(client)),
+        }
+    }
+
+    /// Create a buffer holding the bytes.
+    pub fn create(&self, data: &[u8]) -> CubeclBuffer {
+        CubeclBuffer {
+            handle: self.inner.create(data),
+            size: data.len(),
+        }
+    }
+
+    /// Create a buffer of `size` uninitialized bytes.
+    pub fn empty(&self, size: usize) -> CubeclBuffer {
+        CubeclBuffer {
+            handle: self.inner.empty(size),
+            size,
+        }
+    }
+
+    /// Read the bytes of the buffer, once the kernels writing it are done.
+    pub fn read(&self, buffer: &CubeclBuffer) -> Vec<u8> {
+        self.inner.read(&buffer.handle)
+    }
+
+    /// Launch the kernel [registered](crate::register_kernel) under the name for the runtime of
+    /// the client.
+    pub fn launch(&self, name: &str, args: &LaunchArgs<'_>) -> Result<(), FfiError> {
+        self.inner.launch(name, args)
+    }
+
+    /// Wait for the kernels launched by the client to be done.
+    pub fn sync(&self) {
+        self.inner.sync()
+    }
+}
+
+/// The operations of a [compute client](ComputeClient), without its runtime.
+trait ErasedClient: Send + Sync {
+    fn create(&self, data: &[u8]) -> Handle;
+    fn empty(&self, size: usize) -> Handle;
+    fn read(&self, handle: &Handle) -> Vec<u8>;
+    fn launch(&self, name: &str, args: &LaunchArgs<'_>) -> Result<(), FfiError>;
+    fn sync(&self);
+}
+
+struct RuntimeClient<R: Runtime>(ComputeClient<R::Server, R::Channel>);
+
+impl<R: Runtime> ErasedClient for RuntimeClient<R> {
+    fn create(&self, data: &[u8]) -> Handle {
+        self.0.create(data)
+    }
+
+    fn empty(&self, size: usize) -> Handle {
+        self.0.empty(size)
+    }
+
+    fn read(&self, handle: &Handle) -> Vec<u8> {
+        self.0.read(handle.clone().binding())
+    }
+
+    fn launch(&self, name: &str, args: &LaunchArgs<'_>) -> Result<(), FfiError> {
+        let launch = launcher::<R>(name).ok_or_else(|| FfiError::UnknownKernel {
+            name: name.to_string(),
+            runtime: R::name(),
+        })?;
+        launch(&self.0, args)
+    }
+
+    fn sync(&self) {
+        future::block_on(self.0.sync())
+    }
+}
@@ -0,0 +1,165 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+
+/// The status returned by every function of the C ABI.
+///
+/// Anything other than [Ok](CubeclStatus::Ok) comes with a message, returned by
+/// [cubecl_last_error](crate::cubecl_last_error) on the same thread.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeclStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A pointer argument was null.
+    NullPointer = 1,
+    /// The runtime wasn't enabled when the library was built.
+    UnsupportedRuntime = 2,
+    /// No kernel was registered with the name for the runtime of the client.
+    UnknownKernel = 3,
+    /// The arguments don't match the kernel.
+    InvalidArgument = 4,
+    /// The buffer doesn't have the size of the read.
+    SizeMismatch = 5,
+    /// CubeCL panicked, e.g. when the device couldn't be initialized.
+    Panic = 6,
+    /// An array spans more bytes than its buffer.
+    OutOfBounds = 7,
+}
+
+/// An error of a call through the C ABI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FfiError {
+    /// A pointer argument was null.
+    NullPointer(&'static str),
+    /// The runtime wasn't enabled when the library was built.
+    UnsupportedRuntime(&'static str),
+    /// No kernel was registered with the name for the runtime of the client.
+    UnknownKernel {
+        /// The name of the kernel.
+        name: String,
+        /// The name of the runtime of the client.
+        runtime: &'static str,
+    },
+    /// The argument at the position isn't of the kind expected by the kernel.
+    InvalidArgument {
+        /// The position of the argument.
+        position: usize,
+        /// The kind of argument expected by the kernel.
+        expected: &'static str,
+    },
+    /// The buffer doesn't have the size of the read.
+    SizeMismatch {
+        /// The size of the buffer, in bytes.
+        expected: usize,
+        /// The size of the read, in bytes.
+        actual: usize,
+    },
+    /// CubeCL panicked with the message.
+    Panic(String),
+    /// The array argument at the position spans more bytes than its buffer.
+    OutOfBounds {
+        /// The position of the argument.
+        position: usize,
+        /// The number of bytes spanned by the array, `None` when it overflows.
+        required: Option<usize>,
+        /// The size of the buffer, in bytes.
+        available: usize,
+    },
+}
+
+impl FfiError {
+    /// The status reporting the error over the C ABI.
+    pub fn status(&self) -> CubeclStatus {
+        match self {
+            FfiError::NullPointer(_) => CubeclStatus::NullPointer,
+            FfiError::UnsupportedRuntime(_) => CubeclStatus::UnsupportedRuntime,
+            FfiError::UnknownKernel { .. } => CubeclStatus::UnknownKernel,
+            FfiError::InvalidArgument { .. } => CubeclStatus::InvalidArgument,
+            FfiError::SizeMismatch { .. } => CubeclStatus::SizeMismatch,
+            FfiError::Panic(_) => CubeclStatus::Panic,
+            FfiError::OutOfBounds { .. } => CubeclStatus::OutOfBounds,
+        }
+    }
+}
+
+impl core::fmt::Display for FfiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FfiError::NullPointer(argument) => write!(f, "The argument {argument} is null"),
+            FfiError::UnsupportedRuntime(runtime) => write!(
+                f,
+                "The {runtime} runtime isn't supported, enable its feature when building cubecl-ffi"
+            ),
+            FfiError::UnknownKernel { name, runtime } => write!(
+                f,
+                "No kernel named {name} is registered for the {runtime} runtime"
+            ),
+            FfiError::InvalidArgument { position, expected } => write!(
+                f,
+                "The argument at position {position} should be {expected}"
+            ),
+            FfiError::SizeMismatch { expected, actual } => write!(
+                f,
+                "The buffer holds {expected} bytes, but {actual} bytes were read"
+            ),
+            FfiError::Panic(message) => write!(f, "CubeCL panicked: {message}"),
+            FfiError::OutOfBounds {
+                position,
+                required: Some(required),
+                available,
+            } => write!(
+                f,
+                "The array at position {position} spans {required} bytes, but its buffer holds {available} bytes"
+            ),
+            FfiError::OutOfBounds {
+                position,
+                required: None,
+                available,
+            } => write!(
+                f,
+                "The array at position {position} overflows, its buffer holds {available} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FfiError {}
+
+thread_local! {
+    /// The message of the last error of the thread, kept alive until the next error.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Run the call, catching its panics so they don't unwind into the host, and keep the message of
+/// its error for [cubecl_last_error](crate::cubecl_last_error).
+pub(crate) fn guard(call: impl FnOnce() -> Result<(), FfiError>) -> CubeclStatus {
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(call)).unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(FfiError::Panic(message))
+        });
+
+    match result {
+        Ok(()) => CubeclStatus::Ok,
+        Err(err) => {
+            let status = err.status();
+            // Interior nul bytes can't be represented, they only come from kernel names.
+            let message = CString::new(err.to_string().replace('\0', "")).unwrap();
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+            status
+        }
+    }
+}
+
+/// The message of the last error of the thread, null when no call failed yet.
+pub(crate) fn last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(core::ptr::null(), |message| message.as_ptr())
+    })
+}
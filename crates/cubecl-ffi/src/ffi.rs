@@ -0,0 +1,186 @@
+use std::ffi::{c_char, CStr};
+
+use crate::error::{guard, last_error};
+use crate::{CubeclArg, CubeclBuffer, CubeclClient, CubeclDim, CubeclStatus, FfiError, LaunchArgs};
+
+/// The runtimes a client can be created for, when enabled by their feature.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubeclRuntime {
+    Wgpu = 0,
+    Cuda = 1,
+}
+
+/// Create the client of a device of the runtime, the default device when `device` is negative.
+///
+/// # Safety
+///
+/// `client` must be valid for writes. The client is freed with [cubecl_client_free].
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_client_new(
+    runtime: CubeclRuntime,
+    device: i32,
+    client: *mut *mut CubeclClient,
+) -> CubeclStatus {
+    guard(|| {
+        let client = out(client, "client")?;
+        let created = CubeclClient::for_runtime(runtime, device)?;
+        *client = Box::into_raw(Box::new(created));
+        Ok(())
+    })
+}
+
+/// Free the client. Its buffers stay valid until they are freed.
+///
+/// # Safety
+///
+/// `client` must be null or a client created by [cubecl_client_new], not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_client_free(client: *mut CubeclClient) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// Create a buffer holding the `size` bytes of `data`.
+///
+/// # Safety
+///
+/// `client` must be a valid client, `data` valid for `size` bytes of reads and `buffer` valid for
+/// writes. The buffer is freed with [cubecl_buffer_free].
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_buffer_create(
+    client: *const CubeclClient,
+    data: *const u8,
+    size: usize,
+    buffer: *mut *mut CubeclBuffer,
+) -> CubeclStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or(FfiError::NullPointer("client"))?;
+        let buffer = out(buffer, "buffer")?;
+        if data.is_null() {
+            return Err(FfiError::NullPointer("data"));
+        }
+        let data = core::slice::from_raw_parts(data, size);
+        *buffer = Box::into_raw(Box::new(client.create(data)));
+        Ok(())
+    })
+}
+
+/// Create a buffer of `size` uninitialized bytes.
+///
+/// # Safety
+///
+/// `client` must be a valid client and `buffer` valid for writes. The buffer is freed with
+/// [cubecl_buffer_free].
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_buffer_empty(
+    client: *const CubeclClient,
+    size: usize,
+    buffer: *mut *mut CubeclBuffer,
+) -> CubeclStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or(FfiError::NullPointer("client"))?;
+        let buffer = out(buffer, "buffer")?;
+        *buffer = Box::into_raw(Box::new(client.empty(size)));
+        Ok(())
+    })
+}
+
+/// Read the `size` bytes of the buffer into `data`, once the kernels writing it are done.
+///
+/// # Safety
+///
+/// `client` must be the client of the buffer, `buffer` a valid buffer and `data` valid for `size`
+/// bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_buffer_read(
+    client: *const CubeclClient,
+    buffer: *const CubeclBuffer,
+    data: *mut u8,
+    size: usize,
+) -> CubeclStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or(FfiError::NullPointer("client"))?;
+        let buffer = buffer.as_ref().ok_or(FfiError::NullPointer("buffer"))?;
+        if data.is_null() {
+            return Err(FfiError::NullPointer("data"));
+        }
+        let bytes = client.read(buffer);
+        if bytes.len() != size {
+            return Err(FfiError::SizeMismatch {
+                expected: bytes.len(),
+                actual: size,
+            });
+        }
+        core::slice::from_raw_parts_mut(data, size).copy_from_slice(&bytes);
+        Ok(())
+    })
+}
+
+/// Free the buffer. Its memory is reused once the kernels using it are done.
+///
+/// # Safety
+///
+/// `buffer` must be null or a buffer created by [cubecl_buffer_create] or
+/// [cubecl_buffer_empty], not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_buffer_free(buffer: *mut CubeclBuffer) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(buffer));
+    }
+}
+
+/// Launch the kernel [registered](crate::register_kernel) under the name for the runtime of the
+/// client, with `num_args` arguments.
+///
+/// # Safety
+///
+/// `client` must be a valid client, `name` a nul terminated string and `args` valid for
+/// `num_args` arguments, whose buffers are valid buffers of the client. Arrays longer than their
+/// buffers are rejected.
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_launch(
+    client: *const CubeclClient,
+    name: *const c_char,
+    cube_count: CubeclDim,
+    cube_dim: CubeclDim,
+    args: *const CubeclArg,
+    num_args: usize,
+) -> CubeclStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or(FfiError::NullPointer("client"))?;
+        if name.is_null() {
+            return Err(FfiError::NullPointer("name"));
+        }
+        let name = CStr::from_ptr(name).to_string_lossy();
+        let args = LaunchArgs::from_raw(cube_count, cube_dim, args, num_args)?;
+        client.launch(&name, &args)
+    })
+}
+
+/// Wait for the kernels launched by the client to be done.
+///
+/// # Safety
+///
+/// `client` must be a valid client.
+#[no_mangle]
+pub unsafe extern "C" fn cubecl_sync(client: *const CubeclClient) -> CubeclStatus {
+    guard(|| {
+        let client = client.as_ref().ok_or(FfiError::NullPointer("client"))?;
+        client.sync();
+        Ok(())
+    })
+}
+
+/// The message of the last error of the calling thread, null when no call failed yet.
+///
+/// The message is valid until the next failing call of the thread.
+#[no_mangle]
+pub extern "C" fn cubecl_last_error() -> *const c_char {
+    last_error()
+}
+
+unsafe fn out<'a, T>(pointer: *mut T, name: &'static str) -> Result<&'a mut T, FfiError> {
+    pointer.as_mut().ok_or(FfiError::NullPointer(name))
+}
@@ -0,0 +1,22 @@
+//! A C ABI to launch CubeCL kernels from hosts written in other languages, such as C++ or Python
+//! through `ctypes`.
+//!
+//! The kernels are written and [registered](register_kernel) in Rust, once, by the library
+//! exposing them. Hosts then create [clients](cubecl_client_new) and
+//! [buffers](cubecl_buffer_create), and launch the kernels by name with
+//! [cubecl_launch]. The declarations of the functions are in `include/cubecl.h`.
+//!
+//! Every function returns a [status](CubeclStatus) and never unwinds into the host: the message
+//! of a failure, panics included, is returned by [cubecl_last_error].
+
+mod args;
+mod client;
+mod error;
+mod ffi;
+mod registry;
+
+pub use args::*;
+pub use client::*;
+pub use error::{CubeclStatus, FfiError};
+pub use ffi::*;
+pub use registry::*;
@@ -0,0 +1,63 @@
+use std::any::{Any, TypeId};
+use std::sync::RwLock;
+
+use cubecl_core::prelude::*;
+
+use crate::{FfiError, LaunchArgs};
+
+/// Launches a kernel with the arguments given over the C ABI.
+pub type KernelLauncher<R> = fn(
+    &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+    &LaunchArgs<'_>,
+) -> Result<(), FfiError>;
+
+struct Registered {
+    runtime: TypeId,
+    name: String,
+    /// The [launcher](KernelLauncher) of the runtime.
+    launcher: Box<dyn Any + Send + Sync>,
+}
+
+/// The kernels launchable over the C ABI, few enough to be searched linearly.
+static KERNELS: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+/// Register the kernel under the name for the runtime, so hosts can launch it with
+/// `cubecl_launch`, replacing the kernel previously registered under the same name.
+///
+/// The launcher reads the arguments from the [launch arguments](LaunchArgs) and launches the
+/// kernel:
+///
+/// ```ignore
+/// register_kernel::<WgpuRuntime>("add_one", |client, args| {
+///     add_one::launch::<WgpuRuntime>(
+///         client,
+///         args.cube_count(),
+///         args.cube_dim(),
+///         args.array::<f32, _>(0)?,
+///         args.array::<f32, _>(1)?,
+///     );
+///     Ok(())
+/// });
+/// ```
+pub fn register_kernel<R: Runtime>(name: &str, launcher: KernelLauncher<R>) {
+    let runtime = TypeId::of::<R>();
+    let mut kernels = KERNELS.write().unwrap();
+    kernels.retain(|kernel| kernel.runtime != runtime || kernel.name != name);
+    kernels.push(Registered {
+        runtime,
+        name: name.to_string(),
+        launcher: Box::new(launcher),
+    });
+}
+
+/// The launcher registered under the name for the runtime.
+pub(crate) fn launcher<R: Runtime>(name: &str) -> Option<KernelLauncher<R>> {
+    let runtime = TypeId::of::<R>();
+    KERNELS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|kernel| kernel.runtime == runtime && kernel.name == name)
+        .and_then(|kernel| kernel.launcher.downcast_ref::<KernelLauncher<R>>())
+        .copied()
+}
@@ -0,0 +1,205 @@
+use backend_template::{TemplateDevice, TemplateRuntime};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_ffi::*;
+use std::ffi::CStr;
+
+#[cube(launch)]
+fn scale(input: &Array<f32>, output: &mut Array<f32>, factor: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor;
+    }
+}
+
+const DIM: CubeclDim = CubeclDim { x: 1, y: 1, z: 1 };
+
+fn client() -> *mut CubeclClient {
+    register_kernel::<TemplateRuntime>("scale", |client, args| {
+        scale::launch::<TemplateRuntime>(
+            client,
+            args.cube_count(),
+            args.cube_dim(),
+            args.array::<f32, _>(0)?,
+            args.array::<f32, _>(1)?,
+            args.f32(2)?,
+        );
+        Ok(())
+    });
+    Box::into_raw(Box::new(CubeclClient::new::<TemplateRuntime>(
+        &TemplateDevice,
+    )))
+}
+
+fn buffer(client: *const CubeclClient, values: &[f32]) -> *mut CubeclBuffer {
+    let mut buffer = core::ptr::null_mut();
+    let bytes = f32::as_bytes(values);
+    let status = unsafe { cubecl_buffer_create(client, bytes.as_ptr(), bytes.len(), &mut buffer) };
+    assert_eq!(status, CubeclStatus::Ok);
+    buffer
+}
+
+fn array(buffer: *const CubeclBuffer, len: usize) -> CubeclArg {
+    CubeclArg {
+        kind: CubeclArgKind::Array,
+        buffer,
+        len,
+        line_size: 1,
+        scalar: CubeclScalar { u32: 0 },
+    }
+}
+
+fn last_error() -> String {
+    let message = cubecl_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn buffers_are_created_and_read() {
+    let client = client();
+    let buffer = buffer(client, &[1.0, 2.0, 3.0]);
+
+    let mut values = [0.0f32; 3];
+    let status = unsafe { cubecl_buffer_read(client, buffer, values.as_mut_ptr().cast(), 12) };
+
+    assert_eq!(status, CubeclStatus::Ok);
+    assert_eq!(values, [1.0, 2.0, 3.0]);
+    unsafe {
+        cubecl_buffer_free(buffer);
+        cubecl_client_free(client);
+    }
+}
+
+#[test]
+fn reading_a_buffer_of_another_size_fails() {
+    let client = client();
+    let buffer = buffer(client, &[1.0, 2.0]);
+
+    let mut values = [0.0f32; 3];
+    let status = unsafe { cubecl_buffer_read(client, buffer, values.as_mut_ptr().cast(), 12) };
+
+    assert_eq!(status, CubeclStatus::SizeMismatch);
+    assert_eq!(
+        last_error(),
+        "The buffer holds 8 bytes, but 12 bytes were read"
+    );
+    unsafe {
+        cubecl_buffer_free(buffer);
+        cubecl_client_free(client);
+    }
+}
+
+#[test]
+fn registered_kernels_are_launched() {
+    let client = client();
+    let input = buffer(client, &[1.0, 2.0]);
+    let output = buffer(client, &[0.0, 0.0]);
+    let args = [
+        array(input, 2),
+        array(output, 2),
+        CubeclArg {
+            kind: CubeclArgKind::F32,
+            buffer: core::ptr::null(),
+            len: 0,
+            line_size: 0,
+            scalar: CubeclScalar { f32: 2.0 },
+        },
+    ];
+    let dim = CubeclDim { x: 2, y: 1, z: 1 };
+
+    let status = unsafe { cubecl_launch(client, c"scale".as_ptr(), DIM, dim, args.as_ptr(), 3) };
+
+    assert_eq!(status, CubeclStatus::Ok);
+    assert_eq!(unsafe { cubecl_sync(client) }, CubeclStatus::Ok);
+    unsafe {
+        cubecl_buffer_free(input);
+        cubecl_buffer_free(output);
+        cubecl_client_free(client);
+    }
+}
+
+#[test]
+fn arguments_of_the_wrong_kind_are_rejected() {
+    let client = client();
+    let input = buffer(client, &[1.0, 2.0]);
+    // The factor is missing.
+    let args = [array(input, 2), array(input, 2)];
+
+    let status = unsafe { cubecl_launch(client, c"scale".as_ptr(), DIM, DIM, args.as_ptr(), 2) };
+
+    assert_eq!(status, CubeclStatus::InvalidArgument);
+    assert_eq!(last_error(), "The argument at position 2 should be an f32");
+    unsafe {
+        cubecl_buffer_free(input);
+        cubecl_client_free(client);
+    }
+}
+
+#[test]
+fn arrays_longer_than_their_buffer_are_rejected() {
+    let client = client();
+    let input = buffer(client, &[1.0, 2.0]);
+    let output = buffer(client, &[0.0, 0.0]);
+    let args = [
+        array(input, 2),
+        array(output, 3),
+        CubeclArg {
+            kind: CubeclArgKind::F32,
+            buffer: core::ptr::null(),
+            len: 0,
+            line_size: 0,
+            scalar: CubeclScalar { f32: 2.0 },
+        },
+    ];
+
+    let status = unsafe { cubecl_launch(client, c"scale".as_ptr(), DIM, DIM, args.as_ptr(), 3) };
+
+    assert_eq!(status, CubeclStatus::OutOfBounds);
+    assert_eq!(
+        last_error(),
+        "The array at position 1 spans 12 bytes, but its buffer holds 8 bytes"
+    );
+    unsafe {
+        cubecl_buffer_free(input);
+        cubecl_buffer_free(output);
+        cubecl_client_free(client);
+    }
+}
+
+#[test]
+fn unknown_kernels_are_rejected() {
+    let client = client();
+
+    let status =
+        unsafe { cubecl_launch(client, c"missing".as_ptr(), DIM, DIM, core::ptr::null(), 0) };
+
+    assert_eq!(status, CubeclStatus::UnknownKernel);
+    assert_eq!(
+        last_error(),
+        "No kernel named missing is registered for the template runtime"
+    );
+    unsafe { cubecl_client_free(client) };
+}
+
+#[test]
+fn null_pointers_are_rejected() {
+    let mut buffer = core::ptr::null_mut();
+
+    let status = unsafe { cubecl_buffer_empty(core::ptr::null(), 4, &mut buffer) };
+
+    assert_eq!(status, CubeclStatus::NullPointer);
+    assert_eq!(last_error(), "The argument client is null");
+    assert!(buffer.is_null());
+}
+
+#[test]
+fn disabled_runtimes_are_unsupported() {
+    let mut client = core::ptr::null_mut();
+
+    let status = unsafe { cubecl_client_new(CubeclRuntime::Cuda, -1, &mut client) };
+
+    assert_eq!(status, CubeclStatus::UnsupportedRuntime);
+    assert!(last_error().starts_with("The cuda runtime isn't supported"));
+}
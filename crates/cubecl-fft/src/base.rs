@@ -0,0 +1,384 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use crate::{FftDirection, FftRadix};
+
+/// A complex value, stored as two consecutive elements in the tensors.
+#[derive(CubeType, Copy, Clone)]
+pub(crate) struct Complex<F: Float> {
+    pub re: F,
+    pub im: F,
+}
+
+/// The values written by a radix-4 butterfly.
+#[derive(CubeType, Copy, Clone)]
+pub(crate) struct Radix4<F: Float> {
+    pub x0: Complex<F>,
+    pub x1: Complex<F>,
+    pub x2: Complex<F>,
+    pub x3: Complex<F>,
+}
+
+#[cube]
+pub(crate) fn complex_add<F: Float>(a: Complex<F>, b: Complex<F>) -> Complex<F> {
+    Complex::<F> {
+        re: a.re + b.re,
+        im: a.im + b.im,
+    }
+}
+
+#[cube]
+pub(crate) fn complex_sub<F: Float>(a: Complex<F>, b: Complex<F>) -> Complex<F> {
+    Complex::<F> {
+        re: a.re - b.re,
+        im: a.im - b.im,
+    }
+}
+
+#[cube]
+pub(crate) fn complex_mul<F: Float>(a: Complex<F>, b: Complex<F>) -> Complex<F> {
+    Complex::<F> {
+        re: a.re * b.re - a.im * b.im,
+        im: a.re * b.im + a.im * b.re,
+    }
+}
+
+#[cube]
+pub(crate) fn complex_conj<F: Float>(a: Complex<F>) -> Complex<F> {
+    Complex::<F> {
+        re: a.re,
+        im: F::new(0.0) - a.im,
+    }
+}
+
+#[cube]
+pub(crate) fn complex_scale<F: Float>(a: Complex<F>, scale: F) -> Complex<F> {
+    Complex::<F> {
+        re: a.re * scale,
+        im: a.im * scale,
+    }
+}
+
+/// Multiply by `i` in the forward direction, or by `-i` in the inverse direction.
+#[cube]
+fn rotate<F: Float>(a: Complex<F>, #[comptime] direction: FftDirection) -> Complex<F> {
+    match direction {
+        FftDirection::Forward => Complex::<F> {
+            re: F::new(0.0) - a.im,
+            im: a.re,
+        },
+        FftDirection::Inverse => Complex::<F> {
+            re: a.im,
+            im: F::new(0.0) - a.re,
+        },
+    }
+}
+
+/// The root of unity `exp(∓2πi k / n)`, with a negative exponent in the forward direction.
+#[cube]
+pub(crate) fn twiddle<F: Float>(k: u32, n: u32, #[comptime] direction: FftDirection) -> Complex<F> {
+    // Reducing the exponent keeps the angle precise for large signals.
+    let angle = F::cast_from(k % n) * F::new(6.283_185_5) / F::cast_from(n);
+    let sin = F::sin(angle);
+
+    Complex::<F> {
+        re: F::cos(angle),
+        im: match direction {
+            FftDirection::Forward => F::new(0.0) - sin,
+            FftDirection::Inverse => sin,
+        },
+    }
+}
+
+#[cube]
+pub(crate) fn read_complex<F: Float>(tensor: &Tensor<F>, index: u32) -> Complex<F> {
+    Complex::<F> {
+        re: tensor[2 * index],
+        im: tensor[2 * index + 1],
+    }
+}
+
+#[cube]
+pub(crate) fn write_complex<F: Float>(tensor: &mut Tensor<F>, index: u32, value: Complex<F>) {
+    tensor[2 * index] = value.re;
+    tensor[2 * index + 1] = value.im;
+}
+
+#[cube]
+fn read_shared<F: Float>(shared: &SharedMemory<F>, index: u32) -> Complex<F> {
+    Complex::<F> {
+        re: shared[2 * index],
+        im: shared[2 * index + 1],
+    }
+}
+
+#[cube]
+fn write_shared<F: Float>(shared: &mut SharedMemory<F>, index: u32, value: Complex<F>) {
+    shared[2 * index] = value.re;
+    shared[2 * index + 1] = value.im;
+}
+
+/// The radix-4 butterfly of a Stockham stage, whose inputs are a quarter of the sub-signal
+/// apart, `p` being the index of the butterfly in the sub-signal of length `n`.
+#[cube]
+fn radix4_butterfly<F: Float>(
+    a: Complex<F>,
+    b: Complex<F>,
+    c: Complex<F>,
+    d: Complex<F>,
+    p: u32,
+    n: u32,
+    #[comptime] direction: FftDirection,
+) -> Radix4<F> {
+    let apc = complex_add::<F>(a, c);
+    let amc = complex_sub::<F>(a, c);
+    let bpd = complex_add::<F>(b, d);
+    let jbmd = rotate::<F>(complex_sub::<F>(b, d), direction);
+
+    Radix4::<F> {
+        x0: complex_add::<F>(apc, bpd),
+        x1: complex_mul::<F>(twiddle::<F>(p, n, direction), complex_sub::<F>(amc, jbmd)),
+        x2: complex_mul::<F>(
+            twiddle::<F>(2 * p, n, direction),
+            complex_sub::<F>(apc, bpd),
+        ),
+        x3: complex_mul::<F>(
+            twiddle::<F>(3 * p, n, direction),
+            complex_add::<F>(amc, jbmd),
+        ),
+    }
+}
+
+/// Compute all the stages of signals of comptime length `n` in shared memory, one cube per
+/// signal.
+///
+/// The stages alternate between two halves of the shared memory, following the Stockham
+/// formulation so the output is in natural order without a bit reversal.
+#[cube(launch_unchecked)]
+pub(crate) fn fft_shared_kernel<F: Float>(
+    input: &Tensor<F>,
+    output: &mut Tensor<F>,
+    #[comptime] n: u32,
+    #[comptime] radix: FftRadix,
+    #[comptime] direction: FftDirection,
+) {
+    // The whole cube exits before synchronizing.
+    let signal = CUBE_POS;
+    if signal * 2 * n >= input.len() {
+        return;
+    }
+
+    let offset = signal * n;
+    let mut shared = SharedMemory::<F>::new(comptime!(4 * n));
+
+    let mut index = UNIT_POS;
+    while index < n {
+        write_shared::<F>(&mut shared, index, read_complex::<F>(input, offset + index));
+        index += CUBE_DIM;
+    }
+    sync_units();
+
+    let mut length = n;
+    let mut stride = 1;
+    let mut read = 0;
+
+    while length > 1 {
+        let write = n - read;
+        let use_radix4 = match radix {
+            FftRadix::Radix2 => false,
+            FftRadix::Radix4 => length >= 4,
+        };
+
+        if use_radix4 {
+            let quarter = length / 4;
+            let mut butterfly = UNIT_POS;
+
+            while butterfly < n / 4 {
+                let p = butterfly / stride;
+                let q = butterfly % stride;
+                let values = radix4_butterfly::<F>(
+                    read_shared::<F>(&shared, read + q + stride * p),
+                    read_shared::<F>(&shared, read + q + stride * (p + quarter)),
+                    read_shared::<F>(&shared, read + q + stride * (p + 2 * quarter)),
+                    read_shared::<F>(&shared, read + q + stride * (p + 3 * quarter)),
+                    p,
+                    length,
+                    direction,
+                );
+                let first = write + q + stride * 4 * p;
+                write_shared::<F>(&mut shared, first, values.x0);
+                write_shared::<F>(&mut shared, first + stride, values.x1);
+                write_shared::<F>(&mut shared, first + 2 * stride, values.x2);
+                write_shared::<F>(&mut shared, first + 3 * stride, values.x3);
+                butterfly += CUBE_DIM;
+            }
+
+            length /= 4;
+            stride *= 4;
+        } else {
+            let half = length / 2;
+            let mut butterfly = UNIT_POS;
+
+            while butterfly < n / 2 {
+                let p = butterfly / stride;
+                let q = butterfly % stride;
+                let a = read_shared::<F>(&shared, read + q + stride * p);
+                let b = read_shared::<F>(&shared, read + q + stride * (p + half));
+                let first = write + q + stride * 2 * p;
+                write_shared::<F>(&mut shared, first, complex_add::<F>(a, b));
+                write_shared::<F>(
+                    &mut shared,
+                    first + stride,
+                    complex_mul::<F>(twiddle::<F>(p, length, direction), complex_sub::<F>(a, b)),
+                );
+                butterfly += CUBE_DIM;
+            }
+
+            length /= 2;
+            stride *= 2;
+        }
+
+        read = write;
+        sync_units();
+    }
+
+    let scale = match direction {
+        FftDirection::Forward => F::new(1.0),
+        FftDirection::Inverse => F::new(1.0) / F::cast_from(n),
+    };
+    let mut index = UNIT_POS;
+    while index < n {
+        let value = complex_scale::<F>(read_shared::<F>(&shared, read + index), scale);
+        write_complex::<F>(output, offset + index, value);
+        index += CUBE_DIM;
+    }
+}
+
+/// Compute one Stockham stage of sub-signals of length `length` in global memory, one unit per
+/// butterfly, when the signals don't fit in shared memory.
+#[cube(launch_unchecked)]
+pub(crate) fn fft_stage_kernel<F: Float>(
+    input: &Tensor<F>,
+    output: &mut Tensor<F>,
+    n: u32,
+    length: u32,
+    stride: u32,
+    scale: F,
+    #[comptime] radix: FftRadix,
+    #[comptime] direction: FftDirection,
+) {
+    let num_butterflies = match radix {
+        FftRadix::Radix2 => n / 2,
+        FftRadix::Radix4 => n / 4,
+    };
+    let num_signals = input.len() / (2 * n);
+    if ABSOLUTE_POS >= num_signals * num_butterflies {
+        return;
+    }
+
+    let offset = ABSOLUTE_POS / num_butterflies * n;
+    let butterfly = ABSOLUTE_POS % num_butterflies;
+    let p = butterfly / stride;
+    let q = butterfly % stride;
+
+    match radix {
+        FftRadix::Radix2 => {
+            let half = length / 2;
+            let a = read_complex::<F>(input, offset + q + stride * p);
+            let b = read_complex::<F>(input, offset + q + stride * (p + half));
+            let first = offset + q + stride * 2 * p;
+            let difference =
+                complex_mul::<F>(twiddle::<F>(p, length, direction), complex_sub::<F>(a, b));
+            write_complex::<F>(
+                output,
+                first,
+                complex_scale::<F>(complex_add::<F>(a, b), scale),
+            );
+            write_complex::<F>(
+                output,
+                first + stride,
+                complex_scale::<F>(difference, scale),
+            );
+        }
+        FftRadix::Radix4 => {
+            let quarter = length / 4;
+            let values = radix4_butterfly::<F>(
+                read_complex::<F>(input, offset + q + stride * p),
+                read_complex::<F>(input, offset + q + stride * (p + quarter)),
+                read_complex::<F>(input, offset + q + stride * (p + 2 * quarter)),
+                read_complex::<F>(input, offset + q + stride * (p + 3 * quarter)),
+                p,
+                length,
+                direction,
+            );
+            let first = offset + q + stride * 4 * p;
+            write_complex::<F>(output, first, complex_scale::<F>(values.x0, scale));
+            write_complex::<F>(output, first + stride, complex_scale::<F>(values.x1, scale));
+            write_complex::<F>(
+                output,
+                first + 2 * stride,
+                complex_scale::<F>(values.x2, scale),
+            );
+            write_complex::<F>(
+                output,
+                first + 3 * stride,
+                complex_scale::<F>(values.x3, scale),
+            );
+        }
+    }
+}
+
+/// Turn the complex FFT of length `n` of the real signal of length `2n`, packed as complex
+/// values, into the `n + 1` first values of its FFT.
+#[cube(launch_unchecked)]
+pub(crate) fn rfft_unpack_kernel<F: Float>(packed: &Tensor<F>, output: &mut Tensor<F>, n: u32) {
+    if ABSOLUTE_POS * 2 >= output.len() {
+        return;
+    }
+
+    let offset = ABSOLUTE_POS / (n + 1) * n;
+    let k = ABSOLUTE_POS % (n + 1);
+    let z = read_complex::<F>(packed, offset + k % n);
+    let mirror = complex_conj::<F>(read_complex::<F>(packed, offset + (n - k) % n));
+
+    // The FFTs of the even and odd values of the signal.
+    let even = complex_scale::<F>(complex_add::<F>(z, mirror), F::new(0.5));
+    let odd = complex_scale::<F>(complex_sub::<F>(z, mirror), F::new(0.5));
+    let odd = Complex::<F> {
+        re: odd.im,
+        im: F::new(0.0) - odd.re,
+    };
+    let twiddle = twiddle::<F>(k, 2 * n, FftDirection::Forward);
+
+    write_complex::<F>(
+        output,
+        ABSOLUTE_POS,
+        complex_add::<F>(even, complex_mul::<F>(twiddle, odd)),
+    );
+}
+
+/// Pack the `n + 1` first values of the FFT of a real signal of length `2n` into the complex FFT
+/// of length `n` of the signal packed as complex values, reversing [rfft_unpack_kernel].
+#[cube(launch_unchecked)]
+pub(crate) fn irfft_pack_kernel<F: Float>(input: &Tensor<F>, packed: &mut Tensor<F>, n: u32) {
+    if ABSOLUTE_POS * 2 >= packed.len() {
+        return;
+    }
+
+    let offset = ABSOLUTE_POS / n * (n + 1);
+    let k = ABSOLUTE_POS % n;
+    let x = read_complex::<F>(input, offset + k);
+    let mirror = complex_conj::<F>(read_complex::<F>(input, offset + n - k));
+
+    let even = complex_scale::<F>(complex_add::<F>(x, mirror), F::new(0.5));
+    let odd = complex_mul::<F>(
+        complex_scale::<F>(complex_sub::<F>(x, mirror), F::new(0.5)),
+        twiddle::<F>(k, 2 * n, FftDirection::Inverse),
+    );
+    let odd = Complex::<F> {
+        re: F::new(0.0) - odd.im,
+        im: odd.re,
+    };
+
+    write_complex::<F>(packed, ABSOLUTE_POS, complex_add::<F>(even, odd));
+}
@@ -0,0 +1,244 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_linalg::tensor::{contiguous, TensorHandle};
+
+use crate::{
+    base::{fft_shared_kernel, fft_stage_kernel, irfft_pack_kernel, rfft_unpack_kernel},
+    plan::shared_cube_dim,
+    FftDirection, FftPlan, FftRadix, FftStrategy,
+};
+
+/// The FFT of the complex signals of a tensor of shape `[..., n, 2]`, the last dimension holding
+/// the real and imaginary parts, returning a tensor of the same shape.
+///
+/// The length `n` of the signals must be a power of two, and all the other dimensions are
+/// batches.
+pub fn fft<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    direction: FftDirection,
+    plan: FftPlan,
+) -> TensorHandle<R, F> {
+    let rank = input.shape.len();
+    assert!(
+        rank >= 2 && input.shape[rank - 1] == 2,
+        "The complex signals should have a last dimension of 2"
+    );
+
+    let input = contiguous::<R, F>(client, input);
+    complex_fft(client, input, direction, plan)
+}
+
+/// The FFT of the real signals of a tensor of shape `[..., n]`, returning the `n / 2 + 1` first
+/// values of each FFT as a complex tensor of shape `[..., n / 2 + 1, 2]`, since the others are
+/// their conjugates.
+///
+/// The signals are computed as complex signals of length `n / 2`, for which the plan is made.
+pub fn rfft<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    plan: FftPlan,
+) -> TensorHandle<R, F> {
+    let rank = input.shape.len();
+    let input = contiguous::<R, F>(client, input);
+    let n = input.shape[rank - 1] / 2;
+
+    // Consecutive real values are read as the real and imaginary parts of complex values.
+    let mut shape = input.shape.clone();
+    shape[rank - 1] = n;
+    shape.push(2);
+    let packed = TensorHandle::<R, F>::new_contiguous(shape.clone(), input.handle);
+    let packed = complex_fft(client, packed, FftDirection::Forward, plan);
+
+    shape[rank - 1] = n + 1;
+    let output = TensorHandle::<R, F>::empty(client, shape);
+    let num_values = output.shape.iter().product::<usize>() / 2;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+    unsafe {
+        rfft_unpack_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            packed.as_arg(1),
+            output.as_arg(1),
+            ScalarArg::new(n as u32),
+        );
+    }
+
+    output
+}
+
+/// The inverse of [rfft], returning the real signals of shape `[..., 2 * (m - 1)]` from the
+/// `m` first values of their FFTs, of shape `[..., m, 2]`.
+///
+/// The signals are computed as complex signals of length `m - 1`, for which the plan is made.
+pub fn irfft<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    plan: FftPlan,
+) -> TensorHandle<R, F> {
+    let rank = input.shape.len();
+    assert!(
+        rank >= 2 && input.shape[rank - 1] == 2,
+        "The complex signals should have a last dimension of 2"
+    );
+
+    let input = contiguous::<R, F>(client, input);
+    let n = input.shape[rank - 2] - 1;
+
+    let mut shape = input.shape.clone();
+    shape[rank - 2] = n;
+    let packed = TensorHandle::<R, F>::empty(client, shape.clone());
+    let num_values = packed.shape.iter().product::<usize>() / 2;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+    unsafe {
+        irfft_pack_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(1),
+            packed.as_arg(1),
+            ScalarArg::new(n as u32),
+        );
+    }
+
+    let output = complex_fft(client, packed, FftDirection::Inverse, plan);
+
+    shape.pop();
+    shape[rank - 2] = 2 * n;
+    TensorHandle::new_contiguous(shape, output.handle)
+}
+
+/// The FFT of the contiguous complex signals.
+pub(crate) fn complex_fft<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    direction: FftDirection,
+    plan: FftPlan,
+) -> TensorHandle<R, F> {
+    let rank = input.shape.len();
+    let n = input.shape[rank - 2];
+    assert!(
+        n.is_power_of_two(),
+        "The length of the signals should be a power of two"
+    );
+    let num_signals = input.shape.iter().product::<usize>() / (2 * n);
+
+    match plan.strategy {
+        FftStrategy::Shared => {
+            let output = TensorHandle::<R, F>::empty(client, input.shape.clone());
+            let cube_dim = shared_cube_dim(n);
+            let cube_count = calculate_cube_count_elemwise(
+                num_signals * cube_dim.num_elems() as usize,
+                cube_dim,
+            );
+
+            unsafe {
+                fft_shared_kernel::launch_unchecked::<F, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    input.as_arg(1),
+                    output.as_arg(1),
+                    n as u32,
+                    plan.radix,
+                    direction,
+                );
+            }
+
+            output
+        }
+        FftStrategy::Global => {
+            let stages = stages(n, plan.radix);
+            let cube_dim = CubeDim::default();
+            let mut current = input;
+
+            for (index, (length, stride, radix)) in stages.iter().copied().enumerate() {
+                let output = TensorHandle::<R, F>::empty(client, current.shape.clone());
+                let num_butterflies = match radix {
+                    FftRadix::Radix2 => n / 2,
+                    FftRadix::Radix4 => n / 4,
+                };
+                // The inverse is normalized by the last stage.
+                let scale = match direction == FftDirection::Inverse && index == stages.len() - 1 {
+                    true => 1.0 / n as f32,
+                    false => 1.0,
+                };
+                let cube_count =
+                    calculate_cube_count_elemwise(num_signals * num_butterflies, cube_dim);
+
+                unsafe {
+                    fft_stage_kernel::launch_unchecked::<F, R>(
+                        client,
+                        cube_count,
+                        cube_dim,
+                        current.as_arg(1),
+                        output.as_arg(1),
+                        ScalarArg::new(n as u32),
+                        ScalarArg::new(length as u32),
+                        ScalarArg::new(stride as u32),
+                        ScalarArg::new(F::new(scale)),
+                        radix,
+                        direction,
+                    );
+                }
+
+                current = output;
+            }
+
+            current
+        }
+    }
+}
+
+/// The length of the sub-signals, their stride and the radix of each Stockham stage.
+fn stages(n: usize, radix: FftRadix) -> Vec<(usize, usize, FftRadix)> {
+    let mut stages = Vec::new();
+    let mut length = n;
+    let mut stride = 1;
+
+    while length > 1 {
+        let radix = match radix {
+            FftRadix::Radix4 if length >= 4 => FftRadix::Radix4,
+            _ => FftRadix::Radix2,
+        };
+        stages.push((length, stride, radix));
+
+        let factor = match radix {
+            FftRadix::Radix2 => 2,
+            FftRadix::Radix4 => 4,
+        };
+        length /= factor;
+        stride *= factor;
+    }
+
+    stages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radix4_stages_end_with_radix2_for_odd_powers() {
+        let stages = stages(32, FftRadix::Radix4);
+
+        assert_eq!(
+            stages,
+            [
+                (32, 1, FftRadix::Radix4),
+                (8, 4, FftRadix::Radix4),
+                (2, 16, FftRadix::Radix2)
+            ]
+        );
+    }
+
+    #[test]
+    fn single_value_has_no_stage() {
+        assert!(stages(1, FftRadix::Radix2).is_empty());
+    }
+}
@@ -0,0 +1,17 @@
+//! Fast Fourier transforms of complex and real signals, with the plan either chosen from the
+//! length of the signals or selected by autotune.
+
+mod base;
+mod launch;
+mod plan;
+mod tune;
+
+pub use launch::*;
+pub use plan::*;
+pub use tune::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
+#[cfg(feature = "export_tests")]
+pub(crate) mod test_utils;
+mod testgen;
@@ -0,0 +1,76 @@
+use cubecl_core::prelude::*;
+
+/// The number of values combined by each butterfly of an FFT stage.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FftRadix {
+    /// Halve the sub-signals at each stage.
+    Radix2,
+    /// Quarter the sub-signals at each stage, with a last radix-2 stage when the length isn't a
+    /// power of four. Needs half as many stages as [radix-2](FftRadix::Radix2).
+    Radix4,
+}
+
+/// Where the stages of an FFT are computed.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FftStrategy {
+    /// All stages are computed in shared memory by one kernel, one cube per signal. The signals
+    /// must [fit](FftPlan::shared_memory_size) in shared memory.
+    Shared,
+    /// Each stage is a kernel reading and writing global memory, for signals of any length.
+    Global,
+}
+
+/// The direction of a complex FFT.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum FftDirection {
+    /// The FFT, with a negative exponent.
+    Forward,
+    /// The inverse FFT, with a positive exponent and normalized by the length of the signals.
+    Inverse,
+}
+
+/// How an FFT is computed.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub struct FftPlan {
+    /// The radix of the stages.
+    pub radix: FftRadix,
+    /// Where the stages are computed.
+    pub strategy: FftStrategy,
+}
+
+impl FftPlan {
+    /// Plan the FFT of complex signals of length `n` for the device, in shared memory when the
+    /// signals fit.
+    pub fn new<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        n: usize,
+        elem_size: usize,
+    ) -> Self {
+        let max_shared_memory_size = client
+            .properties()
+            .hardware_properties()
+            .max_shared_memory_size;
+
+        let strategy = match Self::shared_memory_size(n, elem_size) <= max_shared_memory_size {
+            true => FftStrategy::Shared,
+            false => FftStrategy::Global,
+        };
+
+        Self {
+            radix: FftRadix::Radix4,
+            strategy,
+        }
+    }
+
+    /// The number of bytes of shared memory used by the [shared](FftStrategy::Shared) strategy
+    /// for complex signals of length `n`.
+    pub fn shared_memory_size(n: usize, elem_size: usize) -> usize {
+        // Two buffers of complex values.
+        4 * n * elem_size
+    }
+}
+
+/// The number of units per cube of the [shared](FftStrategy::Shared) strategy.
+pub(crate) fn shared_cube_dim(n: usize) -> CubeDim {
+    CubeDim::new((n / 2).clamp(1, 256) as u32, 1, 1)
+}
@@ -0,0 +1,49 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_all {
+    () => {
+        mod fft {
+            use super::*;
+
+            #[test]
+            pub fn test_fft_shared_radix2() {
+                cubecl_fft::tests::test_fft_shared_radix2::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_shared_radix4() {
+                cubecl_fft::tests::test_fft_shared_radix4::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_global_radix4() {
+                cubecl_fft::tests::test_fft_global_radix4::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_ifft_shared() {
+                cubecl_fft::tests::test_ifft_shared::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_ifft_global() {
+                cubecl_fft::tests::test_ifft_global::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_rfft() {
+                cubecl_fft::tests::test_rfft::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_irfft_round_trip() {
+                cubecl_fft::tests::test_irfft_round_trip::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_fft_autotune() {
+                cubecl_fft::tests::test_fft_autotune::<TestRuntime>(&Default::default())
+            }
+        }
+    };
+}
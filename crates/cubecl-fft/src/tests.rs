@@ -0,0 +1,149 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{CubeElement, Runtime};
+use cubecl_linalg::tensor::TensorHandle;
+
+use crate::{
+    fft, fft_autotune, irfft, rfft,
+    test_utils::{assert_equals_approx, generate_random_data},
+    FftDirection, FftPlan, FftRadix, FftStrategy,
+};
+
+/// The DFT of the interleaved complex signals of length `n`.
+fn dft_cpu(data: &[f32], n: usize, direction: FftDirection) -> Vec<f32> {
+    let sign = match direction {
+        FftDirection::Forward => -1.0,
+        FftDirection::Inverse => 1.0,
+    };
+    let scale = match direction {
+        FftDirection::Forward => 1.0,
+        FftDirection::Inverse => 1.0 / n as f64,
+    };
+    let mut output = vec![0.0; data.len()];
+
+    for (signal, values) in data.chunks(2 * n).enumerate() {
+        for k in 0..n {
+            let (mut re, mut im) = (0.0f64, 0.0f64);
+            for j in 0..n {
+                let angle = sign * 2.0 * core::f64::consts::PI * ((j * k) % n) as f64 / n as f64;
+                let (x_re, x_im) = (values[2 * j] as f64, values[2 * j + 1] as f64);
+                re += x_re * angle.cos() - x_im * angle.sin();
+                im += x_re * angle.sin() + x_im * angle.cos();
+            }
+            output[2 * (signal * n + k)] = (re * scale) as f32;
+            output[2 * (signal * n + k) + 1] = (im * scale) as f32;
+        }
+    }
+
+    output
+}
+
+fn test_fft<R: Runtime>(n: usize, plan: FftPlan, direction: FftDirection, device: &R::Device) {
+    let client = R::client(device);
+    let shape = vec![3, n, 2];
+    let data = generate_random_data(shape.iter().product());
+    let input =
+        TensorHandle::<R, f32>::new_contiguous(shape.clone(), client.create(f32::as_bytes(&data)));
+
+    let output = fft::<R, f32>(&client, input.as_ref(), direction, plan);
+
+    assert_eq!(output.shape, shape);
+    let expected = dft_cpu(&data, n, direction);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected, 10e-4) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_fft_shared_radix2<R: Runtime>(device: &R::Device) {
+    let plan = FftPlan {
+        radix: FftRadix::Radix2,
+        strategy: FftStrategy::Shared,
+    };
+    test_fft::<R>(64, plan, FftDirection::Forward, device)
+}
+
+pub fn test_fft_shared_radix4<R: Runtime>(device: &R::Device) {
+    // The last stage is radix-2, since 128 isn't a power of four.
+    let plan = FftPlan {
+        radix: FftRadix::Radix4,
+        strategy: FftStrategy::Shared,
+    };
+    test_fft::<R>(128, plan, FftDirection::Forward, device)
+}
+
+pub fn test_fft_global_radix4<R: Runtime>(device: &R::Device) {
+    let plan = FftPlan {
+        radix: FftRadix::Radix4,
+        strategy: FftStrategy::Global,
+    };
+    test_fft::<R>(32, plan, FftDirection::Forward, device)
+}
+
+pub fn test_ifft_shared<R: Runtime>(device: &R::Device) {
+    let plan = FftPlan {
+        radix: FftRadix::Radix4,
+        strategy: FftStrategy::Shared,
+    };
+    test_fft::<R>(16, plan, FftDirection::Inverse, device)
+}
+
+pub fn test_ifft_global<R: Runtime>(device: &R::Device) {
+    let plan = FftPlan {
+        radix: FftRadix::Radix2,
+        strategy: FftStrategy::Global,
+    };
+    test_fft::<R>(8, plan, FftDirection::Inverse, device)
+}
+
+pub fn test_rfft<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let n = 32;
+    let data = generate_random_data(2 * n);
+    let input =
+        TensorHandle::<R, f32>::new_contiguous(vec![2, n], client.create(f32::as_bytes(&data)));
+    let plan = FftPlan::new::<R>(&client, n / 2, 4);
+
+    let output = rfft::<R, f32>(&client, input.as_ref(), plan);
+
+    let complex: Vec<f32> = data.iter().flat_map(|value| [*value, 0.0]).collect();
+    let expected: Vec<f32> = dft_cpu(&complex, n, FftDirection::Forward)
+        .chunks(2 * n)
+        .flat_map(|signal| signal[..n + 2].to_vec())
+        .collect();
+    assert_eq!(output.shape, [2, n / 2 + 1, 2]);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected, 10e-4) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_irfft_round_trip<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let n = 64;
+    let data = generate_random_data(3 * n);
+    let input =
+        TensorHandle::<R, f32>::new_contiguous(vec![3, n], client.create(f32::as_bytes(&data)));
+    let plan = FftPlan::new::<R>(&client, n / 2, 4);
+
+    let spectrum = rfft::<R, f32>(&client, input.as_ref(), plan);
+    let output = irfft::<R, f32>(&client, spectrum.as_ref(), plan);
+
+    assert_eq!(output.shape, [3, n]);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &data, 10e-4) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_fft_autotune<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let n = 256;
+    let shape = vec![4, n, 2];
+    let data = generate_random_data(shape.iter().product());
+    let input = TensorHandle::<R, f32>::new_contiguous(shape, client.create(f32::as_bytes(&data)));
+
+    let output = fft_autotune::<R, f32>(&client, input.as_ref(), FftDirection::Forward);
+
+    let expected = dft_cpu(&data, n, FftDirection::Forward);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected, 10e-3) {
+        panic!("{}", e);
+    }
+}
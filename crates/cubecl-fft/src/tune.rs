@@ -0,0 +1,179 @@
+#[cfg(autotune_persistent_cache)]
+use cubecl_core::tune::TuneResults;
+use cubecl_core::{
+    prelude::*,
+    tune::{anchor, AutotuneOperation, AutotuneOperationSet, LocalTuner},
+    tune_device_id,
+};
+use cubecl_linalg::tensor::TensorHandle;
+
+use crate::{fft, FftDirection, FftPlan, FftRadix, FftStrategy};
+
+pub(crate) static TUNER: LocalTuner<String, String> = LocalTuner::new("cubecl-fft");
+
+/// The FFT of complex signals like [fft], with the plan selected by autotune for the shape of the
+/// tensor and the device.
+pub fn fft_autotune<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    direction: FftDirection,
+) -> TensorHandle<R, F> {
+    let set = operation_set::<R, F>(client, input, direction);
+
+    TUNER.execute(&tune_device_id::<R>(client), client, Box::new(set))
+}
+
+/// Benchmark every plan of [fft_autotune] for the shape of the tensor, so the result can be
+/// exported with [export_tune_results] and shipped with an application.
+pub fn fft_tune_offline<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    direction: FftDirection,
+) {
+    let set = operation_set::<R, F>(client, input, direction);
+
+    TUNER.tune_offline(&tune_device_id::<R>(client), client, Box::new(set));
+}
+
+/// Export the autotune results of [fft_autotune] for the device of the client, usually after
+/// tuning it offline with [fft_tune_offline].
+#[cfg(autotune_persistent_cache)]
+pub fn export_tune_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<TuneResults<String>> {
+    cubecl_linalg::tune::export_tuner_results::<R>(client, &[&TUNER])
+}
+
+/// Import [exported](export_tune_results) autotune results for the device of the client, so
+/// [fft_autotune] doesn't have to tune the plans at its first launch.
+#[cfg(autotune_persistent_cache)]
+pub fn import_tune_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    results: Vec<TuneResults<String>>,
+) {
+    cubecl_linalg::tune::import_tuner_results::<R>(client, &[&TUNER], results)
+}
+
+fn operation_set<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    direction: FftDirection,
+) -> FftAutotuneOperationSet<R, F> {
+    let rank = input.shape.len();
+
+    FftAutotuneOperationSet {
+        client: client.clone(),
+        input: TensorHandle::new(
+            input.shape.to_vec(),
+            input.strides.to_vec(),
+            input.handle.clone(),
+        ),
+        direction,
+        plans: plans::<R>(client, input.shape[rank - 2], F::as_elem().size()),
+    }
+}
+
+/// The plans supported for the length of the signals, starting with the planned one.
+fn plans<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    n: usize,
+    elem_size: usize,
+) -> Vec<FftPlan> {
+    let default = FftPlan::new::<R>(client, n, elem_size);
+    let mut plans = vec![default];
+
+    for strategy in [FftStrategy::Shared, FftStrategy::Global] {
+        // The planned strategy is global only when the signals don't fit in shared memory.
+        if strategy == FftStrategy::Shared && default.strategy == FftStrategy::Global {
+            continue;
+        }
+        for radix in [FftRadix::Radix4, FftRadix::Radix2] {
+            let plan = FftPlan { radix, strategy };
+            if plan != default {
+                plans.push(plan);
+            }
+        }
+    }
+
+    plans
+}
+
+struct FftAutotuneOperationSet<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    direction: FftDirection,
+    plans: Vec<FftPlan>,
+}
+
+impl<R: Runtime, F: Float> AutotuneOperationSet<String, TensorHandle<R, F>>
+    for FftAutotuneOperationSet<R, F>
+{
+    fn key(&self) -> String {
+        let rank = self.input.shape.len();
+        let n = self.input.shape[rank - 2];
+        let num_signals = self.input.shape.iter().product::<usize>() / (2 * n);
+
+        format!(
+            "fft-{}-n{}-signals{}-{:?}",
+            F::as_elem(),
+            n,
+            anchor(num_signals, None),
+            self.direction,
+        )
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation<TensorHandle<R, F>>>> {
+        self.plans
+            .iter()
+            .map(|plan| {
+                Box::new(FftAutotuneOperation {
+                    client: self.client.clone(),
+                    input: self.input.clone(),
+                    direction: self.direction,
+                    plan: *plan,
+                }) as Box<dyn AutotuneOperation<TensorHandle<R, F>>>
+            })
+            .collect()
+    }
+
+    fn fastest(
+        self: Box<Self>,
+        fastest_index: usize,
+    ) -> Box<dyn AutotuneOperation<TensorHandle<R, F>>> {
+        self.autotunables().swap_remove(fastest_index)
+    }
+}
+
+/// An FFT with the given plan, benchmarked on the signals to transform since it allocates its
+/// output.
+struct FftAutotuneOperation<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    input: TensorHandle<R, F>,
+    direction: FftDirection,
+    plan: FftPlan,
+}
+
+impl<R: Runtime, F: Float> core::fmt::Debug for FftAutotuneOperation<R, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FftAutotuneOperation")
+            .field("shape", &self.input.shape)
+            .field("direction", &self.direction)
+            .field("plan", &self.plan)
+            .finish()
+    }
+}
+
+impl<R: Runtime, F: Float> AutotuneOperation<TensorHandle<R, F>> for FftAutotuneOperation<R, F> {
+    fn execute(self: Box<Self>) -> TensorHandle<R, F> {
+        fft(&self.client, self.input.as_ref(), self.direction, self.plan)
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation<TensorHandle<R, F>>> {
+        Box::new(Self {
+            client: self.client.clone(),
+            input: self.input.clone(),
+            direction: self.direction,
+            plan: self.plan,
+        })
+    }
+}
@@ -5,10 +5,10 @@ use super::HipResource;
 use cubecl_core::compute::DebugInformation;
 use cubecl_core::ir::CubeDim;
 use cubecl_core::Feature;
-use cubecl_core::{prelude::*, KernelId};
+use cubecl_core::{prelude::*, CompilationOptions, KernelId};
 use cubecl_hip_sys::{hiprtcResult_HIPRTC_SUCCESS, HIP_SUCCESS};
 use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{MemoryUsage, OutOfMemoryError};
 use cubecl_runtime::storage::BindingResource;
 use cubecl_runtime::{
     memory_management::MemoryManagement,
@@ -39,7 +39,7 @@ pub(crate) struct HipContext {
 
 #[derive(Debug)]
 struct HipCompiledKernel {
-    _module: cubecl_hip_sys::hipModule_t,
+    module: cubecl_hip_sys::hipModule_t,
     func: cubecl_hip_sys::hipFunction_t,
     cube_dim: CubeDim,
     shared_mem_bytes: usize,
@@ -99,6 +99,10 @@ impl ComputeServer for HipServer {
     type Storage = HipStorage;
     type Feature = Feature;
 
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.id().name()
+    }
+
     fn read(&mut self, binding: server::Binding) -> impl Future<Output = Vec<u8>> + 'static {
         let value = self.read_sync(binding);
         async { value }
@@ -110,9 +114,18 @@ impl ComputeServer for HipServer {
 
     fn create(&mut self, data: &[u8]) -> server::Handle {
         let handle = self.empty(data.len());
+        self.write(handle.clone().binding(), data);
+        handle
+    }
+
+    fn binding_size(&mut self, binding: &server::Binding) -> u64 {
         let ctx = self.get_context();
+        let storage = ctx.memory_management.get(binding.memory.clone());
+        storage.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
 
-        let binding = handle.clone().binding();
+    fn write(&mut self, binding: server::Binding, data: &[u8]) {
+        let ctx = self.get_context();
         let resource = ctx.memory_management.get_resource(
             binding.memory,
             binding.offset_start,
@@ -128,7 +141,6 @@ impl ComputeServer for HipServer {
             );
             assert_eq!(status, HIP_SUCCESS, "Should send data to device");
         }
-        handle
     }
 
     fn empty(&mut self, size: usize) -> server::Handle {
@@ -137,15 +149,23 @@ impl ComputeServer for HipServer {
         server::Handle::new(handle, None, None)
     }
 
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle, OutOfMemoryError> {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.try_reserve(size as u64, None)?;
+        Ok(server::Handle::new(handle, None, None))
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
         count: CubeCount,
         bindings: Vec<server::Binding>,
         mode: ExecutionMode,
+        _stream: server::Stream,
     ) {
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
+        let dynamic_shared_memory = kernel.dynamic_shared_memory();
 
         let profile_level = self.logger.profile_level();
         let profile_info = if profile_level.is_some() {
@@ -190,18 +210,12 @@ impl ComputeServer for HipServer {
         if let Some(level) = profile_level {
             ctx.sync();
             let start = std::time::SystemTime::now();
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, dynamic_shared_memory, resources);
             ctx.sync();
 
             let (name, kernel_id) = profile_info.unwrap();
             let info = match level {
-                ProfileLevel::Basic | ProfileLevel::Medium => {
-                    if let Some(val) = name.split("<").next() {
-                        val.split("::").last().unwrap_or(name).to_string()
-                    } else {
-                        name.to_string()
-                    }
-                }
+                ProfileLevel::Basic | ProfileLevel::Medium => kernel_id.name(),
                 cubecl_runtime::debug::ProfileLevel::Full => {
                     format!("{name}: {kernel_id} CubeCount {count:?}")
                 }
@@ -210,10 +224,25 @@ impl ComputeServer for HipServer {
             self.logger
                 .register_profiled(info, start.elapsed().unwrap());
         } else {
-            ctx.execute_task(kernel_id, count, resources);
+            ctx.execute_task(kernel_id, count, dynamic_shared_memory, resources);
         }
     }
 
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let kernel_id = kernel.id();
+        let ctx = self.get_context();
+        // The kernels in flight may still use the modules.
+        ctx.sync();
+        ctx.module_names.retain(|id, compiled| {
+            if !id.is_variant_of(&kernel_id) {
+                return true;
+            }
+            let status = unsafe { cubecl_hip_sys::hipModuleUnload(compiled.module) };
+            assert_eq!(status, HIP_SUCCESS, "Should unload the module");
+            false
+        });
+    }
+
     fn flush(&mut self) {}
 
     fn sync(&mut self) -> impl Future<Output = ()> + 'static {
@@ -305,10 +334,14 @@ impl HipContext {
         let func_name = CString::new("kernel".to_string()).unwrap();
         // CubeCL compilation
         // jitc = just-in-time compiled
-        let mut jitc_kernel = cube_kernel.compile(mode);
+        let mut jitc_kernel = cube_kernel.compile(&CompilationOptions::default(), mode);
 
         if logger.is_activated() {
-            jitc_kernel.debug_info = Some(DebugInformation::new("cpp", kernel_id.clone()));
+            jitc_kernel.debug_info = Some(DebugInformation::new(
+                "cpp",
+                kernel_id.clone(),
+                cube_kernel.cost(),
+            ));
 
             if let Ok(formatted) = format_cpp(&jitc_kernel.source) {
                 jitc_kernel.source = formatted;
@@ -412,7 +445,7 @@ impl HipContext {
         self.module_names.insert(
             kernel_id.clone(),
             HipCompiledKernel {
-                _module: module,
+                module,
                 func,
                 cube_dim: jitc_kernel.cube_dim,
                 shared_mem_bytes: jitc_kernel.shared_mem_bytes,
@@ -424,6 +457,7 @@ impl HipContext {
         &mut self,
         kernel_id: KernelId,
         dispatch_count: (u32, u32, u32),
+        dynamic_shared_memory: u32,
         resources: Vec<HipResource>,
     ) {
         let mut bindings = resources
@@ -443,7 +477,7 @@ impl HipContext {
                 cube_dim.x,
                 cube_dim.y,
                 cube_dim.z,
-                kernel.shared_mem_bytes as u32,
+                kernel.shared_mem_bytes as u32 + dynamic_shared_memory,
                 self.stream,
                 bindings.as_mut_ptr(),
                 std::ptr::null_mut(),
@@ -94,14 +94,30 @@ impl ComputeStorage for HipStorage {
     }
 
     fn alloc(&mut self, size: u64) -> StorageHandle {
+        self.try_alloc(size)
+            .unwrap_or_else(|| panic!("Out of memory when allocating {size} bytes"))
+    }
+
+    /// The pending deallocations are performed before failing, so their memory can be reused.
+    fn try_alloc(&mut self, size: u64) -> Option<StorageHandle> {
         let id = StorageId::new();
         unsafe {
             let mut dptr: *mut ::std::os::raw::c_void = std::ptr::null_mut();
             let status = cubecl_hip_sys::hipMallocAsync(&mut dptr, size as usize, self.stream);
+            if status == cubecl_hip_sys::hipError_t_hipErrorOutOfMemory {
+                if self.deallocations.is_empty() {
+                    return None;
+                }
+                self.perform_deallocations();
+                return self.try_alloc(size);
+            }
             assert_eq!(status, HIP_SUCCESS, "Should allocate memory");
             self.memory.insert(id, dptr);
         };
-        StorageHandle::new(id, StorageUtilization { offset: 0, size })
+        Some(StorageHandle::new(
+            id,
+            StorageUtilization { offset: 0, size },
+        ))
     }
 
     fn dealloc(&mut self, id: StorageId) {
@@ -15,5 +15,8 @@ mod tests {
     pub type TestRuntime = crate::HipRuntime;
 
     cubecl_core::testgen_all!();
+    cubecl_fft::testgen_all!();
+    cubecl_image::testgen_all!();
     cubecl_linalg::testgen_all!();
+    cubecl_std::testgen_all!();
 }
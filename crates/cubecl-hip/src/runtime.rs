@@ -6,7 +6,7 @@ use cubecl_runtime::{
     channel::MutexComputeChannel,
     client::ComputeClient,
     memory_management::{MemoryDeviceProperties, MemoryManagement},
-    ComputeRuntime, DeviceProperties,
+    ComputeRuntime, DeviceProperties, HardwareProperties,
 };
 
 use crate::{
@@ -60,6 +60,52 @@ fn create_client(device: &HipDevice, options: RuntimeOptions) -> ComputeClient<S
         );
         total
     };
+    let attribute = |attribute: cubecl_hip_sys::hipDeviceAttribute_t| unsafe {
+        let mut value: i32 = 0;
+        let status =
+            cubecl_hip_sys::hipDeviceGetAttribute(&mut value, attribute, device.index as i32);
+        assert_eq!(
+            status, HIP_SUCCESS,
+            "Should get the attribute {attribute} of the device"
+        );
+        value as u32
+    };
+    let hardware_props = HardwareProperties {
+        max_shared_memory_size: attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxSharedMemoryPerBlock,
+        ) as usize,
+        max_cube_dim: (
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxBlockDimX),
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxBlockDimY),
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxBlockDimZ),
+        ),
+        max_units_per_cube: attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxThreadsPerBlock,
+        ),
+        max_cube_count: (
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxGridDimX),
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxGridDimY),
+            attribute(cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxGridDimZ),
+        ),
+        // Kernel arguments are limited to 4KB, and each binding is passed as a pointer.
+        max_bindings: (4096 / core::mem::size_of::<u64>()) as u32,
+        max_buffer_size: max_memory as u64,
+        subcube_size: Some(attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeWarpSize,
+        )),
+        num_multiprocessors: Some(attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMultiprocessorCount,
+        )),
+        max_units_per_multiprocessor: Some(attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxThreadsPerMultiProcessor,
+        )),
+        max_cubes_per_multiprocessor: Some(attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxBlocksPerMultiProcessor,
+        )),
+        registers_per_multiprocessor: Some(attribute(
+            cubecl_hip_sys::hipDeviceAttribute_t_hipDeviceAttributeMaxRegistersPerMultiprocessor,
+        )),
+    };
     let storage = HipStorage::new(stream);
     let mem_properties = MemoryDeviceProperties {
         max_page_size: max_memory as u64 / 4,
@@ -72,7 +118,8 @@ fn create_client(device: &HipDevice, options: RuntimeOptions) -> ComputeClient<S
     );
     let hip_ctx = HipContext::new(memory_management, stream, ctx);
     let server = HipServer::new(hip_ctx);
-    let mut device_props = DeviceProperties::new(&[Feature::Subcube], mem_properties);
+    let mut device_props =
+        DeviceProperties::new(&[Feature::Subcube], mem_properties, hardware_props);
     register_supported_types(&mut device_props);
     // TODO
     // register_wmma_features(&mut device_props);
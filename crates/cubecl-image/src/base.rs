@@ -0,0 +1,20 @@
+use cubecl_core::{prelude::*, tensor_line_size};
+
+/// The images of a tensor of shape `[..., height, width, channels]`, all the leading dimensions
+/// being batches.
+pub(crate) fn assert_images(shape: &[usize]) {
+    assert!(
+        shape.len() >= 3,
+        "The images should have a shape of [..., height, width, channels]"
+    );
+}
+
+/// The line size along the channels, which is 1 unless they are contiguous.
+pub(crate) fn channel_line_size<R: Runtime>(input: &TensorHandleRef<'_, R>) -> u8 {
+    tensor_line_size(
+        R::supported_line_sizes(),
+        input.shape,
+        input.strides,
+        input.shape.len() - 1,
+    )
+}
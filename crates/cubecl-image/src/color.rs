@@ -0,0 +1,108 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_linalg::tensor::{index_offset_with_layout, TensorHandle};
+
+use crate::base::assert_images;
+
+/// A conversion between color spaces of three channels.
+///
+/// YUV is the full range BT.601 encoding used by JPEG, with the chroma centered on `0.5` so
+/// that the values of all channels are in `[0, 1]` for RGB values in `[0, 1]`.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum ColorConversion {
+    /// Convert RGB to YUV.
+    RgbToYuv,
+    /// Convert YUV to RGB.
+    YuvToRgb,
+}
+
+/// The three channels of a pixel.
+#[derive(CubeType, Copy, Clone)]
+struct Color<F: Float> {
+    x: F,
+    y: F,
+    z: F,
+}
+
+#[cube]
+fn rgb_to_yuv<F: Float>(r: F, g: F, b: F) -> Color<F> {
+    let half = F::new(0.5);
+
+    Color::<F> {
+        x: F::new(0.299) * r + F::new(0.587) * g + F::new(0.114) * b,
+        y: F::new(-0.168_736) * r - F::new(0.331_264) * g + F::new(0.5) * b + half,
+        z: F::new(0.5) * r - F::new(0.418_688) * g - F::new(0.081_312) * b + half,
+    }
+}
+
+#[cube]
+fn yuv_to_rgb<F: Float>(y: F, u: F, v: F) -> Color<F> {
+    let half = F::new(0.5);
+
+    Color::<F> {
+        x: y + F::new(1.402) * (v - half),
+        y: y - F::new(0.344_136) * (u - half) - F::new(0.714_136) * (v - half),
+        z: y + F::new(1.772) * (u - half),
+    }
+}
+
+#[cube(launch_unchecked)]
+fn convert_color_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    #[comptime] conversion: ColorConversion,
+) {
+    let position = ABSOLUTE_POS * 3;
+    if position >= output.len() {
+        return;
+    }
+
+    let rank = output.rank();
+    let offset = index_offset_with_layout::<F, F>(input, output, position, 0, rank, false);
+    let stride = input.stride(rank - 1);
+    let a = input[offset][0];
+    let b = input[offset + stride][0];
+    let c = input[offset + 2 * stride][0];
+    let color = match conversion {
+        ColorConversion::RgbToYuv => rgb_to_yuv::<F>(a, b, c),
+        ColorConversion::YuvToRgb => yuv_to_rgb::<F>(a, b, c),
+    };
+
+    output[position] = Line::new(color.x);
+    output[position + 1] = Line::new(color.y);
+    output[position + 2] = Line::new(color.z);
+}
+
+/// Convert the colors of the images of shape `[..., height, width, 3]`.
+///
+/// The input can have any strides.
+pub fn convert_color<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    conversion: ColorConversion,
+) -> TensorHandle<R, F> {
+    assert_images(input.shape);
+    assert_eq!(
+        input.shape[input.shape.len() - 1],
+        3,
+        "The images should have three channels"
+    );
+
+    let output = TensorHandle::<R, F>::empty(client, input.shape.to_vec());
+    let num_pixels = output.shape.iter().product::<usize>() / 3;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_pixels, cube_dim);
+
+    unsafe {
+        convert_color_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_arg(1),
+            conversion,
+        );
+    }
+
+    output
+}
@@ -0,0 +1,120 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_linalg::tensor::{index_offset_with_layout, TensorHandle};
+
+use crate::base::{assert_images, channel_line_size};
+
+/// Convolve the images along the dimension `dim` with the odd number of weights, the borders
+/// being clamped.
+#[cube(launch_unchecked)]
+fn convolve_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    weights: &Array<f32>,
+    output: &mut Tensor<Line<F>>,
+    #[comptime] dim: u32,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let line_size = input.line_size();
+    let offset =
+        index_offset_with_layout::<F, F>(input, output, ABSOLUTE_POS, 0, output.rank(), false)
+            * line_size;
+    let size = output.shape(dim);
+    let stride = input.stride(dim);
+    let position = ABSOLUTE_POS * line_size / output.stride(dim) % size;
+    // The offset of the first pixel along the dimension.
+    let start = offset - position * stride;
+    let radius = weights.len() / 2;
+
+    let mut sum = Line::empty(line_size).fill(F::new(0.0));
+
+    for tap in 0..weights.len() {
+        let neighbor = Min::min(
+            Max::max(i32::cast_from(position + tap) - i32::cast_from(radius), 0),
+            i32::cast_from(size) - 1,
+        );
+        let index = (start + u32::cast_from(neighbor) * stride) / line_size;
+        sum += input[index] * Line::empty(line_size).fill(F::cast_from(weights[tap]));
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+/// Convolve the images of shape `[..., height, width, channels]` with a separable filter, the
+/// rows being convolved with the `horizontal` weights and the columns with the `vertical`
+/// weights. Both must have an odd length, centered on the pixel, and the borders are clamped.
+///
+/// The input can have any strides, and the channels are vectorized when they are contiguous.
+pub fn convolve_separable<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    horizontal: &[f32],
+    vertical: &[f32],
+) -> TensorHandle<R, F> {
+    assert_images(input.shape);
+    assert!(
+        horizontal.len() % 2 == 1 && vertical.len() % 2 == 1,
+        "The filters should have an odd length"
+    );
+
+    let rank = input.shape.len();
+    let rows = convolve::<R, F>(client, input, horizontal, rank - 2);
+    convolve::<R, F>(client, rows.as_ref(), vertical, rank - 3)
+}
+
+/// Blur the images of shape `[..., height, width, channels]` with a Gaussian filter of standard
+/// deviation `sigma`, computed as a [separable convolution](convolve_separable).
+pub fn gaussian_blur<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    sigma: f32,
+) -> TensorHandle<R, F> {
+    let weights = gaussian_weights(sigma);
+    convolve_separable::<R, F>(client, input, &weights, &weights)
+}
+
+/// The normalized weights of a Gaussian filter of standard deviation `sigma`, truncated at three
+/// standard deviations.
+pub fn gaussian_weights(sigma: f32) -> Vec<f32> {
+    assert!(sigma > 0.0, "The standard deviation should be positive");
+
+    let radius = (3.0 * sigma).ceil() as i32;
+    let weights: Vec<f32> = (-radius..=radius)
+        .map(|x| (-(x * x) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f32 = weights.iter().sum();
+
+    weights.into_iter().map(|weight| weight / sum).collect()
+}
+
+fn convolve<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    weights: &[f32],
+    dim: usize,
+) -> TensorHandle<R, F> {
+    let output = TensorHandle::<R, F>::empty(client, input.shape.to_vec());
+    let num_weights = weights.len();
+    let weights = client.create(f32::as_bytes(weights));
+
+    let line_size = channel_line_size(&input);
+    let num_elems: usize = input.shape.iter().product();
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+
+    unsafe {
+        convolve_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            ArrayArg::from_raw_parts(&weights, num_weights, 1),
+            output.as_arg(line_size),
+            dim as u32,
+        );
+    }
+
+    output
+}
@@ -0,0 +1,17 @@
+//! Image processing kernels on images of shape `[height, width, channels]`, like blurs, resizes
+//! and color conversions.
+
+mod base;
+mod color;
+mod convolve;
+mod resize;
+
+pub use color::*;
+pub use convolve::*;
+pub use resize::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
+#[cfg(feature = "export_tests")]
+pub(crate) mod test_utils;
+mod testgen;
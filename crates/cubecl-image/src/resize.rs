@@ -0,0 +1,133 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_linalg::tensor::{index_offset_with_layout, TensorHandle};
+
+use crate::base::{assert_images, channel_line_size};
+
+/// How the pixels are interpolated by [resize].
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum ResizeMode {
+    /// Interpolate linearly between the 2x2 nearest pixels.
+    Bilinear,
+    /// Interpolate with a cubic convolution of the 4x4 nearest pixels, with `a = -0.5`. It can
+    /// overshoot the range of the input near sharp edges.
+    Bicubic,
+}
+
+/// The weight of a pixel at the distance `x` from the sampled position.
+#[cube]
+fn tap_weight<F: Float>(x: F, #[comptime] mode: ResizeMode) -> F {
+    let x = F::abs(x);
+
+    match mode {
+        ResizeMode::Bilinear => F::max(F::new(1.0) - x, F::new(0.0)),
+        ResizeMode::Bicubic => {
+            let mut weight = F::new(0.0);
+            if x <= F::new(1.0) {
+                weight = (F::new(1.5) * x - F::new(2.5)) * x * x + F::new(1.0);
+            } else if x < F::new(2.0) {
+                weight = ((F::new(-0.5) * x + F::new(2.5)) * x - F::new(4.0)) * x + F::new(2.0);
+            }
+            weight
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn resize_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    #[comptime] mode: ResizeMode,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let rank = output.rank();
+    let dim_y = rank - 3;
+    let dim_x = rank - 2;
+    let line_size = input.line_size();
+
+    // The offset of the batch and the channels, the other dimensions having the same size.
+    let offset = (index_offset_with_layout::<F, F>(input, output, ABSOLUTE_POS, 0, dim_y, false)
+        + index_offset_with_layout::<F, F>(input, output, ABSOLUTE_POS, rank - 1, rank, false))
+        * line_size;
+    let position = ABSOLUTE_POS * line_size;
+    let out_y = position / output.stride(dim_y) % output.shape(dim_y);
+    let out_x = position / output.stride(dim_x) % output.shape(dim_x);
+    let height = input.shape(dim_y);
+    let width = input.shape(dim_x);
+
+    // Pixel centers are aligned, so the corners of the images match.
+    let y = (F::cast_from(out_y) + F::new(0.5)) * F::cast_from(height)
+        / F::cast_from(output.shape(dim_y))
+        - F::new(0.5);
+    let x = (F::cast_from(out_x) + F::new(0.5)) * F::cast_from(width)
+        / F::cast_from(output.shape(dim_x))
+        - F::new(0.5);
+
+    let taps = comptime!(match mode {
+        ResizeMode::Bilinear => 2u32,
+        ResizeMode::Bicubic => 4u32,
+    });
+    // The nearest pixels are on both sides of the sampled position.
+    let top = i32::cast_from(F::floor(y)) + 1 - i32::cast_from(taps / 2);
+    let left = i32::cast_from(F::floor(x)) + 1 - i32::cast_from(taps / 2);
+
+    let mut sum = Line::empty(line_size).fill(F::new(0.0));
+
+    for tap_y in 0..taps {
+        let row = top + i32::cast_from(tap_y);
+        let weight_y = tap_weight::<F>(y - F::cast_from(row), mode);
+        let row = u32::cast_from(Min::min(Max::max(row, 0), i32::cast_from(height) - 1));
+
+        for tap_x in 0..taps {
+            let col = left + i32::cast_from(tap_x);
+            let weight = weight_y * tap_weight::<F>(x - F::cast_from(col), mode);
+            let col = u32::cast_from(Min::min(Max::max(col, 0), i32::cast_from(width) - 1));
+            let index = offset + row * input.stride(dim_y) + col * input.stride(dim_x);
+
+            sum += input[index / line_size] * Line::empty(line_size).fill(weight);
+        }
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+/// Resize the images of shape `[..., height, width, channels]` to the given height and width,
+/// the borders being clamped.
+///
+/// The input can have any strides, and the channels are vectorized when they are contiguous.
+pub fn resize<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    height: usize,
+    width: usize,
+    mode: ResizeMode,
+) -> TensorHandle<R, F> {
+    assert_images(input.shape);
+
+    let rank = input.shape.len();
+    let mut shape = input.shape.to_vec();
+    shape[rank - 3] = height;
+    shape[rank - 2] = width;
+    let output = TensorHandle::<R, F>::empty(client, shape);
+
+    let line_size = channel_line_size(&input);
+    let num_elems: usize = output.shape.iter().product();
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+
+    unsafe {
+        resize_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_arg(line_size),
+            mode,
+        );
+    }
+
+    output
+}
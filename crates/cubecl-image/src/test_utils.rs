@@ -0,0 +1,41 @@
+use cubecl_core::{client::ComputeClient, server::Handle, CubeElement, Runtime};
+
+pub(crate) fn assert_equals_approx<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: Handle,
+    expected: &[f32],
+    epsilon: f32,
+) -> Result<(), String> {
+    let actual = client.read(output.binding());
+    let actual = f32::from_bytes(&actual);
+
+    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+        if (a - e).abs() >= epsilon {
+            return Err(format!(
+            "Values differ more than epsilon: index={} actual={}, expected={}, difference={}, epsilon={}",
+            i,
+            a,
+            e,
+            (a - e).abs(),
+            epsilon
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn generate_random_data(num_elements: usize) -> Vec<f32> {
+    fn lcg(seed: &mut u64) -> f32 {
+        const A: u64 = 1664525;
+        const C: u64 = 1013904223;
+        const M: f64 = 2u64.pow(32) as f64;
+
+        *seed = (A.wrapping_mul(*seed).wrapping_add(C)) % (1u64 << 32);
+        (*seed as f64 / M * 2.0 - 1.0) as f32
+    }
+
+    let mut seed = 12345;
+
+    (0..num_elements).map(|_| lcg(&mut seed)).collect()
+}
@@ -0,0 +1,44 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_all {
+    () => {
+        mod image {
+            use super::*;
+
+            #[test]
+            pub fn test_gaussian_blur() {
+                cubecl_image::tests::test_gaussian_blur::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_gaussian_blur_strided() {
+                cubecl_image::tests::test_gaussian_blur_strided::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_resize_bilinear() {
+                cubecl_image::tests::test_resize_bilinear::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_resize_bicubic() {
+                cubecl_image::tests::test_resize_bicubic::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_resize_bicubic_strided() {
+                cubecl_image::tests::test_resize_bicubic_strided::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_rgb_to_yuv() {
+                cubecl_image::tests::test_rgb_to_yuv::<TestRuntime>(&Default::default())
+            }
+
+            #[test]
+            pub fn test_yuv_round_trip_strided() {
+                cubecl_image::tests::test_yuv_round_trip_strided::<TestRuntime>(&Default::default())
+            }
+        }
+    };
+}
@@ -0,0 +1,228 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, CubeElement};
+use cubecl_linalg::tensor::TensorHandle;
+
+use crate::{
+    convert_color, gaussian_blur, gaussian_weights, resize,
+    test_utils::{assert_equals_approx, generate_random_data},
+    ColorConversion, ResizeMode,
+};
+
+/// A single image of shape `[height, width, channels]` on the host.
+struct Image {
+    height: usize,
+    width: usize,
+    channels: usize,
+    data: Vec<f32>,
+}
+
+impl Image {
+    fn random(height: usize, width: usize, channels: usize) -> Self {
+        Self {
+            height,
+            width,
+            channels,
+            data: generate_random_data(height * width * channels),
+        }
+    }
+
+    /// The value at the clamped position.
+    fn get(&self, y: i64, x: i64, c: usize) -> f32 {
+        let y = y.clamp(0, self.height as i64 - 1) as usize;
+        let x = x.clamp(0, self.width as i64 - 1) as usize;
+        self.data[(y * self.width + x) * self.channels + c]
+    }
+
+    fn map(&self, height: usize, width: usize, f: impl Fn(usize, usize, usize) -> f32) -> Self {
+        let mut data = Vec::with_capacity(height * width * self.channels);
+        for y in 0..height {
+            for x in 0..width {
+                for c in 0..self.channels {
+                    data.push(f(y, x, c));
+                }
+            }
+        }
+
+        Self {
+            height,
+            width,
+            channels: self.channels,
+            data,
+        }
+    }
+
+    /// Upload the image with its rows and columns swapped in memory, so it isn't contiguous.
+    fn upload_transposed<R: Runtime>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> TensorHandle<R, f32> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for x in 0..self.width {
+            for y in 0..self.height {
+                for c in 0..self.channels {
+                    data.push(self.get(y as i64, x as i64, c));
+                }
+            }
+        }
+
+        TensorHandle::new(
+            vec![1, self.height, self.width, self.channels],
+            vec![
+                self.data.len(),
+                self.channels,
+                self.height * self.channels,
+                1,
+            ],
+            client.create(f32::as_bytes(&data)),
+        )
+    }
+
+    fn upload<R: Runtime>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> TensorHandle<R, f32> {
+        TensorHandle::new_contiguous(
+            vec![1, self.height, self.width, self.channels],
+            client.create(f32::as_bytes(&self.data)),
+        )
+    }
+}
+
+fn blur_cpu(image: &Image, sigma: f32) -> Image {
+    let weights = gaussian_weights(sigma);
+    let radius = (weights.len() / 2) as i64;
+    let convolve = |image: &Image, dy: i64, dx: i64| {
+        image.map(image.height, image.width, |y, x, c| {
+            weights
+                .iter()
+                .enumerate()
+                .map(|(tap, weight)| {
+                    let offset = tap as i64 - radius;
+                    weight * image.get(y as i64 + offset * dy, x as i64 + offset * dx, c)
+                })
+                .sum()
+        })
+    };
+
+    convolve(&convolve(image, 0, 1), 1, 0)
+}
+
+fn resize_cpu(image: &Image, height: usize, width: usize, mode: ResizeMode) -> Image {
+    let weight = |x: f32| {
+        let x = x.abs();
+        match mode {
+            ResizeMode::Bilinear => (1.0 - x).max(0.0),
+            ResizeMode::Bicubic if x <= 1.0 => (1.5 * x - 2.5) * x * x + 1.0,
+            ResizeMode::Bicubic if x < 2.0 => ((-0.5 * x + 2.5) * x - 4.0) * x + 2.0,
+            ResizeMode::Bicubic => 0.0,
+        }
+    };
+    let sample = |out: usize, size: usize, out_size: usize| {
+        (out as f32 + 0.5) * size as f32 / out_size as f32 - 0.5
+    };
+
+    image.map(height, width, |y, x, c| {
+        let sy = sample(y, image.height, height);
+        let sx = sample(x, image.width, width);
+        let mut sum = 0.0;
+        for row in sy.floor() as i64 - 2..=sy.floor() as i64 + 2 {
+            for col in sx.floor() as i64 - 2..=sx.floor() as i64 + 2 {
+                sum += weight(sy - row as f32) * weight(sx - col as f32) * image.get(row, col, c);
+            }
+        }
+        sum
+    })
+}
+
+pub fn test_gaussian_blur<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let image = Image::random(6, 5, 4);
+    let input = image.upload::<R>(&client);
+
+    let output = gaussian_blur::<R, f32>(&client, input.as_ref(), 1.0);
+
+    let expected = blur_cpu(&image, 1.0);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected.data, 10e-5) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_gaussian_blur_strided<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let image = Image::random(7, 4, 3);
+    let input = image.upload_transposed::<R>(&client);
+
+    let output = gaussian_blur::<R, f32>(&client, input.as_ref(), 0.8);
+
+    let expected = blur_cpu(&image, 0.8);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected.data, 10e-5) {
+        panic!("{}", e);
+    }
+}
+
+fn test_resize<R: Runtime>(mode: ResizeMode, device: &R::Device) {
+    let client = R::client(device);
+    let image = Image::random(4, 5, 4);
+    let input = image.upload::<R>(&client);
+
+    let output = resize::<R, f32>(&client, input.as_ref(), 7, 3, mode);
+
+    assert_eq!(output.shape, [1, 7, 3, 4]);
+    let expected = resize_cpu(&image, 7, 3, mode);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected.data, 10e-5) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_resize_bilinear<R: Runtime>(device: &R::Device) {
+    test_resize::<R>(ResizeMode::Bilinear, device)
+}
+
+pub fn test_resize_bicubic<R: Runtime>(device: &R::Device) {
+    test_resize::<R>(ResizeMode::Bicubic, device)
+}
+
+pub fn test_resize_bicubic_strided<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let image = Image::random(3, 6, 2);
+    let input = image.upload_transposed::<R>(&client);
+
+    let output = resize::<R, f32>(&client, input.as_ref(), 8, 4, ResizeMode::Bicubic);
+
+    let expected = resize_cpu(&image, 8, 4, ResizeMode::Bicubic);
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected.data, 10e-5) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_rgb_to_yuv<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    // White, red and black.
+    let input = TensorHandle::<R, f32>::new_contiguous(
+        vec![1, 1, 3, 3],
+        client.create(f32::as_bytes(&[
+            1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ])),
+    );
+
+    let output = convert_color::<R, f32>(&client, input.as_ref(), ColorConversion::RgbToYuv);
+
+    let expected = [1.0, 0.5, 0.5, 0.299, 0.331_264, 1.0, 0.0, 0.5, 0.5];
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &expected, 10e-5) {
+        panic!("{}", e);
+    }
+}
+
+pub fn test_yuv_round_trip_strided<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let image = Image::random(5, 4, 3);
+    let input = image.upload_transposed::<R>(&client);
+
+    let yuv = convert_color::<R, f32>(&client, input.as_ref(), ColorConversion::RgbToYuv);
+    let output = convert_color::<R, f32>(&client, yuv.as_ref(), ColorConversion::YuvToRgb);
+
+    if let Err(e) = assert_equals_approx::<R>(&client, output.handle, &image.data, 10e-4) {
+        panic!("{}", e);
+    }
+}
@@ -0,0 +1,167 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use super::config::CubeAttentionConfig;
+
+/// Scaled dot-product attention `softmax(Q * K^T / sqrt(d)) * V`, where each cube computes
+/// `block_q` rows of the output, one per unit.
+///
+/// The keys and values are loaded in shared memory by blocks of `block_kv` rows, and the softmax
+/// is computed online: the running maximum and sum of each row rescale the accumulator whenever a
+/// block raises the maximum, so the scores are never written to global memory.
+#[cube(launch_unchecked)]
+// Runtime integers have no `div_ceil` in kernels.
+#[allow(clippy::manual_div_ceil)]
+pub fn attention_kernel<F: Float, FA: Float>(
+    query: &Tensor<F>,
+    key: &Tensor<F>,
+    value: &Tensor<F>,
+    out: &mut Tensor<F>,
+    #[comptime] config: CubeAttentionConfig,
+) {
+    let head_dim = config.head_dim;
+    let block_q = config.block_q;
+    let block_kv = config.block_kv;
+    let causal = config.causal;
+
+    let rank = query.rank();
+    let seq_q = query.shape(rank - 2);
+    let seq_kv = key.shape(rank - 2);
+    let offset_q = CUBE_POS_Y * seq_q * head_dim;
+    let offset_kv = CUBE_POS_Y * seq_kv * head_dim;
+    let row = CUBE_POS_X * block_q + UNIT_POS_X;
+    let active = row < seq_q;
+
+    let scale = FA::new(1.0) / FA::sqrt(FA::cast_from(head_dim));
+    let mut q = Array::<FA>::new(head_dim);
+    let mut acc = Array::<FA>::new(head_dim);
+    for d in 0..head_dim {
+        let mut q_value = FA::new(0.0);
+        if active {
+            q_value = FA::cast_from(query[offset_q + row * head_dim + d]) * scale;
+        }
+        q[d] = q_value;
+        acc[d] = FA::new(0.0);
+    }
+
+    let mut scores = Array::<FA>::new(block_kv);
+    let mut row_max = FA::new(0.0);
+    let mut row_sum = FA::new(0.0);
+    let mut started = false.runtime();
+
+    let key_tile = SharedMemory::<F>::new(block_kv * head_dim);
+    let value_tile = SharedMemory::<F>::new(block_kv * head_dim);
+
+    let mut num_tiles = (seq_kv + block_kv - 1) / block_kv;
+    if causal {
+        // Keys after the last row of the cube are masked for all of its units.
+        let last_key = Min::min((CUBE_POS_X + 1) * block_q, seq_kv);
+        num_tiles = (last_key + block_kv - 1) / block_kv;
+    }
+
+    for tile in 0..num_tiles {
+        let kv_start = tile * block_kv;
+
+        load_tile::<F>(key, key_tile, offset_kv, kv_start, seq_kv, config);
+        load_tile::<F>(value, value_tile, offset_kv, kv_start, seq_kv, config);
+
+        sync_units();
+
+        let mut tile_max = row_max;
+        let mut tile_started = started;
+        for j in 0..block_kv {
+            let kv_pos = kv_start + j;
+            let mut masked = kv_pos >= seq_kv;
+            if causal {
+                masked = masked || kv_pos > row;
+            }
+
+            let mut score = FA::new(0.0);
+            if !masked {
+                for d in 0..head_dim {
+                    score += q[d] * FA::cast_from(key_tile[j * head_dim + d]);
+                }
+
+                if tile_started {
+                    tile_max = FA::max(tile_max, score);
+                } else {
+                    tile_max = score;
+                    tile_started = true;
+                }
+            }
+            scores[j] = score;
+        }
+
+        // A tile is only fully masked for a row when a previous tile wasn't.
+        if tile_started {
+            // The accumulator is empty until the first unmasked tile.
+            let mut correction = FA::new(0.0);
+            if started {
+                correction = FA::exp(row_max - tile_max);
+            }
+            let mut probabilities_sum = FA::new(0.0);
+
+            for d in 0..head_dim {
+                acc[d] *= correction;
+            }
+
+            for j in 0..block_kv {
+                let kv_pos = kv_start + j;
+                let mut masked = kv_pos >= seq_kv;
+                if causal {
+                    masked = masked || kv_pos > row;
+                }
+
+                if !masked {
+                    let probability = FA::exp(scores[j] - tile_max);
+                    probabilities_sum += probability;
+
+                    for d in 0..head_dim {
+                        acc[d] += probability * FA::cast_from(value_tile[j * head_dim + d]);
+                    }
+                }
+            }
+
+            row_sum = row_sum * correction + probabilities_sum;
+            row_max = tile_max;
+            started = true;
+        }
+
+        sync_units();
+    }
+
+    if active {
+        for d in 0..head_dim {
+            out[offset_q + row * head_dim + d] = F::cast_from(acc[d] / row_sum);
+        }
+    }
+}
+
+/// Load `block_kv` rows of keys or values starting at `kv_start`, filling the rows after the end
+/// of the sequence with zeros.
+#[cube]
+fn load_tile<F: Float>(
+    input: &Tensor<F>,
+    mut tile: SharedMemory<F>,
+    offset: u32,
+    kv_start: u32,
+    seq_kv: u32,
+    #[comptime] config: CubeAttentionConfig,
+) {
+    let head_dim = config.head_dim;
+    let tile_size = config.block_kv * head_dim;
+    let num_loads = comptime!(tile_size.div_ceil(config.block_q));
+
+    for i in 0..num_loads {
+        let index = i * config.block_q + UNIT_POS_X;
+
+        if index < tile_size {
+            let kv_pos = kv_start + index / head_dim;
+            let mut value = F::new(0.0);
+            if kv_pos < seq_kv {
+                value = input[offset + kv_start * head_dim + index];
+            }
+            tile[index] = value;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use cubecl_core::{
+    self as cubecl,
+    prelude::{CubeContext, Init},
+};
+use cubecl_core::{ir::CubeDim, CubeCount, CubeType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Attention tiling parameters
+pub struct AttentionConfig {
+    /// Number of query rows computed by each cube, one per unit
+    pub block_q: usize,
+    /// Number of key and value rows loaded in shared memory at once
+    pub block_kv: usize,
+}
+
+impl Default for AttentionConfig {
+    fn default() -> Self {
+        Self {
+            block_q: 64,
+            block_kv: 32,
+        }
+    }
+}
+
+impl AttentionConfig {
+    /// The number of bytes of shared memory used by the kernel.
+    pub fn shared_memory_size(&self, head_dim: usize, elem_size: usize) -> usize {
+        2 * self.block_kv * head_dim * elem_size
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, CubeType)]
+/// Attention parameters known at compile time
+pub struct CubeAttentionConfig {
+    /// Number of query rows computed by each cube
+    pub block_q: u32,
+    /// Number of key and value rows loaded in shared memory at once
+    pub block_kv: u32,
+    /// Size of the last dimension of all tensors
+    pub head_dim: u32,
+    /// Keys after the query row are masked
+    pub causal: bool,
+}
+
+impl Init for CubeAttentionConfig {
+    fn init(self, _context: &mut CubeContext) -> Self {
+        self
+    }
+}
+
+impl CubeAttentionConfig {
+    pub fn new(config: &AttentionConfig, head_dim: usize, causal: bool) -> Self {
+        CubeAttentionConfig {
+            block_q: config.block_q as u32,
+            block_kv: config.block_kv as u32,
+            head_dim: head_dim as u32,
+            causal,
+        }
+    }
+}
+
+pub fn attention_cube_count(query_shape: &[usize], config: &AttentionConfig) -> CubeCount {
+    let rank = query_shape.len();
+    let seq_q = query_shape[rank - 2];
+    let num_batches: usize = query_shape.iter().take(rank - 2).product();
+
+    CubeCount::Static(seq_q.div_ceil(config.block_q) as u32, num_batches as u32, 1)
+}
+
+pub fn attention_cube_dim(config: &AttentionConfig) -> CubeDim {
+    CubeDim::new(config.block_q as u32, 1, 1)
+}
@@ -0,0 +1,116 @@
+use cubecl_core::prelude::*;
+
+use crate::{
+    attention::{
+        base::attention_kernel,
+        config::{attention_cube_count, attention_cube_dim, AttentionConfig, CubeAttentionConfig},
+    },
+    tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle},
+};
+
+/// Scaled dot-product attention of the `query`, `key` and `value` tensors of shape
+/// `[..., seq, head_dim]`, accumulated in `f32`.
+pub fn attention<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandle<R, F>,
+    key: TensorHandle<R, F>,
+    value: TensorHandle<R, F>,
+    out: TensorHandle<R, F>,
+    causal: bool,
+    config: AttentionConfig,
+) -> TensorHandle<R, F> {
+    attention_ref::<R, F>(
+        client,
+        query.as_ref(),
+        key.as_ref(),
+        value.as_ref(),
+        out.as_ref(),
+        causal,
+        config,
+    );
+
+    out
+}
+
+/// Scaled dot-product attention of the `query`, `key` and `value` tensors of shape
+/// `[..., seq, head_dim]`, accumulated in `f32`.
+///
+/// With `causal`, each query row only attends to the keys at the same or a previous position.
+/// The output must be contiguous.
+pub fn attention_ref<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    causal: bool,
+    config: AttentionConfig,
+) {
+    let rank = query.shape.len();
+    let head_dim = query.shape[rank - 1];
+
+    assert!(
+        rank >= 2,
+        "Attention tensors should have a rank of at least 2"
+    );
+    assert_eq!(
+        key.shape, value.shape,
+        "Keys and values should have the same shape"
+    );
+    assert_eq!(
+        query.shape[..rank - 2],
+        key.shape[..rank - 2],
+        "Queries and keys should have the same batch dimensions"
+    );
+    assert_eq!(
+        key.shape[rank - 1],
+        head_dim,
+        "Queries and keys should have the same head dimension"
+    );
+    assert_eq!(
+        query.shape, out.shape,
+        "The output should have the shape of the queries"
+    );
+    assert_eq!(
+        matrix_layout(out.strides),
+        MatrixLayout::Contiguous,
+        "The output should be contiguous"
+    );
+    assert!(
+        config.shared_memory_size(head_dim, F::as_elem().size())
+            <= client
+                .properties()
+                .hardware_properties()
+                .max_shared_memory_size,
+        "Shared memory limit will be busted. "
+    );
+
+    let contiguous = |tensor: TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
+        MatrixLayout::Contiguous => TensorHandle::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        ),
+        _ => into_contiguous::<R, F>(client, tensor),
+    };
+    let query = contiguous(query);
+    let key = contiguous(key);
+    let value = contiguous(value);
+
+    let cube_count = attention_cube_count(out.shape, &config);
+    let cube_dim = attention_cube_dim(&config);
+    let cube_config = CubeAttentionConfig::new(&config, head_dim, causal);
+
+    unsafe {
+        attention_kernel::launch_unchecked::<F, f32, R>(
+            client,
+            cube_count,
+            cube_dim,
+            query.as_arg(1),
+            key.as_arg(1),
+            value.as_arg(1),
+            TensorArg::from_raw_parts(out.handle, out.strides, out.shape, 1),
+            cube_config,
+        );
+    }
+}
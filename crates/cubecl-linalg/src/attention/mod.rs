@@ -0,0 +1,16 @@
+mod base;
+pub(crate) mod config;
+mod launch;
+mod tune;
+
+pub use config::AttentionConfig;
+pub use launch::attention as launch;
+pub use launch::attention_ref as launch_ref;
+pub use tune::attention_ref_autotune as launch_ref_autotune;
+pub use tune::attention_tune_offline as tune_offline;
+
+#[cfg(autotune_persistent_cache)]
+pub(crate) use tune::TUNER;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
@@ -0,0 +1,227 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{
+    client::ComputeClient,
+    ir::{Elem, FloatKind},
+    prelude::Float,
+    CubeElement, Feature, Runtime,
+};
+use half::f16;
+
+use crate::{
+    attention::{self, AttentionConfig},
+    tensor::TensorHandle,
+    tests::test_utils::generate_random_data,
+};
+
+struct AttentionTestCase {
+    batch: usize,
+    seq_q: usize,
+    seq_kv: usize,
+    head_dim: usize,
+    causal: bool,
+}
+
+impl AttentionTestCase {
+    fn query_shape(&self) -> Vec<usize> {
+        vec![self.batch, self.seq_q, self.head_dim]
+    }
+
+    fn kv_shape(&self) -> Vec<usize> {
+        vec![self.batch, self.seq_kv, self.head_dim]
+    }
+
+    fn attention_cpu(&self, query: &[f32], key: &[f32], value: &[f32]) -> Vec<f32> {
+        let d = self.head_dim;
+        let scale = 1.0 / (d as f32).sqrt();
+        let mut out = vec![0.; self.batch * self.seq_q * d];
+
+        for b in 0..self.batch {
+            for i in 0..self.seq_q {
+                let q = &query[(b * self.seq_q + i) * d..][..d];
+                let num_keys = match self.causal {
+                    true => usize::min(i + 1, self.seq_kv),
+                    false => self.seq_kv,
+                };
+                let scores: Vec<f32> = (0..num_keys)
+                    .map(|j| {
+                        let k = &key[(b * self.seq_kv + j) * d..][..d];
+                        q.iter().zip(k).map(|(q, k)| q * k).sum::<f32>() * scale
+                    })
+                    .collect();
+                let max = scores.iter().cloned().fold(f32::MIN, f32::max);
+                let sum: f32 = scores.iter().map(|s| (s - max).exp()).sum();
+
+                for (j, score) in scores.iter().enumerate() {
+                    let probability = (score - max).exp() / sum;
+                    for c in 0..d {
+                        out[(b * self.seq_q + i) * d + c] +=
+                            probability * value[(b * self.seq_kv + j) * d + c];
+                    }
+                }
+            }
+        }
+
+        out
+    }
+}
+
+fn create<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    data: &[F],
+    shape: Vec<usize>,
+) -> TensorHandle<R, F> {
+    TensorHandle::new_contiguous(shape, client.create(F::as_bytes(data)))
+}
+
+fn test_attention<R: Runtime>(
+    case: AttentionTestCase,
+    config: Option<AttentionConfig>,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+    let query = generate_random_data(case.query_shape().iter().product());
+    let key: Vec<f32> =
+        generate_random_data(case.kv_shape().iter().product::<usize>() + 1)[1..].to_vec();
+    let value: Vec<f32> =
+        generate_random_data(case.kv_shape().iter().product::<usize>() + 2)[2..].to_vec();
+    let expected = case.attention_cpu(&query, &key, &value);
+
+    let out = TensorHandle::<R, f32>::empty(&client, case.query_shape());
+    let query = create::<R, f32>(&client, &query, case.query_shape());
+    let key = create::<R, f32>(&client, &key, case.kv_shape());
+    let value = create::<R, f32>(&client, &value, case.kv_shape());
+
+    match config {
+        Some(config) => attention::launch_ref::<R, f32>(
+            &client,
+            query.as_ref(),
+            key.as_ref(),
+            value.as_ref(),
+            out.as_ref(),
+            case.causal,
+            config,
+        ),
+        None => attention::launch_ref_autotune::<R, f32>(
+            &client,
+            query.as_ref(),
+            key.as_ref(),
+            value.as_ref(),
+            out.as_ref(),
+            case.causal,
+        ),
+    }
+
+    let actual = client.read(out.handle.binding());
+    assert_approx(f32::from_bytes(&actual), &expected, 10e-4);
+}
+
+fn assert_approx(actual: &[f32], expected: &[f32], epsilon: f32) {
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            (a - e).abs() < epsilon,
+            "Values differ more than epsilon: index={i} actual={a}, expected={e}"
+        );
+    }
+}
+
+pub fn test_attention_one_cube<R: Runtime>(device: &R::Device) {
+    let case = AttentionTestCase {
+        batch: 1,
+        seq_q: 32,
+        seq_kv: 32,
+        head_dim: 16,
+        causal: false,
+    };
+
+    test_attention::<R>(case, Some(AttentionConfig::default()), device);
+}
+
+pub fn test_attention_with_check_bounds<R: Runtime>(device: &R::Device) {
+    let case = AttentionTestCase {
+        batch: 3,
+        seq_q: 50,
+        seq_kv: 70,
+        head_dim: 32,
+        causal: false,
+    };
+    let config = AttentionConfig {
+        block_q: 16,
+        block_kv: 16,
+    };
+
+    test_attention::<R>(case, Some(config), device);
+}
+
+pub fn test_attention_causal<R: Runtime>(device: &R::Device) {
+    let case = AttentionTestCase {
+        batch: 2,
+        seq_q: 100,
+        seq_kv: 100,
+        head_dim: 32,
+        causal: true,
+    };
+    let config = AttentionConfig {
+        block_q: 32,
+        block_kv: 16,
+    };
+
+    test_attention::<R>(case, Some(config), device);
+}
+
+pub fn test_attention_autotune<R: Runtime>(device: &R::Device) {
+    let case = AttentionTestCase {
+        batch: 2,
+        seq_q: 64,
+        seq_kv: 64,
+        head_dim: 32,
+        causal: true,
+    };
+
+    test_attention::<R>(case, None, device);
+}
+
+pub fn test_attention_f16<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if !client
+        .properties()
+        .feature_enabled(Feature::Type(Elem::Float(FloatKind::F16)))
+    {
+        // Can't execute the test.
+        return;
+    }
+
+    let case = AttentionTestCase {
+        batch: 1,
+        seq_q: 40,
+        seq_kv: 40,
+        head_dim: 16,
+        causal: true,
+    };
+    let to_f16 = |data: Vec<f32>| data.into_iter().map(f16::from_f32).collect::<Vec<_>>();
+    let query = to_f16(generate_random_data(case.query_shape().iter().product()));
+    let key =
+        to_f16(generate_random_data(case.kv_shape().iter().product::<usize>() + 1)[1..].to_vec());
+    let value =
+        to_f16(generate_random_data(case.kv_shape().iter().product::<usize>() + 2)[2..].to_vec());
+    let to_f32 = |data: &[f16]| data.iter().map(|v| v.to_f32()).collect::<Vec<_>>();
+    let expected = case.attention_cpu(&to_f32(&query), &to_f32(&key), &to_f32(&value));
+
+    let out = TensorHandle::<R, f16>::empty(&client, case.query_shape());
+    let query = create::<R, f16>(&client, &query, case.query_shape());
+    let key = create::<R, f16>(&client, &key, case.kv_shape());
+    let value = create::<R, f16>(&client, &value, case.kv_shape());
+
+    attention::launch_ref::<R, f16>(
+        &client,
+        query.as_ref(),
+        key.as_ref(),
+        value.as_ref(),
+        out.as_ref(),
+        case.causal,
+        AttentionConfig::default(),
+    );
+
+    let actual = client.read(out.handle.binding());
+    assert_approx(&to_f32(f16::from_bytes(&actual)), &expected, 10e-3);
+}
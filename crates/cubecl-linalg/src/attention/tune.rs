@@ -0,0 +1,210 @@
+use cubecl_core::{
+    prelude::*,
+    tune::{anchor, AutotuneOperation, AutotuneOperationSet, LocalTuner, SearchSpace, TunePoint},
+    tune_device_id,
+};
+
+use crate::{
+    attention::{config::AttentionConfig, launch::attention_ref},
+    tensor::TensorHandle,
+};
+
+pub(crate) static TUNER: LocalTuner<String, String> = LocalTuner::new("cubecl-linalg-attention");
+
+/// Scaled dot-product attention like [attention_ref](super::launch_ref), with the tile sizes
+/// selected by autotune for the shapes of the tensors and the device.
+pub fn attention_ref_autotune<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    causal: bool,
+) {
+    let set = operation_set::<R, F>(client, query, key, value, out, causal);
+
+    TUNER.execute(&tune_device_id::<R>(client), client, Box::new(set));
+}
+
+/// Benchmark every tile size of [attention_ref_autotune] for the shapes of the tensors, so the
+/// result can be exported with `tune::export_tune_results` and shipped with an application.
+pub fn attention_tune_offline<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    causal: bool,
+) {
+    let set = operation_set::<R, F>(client, query, key, value, out, causal);
+
+    TUNER.tune_offline(&tune_device_id::<R>(client), client, Box::new(set));
+}
+
+fn operation_set<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    query: TensorHandleRef<'_, R>,
+    key: TensorHandleRef<'_, R>,
+    value: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    causal: bool,
+) -> AttentionAutotuneOperationSet<R, F> {
+    let head_dim = query.shape[query.shape.len() - 1];
+    let owned = |tensor: TensorHandleRef<'_, R>| {
+        TensorHandle::<R, F>::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        )
+    };
+
+    AttentionAutotuneOperationSet {
+        client: client.clone(),
+        query: owned(query),
+        key: owned(key),
+        value: owned(value),
+        out: owned(out),
+        causal,
+        search_space: search_space::<R>(client, head_dim, F::as_elem().size()),
+    }
+}
+
+/// The tile sizes fitting in the shared memory and the cube of the device, starting with the
+/// default configuration.
+fn search_space<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    head_dim: usize,
+    elem_size: usize,
+) -> SearchSpace {
+    let properties = client.properties().hardware_properties();
+    let max_shared_memory_size = properties.max_shared_memory_size;
+    let max_units_per_cube = properties.max_units_per_cube;
+    let default = AttentionConfig::default();
+
+    SearchSpace::new()
+        .param("block_q", [16, 32, 64, 128])
+        .param("block_kv", [16, 32, 64])
+        .constraint(move |point| {
+            let config = config(point);
+
+            config.shared_memory_size(head_dim, elem_size) <= max_shared_memory_size
+                && config.block_q as u32 <= max_units_per_cube
+        })
+        .prior(move |point| match config(point) == default {
+            true => 1.0,
+            false => 0.0,
+        })
+        .early_stopping(4)
+}
+
+fn config(point: &TunePoint) -> AttentionConfig {
+    AttentionConfig {
+        block_q: point.get("block_q") as usize,
+        block_kv: point.get("block_kv") as usize,
+    }
+}
+
+struct AttentionAutotuneOperationSet<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    query: TensorHandle<R, F>,
+    key: TensorHandle<R, F>,
+    value: TensorHandle<R, F>,
+    out: TensorHandle<R, F>,
+    causal: bool,
+    search_space: SearchSpace,
+}
+
+impl<R: Runtime, F: Float> AutotuneOperationSet<String> for AttentionAutotuneOperationSet<R, F> {
+    fn key(&self) -> String {
+        let rank = self.query.shape.len();
+        let num_batches: usize = self.query.shape.iter().take(rank - 2).product();
+
+        format!(
+            "attention-{}-batches{}-q{}-kv{}-d{}-causal{}",
+            F::as_elem(),
+            anchor(num_batches, None),
+            anchor(self.query.shape[rank - 2], None),
+            anchor(self.key.shape[rank - 2], None),
+            self.query.shape[rank - 1],
+            self.causal,
+        )
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation>> {
+        self.search_space
+            .points()
+            .iter()
+            .map(|point| {
+                Box::new(AttentionAutotuneOperation {
+                    client: self.client.clone(),
+                    query: self.query.clone(),
+                    key: self.key.clone(),
+                    value: self.value.clone(),
+                    out: self.out.clone(),
+                    causal: self.causal,
+                    config: config(point),
+                }) as Box<dyn AutotuneOperation>
+            })
+            .collect()
+    }
+
+    fn fastest(self: Box<Self>, fastest_index: usize) -> Box<dyn AutotuneOperation> {
+        self.autotunables().swap_remove(fastest_index)
+    }
+
+    fn benchmark_order(&self, _key: &String, _num_candidates: usize) -> Vec<usize> {
+        self.search_space
+            .benchmark_order(&self.search_space.points())
+    }
+
+    fn early_stopping(&self) -> Option<usize> {
+        self.search_space.patience()
+    }
+}
+
+/// Attention with the given tile sizes, benchmarked on the tensors to compute since it only
+/// overwrites the output.
+struct AttentionAutotuneOperation<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    query: TensorHandle<R, F>,
+    key: TensorHandle<R, F>,
+    value: TensorHandle<R, F>,
+    out: TensorHandle<R, F>,
+    causal: bool,
+    config: AttentionConfig,
+}
+
+impl<R: Runtime, F: Float> core::fmt::Debug for AttentionAutotuneOperation<R, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AttentionAutotuneOperation")
+            .field("config", &self.config)
+            .field("causal", &self.causal)
+            .finish()
+    }
+}
+
+impl<R: Runtime, F: Float> AutotuneOperation for AttentionAutotuneOperation<R, F> {
+    fn execute(self: Box<Self>) {
+        attention_ref::<R, F>(
+            &self.client,
+            self.query.as_ref(),
+            self.key.as_ref(),
+            self.value.as_ref(),
+            self.out.as_ref(),
+            self.causal,
+            self.config,
+        );
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation> {
+        Box::new(Self {
+            client: self.client.clone(),
+            query: self.query.clone(),
+            key: self.key.clone(),
+            value: self.value.clone(),
+            out: self.out.clone(),
+            causal: self.causal,
+            config: self.config,
+        })
+    }
+}
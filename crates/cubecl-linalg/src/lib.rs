@@ -1,5 +1,12 @@
+/// Attention components.
+pub mod attention;
 /// Matrix multiplication components.
 pub mod matmul;
+/// Sparse matrix formats and products.
+pub mod sparse;
 /// Contains basic tensor helpers.
 pub mod tensor;
 mod tests;
+/// Offline autotune results of the operations of this crate.
+#[cfg(autotune_persistent_cache)]
+pub mod tune;
@@ -1,8 +1,4 @@
-use cubecl_core::{
-    client::ComputeClient,
-    ir::{Elem, FloatKind},
-    Feature, Runtime,
-};
+use cubecl_core::{client::ComputeClient, prelude::*, Feature};
 
 use crate::matmul::cmma::config::CmmaConfig;
 
@@ -20,12 +16,20 @@ pub enum UnavailabilityReason {
 pub fn check_cmma_availability<R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     cmma_config: &CmmaConfig,
+) -> Result<(), UnavailabilityReason> {
+    check_cmma_availability_mixed::<R, f32>(client, cmma_config)
+}
+
+/// Checks if the matmul cmma can be used when accumulating in `FA`.
+pub fn check_cmma_availability_mixed<R: Runtime, FA: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    cmma_config: &CmmaConfig,
 ) -> Result<(), UnavailabilityReason> {
     let tile_dim: TileDimension = cmma_config.tile_dimension_strategy.into();
     if !client.properties().feature_enabled(Feature::Cmma {
-        a: Elem::Float(FloatKind::F16),
-        b: Elem::Float(FloatKind::F16),
-        c: Elem::Float(FloatKind::F32),
+        a: cmma_config.precision.elem(),
+        b: cmma_config.precision.elem(),
+        c: FA::as_elem(),
         m: tile_dim.m as u8,
         k: tile_dim.k as u8,
         n: tile_dim.n as u8,
@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core::cube;
 use cubecl_core::{self as cubecl, prelude::*};
 
@@ -12,45 +13,59 @@ use crate::matmul::cmma::compute_loop::{
 use crate::matmul::cmma::config::ComputeLoopOrderStrategy;
 
 #[cube(launch_unchecked)]
-pub fn cmma_launch<F: Float, FC: Float>(
+pub fn cmma_launch<F: Float, FC: Float, FA: Float, E: FusedEpilogue<F>>(
     lhs: &Tensor<F>,
     rhs: &Tensor<F>,
     out: &mut Tensor<F>,
+    operand: &Tensor<F>,
     #[comptime] comptime_info: ComptimeCmmaInfo,
 ) {
     match comptime_info.main_loop_strategy {
-        MainLoopStrategy::Standard => {
-            cmma_build_step_1::<StandardMainLoop, F, FC>(lhs, rhs, out, comptime_info)
-        }
+        MainLoopStrategy::Standard => cmma_build_step_1::<StandardMainLoop, F, FC, FA, E>(
+            lhs,
+            rhs,
+            out,
+            operand,
+            comptime_info,
+        ),
         MainLoopStrategy::Split(_) => {
-            cmma_build_step_1::<SplitMainLoop, F, FC>(lhs, rhs, out, comptime_info)
+            cmma_build_step_1::<SplitMainLoop, F, FC, FA, E>(lhs, rhs, out, operand, comptime_info)
         }
     }
 }
 
 #[cube]
-pub fn cmma_build_step_1<D: CmmaMain, F: Float, FC: Float>(
+pub fn cmma_build_step_1<D: CmmaMain, F: Float, FC: Float, FA: Float, E: FusedEpilogue<F>>(
     lhs: &Tensor<F>,
     rhs: &Tensor<F>,
     out: &mut Tensor<F>,
+    operand: &Tensor<F>,
     #[comptime] comptime_info: ComptimeCmmaInfo,
 ) {
     match comptime_info.compute_loop_order_strategy {
         ComputeLoopOrderStrategy::AllBuffersFirst => {
-            cmma_execute::<BuffersFirstComputeLoop, D, F, FC>(lhs, rhs, out, comptime_info)
+            cmma_execute::<BuffersFirstComputeLoop, D, F, FC, FA, E>(
+                lhs,
+                rhs,
+                out,
+                operand,
+                comptime_info,
+            )
         }
         ComputeLoopOrderStrategy::AllAccumulatorsFirst(reuse_lhs_fragment) => {
             match reuse_lhs_fragment {
-                false => cmma_execute::<AccumulatorsFirstComputeLoop, D, F, FC>(
+                false => cmma_execute::<AccumulatorsFirstComputeLoop, D, F, FC, FA, E>(
                     lhs,
                     rhs,
                     out,
+                    operand,
                     comptime_info,
                 ),
-                true => cmma_execute::<AccumulatorsFirstWithReuseComputeLoop, D, F, FC>(
+                true => cmma_execute::<AccumulatorsFirstWithReuseComputeLoop, D, F, FC, FA, E>(
                     lhs,
                     rhs,
                     out,
+                    operand,
                     comptime_info,
                 ),
             }
@@ -59,16 +74,24 @@ pub fn cmma_build_step_1<D: CmmaMain, F: Float, FC: Float>(
 }
 
 #[cube]
-pub fn cmma_execute<C: ComputeLoop, D: CmmaMain, F: Float, FC: Float>(
+pub fn cmma_execute<
+    C: ComputeLoop,
+    D: CmmaMain,
+    F: Float,
+    FC: Float,
+    FA: Float,
+    E: FusedEpilogue<F>,
+>(
     lhs: &Tensor<F>,
     rhs: &Tensor<F>,
     out: &mut Tensor<F>,
+    operand: &Tensor<F>,
     #[comptime] comptime_info: ComptimeCmmaInfo,
 ) {
     let (runtime_info, mut fragments, shared_memories) =
-        D::prologue::<F, FC>(lhs, rhs, out, comptime_info);
+        D::prologue::<F, FC, FA>(lhs, rhs, out, comptime_info);
 
-    D::main_loop::<C, F, FC>(
+    D::main_loop::<C, F, FC, FA>(
         lhs,
         rhs,
         shared_memories,
@@ -77,5 +100,11 @@ pub fn cmma_execute<C: ComputeLoop, D: CmmaMain, F: Float, FC: Float>(
         comptime_info,
     );
 
-    D::epilogue(out, fragments.accumulators, runtime_info, comptime_info);
+    D::epilogue::<F, FA, E>(
+        out,
+        operand,
+        fragments.accumulators,
+        runtime_info,
+        comptime_info,
+    );
 }
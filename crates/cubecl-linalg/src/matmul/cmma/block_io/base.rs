@@ -1,5 +1,6 @@
 use crate::matmul::cmma::load_shared_memory::load_info::LoadInfo;
 use crate::matmul::cmma::prologue::{Dimensions, RuntimeCmmaInfo};
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
@@ -16,11 +17,12 @@ pub(crate) trait BlockLoader<F: Float, FC: Float> {
 }
 
 #[cube]
-pub(crate) trait BlockWriter<F: Float>: Send + Sync + 'static {
+pub(crate) trait BlockWriter<F: Float, FA: Float>: Send + Sync + 'static {
     #[allow(clippy::too_many_arguments)]
-    fn write_single(
+    fn write_single<E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulator_sm: SharedMemory<F>,
+        operand: &Tensor<F>,
+        accumulator_sm: SharedMemory<FA>,
         batch_offset: u32,
         read_position: u32,
         write_row: u32,
@@ -1,5 +1,6 @@
 use crate::matmul::cmma::load_shared_memory::load_info::LoadInfo;
 use crate::matmul::cmma::prologue::{Dimensions, RuntimeCmmaInfo};
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
@@ -45,10 +46,11 @@ impl<F: Float, FC: Float> BlockLoader<F, FC> for VerticalCheckBlockIO {
 }
 
 #[cube]
-impl<F: Float> BlockWriter<F> for VerticalCheckBlockIO {
-    fn write_single(
+impl<F: Float, FA: Float> BlockWriter<F, FA> for VerticalCheckBlockIO {
+    fn write_single<E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulator_sm: SharedMemory<F>,
+        operand: &Tensor<F>,
+        accumulator_sm: SharedMemory<FA>,
         batch_offset: u32,
         read_position: u32,
         write_row: u32,
@@ -63,13 +65,25 @@ impl<F: Float> BlockWriter<F> for VerticalCheckBlockIO {
 
             if is_scalar {
                 let val = accumulator_sm[read_position];
-                out[write_position / out_vec] = val;
+                out[write_position / out_vec] = E::apply(
+                    F::cast_from(val),
+                    operand,
+                    write_row,
+                    write_col,
+                    write_position,
+                );
             } else {
                 let mut value = F::vectorized_empty(out_vec);
 
                 #[unroll]
                 for i in 0..out_vec {
-                    value[i] = accumulator_sm[read_position + i];
+                    value[i] = E::apply(
+                        F::cast_from(accumulator_sm[read_position + i]),
+                        operand,
+                        write_row,
+                        write_col + i,
+                        write_position + i,
+                    );
                 }
 
                 out[write_position / out_vec] = value;
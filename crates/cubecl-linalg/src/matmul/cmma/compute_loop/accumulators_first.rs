@@ -15,9 +15,9 @@ pub(crate) struct AccumulatorsFirstWithReuseComputeLoop {}
 
 #[cube]
 impl ComputeLoop for AccumulatorsFirstComputeLoop {
-    fn compute_loop<F: Float, FC: Float>(
+    fn compute_loop<F: Float, FC: Float, FA: Float>(
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         ids: Ids,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -55,7 +55,7 @@ impl ComputeLoop for AccumulatorsFirstComputeLoop {
                 );
 
                 let accumulator = &fragments.accumulators.index(accumulator_iter);
-                cmma::execute::<FC, FC, F, F>(
+                cmma::execute::<FC, FC, FA, FA>(
                     &fragments.lhs,
                     &fragments.rhs,
                     accumulator,
@@ -68,9 +68,9 @@ impl ComputeLoop for AccumulatorsFirstComputeLoop {
 
 #[cube]
 impl ComputeLoop for AccumulatorsFirstWithReuseComputeLoop {
-    fn compute_loop<F: Float, FC: Float>(
+    fn compute_loop<F: Float, FC: Float, FA: Float>(
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         ids: Ids,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -108,7 +108,7 @@ impl ComputeLoop for AccumulatorsFirstWithReuseComputeLoop {
                 );
 
                 let accumulator = &fragments.accumulators.index(accumulator_iter);
-                cmma::execute::<FC, FC, F, F>(
+                cmma::execute::<FC, FC, FA, FA>(
                     &fragments.lhs,
                     &fragments.rhs,
                     accumulator,
@@ -16,9 +16,9 @@ use crate::matmul::cmma::{
 
 #[cube]
 pub(crate) trait ComputeLoop {
-    fn compute_loop<F: Float, FC: Float>(
+    fn compute_loop<F: Float, FC: Float, FA: Float>(
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         ids: Ids,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     );
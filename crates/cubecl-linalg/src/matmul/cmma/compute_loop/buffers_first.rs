@@ -14,9 +14,9 @@ pub(crate) struct BuffersFirstComputeLoop {}
 
 #[cube]
 impl ComputeLoop for BuffersFirstComputeLoop {
-    fn compute_loop<F: Float, FC: Float>(
+    fn compute_loop<F: Float, FC: Float, FA: Float>(
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         compute_ids: Ids,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -54,7 +54,7 @@ impl ComputeLoop for BuffersFirstComputeLoop {
                 );
 
                 let accumulator = &fragments.accumulators.index(accumulator_iter);
-                cmma::execute::<FC, FC, F, F>(
+                cmma::execute::<FC, FC, FA, FA>(
                     &fragments.lhs,
                     &fragments.rhs,
                     accumulator,
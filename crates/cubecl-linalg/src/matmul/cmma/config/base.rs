@@ -7,7 +7,7 @@ use super::{
         ComputeLoopOrderStrategy, MainLoopStrategy, RasterizationStrategy, SmemLoaderStrategy,
         WriteOutStrategy,
     },
-    NumComputePlanesStrategy, TileDimensionStrategy, TilingOrderStrategy,
+    CmmaPrecision, NumComputePlanesStrategy, TileDimensionStrategy, TilingOrderStrategy,
 };
 
 pub(crate) const CMMA_PLANE_DIM: u8 = 32;
@@ -35,6 +35,8 @@ pub struct CmmaConfig {
     pub main_loop_strategy: MainLoopStrategy,
     pub tile_dimension_strategy: TileDimensionStrategy,
     pub num_compute_planes_strategy: NumComputePlanesStrategy,
+    /// Precision of the inputs consumed by tensor cores
+    pub precision: CmmaPrecision,
 }
 
 impl Default for CmmaConfig {
@@ -52,6 +54,7 @@ impl Default for CmmaConfig {
             MainLoopStrategy::Standard,
             TileDimensionStrategy::M16K16N16,
             NumComputePlanesStrategy::NumTilesLhs,
+            CmmaPrecision::Half,
         )
     }
 }
@@ -71,6 +74,7 @@ impl CmmaConfig {
         main_loop_strategy: MainLoopStrategy,
         tile_dimension_strategy: TileDimensionStrategy,
         num_compute_planes_strategy: NumComputePlanesStrategy,
+        precision: CmmaPrecision,
     ) -> CmmaConfig {
         // Don't modify things here
         CmmaConfig {
@@ -86,6 +90,7 @@ impl CmmaConfig {
             main_loop_strategy,
             tile_dimension_strategy,
             num_compute_planes_strategy,
+            precision,
         }
     }
 
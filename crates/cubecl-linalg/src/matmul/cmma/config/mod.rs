@@ -4,4 +4,5 @@ mod strategy;
 
 pub(crate) use base::*;
 pub(crate) use predefined::PredefinedCmmaConfig;
+pub use strategy::CmmaPrecision;
 pub(crate) use strategy::*;
@@ -3,13 +3,14 @@ use super::{
         ComputeLoopOrderStrategy, MainLoopStrategy, RasterizationStrategy, SmemLoaderStrategy,
         WriteOutStrategy,
     },
-    CmmaConfig, TilingOrderStrategy,
+    CmmaConfig, CmmaPrecision, TileDimensionStrategy, TilingOrderStrategy,
 };
 
 #[derive(Clone, Copy)]
 #[allow(dead_code)]
 pub enum PredefinedCmmaConfig {
     M128K16,
+    M128K16Tf32,
     M64K32,
     M64K16,
     M32K16,
@@ -38,6 +39,14 @@ impl From<PredefinedCmmaConfig> for CmmaConfig {
                 b_n: 128,
                 ..Default::default()
             },
+            PredefinedCmmaConfig::M128K16Tf32 => CmmaConfig {
+                b_m: 128,
+                b_k: 16,
+                b_n: 128,
+                tile_dimension_strategy: TileDimensionStrategy::M16K8N16,
+                precision: CmmaPrecision::Tf32,
+                ..Default::default()
+            },
             PredefinedCmmaConfig::M64K32 => CmmaConfig {
                 b_m: 64,
                 b_k: 32,
@@ -1,3 +1,5 @@
+use cubecl_core::ir::{Elem, FloatKind};
+
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
 /// Defines how data travels from accumulators to global output
 pub enum WriteOutStrategy {
@@ -111,6 +113,9 @@ pub enum TileDimensionStrategy {
     // M: 8, K: 16, N: 32
     // Doesn't work
     M8K16N32,
+    // M: 16, K: 8, N: 16
+    // Only for TF32
+    M16K8N16,
 }
 
 pub struct TileDimension {
@@ -129,6 +134,26 @@ impl From<TileDimensionStrategy> for TileDimension {
             },
             TileDimensionStrategy::M32K16N8 => panic!("Unsupported, contains a bug"),
             TileDimensionStrategy::M8K16N32 => panic!("Unsupported, contains a bug"),
+            TileDimensionStrategy::M16K8N16 => Self { m: 16, k: 8, n: 16 },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Default)]
+/// Defines the precision of the inputs consumed by tensor cores
+pub enum CmmaPrecision {
+    /// Inputs are cast to f16
+    #[default]
+    Half,
+    /// Inputs stay in f32 and are executed in TF32, which needs tiles with a k of 8
+    Tf32,
+}
+
+impl CmmaPrecision {
+    pub(crate) fn elem(&self) -> Elem {
+        match self {
+            CmmaPrecision::Half => Elem::Float(FloatKind::F16),
+            CmmaPrecision::Tf32 => Elem::Float(FloatKind::F32),
         }
     }
 }
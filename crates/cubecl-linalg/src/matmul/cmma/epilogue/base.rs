@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
@@ -14,18 +15,31 @@ use super::super::{
 use super::{large_smem::LargeSmemWriter, reuse_smem::ReuseSmemWriter};
 
 #[cube]
-pub(crate) fn write_to_output<F: Float>(
+pub(crate) fn write_to_output<F: Float, FA: Float, E: FusedEpilogue<F>>(
     out: &mut Tensor<F>,
-    accumulators: Sequence<cmma::Matrix<F>>,
+    operand: &Tensor<F>,
+    accumulators: Sequence<cmma::Matrix<FA>>,
     runtime_info: RuntimeCmmaInfo,
     #[comptime] comptime_info: ComptimeCmmaInfo,
 ) {
     match comptime_info.write_out_strategy {
         WriteOutStrategy::LargeSmem => {
-            LargeSmemWriter::write_to_output(out, accumulators, runtime_info, comptime_info);
+            LargeSmemWriter::write_to_output::<F, FA, E>(
+                out,
+                operand,
+                accumulators,
+                runtime_info,
+                comptime_info,
+            );
         }
         WriteOutStrategy::ReuseSmem => {
-            ReuseSmemWriter::write_to_output(out, accumulators, runtime_info, comptime_info);
+            ReuseSmemWriter::write_to_output::<F, FA, E>(
+                out,
+                operand,
+                accumulators,
+                runtime_info,
+                comptime_info,
+            );
         }
     }
 }
@@ -33,19 +47,21 @@ pub(crate) fn write_to_output<F: Float>(
 #[cube]
 /// Writes accumulators to global memory
 pub(crate) trait OutputWriter: Send + Sync + 'static {
-    fn write_to_output<F: Float>(
+    fn write_to_output<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     );
 }
 
 #[cube]
-pub(crate) fn shared_memory_to_output<F: Float>(
+pub(crate) fn shared_memory_to_output<F: Float, FA: Float, E: FusedEpilogue<F>>(
     out: &mut Tensor<F>,
+    operand: &Tensor<F>,
     smem_position: u32,
-    accumulator_sm: SharedMemory<F>,
+    accumulator_sm: SharedMemory<FA>,
     n_iter: u32,
     runtime_info: RuntimeCmmaInfo,
     #[comptime] comptime_info: ComptimeCmmaInfo,
@@ -55,8 +71,9 @@ pub(crate) fn shared_memory_to_output<F: Float>(
 
     if check_m_bounds {
         if check_n_bounds {
-            write_tile::<F, WholeCheckBlockIO>(
+            write_tile::<F, FA, E, WholeCheckBlockIO>(
                 out,
+                operand,
                 smem_position,
                 accumulator_sm,
                 n_iter,
@@ -64,8 +81,9 @@ pub(crate) fn shared_memory_to_output<F: Float>(
                 comptime_info,
             );
         } else {
-            write_tile::<F, VerticalCheckBlockIO>(
+            write_tile::<F, FA, E, VerticalCheckBlockIO>(
                 out,
+                operand,
                 smem_position,
                 accumulator_sm,
                 n_iter,
@@ -74,8 +92,9 @@ pub(crate) fn shared_memory_to_output<F: Float>(
             );
         }
     } else if check_n_bounds {
-        write_tile::<F, HorizontalCheckBlockIO>(
+        write_tile::<F, FA, E, HorizontalCheckBlockIO>(
             out,
+            operand,
             smem_position,
             accumulator_sm,
             n_iter,
@@ -83,8 +102,9 @@ pub(crate) fn shared_memory_to_output<F: Float>(
             comptime_info,
         );
     } else {
-        write_tile::<F, UncheckedBlockIO>(
+        write_tile::<F, FA, E, UncheckedBlockIO>(
             out,
+            operand,
             smem_position,
             accumulator_sm,
             n_iter,
@@ -95,10 +115,11 @@ pub(crate) fn shared_memory_to_output<F: Float>(
 }
 
 #[cube]
-fn write_tile<F: Float, W: BlockWriter<F>>(
+fn write_tile<F: Float, FA: Float, E: FusedEpilogue<F>, W: BlockWriter<F, FA>>(
     out: &mut Tensor<F>,
+    operand: &Tensor<F>,
     smem_position: u32,
-    accumulator_sm: SharedMemory<F>,
+    accumulator_sm: SharedMemory<FA>,
     n_iter: u32,
     runtime_info: RuntimeCmmaInfo,
     #[comptime] comptime_info: ComptimeCmmaInfo,
@@ -142,8 +163,9 @@ fn write_tile<F: Float, W: BlockWriter<F>>(
         let read_pos = smem_offset + i * smem_step;
         let write_row = row_offset + unit_write_row + i * lane_row_step;
 
-        W::write_single(
+        W::write_single::<E>(
             out,
+            operand,
             accumulator_sm,
             offsets.batch_out,
             read_pos,
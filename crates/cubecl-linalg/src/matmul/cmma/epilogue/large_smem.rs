@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
@@ -12,9 +13,10 @@ pub(crate) struct LargeSmemWriter;
 
 #[cube]
 impl OutputWriter for LargeSmemWriter {
-    fn write_to_output<F: Float>(
+    fn write_to_output<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -25,7 +27,7 @@ impl OutputWriter for LargeSmemWriter {
         let smem_stride = comptime_info.tile_size_m * comptime_info.tile_size_n;
         let smem_size = num_accumulators * num_compute_planes * smem_stride;
 
-        let mut acc_sm = SharedMemory::<F>::new(smem_size);
+        let mut acc_sm = SharedMemory::<FA>::new(smem_size);
 
         let slice_offset = plane_id * num_accumulators * smem_stride;
         let smem_position_base = num_accumulators * plane_id;
@@ -37,7 +39,7 @@ impl OutputWriter for LargeSmemWriter {
 
             let slice = acc_sm.slice_mut(slice_start, slice_end);
 
-            cmma::store::<F>(
+            cmma::store::<FA>(
                 slice,
                 accumulators.index(n),
                 comptime_info.tile_size_n,
@@ -48,7 +50,15 @@ impl OutputWriter for LargeSmemWriter {
         #[unroll]
         for n in 0..num_accumulators {
             let smem_position = smem_position_base + n;
-            shared_memory_to_output(out, smem_position, acc_sm, n, runtime_info, comptime_info);
+            shared_memory_to_output::<F, FA, E>(
+                out,
+                operand,
+                smem_position,
+                acc_sm,
+                n,
+                runtime_info,
+                comptime_info,
+            );
         }
     }
 }
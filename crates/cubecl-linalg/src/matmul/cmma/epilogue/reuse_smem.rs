@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
@@ -11,9 +12,10 @@ pub(crate) struct ReuseSmemWriter;
 
 #[cube]
 impl OutputWriter for ReuseSmemWriter {
-    fn write_to_output<F: Float>(
+    fn write_to_output<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -24,21 +26,29 @@ impl OutputWriter for ReuseSmemWriter {
         let smem_stride = comptime_info.tile_size_m * comptime_info.tile_size_n;
         let smem_size = num_compute_planes * smem_stride;
 
-        let acc_sm = SharedMemory::<F>::new(smem_size);
+        let acc_sm = SharedMemory::<FA>::new(smem_size);
 
         let slice_offset = plane_id * smem_stride;
         let slice = acc_sm.slice_mut_unsafe(slice_offset, slice_offset + smem_stride);
 
         #[unroll]
         for n in 0..num_accumulators {
-            cmma::store::<F>(
+            cmma::store::<FA>(
                 slice,
                 accumulators.index(n),
                 comptime_info.tile_size_n,
                 cmma::MatrixLayout::RowMajor,
             );
 
-            shared_memory_to_output(out, plane_id, acc_sm, n, runtime_info, comptime_info);
+            shared_memory_to_output::<F, FA, E>(
+                out,
+                operand,
+                plane_id,
+                acc_sm,
+                n,
+                runtime_info,
+                comptime_info,
+            );
         }
     }
 }
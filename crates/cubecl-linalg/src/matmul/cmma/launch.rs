@@ -6,7 +6,11 @@ use cubecl_core::{
 use half::f16;
 
 use crate::{
-    matmul::cmma::{base::cmma_launch, config::CmmaConfig},
+    matmul::cmma::{
+        base::cmma_launch,
+        config::{CmmaConfig, CmmaPrecision},
+    },
+    matmul::fused_epilogue::{epilogue_operand, FusedEpilogue, Identity},
     tensor::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle},
 };
 
@@ -36,6 +40,34 @@ pub fn matmul_cmma_ref<R: Runtime, F: Float>(
     out: TensorHandleRef<'_, R>,
     cmma_config: CmmaConfig,
 ) {
+    matmul_cmma_ref_mixed::<R, F, F>(client, lhs, rhs, out, cmma_config);
+}
+
+/// Matrix multiplication using [cooperative matrix-multiply and accumulate operations](cubecl_core::cmma),
+/// where the `F` inputs are multiplied with the [precision](CmmaPrecision) of the config and
+/// accumulated in `FA` before being written to the `F` output.
+pub fn matmul_cmma_ref_mixed<R: Runtime, F: Float, FA: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    cmma_config: CmmaConfig,
+) {
+    matmul_cmma_ref_fused::<R, F, FA, Identity>(client, lhs, rhs, out, None, cmma_config);
+}
+
+/// Matrix multiplication using [cooperative matrix-multiply and accumulate operations](cubecl_core::cmma)
+/// like [matmul_cmma_ref_mixed], applying the [epilogue](FusedEpilogue) `E` to the values before
+/// they are stored, with the given operand.
+pub fn matmul_cmma_ref_fused<R: Runtime, F: Float, FA: Float, E: FusedEpilogue<F>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    operand: Option<TensorHandleRef<'_, R>>,
+    cmma_config: CmmaConfig,
+) {
+    let operand = epilogue_operand::<R, F>(client, operand);
     let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
         MatrixLayout::Contiguous => true,
         MatrixLayout::MildlyPermuted {
@@ -49,36 +81,47 @@ pub fn matmul_cmma_ref<R: Runtime, F: Float>(
     let rhs_correct_layout = check_layout(&rhs);
 
     match (lhs_correct_layout, rhs_correct_layout) {
-        (true, true) => matmul_cmma_ref_no_check::<R, F>(client, lhs, rhs, out, cmma_config),
-        (true, false) => matmul_cmma_ref_no_check::<R, F>(
+        (true, true) => matmul_cmma_ref_no_check::<R, F, FA, E>(
+            client,
+            lhs,
+            rhs,
+            out,
+            operand.as_ref(),
+            cmma_config,
+        ),
+        (true, false) => matmul_cmma_ref_no_check::<R, F, FA, E>(
             client,
             lhs,
             into_contiguous::<R, F>(client, rhs).as_ref(),
             out,
+            operand.as_ref(),
             cmma_config,
         ),
-        (false, true) => matmul_cmma_ref_no_check::<R, F>(
+        (false, true) => matmul_cmma_ref_no_check::<R, F, FA, E>(
             client,
             into_contiguous::<R, F>(client, lhs).as_ref(),
             rhs,
             out,
+            operand.as_ref(),
             cmma_config,
         ),
-        (false, false) => matmul_cmma_ref_no_check::<R, F>(
+        (false, false) => matmul_cmma_ref_no_check::<R, F, FA, E>(
             client,
             into_contiguous::<R, F>(client, lhs).as_ref(),
             into_contiguous::<R, F>(client, rhs).as_ref(),
             out,
+            operand.as_ref(),
             cmma_config,
         ),
     }
 }
 
-fn matmul_cmma_ref_no_check<R: Runtime, F: Float>(
+fn matmul_cmma_ref_no_check<R: Runtime, F: Float, FA: Float, E: FusedEpilogue<F>>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    operand: TensorHandleRef<'_, R>,
     cmma_config: CmmaConfig,
 ) {
     let rank = lhs.strides.len();
@@ -95,15 +138,38 @@ fn matmul_cmma_ref_no_check<R: Runtime, F: Float>(
     let out_vectorization =
         tensor_line_size(available_vectorizations, out.shape, out.strides, rank - 1);
 
+    let cube_count = cmma_config.cube_count(out.shape);
+    let cube_dim = cmma_config.cube_dim();
+    let comptime_info = cmma_config.comptime_info(m, k, n);
+
     unsafe {
-        cmma_launch::launch_unchecked::<F, f16, R>(
-            client,
-            cmma_config.cube_count(out.shape),
-            cmma_config.cube_dim(),
-            TensorArg::from_raw_parts(lhs.handle, lhs.strides, lhs.shape, lhs_vectorization),
-            TensorArg::from_raw_parts(rhs.handle, rhs.strides, rhs.shape, rhs_vectorization),
-            TensorArg::from_raw_parts(out.handle, out.strides, out.shape, out_vectorization),
-            cmma_config.comptime_info(m, k, n),
-        );
+        let lhs = TensorArg::from_raw_parts(lhs.handle, lhs.strides, lhs.shape, lhs_vectorization);
+        let rhs = TensorArg::from_raw_parts(rhs.handle, rhs.strides, rhs.shape, rhs_vectorization);
+        let out = TensorArg::from_raw_parts(out.handle, out.strides, out.shape, out_vectorization);
+        // The epilogue reads the operand by element.
+        let operand = TensorArg::from_raw_parts(operand.handle, operand.strides, operand.shape, 1);
+
+        match cmma_config.precision {
+            CmmaPrecision::Half => cmma_launch::launch_unchecked::<F, f16, FA, E, R>(
+                client,
+                cube_count,
+                cube_dim,
+                lhs,
+                rhs,
+                out,
+                operand,
+                comptime_info,
+            ),
+            CmmaPrecision::Tf32 => cmma_launch::launch_unchecked::<F, f32, FA, E, R>(
+                client,
+                cube_count,
+                cube_dim,
+                lhs,
+                rhs,
+                out,
+                operand,
+                comptime_info,
+            ),
+        }
     }
 }
@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core::cube;
 use cubecl_core::{self as cubecl, prelude::*};
 
@@ -11,25 +12,26 @@ use super::super::{
 
 #[cube]
 pub(crate) trait CmmaMain {
-    fn prologue<F: Float, FC: Float>(
+    fn prologue<F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         out: &mut Tensor<F>,
         #[comptime] comptime_info: ComptimeCmmaInfo,
-    ) -> (RuntimeCmmaInfo, Fragments<F, FC>, SharedMemories<FC>);
+    ) -> (RuntimeCmmaInfo, Fragments<FA, FC>, SharedMemories<FC>);
 
-    fn main_loop<C: ComputeLoop, F: Float, FC: Float>(
+    fn main_loop<C: ComputeLoop, F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     );
 
-    fn epilogue<F: Float>(
+    fn epilogue<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     );
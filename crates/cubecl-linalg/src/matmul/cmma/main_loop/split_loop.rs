@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core::cube;
 use cubecl_core::{self as cubecl, prelude::*};
 
@@ -16,20 +17,20 @@ pub(crate) struct SplitMainLoop {}
 
 #[cube]
 impl CmmaMain for SplitMainLoop {
-    fn prologue<F: Float, FC: Float>(
+    fn prologue<F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         out: &mut Tensor<F>,
         #[comptime] comptime_info: ComptimeCmmaInfo,
-    ) -> (RuntimeCmmaInfo, Fragments<F, FC>, SharedMemories<FC>) {
-        prologue::<SplitMainLoop, F, FC>(lhs, rhs, out, comptime_info)
+    ) -> (RuntimeCmmaInfo, Fragments<FA, FC>, SharedMemories<FC>) {
+        prologue::<SplitMainLoop, F, FC, FA>(lhs, rhs, out, comptime_info)
     }
 
-    fn main_loop<C: ComputeLoop, F: Float, FC: Float>(
+    fn main_loop<C: ComputeLoop, F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -53,7 +54,7 @@ impl CmmaMain for SplitMainLoop {
             sync_units();
 
             if is_compute_plane(comptime_info) {
-                C::compute_loop::<F, FC>(
+                C::compute_loop::<F, FC, FA>(
                     shared_memories,
                     fragments,
                     runtime_info.compute_ids,
@@ -65,14 +66,15 @@ impl CmmaMain for SplitMainLoop {
         }
     }
 
-    fn epilogue<F: Float>(
+    fn epilogue<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
         if is_compute_plane(comptime_info) {
-            write_to_output(out, accumulators, runtime_info, comptime_info);
+            write_to_output::<F, FA, E>(out, operand, accumulators, runtime_info, comptime_info);
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::matmul::fused_epilogue::FusedEpilogue;
 use cubecl_core::cube;
 use cubecl_core::{self as cubecl, prelude::*};
 
@@ -16,20 +17,20 @@ pub(crate) struct StandardMainLoop {}
 
 #[cube]
 impl CmmaMain for StandardMainLoop {
-    fn prologue<F: Float, FC: Float>(
+    fn prologue<F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         out: &mut Tensor<F>,
         #[comptime] comptime_info: ComptimeCmmaInfo,
-    ) -> (RuntimeCmmaInfo, Fragments<F, FC>, SharedMemories<FC>) {
-        prologue::<StandardMainLoop, F, FC>(lhs, rhs, out, comptime_info)
+    ) -> (RuntimeCmmaInfo, Fragments<FA, FC>, SharedMemories<FC>) {
+        prologue::<StandardMainLoop, F, FC, FA>(lhs, rhs, out, comptime_info)
     }
 
-    fn main_loop<C: ComputeLoop, F: Float, FC: Float>(
+    fn main_loop<C: ComputeLoop, F: Float, FC: Float, FA: Float>(
         lhs: &Tensor<F>,
         rhs: &Tensor<F>,
         shared_memories: SharedMemories<FC>,
-        fragments: &mut Fragments<F, FC>,
+        fragments: &mut Fragments<FA, FC>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
@@ -50,7 +51,7 @@ impl CmmaMain for StandardMainLoop {
 
             sync_units();
 
-            C::compute_loop::<F, FC>(
+            C::compute_loop::<F, FC, FA>(
                 shared_memories,
                 fragments,
                 runtime_info.compute_ids,
@@ -61,13 +62,14 @@ impl CmmaMain for StandardMainLoop {
         }
     }
 
-    fn epilogue<F: Float>(
+    fn epilogue<F: Float, FA: Float, E: FusedEpilogue<F>>(
         out: &mut Tensor<F>,
-        accumulators: Sequence<cmma::Matrix<F>>,
+        operand: &Tensor<F>,
+        accumulators: Sequence<cmma::Matrix<FA>>,
         runtime_info: RuntimeCmmaInfo,
         #[comptime] comptime_info: ComptimeCmmaInfo,
     ) {
-        write_to_output(out, accumulators, runtime_info, comptime_info);
+        write_to_output::<F, FA, E>(out, operand, accumulators, runtime_info, comptime_info);
     }
 
     fn get_compute_ids(#[comptime] _comptime_info: ComptimeCmmaInfo) -> Ids {
@@ -11,5 +11,9 @@ mod prologue;
 pub(crate) mod rasterization;
 
 pub use availability::check_cmma_availability as is_available;
+pub use availability::check_cmma_availability_mixed as is_available_mixed;
+pub use config::CmmaPrecision;
 pub use launch::matmul_cmma as launch;
 pub use launch::matmul_cmma_ref as launch_ref;
+pub use launch::matmul_cmma_ref_fused as launch_ref_fused;
+pub use launch::matmul_cmma_ref_mixed as launch_ref_mixed;
@@ -11,14 +11,14 @@ use crate::matmul::cmma::prologue::{make_fragments, make_shared_memories};
 use super::super::prologue::{Fragments, SharedMemories};
 
 #[cube]
-pub(crate) fn prologue<D: CmmaMain, F: Float, FC: Float>(
+pub(crate) fn prologue<D: CmmaMain, F: Float, FC: Float, FA: Float>(
     lhs: &Tensor<F>,
     rhs: &Tensor<F>,
     out: &mut Tensor<F>,
     #[comptime] comptime_info: ComptimeCmmaInfo,
-) -> (RuntimeCmmaInfo, Fragments<F, FC>, SharedMemories<FC>) {
+) -> (RuntimeCmmaInfo, Fragments<FA, FC>, SharedMemories<FC>) {
     let runtime_info = get_runtime_info::<F, D>(lhs, rhs, out, comptime_info);
-    let fragments = make_fragments::<F, FC>(comptime_info);
+    let fragments = make_fragments::<FA, FC>(comptime_info);
     let shared_memories = make_shared_memories::<FC>(comptime_info);
 
     (runtime_info, fragments, shared_memories)
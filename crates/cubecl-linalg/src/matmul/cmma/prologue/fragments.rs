@@ -4,31 +4,31 @@ use cubecl_core::{self as cubecl, prelude::*};
 use super::super::config::ComptimeCmmaInfo;
 
 #[derive(CubeType)]
-pub(crate) struct Fragments<F: Float, FC: Float> {
-    pub accumulators: Sequence<cmma::Matrix<F>>,
+pub(crate) struct Fragments<FA: Float, FC: Float> {
+    pub accumulators: Sequence<cmma::Matrix<FA>>,
     pub lhs: cmma::Matrix<FC>,
     pub rhs: cmma::Matrix<FC>,
 }
 
 #[cube]
-pub(crate) fn make_fragments<F: Float, FC: Float>(
+pub(crate) fn make_fragments<FA: Float, FC: Float>(
     #[comptime] config: ComptimeCmmaInfo,
-) -> Fragments<F, FC> {
+) -> Fragments<FA, FC> {
     let num_accumulators = config.num_accumulators;
     let tile_size_m = config.tile_size_m;
     let tile_size_n = config.tile_size_n;
     let tile_size_k = config.tile_size_k;
-    let mut accumulators = Sequence::<cmma::Matrix<F>>::new();
+    let mut accumulators = Sequence::<cmma::Matrix<FA>>::new();
 
     #[unroll]
     for _ in 0..num_accumulators {
-        let acc = cmma::Matrix::<F>::from_value(
+        let acc = cmma::Matrix::<FA>::from_value(
             cmma::MatrixIdent::Accumulator,
             tile_size_m,
             tile_size_n,
             tile_size_k,
             cmma::MatrixLayout::Undefined,
-            F::new(0.0),
+            FA::new(0.0),
         );
 
         accumulators.push(acc);
@@ -56,7 +56,7 @@ pub(crate) fn make_fragments<F: Float, FC: Float>(
         )
     };
 
-    Fragments::<F, FC> {
+    Fragments::<FA, FC> {
         accumulators,
         lhs,
         rhs,
@@ -0,0 +1,84 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::tensor::TensorHandle;
+
+/// An elementwise function applied in-register to the values computed by a matmul before they
+/// are stored, avoiding a separate kernel reading the output back from global memory.
+///
+/// The epilogue can read an `operand` tensor passed at launch, such as a bias or a residual.
+/// It is launched without vectorization, so it is indexed by element.
+///
+/// ```ignore
+/// pub struct BiasGelu;
+///
+/// #[cube]
+/// impl<F: Float> FusedEpilogue<F> for BiasGelu {
+///     fn apply(value: F, operand: &Tensor<F>, _row: u32, col: u32, _position: u32) -> F {
+///         let x = value + operand[col];
+///         x * (F::erf(x / F::sqrt(F::new(2.0))) + F::new(1.0)) / F::new(2.0)
+///     }
+/// }
+/// ```
+#[cube]
+pub trait FusedEpilogue<F: Float>: Send + Sync + 'static {
+    /// Transform the `value` of the output at `row` and `col`, whose index in the output buffer
+    /// is `position`.
+    fn apply(value: F, operand: &Tensor<F>, row: u32, col: u32, position: u32) -> F;
+}
+
+/// Store the values as they are computed.
+pub struct Identity;
+
+/// Apply a rectified linear unit.
+pub struct Relu;
+
+/// Add the operand of shape `[n]` as a bias for each column.
+pub struct BiasAdd;
+
+/// Add the operand of the same shape as the output, such as a residual connection.
+pub struct ResidualAdd;
+
+#[cube]
+impl<F: Float> FusedEpilogue<F> for Identity {
+    fn apply(value: F, _operand: &Tensor<F>, _row: u32, _col: u32, _position: u32) -> F {
+        value
+    }
+}
+
+#[cube]
+impl<F: Float> FusedEpilogue<F> for Relu {
+    fn apply(value: F, _operand: &Tensor<F>, _row: u32, _col: u32, _position: u32) -> F {
+        F::max(value, F::new(0.0))
+    }
+}
+
+#[cube]
+impl<F: Float> FusedEpilogue<F> for BiasAdd {
+    fn apply(value: F, operand: &Tensor<F>, _row: u32, col: u32, _position: u32) -> F {
+        value + operand[col]
+    }
+}
+
+#[cube]
+impl<F: Float> FusedEpilogue<F> for ResidualAdd {
+    fn apply(value: F, operand: &Tensor<F>, _row: u32, _col: u32, position: u32) -> F {
+        value + operand[position]
+    }
+}
+
+/// The tensor read by the epilogue, or a placeholder for epilogues without operand, since the
+/// matmul kernels always bind one.
+pub(crate) fn epilogue_operand<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    operand: Option<TensorHandleRef<'_, R>>,
+) -> TensorHandle<R, F> {
+    match operand {
+        Some(operand) => TensorHandle::new(
+            operand.shape.to_vec(),
+            operand.strides.to_vec(),
+            operand.handle.clone(),
+        ),
+        None => TensorHandle::empty(client, vec![1]),
+    }
+}
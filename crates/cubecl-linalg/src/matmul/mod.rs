@@ -1,9 +1,16 @@
-use cmma::config::{CmmaConfig, PredefinedCmmaConfig};
+use cmma::{
+    config::{CmmaConfig, PredefinedCmmaConfig},
+    CmmaPrecision,
+};
 use cubecl_core::prelude::*;
+use fused_epilogue::{FusedEpilogue, Identity};
 
 /// Contains algorithms for cooperative matrix multiplication.
 pub mod cmma;
 
+/// Contains the elementwise functions fused at the end of matrix multiplications.
+pub mod fused_epilogue;
+
 /// Contains algorithms for tiling 2d matrix multiplication when cooperative matrix are not
 /// available.
 pub mod tiling2d;
@@ -18,10 +25,65 @@ pub fn launch_ref<R: Runtime, F: Float>(
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
 ) {
-    let cmma_config: CmmaConfig = PredefinedCmmaConfig::M128K16.into();
-    if cmma::is_available::<R>(client, &cmma_config).is_ok() {
-        cmma::launch_ref::<R, F>(client, lhs, rhs, out, cmma_config);
+    launch_ref_with_precision::<R, F>(client, lhs, rhs, out, CmmaPrecision::Half);
+}
+
+/// Launch a matrix multiplication kernel, whose inputs are consumed by tensor cores with the
+/// given [precision](CmmaPrecision).
+///
+/// When the device doesn't support the precision, the plain tiling 2d matrix multiplication is
+/// launched instead.
+pub fn launch_ref_with_precision<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    precision: CmmaPrecision,
+) {
+    launch_ref_mixed::<R, F, F>(client, lhs, rhs, out, precision);
+}
+
+/// Launch a matrix multiplication kernel with mixed precision: the `F` inputs are multiplied with
+/// the given [precision](CmmaPrecision) and accumulated in `FA`, e.g. `f16` inputs with `f32`
+/// accumulation, before being written to the `F` output.
+///
+/// When the device doesn't support the precision, the plain tiling 2d matrix multiplication is
+/// launched instead, computing and accumulating in `FA`.
+pub fn launch_ref_mixed<R: Runtime, F: Float, FA: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    precision: CmmaPrecision,
+) {
+    launch_ref_fused::<R, F, FA, Identity>(client, lhs, rhs, out, None, precision);
+}
+
+/// Launch a matrix multiplication kernel like [launch_ref_mixed], applying the
+/// [epilogue](FusedEpilogue) `E` to the values before they are stored, such as a bias add with
+/// the bias as operand, instead of launching a separate elementwise kernel on the output.
+pub fn launch_ref_fused<R: Runtime, F: Float, FA: Float, E: FusedEpilogue<F>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    operand: Option<TensorHandleRef<'_, R>>,
+    precision: CmmaPrecision,
+) {
+    let cmma_config: CmmaConfig = match precision {
+        CmmaPrecision::Half => PredefinedCmmaConfig::M128K16.into(),
+        CmmaPrecision::Tf32 => PredefinedCmmaConfig::M128K16Tf32.into(),
+    };
+    if cmma::is_available_mixed::<R, FA>(client, &cmma_config).is_ok() {
+        cmma::launch_ref_fused::<R, F, FA, E>(client, lhs, rhs, out, operand, cmma_config);
     } else {
-        tiling2d::launch_ref::<R, F>(client, lhs, rhs, out, Default::default());
+        tiling2d::launch_ref_fused::<R, F, FA, E>(
+            client,
+            lhs,
+            rhs,
+            out,
+            operand,
+            Default::default(),
+        );
     }
 }
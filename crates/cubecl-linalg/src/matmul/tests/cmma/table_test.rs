@@ -69,6 +69,10 @@ impl Iterator for CmmaConfigIterator {
                 PredefinedCmmaConfig::BuffersFirst,
                 "buffers_first".to_string(),
             )),
+            15 => Some((
+                PredefinedCmmaConfig::M128K16Tf32,
+                "m128_k16_tf32".to_string(),
+            )),
             16 => Some((PredefinedCmmaConfig::M16K32N64, "m_16_k32_n64".to_string())),
             17 => Some((PredefinedCmmaConfig::M32K16N64, "m_32_k16_n64".to_string())),
             _ => None,
@@ -5,7 +5,7 @@ use crate::matmul::{
     tests::matmul_test_case::MatmulTestCase,
 };
 
-use super::super::test_utils::assert_equals_approx;
+use crate::tests::test_utils::assert_equals_approx;
 
 #[derive(Copy, Clone)]
 pub enum MatmulTest {
@@ -2,9 +2,9 @@ use cubecl_core::CubeElement;
 use cubecl_core::{client::ComputeClient, Runtime};
 use half::f16;
 
-use crate::tensor::TensorHandle;
+use crate::{tensor::TensorHandle, tests::test_utils::random_tensor};
 
-use super::test_utils::{create_empty, random_tensor};
+use super::test_utils::create_empty;
 
 pub(crate) struct MatmulTestCase {
     pub m: usize,
@@ -75,31 +75,6 @@ pub(crate) fn assert_equals<R: Runtime>(
     pretty_assertions::assert_eq!(actual, expected);
 }
 
-pub(crate) fn assert_equals_approx<R: Runtime>(
-    client: &ComputeClient<R::Server, R::Channel>,
-    output: Handle,
-    expected: &[f32],
-    epsilon: f32,
-) -> Result<(), String> {
-    let actual = client.read(output.binding());
-    let actual = f32::from_bytes(&actual);
-
-    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
-        if (a - e).abs() >= epsilon {
-            return Err(format!(
-            "Values differ more than epsilon: index={} actual={}, expected={}, difference={}, epsilon={}",
-            i,
-            a,
-            e,
-            (a - e).abs(),
-            epsilon
-            ));
-        }
-    }
-
-    Ok(())
-}
-
 pub fn make_tiling2d_config(m: usize, k: usize, n: usize) -> CubeTiling2dConfig {
     let tiling2d_config = Tiling2dConfig {
         block_size_m: 8,
@@ -109,27 +84,3 @@ pub fn make_tiling2d_config(m: usize, k: usize, n: usize) -> CubeTiling2dConfig
     };
     CubeTiling2dConfig::new(&tiling2d_config, m, k, n, false, false)
 }
-
-pub(crate) fn random_tensor<R: Runtime>(
-    client: &ComputeClient<R::Server, R::Channel>,
-    shape: Vec<usize>,
-) -> TensorHandle<R, f32> {
-    let data = generate_random_data(shape.iter().product());
-    let handle = client.create(cast_slice(&data));
-    TensorHandle::new_contiguous(shape, handle)
-}
-
-pub(crate) fn generate_random_data(num_elements: usize) -> Vec<f32> {
-    fn lcg(seed: &mut u64) -> f32 {
-        const A: u64 = 1664525;
-        const C: u64 = 1013904223;
-        const M: f64 = 2u64.pow(32) as f64;
-
-        *seed = (A.wrapping_mul(*seed).wrapping_add(C)) % (1u64 << 32);
-        (*seed as f64 / M * 2.0 - 1.0) as f32
-    }
-
-    let mut seed = 12345;
-
-    (0..num_elements).map(|_| lcg(&mut seed)).collect()
-}
@@ -28,7 +28,7 @@ fn tile_outer_product_test<F: Float>(
     for i in 0..tile_size * tile_size {
         results[i] = F::new(0.);
     }
-    tile_outer_product::<F>(register_m, register_n, results, config)
+    tile_outer_product::<F, F>(register_m, register_n, results, config)
 }
 
 /// Exported test
@@ -105,7 +105,7 @@ fn compute_loop_test<F: Float>(
         skip_col: 0,
     };
 
-    compute_loop(coordinates, shared_lhs, shared_rhs, results, config)
+    compute_loop::<F, F>(coordinates, shared_lhs, shared_rhs, results, config)
 }
 
 /// Exported test
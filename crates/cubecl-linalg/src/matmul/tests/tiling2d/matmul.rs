@@ -1,8 +1,8 @@
-use cubecl_core::Runtime;
+use cubecl_core::{CubeElement, Runtime};
 
-use crate::matmul::{
-    tests::{matmul_test_case::MatmulTestCase, test_utils::assert_equals_approx},
-    tiling2d,
+use crate::{
+    matmul::{fused_epilogue::BiasAdd, tests::matmul_test_case::MatmulTestCase, tiling2d},
+    tests::test_utils::{assert_equals_approx, random_tensor},
 };
 
 pub fn test_matmul_tiling2d_one_cube<R: Runtime>(device: &R::Device) {
@@ -49,6 +49,41 @@ pub fn test_matmul_tiling2d_with_batches<R: Runtime>(device: &R::Device) {
     test_tiling2d::<R>(case, device);
 }
 
+pub fn test_matmul_tiling2d_fused_bias_add<R: Runtime>(device: &R::Device) {
+    let case = MatmulTestCase {
+        m: 60,
+        k: 60,
+        n: 60,
+        batch: 2,
+    };
+    let client = R::client(device);
+    let lhs = case.random_lhs::<R>(&client);
+    let rhs = case.random_rhs::<R>(&client);
+    let bias = random_tensor::<R>(&client, vec![case.n]);
+    let out = case.empty_out(&client);
+
+    let bias_values = f32::from_bytes(&client.read(bias.handle.clone().binding())).to_vec();
+    let expected: Vec<f32> = case
+        .matmul_cpu(&lhs, &rhs, &client)
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| value + bias_values[i % case.n])
+        .collect();
+
+    tiling2d::launch_ref_fused::<R, f32, f32, BiasAdd>(
+        &client,
+        lhs.as_ref(),
+        rhs.as_ref(),
+        out.as_ref(),
+        Some(bias.as_ref()),
+        Default::default(),
+    );
+
+    if let Err(e) = assert_equals_approx::<R>(&client, out.handle, &expected, 10e-3) {
+        panic!("{}", e);
+    }
+}
+
 fn test_tiling2d<R: Runtime>(case: MatmulTestCase, device: &R::Device) {
     let client = R::client(device);
     let lhs = case.random_lhs::<R>(&client);
@@ -2,26 +2,29 @@ use cubecl_core::prelude::*;
 use cubecl_core::{self as cubecl, CubeType};
 
 use super::{block_loop::block_loop, config::CubeTiling2dConfig};
+use crate::matmul::fused_epilogue::FusedEpilogue;
 
 /// Most common tile size, the one used in most tests.
 pub(crate) const TILE_SIZE: usize = 4;
 
 #[cube(launch_unchecked)]
 #[allow(unused_mut)]
-pub fn tiling2d_cube_kernel<F: Float>(
+pub fn tiling2d_cube_kernel<F: Float, FA: Float, E: FusedEpilogue<F>>(
     lhs: &Tensor<Line<F>>,
     rhs: &Tensor<Line<F>>,
     out: &mut Tensor<Line<F>>,
+    operand: &Tensor<F>,
     #[comptime] config: CubeTiling2dConfig,
 ) {
     let dims = get_dims::<F>(lhs, rhs);
     let coordinates = calculate_coordinates(CUBE_POS_X, CUBE_POS_Y, UNIT_POS, config);
     let offsets = calculate_batch_offsets::<F>(lhs, rhs, out, CUBE_POS_Z);
     let shared_memories = make_shared_memories::<F>(config);
-    block_loop::<F>(
+    block_loop::<F, FA, E>(
         lhs,
         rhs,
         out,
+        operand,
         coordinates,
         offsets,
         shared_memories,
@@ -1,6 +1,8 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
+use crate::matmul::fused_epilogue::FusedEpilogue;
+
 use super::{
     base::{BatchOffsets, Coordinates, Dimensions, SharedMemories},
     compute_loop::compute_loop,
@@ -11,17 +13,18 @@ use super::{
 };
 
 #[cube]
-pub(crate) fn block_loop<F: Float>(
+pub(crate) fn block_loop<F: Float, FA: Float, E: FusedEpilogue<F>>(
     lhs: &Tensor<Line<F>>,
     rhs: &Tensor<Line<F>>,
     out: &mut Tensor<Line<F>>,
+    operand: &Tensor<F>,
     coordinates: Coordinates,
     offsets: BatchOffsets,
     shared: SharedMemories<F>,
     #[comptime] config: CubeTiling2dConfig,
     dims: Dimensions,
 ) {
-    let mut results = init_results::<F>(config);
+    let mut results = init_results::<FA>(config);
     let block_size_k = config.block_size_k;
     let n_loops = (dims.k + block_size_k - 1) / block_size_k;
 
@@ -41,11 +44,12 @@ pub(crate) fn block_loop<F: Float>(
 
         sync_units();
 
-        compute_loop::<F>(coordinates, shared.lhs, shared.rhs, &mut results, config);
+        compute_loop::<F, FA>(coordinates, shared.lhs, shared.rhs, &mut results, config);
 
         sync_units();
     }
 
+    let results = epilogue::<F, FA, E>(&results, operand, coordinates, offsets.out, dims, config);
     write_to_output::<F, TileWriter<F>>(out, &results, coordinates, offsets.out, dims, config);
 }
 
@@ -62,3 +66,35 @@ fn init_results<F: Float>(#[comptime] config: CubeTiling2dConfig) -> Array<F> {
 
     results
 }
+
+#[cube]
+fn epilogue<F: Float, FA: Float, E: FusedEpilogue<F>>(
+    results: &Array<FA>,
+    operand: &Tensor<F>,
+    coordinates: Coordinates,
+    offset_output: u32,
+    dims: Dimensions,
+    #[comptime] config: CubeTiling2dConfig,
+) -> Array<F> {
+    let tile_size = config.tile_size;
+    let unroll = config.unroll_tile;
+    let row_base = coordinates.skip_row + coordinates.unit_row;
+    let col_base = coordinates.skip_col + coordinates.unit_col;
+
+    let mut output = Array::<F>::new(tile_size * tile_size);
+    #[unroll(unroll)]
+    for i in 0..tile_size * tile_size {
+        let row = row_base + i / tile_size;
+        let col = col_base + i % tile_size;
+        let mut value = F::cast_from(results[i]);
+
+        // The values outside of the output are never written.
+        if row < dims.m && col < dims.n {
+            value = E::apply(value, operand, row, col, offset_output + row * dims.n + col);
+        }
+
+        output[i] = value;
+    }
+
+    output
+}
@@ -1,8 +1,9 @@
 use std::cmp::max;
 
-use cubecl_core::{prelude::*, Compiler};
+use cubecl_core::prelude::*;
 
 use crate::{
+    matmul::fused_epilogue::{epilogue_operand, FusedEpilogue, Identity},
     matmul::tiling2d::{
         base::tiling2d_cube_kernel,
         config::{tiling2d_cube_count, tiling2d_cube_dim, CubeTiling2dConfig},
@@ -32,10 +33,35 @@ pub fn matmul_tiling_2d_ref<R: Runtime, F: Float>(
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
     config: Tiling2dConfig,
+) {
+    matmul_tiling_2d_ref_mixed::<R, F, F>(client, lhs, rhs, out, config);
+}
+
+/// Matrix multiplication using tiling 2d algorithm, where the products of the `F` inputs are
+/// computed and accumulated in `FA` before being written to the `F` output.
+pub fn matmul_tiling_2d_ref_mixed<R: Runtime, F: Float, FA: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    config: Tiling2dConfig,
+) {
+    matmul_tiling_2d_ref_fused::<R, F, FA, Identity>(client, lhs, rhs, out, None, config);
+}
+
+/// Matrix multiplication using tiling 2d algorithm like [matmul_tiling_2d_ref_mixed], applying
+/// the [epilogue](FusedEpilogue) `E` to the values before they are stored, with the given operand.
+pub fn matmul_tiling_2d_ref_fused<R: Runtime, F: Float, FA: Float, E: FusedEpilogue<F>>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: TensorHandleRef<'_, R>,
+    rhs: TensorHandleRef<'_, R>,
+    out: TensorHandleRef<'_, R>,
+    operand: Option<TensorHandleRef<'_, R>>,
+    config: Tiling2dConfig,
 ) {
     assert!(
         F::as_elem().size() * config.block_size_k * max(config.block_size_m, config.block_size_n)
-            <= <R::Compiler as Compiler>::max_shared_memory_size(),
+            <= client.properties().hardware_properties().max_shared_memory_size,
         "Shared memory limit will be busted. "
     );
     let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_layout(tensor.strides) {
@@ -46,41 +72,53 @@ pub fn matmul_tiling_2d_ref<R: Runtime, F: Float>(
         } => true,
         MatrixLayout::HighlyPermuted => false,
     };
+    let operand = epilogue_operand::<R, F>(client, operand);
     let lhs_correct_layout = check_layout(&lhs);
     let rhs_correct_layout = check_layout(&rhs);
 
     match (lhs_correct_layout, rhs_correct_layout) {
-        (true, true) => matmul_tiling_2d_ref_no_check::<R, F>(client, lhs, rhs, out, config),
-        (true, false) => matmul_tiling_2d_ref_no_check::<R, F>(
+        (true, true) => matmul_tiling_2d_ref_no_check::<R, F, FA, E>(
+            client,
+            lhs,
+            rhs,
+            out,
+            operand.as_ref(),
+            config,
+        ),
+        (true, false) => matmul_tiling_2d_ref_no_check::<R, F, FA, E>(
             client,
             lhs,
             into_contiguous::<R, F>(client, rhs).as_ref(),
             out,
+            operand.as_ref(),
             config,
         ),
-        (false, true) => matmul_tiling_2d_ref_no_check::<R, F>(
+        (false, true) => matmul_tiling_2d_ref_no_check::<R, F, FA, E>(
             client,
             into_contiguous::<R, F>(client, lhs).as_ref(),
             rhs,
             out,
+            operand.as_ref(),
             config,
         ),
-        (false, false) => matmul_tiling_2d_ref_no_check::<R, F>(
+        (false, false) => matmul_tiling_2d_ref_no_check::<R, F, FA, E>(
             client,
             into_contiguous::<R, F>(client, lhs).as_ref(),
             into_contiguous::<R, F>(client, rhs).as_ref(),
             out,
+            operand.as_ref(),
             config,
         ),
     }
 }
 
 /// Matrix multiplication using tiling 2d algorithm.
-fn matmul_tiling_2d_ref_no_check<R: Runtime, F: Float>(
+fn matmul_tiling_2d_ref_no_check<R: Runtime, F: Float, FA: Float, E: FusedEpilogue<F>>(
     client: &ComputeClient<R::Server, R::Channel>,
     lhs: TensorHandleRef<'_, R>,
     rhs: TensorHandleRef<'_, R>,
     out: TensorHandleRef<'_, R>,
+    operand: TensorHandleRef<'_, R>,
     config: Tiling2dConfig,
 ) {
     let rank = lhs.strides.len();
@@ -126,13 +164,15 @@ fn matmul_tiling_2d_ref_no_check<R: Runtime, F: Float>(
     let cube_config = CubeTiling2dConfig::new(&config, m, k, n, lhs_transposed, rhs_transposed);
 
     unsafe {
-        tiling2d_cube_kernel::launch_unchecked::<F, R>(
+        tiling2d_cube_kernel::launch_unchecked::<F, FA, E, R>(
             client,
             cube_count,
             cube_dim,
             TensorArg::from_raw_parts(lhs.handle, lhs.strides, lhs.shape, lhs_vectorization),
             TensorArg::from_raw_parts(rhs.handle, rhs.strides, rhs.shape, rhs_vectorization),
             TensorArg::from_raw_parts(out.handle, out.strides, out.shape, out_vectorization),
+            // The epilogue reads the operand by element.
+            TensorArg::from_raw_parts(operand.handle, operand.strides, operand.shape, 1),
             cube_config,
         );
     }
@@ -10,3 +10,5 @@ pub(crate) mod write_output;
 
 pub use launch::matmul_tiling_2d as launch;
 pub use launch::matmul_tiling_2d_ref as launch_ref;
+pub use launch::matmul_tiling_2d_ref_fused as launch_ref_fused;
+pub use launch::matmul_tiling_2d_ref_mixed as launch_ref_mixed;
@@ -4,10 +4,10 @@ use cubecl_core::prelude::*;
 use super::config::CubeTiling2dConfig;
 
 #[cube]
-pub(crate) fn tile_outer_product<F: Float>(
+pub(crate) fn tile_outer_product<F: Float, FA: Float>(
     register_m: Line<F>,
     register_n: Line<F>,
-    results: &mut Array<F>,
+    results: &mut Array<FA>,
     #[comptime] config: CubeTiling2dConfig,
 ) {
     let tile_size = config.tile_size;
@@ -18,7 +18,7 @@ pub(crate) fn tile_outer_product<F: Float>(
         let res_pos_base = res_idx_m * tile_size;
         #[unroll(unroll)]
         for res_idx_n in 0..register_n.size() {
-            let mul: F = register_m[res_idx_m] * register_n[res_idx_n];
+            let mul = FA::cast_from(register_m[res_idx_m]) * FA::cast_from(register_n[res_idx_n]);
             results[res_pos_base + res_idx_n] += mul;
         }
     }
@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use cubecl_core::{prelude::*, server::Handle, CubeElement};
+
+/// A sparse matrix in compressed sparse row format.
+///
+/// The nonzero values of the row `r` are at the positions `row_offsets[r]..row_offsets[r + 1]`
+/// of `values`, and their columns at the same positions of `column_indices`.
+pub struct CsrMatrix<R: Runtime, F: Float> {
+    /// The `u32` offsets of the rows in the nonzero values, with a last offset being the number
+    /// of nonzero values.
+    pub row_offsets: Handle,
+    /// The `u32` column of each nonzero value.
+    pub column_indices: Handle,
+    /// The nonzero values.
+    pub values: Handle,
+    /// The number of rows.
+    pub rows: usize,
+    /// The number of columns.
+    pub cols: usize,
+    /// The number of nonzero values.
+    pub nnz: usize,
+    _runtime: PhantomData<(R, F)>,
+}
+
+/// A sparse matrix in coordinate format, where the nonzero value `i` is at the row
+/// `row_indices[i]` and the column `column_indices[i]`.
+///
+/// Unlike [CSR](CsrMatrix), the values don't need to be sorted, and the same position can be
+/// repeated, its values being summed.
+pub struct CooMatrix<R: Runtime, F: Float> {
+    /// The `u32` row of each nonzero value.
+    pub row_indices: Handle,
+    /// The `u32` column of each nonzero value.
+    pub column_indices: Handle,
+    /// The nonzero values.
+    pub values: Handle,
+    /// The number of rows.
+    pub rows: usize,
+    /// The number of columns.
+    pub cols: usize,
+    /// The number of nonzero values.
+    pub nnz: usize,
+    _runtime: PhantomData<(R, F)>,
+}
+
+impl<R: Runtime, F: Float> CsrMatrix<R, F> {
+    /// Create a matrix from buffers already on the device.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        nnz: usize,
+        row_offsets: Handle,
+        column_indices: Handle,
+        values: Handle,
+    ) -> Self {
+        Self {
+            row_offsets,
+            column_indices,
+            values,
+            rows,
+            cols,
+            nnz,
+            _runtime: PhantomData,
+        }
+    }
+
+    /// Upload a matrix from host buffers.
+    pub fn from_host(
+        client: &ComputeClient<R::Server, R::Channel>,
+        cols: usize,
+        row_offsets: &[u32],
+        column_indices: &[u32],
+        values: &[F],
+    ) -> Self
+    where
+        F: CubeElement,
+    {
+        assert!(
+            !row_offsets.is_empty(),
+            "The row offsets should end with the number of nonzero values"
+        );
+        assert_eq!(
+            *row_offsets.last().unwrap() as usize,
+            values.len(),
+            "The last row offset should be the number of nonzero values"
+        );
+        assert_eq!(
+            column_indices.len(),
+            values.len(),
+            "Each nonzero value should have a column"
+        );
+
+        Self::new(
+            row_offsets.len() - 1,
+            cols,
+            values.len(),
+            client.create(u32::as_bytes(row_offsets)),
+            client.create(u32::as_bytes(column_indices)),
+            client.create(F::as_bytes(values)),
+        )
+    }
+}
+
+impl<R: Runtime, F: Float> CooMatrix<R, F> {
+    /// Create a matrix from buffers already on the device.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        nnz: usize,
+        row_indices: Handle,
+        column_indices: Handle,
+        values: Handle,
+    ) -> Self {
+        Self {
+            row_indices,
+            column_indices,
+            values,
+            rows,
+            cols,
+            nnz,
+            _runtime: PhantomData,
+        }
+    }
+
+    /// Upload a matrix from host buffers.
+    pub fn from_host(
+        client: &ComputeClient<R::Server, R::Channel>,
+        rows: usize,
+        cols: usize,
+        row_indices: &[u32],
+        column_indices: &[u32],
+        values: &[F],
+    ) -> Self
+    where
+        F: CubeElement,
+    {
+        assert!(
+            row_indices.len() == values.len() && column_indices.len() == values.len(),
+            "Each nonzero value should have a row and a column"
+        );
+
+        Self::new(
+            rows,
+            cols,
+            values.len(),
+            client.create(u32::as_bytes(row_indices)),
+            client.create(u32::as_bytes(column_indices)),
+            client.create(F::as_bytes(values)),
+        )
+    }
+}
+
+impl<R: Runtime, F: Float> Clone for CsrMatrix<R, F> {
+    fn clone(&self) -> Self {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.nnz,
+            self.row_offsets.clone(),
+            self.column_indices.clone(),
+            self.values.clone(),
+        )
+    }
+}
+
+impl<R: Runtime, F: Float> Clone for CooMatrix<R, F> {
+    fn clone(&self) -> Self {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.nnz,
+            self.row_indices.clone(),
+            self.column_indices.clone(),
+            self.values.clone(),
+        )
+    }
+}
+
+impl<R: Runtime, F: Float> core::fmt::Debug for CsrMatrix<R, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CsrMatrix")
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("nnz", &self.nnz)
+            .finish()
+    }
+}
+
+impl<R: Runtime, F: Float> core::fmt::Debug for CooMatrix<R, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CooMatrix")
+            .field("rows", &self.rows)
+            .field("cols", &self.cols)
+            .field("nnz", &self.nnz)
+            .finish()
+    }
+}
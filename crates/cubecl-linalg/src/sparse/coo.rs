@@ -0,0 +1,135 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::CooMatrix;
+use crate::tensor::{atomic_add, matrix_layout, MatrixLayout, TensorHandle};
+
+#[cube(launch_unchecked)]
+fn coo_spmm_kernel<F: Float>(
+    row_indices: &Array<u32>,
+    column_indices: &Array<u32>,
+    values: &Array<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<AtomicU32>,
+) {
+    let n = output.shape(1);
+    if ABSOLUTE_POS >= values.len() * n {
+        return;
+    }
+
+    let index = ABSOLUTE_POS / n;
+    let col = ABSOLUTE_POS % n;
+    let value = values[index] * dense[column_indices[index] * n + col];
+
+    atomic_add::<F>(output, row_indices[index] * n + col, value);
+}
+
+#[cube(launch_unchecked)]
+fn coo_spmm_ordered_kernel<F: Float>(
+    row_indices: &Array<u32>,
+    column_indices: &Array<u32>,
+    values: &Array<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<F>,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let n = output.shape(1);
+    let row = ABSOLUTE_POS / n;
+    let col = ABSOLUTE_POS % n;
+    let mut sum = F::new(0.0);
+
+    // The products are added in the order of the values, whatever the scheduling.
+    for index in 0..values.len() {
+        if row_indices[index] == row {
+            sum += values[index] * dense[column_indices[index] * n + col];
+        }
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+/// Multiply the sparse matrix of shape `[m, k]` by the dense matrix of shape `[k, n]`, returning
+/// the dense output of shape `[m, n]`.
+///
+/// Each unit adds the product of one nonzero value atomically, so only elements of 32 bits are
+/// supported. Convert the matrix to [CSR](super::CsrMatrix) for repeated products.
+///
+/// With a [deterministic](ComputeClient::deterministic) client, each unit instead computes one
+/// element of the output, reading all the nonzero values.
+pub fn coo_spmm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CooMatrix<R, F>,
+    dense: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    assert_eq!(
+        dense.shape.len(),
+        2,
+        "The dense matrix should have a rank of 2"
+    );
+    assert_eq!(
+        dense.shape[0], sparse.cols,
+        "The dense matrix should have as many rows as the sparse matrix has columns"
+    );
+    assert_eq!(
+        matrix_layout(dense.strides),
+        MatrixLayout::Contiguous,
+        "The dense matrix should be contiguous"
+    );
+    assert_eq!(
+        F::as_elem().size(),
+        4,
+        "Sparse COO products only support elements of 32 bits"
+    );
+
+    let n = dense.shape[1];
+    let output = TensorHandle::<R, F>::zeros(client, vec![sparse.rows, n]);
+    if sparse.nnz == 0 {
+        return output;
+    }
+
+    let cube_dim = CubeDim::default();
+
+    if client.is_deterministic() {
+        let cube_count = calculate_cube_count_elemwise(sparse.rows * n, cube_dim);
+
+        unsafe {
+            coo_spmm_ordered_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count,
+                cube_dim,
+                ArrayArg::from_raw_parts(&sparse.row_indices, sparse.nnz, 1),
+                ArrayArg::from_raw_parts(&sparse.column_indices, sparse.nnz, 1),
+                ArrayArg::from_raw_parts(&sparse.values, sparse.nnz, 1),
+                dense.as_tensor_arg(1),
+                output.as_arg(1),
+            );
+        }
+
+        return output;
+    }
+
+    let cube_count = calculate_cube_count_elemwise(sparse.nnz * n, cube_dim);
+
+    unsafe {
+        coo_spmm_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts(&sparse.row_indices, sparse.nnz, 1),
+            ArrayArg::from_raw_parts(&sparse.column_indices, sparse.nnz, 1),
+            ArrayArg::from_raw_parts(&sparse.values, sparse.nnz, 1),
+            dense.as_tensor_arg(1),
+            TensorArg::from_raw_parts_typed::<AtomicU32>(
+                &output.handle,
+                &output.strides,
+                &output.shape,
+                1,
+            ),
+        );
+    }
+
+    output
+}
@@ -0,0 +1,308 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, Feature};
+
+use super::CsrMatrix;
+use crate::tensor::{atomic_add, matrix_layout, MatrixLayout, TensorHandle};
+
+/// How the rows of a [CSR matrix](CsrMatrix) are distributed by [spmm].
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum SparseStrategy {
+    /// Each unit computes one element of the output, reading the nonzero values of its row.
+    /// Fast when rows are short and of similar lengths.
+    RowPerUnit,
+    /// Each subcube computes one row of the output, its units splitting the nonzero values of
+    /// the row. Fast when rows are long. Requires [subcube](Feature::Subcube) operations.
+    RowPerSubcube,
+    /// Each unit processes the same number of nonzero values, accumulating the rows it shares
+    /// with other units atomically, so the work stays balanced when row lengths are skewed, as
+    /// in power-law graphs. Only elements of 32 bits are supported.
+    ///
+    /// A [deterministic](ComputeClient::deterministic) client uses the
+    /// [row per unit](SparseStrategy::RowPerUnit) strategy instead.
+    MergeBased,
+}
+
+/// The number of nonzero values processed by a unit with the
+/// [merge-based](SparseStrategy::MergeBased) strategy.
+const ITEMS_PER_UNIT: u32 = 32;
+
+#[cube(launch_unchecked)]
+fn spmm_row_per_unit_kernel<F: Float>(
+    row_offsets: &Array<u32>,
+    column_indices: &Array<u32>,
+    values: &Array<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<F>,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let n = output.shape(1);
+    let row = ABSOLUTE_POS / n;
+    let col = ABSOLUTE_POS % n;
+    let mut sum = F::new(0.0);
+
+    // Units of the same row read consecutive columns of the dense matrix.
+    for index in row_offsets[row]..row_offsets[row + 1] {
+        sum += values[index] * dense[column_indices[index] * n + col];
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+#[cube(launch_unchecked)]
+fn spmm_row_per_subcube_kernel<F: Float>(
+    row_offsets: &Array<u32>,
+    column_indices: &Array<u32>,
+    values: &Array<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<F>,
+) {
+    // The whole subcube shares the row, so it exits uniformly.
+    let row = CUBE_POS_X * CUBE_DIM_Y + UNIT_POS_Y;
+    if row >= output.shape(0) {
+        return;
+    }
+
+    let n = output.shape(1);
+    let start = row_offsets[row];
+    let end = row_offsets[row + 1];
+
+    for col in 0..n {
+        let mut sum = F::new(0.0);
+        let mut index = start + UNIT_POS_X;
+
+        while index < end {
+            sum += values[index] * dense[column_indices[index] * n + col];
+            index += SUBCUBE_DIM;
+        }
+
+        let sum = subcube_sum(sum);
+        if UNIT_POS_X == 0 {
+            output[row * n + col] = sum;
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn spmm_merge_based_kernel<F: Float>(
+    row_offsets: &Array<u32>,
+    column_indices: &Array<u32>,
+    values: &Array<F>,
+    dense: &Tensor<F>,
+    output: &mut Tensor<AtomicU32>,
+    #[comptime] items_per_unit: u32,
+) {
+    let num_rows = row_offsets.len() - 1;
+    let nnz = row_offsets[num_rows];
+    let start = ABSOLUTE_POS * items_per_unit;
+    if start >= nnz {
+        return;
+    }
+    let end = Min::min(start + items_per_unit, nnz);
+
+    // The last row starting before the first value, skipping the empty rows.
+    let mut first_row = 0;
+    let mut last_row = num_rows;
+    while first_row < last_row {
+        // Runtime integers have no `div_ceil` in kernels.
+        #[allow(clippy::manual_div_ceil)]
+        let mid = (first_row + last_row + 1) / 2;
+        if row_offsets[mid] <= start {
+            first_row = mid;
+        } else {
+            last_row = mid - 1;
+        }
+    }
+
+    let n = output.shape(1);
+
+    for col in 0..n {
+        let mut row = first_row;
+        let mut sum = F::new(0.0);
+
+        for index in start..end {
+            if row_offsets[row + 1] <= index {
+                // The row can be shared with the previous or the next units.
+                atomic_add::<F>(output, row * n + col, sum);
+                sum = F::new(0.0);
+
+                while row_offsets[row + 1] <= index {
+                    row += 1;
+                }
+            }
+
+            sum += values[index] * dense[column_indices[index] * n + col];
+        }
+
+        atomic_add::<F>(output, row * n + col, sum);
+    }
+}
+
+/// Multiply the sparse matrix of shape `[m, k]` by the dense matrix of shape `[k, n]`, returning
+/// the dense output of shape `[m, n]`.
+///
+/// # Panics
+///
+/// If the strategy isn't supported by the device or the element type.
+pub fn spmm<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CsrMatrix<R, F>,
+    dense: TensorHandleRef<'_, R>,
+    strategy: SparseStrategy,
+) -> TensorHandle<R, F> {
+    assert_eq!(
+        dense.shape.len(),
+        2,
+        "The dense matrix should have a rank of 2"
+    );
+    assert_eq!(
+        dense.shape[0], sparse.cols,
+        "The dense matrix should have as many rows as the sparse matrix has columns"
+    );
+    assert_eq!(
+        matrix_layout(dense.strides),
+        MatrixLayout::Contiguous,
+        "The dense matrix should be contiguous"
+    );
+
+    let n = dense.shape[1];
+    let shape = vec![sparse.rows, n];
+    if sparse.nnz == 0 {
+        return TensorHandle::zeros(client, shape);
+    }
+
+    let row_offsets = unsafe { ArrayArg::from_raw_parts(&sparse.row_offsets, sparse.rows + 1, 1) };
+    let column_indices = unsafe { ArrayArg::from_raw_parts(&sparse.column_indices, sparse.nnz, 1) };
+    let values = unsafe { ArrayArg::from_raw_parts(&sparse.values, sparse.nnz, 1) };
+
+    // The atomic partial sums are added in the order of the scheduling.
+    let strategy = match strategy {
+        SparseStrategy::MergeBased if client.is_deterministic() => SparseStrategy::RowPerUnit,
+        strategy => strategy,
+    };
+
+    match strategy {
+        SparseStrategy::RowPerUnit => {
+            let output = TensorHandle::empty(client, shape);
+            let cube_dim = CubeDim::default();
+            let cube_count = calculate_cube_count_elemwise(sparse.rows * n, cube_dim);
+
+            unsafe {
+                spmm_row_per_unit_kernel::launch_unchecked::<F, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    row_offsets,
+                    column_indices,
+                    values,
+                    dense.as_tensor_arg(1),
+                    output.as_arg(1),
+                );
+            }
+
+            output
+        }
+        SparseStrategy::RowPerSubcube => {
+            assert!(
+                client.properties().feature_enabled(Feature::Subcube),
+                "The row per subcube strategy requires subcube operations"
+            );
+            let output = TensorHandle::empty(client, shape);
+            let subcube_size = client
+                .properties()
+                .hardware_properties()
+                .subcube_size
+                .unwrap_or(32);
+            let rows_per_cube = Ord::max(256 / subcube_size, 1);
+            let cube_dim = CubeDim::new(subcube_size, rows_per_cube, 1);
+            let cube_count = CubeCount::Static((sparse.rows as u32).div_ceil(rows_per_cube), 1, 1);
+
+            unsafe {
+                spmm_row_per_subcube_kernel::launch_unchecked::<F, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    row_offsets,
+                    column_indices,
+                    values,
+                    dense.as_tensor_arg(1),
+                    output.as_arg(1),
+                );
+            }
+
+            output
+        }
+        SparseStrategy::MergeBased => {
+            assert_eq!(
+                F::as_elem().size(),
+                4,
+                "The merge-based strategy only supports elements of 32 bits"
+            );
+            // The units only add their partial sums to the output.
+            let output = TensorHandle::<R, F>::zeros(client, shape);
+            let cube_dim = CubeDim::default();
+            let cube_count = calculate_cube_count_elemwise(
+                sparse.nnz.div_ceil(ITEMS_PER_UNIT as usize),
+                cube_dim,
+            );
+
+            unsafe {
+                spmm_merge_based_kernel::launch_unchecked::<F, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    row_offsets,
+                    column_indices,
+                    values,
+                    dense.as_tensor_arg(1),
+                    TensorArg::from_raw_parts_typed::<AtomicU32>(
+                        &output.handle,
+                        &output.strides,
+                        &output.shape,
+                        1,
+                    ),
+                    ITEMS_PER_UNIT,
+                );
+            }
+
+            output
+        }
+    }
+}
+
+/// Multiply the sparse matrix of shape `[m, k]` by the vector of shape `[k]`, returning the
+/// vector of shape `[m]`.
+pub fn spmv<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CsrMatrix<R, F>,
+    vector: TensorHandleRef<'_, R>,
+    strategy: SparseStrategy,
+) -> TensorHandle<R, F> {
+    assert_eq!(vector.shape.len(), 1, "The vector should have a rank of 1");
+
+    // The vector is a dense matrix with a single column.
+    let shape = [vector.shape[0], 1];
+    let strides = [vector.strides[0], 1];
+    let dense = unsafe { TensorHandleRef::from_raw_parts(vector.handle, &strides, &shape) };
+    let output = spmm(client, sparse, dense, strategy);
+
+    TensorHandle::new_contiguous(vec![sparse.rows], output.handle)
+}
+
+/// The strategies supported by the device for the element type.
+pub fn supported_strategies<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<SparseStrategy> {
+    let mut strategies = vec![SparseStrategy::RowPerUnit];
+
+    if client.properties().feature_enabled(Feature::Subcube) {
+        strategies.push(SparseStrategy::RowPerSubcube);
+    }
+    if F::as_elem().size() == 4 {
+        strategies.push(SparseStrategy::MergeBased);
+    }
+
+    strategies
+}
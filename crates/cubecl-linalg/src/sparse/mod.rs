@@ -0,0 +1,12 @@
+mod base;
+mod coo;
+mod csr;
+mod tune;
+
+pub use base::*;
+pub use coo::*;
+pub use csr::*;
+pub use tune::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
@@ -0,0 +1,168 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{prelude::*, Feature};
+
+use crate::{
+    sparse::{coo_spmm, spmm, spmm_autotune, spmv, CooMatrix, CsrMatrix, SparseStrategy},
+    tensor::TensorHandle,
+};
+
+/// A matrix with empty, short and long rows, stored as row offsets, columns and values.
+///
+/// The values are small multiples of a power of two, so the sums are exact in any order.
+fn skewed_matrix(rows: usize, cols: usize) -> (Vec<u32>, Vec<u32>, Vec<f32>) {
+    let mut row_offsets = vec![0];
+    let mut column_indices = Vec::new();
+    let mut values = Vec::new();
+
+    for row in 0..rows {
+        let length = match row % 7 {
+            0 => cols,
+            1 | 4 => 0,
+            _ => row % 5 + 1,
+        };
+        for index in 0..length {
+            column_indices.push(((row + index * 3) % cols) as u32);
+            values.push(((row + index) % 5) as f32 / 4.0 - 0.5);
+        }
+        row_offsets.push(values.len() as u32);
+    }
+
+    // Columns are unique within a row only when the stride is coprime with the columns.
+    assert_eq!(cols % 3, 1);
+    (row_offsets, column_indices, values)
+}
+
+fn dense_data(rows: usize, cols: usize) -> Vec<f32> {
+    (0..rows * cols).map(|i| (i % 7) as f32 - 3.0).collect()
+}
+
+fn spmm_cpu(
+    row_offsets: &[u32],
+    column_indices: &[u32],
+    values: &[f32],
+    dense: &[f32],
+    n: usize,
+) -> Vec<f32> {
+    let rows = row_offsets.len() - 1;
+    let mut output = vec![0.0; rows * n];
+
+    for row in 0..rows {
+        for index in row_offsets[row] as usize..row_offsets[row + 1] as usize {
+            let column = column_indices[index] as usize;
+            for col in 0..n {
+                output[row * n + col] += values[index] * dense[column * n + col];
+            }
+        }
+    }
+
+    output
+}
+
+fn test_spmm<R: Runtime>(strategy: SparseStrategy, client: ComputeClient<R::Server, R::Channel>) {
+    if strategy == SparseStrategy::RowPerSubcube
+        && !client.properties().feature_enabled(Feature::Subcube)
+    {
+        return;
+    }
+
+    let (rows, k, n) = (45, 16, 5);
+    let (row_offsets, column_indices, values) = skewed_matrix(rows, k);
+    let dense = dense_data(k, n);
+    let sparse = CsrMatrix::<R, f32>::from_host(&client, k, &row_offsets, &column_indices, &values);
+    let dense_handle =
+        TensorHandle::<R, f32>::new_contiguous(vec![k, n], client.create(f32::as_bytes(&dense)));
+
+    let output = spmm(&client, &sparse, dense_handle.as_ref(), strategy);
+
+    let actual = client.read(output.handle.binding());
+    let expected = spmm_cpu(&row_offsets, &column_indices, &values, &dense, n);
+    assert_eq!(output.shape, [rows, n]);
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_spmm_row_per_unit<R: Runtime>(device: &R::Device) {
+    test_spmm::<R>(SparseStrategy::RowPerUnit, R::client(device))
+}
+
+pub fn test_spmm_row_per_subcube<R: Runtime>(device: &R::Device) {
+    test_spmm::<R>(SparseStrategy::RowPerSubcube, R::client(device))
+}
+
+pub fn test_spmm_merge_based<R: Runtime>(device: &R::Device) {
+    test_spmm::<R>(SparseStrategy::MergeBased, R::client(device))
+}
+
+pub fn test_spmm_merge_based_deterministic<R: Runtime>(device: &R::Device) {
+    test_spmm::<R>(
+        SparseStrategy::MergeBased,
+        R::client(device).deterministic(true),
+    )
+}
+
+pub fn test_spmv<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (rows, k) = (30, 7);
+    let (row_offsets, column_indices, values) = skewed_matrix(rows, k);
+    let vector = dense_data(k, 1);
+    let sparse = CsrMatrix::<R, f32>::from_host(&client, k, &row_offsets, &column_indices, &values);
+    let vector_handle =
+        TensorHandle::<R, f32>::new_contiguous(vec![k], client.create(f32::as_bytes(&vector)));
+
+    let output = spmv(
+        &client,
+        &sparse,
+        vector_handle.as_ref(),
+        SparseStrategy::RowPerUnit,
+    );
+
+    let actual = client.read(output.handle.binding());
+    let expected = spmm_cpu(&row_offsets, &column_indices, &values, &vector, 1);
+    assert_eq!(output.shape, [rows]);
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_coo_spmm_duplicates<R: Runtime>(device: &R::Device) {
+    coo_spmm_duplicates::<R>(R::client(device));
+}
+
+pub fn test_coo_spmm_deterministic<R: Runtime>(device: &R::Device) {
+    coo_spmm_duplicates::<R>(R::client(device).deterministic(true));
+}
+
+fn coo_spmm_duplicates<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    // The position (1, 0) is repeated, and the values are out of order.
+    let sparse = CooMatrix::<R, f32>::from_host(
+        &client,
+        3,
+        2,
+        &[2, 1, 0, 1],
+        &[1, 0, 1, 0],
+        &[1.0, 2.0, 3.0, 4.0],
+    );
+    let dense = TensorHandle::<R, f32>::new_contiguous(
+        vec![2, 2],
+        client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0])),
+    );
+
+    let output = coo_spmm(&client, &sparse, dense.as_ref());
+
+    let actual = client.read(output.handle.binding());
+    assert_eq!(f32::from_bytes(&actual), [9.0, 12.0, 6.0, 12.0, 3.0, 4.0]);
+}
+
+pub fn test_spmm_autotune<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (rows, k, n) = (45, 16, 5);
+    let (row_offsets, column_indices, values) = skewed_matrix(rows, k);
+    let dense = dense_data(k, n);
+    let sparse = CsrMatrix::<R, f32>::from_host(&client, k, &row_offsets, &column_indices, &values);
+    let dense_handle =
+        TensorHandle::<R, f32>::new_contiguous(vec![k, n], client.create(f32::as_bytes(&dense)));
+
+    let output = spmm_autotune(&client, &sparse, dense_handle.as_ref());
+
+    let actual = client.read(output.handle.binding());
+    let expected = spmm_cpu(&row_offsets, &column_indices, &values, &dense, n);
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
@@ -0,0 +1,132 @@
+use cubecl_core::{
+    prelude::*,
+    tune::{anchor, AutotuneOperation, AutotuneOperationSet, LocalTuner},
+    tune_device_id,
+};
+
+use super::{spmm, supported_strategies, CsrMatrix, SparseStrategy};
+use crate::tensor::TensorHandle;
+
+pub(crate) static TUNER: LocalTuner<String, String> = LocalTuner::new("cubecl-linalg-sparse");
+
+/// Sparse-dense matrix multiplication like [spmm], with the strategy selected by autotune for
+/// the shapes of the matrices and the device.
+pub fn spmm_autotune<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CsrMatrix<R, F>,
+    dense: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, F> {
+    let set = operation_set(client, sparse, dense);
+
+    TUNER.execute(&tune_device_id::<R>(client), client, Box::new(set))
+}
+
+/// Benchmark every strategy of [spmm_autotune] for the shapes of the matrices, so the result can
+/// be exported with `tune::export_tune_results` and shipped with an application.
+pub fn spmm_tune_offline<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CsrMatrix<R, F>,
+    dense: TensorHandleRef<'_, R>,
+) {
+    let set = operation_set(client, sparse, dense);
+
+    TUNER.tune_offline(&tune_device_id::<R>(client), client, Box::new(set));
+}
+
+fn operation_set<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sparse: &CsrMatrix<R, F>,
+    dense: TensorHandleRef<'_, R>,
+) -> SpmmAutotuneOperationSet<R, F> {
+    SpmmAutotuneOperationSet {
+        client: client.clone(),
+        sparse: sparse.clone(),
+        dense: TensorHandle::new(
+            dense.shape.to_vec(),
+            dense.strides.to_vec(),
+            dense.handle.clone(),
+        ),
+        strategies: supported_strategies::<R, F>(client),
+    }
+}
+
+struct SpmmAutotuneOperationSet<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    sparse: CsrMatrix<R, F>,
+    dense: TensorHandle<R, F>,
+    strategies: Vec<SparseStrategy>,
+}
+
+impl<R: Runtime, F: Float> AutotuneOperationSet<String, TensorHandle<R, F>>
+    for SpmmAutotuneOperationSet<R, F>
+{
+    fn key(&self) -> String {
+        // The average row length decides between the strategies more than the sizes.
+        format!(
+            "spmm-{}-m{}-nnz{}-n{}",
+            F::as_elem(),
+            anchor(self.sparse.rows, None),
+            anchor(self.sparse.nnz, None),
+            anchor(self.dense.shape[1], None),
+        )
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation<TensorHandle<R, F>>>> {
+        self.strategies
+            .iter()
+            .map(|strategy| {
+                Box::new(SpmmAutotuneOperation {
+                    client: self.client.clone(),
+                    sparse: self.sparse.clone(),
+                    dense: self.dense.clone(),
+                    strategy: *strategy,
+                }) as Box<dyn AutotuneOperation<TensorHandle<R, F>>>
+            })
+            .collect()
+    }
+
+    fn fastest(
+        self: Box<Self>,
+        fastest_index: usize,
+    ) -> Box<dyn AutotuneOperation<TensorHandle<R, F>>> {
+        self.autotunables().swap_remove(fastest_index)
+    }
+}
+
+/// A sparse-dense matrix multiplication with the given strategy, benchmarked on the matrices to
+/// multiply since it allocates its output.
+struct SpmmAutotuneOperation<R: Runtime, F: Float> {
+    client: ComputeClient<R::Server, R::Channel>,
+    sparse: CsrMatrix<R, F>,
+    dense: TensorHandle<R, F>,
+    strategy: SparseStrategy,
+}
+
+impl<R: Runtime, F: Float> core::fmt::Debug for SpmmAutotuneOperation<R, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SpmmAutotuneOperation")
+            .field("sparse", &self.sparse)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+impl<R: Runtime, F: Float> AutotuneOperation<TensorHandle<R, F>> for SpmmAutotuneOperation<R, F> {
+    fn execute(self: Box<Self>) -> TensorHandle<R, F> {
+        spmm(
+            &self.client,
+            &self.sparse,
+            self.dense.as_ref(),
+            self.strategy,
+        )
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation<TensorHandle<R, F>>> {
+        Box::new(Self {
+            client: self.client.clone(),
+            sparse: self.sparse.clone(),
+            dense: self.dense.clone(),
+            strategy: self.strategy,
+        })
+    }
+}
@@ -92,6 +92,7 @@ where
             handle: &self.handle,
             strides: &self.strides,
             shape: &self.shape,
+            elem: Some(E::as_elem()),
             runtime: PhantomData,
         }
     }
@@ -101,7 +102,12 @@ where
         let handle: TensorHandleRef<'a, R> = self.as_ref();
 
         unsafe {
-            TensorArg::from_raw_parts(handle.handle, handle.strides, handle.shape, vectorisation)
+            TensorArg::from_raw_parts_typed::<E>(
+                handle.handle,
+                handle.strides,
+                handle.shape,
+                vectorisation,
+            )
         }
     }
 
@@ -144,7 +150,7 @@ where
             rank - 1,
         );
 
-        let cube_dim = CubeDim::default();
+        let cube_dim = CubeDim::suggested(client.properties().hardware_properties());
         let cube_count =
             calculate_cube_count_elemwise(num_elements / vectorization_factor as usize, cube_dim);
 
@@ -0,0 +1,211 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::{contiguous, exclusive_sum, TensorHandle};
+
+/// The values selected by a mask, packed at the start of the output in row-major order.
+///
+/// The number of selected values is only known on the device, so the output has as many values as
+/// the mask and the values after the selected ones are unspecified.
+pub struct Compacted<R: Runtime, E: CubePrimitive> {
+    /// The selected values, of shape `[n]` for a mask of `n` values.
+    pub output: TensorHandle<R, E>,
+    /// The number of selected values, as a `u32` tensor of shape `[1]`.
+    pub count: TensorHandle<R, u32>,
+}
+
+impl<R: Runtime, E: CubePrimitive> Compacted<R, E> {
+    /// Read the number of selected values back from the device.
+    pub fn read_count(&self, client: &ComputeClient<R::Server, R::Channel>) -> usize {
+        let count = client.read(self.count.handle.clone().binding());
+        u32::from_bytes(&count)[0] as usize
+    }
+
+    /// The selected values only, reading their number back from the device.
+    pub fn into_selected(
+        self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> TensorHandle<R, E> {
+        let count = self.read_count(client);
+        TensorHandle::new_contiguous(vec![count], self.output.handle)
+    }
+}
+
+impl<R: Runtime, E: CubePrimitive> Clone for Compacted<R, E> {
+    fn clone(&self) -> Self {
+        Self {
+            output: self.output.clone(),
+            count: self.count.clone(),
+        }
+    }
+}
+
+impl<R: Runtime, E: CubePrimitive> core::fmt::Debug for Compacted<R, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Compacted")
+            .field("output", &self.output)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+#[cube(launch_unchecked)]
+fn flags_kernel<M: Numeric>(mask: &Tensor<M>, flags: &mut Tensor<u32>) {
+    if ABSOLUTE_POS < mask.len() {
+        let mut flag = 0;
+        if mask[ABSOLUTE_POS] != M::from_int(0) {
+            flag = 1;
+        }
+        flags[ABSOLUTE_POS] = flag;
+    }
+}
+
+/// The last unit writes the number of selected values, its offset plus its own flag.
+#[cube]
+fn write_count(flags: &Tensor<u32>, offsets: &Tensor<u32>, count: &mut Tensor<u32>, index: u32) {
+    if index == flags.len() - 1 {
+        count[0] = offsets[index] + flags[index];
+    }
+}
+
+#[cube(launch_unchecked)]
+fn compact_kernel<E: CubePrimitive>(
+    values: &Tensor<E>,
+    flags: &Tensor<u32>,
+    offsets: &Tensor<u32>,
+    output: &mut Tensor<E>,
+    count: &mut Tensor<u32>,
+) {
+    let index = ABSOLUTE_POS;
+
+    if index < flags.len() {
+        if flags[index] != 0 {
+            let position = offsets[index];
+            output[position] = values[index];
+        }
+        write_count(flags, offsets, count, index);
+    }
+}
+
+#[cube(launch_unchecked)]
+fn nonzero_kernel(
+    flags: &Tensor<u32>,
+    offsets: &Tensor<u32>,
+    indices: &mut Tensor<u32>,
+    count: &mut Tensor<u32>,
+) {
+    let index = ABSOLUTE_POS;
+
+    if index < flags.len() {
+        if flags[index] != 0 {
+            let position = offsets[index];
+            indices[position] = index;
+        }
+        write_count(flags, offsets, count, index);
+    }
+}
+
+/// The flags of the nonzero values of the mask and their exclusive prefix sum, which is the
+/// position of each selected value in the output.
+fn flags_and_offsets<R: Runtime, M: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    mask: TensorHandleRef<'_, R>,
+) -> (TensorHandle<R, u32>, TensorHandle<R, u32>) {
+    let mask = contiguous::<R, M>(client, mask);
+    let num_values: usize = mask.shape.iter().product();
+    let flags = TensorHandle::<R, u32>::empty(client, vec![num_values]);
+
+    if num_values > 0 {
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+        unsafe {
+            flags_kernel::launch_unchecked::<M, R>(
+                client,
+                cube_count,
+                cube_dim,
+                mask.as_arg(1),
+                flags.as_arg(1),
+            );
+        }
+    }
+
+    let offsets = exclusive_sum::<R>(client, flags.as_ref());
+    (flags, offsets)
+}
+
+/// The flat row-major indices of the nonzero values of the mask, in increasing order.
+pub fn nonzero<R: Runtime, M: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    mask: TensorHandleRef<'_, R>,
+) -> Compacted<R, u32> {
+    let (flags, offsets) = flags_and_offsets::<R, M>(client, mask);
+    let num_values = flags.shape[0];
+    let indices = TensorHandle::<R, u32>::empty(client, vec![num_values]);
+    let count = TensorHandle::<R, u32>::zeros(client, vec![1]);
+
+    if num_values > 0 {
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+        unsafe {
+            nonzero_kernel::launch_unchecked::<R>(
+                client,
+                cube_count,
+                cube_dim,
+                flags.as_arg(1),
+                offsets.as_arg(1),
+                indices.as_arg(1),
+                count.as_arg(1),
+            );
+        }
+    }
+
+    Compacted {
+        output: indices,
+        count,
+    }
+}
+
+/// The values where the mask is nonzero, in row-major order.
+///
+/// The values and the mask have the same shape.
+pub fn compact<R: Runtime, E: CubePrimitive, M: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    mask: TensorHandleRef<'_, R>,
+) -> Compacted<R, E> {
+    assert_eq!(
+        values.shape, mask.shape,
+        "The values and the mask should have the same shape"
+    );
+
+    let values = contiguous::<R, E>(client, values);
+    let (flags, offsets) = flags_and_offsets::<R, M>(client, mask);
+    let num_values = flags.shape[0];
+    let output = TensorHandle::<R, E>::new_contiguous(
+        vec![num_values],
+        client.empty(num_values * E::as_elem().size()),
+    );
+    let count = TensorHandle::<R, u32>::zeros(client, vec![1]);
+
+    if num_values > 0 {
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+        unsafe {
+            compact_kernel::launch_unchecked::<E, R>(
+                client,
+                cube_count,
+                cube_dim,
+                values.as_arg(1),
+                flags.as_arg(1),
+                offsets.as_arg(1),
+                output.as_arg(1),
+                count.as_arg(1),
+            );
+        }
+    }
+
+    Compacted { output, count }
+}
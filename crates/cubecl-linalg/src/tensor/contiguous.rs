@@ -106,7 +106,7 @@ pub fn into_contiguous_prefetch<R: Runtime, E: CubePrimitive>(
     let num_elems_per_unit = vectorization_factor as u32 * elems_per_unit;
 
     let num_elems: usize = input.shape.iter().product();
-    let cube_dim = CubeDim::default();
+    let cube_dim = CubeDim::suggested(client.properties().hardware_properties());
     let cube_count =
         calculate_cube_count_elemwise(num_elems / num_elems_per_unit as usize, cube_dim);
     let handle = client.empty(num_elems * E::as_elem().size());
@@ -0,0 +1,398 @@
+use super::{into_contiguous, matrix_layout, MatrixLayout, TensorHandle};
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+/// How the indices outside of the indexed dimension are handled.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Default)]
+pub enum IndexPolicy {
+    /// Indices are clamped to the first or the last position.
+    #[default]
+    Clamp,
+    /// Indices wrap around the dimension, so `-1` is the last position.
+    Wrap,
+    /// Rows with invalid indices are skipped and the operation returns an
+    /// [error](IndexOutOfBounds), which waits for the kernel to complete.
+    Error,
+}
+
+/// How the values are written by a [scatter].
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum ScatterMode {
+    /// The values are stored, a random one being kept when indices are duplicated.
+    Assign,
+    /// The values are added to the output, assuming indices are unique.
+    Add,
+    /// The values are added to the output atomically, so duplicated indices accumulate. Only
+    /// elements of 32 bits are supported.
+    ///
+    /// With a [deterministic](ComputeClient::deterministic) client, each output element instead
+    /// adds its values in the order of the indices, reading all the indices.
+    AtomicAdd,
+}
+
+/// Some indices were outside of the indexed dimension with the [error](IndexPolicy::Error)
+/// policy, or the indexed dimension is empty, which no policy can resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfBounds;
+
+impl core::fmt::Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Some indices are outside of the indexed dimension")
+    }
+}
+
+/// The row of `index`, or `num_rows` when it's invalid and flagged in `errors`.
+#[cube]
+fn resolve_index(
+    index: i32,
+    num_rows: u32,
+    errors: &mut Array<u32>,
+    #[comptime] policy: IndexPolicy,
+) -> u32 {
+    let num_rows_signed = i32::cast_from(num_rows);
+
+    match policy {
+        IndexPolicy::Clamp => u32::cast_from(Max::max(Min::min(index, num_rows_signed - 1), 0)),
+        IndexPolicy::Wrap => {
+            u32::cast_from(((index % num_rows_signed) + num_rows_signed) % num_rows_signed)
+        }
+        IndexPolicy::Error => {
+            let mut row = u32::cast_from(index);
+            if index < 0 || index >= num_rows_signed {
+                // All units write the same value, so the race is benign.
+                errors[0] = 1;
+                row = num_rows;
+            }
+            row
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn gather_kernel<E: CubePrimitive>(
+    input: &Tensor<Line<E>>,
+    indices: &Tensor<i32>,
+    output: &mut Tensor<Line<E>>,
+    errors: &mut Array<u32>,
+    #[comptime] policy: IndexPolicy,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let row_size = input.len() / input.shape(0);
+    let position = ABSOLUTE_POS / row_size;
+    let row = resolve_index(indices[position], input.shape(0), errors, policy);
+
+    if row < input.shape(0) {
+        output[ABSOLUTE_POS] = input[row * row_size + ABSOLUTE_POS % row_size];
+    }
+}
+
+#[cube(launch_unchecked)]
+fn scatter_kernel<E: Numeric>(
+    output: &mut Tensor<Line<E>>,
+    indices: &Tensor<i32>,
+    values: &Tensor<Line<E>>,
+    errors: &mut Array<u32>,
+    #[comptime] policy: IndexPolicy,
+    #[comptime] add: bool,
+) {
+    if ABSOLUTE_POS >= values.len() {
+        return;
+    }
+
+    let row_size = output.len() / output.shape(0);
+    let position = ABSOLUTE_POS / row_size;
+    let row = resolve_index(indices[position], output.shape(0), errors, policy);
+
+    if row < output.shape(0) {
+        let index = row * row_size + ABSOLUTE_POS % row_size;
+
+        if add {
+            output[index] += values[ABSOLUTE_POS];
+        } else {
+            output[index] = values[ABSOLUTE_POS];
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn scatter_atomic_add_kernel<E: Numeric>(
+    output: &mut Tensor<AtomicU32>,
+    indices: &Tensor<i32>,
+    values: &Tensor<E>,
+    errors: &mut Array<u32>,
+    #[comptime] policy: IndexPolicy,
+) {
+    if ABSOLUTE_POS >= values.len() {
+        return;
+    }
+
+    let row_size = output.len() / output.shape(0);
+    let position = ABSOLUTE_POS / row_size;
+    let row = resolve_index(indices[position], output.shape(0), errors, policy);
+
+    if row < output.shape(0) {
+        let index = row * row_size + ABSOLUTE_POS % row_size;
+        atomic_add::<E>(output, index, values[ABSOLUTE_POS]);
+    }
+}
+
+#[cube(launch_unchecked)]
+fn scatter_ordered_add_kernel<E: Numeric>(
+    output: &mut Tensor<Line<E>>,
+    indices: &Tensor<i32>,
+    values: &Tensor<Line<E>>,
+    errors: &mut Array<u32>,
+    #[comptime] policy: IndexPolicy,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let row_size = output.len() / output.shape(0);
+    let row = ABSOLUTE_POS / row_size;
+    let column = ABSOLUTE_POS % row_size;
+    let mut sum = output[ABSOLUTE_POS];
+
+    // The values are added in the order of the indices, whatever the scheduling.
+    for position in 0..indices.len() {
+        if resolve_index(indices[position], output.shape(0), errors, policy) == row {
+            sum += values[position * row_size + column];
+        }
+    }
+
+    output[ABSOLUTE_POS] = sum;
+}
+
+/// Atomically add the value to the element of 32 bits at `index`, with a compare and swap loop on
+/// its bits since atomic additions are only available for integers.
+#[cube]
+pub(crate) fn atomic_add<E: Numeric>(output: &mut Tensor<AtomicU32>, index: u32, value: E) {
+    let mut current = AtomicU32::load(&output[index]);
+
+    loop {
+        let sum = u32::bitcast_from(E::bitcast_from(current) + value);
+        let previous = AtomicU32::compare_and_swap(&output[index], current, sum);
+
+        if previous == current {
+            break;
+        }
+        current = previous;
+    }
+}
+
+/// Gather the rows of the input along its first dimension, as in an embedding lookup.
+///
+/// The output has the shape of the indices followed by the remaining dimensions of the input,
+/// its row `i` being the row `indices[i]` of the input.
+pub fn gather<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    indices: TensorHandleRef<'_, R>,
+    policy: IndexPolicy,
+) -> Result<TensorHandle<R, E>, IndexOutOfBounds> {
+    let input = contiguous::<R, E>(client, input);
+    let indices = contiguous::<R, i32>(client, indices);
+
+    let mut shape = indices.shape.clone();
+    shape.extend_from_slice(&input.shape[1..]);
+    let num_elems: usize = shape.iter().product();
+    let output =
+        TensorHandle::<R, E>::new_contiguous(shape, client.empty(num_elems * E::as_elem().size()));
+
+    if num_elems == 0 {
+        return Ok(output);
+    }
+    check_rows(input.shape[0], &indices.shape)?;
+
+    let line_size = row_line_size::<R>(&input.shape);
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+    let errors = errors_flag::<R>(client, policy);
+
+    unsafe {
+        gather_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(line_size),
+            indices.as_arg(1),
+            output.as_arg(line_size),
+            ArrayArg::from_raw_parts(&errors.handle, 1, 1),
+            policy,
+        );
+    }
+
+    check_errors(client, errors, policy).map(|_| output)
+}
+
+/// Scatter the rows of the values into the rows of the output along its first dimension, the
+/// row `i` of the values being written to the row `indices[i]` of the output.
+///
+/// The values have the shape of the indices followed by the remaining dimensions of the output,
+/// which must be contiguous.
+pub fn scatter<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    output: TensorHandleRef<'_, R>,
+    indices: TensorHandleRef<'_, R>,
+    values: TensorHandleRef<'_, R>,
+    mode: ScatterMode,
+    policy: IndexPolicy,
+) -> Result<(), IndexOutOfBounds> {
+    assert_eq!(
+        matrix_layout(output.strides),
+        MatrixLayout::Contiguous,
+        "The output of a scatter should be contiguous"
+    );
+    assert_eq!(
+        values.shape[indices.shape.len()..],
+        output.shape[1..],
+        "The values should have the rows of the output"
+    );
+
+    let indices = contiguous::<R, i32>(client, indices);
+    let values = contiguous::<R, E>(client, values);
+    let num_elems: usize = values.shape.iter().product();
+
+    if num_elems == 0 {
+        return Ok(());
+    }
+    check_rows(output.shape[0], &indices.shape)?;
+
+    let cube_dim = CubeDim::default();
+    let errors = errors_flag::<R>(client, policy);
+    let errors_arg = unsafe { ArrayArg::from_raw_parts(&errors.handle, 1, 1) };
+
+    match mode {
+        ScatterMode::Assign | ScatterMode::Add => {
+            let line_size = row_line_size::<R>(output.shape);
+            let cube_count =
+                calculate_cube_count_elemwise(num_elems / line_size as usize, cube_dim);
+
+            unsafe {
+                scatter_kernel::launch_unchecked::<E, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    output.as_tensor_arg(line_size),
+                    indices.as_arg(1),
+                    values.as_arg(line_size),
+                    errors_arg,
+                    policy,
+                    mode == ScatterMode::Add,
+                );
+            }
+        }
+        ScatterMode::AtomicAdd if client.is_deterministic() => {
+            let line_size = row_line_size::<R>(output.shape);
+            let num_outputs: usize = output.shape.iter().product();
+            let cube_count =
+                calculate_cube_count_elemwise(num_outputs / line_size as usize, cube_dim);
+
+            unsafe {
+                scatter_ordered_add_kernel::launch_unchecked::<E, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    output.as_tensor_arg(line_size),
+                    indices.as_arg(1),
+                    values.as_arg(line_size),
+                    errors_arg,
+                    policy,
+                );
+            }
+        }
+        ScatterMode::AtomicAdd => {
+            assert_eq!(
+                E::as_elem().size(),
+                4,
+                "Atomic additions are only supported for elements of 32 bits"
+            );
+            let cube_count = calculate_cube_count_elemwise(num_elems, cube_dim);
+
+            unsafe {
+                scatter_atomic_add_kernel::launch_unchecked::<E, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    TensorArg::from_raw_parts_typed::<AtomicU32>(
+                        output.handle,
+                        output.strides,
+                        output.shape,
+                        1,
+                    ),
+                    indices.as_arg(1),
+                    values.as_arg(1),
+                    errors_arg,
+                    policy,
+                );
+            }
+        }
+    }
+
+    check_errors(client, errors, policy)
+}
+
+/// The tensor itself when it's contiguous, or a contiguous copy.
+pub fn contiguous<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tensor: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, E> {
+    match matrix_layout(tensor.strides) {
+        MatrixLayout::Contiguous => TensorHandle::new(
+            tensor.shape.to_vec(),
+            tensor.strides.to_vec(),
+            tensor.handle.clone(),
+        ),
+        _ => into_contiguous::<R, E>(client, tensor),
+    }
+}
+
+/// The largest line size dividing the rows of a contiguous tensor indexed along its first
+/// dimension, so a line never spans two rows.
+fn row_line_size<R: Runtime>(shape: &[usize]) -> u8 {
+    let row_size: usize = shape[1..].iter().product();
+
+    R::supported_line_sizes()
+        .iter()
+        .copied()
+        .find(|line_size| row_size % *line_size as usize == 0)
+        .unwrap_or(1)
+}
+
+/// Indices can't be resolved when the indexed dimension is empty, whatever the policy.
+fn check_rows(num_rows: usize, indices_shape: &[usize]) -> Result<(), IndexOutOfBounds> {
+    match num_rows == 0 && indices_shape.iter().product::<usize>() > 0 {
+        true => Err(IndexOutOfBounds),
+        false => Ok(()),
+    }
+}
+
+/// The flag set by the kernels when an index is invalid, only written with the
+/// [error](IndexPolicy::Error) policy.
+fn errors_flag<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    policy: IndexPolicy,
+) -> TensorHandle<R, u32> {
+    match policy {
+        IndexPolicy::Error => TensorHandle::zeros(client, vec![1]),
+        _ => TensorHandle::empty(client, vec![1]),
+    }
+}
+
+fn check_errors<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    errors: TensorHandle<R, u32>,
+    policy: IndexPolicy,
+) -> Result<(), IndexOutOfBounds> {
+    if policy != IndexPolicy::Error {
+        return Ok(());
+    }
+
+    match u32::from_bytes(&client.read(errors.handle.binding()))[0] {
+        0 => Ok(()),
+        _ => Err(IndexOutOfBounds),
+    }
+}
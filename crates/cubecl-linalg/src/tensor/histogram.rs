@@ -0,0 +1,401 @@
+use cubecl::prelude::*;
+use cubecl_core::{
+    self as cubecl, calculate_cube_count_elemwise,
+    tune::{anchor, AutotuneOperation, AutotuneOperationSet, LocalTuner},
+    tune_device_id,
+};
+
+use super::{contiguous, TensorHandle};
+
+/// How the bins of a [histogram] are accumulated.
+///
+/// The counts are integers, so the atomic additions of every strategy give the same bins
+/// whatever their order, including with a [deterministic](ComputeClient::deterministic) client.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum HistogramStrategy {
+    /// Every value is counted with an atomic addition to the global bins. Slow when many values
+    /// fall in the same bins.
+    Global,
+    /// Each cube counts its values in private bins in shared memory, which are added to the
+    /// global bins once. The bins are replicated so that units contend on fewer counters, which
+    /// needs `4 * num_bins * replicas` bytes of shared memory.
+    Shared {
+        /// The number of copies of the bins in each cube.
+        replicas: u32,
+    },
+}
+
+/// How the bin of a value is computed.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+enum Binning {
+    /// The value is the index of its bin.
+    Index,
+    /// The values in `[min, max]` are split in bins of equal widths.
+    Range,
+}
+
+/// The number of values counted by each unit.
+const VALUES_PER_UNIT: u32 = 16;
+
+/// The bin of the value, or `num_bins` when it's outside of the bins.
+#[cube]
+fn bin_of<E: Numeric>(
+    value: E,
+    num_bins: u32,
+    min: f32,
+    max: f32,
+    #[comptime] binning: Binning,
+) -> u32 {
+    match binning {
+        Binning::Index => Min::min(u32::cast_from(value), num_bins),
+        Binning::Range => {
+            let value = f32::cast_from(value);
+            let mut bin = num_bins;
+            // Not a number is never counted.
+            if value >= min && value <= max {
+                let position = (value - min) / (max - min) * f32::cast_from(num_bins);
+                // The maximum is in the last bin.
+                bin = Min::min(u32::cast_from(position), num_bins - 1);
+            }
+            bin
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn histogram_global_kernel<E: Numeric>(
+    values: &Tensor<E>,
+    bins: &mut Tensor<AtomicU32>,
+    min: f32,
+    max: f32,
+    #[comptime] binning: Binning,
+) {
+    let num_bins = bins.len();
+
+    for i in 0..VALUES_PER_UNIT {
+        // Consecutive units read consecutive values.
+        let index = (CUBE_POS * VALUES_PER_UNIT + i) * CUBE_DIM + UNIT_POS;
+
+        if index < values.len() {
+            let bin = bin_of::<E>(values[index], num_bins, min, max, binning);
+            if bin < num_bins {
+                AtomicU32::add(&bins[bin], 1);
+            }
+        }
+    }
+}
+
+#[cube(launch_unchecked)]
+fn histogram_shared_kernel<E: Numeric>(
+    values: &Tensor<E>,
+    bins: &mut Tensor<AtomicU32>,
+    min: f32,
+    max: f32,
+    #[comptime] num_bins: u32,
+    #[comptime] replicas: u32,
+    #[comptime] binning: Binning,
+) {
+    let shared = SharedMemory::<AtomicU32>::new(comptime!(num_bins * replicas));
+
+    let mut bin = UNIT_POS;
+    while bin < num_bins * replicas {
+        AtomicU32::store(&shared[bin], 0);
+        bin += CUBE_DIM;
+    }
+    sync_units();
+
+    // Neighboring units count in different replicas.
+    let replica = UNIT_POS % replicas * num_bins;
+
+    for i in 0..VALUES_PER_UNIT {
+        let index = (CUBE_POS * VALUES_PER_UNIT + i) * CUBE_DIM + UNIT_POS;
+
+        if index < values.len() {
+            let bin = bin_of::<E>(values[index], num_bins, min, max, binning);
+            if bin < num_bins {
+                AtomicU32::add(&shared[replica + bin], 1);
+            }
+        }
+    }
+    sync_units();
+
+    let mut bin = UNIT_POS;
+    while bin < num_bins {
+        let mut count = 0;
+        for replica in 0..replicas {
+            count += AtomicU32::load(&shared[replica * num_bins + bin]);
+        }
+
+        if count > 0 {
+            AtomicU32::add(&bins[bin], count);
+        }
+        bin += CUBE_DIM;
+    }
+}
+
+/// Count the values of each bin, the values in `[min, max]` being split in `num_bins` bins of
+/// equal widths. Other values, including not a number, aren't counted.
+///
+/// Returns the `u32` counts of the bins.
+pub fn histogram<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+    strategy: HistogramStrategy,
+) -> TensorHandle<R, u32> {
+    assert!(min < max, "The range of the bins should not be empty");
+    launch::<R, E>(client, values, num_bins, min, max, Binning::Range, strategy)
+}
+
+/// Count the occurrences of each value in `0..num_bins`, other values not being counted.
+///
+/// Returns the `u32` counts of the values.
+pub fn bincount<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    strategy: HistogramStrategy,
+) -> TensorHandle<R, u32> {
+    launch::<R, u32>(client, values, num_bins, 0.0, 0.0, Binning::Index, strategy)
+}
+
+/// The number of bytes of shared memory used by the strategy.
+pub fn histogram_shared_memory_size(num_bins: usize, strategy: HistogramStrategy) -> usize {
+    match strategy {
+        HistogramStrategy::Global => 0,
+        HistogramStrategy::Shared { replicas } => 4 * num_bins * replicas as usize,
+    }
+}
+
+fn launch<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+    binning: Binning,
+    strategy: HistogramStrategy,
+) -> TensorHandle<R, u32> {
+    assert!(num_bins > 0, "A histogram should have bins");
+
+    let values = contiguous::<R, E>(client, values);
+    let bins = TensorHandle::<R, u32>::zeros(client, vec![num_bins]);
+    let num_values: usize = values.shape.iter().product();
+    let bins_arg = unsafe {
+        TensorArg::from_raw_parts_typed::<AtomicU32>(&bins.handle, &bins.strides, &bins.shape, 1)
+    };
+
+    let cube_dim = CubeDim::default();
+    let cube_count =
+        calculate_cube_count_elemwise(num_values.div_ceil(VALUES_PER_UNIT as usize), cube_dim);
+
+    match strategy {
+        HistogramStrategy::Global => unsafe {
+            histogram_global_kernel::launch_unchecked::<E, R>(
+                client,
+                cube_count,
+                cube_dim,
+                values.as_arg(1),
+                bins_arg,
+                ScalarArg::new(min),
+                ScalarArg::new(max),
+                binning,
+            );
+        },
+        HistogramStrategy::Shared { replicas } => {
+            let max_shared_memory_size = client
+                .properties()
+                .hardware_properties()
+                .max_shared_memory_size;
+            assert!(
+                replicas > 0
+                    && histogram_shared_memory_size(num_bins, strategy) <= max_shared_memory_size,
+                "The bins should fit in shared memory"
+            );
+
+            unsafe {
+                histogram_shared_kernel::launch_unchecked::<E, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    values.as_arg(1),
+                    bins_arg,
+                    ScalarArg::new(min),
+                    ScalarArg::new(max),
+                    num_bins as u32,
+                    replicas,
+                    binning,
+                );
+            }
+        }
+    }
+
+    bins
+}
+
+pub(crate) static TUNER: LocalTuner<String, String> = LocalTuner::new("cubecl-linalg-histogram");
+
+/// A [histogram] with the strategy selected by autotune for the number of values and bins and the
+/// device.
+pub fn histogram_autotune<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+) -> TensorHandle<R, u32> {
+    let set = operation_set::<R, E>(client, values, num_bins, min, max);
+
+    TUNER.execute(&tune_device_id::<R>(client), client, Box::new(set))
+}
+
+/// Benchmark every strategy of [histogram_autotune] for the number of values and bins, so the
+/// result can be exported with `tune::export_tune_results` and shipped with an application.
+pub fn histogram_tune_offline<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+) {
+    let set = operation_set::<R, E>(client, values, num_bins, min, max);
+
+    TUNER.tune_offline(&tune_device_id::<R>(client), client, Box::new(set));
+}
+
+fn operation_set<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<'_, R>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+) -> HistogramAutotuneOperationSet<R, E> {
+    HistogramAutotuneOperationSet {
+        client: client.clone(),
+        values: TensorHandle::new(
+            values.shape.to_vec(),
+            values.strides.to_vec(),
+            values.handle.clone(),
+        ),
+        num_bins,
+        min,
+        max,
+        strategies: strategies::<R>(client, num_bins),
+    }
+}
+
+/// The strategies fitting in the shared memory of the device, with privatized bins first.
+fn strategies<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    num_bins: usize,
+) -> Vec<HistogramStrategy> {
+    let max_shared_memory_size = client
+        .properties()
+        .hardware_properties()
+        .max_shared_memory_size;
+    let mut strategies: Vec<HistogramStrategy> = [1, 4, 16]
+        .into_iter()
+        .map(|replicas| HistogramStrategy::Shared { replicas })
+        .filter(|strategy| {
+            histogram_shared_memory_size(num_bins, *strategy) <= max_shared_memory_size
+        })
+        .collect();
+    strategies.push(HistogramStrategy::Global);
+
+    strategies
+}
+
+struct HistogramAutotuneOperationSet<R: Runtime, E: Numeric> {
+    client: ComputeClient<R::Server, R::Channel>,
+    values: TensorHandle<R, E>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+    strategies: Vec<HistogramStrategy>,
+}
+
+impl<R: Runtime, E: Numeric> AutotuneOperationSet<String, TensorHandle<R, u32>>
+    for HistogramAutotuneOperationSet<R, E>
+{
+    fn key(&self) -> String {
+        let num_values: usize = self.values.shape.iter().product();
+
+        format!(
+            "histogram-{}-values{}-bins{}",
+            E::as_elem(),
+            anchor(num_values, None),
+            anchor(self.num_bins, None),
+        )
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation<TensorHandle<R, u32>>>> {
+        self.strategies
+            .iter()
+            .map(|strategy| {
+                Box::new(HistogramAutotuneOperation {
+                    client: self.client.clone(),
+                    values: self.values.clone(),
+                    num_bins: self.num_bins,
+                    min: self.min,
+                    max: self.max,
+                    strategy: *strategy,
+                }) as Box<dyn AutotuneOperation<TensorHandle<R, u32>>>
+            })
+            .collect()
+    }
+
+    fn fastest(
+        self: Box<Self>,
+        fastest_index: usize,
+    ) -> Box<dyn AutotuneOperation<TensorHandle<R, u32>>> {
+        self.autotunables().swap_remove(fastest_index)
+    }
+}
+
+/// A histogram with the given strategy, benchmarked on the values to count since it allocates
+/// its bins.
+struct HistogramAutotuneOperation<R: Runtime, E: Numeric> {
+    client: ComputeClient<R::Server, R::Channel>,
+    values: TensorHandle<R, E>,
+    num_bins: usize,
+    min: f32,
+    max: f32,
+    strategy: HistogramStrategy,
+}
+
+impl<R: Runtime, E: Numeric> core::fmt::Debug for HistogramAutotuneOperation<R, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HistogramAutotuneOperation")
+            .field("num_bins", &self.num_bins)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+impl<R: Runtime, E: Numeric> AutotuneOperation<TensorHandle<R, u32>>
+    for HistogramAutotuneOperation<R, E>
+{
+    fn execute(self: Box<Self>) -> TensorHandle<R, u32> {
+        histogram::<R, E>(
+            &self.client,
+            self.values.as_ref(),
+            self.num_bins,
+            self.min,
+            self.max,
+            self.strategy,
+        )
+    }
+
+    fn clone(&self) -> Box<dyn AutotuneOperation<TensorHandle<R, u32>>> {
+        Box::new(Self {
+            client: self.client.clone(),
+            values: self.values.clone(),
+            num_bins: self.num_bins,
+            min: self.min,
+            max: self.max,
+            strategy: self.strategy,
+        })
+    }
+}
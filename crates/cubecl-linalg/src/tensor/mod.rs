@@ -1,7 +1,18 @@
 mod base;
+mod compact;
 mod contiguous;
+mod gather_scatter;
+mod histogram;
 mod layout;
+mod scan;
 
 pub use base::*;
+pub use compact::*;
 pub use contiguous::*;
+pub use gather_scatter::*;
+pub use histogram::*;
 pub use layout::*;
+pub use scan::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
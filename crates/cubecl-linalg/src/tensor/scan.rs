@@ -0,0 +1,126 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use super::{contiguous, TensorHandle};
+
+/// The number of values scanned by each cube.
+const SCAN_CUBE_DIM: u32 = 256;
+
+/// Scan the values of each cube, the total of the cube being written to `totals`.
+#[cube(launch_unchecked)]
+fn scan_cube_kernel(
+    input: &Tensor<u32>,
+    output: &mut Tensor<u32>,
+    totals: &mut Tensor<u32>,
+    #[comptime] cube_dim: u32,
+) {
+    let mut shared = SharedMemory::<u32>::new(cube_dim);
+    let index = CUBE_POS * cube_dim + UNIT_POS;
+
+    let mut value = 0;
+    if index < input.len() {
+        value = input[index];
+    }
+    shared[UNIT_POS] = value;
+    sync_units();
+
+    // Each step adds the partial sum ending `offset` values before, doubling the number of
+    // values summed by each unit.
+    let mut offset = 1;
+    while offset < cube_dim {
+        let mut previous = 0;
+        if UNIT_POS >= offset {
+            previous = shared[UNIT_POS - offset];
+        }
+        sync_units();
+
+        shared[UNIT_POS] += previous;
+        sync_units();
+
+        offset *= 2;
+    }
+
+    let inclusive = shared[UNIT_POS];
+    if index < output.len() {
+        output[index] = inclusive - value;
+    }
+    if UNIT_POS == cube_dim - 1 && CUBE_POS < totals.len() {
+        totals[CUBE_POS] = inclusive;
+    }
+}
+
+/// Add the scanned totals of the previous cubes to the values of each cube.
+#[cube(launch_unchecked)]
+fn add_cube_offsets_kernel(
+    output: &mut Tensor<u32>,
+    offsets: &Tensor<u32>,
+    #[comptime] cube_dim: u32,
+) {
+    let index = CUBE_POS * cube_dim + UNIT_POS;
+
+    if index < output.len() {
+        output[index] += offsets[CUBE_POS];
+    }
+}
+
+/// The exclusive prefix sum of the `u32` values in row-major order, the value at `i` in the
+/// output being the sum of the values before `i`.
+///
+/// Returns a contiguous tensor of the shape of the input. The sums wrap on overflow.
+pub fn exclusive_sum<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+) -> TensorHandle<R, u32> {
+    let input = contiguous::<R, u32>(client, input);
+    let output = scan::<R>(client, &input);
+
+    TensorHandle::new_contiguous(input.shape, output.handle)
+}
+
+/// Scan the contiguous values in a flat tensor, scanning the totals of the cubes recursively.
+fn scan<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandle<R, u32>,
+) -> TensorHandle<R, u32> {
+    let num_values: usize = input.shape.iter().product();
+    let output = TensorHandle::<R, u32>::empty(client, vec![num_values]);
+    if num_values == 0 {
+        return output;
+    }
+
+    let num_cubes = num_values.div_ceil(SCAN_CUBE_DIM as usize);
+    let totals = TensorHandle::<R, u32>::empty(client, vec![num_cubes]);
+    let input = TensorHandle::<R, u32>::new(vec![num_values], vec![1], input.handle.clone());
+
+    let cube_dim = CubeDim::new(SCAN_CUBE_DIM, 1, 1);
+    let cube_count = calculate_cube_count_elemwise(num_values, cube_dim);
+
+    unsafe {
+        scan_cube_kernel::launch_unchecked::<R>(
+            client,
+            cube_count.clone(),
+            cube_dim,
+            input.as_arg(1),
+            output.as_arg(1),
+            totals.as_arg(1),
+            SCAN_CUBE_DIM,
+        );
+    }
+
+    if num_cubes > 1 {
+        let offsets = scan::<R>(client, &totals);
+
+        unsafe {
+            add_cube_offsets_kernel::launch_unchecked::<R>(
+                client,
+                cube_count,
+                cube_dim,
+                output.as_arg(1),
+                offsets.as_arg(1),
+                SCAN_CUBE_DIM,
+            );
+        }
+    }
+
+    output
+}
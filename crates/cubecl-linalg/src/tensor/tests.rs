@@ -0,0 +1,289 @@
+#![allow(missing_docs)]
+
+use cubecl_core::{CubeElement, Runtime};
+
+use crate::{
+    tensor::{
+        bincount, compact, exclusive_sum, gather, histogram, histogram_autotune, nonzero, scatter,
+        HistogramStrategy, IndexOutOfBounds, IndexPolicy, ScatterMode, TensorHandle,
+    },
+    tests::test_utils::{generate_random_data, random_tensor},
+};
+
+fn indices<R: Runtime>(
+    client: &cubecl_core::client::ComputeClient<R::Server, R::Channel>,
+    indices: &[i32],
+    shape: Vec<usize>,
+) -> TensorHandle<R, i32> {
+    TensorHandle::new_contiguous(shape, client.create(i32::as_bytes(indices)))
+}
+
+pub fn test_gather_embedding<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let table = random_tensor::<R>(&client, vec![10, 8]);
+    let data = f32::from_bytes(&client.read(table.handle.clone().binding())).to_vec();
+    let indices = indices::<R>(&client, &[3, -1, 12, 0, 3, 9], vec![2, 3]);
+
+    let output =
+        gather::<R, f32>(&client, table.as_ref(), indices.as_ref(), IndexPolicy::Wrap).unwrap();
+
+    assert_eq!(output.shape, vec![2, 3, 8]);
+    let expected: Vec<f32> = [3, 9, 2, 0, 3, 9]
+        .iter()
+        .flat_map(|row| data[row * 8..(row + 1) * 8].to_vec())
+        .collect();
+    let actual = client.read(output.handle.binding());
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_gather_clamp<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let table = random_tensor::<R>(&client, vec![4, 3]);
+    let data = f32::from_bytes(&client.read(table.handle.clone().binding())).to_vec();
+    let indices = indices::<R>(&client, &[-2, 7], vec![2]);
+
+    let output = gather::<R, f32>(
+        &client,
+        table.as_ref(),
+        indices.as_ref(),
+        IndexPolicy::Clamp,
+    )
+    .unwrap();
+
+    let expected: Vec<f32> = [0, 3]
+        .iter()
+        .flat_map(|row| data[row * 3..(row + 1) * 3].to_vec())
+        .collect();
+    let actual = client.read(output.handle.binding());
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_gather_out_of_bounds<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let table = random_tensor::<R>(&client, vec![4, 3]);
+    let indices = indices::<R>(&client, &[1, 4], vec![2]);
+
+    let result = gather::<R, f32>(
+        &client,
+        table.as_ref(),
+        indices.as_ref(),
+        IndexPolicy::Error,
+    );
+
+    assert_eq!(result.unwrap_err(), IndexOutOfBounds);
+}
+
+pub fn test_scatter_assign<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let output = TensorHandle::<R, f32>::zeros(&client, vec![5, 4]);
+    let values = random_tensor::<R>(&client, vec![2, 4]);
+    let data = f32::from_bytes(&client.read(values.handle.clone().binding())).to_vec();
+    let indices = indices::<R>(&client, &[4, 1], vec![2]);
+
+    scatter::<R, f32>(
+        &client,
+        output.as_ref(),
+        indices.as_ref(),
+        values.as_ref(),
+        ScatterMode::Assign,
+        IndexPolicy::Error,
+    )
+    .unwrap();
+
+    let mut expected = vec![0.0; 20];
+    expected[16..20].copy_from_slice(&data[0..4]);
+    expected[4..8].copy_from_slice(&data[4..8]);
+    let actual = client.read(output.handle.binding());
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+pub fn test_scatter_atomic_add<R: Runtime>(device: &R::Device) {
+    scatter_atomic_add::<R>(R::client(device));
+}
+
+pub fn test_scatter_atomic_add_deterministic<R: Runtime>(device: &R::Device) {
+    scatter_atomic_add::<R>(R::client(device).deterministic(true));
+}
+
+fn scatter_atomic_add<R: Runtime>(
+    client: cubecl_core::client::ComputeClient<R::Server, R::Channel>,
+) {
+    let output = TensorHandle::<R, f32>::zeros(&client, vec![3, 2]);
+    let values = TensorHandle::<R, f32>::new_contiguous(
+        vec![4, 2],
+        client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0])),
+    );
+    let indices = indices::<R>(&client, &[2, 0, 2, 2], vec![4]);
+
+    scatter::<R, f32>(
+        &client,
+        output.as_ref(),
+        indices.as_ref(),
+        values.as_ref(),
+        ScatterMode::AtomicAdd,
+        IndexPolicy::Clamp,
+    )
+    .unwrap();
+
+    let actual = client.read(output.handle.binding());
+    assert_eq!(f32::from_bytes(&actual), [3.0, 4.0, 0.0, 0.0, 13.0, 16.0]);
+}
+
+pub fn test_gather_empty_dimension<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let table = TensorHandle::<R, f32>::empty(&client, vec![0, 3]);
+    let indices = indices::<R>(&client, &[0, -1], vec![2]);
+
+    let result = gather::<R, f32>(&client, table.as_ref(), indices.as_ref(), IndexPolicy::Wrap);
+
+    assert_eq!(result.unwrap_err(), IndexOutOfBounds);
+}
+
+/// Values in `[-1, 1]` with a not a number, and their counts in 7 bins over `[-0.5, 1]`.
+fn histogram_values() -> (Vec<f32>, Vec<u32>) {
+    let mut values = generate_random_data(5000);
+    values[17] = f32::NAN;
+    values[42] = 1.0;
+
+    let mut counts = vec![0; 7];
+    for value in values.iter().filter(|value| (-0.5..=1.0).contains(*value)) {
+        let bin = ((value + 0.5) / 1.5 * 7.0) as usize;
+        counts[bin.min(6)] += 1;
+    }
+
+    (values, counts)
+}
+
+fn test_histogram<R: Runtime>(
+    strategy: HistogramStrategy,
+    client: cubecl_core::client::ComputeClient<R::Server, R::Channel>,
+) {
+    let (values, expected) = histogram_values();
+    let input = TensorHandle::<R, f32>::new_contiguous(
+        vec![values.len()],
+        client.create(f32::as_bytes(&values)),
+    );
+
+    let bins = histogram::<R, f32>(&client, input.as_ref(), 7, -0.5, 1.0, strategy);
+
+    let actual = client.read(bins.handle.binding());
+    assert_eq!(u32::from_bytes(&actual), expected);
+}
+
+pub fn test_histogram_global<R: Runtime>(device: &R::Device) {
+    test_histogram::<R>(HistogramStrategy::Global, R::client(device))
+}
+
+pub fn test_histogram_deterministic<R: Runtime>(device: &R::Device) {
+    test_histogram::<R>(
+        HistogramStrategy::Global,
+        R::client(device).deterministic(true),
+    )
+}
+
+pub fn test_histogram_shared<R: Runtime>(device: &R::Device) {
+    test_histogram::<R>(HistogramStrategy::Shared { replicas: 1 }, R::client(device))
+}
+
+pub fn test_histogram_shared_replicated<R: Runtime>(device: &R::Device) {
+    test_histogram::<R>(HistogramStrategy::Shared { replicas: 4 }, R::client(device))
+}
+
+pub fn test_histogram_autotune<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (values, expected) = histogram_values();
+    let input = TensorHandle::<R, f32>::new_contiguous(
+        vec![values.len()],
+        client.create(f32::as_bytes(&values)),
+    );
+
+    let bins = histogram_autotune::<R, f32>(&client, input.as_ref(), 7, -0.5, 1.0);
+
+    let actual = client.read(bins.handle.binding());
+    assert_eq!(u32::from_bytes(&actual), expected);
+}
+
+pub fn test_bincount<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let values: Vec<u32> = (0..3000).map(|i| (i * i) % 13).collect();
+    let input = TensorHandle::<R, u32>::new_contiguous(
+        vec![30, 100],
+        client.create(u32::as_bytes(&values)),
+    );
+
+    let bins = bincount::<R>(
+        &client,
+        input.as_ref(),
+        10,
+        HistogramStrategy::Shared { replicas: 2 },
+    );
+
+    // The values from 10 to 12 aren't counted.
+    let mut expected = vec![0; 10];
+    for value in values.iter().filter(|value| **value < 10) {
+        expected[*value as usize] += 1;
+    }
+    let actual = client.read(bins.handle.binding());
+    assert_eq!(u32::from_bytes(&actual), expected);
+}
+
+pub fn test_exclusive_sum<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    // Enough values for the totals of the cubes to be scanned in two levels.
+    let values: Vec<u32> = (0..70_000).map(|i| (i * 7) % 5).collect();
+    let input = TensorHandle::<R, u32>::new_contiguous(
+        vec![70, 1000],
+        client.create(u32::as_bytes(&values)),
+    );
+
+    let output = exclusive_sum::<R>(&client, input.as_ref());
+
+    let mut sum = 0;
+    let expected: Vec<u32> = values
+        .iter()
+        .map(|value| {
+            let before = sum;
+            sum += value;
+            before
+        })
+        .collect();
+    assert_eq!(output.shape, vec![70, 1000]);
+    let actual = client.read(output.handle.binding());
+    assert_eq!(u32::from_bytes(&actual), expected);
+}
+
+pub fn test_nonzero<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let mask: Vec<f32> = (0..3000).map(|i| ((i * i) % 3) as f32).collect();
+    let input =
+        TensorHandle::<R, f32>::new_contiguous(vec![30, 100], client.create(f32::as_bytes(&mask)));
+
+    let indices = nonzero::<R, f32>(&client, input.as_ref()).into_selected(&client);
+
+    let expected: Vec<u32> = (0..3000).filter(|i| mask[*i as usize] != 0.0).collect();
+    assert_eq!(indices.shape, vec![expected.len()]);
+    let actual = client.read(indices.handle.binding());
+    assert_eq!(&u32::from_bytes(&actual)[..expected.len()], expected);
+}
+
+pub fn test_compact<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let values = generate_random_data(1000);
+    let mask: Vec<u32> = (0..1000).map(|i| (i % 7 == 3) as u32).collect();
+    let values_handle =
+        TensorHandle::<R, f32>::new_contiguous(vec![1000], client.create(f32::as_bytes(&values)));
+    let mask_handle =
+        TensorHandle::<R, u32>::new_contiguous(vec![1000], client.create(u32::as_bytes(&mask)));
+
+    let compacted = compact::<R, f32, u32>(&client, values_handle.as_ref(), mask_handle.as_ref());
+
+    let expected: Vec<f32> = values
+        .iter()
+        .zip(mask.iter())
+        .filter(|(_, flag)| **flag != 0)
+        .map(|(value, _)| *value)
+        .collect();
+    assert_eq!(compacted.read_count(&client), expected.len());
+    let actual = client.read(compacted.output.handle.binding());
+    assert_eq!(&f32::from_bytes(&actual)[..expected.len()], expected);
+}
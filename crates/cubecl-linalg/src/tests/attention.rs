@@ -0,0 +1,40 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_attention {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_attention_one_cube() {
+            cubecl_linalg::attention::tests::test_attention_one_cube::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_attention_with_check_bounds() {
+            cubecl_linalg::attention::tests::test_attention_with_check_bounds::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_attention_causal() {
+            cubecl_linalg::attention::tests::test_attention_causal::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_attention_autotune() {
+            cubecl_linalg::attention::tests::test_attention_autotune::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_attention_f16() {
+            cubecl_linalg::attention::tests::test_attention_f16::<TestRuntime>(&Default::default())
+        }
+    };
+}
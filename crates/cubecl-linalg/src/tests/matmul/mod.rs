@@ -4,14 +4,11 @@ pub mod cmma;
 pub mod tiling2d;
 
 #[macro_export]
-macro_rules! testgen_all {
+macro_rules! testgen_matmul {
     () => {
-        mod linalg {
-            use super::*;
-            use cubecl_linalg::matmul::tests;
+        use cubecl_linalg::matmul::tests;
 
-            cubecl_linalg::testgen_cmma!();
-            cubecl_linalg::testgen_tiling2d!();
-        }
+        cubecl_linalg::testgen_cmma!();
+        cubecl_linalg::testgen_tiling2d!();
     };
 }
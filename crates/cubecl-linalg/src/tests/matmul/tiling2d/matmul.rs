@@ -31,5 +31,12 @@ macro_rules! testgen_tiling2d_matmul {
                 &Default::default(),
             )
         }
+
+        #[test]
+        pub fn test_matmul_tiling2d_fused_bias_add() {
+            tests::tiling2d::matmul::test_matmul_tiling2d_fused_bias_add::<TestRuntime>(
+                &Default::default(),
+            )
+        }
     };
 }
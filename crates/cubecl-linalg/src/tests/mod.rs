@@ -1 +1,22 @@
+#![allow(missing_docs)]
+
+mod attention;
 mod matmul;
+mod sparse;
+mod tensor;
+#[cfg(feature = "export_tests")]
+pub(crate) mod test_utils;
+
+#[macro_export]
+macro_rules! testgen_all {
+    () => {
+        mod linalg {
+            use super::*;
+
+            cubecl_linalg::testgen_matmul!();
+            cubecl_linalg::testgen_attention!();
+            cubecl_linalg::testgen_tensor!();
+            cubecl_linalg::testgen_sparse!();
+        }
+    };
+}
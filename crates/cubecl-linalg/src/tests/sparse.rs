@@ -0,0 +1,55 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_sparse {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_spmm_row_per_unit() {
+            cubecl_linalg::sparse::tests::test_spmm_row_per_unit::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_spmm_row_per_subcube() {
+            cubecl_linalg::sparse::tests::test_spmm_row_per_subcube::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_spmm_merge_based() {
+            cubecl_linalg::sparse::tests::test_spmm_merge_based::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_spmm_merge_based_deterministic() {
+            cubecl_linalg::sparse::tests::test_spmm_merge_based_deterministic::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_spmv() {
+            cubecl_linalg::sparse::tests::test_spmv::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_coo_spmm_duplicates() {
+            cubecl_linalg::sparse::tests::test_coo_spmm_duplicates::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_coo_spmm_deterministic() {
+            cubecl_linalg::sparse::tests::test_coo_spmm_deterministic::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_spmm_autotune() {
+            cubecl_linalg::sparse::tests::test_spmm_autotune::<TestRuntime>(&Default::default())
+        }
+    };
+}
@@ -0,0 +1,101 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_tensor {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_gather_embedding() {
+            cubecl_linalg::tensor::tests::test_gather_embedding::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_gather_clamp() {
+            cubecl_linalg::tensor::tests::test_gather_clamp::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_gather_out_of_bounds() {
+            cubecl_linalg::tensor::tests::test_gather_out_of_bounds::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_gather_empty_dimension() {
+            cubecl_linalg::tensor::tests::test_gather_empty_dimension::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_scatter_assign() {
+            cubecl_linalg::tensor::tests::test_scatter_assign::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_scatter_atomic_add() {
+            cubecl_linalg::tensor::tests::test_scatter_atomic_add::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_scatter_atomic_add_deterministic() {
+            cubecl_linalg::tensor::tests::test_scatter_atomic_add_deterministic::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_histogram_global() {
+            cubecl_linalg::tensor::tests::test_histogram_global::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_histogram_deterministic() {
+            cubecl_linalg::tensor::tests::test_histogram_deterministic::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_histogram_shared() {
+            cubecl_linalg::tensor::tests::test_histogram_shared::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_histogram_shared_replicated() {
+            cubecl_linalg::tensor::tests::test_histogram_shared_replicated::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_histogram_autotune() {
+            cubecl_linalg::tensor::tests::test_histogram_autotune::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+
+        #[test]
+        pub fn test_bincount() {
+            cubecl_linalg::tensor::tests::test_bincount::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_exclusive_sum() {
+            cubecl_linalg::tensor::tests::test_exclusive_sum::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_nonzero() {
+            cubecl_linalg::tensor::tests::test_nonzero::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_compact() {
+            cubecl_linalg::tensor::tests::test_compact::<TestRuntime>(&Default::default())
+        }
+    };
+}
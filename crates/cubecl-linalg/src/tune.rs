@@ -0,0 +1,62 @@
+use cubecl_core::{prelude::*, tune::LocalTuner, tune::TuneResults, tune_device_id};
+
+/// The tuners of the operations selected by autotune in this crate.
+fn tuners() -> [&'static LocalTuner<String, String>; 3] {
+    [
+        &crate::attention::TUNER,
+        &crate::sparse::TUNER,
+        &crate::tensor::TUNER,
+    ]
+}
+
+/// Export the autotune results of every operation of this crate for the device of the client,
+/// usually after tuning them offline with functions like
+/// [histogram_tune_offline](crate::tensor::histogram_tune_offline).
+pub fn export_tune_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> Vec<TuneResults<String>> {
+    export_tuner_results::<R>(client, &tuners())
+}
+
+/// Import [exported](export_tune_results) autotune results for the device of the client, so the
+/// operations don't have to be tuned at their first launch.
+///
+/// The results are imported into the tuner with the same name; results of unknown tuners are
+/// ignored.
+pub fn import_tune_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    results: Vec<TuneResults<String>>,
+) {
+    import_tuner_results::<R>(client, &tuners(), results)
+}
+
+/// Export the autotune results of the `tuners` for the device of the client, like
+/// [export_tune_results] does for the tuners of this crate.
+pub fn export_tuner_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tuners: &[&LocalTuner<String, String>],
+) -> Vec<TuneResults<String>> {
+    let id = tune_device_id::<R>(client);
+
+    tuners
+        .iter()
+        .map(|tuner| tuner.export_results(&id))
+        .filter(|results| !results.results.is_empty())
+        .collect()
+}
+
+/// Import autotune results into the `tuners` for the device of the client, like
+/// [import_tune_results] does for the tuners of this crate.
+pub fn import_tuner_results<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tuners: &[&LocalTuner<String, String>],
+    results: Vec<TuneResults<String>>,
+) {
+    let id = tune_device_id::<R>(client);
+
+    for results in results {
+        if let Some(tuner) = tuners.iter().find(|tuner| tuner.name() == results.name) {
+            tuner.import_results(&id, results);
+        }
+    }
+}
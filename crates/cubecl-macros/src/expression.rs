@@ -73,7 +73,7 @@ pub enum Expression {
     VerbatimTerminated {
         tokens: TokenStream,
     },
-    Continue(Span),
+    Continue,
     ForLoop {
         range: Box<Expression>,
         unroll: Option<Box<Expression>>,
@@ -81,10 +81,14 @@ pub enum Expression {
         var_ty: Option<syn::Type>,
         block: Block,
         scope: Scope,
+        /// Whether the body returns a value from the function.
+        returns: bool,
     },
     Loop {
         block: Block,
         scope: Scope,
+        /// Whether the body returns a value from the function.
+        returns: bool,
     },
     If {
         condition: Box<Expression>,
@@ -95,10 +99,11 @@ pub enum Expression {
         value: Box<Expression>,
         cases: Vec<(Lit, Block)>,
         default: Block,
+        /// Whether a case returns a value from the function.
+        returns: bool,
     },
     Return {
         expr: Option<Box<Expression>>,
-        span: Span,
         _ty: Type,
     },
     Range {
@@ -121,7 +126,8 @@ pub enum Expression {
     Slice {
         expr: Box<Expression>,
         span: Span,
-        _ranges: Vec<Expression>,
+        ranges: Vec<Expression>,
+        mutable: bool,
     },
     ArrayInit {
         init: Box<Expression>,
@@ -146,6 +152,7 @@ pub enum Expression {
 #[derive(Clone, Debug)]
 pub struct ConstMatchArm {
     pub pat: syn::Pat,
+    pub guard: Option<syn::Expr>,
     pub expr: Box<Expression>,
 }
 
@@ -169,7 +176,7 @@ impl Expression {
             Expression::FunctionCall { .. } => None,
             Expression::Break { .. } => None,
             Expression::Cast { to, .. } => Some(to.clone()),
-            Expression::Continue { .. } => None,
+            Expression::Continue => None,
             Expression::ForLoop { .. } => None,
             Expression::FieldAccess { .. } => None,
             Expression::MethodCall { .. } => None,
@@ -206,6 +213,7 @@ impl Expression {
             Expression::Array { elements, .. } => elements.iter().all(|it| it.is_const()),
             Expression::Tuple { elements, .. } => elements.iter().all(|it| it.is_const()),
             Expression::CompilerIntrinsic { .. } => true,
+            Expression::ConstMatch { arms, .. } => arms.iter().all(|arm| arm.expr.is_const()),
             _ => false,
         }
     }
@@ -240,6 +248,13 @@ impl Expression {
             }
             Expression::Reference { inner } => inner.as_const(context).map(|base| quote![&#base]),
             Expression::MethodCall { .. } if self.is_const() => Some(self.to_tokens(context)),
+            Expression::ConstMatch { const_expr, arms } => {
+                let arms = arms
+                    .iter()
+                    .map(|arm| arm.expr.as_const(context).map(|expr| arm.with_body(expr)))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(quote![match #const_expr { #(#arms,)* }])
+            }
             _ => None,
         }
     }
@@ -17,6 +17,8 @@ impl CubeTypeStruct {
         let arg_settings_impl = self.arg_settings_impl();
         let launch_arg_impl = self.launch_arg_impl();
         let expand_type_impl = self.expand_type_impl();
+        let runtime_impl = self.runtime_impl();
+        let cube_struct_impl = self.cube_struct_impl();
 
         if with_launch {
             quote! {
@@ -28,12 +30,70 @@ impl CubeTypeStruct {
                 #arg_settings_impl
                 #launch_arg_impl
                 #expand_type_impl
+                #cube_struct_impl
             }
         } else {
             quote! {
                 #expand_ty
                 #cube_type_impl
                 #expand_type_impl
+                #runtime_impl
+                #cube_struct_impl
+            }
+        }
+    }
+
+    fn cube_struct_impl(&self) -> TokenStream {
+        if !self.buffer.is_present() {
+            return TokenStream::new();
+        }
+
+        if let Some(field) = self.fields.iter().find(|field| field.comptime.is_present()) {
+            return syn::Error::new_spanned(
+                field.ident.as_ref().unwrap(),
+                "Comptime fields can't be stored in global buffers",
+            )
+            .into_compile_error();
+        }
+
+        let cube_struct = prelude_type("CubeStruct");
+        let cube_primitive = prelude_type("CubePrimitive");
+        let field_info = prelude_type("StructFieldInfo");
+        let expand_element = prelude_type("ExpandElement");
+        let name = &self.ident;
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+
+        let infos = self.fields.iter().map(|field| {
+            let (_, ident, ty) = field.split();
+            let line_size = field.line_size.unwrap_or(1);
+            quote![#field_info::new(stringify!(#ident), <#ty as #cube_primitive>::as_elem(), #line_size)]
+        });
+        let from_fields = self.fields.iter().map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            quote![#ident: fields.next().unwrap().into()]
+        });
+        let into_fields = self.fields.iter().map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            quote![value.#ident.into()]
+        });
+
+        quote! {
+            impl #generics #cube_struct for #name #generic_names #where_clause {
+                fn fields() -> Vec<#field_info> {
+                    vec![#(#infos),*]
+                }
+
+                fn __expand_from_fields(fields: Vec<#expand_element>) -> Self::ExpandType {
+                    let mut fields = fields.into_iter();
+                    #name_expand {
+                        #(#from_fields),*
+                    }
+                }
+
+                fn __expand_into_fields(value: Self::ExpandType) -> Vec<#expand_element> {
+                    vec![#(#into_fields),*]
+                }
             }
         }
     }
@@ -97,6 +157,7 @@ impl CubeTypeStruct {
         let register_body = self
             .fields
             .iter()
+            .filter(|field| !field.comptime.is_present())
             .map(TypeField::split)
             .map(|(_, ident, _)| quote![self.#ident.register(launcher)]);
 
@@ -139,8 +200,12 @@ impl CubeTypeStruct {
             let name = &field.ident;
             let ty = &field.ty;
 
-            quote! {
-               #name: <#ty as #launch_arg>::compilation_arg::<R>(&runtime_arg.#name)
+            if field.comptime.is_present() {
+                quote![#name: runtime_arg.#name.clone()]
+            } else {
+                quote! {
+                   #name: <#ty as #launch_arg>::compilation_arg::<R>(&runtime_arg.#name)
+                }
             }
         });
         quote! {
@@ -211,11 +276,21 @@ impl CubeTypeStruct {
 
     fn launch_arg_impl(&self) -> proc_macro2::TokenStream {
         let launch_arg_expand = prelude_type("LaunchArgExpand");
-        let body_input = self.fields.iter().map(TypeField::split).map(|(_vis, name, ty)| {
-            quote![#name: <#ty as #launch_arg_expand>::expand(&arg.#name, builder)]
+        let body_input = self.fields.iter().map(|field| {
+            let (_vis, name, ty) = field.split();
+            if field.comptime.is_present() {
+                quote![#name: arg.#name.clone()]
+            } else {
+                quote![#name: <#ty as #launch_arg_expand>::expand(&arg.#name, builder)]
+            }
         });
-        let body_output = self.fields.iter().map(TypeField::split).map(|(_vis, name, ty)| {
-            quote![#name: <#ty as #launch_arg_expand>::expand_output(&arg.#name, builder)]
+        let body_output = self.fields.iter().map(|field| {
+            let (_vis, name, ty) = field.split();
+            if field.comptime.is_present() {
+                quote![#name: arg.#name.clone()]
+            } else {
+                quote![#name: <#ty as #launch_arg_expand>::expand_output(&arg.#name, builder)]
+            }
         });
 
         let name = &self.ident;
@@ -271,21 +346,17 @@ impl CubeTypeStruct {
 
     fn expand_type_impl(&self) -> proc_macro2::TokenStream {
         let init = prelude_type("Init");
-        let into_runtime = prelude_type("IntoRuntime");
         let context = prelude_type("CubeContext");
-        let name = &self.ident;
         let name_expand = &self.name_expand;
         let (generics, generic_names, where_clause) = self.generics.split_for_impl();
-        let body = self
-            .fields
-            .iter()
-            .map(TypeField::split)
-            .map(|(_, ident, _)| quote![#ident: #init::init(self.#ident, context)]);
-        let fields_to_runtime = self
-            .fields
-            .iter()
-            .map(TypeField::split)
-            .map(|(_, name, _)| quote![#name: #into_runtime::__expand_runtime_method(self.#name, context)]);
+        let body = self.fields.iter().map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            if field.comptime.is_present() {
+                quote![#ident: self.#ident]
+            } else {
+                quote![#ident: #init::init(self.#ident, context)]
+            }
+        });
 
         quote! {
             impl #generics #init for #name_expand #generic_names #where_clause {
@@ -295,7 +366,26 @@ impl CubeTypeStruct {
                     }
                 }
             }
+        }
+    }
 
+    /// Launched types hold global buffers, which can't exist at compile time, so they are never
+    /// converted to runtime values.
+    fn runtime_impl(&self) -> proc_macro2::TokenStream {
+        let into_runtime = prelude_type("IntoRuntime");
+        let name = &self.ident;
+        let name_expand = &self.name_expand;
+        let (generics, generic_names, where_clause) = self.generics.split_for_impl();
+        let fields_to_runtime = self.fields.iter().map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            if field.comptime.is_present() {
+                quote![#name: self.#name]
+            } else {
+                quote![#name: #into_runtime::__expand_runtime_method(self.#name, context)]
+            }
+        });
+
+        quote! {
             impl #generics #into_runtime for #name #generic_names #where_clause {
                 fn __expand_runtime_method(self, context: &mut CubeContext) -> Self::ExpandType {
                     let expand = #name_expand {
@@ -326,14 +416,22 @@ impl TypeField {
         let vis = &self.vis;
         let name = self.ident.as_ref().unwrap();
         let ty = &self.ty;
-        quote![#vis #name: <#ty as #launch_arg>::RuntimeArg<'a, R>]
+        if self.comptime.is_present() {
+            quote![#vis #name: #ty]
+        } else {
+            quote![#vis #name: <#ty as #launch_arg>::RuntimeArg<'a, R>]
+        }
     }
 
     pub fn launch_new_arg(&self) -> TokenStream {
         let launch_arg = prelude_type("LaunchArg");
         let name = self.ident.as_ref().unwrap();
         let ty = &self.ty;
-        quote![#name: <#ty as #launch_arg>::RuntimeArg<'a, R>]
+        if self.comptime.is_present() {
+            quote![#name: #ty]
+        } else {
+            quote![#name: <#ty as #launch_arg>::RuntimeArg<'a, R>]
+        }
     }
 
     pub fn compilation_arg_field(&self) -> TokenStream {
@@ -341,7 +439,11 @@ impl TypeField {
         let vis = &self.vis;
         let name = self.ident.as_ref().unwrap();
         let ty = &self.ty;
-        quote![#vis #name: <#ty as #launch_arg>::CompilationArg]
+        if self.comptime.is_present() {
+            quote![#vis #name: #ty]
+        } else {
+            quote![#vis #name: <#ty as #launch_arg>::CompilationArg]
+        }
     }
 
     pub fn split(&self) -> (&Visibility, &Ident, &Type) {
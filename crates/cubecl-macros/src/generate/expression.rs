@@ -218,10 +218,25 @@ impl Expression {
                 let path = frontend_path();
                 quote![#path::branch::break_expand(context);]
             }
-            Expression::Continue(span) => error!(*span, "Continue not supported yet"),
-            Expression::Return { expr, span, .. } => {
-                if expr.is_some() {
-                    error!(*span, "Only void return is supported.")
+            Expression::Continue => {
+                let path = frontend_path();
+                quote![#path::branch::continue_expand(context);]
+            }
+            Expression::Return { expr, .. } => {
+                if let Some(expr) = expr {
+                    let value = match expr.as_const(context) {
+                        Some(as_const) => {
+                            let expand = frontend_type("ExpandElementTyped");
+                            quote![#expand::from_lit(#as_const)]
+                        }
+                        None => expr.to_tokens(context),
+                    };
+                    quote! {
+                        {
+                            let __value = #value;
+                            __return.expand_return(context, __value);
+                        }
+                    }
                 } else {
                     quote![cubecl::frontend::branch::return_expand(context);]
                 }
@@ -242,6 +257,7 @@ impl Expression {
                 var_ty,
                 block,
                 scope,
+                returns,
             } => {
                 let for_ty = frontend_type("branch");
 
@@ -252,20 +268,30 @@ impl Expression {
                     .unwrap_or(quote![false]);
                 let block = context.in_fn_mut(scope, |ctx| block.to_tokens(ctx));
                 let var_ty = var_ty.as_ref().map(|it| quote![: #it]);
+                let propagate = propagate_return(*returns);
 
                 quote! {
                     {
                         let _range = #range;
                         let _unroll = #unroll;
                         #for_ty::for_expand(context, _range, _unroll, |context, #var_name #var_ty| #block);
+                        #propagate
                     }
                 }
             }
-            Expression::Loop { block, scope } => {
+            Expression::Loop {
+                block,
+                scope,
+                returns,
+            } => {
                 let loop_ty = frontend_type("branch");
                 let block = context.in_fn_mut(scope, |ctx| block.to_tokens(ctx));
+                let propagate = propagate_return(*returns);
 
-                quote![#loop_ty::loop_expand(context, |context| #block);]
+                quote! {
+                    #loop_ty::loop_expand(context, |context| #block);
+                    #propagate
+                }
             }
             Expression::If {
                 condition,
@@ -331,6 +357,7 @@ impl Expression {
                 value,
                 cases,
                 default,
+                returns,
             } => {
                 let branch = frontend_type("branch");
                 let switch = match default.ret.is_some() {
@@ -346,12 +373,15 @@ impl Expression {
                         quote![.case(context, #val, |context| #block)]
                     })
                     .collect::<Vec<_>>();
+                let propagate = propagate_return(*returns);
                 quote! {
                     {
                         let _val = #value;
-                        #branch::#switch(context, _val.into(), |context| #default)
+                        let _switch = #branch::#switch(context, _val.into(), |context| #default)
                             #(#blocks)*
-                            .finish(context)
+                            .finish(context);
+                        #propagate
+                        _switch
                     }
                 }
             }
@@ -398,8 +428,56 @@ impl Expression {
                 }
             }
 
-            Expression::Slice { span, .. } => {
-                error!(*span, "Slice expressions not yet implemented")
+            Expression::Slice {
+                expr,
+                span,
+                ranges,
+                mutable,
+            } => {
+                let [Expression::Range {
+                    start,
+                    end,
+                    inclusive,
+                    ..
+                }] = ranges.as_slice()
+                else {
+                    return error!(*span, "Only slices with a single range are supported");
+                };
+                let elem = frontend_type("ExpandElementTyped");
+                let slice_op = frontend_type("SliceOperatorExpand");
+                let frontend_path = frontend_path();
+
+                let expr = expr.to_tokens(context);
+                let start = start
+                    .as_const(context)
+                    .map(|as_const| quote![#elem::<u32>::from_lit(#as_const)])
+                    .unwrap_or_else(|| start.to_tokens(context));
+                let one = inclusive.then(|| quote![+ 1]);
+                // Without an end, the slice goes to the end of the container.
+                let end = match end {
+                    Some(end) => match end.as_const(context) {
+                        Some(as_const) => quote![#elem::<u32>::from_lit((#as_const) #one)],
+                        None if *inclusive => {
+                            let end = end.to_tokens(context);
+                            quote![#frontend_path::add::expand(context, #end, #elem::from_lit(1u32))]
+                        }
+                        None => end.to_tokens(context),
+                    },
+                    None => quote![_array.clone().__expand_len_method(context)],
+                };
+                let method = match mutable {
+                    true => format_ident!("__expand_slice_mut_method"),
+                    false => format_ident!("__expand_slice_method"),
+                };
+
+                quote! {
+                    {
+                        let _array = #expr;
+                        let _start = #start;
+                        let _end = #end;
+                        #slice_op::#method(&_array, context, _start, _end)
+                    }
+                }
             }
             Expression::ArrayInit { init, len } => {
                 let init_ty = frontend_type("ArrayInit");
@@ -469,11 +547,20 @@ impl Expression {
 
 impl ConstMatchArm {
     pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
-        let path = &self.pat;
         let expr = self.expr.to_tokens(context);
+        self.with_body(expr)
+    }
 
-        quote! {
-            #path => #expr
+    pub fn with_body(&self, body: TokenStream) -> TokenStream {
+        let path = &self.pat;
+
+        match &self.guard {
+            Some(guard) => quote! {
+                #path if #guard => #body
+            },
+            None => quote! {
+                #path => #body
+            },
         }
     }
 }
@@ -501,6 +588,12 @@ impl Block {
     }
 }
 
+/// Break out of the enclosing loop after a loop or a switch returning from the function, since
+/// breaking only left the innermost one.
+fn propagate_return(returns: bool) -> Option<TokenStream> {
+    returns.then(|| quote![__return.expand_propagate(context);])
+}
+
 fn split_generics(path: &Expression, context: &mut Context) -> (PathArguments, TokenStream) {
     let mut path = match path {
         Expression::Path { path, .. } => path.clone(),
@@ -1,24 +1,30 @@
 use darling::usage::{CollectLifetimes as _, CollectTypeParams as _, GenericsExt as _, Purpose};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::Ident;
+use syn::{Ident, Type};
 
 use crate::{
+    expression::Block,
     parse::kernel::{KernelBody, KernelFn, KernelParam, KernelReturns, KernelSignature, Launch},
-    paths::{core_type, prelude_path, prelude_type},
+    paths::{core_type, frontend_type, prelude_path, prelude_type},
+    scope::Context,
 };
 
 impl KernelFn {
     pub fn to_tokens_mut(&mut self) -> TokenStream {
         let prelude_path = prelude_path();
+        let vis = &self.vis;
         let sig = &self.sig;
         let body = match &self.body {
+            KernelBody::Block(block) if self.context.value_returns > 0 => {
+                &returning_body(block, &sig.returns.ty(), &mut self.context)
+            }
             KernelBody::Block(block) => &block.to_tokens(&mut self.context),
             KernelBody::Verbatim(tokens) => tokens,
         };
 
         let out = quote! {
-            #sig {
+            #vis #sig {
                 use #prelude_path::IntoRuntime as _;
 
                 #body
@@ -29,6 +35,35 @@ impl KernelFn {
     }
 }
 
+/// Expand the body in a loop running once, so that returning a value can break out of it.
+fn returning_body(block: &Block, return_ty: &Type, context: &mut Context) -> TokenStream {
+    let branch = frontend_type("branch");
+    let nested = context.nested_returns;
+    // The loop body is a mutable closure, so the parameters must be cloned as in for loops. The
+    // body is parsed in the scope following the parameters.
+    let body = context.in_fn_mut(&1, |ctx| block.to_tokens(ctx));
+    let body = match block.ret {
+        Some(_) => quote! {
+            let __value = #body;
+            __return.expand_return(context, __value);
+        },
+        None => quote! {
+            #body;
+            #branch::break_expand(context);
+        },
+    };
+
+    quote! {
+        {
+            let __return = #branch::ReturnExpand::<#return_ty>::new(context, #nested);
+            #branch::loop_expand(context, |context| {
+                #body
+            });
+            __return.finish()
+        }
+    }
+}
+
 impl ToTokens for KernelSignature {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let cube_context = prelude_type("CubeContext");
@@ -95,7 +130,8 @@ impl Launch {
         let type_params: Vec<_> = declared_type_params.difference(&used_type_params).collect();
 
         (!lifetimes.is_empty() || !type_params.is_empty())
-            .then(|| quote![__ty: ::core::marker::PhantomData<(#(#lifetimes,)* #(#type_params),*)>])
+            // Only the types are used, so the kernel is `Send + Sync` regardless of the generics.
+            .then(|| quote![__ty: ::core::marker::PhantomData<fn() -> (#(#lifetimes,)* #(#type_params),*)>])
     }
 
     pub fn compilation_args_def(&self) -> (Vec<TokenStream>, Vec<Ident>) {
@@ -206,6 +242,8 @@ impl Launch {
 
         quote! {
             let mut builder = #kernel_builder::with_local_allocator(#allocator);
+            builder.context.device_functions = <<__R as #runtime>::Compiler as #compiler>::device_functions();
+            builder.context.matrix_ops = <<__R as #runtime>::Compiler as #compiler>::matrix_ops;
             #io_map
             expand #generics(&mut builder.context, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
             builder.build(self.settings.clone())
@@ -224,6 +262,7 @@ impl Launch {
             let kernel_doc = format!("{} Kernel", self.func.sig.name);
 
             let (generics, generic_names, where_clause) = self.kernel_generics.split_for_impl();
+            let (static_generics, _, static_where_clause) = self.static_generics.split_for_impl();
             let const_params: Vec<_> = self.comptime_params().collect();
             let param_names = self
                 .comptime_params()
@@ -257,7 +296,7 @@ impl Launch {
                     }
                 }
 
-                impl #generics #kernel for #kernel_name #generic_names #where_clause {
+                impl #static_generics #kernel for #kernel_name #generic_names #static_where_clause {
                     fn define(&self) -> #kernel_definition {
                         #define
                     }
@@ -267,6 +306,14 @@ impl Launch {
                         let cube_dim = self.settings.cube_dim.clone();
                         #kernel_id::new::<Self>().info((cube_dim, #(self.#info.clone()),* ))
                     }
+
+                    fn dynamic_shared_memory(&self) -> u32 {
+                        self.settings.dynamic_shared_memory
+                    }
+
+                    fn zero_initialize_shared_memory(&self) -> bool {
+                        self.settings.zero_initialize_shared_memory
+                    }
                 }
             }
         } else {
@@ -14,23 +14,41 @@ impl ToTokens for Launch {
 
         let name = &self.func.sig.name;
         let launch = self.launch();
+        let launch_checked = self.launch_checked();
         let launch_unchecked = self.launch_unchecked();
+        let launch_specialized = self.launch_specialized();
+        let launch_specialized_unchecked = self.launch_specialized_unchecked();
+        let launch_race_detection = self.launch_race_detection();
+        let launch_division_check = self.launch_division_check();
         let dummy = self.create_dummy_kernel();
         let kernel = self.kernel_definition();
-        let mut func = self.func.clone();
-        func.sig.name = format_ident!("expand");
-        let func = func.to_tokens_mut();
+        let func = match self.args.is_inline_never() {
+            Ok(true) => self.device_function(),
+            _ => {
+                let mut func = self.func.clone();
+                func.sig.name = format_ident!("expand");
+                let func = func.to_tokens_mut();
+                quote! {
+                    #[allow(unused, clippy::all)]
+                    pub #func
+                }
+            }
+        };
 
         let out = quote! {
             #vis mod #name {
                 use super::*;
 
-                #[allow(unused, clippy::all)]
-                pub #func
+                #func
 
                 #kernel
                 #launch
+                #launch_checked
                 #launch_unchecked
+                #launch_specialized
+                #launch_specialized_unchecked
+                #launch_race_detection
+                #launch_division_check
                 #dummy
             }
         };
@@ -45,6 +63,61 @@ impl ToTokens for Launch {
 }
 
 impl Launch {
+    /// The expand function of a `#[cube(inline = never)]` function, which expands the body into
+    /// a device function when the compiler supports it.
+    fn device_function(&self) -> TokenStream {
+        let call_expand = prelude_type("call_expand");
+
+        let mut inlined = self.func.clone();
+        inlined.sig.name = format_ident!("__expand_inlined");
+        let inlined = inlined.to_tokens_mut();
+
+        let mut sig = self.func.sig.clone();
+        sig.name = format_ident!("expand");
+
+        let name = self.func.sig.name.to_string();
+        let params: Vec<_> = self.func.sig.parameters.iter().map(|it| &it.name).collect();
+        let type_params = self.func.sig.generics.type_params().map(|it| &it.ident);
+        let const_params = self.func.sig.generics.const_params().map(|it| &it.ident);
+        let (_, generics, _) = self.func.sig.generics.split_for_impl();
+        let generics = generics.as_turbofish();
+        let is_unit =
+            matches!(self.func.sig.returns.ty(), syn::Type::Tuple(tuple) if tuple.elems.is_empty());
+
+        let (ret, out) = if is_unit {
+            (quote![None], quote![;])
+        } else {
+            (quote![Some(__out.into())], quote![.unwrap().into()])
+        };
+
+        quote! {
+            #[allow(unused, clippy::all)]
+            #inlined
+
+            #[allow(unused, clippy::all)]
+            pub #sig {
+                let __args = vec![#(#params.into()),*];
+                #call_expand(
+                    context,
+                    concat!(module_path!(), "::", #name),
+                    // Instantiations differing only by their const generics are distinct functions.
+                    &format!(
+                        "{}{:?}",
+                        ::core::any::type_name::<(#(#type_params,)*)>(),
+                        (#(#const_params,)*),
+                    ),
+                    __args,
+                    |context, __args| {
+                        let mut __args = __args.into_iter();
+                        #(let #params = __args.next().unwrap().into();)*
+                        let __out = __expand_inlined #generics(context, #(#params),*);
+                        #ret
+                    },
+                )#out
+            }
+        }
+    }
+
     fn launch(&self) -> TokenStream {
         if self.args.launch.is_present() {
             let compute_client = prelude_type("ComputeClient");
@@ -58,6 +131,7 @@ impl Launch {
             let generics = &self.launch_generics;
             let args = self.launch_args();
             let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
 
             quote! {
                 #[allow(clippy::too_many_arguments)]
@@ -66,6 +140,7 @@ impl Launch {
                     __client: &#compute_client<__R::Server, __R::Channel>,
                     __cube_count: #cube_count,
                     __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
                     #(#args),*
                 ) -> () {
                     #body
@@ -77,6 +152,41 @@ impl Launch {
         }
     }
 
+    fn launch_checked(&self) -> TokenStream {
+        if self.args.launch.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+            let launch_error = prelude_type("LaunchError");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime after validating the arguments",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub fn launch_checked #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
+                    #(#args),*
+                ) -> Result<(), #launch_error> {
+                    #body
+                    launcher.launch_checked(__cube_count, kernel, __client)
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
     fn launch_unchecked(&self) -> TokenStream {
         if self.args.launch_unchecked.is_present() {
             let compute_client = prelude_type("ComputeClient");
@@ -90,6 +200,7 @@ impl Launch {
             let generics = &self.launch_generics;
             let args = self.launch_args();
             let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
 
             quote! {
                 #[allow(clippy::too_many_arguments)]
@@ -98,6 +209,7 @@ impl Launch {
                     __client: &#compute_client<__R::Server, __R::Channel>,
                     __cube_count: #cube_count,
                     __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
                     #(#args),*
                 ) -> () {
                     #body
@@ -109,6 +221,148 @@ impl Launch {
         }
     }
 
+    fn launch_specialized(&self) -> TokenStream {
+        if self.args.launch.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+            let specialization_cache = prelude_type("SpecializationCache");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime, specialized for the shapes of the tensors",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub fn launch_specialized #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __specializations: &#specialization_cache,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
+                    #(#args),*
+                ) -> () {
+                    #body
+                    launcher.launch_specialized(__cube_count, kernel, __client, __specializations);
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    fn launch_specialized_unchecked(&self) -> TokenStream {
+        if self.args.launch_unchecked.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+            let specialization_cache = prelude_type("SpecializationCache");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime, specialized for the shapes of the tensors",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub unsafe fn launch_specialized_unchecked #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __specializations: &#specialization_cache,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
+                    #(#args),*
+                ) -> () {
+                    #body
+                    launcher.launch_specialized_unchecked(__cube_count, kernel, __client, __specializations);
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    fn launch_race_detection(&self) -> TokenStream {
+        if self.args.launch.is_present() || self.args.launch_unchecked.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+            let data_race = prelude_type("DataRace");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime, recording its memory accesses to find the data races between its units",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub fn launch_race_detection #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __max_accesses: u32,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
+                    #(#args),*
+                ) -> Vec<#data_race> {
+                    #body
+                    launcher.launch_race_detection(__cube_count, kernel, __client, __max_accesses)
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
+    fn launch_division_check(&self) -> TokenStream {
+        if self.args.launch.is_present() || self.args.launch_unchecked.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime with its divisions by zero returning zero, and return the number of divisions by zero it executed",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub fn launch_division_check #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
+                    #(#args),*
+                ) -> u32 {
+                    #body
+                    launcher.launch_division_check(__cube_count, kernel, __client)
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
     fn launch_body(&self) -> TokenStream {
         let kernel_launcher = prelude_type("KernelLauncher");
 
@@ -147,9 +401,36 @@ impl Launch {
 
     fn configure_settings(&self) -> TokenStream {
         let kernel_settings = prelude_type("KernelSettings");
+        let zero_shared_memory = self.args.zero_shared_memory.is_present();
+        let fast_math = match self.args.fast_math.is_present() {
+            true => quote![Relaxed],
+            false => quote![Strict],
+        };
+        let division = match self.args.safe_division.is_present() {
+            true => quote![Zero],
+            false => quote![Native],
+        };
+        let core_path = core_path();
+
+        let dynamic_shared_memory = match self.args.dynamic_shared_memory.is_present() {
+            true => quote![__dynamic_shared_memory],
+            false => quote![0],
+        };
 
         quote! {
-            let mut __settings = #kernel_settings::default().cube_dim(__cube_dim);
+            let mut __settings = #kernel_settings::default()
+                .cube_dim(__cube_dim)
+                .zero_initialize_shared_memory(#zero_shared_memory)
+                .fast_math(#core_path::ir::FastMath::#fast_math)
+                .division(#core_path::ir::DivisionPolicy::#division)
+                .dynamic_shared_memory(#dynamic_shared_memory);
+        }
+    }
+
+    fn dynamic_shared_memory_param(&self) -> TokenStream {
+        match self.args.dynamic_shared_memory.is_present() {
+            true => quote![__dynamic_shared_memory: u32,],
+            false => TokenStream::new(),
         }
     }
 
@@ -166,6 +447,7 @@ impl Launch {
             let (_, generic_names, _) = self.kernel_generics.split_for_impl();
 
             let settings = self.configure_settings();
+            let dynamic_shared_memory = self.dynamic_shared_memory_param();
             let kernel_name = self.kernel_name();
             let core_path = core_path();
             let comptime_args = self.launch_args();
@@ -178,6 +460,7 @@ impl Launch {
                 pub fn create_dummy_kernel #generics(
                     __cube_count: #cube_count,
                     __cube_dim: #cube_dim,
+                    #dynamic_shared_memory
                     #(#comptime_args),*
                 ) -> #kernel_name #generic_names {
                     use #core_path::frontend::ArgSettings as _;
@@ -6,8 +6,21 @@ use crate::{expression::Expression, paths::frontend_type, scope::Context, statem
 
 impl Statement {
     pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
+        let statement = self.statement_tokens(context);
         match self {
-            Statement::Local { variable, init } => {
+            Statement::Local { span, .. } | Statement::Expression { span, .. } => {
+                quote_spanned! {*span=>
+                    context.register_location(file!(), line!(), column!());
+                    #statement
+                }
+            }
+            Statement::Skip => statement,
+        }
+    }
+
+    fn statement_tokens(&self, context: &mut Context) -> TokenStream {
+        match self {
+            Statement::Local { variable, init, .. } => {
                 let cube_type = frontend_type("CubeType");
                 let name = &variable.name;
                 let is_mut = variable.is_mut || init.as_deref().map(is_mut_owned).unwrap_or(false);
@@ -54,6 +67,7 @@ impl Statement {
             Statement::Expression {
                 expression,
                 terminated,
+                ..
             } => {
                 let terminator = terminated.then(|| Token![;](Span::call_site()));
                 if let Some(as_const) = expression.as_const(context) {
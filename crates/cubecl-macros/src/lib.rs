@@ -26,10 +26,25 @@ mod statement;
 /// Mark a cube function, trait or implementation for expansion.
 ///
 /// # Arguments
-/// * `launch` - generates a function to launch the kernel
+/// * `launch` - generates a function to launch the kernel. Kernels are identified by their type,
+///   so the launch functions bound every type parameter by `'static`
 /// * `launch_unchecked` - generates a launch function without checks
 /// * `debug` - panics after generation to print the output to console
 /// * `create_dummy_kernel` - Generates a function to create a kernel without launching it. Used for testing.
+/// * `zero_shared_memory` - zero-initializes the shared memory before the kernel runs, even in unchecked mode
+/// * `safe_division` - divisions and remainders by zero return zero in checked mode, see `DivisionPolicy::Zero`
+/// * `fast_math` - compiles the kernel with approximate floating point functions, see `FastMath::Relaxed`
+/// * `dynamic_shared_memory` - adds a `u32` parameter after the cube dim to the launch functions,
+///   the size in bytes of the shared memory declared with `SharedMemory::new_dynamic`
+///
+/// # Comptime matches
+///
+/// Matches on comptime values bind comptime values. Every bare identifier of a pattern is a
+/// binding, so constants and unit variants must be written as paths, such as
+/// `Activation::Identity` or `self::LIMIT`.
+///
+/// `if let` and `while let` also match comptime values. The pattern of a `while let` is matched
+/// once when the kernel is expanded, so its loop either runs until a `break` or doesn't run at all.
 ///
 /// # Example
 ///
@@ -105,12 +120,19 @@ fn cube_impl(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream>
 }
 
 /// Derive macro to define a cube type that is launched with a kernel
+///
+/// Fields marked with `#[expand(comptime)]` are passed by value to the kernel compilation instead
+/// of being registered as runtime arguments.
 #[proc_macro_derive(CubeLaunch, attributes(expand))]
 pub fn module_derive_cube_launch(input: TokenStream) -> TokenStream {
     gen_cube_type(input, true)
 }
 
 /// Derive macro to define a cube type that is not launched
+///
+/// Structs marked with `#[expand(buffer)]` can be stored in a `StructArray`, with their line
+/// fields annotated with `#[expand(line_size = N)]`. They are stored as 32-bit words with the
+/// WGSL struct layout, not as struct declarations in the generated shaders.
 #[proc_macro_derive(CubeType, attributes(expand))]
 pub fn module_derive_cube_type(input: TokenStream) -> TokenStream {
     gen_cube_type(input, false)
@@ -1,3 +1,4 @@
+use proc_macro2::Span;
 use quote::quote;
 use syn::{spanned::Spanned, Expr, ExprForLoop, ExprIf, ExprLoop, ExprMatch, Ident, Lit, Pat};
 
@@ -21,15 +22,17 @@ pub fn expand_for_loop(for_loop: ExprForLoop, context: &mut Context) -> syn::Res
         return expand_for_in_loop(var.ident, right, for_loop.body, context);
     }
 
-    let (block, scope) = context.in_scope(|context| {
-        context.push_variable(
-            var.ident.clone(),
-            var.ty.clone(),
-            false,
-            var.is_ref,
-            var.is_mut,
-        );
-        Block::from_block(for_loop.body, context)
+    let ((block, scope), returns) = context.in_breakable(|context| {
+        context.in_scope(|context| {
+            context.push_variable(
+                var.ident.clone(),
+                var.ty.clone(),
+                false,
+                var.is_ref,
+                var.is_mut,
+            );
+            Block::from_block(for_loop.body, context)
+        })
     })?;
 
     Ok(Expression::ForLoop {
@@ -39,6 +42,7 @@ pub fn expand_for_loop(for_loop: ExprForLoop, context: &mut Context) -> syn::Res
         var_ty: var.ty,
         block,
         scope,
+        returns,
     })
 }
 
@@ -67,16 +71,31 @@ fn expand_for_in_loop(
 }
 
 pub fn expand_loop(loop_expr: ExprLoop, context: &mut Context) -> syn::Result<Expression> {
-    let (block, scope) = context.in_scope(|ctx| Block::from_block(loop_expr.body, ctx))?;
-    Ok(Expression::Loop { block, scope })
+    let ((block, scope), returns) =
+        context.in_breakable(|ctx| ctx.in_scope(|ctx| Block::from_block(loop_expr.body, ctx)))?;
+    Ok(Expression::Loop {
+        block,
+        scope,
+        returns,
+    })
 }
 
 pub fn expand_if(if_expr: ExprIf, context: &mut Context) -> syn::Result<Expression> {
     let span = if_expr.span();
+    // Bindings of an `if let` pattern are comptime values, like the matched expression.
+    let bindings = match if_expr.cond.as_ref() {
+        Expr::Let(cond) => pattern_bindings(&cond.pat),
+        _ => Vec::new(),
+    };
     let condition = Expression::from_expr(*if_expr.cond, context)
         .map_err(|_| syn::Error::new(span, "Unsupported while condition"))?;
 
-    let (then_block, _) = context.in_scope(|ctx| Block::from_block(if_expr.then_branch, ctx))?;
+    let (then_block, _) = context.in_scope(|ctx| {
+        for ident in bindings {
+            ctx.push_variable(ident, None, true, false, false);
+        }
+        Block::from_block(if_expr.then_branch, ctx)
+    })?;
     let else_branch = if let Some((_, else_branch)) = if_expr.else_branch {
         let (expr, _) = context.in_scope(|ctx| Expression::from_expr(*else_branch, ctx))?;
         Some(Box::new(expr))
@@ -111,17 +130,15 @@ pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression
         match expr {
             Expr::Block(block) => Block::from_block(block.block, context).ok(),
             expr => {
+                let span = expr.span();
                 let expr = Expression::from_expr(expr, context).ok()?;
-                Some(Block {
-                    ret: Some(Box::new(expr)),
-                    inner: vec![],
-                    ty: None,
-                })
+                Some(Block::from_ret(Box::new(expr), span))
             }
         }
     }
 
     let value = Box::new(Expression::from_expr(*mat.expr, context).ok()?);
+    let value_returns = context.value_returns;
 
     let arms = mat
         .arms
@@ -148,10 +165,14 @@ pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression
         .map(|(lit, body)| Some((lit, parse_body(*body, context)?)))
         .collect::<Option<Vec<_>>>()?;
 
+    let returns = context.value_returns > value_returns;
+    context.nested_returns |= returns;
+
     Some(Expression::Switch {
         value,
         cases,
         default,
+        returns,
     })
 }
 
@@ -164,6 +185,19 @@ impl Block {
             .collect::<Result<Vec<_>, _>>()?;
         // Pop implicit return if it exists so we can assign it as the block output
         let ret = match statements.pop() {
+            // A trailing `return` leaves the function instead of producing the block output.
+            Some(Statement::Expression {
+                expression,
+                terminated: false,
+                span,
+            }) if matches!(*expression, Expression::Return { .. }) => {
+                statements.push(Statement::Expression {
+                    expression,
+                    terminated: true,
+                    span,
+                });
+                None
+            }
             Some(Statement::Expression {
                 expression,
                 terminated: false,
@@ -182,4 +216,55 @@ impl Block {
             ty,
         })
     }
+
+    /// A block made of a single expression, such as the body of a match arm.
+    pub fn from_ret(expr: Box<Expression>, span: Span) -> Self {
+        match *expr {
+            Expression::Return { .. } => Self {
+                inner: vec![Statement::Expression {
+                    expression: expr,
+                    terminated: true,
+                    span,
+                }],
+                ret: None,
+                ty: None,
+            },
+            _ => Self {
+                ret: Some(expr),
+                inner: vec![],
+                ty: None,
+            },
+        }
+    }
+}
+
+/// The identifiers bound by a match arm pattern.
+///
+/// Constants and unit variants can't be told apart from bindings without resolving their names,
+/// so they must be written as paths, such as `Activation::Identity`, and every bare identifier is
+/// a binding.
+pub fn pattern_bindings(pat: &Pat) -> Vec<Ident> {
+    match pat {
+        Pat::Ident(ident) => {
+            let mut idents = vec![ident.ident.clone()];
+            if let Some((_, subpat)) = &ident.subpat {
+                idents.extend(pattern_bindings(subpat));
+            }
+            idents
+        }
+        // Every case binds the same identifiers.
+        Pat::Or(or) => or.cases.first().map(pattern_bindings).unwrap_or_default(),
+        Pat::Paren(paren) => pattern_bindings(&paren.pat),
+        Pat::Reference(reference) => pattern_bindings(&reference.pat),
+        Pat::Slice(slice) => slice.elems.iter().flat_map(pattern_bindings).collect(),
+        Pat::Struct(pat) => pat
+            .fields
+            .iter()
+            .flat_map(|field| pattern_bindings(&field.pat))
+            .collect(),
+        Pat::Tuple(tuple) => tuple.elems.iter().flat_map(pattern_bindings).collect(),
+        Pat::TupleStruct(tuple) => tuple.elems.iter().flat_map(pattern_bindings).collect(),
+        Pat::Type(ty) => pattern_bindings(&ty.pat),
+        _ => vec![],
+    }
 }
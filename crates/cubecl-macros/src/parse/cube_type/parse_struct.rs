@@ -17,6 +17,8 @@ pub struct CubeTypeStruct {
     pub fields: Vec<TypeField>,
     pub generics: Generics,
     pub vis: Visibility,
+    /// Whether the struct can be stored in global buffers.
+    pub buffer: Flag,
 }
 
 #[derive(FromField, Clone, Debug)]
@@ -26,6 +28,8 @@ pub struct TypeField {
     pub ident: Option<Ident>,
     pub ty: Type,
     pub comptime: Flag,
+    /// The number of elements of a line field stored in a global buffer.
+    pub line_size: Option<u8>,
 }
 
 fn unwrap_fields(mut ty: CubeTypeStruct) -> CubeTypeStruct {
@@ -2,7 +2,7 @@ use std::mem::take;
 
 use quote::quote_spanned;
 use syn::{
-    parse_quote,
+    parse_quote, parse_quote_spanned,
     spanned::Spanned,
     visit_mut::{self, VisitMut},
     Expr, ExprLoop, ExprWhile, Index, Local, LocalInit, Pat, PatStruct, PatTuple, PatTupleStruct,
@@ -13,7 +13,10 @@ pub struct Desugar;
 impl VisitMut for Desugar {
     fn visit_expr_mut(&mut self, i: &mut syn::Expr) {
         if let Expr::While(inner) = i {
-            *i = Expr::Loop(desugar_while(inner))
+            *i = match inner.cond.as_ref() {
+                Expr::Let(_) => Expr::Loop(desugar_while_let(inner)),
+                _ => Expr::Loop(desugar_while(inner)),
+            }
         }
         visit_mut::visit_expr_mut(self, i);
     }
@@ -61,6 +64,26 @@ fn desugar_while(inner: &ExprWhile) -> ExprLoop {
     }
 }
 
+/// Only comptime values can be matched, so the pattern is matched once when the kernel is
+/// expanded, and the runtime loop either runs the body or breaks right away.
+fn desugar_while_let(inner: &ExprWhile) -> ExprLoop {
+    let cond = &inner.cond;
+    let attrs = &inner.attrs;
+    let label = &inner.label;
+    let body = &inner.body;
+    let if_let: Expr = parse_quote_spanned! {cond.span()=>
+        if #cond #body else {
+            break;
+        }
+    };
+    parse_quote! {
+        #(#attrs)*
+        #label loop {
+            #if_let
+        }
+    }
+}
+
 fn desugar_struct_destructure(pat: PatStruct, init: LocalInit) -> Vec<Stmt> {
     let fields = pat.fields.into_iter().map(|field| {
         let attrs = field.attrs;
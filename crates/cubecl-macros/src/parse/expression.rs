@@ -12,7 +12,7 @@ use crate::{
 };
 
 use super::{
-    branch::{expand_for_loop, expand_if, expand_loop, numeric_match},
+    branch::{expand_for_loop, expand_if, expand_loop, numeric_match, pattern_bindings},
     operator::{parse_binop, parse_unop},
 };
 
@@ -152,7 +152,7 @@ impl Expression {
             Expr::Const(block) => Expression::Verbatim {
                 tokens: quote![#block],
             },
-            Expr::Continue(cont) => Expression::Continue(cont.span()),
+            Expr::Continue(_) => Expression::Continue,
             Expr::ForLoop(for_loop) => expand_for_loop(for_loop, context)?,
             Expr::Loop(loop_expr) => expand_loop(loop_expr, context)?,
             Expr::If(if_expr) => expand_if(if_expr, context)?,
@@ -191,14 +191,15 @@ impl Expression {
             Expr::Group(group) => Expression::from_expr(*group.expr, context)?,
             Expr::Paren(paren) => Expression::from_expr(*paren.expr, context)?,
             Expr::Return(ret) => {
-                let span = ret.expr.span();
+                if ret.expr.is_some() {
+                    context.value_returns += 1;
+                }
                 Expression::Return {
                     expr: ret
                         .expr
                         .map(|expr| Expression::from_expr(*expr, context))
                         .transpose()?
                         .map(Box::new),
-                    span,
                     _ty: context.return_type.clone(),
                 }
             }
@@ -232,7 +233,8 @@ impl Expression {
                     Expression::Slice {
                         expr: Box::new(expr),
                         span,
-                        _ranges: ranges,
+                        ranges,
+                        mutable: false,
                     }
                 } else {
                     let index = match index {
@@ -283,9 +285,18 @@ impl Expression {
                     let mut arms = Vec::new();
 
                     for arm in mat.arms.iter() {
+                        // Bindings of the pattern are comptime values, like the matched expression.
+                        let (expr, _) = context.in_scope(|ctx| {
+                            for ident in pattern_bindings(&arm.pat) {
+                                ctx.push_variable(ident, None, true, false, false);
+                            }
+                            Self::from_expr(arm.body.as_ref().clone(), ctx)
+                        })?;
+
                         arms.push(ConstMatchArm {
                             pat: arm.pat.clone(),
-                            expr: Box::new(Self::from_expr(arm.body.as_ref().clone(), context)?),
+                            guard: arm.guard.as_ref().map(|(_, guard)| guard.as_ref().clone()),
+                            expr: Box::new(expr),
                         });
                     }
 
@@ -334,9 +345,16 @@ impl Expression {
             }
             Expr::Infer(_) => Expression::Verbatim { tokens: quote![_] },
             Expr::Verbatim(verbatim) => Expression::Verbatim { tokens: verbatim },
-            Expr::Reference(reference) => Expression::Reference {
-                inner: Box::new(Expression::from_expr(*reference.expr, context)?),
-            },
+            Expr::Reference(reference) => {
+                let mut inner = Expression::from_expr(*reference.expr, context)?;
+                // `&mut array[a..b]` is a read-write slice.
+                if let Expression::Slice { mutable, .. } = &mut inner {
+                    *mutable = reference.mutability.is_some();
+                }
+                Expression::Reference {
+                    inner: Box::new(inner),
+                }
+            }
             Expr::Closure(expr) => {
                 let (body, scope) =
                     context.in_scope(|ctx| Expression::from_expr(*expr.body, ctx))?;
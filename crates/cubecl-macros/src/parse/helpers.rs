@@ -136,14 +136,14 @@ impl VisitMut for ReplaceIndices {
 
     fn visit_item_fn_mut(&mut self, i: &mut syn::ItemFn) {
         let prelude_path = prelude_path();
-        let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _};];
+        let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _, SliceOperator as _};];
         i.block.stmts.insert(0, import);
         visit_mut::visit_item_fn_mut(self, i);
     }
 
     fn visit_impl_item_fn_mut(&mut self, i: &mut syn::ImplItemFn) {
         let prelude_path = prelude_path();
-        let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _};];
+        let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _, SliceOperator as _};];
         i.block.stmts.insert(0, import);
         visit_mut::visit_impl_item_fn_mut(self, i);
     }
@@ -151,7 +151,7 @@ impl VisitMut for ReplaceIndices {
     fn visit_trait_item_fn_mut(&mut self, i: &mut syn::TraitItemFn) {
         if let Some(block) = &mut i.default {
             let prelude_path = prelude_path();
-            let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _};];
+            let import = parse_quote![use #prelude_path::{CubeIndex as _, CubeIndexMut as _, SliceOperator as _};];
             block.stmts.insert(0, import);
         }
         visit_mut::visit_trait_item_fn_mut(self, i);
@@ -170,8 +170,13 @@ impl VisitMut for ReplaceIndex {
             }
             Expr::Index(index) => {
                 let inner = &index.expr;
-                let index = &index.index;
-                *i = parse_quote![*#inner.cube_idx(#index)]
+                *i = match slice_bounds(&index.index) {
+                    Some((start, end)) => parse_quote![*#inner.slice(#start, #end)],
+                    None => {
+                        let index = &index.index;
+                        parse_quote![*#inner.cube_idx(#index)]
+                    }
+                }
             }
             _ => {}
         }
@@ -183,13 +188,39 @@ impl VisitMut for ReplaceIndexMut {
     fn visit_expr_mut(&mut self, i: &mut syn::Expr) {
         if let Expr::Index(index) = i {
             let inner = &index.expr;
-            let index = &index.index;
-            *i = parse_quote![*#inner.cube_idx_mut(#index)]
+            *i = match slice_bounds(&index.index) {
+                Some((start, end)) => parse_quote![*#inner.slice_mut(#start, #end)],
+                None => {
+                    let index = &index.index;
+                    parse_quote![*#inner.cube_idx_mut(#index)]
+                }
+            }
         }
         visit_mut::visit_expr_mut(self, i);
     }
 }
 
+/// The bounds of a range index, only used to type check the unexpanded function, so the missing
+/// bounds are placeholders.
+fn slice_bounds(index: &Expr) -> Option<(Expr, Expr)> {
+    match index {
+        Expr::Range(range) => {
+            let start = range
+                .start
+                .as_deref()
+                .cloned()
+                .unwrap_or_else(|| parse_quote![0u32]);
+            let end = range
+                .end
+                .as_deref()
+                .cloned()
+                .unwrap_or_else(|| parse_quote![u32::MAX]);
+            Some((start, end))
+        }
+        _ => None,
+    }
+}
+
 pub fn is_comptime_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("comptime")
 }
@@ -15,7 +15,17 @@ pub(crate) struct KernelArgs {
     pub launch_unchecked: Flag,
     pub debug: Flag,
     pub create_dummy_kernel: Flag,
+    /// Zero-initializes the shared memory of the launched kernel.
+    pub zero_shared_memory: Flag,
+    /// Compiles the launched kernel with relaxed floating point semantics.
+    pub fast_math: Flag,
+    /// Makes the divisions by zero of the kernel return zero in checked mode.
+    pub safe_division: Flag,
+    /// Adds a parameter to the launch functions for the size of the dynamic shared memory.
+    pub dynamic_shared_memory: Flag,
     pub local_allocator: Option<Expr>,
+    /// `inline = never` expands the function into a device function instead of inlining it.
+    pub inline: Option<Expr>,
 }
 
 pub fn from_tokens<T: FromMeta>(tokens: TokenStream) -> syn::Result<T> {
@@ -27,6 +37,18 @@ impl KernelArgs {
     pub fn is_launch(&self) -> bool {
         self.launch.is_present() || self.launch_unchecked.is_present()
     }
+
+    pub fn is_inline_never(&self) -> syn::Result<bool> {
+        match &self.inline {
+            None => Ok(false),
+            Some(Expr::Path(path)) if path.path.is_ident("never") => Ok(true),
+            Some(Expr::Path(path)) if path.path.is_ident("always") => Ok(false),
+            Some(expr) => Err(syn::Error::new_spanned(
+                expr,
+                "Expected `inline = never` or `inline = always`",
+            )),
+        }
+    }
 }
 
 pub struct Launch {
@@ -34,11 +56,14 @@ pub struct Launch {
     pub vis: Visibility,
     pub func: KernelFn,
     pub kernel_generics: Generics,
+    /// The kernel generics bounded by `'static`, for the items taking the `TypeId` of the kernel.
+    pub static_generics: Generics,
     pub launch_generics: Generics,
 }
 
 #[derive(Clone)]
 pub struct KernelFn {
+    pub vis: Visibility,
     pub sig: KernelSignature,
     pub body: KernelBody,
     pub context: Context,
@@ -216,6 +241,7 @@ impl KernelFn {
         let (block, _) = context.in_scope(|ctx| Block::from_block(block, ctx))?;
 
         Ok(KernelFn {
+            vis: Visibility::Inherited,
             sig,
             body: KernelBody::Block(block),
             context,
@@ -229,9 +255,33 @@ impl Launch {
 
         let vis = function.vis;
         let func = KernelFn::from_sig_and_block(function.sig, *function.block)?;
+
+        if args.is_inline_never()? {
+            if args.is_launch() {
+                return Err(syn::Error::new(
+                    func.sig.name.span(),
+                    "Kernels can't be device functions",
+                ));
+            }
+            for param in func.sig.parameters.iter() {
+                if param.is_const || (param.is_ref && param.is_mut) || param.name == "self" {
+                    return Err(syn::Error::new(
+                        param.name.span(),
+                        "Device functions only support runtime parameters passed by value",
+                    ));
+                }
+            }
+        }
+
         let mut kernel_generics = func.sig.generics.clone();
         kernel_generics.params.push(parse_quote![__R: #runtime]);
-        let mut expand_generics = kernel_generics.clone();
+        // Kernels are identified by the `TypeId` of their type, so the instantiations that are
+        // launched must be `'static`.
+        let mut static_generics = kernel_generics.clone();
+        for param in static_generics.type_params_mut() {
+            param.bounds.push(parse_quote!['static]);
+        }
+        let mut expand_generics = static_generics.clone();
         expand_generics.params =
             Punctuated::from_iter(iter::once(parse_quote!['kernel]).chain(expand_generics.params));
 
@@ -240,6 +290,7 @@ impl Launch {
             vis,
             func,
             kernel_generics,
+            static_generics,
             launch_generics: expand_generics,
         })
     }
@@ -1,5 +1,5 @@
 use quote::format_ident;
-use syn::{Pat, Stmt, Type, TypeReference};
+use syn::{spanned::Spanned, Pat, Stmt, Type, TypeReference};
 
 use crate::{
     expression::Expression,
@@ -9,6 +9,7 @@ use crate::{
 
 impl Statement {
     pub fn from_stmt(stmt: Stmt, context: &mut Context) -> syn::Result<Self> {
+        let span = stmt.span();
         let statement = match stmt {
             Stmt::Local(local) => {
                 let init = local
@@ -26,13 +27,18 @@ impl Statement {
 
                 let variable =
                     context.push_variable(ident, ty, is_const && !is_mut, is_ref, is_mut);
-                Self::Local { variable, init }
+                Self::Local {
+                    variable,
+                    init,
+                    span,
+                }
             }
             Stmt::Expr(expr, semi) => {
                 let expression = Box::new(Expression::from_expr(expr, context)?);
                 Statement::Expression {
                     terminated: semi.is_some() || !expression.needs_terminator(),
                     expression,
+                    span,
                 }
             }
             Stmt::Item(_) => Statement::Skip,
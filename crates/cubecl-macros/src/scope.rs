@@ -42,6 +42,10 @@ pub struct Context {
     scopes: Vec<ManagedScope>,
     level: usize,
     mut_scope_idx: usize,
+    /// The number of `return` expressions with a value parsed so far.
+    pub value_returns: usize,
+    /// Whether a `return` with a value is nested in a loop or a switch.
+    pub nested_returns: bool,
 }
 
 impl Context {
@@ -66,6 +70,8 @@ impl Context {
             scopes: vec![root_scope],
             level: 0,
             mut_scope_idx: 0,
+            value_returns: 0,
+            nested_returns: false,
         }
     }
 
@@ -114,6 +120,19 @@ impl Context {
         Ok((res, self.scopes.len()))
     }
 
+    /// Parse the body of a loop or a switch, returning whether it contains a `return` with a
+    /// value. Breaking out of the body doesn't return from the function in that case.
+    pub fn in_breakable<T>(
+        &mut self,
+        with: impl FnOnce(&mut Self) -> syn::Result<T>,
+    ) -> syn::Result<(T, bool)> {
+        let value_returns = self.value_returns;
+        let res = with(self)?;
+        let returns = self.value_returns > value_returns;
+        self.nested_returns |= returns;
+        Ok((res, returns))
+    }
+
     /// Mutable closures (for loops) have different behaviour because outer vars must be cloned
     pub fn in_fn_mut<T>(&mut self, scope: &Scope, with: impl FnOnce(&mut Self) -> T) -> T {
         let level = replace(&mut self.level, *scope);
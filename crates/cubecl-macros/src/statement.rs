@@ -1,15 +1,19 @@
 use crate::{expression::Expression, scope::ManagedVar};
+use proc_macro2::Span;
 use syn::{Ident, Type};
 
 #[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Statement {
     Local {
         variable: ManagedVar,
         init: Option<Box<Expression>>,
+        span: Span,
     },
     Expression {
         expression: Box<Expression>,
         terminated: bool,
+        span: Span,
     },
     Skip,
 }
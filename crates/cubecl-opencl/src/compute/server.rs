@@ -0,0 +1,395 @@
+use cubecl_cpp::{formatter::format_cpp, OpenClCompiler};
+
+use super::storage::{OpenClResource, OpenClStorage};
+use cubecl_core::compute::DebugInformation;
+use cubecl_core::ir::CubeDim;
+use cubecl_core::Feature;
+use cubecl_core::{prelude::*, CompilationOptions, KernelId};
+use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
+use cubecl_runtime::memory_management::{MemoryUsage, OutOfMemoryError};
+use cubecl_runtime::storage::BindingResource;
+use cubecl_runtime::{
+    memory_management::MemoryManagement,
+    server::{self, ComputeServer},
+};
+use cubecl_runtime::{ExecutionMode, TimestampsError, TimestampsResult};
+use opencl3::command_queue::CommandQueue;
+use opencl3::context::Context;
+use opencl3::kernel::{ExecuteKernel, Kernel};
+use opencl3::memory::ClMem;
+use opencl3::program::Program;
+use opencl3::types::CL_BLOCKING;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The name of the entry point written by the OpenCL dialect.
+const KERNEL_NAME: &str = "kernel_main";
+
+#[derive(Debug)]
+pub struct OpenClServer {
+    ctx: OpenClContext,
+    logger: DebugLogger,
+}
+
+#[derive(Debug)]
+pub(crate) struct OpenClContext {
+    context: Arc<Context>,
+    queue: CommandQueue,
+    memory_management: MemoryManagement<OpenClStorage>,
+    kernels: HashMap<KernelId, CompiledKernel>,
+    timestamps: KernelTimestamps,
+}
+
+#[derive(Debug)]
+enum KernelTimestamps {
+    Inferred { start_time: Instant },
+    Disabled,
+}
+
+impl KernelTimestamps {
+    fn enable(&mut self) {
+        if !matches!(self, Self::Disabled) {
+            return;
+        }
+
+        *self = Self::Inferred {
+            start_time: Instant::now(),
+        };
+    }
+
+    fn disable(&mut self) {
+        *self = Self::Disabled;
+    }
+}
+
+#[derive(Debug)]
+struct CompiledKernel {
+    cube_dim: CubeDim,
+    kernel: Kernel,
+}
+
+unsafe impl Send for OpenClServer {}
+
+impl OpenClServer {
+    fn read_sync(&mut self, binding: server::Binding) -> Vec<u8> {
+        let ctx = self.get_context();
+        let resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+
+        let mut data = vec![0; resource.size() as usize];
+
+        unsafe {
+            ctx.queue
+                .enqueue_read_buffer(&resource.buffer, CL_BLOCKING, 0, &mut data, &[])
+                .unwrap();
+        };
+
+        data
+    }
+}
+
+impl ComputeServer for OpenClServer {
+    type Kernel = Box<dyn CubeTask<OpenClCompiler>>;
+    type Storage = OpenClStorage;
+    type Feature = Feature;
+
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.id().name()
+    }
+
+    fn read(&mut self, binding: server::Binding) -> impl Future<Output = Vec<u8>> + 'static {
+        let value = self.read_sync(binding);
+        async { value }
+    }
+
+    fn create(&mut self, data: &[u8]) -> server::Handle {
+        let handle = self.empty(data.len());
+        self.write(handle.clone().binding(), data);
+        handle
+    }
+
+    fn binding_size(&mut self, binding: &server::Binding) -> u64 {
+        let ctx = self.get_context();
+        let storage = ctx.memory_management.get(binding.memory.clone());
+        storage.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
+
+    fn write(&mut self, binding: server::Binding, data: &[u8]) {
+        let ctx = self.get_context();
+        let mut resource = ctx.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+
+        unsafe {
+            ctx.queue
+                .enqueue_write_buffer(&mut resource.buffer, CL_BLOCKING, 0, data, &[])
+                .unwrap();
+        }
+    }
+
+    fn empty(&mut self, size: usize) -> server::Handle {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.reserve(size as u64, None);
+        server::Handle::new(handle, None, None)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle, OutOfMemoryError> {
+        let ctx = self.get_context();
+        let handle = ctx.memory_management.try_reserve(size as u64, None)?;
+        Ok(server::Handle::new(handle, None, None))
+    }
+
+    unsafe fn execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<server::Binding>,
+        mode: ExecutionMode,
+        _stream: server::Stream,
+    ) {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+        let dynamic_shared_memory = kernel.dynamic_shared_memory();
+
+        let profile_level = self.logger.profile_level();
+        let profile_info = if profile_level.is_some() {
+            Some((kernel.name(), kernel_id.clone()))
+        } else {
+            None
+        };
+
+        let count = match count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            // OpenCL can't read the dispatch size from a buffer, so it's read back before launching.
+            CubeCount::Dynamic(binding) => {
+                let data = self.read_sync(binding);
+                let data = bytemuck::cast_slice(&data);
+                assert!(
+                    data.len() == 3,
+                    "Dynamic cube count should contain 3 values"
+                );
+                (data[0], data[1], data[2])
+            }
+        };
+
+        let (ctx, logger) = self.get_context_with_logger();
+
+        if !ctx.kernels.contains_key(&kernel_id) {
+            ctx.compile_kernel(&kernel_id, kernel, logger, mode);
+        }
+
+        let resources = bindings
+            .into_iter()
+            .map(|binding| {
+                ctx.memory_management.get_resource(
+                    binding.memory,
+                    binding.offset_start,
+                    binding.offset_end,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(level) = profile_level {
+            ctx.sync();
+            let start = std::time::SystemTime::now();
+            ctx.execute_task(kernel_id, count, dynamic_shared_memory, resources);
+            ctx.sync();
+
+            let (name, kernel_id) = profile_info.unwrap();
+            let info = match level {
+                ProfileLevel::Basic | ProfileLevel::Medium => kernel_id.name(),
+                ProfileLevel::Full => {
+                    format!("{name}: {kernel_id} CubeCount {count:?}")
+                }
+            };
+
+            self.logger
+                .register_profiled(info, start.elapsed().unwrap());
+        } else {
+            ctx.execute_task(kernel_id, count, dynamic_shared_memory, resources);
+        }
+    }
+
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let kernel_id = kernel.id();
+        // OpenCL keeps the kernels alive until the commands using them are completed.
+        self.get_context()
+            .kernels
+            .retain(|id, _| !id.is_variant_of(&kernel_id));
+    }
+
+    fn flush(&mut self) {
+        self.ctx.queue.flush().unwrap();
+    }
+
+    fn sync(&mut self) -> impl Future<Output = ()> + 'static {
+        self.logger.profile_summary();
+
+        let ctx = self.get_context();
+        ctx.sync();
+        async move {}
+    }
+
+    fn sync_elapsed(&mut self) -> impl Future<Output = TimestampsResult> + 'static {
+        self.logger.profile_summary();
+
+        let ctx = self.get_context();
+        ctx.sync();
+
+        let duration = match &mut ctx.timestamps {
+            KernelTimestamps::Inferred { start_time } => {
+                let duration = start_time.elapsed();
+                *start_time = Instant::now();
+                Ok(duration)
+            }
+            KernelTimestamps::Disabled => Err(TimestampsError::Disabled),
+        };
+
+        async move { duration }
+    }
+
+    fn get_resource(&mut self, binding: server::Binding) -> BindingResource<Self> {
+        let ctx = self.get_context();
+        BindingResource::new(
+            binding.clone(),
+            ctx.memory_management.get_resource(
+                binding.memory,
+                binding.offset_start,
+                binding.offset_end,
+            ),
+        )
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.ctx.memory_usage()
+    }
+
+    fn enable_timestamps(&mut self) {
+        self.ctx.timestamps.enable();
+    }
+
+    fn disable_timestamps(&mut self) {
+        if self.logger.profile_level().is_none() {
+            self.ctx.timestamps.disable();
+        }
+    }
+}
+
+impl OpenClContext {
+    pub fn new(
+        memory_management: MemoryManagement<OpenClStorage>,
+        context: Arc<Context>,
+        queue: CommandQueue,
+    ) -> Self {
+        Self {
+            context,
+            queue,
+            memory_management,
+            kernels: HashMap::new(),
+            timestamps: KernelTimestamps::Disabled,
+        }
+    }
+
+    fn sync(&mut self) {
+        self.queue.finish().unwrap();
+    }
+
+    fn compile_kernel(
+        &mut self,
+        kernel_id: &KernelId,
+        kernel: Box<dyn CubeTask<OpenClCompiler>>,
+        logger: &mut DebugLogger,
+        mode: ExecutionMode,
+    ) {
+        let mut kernel_compiled = kernel.compile(&CompilationOptions::default(), mode);
+
+        if logger.is_activated() {
+            kernel_compiled.debug_info = Some(DebugInformation::new(
+                "cl",
+                kernel_id.clone(),
+                kernel.cost(),
+            ));
+
+            if let Ok(formatted) = format_cpp(&kernel_compiled.source) {
+                kernel_compiled.source = formatted;
+            }
+        }
+
+        let cube_dim = kernel_compiled.cube_dim;
+        let kernel_compiled = logger.debug(kernel_compiled);
+
+        let source = &kernel_compiled.source;
+        let program = Program::create_and_build_from_source(&self.context, source, "")
+            .unwrap_or_else(|log| panic!("[Compilation Error] {log}\n[Source]  \n{source}"));
+        let kernel = Kernel::create(&program, KERNEL_NAME).unwrap();
+
+        self.kernels
+            .insert(kernel_id.clone(), CompiledKernel { cube_dim, kernel });
+    }
+
+    fn execute_task(
+        &mut self,
+        kernel_id: KernelId,
+        dispatch_count: (u32, u32, u32),
+        dynamic_shared_memory: u32,
+        resources: Vec<OpenClResource>,
+    ) {
+        let kernel = self.kernels.get(&kernel_id).unwrap();
+        let cube_dim = kernel.cube_dim;
+        let mut execution = ExecuteKernel::new(&kernel.kernel);
+
+        unsafe {
+            for resource in resources.iter() {
+                execution.set_arg(&resource.buffer.get());
+            }
+            // The remaining parameters are the dynamic shared memories, which are sized at launch.
+            for _ in resources.len()..execution.num_args as usize {
+                execution.set_arg_local_buffer(Ord::max(dynamic_shared_memory, 1) as usize);
+            }
+
+            execution
+                .set_global_work_sizes(&[
+                    (dispatch_count.0 * cube_dim.x) as usize,
+                    (dispatch_count.1 * cube_dim.y) as usize,
+                    (dispatch_count.2 * cube_dim.z) as usize,
+                ])
+                .set_local_work_sizes(&[
+                    cube_dim.x as usize,
+                    cube_dim.y as usize,
+                    cube_dim.z as usize,
+                ])
+                .enqueue_nd_range(&self.queue)
+                .unwrap();
+        };
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+}
+
+impl OpenClServer {
+    /// Create a new OpenCL server.
+    pub(crate) fn new(mut ctx: OpenClContext) -> Self {
+        let logger = DebugLogger::default();
+        if logger.profile_level().is_some() {
+            ctx.timestamps.enable();
+        }
+        Self { ctx, logger }
+    }
+
+    fn get_context(&mut self) -> &mut OpenClContext {
+        self.get_context_with_logger().0
+    }
+
+    fn get_context_with_logger(&mut self) -> (&mut OpenClContext, &mut DebugLogger) {
+        (&mut self.ctx, &mut self.logger)
+    }
+}
@@ -0,0 +1,95 @@
+use cubecl_runtime::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use opencl3::{
+    context::Context,
+    memory::{Buffer, CL_MEM_READ_WRITE},
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// Buffer storage for OpenCL.
+pub struct OpenClStorage {
+    context: Arc<Context>,
+    memory: HashMap<StorageId, Buffer<u8>>,
+}
+
+// OpenCL objects are thread safe, they are only raw pointers to reference counted handles.
+unsafe impl Send for OpenClStorage {}
+
+impl core::fmt::Debug for OpenClStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("OpenClStorage {{ context: {:?} }}", self.context.get()).as_str())
+    }
+}
+
+impl OpenClStorage {
+    /// Create a new storage on the given [context](Context).
+    pub fn new(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            memory: HashMap::new(),
+        }
+    }
+}
+
+/// The memory resource that can be allocated for OpenCL.
+#[derive(new, Debug)]
+pub struct OpenClResource {
+    /// A sub-buffer covering the bound range of the allocation.
+    pub buffer: Buffer<u8>,
+    offset: u64,
+    size: u64,
+}
+
+unsafe impl Send for OpenClResource {}
+
+impl OpenClResource {
+    /// Return the buffer size.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Return the buffer offset.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl ComputeStorage for OpenClStorage {
+    type Resource = OpenClResource;
+    // Sub-buffers must be aligned to the base address alignment of the device, which is queried
+    // when creating the client, this is only the minimum.
+    const ALIGNMENT: u64 = 32;
+
+    fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+        let buffer = self.memory.get(&handle.id).unwrap();
+
+        let offset = handle.offset();
+        let size = handle.size();
+        let buffer = unsafe {
+            buffer
+                .create_sub_buffer(CL_MEM_READ_WRITE, offset as usize, size as usize)
+                .unwrap()
+        };
+
+        OpenClResource::new(buffer, offset, size)
+    }
+
+    fn alloc(&mut self, size: u64) -> StorageHandle {
+        let id = StorageId::new();
+        let buffer = unsafe {
+            Buffer::create(
+                &self.context,
+                CL_MEM_READ_WRITE,
+                size as usize,
+                std::ptr::null_mut(),
+            )
+            .unwrap()
+        };
+        self.memory.insert(id, buffer);
+        StorageHandle::new(id, StorageUtilization { offset: 0, size })
+    }
+
+    // Releasing a buffer is deferred by OpenCL until the kernels using it are done.
+    fn dealloc(&mut self, id: StorageId) {
+        self.memory.remove(&id);
+    }
+}
@@ -0,0 +1,11 @@
+/// An OpenCL device, indexed across the devices of every platform.
+#[derive(new, Clone, PartialEq, Eq, Default, Hash)]
+pub struct OpenClDevice {
+    pub index: usize,
+}
+
+impl core::fmt::Debug for OpenClDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OpenCl({})", self.index)
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use cubecl_core::{
+    ir::{Elem, FloatKind, IntKind},
+    Feature, MemoryConfiguration, Runtime,
+};
+use cubecl_runtime::{
+    channel::MutexComputeChannel,
+    client::ComputeClient,
+    memory_management::{MemoryDeviceProperties, MemoryManagement},
+    storage::ComputeStorage,
+    ComputeRuntime, DeviceProperties, HardwareProperties,
+};
+use opencl3::{
+    command_queue::CommandQueue,
+    context::Context,
+    device::{get_all_devices, Device, CL_DEVICE_TYPE_ALL},
+};
+
+use crate::{
+    compute::{OpenClContext, OpenClServer, OpenClStorage},
+    device::OpenClDevice,
+};
+use cubecl_cpp::OpenClCompiler;
+
+/// The values that control how an OpenCL Runtime will perform its calculations.
+#[derive(Default)]
+pub struct RuntimeOptions {
+    /// Configures the memory management.
+    pub memory_config: MemoryConfiguration,
+}
+
+#[derive(Debug)]
+pub struct OpenClRuntime;
+
+type Server = OpenClServer;
+type Channel = MutexComputeChannel<Server>;
+
+static RUNTIME: ComputeRuntime<OpenClDevice, Server, Channel> = ComputeRuntime::new();
+
+fn create_client(device: &OpenClDevice, options: RuntimeOptions) -> ComputeClient<Server, Channel> {
+    let device_ids = get_all_devices(CL_DEVICE_TYPE_ALL).unwrap();
+    let device_id = *device_ids.get(device.index).unwrap_or_else(|| {
+        panic!(
+            "No OpenCL device at index {}, only {} devices were found",
+            device.index,
+            device_ids.len()
+        )
+    });
+    let device_cl = Device::new(device_id);
+    log::info!(
+        "Created OpenCL compute server on device {:?} => {}",
+        device,
+        device_cl.name().unwrap_or_default()
+    );
+
+    let context = Arc::new(Context::from_device(&device_cl).unwrap());
+    let queue = CommandQueue::create_default_with_properties(&context, 0, 0).unwrap();
+
+    let max_buffer_size = device_cl.max_mem_alloc_size().unwrap();
+    let max_work_item_sizes = device_cl.max_work_item_sizes().unwrap();
+    let hardware_props = HardwareProperties {
+        max_shared_memory_size: device_cl.local_mem_size().unwrap() as usize,
+        max_cube_dim: (
+            max_work_item_sizes[0] as u32,
+            max_work_item_sizes[1] as u32,
+            max_work_item_sizes[2] as u32,
+        ),
+        max_units_per_cube: device_cl.max_work_group_size().unwrap() as u32,
+        // The global position of a unit, the cube count times the cube dim, is read as a `uint` by
+        // the kernels, so it must fit in 32 bits with the largest cube dim.
+        max_cube_count: (
+            u32::MAX / max_work_item_sizes[0] as u32,
+            u32::MAX / max_work_item_sizes[1] as u32,
+            u32::MAX / max_work_item_sizes[2] as u32,
+        ),
+        // Kernel parameters are limited in size, and each binding is passed as a buffer object.
+        max_bindings: (device_cl.max_parameter_size().unwrap()
+            / core::mem::size_of::<opencl3::types::cl_mem>()) as u32,
+        max_buffer_size,
+        subcube_size: None,
+        num_multiprocessors: Some(device_cl.max_compute_units().unwrap()),
+        max_units_per_multiprocessor: None,
+        max_cubes_per_multiprocessor: None,
+        registers_per_multiprocessor: None,
+    };
+    let storage = OpenClStorage::new(context.clone());
+    // Sub-buffers have to start at a multiple of the base address alignment, given in bits.
+    let alignment =
+        (device_cl.mem_base_addr_align().unwrap() as u64 / 8).max(OpenClStorage::ALIGNMENT);
+    let mem_properties = MemoryDeviceProperties {
+        max_page_size: max_buffer_size,
+        alignment,
+    };
+
+    let memory_management = MemoryManagement::from_configuration(
+        storage,
+        mem_properties.clone(),
+        options.memory_config,
+    );
+    let ctx = OpenClContext::new(memory_management, context, queue);
+    let server = OpenClServer::new(ctx);
+    let mut device_props = DeviceProperties::new(&[], mem_properties, hardware_props);
+    register_supported_types(&mut device_props);
+
+    ComputeClient::new(MutexComputeChannel::new(server), device_props)
+}
+
+impl Runtime for OpenClRuntime {
+    type Compiler = OpenClCompiler;
+    type Server = OpenClServer;
+
+    type Channel = MutexComputeChannel<OpenClServer>;
+    type Device = OpenClDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        RUNTIME.client(device, move || {
+            create_client(device, RuntimeOptions::default())
+        })
+    }
+
+    fn name() -> &'static str {
+        "opencl"
+    }
+
+    fn require_array_lengths() -> bool {
+        true
+    }
+
+    fn supported_line_sizes() -> &'static [u8] {
+        &[4, 2]
+    }
+}
+
+/// Half precision requires the `cl_khr_fp16` extension and booleans can't be stored in buffers,
+/// so only 32-bit types are supported.
+fn register_supported_types(props: &mut DeviceProperties<Feature>) {
+    let supported_types = [
+        Elem::UInt,
+        Elem::Int(IntKind::I32),
+        Elem::AtomicInt(IntKind::I32),
+        Elem::AtomicUInt,
+        Elem::Float(FloatKind::F32),
+    ];
+
+    for ty in supported_types {
+        props.register_feature(Feature::Type(ty));
+    }
+    // Lines of any size are stored in structs, so every matrix size is supported.
+    for size in [2, 3, 4] {
+        props.register_feature(Feature::Matrix(size));
+    }
+}
@@ -75,6 +75,14 @@ impl Optimizer {
                 let loop_break = self.loop_break.back().expect("Can't break outside loop");
                 self.program.add_edge(current_block, *loop_break, ());
             }
+            Branch::Continue => {
+                let current_block = self.current_block.take().unwrap();
+                let loop_continue = self
+                    .loop_continue
+                    .back()
+                    .expect("Can't continue outside loop");
+                self.program.add_edge(current_block, *loop_continue, ());
+            }
         }
     }
 
@@ -230,16 +238,19 @@ impl Optimizer {
 
         self.program.add_edge(header, body, ());
 
-        self.loop_break.push_back(next);
-
-        self.current_block = Some(body);
-        self.parse_scope(loop_.scope);
         let continue_target = self.program.add_node(BasicBlock::default());
         self.program[continue_target]
             .block_use
             .push(BlockUse::ContinueTarget);
 
+        self.loop_break.push_back(next);
+        self.loop_continue.push_back(continue_target);
+
+        self.current_block = Some(body);
+        self.parse_scope(loop_.scope);
+
         self.loop_break.pop_back();
+        self.loop_continue.pop_back();
 
         if let Some(current_block) = self.current_block {
             self.program.add_edge(current_block, continue_target, ());
@@ -286,25 +297,21 @@ impl Optimizer {
         self.program.add_edge(header, body, ());
         self.program.add_edge(header, next, ());
 
+        // The continue target increments the counter, so `continue` can't skip it.
+        let continue_target = self.program.add_node(BasicBlock::default());
+
         self.loop_break.push_back(next);
+        self.loop_continue.push_back(continue_target);
 
         self.current_block = Some(body);
         self.parse_scope(range_loop.scope);
 
         self.loop_break.pop_back();
+        self.loop_continue.pop_back();
 
-        let current_block = self.current_block.expect("For loop has no loopback path");
-
-        let continue_target = if self.program[current_block]
-            .block_use
-            .contains(&BlockUse::Merge)
-        {
-            let target = self.program.add_node(BasicBlock::default());
-            self.program.add_edge(current_block, target, ());
-            target
-        } else {
-            current_block
-        };
+        if let Some(current_block) = self.current_block {
+            self.program.add_edge(current_block, continue_target, ());
+        }
 
         self.program.add_edge(continue_target, header, ());
 
@@ -339,7 +346,7 @@ impl Optimizer {
                 merge: next,
             };
         }
-        self.program[current_block].ops.borrow_mut().push(
+        self.program[continue_target].ops.borrow_mut().push(
             Operator::Add(BinaryOperator {
                 lhs: i,
                 rhs: step,
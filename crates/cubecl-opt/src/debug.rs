@@ -301,6 +301,10 @@ impl Display for Instruction {
             OpId::Floor => write!(f, "{}.floor()", args[0]),
             OpId::Ceil => write!(f, "{}.ceil()", args[0]),
             OpId::Erf => write!(f, "{}.erf()", args[0]),
+            OpId::Erfc => write!(f, "{}.erfc()", args[0]),
+            OpId::Expm1 => write!(f, "{}.expm1()", args[0]),
+            OpId::Tgamma => write!(f, "{}.tgamma()", args[0]),
+            OpId::Lgamma => write!(f, "{}.lgamma()", args[0]),
             OpId::Recip => write!(f, "1.0 / {}", args[0]),
             OpId::Equal => write!(f, "{} == {}", args[0], args[1]),
             OpId::NotEqual => write!(f, "{} != {}", args[0], args[1]),
@@ -326,17 +330,36 @@ impl Display for Instruction {
             OpId::Neg => write!(f, "-{}", args[0]),
             OpId::Max => write!(f, "max({}, {})", args[0], args[1]),
             OpId::Min => write!(f, "min({}, {})", args[0], args[1]),
+            OpId::SaturatingAdd => write!(f, "saturating_add({}, {})", args[0], args[1]),
+            OpId::SaturatingSub => write!(f, "saturating_sub({}, {})", args[0], args[1]),
+            OpId::WrappingAdd => write!(f, "wrapping_add({}, {})", args[0], args[1]),
+            OpId::WrappingSub => write!(f, "wrapping_sub({}, {})", args[0], args[1]),
+            OpId::WrappingMul => write!(f, "wrapping_mul({}, {})", args[0], args[1]),
             OpId::BitwiseAnd => write!(f, "{} & {}", args[0], args[1]),
             OpId::BitwiseOr => write!(f, "{} | {}", args[0], args[1]),
             OpId::BitwiseXor => write!(f, "{} ^ {}", args[0], args[1]),
             OpId::ShiftLeft => write!(f, "{} << {}", args[0], args[1]),
             OpId::ShiftRight => write!(f, "{} >> {}", args[0], args[1]),
+            OpId::RotateLeft => write!(f, "{}.rotate_left({})", args[0], args[1]),
+            OpId::RotateRight => write!(f, "{}.rotate_right({})", args[0], args[1]),
+            OpId::BitfieldExtract => {
+                write!(f, "extract_bits({}, {}, {})", args[0], args[1], args[2])
+            }
+            OpId::BitfieldInsert => write!(
+                f,
+                "insert_bits({}, {}, {}, {})",
+                args[0], args[1], args[2], args[3]
+            ),
             OpId::Remainder => write!(f, "{} % {}", args[0], args[1]),
             OpId::Magnitude => write!(f, "{}.length()", args[0]),
             OpId::Normalize => write!(f, "{}.normalize()", args[0]),
             OpId::Dot => write!(f, "dot({}, {})", args[0], args[1]),
             OpId::Select => write!(f, "select({}, {}, {})", args[0], args[1], args[2]),
             OpId::Bitcast => write!(f, "bitcast<{}>({})", self.item, args[0]),
+            OpId::PackF16 => write!(f, "pack_f16({})", args[0]),
+            OpId::UnpackF16 => write!(f, "unpack_f16({})", args[0]),
+            OpId::PackBf16 => write!(f, "pack_bf16({})", args[0]),
+            OpId::UnpackBf16 => write!(f, "unpack_bf16({})", args[0]),
             OpId::Length => write!(f, "{}.len()", args[0]),
             OpId::Shape => write!(f, "{}.shape[{}]", args[0], args[1]),
             OpId::Stride => write!(f, "{}.stride[{}]", args[0], args[1]),
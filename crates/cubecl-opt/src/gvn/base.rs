@@ -222,6 +222,10 @@ pub enum OpId {
     Floor,
     Ceil,
     Erf,
+    Erfc,
+    Expm1,
+    Tgamma,
+    Lgamma,
     Recip,
     Equal,
     NotEqual,
@@ -239,17 +243,30 @@ pub enum OpId {
     Neg,
     Max,
     Min,
+    SaturatingAdd,
+    SaturatingSub,
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
     BitwiseAnd,
     BitwiseOr,
     BitwiseXor,
     ShiftLeft,
     ShiftRight,
+    RotateLeft,
+    RotateRight,
+    BitfieldExtract,
+    BitfieldInsert,
     Remainder,
     Magnitude,
     Normalize,
     Dot,
     Select,
     Bitcast,
+    PackF16,
+    UnpackF16,
+    PackBf16,
+    UnpackBf16,
     Length,
     Shape,
     Stride,
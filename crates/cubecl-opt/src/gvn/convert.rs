@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use cubecl_core::ir::{
-    BinaryOperator, Branch, ClampOperator, ConstantScalarValue, FmaOperator, LineInitOperator,
-    Metadata, Operation, Operator, Select, UnaryOperator, Variable,
+    BinaryOperator, BitfieldExtractOperator, BitfieldInsertOperator, Branch, ClampOperator,
+    ConstantScalarValue, FmaOperator, LineInitOperator, Metadata, Operation, Operator, Select,
+    UnaryOperator, Variable,
 };
 use float_ord::FloatOrd;
 use smallvec::SmallVec;
@@ -127,6 +128,26 @@ impl Expression {
                         out,
                     })
                     .into(),
+                    OpId::Erfc => Operator::Erfc(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::Expm1 => Operator::Expm1(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::Tgamma => Operator::Tgamma(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::Lgamma => Operator::Lgamma(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
                     OpId::Recip => Operator::Recip(UnaryOperator {
                         input: args[0],
                         out,
@@ -226,6 +247,36 @@ impl Expression {
                         out,
                     })
                     .into(),
+                    OpId::SaturatingAdd => Operator::SaturatingAdd(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::SaturatingSub => Operator::SaturatingSub(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::WrappingAdd => Operator::WrappingAdd(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::WrappingSub => Operator::WrappingSub(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::WrappingMul => Operator::WrappingMul(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
                     OpId::BitwiseAnd => Operator::BitwiseAnd(BinaryOperator {
                         lhs: args[0],
                         rhs: args[1],
@@ -256,6 +307,33 @@ impl Expression {
                         out,
                     })
                     .into(),
+                    OpId::RotateLeft => Operator::RotateLeft(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::RotateRight => Operator::RotateRight(BinaryOperator {
+                        lhs: args[0],
+                        rhs: args[1],
+                        out,
+                    })
+                    .into(),
+                    OpId::BitfieldExtract => Operator::BitfieldExtract(BitfieldExtractOperator {
+                        input: args[0],
+                        offset: args[1],
+                        count: args[2],
+                        out,
+                    })
+                    .into(),
+                    OpId::BitfieldInsert => Operator::BitfieldInsert(BitfieldInsertOperator {
+                        base: args[0],
+                        insert: args[1],
+                        offset: args[2],
+                        count: args[3],
+                        out,
+                    })
+                    .into(),
                     OpId::Remainder => Operator::Remainder(BinaryOperator {
                         lhs: args[0],
                         rhs: args[1],
@@ -290,6 +368,26 @@ impl Expression {
                         out,
                     })
                     .into(),
+                    OpId::PackF16 => Operator::PackF16(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::UnpackF16 => Operator::UnpackF16(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::PackBf16 => Operator::PackBf16(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
+                    OpId::UnpackBf16 => Operator::UnpackBf16(UnaryOperator {
+                        input: args[0],
+                        out,
+                    })
+                    .into(),
                     OpId::Length => Metadata::Length { var: args[0], out }.into(),
                     OpId::Shape => Metadata::Shape {
                         var: args[0],
@@ -471,6 +569,10 @@ pub fn id_of_op(op: &Operator) -> OpId {
         Operator::Floor(_) => OpId::Floor,
         Operator::Ceil(_) => OpId::Ceil,
         Operator::Erf(_) => OpId::Erf,
+        Operator::Erfc(_) => OpId::Erfc,
+        Operator::Expm1(_) => OpId::Expm1,
+        Operator::Tgamma(_) => OpId::Tgamma,
+        Operator::Lgamma(_) => OpId::Lgamma,
         Operator::Recip(_) => OpId::Recip,
         Operator::Equal(_) => OpId::Equal,
         Operator::NotEqual(_) => OpId::NotEqual,
@@ -489,16 +591,29 @@ pub fn id_of_op(op: &Operator) -> OpId {
         Operator::Neg(_) => OpId::Neg,
         Operator::Max(_) => OpId::Max,
         Operator::Min(_) => OpId::Min,
+        Operator::SaturatingAdd(_) => OpId::SaturatingAdd,
+        Operator::SaturatingSub(_) => OpId::SaturatingSub,
+        Operator::WrappingAdd(_) => OpId::WrappingAdd,
+        Operator::WrappingSub(_) => OpId::WrappingSub,
+        Operator::WrappingMul(_) => OpId::WrappingMul,
         Operator::BitwiseAnd(_) => OpId::BitwiseAnd,
         Operator::BitwiseOr(_) => OpId::BitwiseOr,
         Operator::BitwiseXor(_) => OpId::BitwiseXor,
         Operator::ShiftLeft(_) => OpId::ShiftLeft,
         Operator::ShiftRight(_) => OpId::ShiftRight,
+        Operator::RotateLeft(_) => OpId::RotateLeft,
+        Operator::RotateRight(_) => OpId::RotateRight,
+        Operator::BitfieldExtract(_) => OpId::BitfieldExtract,
+        Operator::BitfieldInsert(_) => OpId::BitfieldInsert,
         Operator::Remainder(_) => OpId::Remainder,
         Operator::Magnitude(_) => OpId::Magnitude,
         Operator::Normalize(_) => OpId::Normalize,
         Operator::Dot(_) => OpId::Dot,
         Operator::Bitcast(_) => OpId::Bitcast,
+        Operator::PackF16(_) => OpId::PackF16,
+        Operator::UnpackF16(_) => OpId::UnpackF16,
+        Operator::PackBf16(_) => OpId::PackBf16,
+        Operator::UnpackBf16(_) => OpId::UnpackBf16,
         _ => unreachable!(),
     }
 }
@@ -130,9 +130,12 @@ impl ValueTable {
                 let expr = Instruction::new(OpId::Select, &[cond, then, or_else], item);
                 Ok((expr.into(), value_of_var(&op.out)))
             }
-            Operation::Branch(_) | Operation::Synchronization(_) | Operation::CoopMma(_) => {
-                Err(None)
-            }
+            // Calls are opaque, so their output is never numbered
+            Operation::Call(call) => Err(call.out.as_ref().and_then(value_of_var)),
+            Operation::Branch(_)
+            | Operation::Synchronization(_)
+            | Operation::CoopMma(_)
+            | Operation::Location(_) => Err(None),
         }
     }
 
@@ -166,6 +169,9 @@ impl ValueTable {
             | Operator::BitwiseXor(op)
             | Operator::Max(op)
             | Operator::Min(op)
+            | Operator::SaturatingAdd(op)
+            | Operator::WrappingAdd(op)
+            | Operator::WrappingMul(op)
             | Operator::Dot(op) => {
                 let item = op.out.item();
                 let mut lhs = self.lookup_or_add_var(&op.lhs)?;
@@ -186,7 +192,11 @@ impl ValueTable {
             | Operator::Modulo(op)
             | Operator::Remainder(op)
             | Operator::ShiftLeft(op)
-            | Operator::ShiftRight(op) => {
+            | Operator::ShiftRight(op)
+            | Operator::RotateLeft(op)
+            | Operator::RotateRight(op)
+            | Operator::SaturatingSub(op)
+            | Operator::WrappingSub(op) => {
                 let item = op.out.item();
                 let lhs = self.lookup_or_add_var(&op.lhs)?;
                 let rhs = self.lookup_or_add_var(&op.rhs)?;
@@ -227,6 +237,10 @@ impl ValueTable {
             | Operator::Floor(op)
             | Operator::Ceil(op)
             | Operator::Erf(op)
+            | Operator::Erfc(op)
+            | Operator::Expm1(op)
+            | Operator::Tgamma(op)
+            | Operator::Lgamma(op)
             | Operator::Recip(op)
             | Operator::Not(op)
             | Operator::Neg(op)
@@ -239,7 +253,11 @@ impl ValueTable {
                 let expr = Instruction::new(op, &[input], item);
                 (expr.into(), out)
             }
-            Operator::Bitcast(op) => {
+            Operator::Bitcast(op)
+            | Operator::PackF16(op)
+            | Operator::UnpackF16(op)
+            | Operator::PackBf16(op)
+            | Operator::UnpackBf16(op) => {
                 let item = op.out.item();
                 let input = self.lookup_or_add_var(&op.input)?;
                 let out = value_of_var(&op.out);
@@ -271,6 +289,27 @@ impl ValueTable {
                 let expr = Instruction::new(op, &[val, min, max], item);
                 (expr.into(), out)
             }
+            Operator::BitfieldExtract(op) => {
+                let item = op.out.item();
+                let input = self.lookup_or_add_var(&op.input)?;
+                let offset = self.lookup_or_add_var(&op.offset)?;
+                let count = self.lookup_or_add_var(&op.count)?;
+                let out = value_of_var(&op.out);
+                let op = id_of_op(operator);
+                let expr = Instruction::new(op, &[input, offset, count], item);
+                (expr.into(), out)
+            }
+            Operator::BitfieldInsert(op) => {
+                let item = op.out.item();
+                let base = self.lookup_or_add_var(&op.base)?;
+                let insert = self.lookup_or_add_var(&op.insert)?;
+                let offset = self.lookup_or_add_var(&op.offset)?;
+                let count = self.lookup_or_add_var(&op.count)?;
+                let out = value_of_var(&op.out);
+                let op = id_of_op(operator);
+                let expr = Instruction::new(op, &[base, insert, offset, count], item);
+                (expr.into(), out)
+            }
             Operator::InitLine(op) => {
                 let item = op.out.item();
                 let operands = op.inputs.iter().map(|it| self.lookup_or_add_var(it));
@@ -294,6 +333,10 @@ impl ValueTable {
                 (expr.into(), out)
             }
 
+            Operator::Swizzle(op) => Err(value_of_var(&op.out))?,
+            Operator::MatrixMul(op) => Err(value_of_var(&op.out))?,
+            Operator::Transpose(op) | Operator::Determinant(op) => Err(value_of_var(&op.out))?,
+
             Operator::AtomicSwap(op)
             | Operator::AtomicAdd(op)
             | Operator::AtomicSub(op)
@@ -307,8 +350,10 @@ impl ValueTable {
             Operator::AtomicStore(_) => Err(None)?,
             Operator::IndexAssign(_)
             | Operator::UncheckedIndexAssign(_)
+            | Operator::SwizzleAssign(_)
             | Operator::Slice(_)
             | Operator::CopyBulk(_)
+            | Operator::CopyBulkAsync(_)
             | Operator::Copy(_) => Err(None)?,
         };
         Ok((expr, val))
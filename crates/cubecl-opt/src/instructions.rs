@@ -17,10 +17,18 @@ impl Optimizer {
         match op {
             Operation::Operator(operator) => self.visit_operator(operator, visit_read, visit_write),
             Operation::Metadata(meta) => self.visit_meta(meta, visit_read, visit_write),
-            // Sync has no outputs
-            Operation::Synchronization(_) => {}
+            // Sync and locations have no outputs
+            Operation::Synchronization(_) | Operation::Location(_) => {}
             Operation::Subcube(subcube) => self.visit_subcube(subcube, visit_read, visit_write),
             Operation::CoopMma(coop_mma) => self.visit_cmma(coop_mma, visit_read, visit_write),
+            Operation::Call(call) => {
+                for arg in call.args.iter_mut() {
+                    visit_read(self, arg);
+                }
+                if let Some(out) = &mut call.out {
+                    visit_write(self, out);
+                }
+            }
             // Procedures get compiled out before visiting
             Operation::Branch(Branch::Select(select)) => {
                 visit_read(self, &mut select.cond);
@@ -66,13 +74,21 @@ impl Optimizer {
             | Operator::Or(binary_operator)
             | Operator::Max(binary_operator)
             | Operator::Min(binary_operator)
+            | Operator::SaturatingAdd(binary_operator)
+            | Operator::SaturatingSub(binary_operator)
+            | Operator::WrappingAdd(binary_operator)
+            | Operator::WrappingSub(binary_operator)
+            | Operator::WrappingMul(binary_operator)
             | Operator::BitwiseAnd(binary_operator)
             | Operator::BitwiseOr(binary_operator)
             | Operator::BitwiseXor(binary_operator)
             | Operator::ShiftLeft(binary_operator)
             | Operator::ShiftRight(binary_operator)
+            | Operator::RotateLeft(binary_operator)
+            | Operator::RotateRight(binary_operator)
             | Operator::Remainder(binary_operator)
             | Operator::Dot(binary_operator)
+            | Operator::MatrixMul(binary_operator)
             | Operator::AtomicAdd(binary_operator)
             | Operator::AtomicSub(binary_operator)
             | Operator::AtomicMax(binary_operator)
@@ -97,15 +113,25 @@ impl Optimizer {
             | Operator::Floor(unary_operator)
             | Operator::Ceil(unary_operator)
             | Operator::Erf(unary_operator)
+            | Operator::Erfc(unary_operator)
+            | Operator::Expm1(unary_operator)
+            | Operator::Tgamma(unary_operator)
+            | Operator::Lgamma(unary_operator)
             | Operator::Recip(unary_operator)
             | Operator::Assign(unary_operator)
             | Operator::Not(unary_operator)
             | Operator::Neg(unary_operator)
             | Operator::Bitcast(unary_operator)
+            | Operator::PackF16(unary_operator)
+            | Operator::UnpackF16(unary_operator)
+            | Operator::PackBf16(unary_operator)
+            | Operator::UnpackBf16(unary_operator)
             | Operator::Magnitude(unary_operator)
             | Operator::AtomicLoad(unary_operator)
             | Operator::AtomicStore(unary_operator)
-            | Operator::Normalize(unary_operator) => {
+            | Operator::Normalize(unary_operator)
+            | Operator::Transpose(unary_operator)
+            | Operator::Determinant(unary_operator) => {
                 self.visit_unop(unary_operator, visit_read, visit_write)
             }
 
@@ -115,6 +141,19 @@ impl Optimizer {
                 visit_read(self, &mut clamp_operator.max_value);
                 visit_write(self, &mut clamp_operator.out);
             }
+            Operator::BitfieldExtract(bitfield_operator) => {
+                visit_read(self, &mut bitfield_operator.input);
+                visit_read(self, &mut bitfield_operator.offset);
+                visit_read(self, &mut bitfield_operator.count);
+                visit_write(self, &mut bitfield_operator.out);
+            }
+            Operator::BitfieldInsert(bitfield_operator) => {
+                visit_read(self, &mut bitfield_operator.base);
+                visit_read(self, &mut bitfield_operator.insert);
+                visit_read(self, &mut bitfield_operator.offset);
+                visit_read(self, &mut bitfield_operator.count);
+                visit_write(self, &mut bitfield_operator.out);
+            }
             Operator::Slice(slice_operator) => {
                 visit_read(self, &mut slice_operator.start);
                 visit_read(self, &mut slice_operator.end);
@@ -127,6 +166,10 @@ impl Optimizer {
                 }
                 visit_write(self, &mut line_init_operator.out)
             }
+            Operator::Swizzle(swizzle_operator) | Operator::SwizzleAssign(swizzle_operator) => {
+                visit_read(self, &mut swizzle_operator.input);
+                visit_write(self, &mut swizzle_operator.out);
+            }
             // Atomics are always pointers
             Operator::AtomicCompareAndSwap(op) => {
                 visit_read(self, &mut op.input);
@@ -140,7 +183,8 @@ impl Optimizer {
                 visit_read(self, &mut copy_operator.out_index);
                 visit_write(self, &mut copy_operator.out);
             }
-            Operator::CopyBulk(copy_bulk_operator) => {
+            Operator::CopyBulk(copy_bulk_operator)
+            | Operator::CopyBulkAsync(copy_bulk_operator) => {
                 visit_read(self, &mut copy_bulk_operator.input);
                 visit_read(self, &mut copy_bulk_operator.in_index);
                 visit_read(self, &mut copy_bulk_operator.out_index);
@@ -139,6 +139,8 @@ pub struct Optimizer {
     current_block: Option<NodeIndex>,
     /// The current loop's break target
     loop_break: VecDeque<NodeIndex>,
+    /// The current loop's continue target
+    loop_continue: VecDeque<NodeIndex>,
     /// The single return block
     pub ret: NodeIndex,
     /// Root scope to allocate variables on
@@ -156,6 +158,7 @@ impl Default for Optimizer {
             program: Default::default(),
             current_block: Default::default(),
             loop_break: Default::default(),
+            loop_continue: Default::default(),
             ret: Default::default(),
             root_scope: Scope::root(),
             cube_dim: Default::default(),
@@ -309,10 +312,13 @@ impl Optimizer {
         for node in self.node_ids() {
             let ops = self.program[node].ops.clone();
             for op in ops.borrow().values() {
-                if let Operation::Operator(Operator::IndexAssign(binop)) = op {
-                    if let Variable::Local { id, depth, .. } = &binop.out {
-                        self.program.variables.remove(&(*id, *depth));
-                    }
+                let out = match op {
+                    Operation::Operator(Operator::IndexAssign(binop)) => &binop.out,
+                    Operation::Operator(Operator::SwizzleAssign(swizzle)) => &swizzle.out,
+                    _ => continue,
+                };
+                if let Variable::Local { id, depth, .. } = out {
+                    self.program.variables.remove(&(*id, *depth));
                 }
             }
         }
@@ -377,9 +383,12 @@ impl Optimizer {
             }
         }
 
-        let is_break = processed
-            .operations
-            .contains(&Operation::Branch(Branch::Break));
+        let is_break = processed.operations.iter().any(|op| {
+            matches!(
+                op,
+                Operation::Branch(Branch::Break) | Operation::Branch(Branch::Continue)
+            )
+        });
 
         for instruction in processed.operations {
             match instruction {
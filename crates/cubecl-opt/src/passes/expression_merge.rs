@@ -189,6 +189,7 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
         | (Operator::BitwiseXor(lhs), Operator::BitwiseXor(rhs))
         | (Operator::Div(lhs), Operator::Div(rhs))
         | (Operator::Dot(lhs), Operator::Dot(rhs))
+        | (Operator::MatrixMul(lhs), Operator::MatrixMul(rhs))
         | (Operator::Equal(lhs), Operator::Equal(rhs))
         | (Operator::Greater(lhs), Operator::Greater(rhs))
         | (Operator::GreaterEqual(lhs), Operator::GreaterEqual(rhs))
@@ -198,6 +199,11 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
         | (Operator::LowerEqual(lhs), Operator::LowerEqual(rhs))
         | (Operator::Max(lhs), Operator::Max(rhs))
         | (Operator::Min(lhs), Operator::Min(rhs))
+        | (Operator::SaturatingAdd(lhs), Operator::SaturatingAdd(rhs))
+        | (Operator::SaturatingSub(lhs), Operator::SaturatingSub(rhs))
+        | (Operator::WrappingAdd(lhs), Operator::WrappingAdd(rhs))
+        | (Operator::WrappingSub(lhs), Operator::WrappingSub(rhs))
+        | (Operator::WrappingMul(lhs), Operator::WrappingMul(rhs))
         | (Operator::Modulo(lhs), Operator::Modulo(rhs))
         | (Operator::Mul(lhs), Operator::Mul(rhs))
         | (Operator::NotEqual(lhs), Operator::NotEqual(rhs))
@@ -206,6 +212,8 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
         | (Operator::Remainder(lhs), Operator::Remainder(rhs))
         | (Operator::ShiftLeft(lhs), Operator::ShiftLeft(rhs))
         | (Operator::ShiftRight(lhs), Operator::ShiftRight(rhs))
+        | (Operator::RotateLeft(lhs), Operator::RotateLeft(rhs))
+        | (Operator::RotateRight(lhs), Operator::RotateRight(rhs))
         | (Operator::Sub(lhs), Operator::Sub(rhs))
         | (Operator::UncheckedIndex(lhs), Operator::UncheckedIndex(rhs))
         | (Operator::UncheckedIndexAssign(lhs), Operator::UncheckedIndexAssign(rhs)) => {
@@ -214,9 +222,17 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
 
         (Operator::Abs(lhs), Operator::Abs(rhs))
         | (Operator::Bitcast(lhs), Operator::Bitcast(rhs))
+        | (Operator::PackF16(lhs), Operator::PackF16(rhs))
+        | (Operator::UnpackF16(lhs), Operator::UnpackF16(rhs))
+        | (Operator::PackBf16(lhs), Operator::PackBf16(rhs))
+        | (Operator::UnpackBf16(lhs), Operator::UnpackBf16(rhs))
         | (Operator::Ceil(lhs), Operator::Ceil(rhs))
         | (Operator::Cos(lhs), Operator::Cos(rhs))
         | (Operator::Erf(lhs), Operator::Erf(rhs))
+        | (Operator::Erfc(lhs), Operator::Erfc(rhs))
+        | (Operator::Expm1(lhs), Operator::Expm1(rhs))
+        | (Operator::Tgamma(lhs), Operator::Tgamma(rhs))
+        | (Operator::Lgamma(lhs), Operator::Lgamma(rhs))
         | (Operator::Exp(lhs), Operator::Exp(rhs))
         | (Operator::Floor(lhs), Operator::Floor(rhs))
         | (Operator::Log(lhs), Operator::Log(rhs))
@@ -229,7 +245,9 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
         | (Operator::Round(lhs), Operator::Round(rhs))
         | (Operator::Sin(lhs), Operator::Sin(rhs))
         | (Operator::Sqrt(lhs), Operator::Sqrt(rhs))
-        | (Operator::Tanh(lhs), Operator::Tanh(rhs)) => lhs.input == rhs.input,
+        | (Operator::Tanh(lhs), Operator::Tanh(rhs))
+        | (Operator::Transpose(lhs), Operator::Transpose(rhs))
+        | (Operator::Determinant(lhs), Operator::Determinant(rhs)) => lhs.input == rhs.input,
 
         (Operator::Clamp(lhs), Operator::Clamp(rhs)) => {
             lhs.input == rhs.input
@@ -239,7 +257,19 @@ fn operator_rhs_eq(lhs: &Operator, rhs: &Operator) -> bool {
         (Operator::Fma(lhs), Operator::Fma(rhs)) => {
             lhs.a == rhs.a && lhs.b == rhs.b && lhs.c == rhs.c
         }
+        (Operator::BitfieldExtract(lhs), Operator::BitfieldExtract(rhs)) => {
+            lhs.input == rhs.input && lhs.offset == rhs.offset && lhs.count == rhs.count
+        }
+        (Operator::BitfieldInsert(lhs), Operator::BitfieldInsert(rhs)) => {
+            lhs.base == rhs.base
+                && lhs.insert == rhs.insert
+                && lhs.offset == rhs.offset
+                && lhs.count == rhs.count
+        }
         (Operator::InitLine(lhs), Operator::InitLine(rhs)) => lhs.inputs == rhs.inputs,
+        (Operator::Swizzle(lhs), Operator::Swizzle(rhs)) => {
+            lhs.input == rhs.input && lhs.components == rhs.components
+        }
         _ => false,
     }
 }
@@ -0,0 +1,325 @@
+//! Python bindings to launch CubeCL kernels, e.g. from notebooks to validate them or benchmark
+//! them against CuPy.
+//!
+//! The kernels are [registered](cubecl_ffi::register_kernel) in Rust by the crate building the
+//! Python module, which adds the bindings to its module with [register]:
+//!
+//! ```ignore
+//! #[pymodule]
+//! fn my_kernels(module: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     cubecl_ffi::register_kernel::<WgpuRuntime>("scale", launch_scale);
+//!     cubecl_py::register(module)
+//! }
+//! ```
+//!
+//! ```python
+//! client = my_kernels.Client("wgpu")
+//! input = client.create(np.arange(4, dtype=np.float32))
+//! output = client.empty(4)
+//! client.launch("scale", (1, 1, 1), (4, 1, 1), [input, output, my_kernels.f32(2.0)])
+//! result = client.to_numpy(output, np.float32)
+//! ```
+
+// The code generated by pyo3 for the methods returning a `PyResult` converts their errors.
+#![allow(clippy::useless_conversion)]
+
+use cubecl_ffi::{Arg, CubeclBuffer, CubeclClient, CubeclDim, CubeclRuntime, FfiError, LaunchArgs};
+use numpy::PyArray1;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// A client of a device, launching the registered kernels.
+#[pyclass(module = "cubecl", frozen)]
+pub struct Client {
+    client: CubeclClient,
+}
+
+/// A buffer of the device, holding `len` elements of `itemsize` bytes.
+#[pyclass(module = "cubecl", frozen)]
+pub struct Buffer {
+    buffer: CubeclBuffer,
+    #[pyo3(get)]
+    len: usize,
+    #[pyo3(get)]
+    itemsize: usize,
+}
+
+/// A buffer given to a kernel as an array of `len` elements, read `line_size` elements at a time.
+#[pyclass(module = "cubecl", frozen)]
+pub struct Array {
+    buffer: Py<Buffer>,
+    #[pyo3(get)]
+    len: usize,
+    #[pyo3(get)]
+    line_size: u8,
+}
+
+/// A scalar argument of a kernel, created with `u32`, `i32` or `f32`.
+#[pyclass(module = "cubecl", frozen)]
+#[derive(Clone, Copy)]
+pub struct Scalar {
+    arg: Arg<'static>,
+}
+
+impl From<CubeclClient> for Client {
+    fn from(client: CubeclClient) -> Self {
+        Self { client }
+    }
+}
+
+#[pymethods]
+impl Client {
+    /// The client of a device of the runtime, `"wgpu"` or `"cuda"`, the default device when
+    /// `device` is negative.
+    #[new]
+    #[pyo3(signature = (runtime = "wgpu", device = -1))]
+    fn new(py: Python<'_>, runtime: &str, device: i32) -> PyResult<Self> {
+        let runtime = match runtime {
+            "wgpu" => CubeclRuntime::Wgpu,
+            "cuda" => CubeclRuntime::Cuda,
+            _ => return Err(PyValueError::new_err(format!("Unknown runtime {runtime}"))),
+        };
+        let client = py
+            .allow_threads(|| CubeclClient::for_runtime(runtime, device))
+            .map_err(into_py_err)?;
+        Ok(client.into())
+    }
+
+    /// Create a buffer holding the data, any contiguous object supporting the buffer protocol
+    /// such as a numpy array or `bytes`, uploaded without an intermediate copy.
+    fn create(&self, data: &Bound<'_, PyAny>) -> PyResult<Buffer> {
+        let view = data
+            .py()
+            .import_bound("builtins")?
+            .getattr("memoryview")?
+            .call1((data,))?;
+        let itemsize = view.getattr("itemsize")?.extract::<usize>()?;
+        let bytes = PyBuffer::<u8>::get_bound(&view.call_method1("cast", ("B",))?)?;
+        if !bytes.is_c_contiguous() {
+            return Err(PyValueError::new_err("The data must be contiguous"));
+        }
+
+        // The view keeps the bytes alive and unmoved until the upload is done.
+        let data =
+            unsafe { core::slice::from_raw_parts(bytes.buf_ptr() as *const u8, bytes.len_bytes()) };
+        Ok(Buffer {
+            buffer: self.client.create(data),
+            len: data.len() / itemsize,
+            itemsize,
+        })
+    }
+
+    /// Create a buffer of `len` uninitialized elements of `itemsize` bytes.
+    #[pyo3(signature = (len, itemsize = 4))]
+    fn empty(&self, len: usize, itemsize: usize) -> Buffer {
+        Buffer {
+            buffer: self.client.empty(len * itemsize),
+            len,
+            itemsize,
+        }
+    }
+
+    /// The bytes of the buffer, once the kernels writing it are done.
+    fn read<'py>(&self, py: Python<'py>, buffer: &Buffer) -> Bound<'py, PyBytes> {
+        let bytes = py.allow_threads(|| self.client.read(&buffer.buffer));
+        PyBytes::new_bound(py, &bytes)
+    }
+
+    /// The elements of the buffer as a numpy array of the dtype, once the kernels writing it are
+    /// done. The array takes ownership of the bytes read from the device without copying them.
+    #[pyo3(signature = (buffer, dtype = None))]
+    fn to_numpy<'py>(
+        &self,
+        py: Python<'py>,
+        buffer: &Buffer,
+        dtype: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let bytes = py.allow_threads(|| self.client.read(&buffer.buffer));
+        let array = PyArray1::from_vec_bound(py, bytes).into_any();
+        match dtype {
+            Some(dtype) => array.call_method1("view", (dtype,)),
+            None => Ok(array),
+        }
+    }
+
+    /// Launch the kernel registered under the name, with `cube_count` cubes of `cube_dim`
+    /// units. The arguments are buffers, arrays and scalars.
+    fn launch(
+        &self,
+        py: Python<'_>,
+        name: &str,
+        cube_count: (u32, u32, u32),
+        cube_dim: (u32, u32, u32),
+        args: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let borrowed = args
+            .iter()
+            .enumerate()
+            .map(|(position, arg)| borrow_arg(position, arg))
+            .collect::<PyResult<Vec<_>>>()?;
+        let args = borrowed
+            .iter()
+            .map(|arg| match arg {
+                BorrowedArg::Array {
+                    buffer,
+                    len,
+                    line_size,
+                } => Arg::Array {
+                    handle: buffer.get().buffer.handle(),
+                    size: buffer.get().buffer.size(),
+                    len: *len,
+                    line_size: *line_size,
+                },
+                BorrowedArg::Scalar(arg) => *arg,
+            })
+            .collect();
+
+        let args = LaunchArgs::new(dim(cube_count), dim(cube_dim), args);
+        py.allow_threads(|| self.client.launch(name, &args))
+            .map_err(into_py_err)
+    }
+
+    /// Wait for the launched kernels to be done, e.g. before measuring their duration.
+    fn sync(&self, py: Python<'_>) {
+        py.allow_threads(|| self.client.sync())
+    }
+}
+
+#[pymethods]
+impl Buffer {
+    /// The size of the buffer in bytes.
+    #[getter]
+    fn nbytes(&self) -> usize {
+        self.len * self.itemsize
+    }
+
+    /// The buffer as an array read `line_size` elements at a time.
+    fn lines(slf: Py<Self>, py: Python<'_>, line_size: u8) -> PyResult<Array> {
+        let len = slf.get().len;
+        Array::new(py, slf, Some(len), line_size)
+    }
+}
+
+#[pymethods]
+impl Array {
+    /// The first `len` elements of the buffer, all of them by default.
+    ///
+    /// Raises a `ValueError` when the buffer holds fewer than `len` elements.
+    #[new]
+    #[pyo3(signature = (buffer, len = None, line_size = 1))]
+    fn new(
+        _py: Python<'_>,
+        buffer: Py<Buffer>,
+        len: Option<usize>,
+        line_size: u8,
+    ) -> PyResult<Self> {
+        let available = buffer.get().len;
+        let len = len.unwrap_or(available);
+        if len > available {
+            return Err(PyValueError::new_err(format!(
+                "The array has {len} elements, but its buffer only holds {available}"
+            )));
+        }
+
+        Ok(Self {
+            buffer,
+            len,
+            line_size: line_size.max(1),
+        })
+    }
+}
+
+/// A `u32` scalar argument.
+#[pyfunction]
+#[pyo3(name = "u32")]
+fn scalar_u32(value: u32) -> Scalar {
+    Scalar {
+        arg: Arg::U32(value),
+    }
+}
+
+/// An `i32` scalar argument.
+#[pyfunction]
+#[pyo3(name = "i32")]
+fn scalar_i32(value: i32) -> Scalar {
+    Scalar {
+        arg: Arg::I32(value),
+    }
+}
+
+/// An `f32` scalar argument.
+#[pyfunction]
+#[pyo3(name = "f32")]
+fn scalar_f32(value: f32) -> Scalar {
+    Scalar {
+        arg: Arg::F32(value),
+    }
+}
+
+/// Add the classes and functions of the bindings to the module.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Client>()?;
+    module.add_class::<Buffer>()?;
+    module.add_class::<Array>()?;
+    module.add_class::<Scalar>()?;
+    module.add_function(wrap_pyfunction!(scalar_u32, module)?)?;
+    module.add_function(wrap_pyfunction!(scalar_i32, module)?)?;
+    module.add_function(wrap_pyfunction!(scalar_f32, module)?)?;
+    Ok(())
+}
+
+/// The bindings without any registered kernel, to manage buffers.
+#[pymodule]
+#[pyo3(name = "cubecl")]
+fn cubecl_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    register(module)
+}
+
+/// An argument of a launch, borrowing its buffer until the launch is done.
+enum BorrowedArg<'py> {
+    Array {
+        buffer: Bound<'py, Buffer>,
+        len: usize,
+        line_size: u8,
+    },
+    Scalar(Arg<'static>),
+}
+
+fn borrow_arg<'py>(position: usize, arg: &Bound<'py, PyAny>) -> PyResult<BorrowedArg<'py>> {
+    if let Ok(buffer) = arg.downcast::<Buffer>() {
+        let len = buffer.get().len;
+        return Ok(BorrowedArg::Array {
+            buffer: buffer.clone(),
+            len,
+            line_size: 1,
+        });
+    }
+    if let Ok(array) = arg.downcast::<Array>() {
+        let array = array.get();
+        return Ok(BorrowedArg::Array {
+            buffer: array.buffer.bind(arg.py()).clone(),
+            len: array.len,
+            line_size: array.line_size,
+        });
+    }
+    if let Ok(scalar) = arg.downcast::<Scalar>() {
+        return Ok(BorrowedArg::Scalar(scalar.get().arg));
+    }
+
+    Err(PyValueError::new_err(format!(
+        "The argument at position {position} should be a Buffer, an Array or a Scalar"
+    )))
+}
+
+fn dim((x, y, z): (u32, u32, u32)) -> CubeclDim {
+    CubeclDim { x, y, z }
+}
+
+fn into_py_err(err: FfiError) -> PyErr {
+    match err {
+        FfiError::Panic(_) => PyRuntimeError::new_err(err.to_string()),
+        _ => PyValueError::new_err(err.to_string()),
+    }
+}
@@ -0,0 +1,103 @@
+use backend_template::{TemplateDevice, TemplateRuntime};
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_ffi::{register_kernel, CubeclClient};
+use cubecl_py::Client;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyModule};
+
+#[cube(launch)]
+fn scale(input: &Array<f32>, output: &mut Array<f32>, factor: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor;
+    }
+}
+
+/// Run the Python code with `cubecl`, the bindings, and `client`, a client of the template
+/// runtime.
+fn run(code: &str) -> PyResult<()> {
+    register_kernel::<TemplateRuntime>("scale", |client, args| {
+        scale::launch::<TemplateRuntime>(
+            client,
+            args.cube_count(),
+            args.cube_dim(),
+            args.array::<f32, _>(0)?,
+            args.array::<f32, _>(1)?,
+            args.f32(2)?,
+        );
+        Ok(())
+    });
+
+    Python::with_gil(|py| {
+        let module = PyModule::new_bound(py, "cubecl")?;
+        cubecl_py::register(&module)?;
+        let client = CubeclClient::new::<TemplateRuntime>(&TemplateDevice);
+
+        let globals = PyDict::new_bound(py);
+        globals.set_item("cubecl", module)?;
+        globals.set_item("client", Py::new(py, Client::from(client))?)?;
+        py.run_bound(code, Some(&globals), None)
+    })
+}
+
+#[test]
+fn buffers_are_created_and_read() {
+    run(r#"
+import array
+buffer = client.create(array.array("f", [1.0, 2.0, 3.0]))
+assert (buffer.len, buffer.itemsize, buffer.nbytes) == (3, 4, 12)
+assert array.array("f", client.read(buffer)).tolist() == [1.0, 2.0, 3.0]
+"#)
+    .unwrap();
+}
+
+#[test]
+fn registered_kernels_are_launched() {
+    run(r#"
+import array
+input = client.create(array.array("f", [1.0, 2.0]))
+output = client.empty(2)
+client.launch("scale", (1, 1, 1), (2, 1, 1), [input, cubecl.Array(output), cubecl.f32(2.0)])
+client.sync()
+"#)
+    .unwrap();
+}
+
+#[test]
+fn arrays_longer_than_their_buffer_raise_a_value_error() {
+    run(r#"
+buffer = client.empty(2)
+assert cubecl.Array(buffer, 2).len == 2
+try:
+    cubecl.Array(buffer, 3)
+    raise AssertionError("The array should be rejected")
+except ValueError as err:
+    assert str(err) == "The array has 3 elements, but its buffer only holds 2", err
+"#)
+    .unwrap();
+}
+
+#[test]
+fn unknown_kernels_raise_a_value_error() {
+    run(r#"
+try:
+    client.launch("missing", (1, 1, 1), (1, 1, 1), [])
+    raise AssertionError("The launch should fail")
+except ValueError as err:
+    assert str(err) == "No kernel named missing is registered for the template runtime", err
+"#)
+    .unwrap();
+}
+
+#[test]
+fn arguments_of_the_wrong_type_raise_a_value_error() {
+    run(r#"
+buffer = client.empty(2)
+try:
+    client.launch("scale", (1, 1, 1), (2, 1, 1), [buffer, buffer, 2.0])
+    raise AssertionError("The launch should fail")
+except ValueError as err:
+    assert str(err) == "The argument at position 2 should be a Buffer, an Array or a Scalar", err
+"#)
+    .unwrap();
+}
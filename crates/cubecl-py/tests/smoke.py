@@ -0,0 +1,31 @@
+"""Smoke test of the module built by `maturin develop`, run by the CI on the wgpu runtime."""
+
+import numpy as np
+
+import cubecl
+
+client = cubecl.Client("wgpu")
+
+values = np.arange(8, dtype=np.float32)
+buffer = client.create(values)
+assert (buffer.len, buffer.itemsize, buffer.nbytes) == (8, 4, 32)
+assert np.array_equal(client.to_numpy(buffer, np.float32), values)
+
+empty = client.empty(4, itemsize=4)
+assert empty.nbytes == 16
+
+assert buffer.lines(4).line_size == 4
+assert cubecl.Array(buffer, 4).len == 4
+try:
+    cubecl.Array(buffer, 9)
+    raise AssertionError("An array longer than its buffer should be rejected")
+except ValueError:
+    pass
+
+try:
+    client.launch("missing", (1, 1, 1), (1, 1, 1), [buffer])
+    raise AssertionError("Launching an unregistered kernel should fail")
+except ValueError:
+    pass
+
+print("cubecl-py smoke test passed")
@@ -4,6 +4,8 @@ fn main() {
     // Setup cfg aliases
     cfg_aliases! {
         autotune_persistent_cache: { all(feature = "std", any(target_os = "windows", target_os = "linux", target_os = "macos")) },
+        remote: { all(feature = "remote", any(target_os = "windows", target_os = "linux", target_os = "macos")) },
         exclusive_memory_only: { any(feature = "exclusive-memory-only", target_family = "wasm") },
+        watchdog: { all(feature = "std", not(target_family = "wasm")) },
     }
 }
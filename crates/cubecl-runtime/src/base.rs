@@ -1,11 +1,22 @@
 use crate::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
-use core::ops::DerefMut;
-use hashbrown::HashMap;
+use core::hash::BuildHasher;
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
+
+/// The number of shards of the clients, so the devices of different shards are initialized and
+/// looked up without contending on the same lock.
+const SHARDS: usize = 8;
+
+type Clients<Device, Server, Channel> =
+    spin::RwLock<Option<HashMap<Device, ComputeClient<Server, Channel>>>>;
 
 /// The compute type has the responsibility to retrieve the correct compute client based on the
 /// given device.
+///
+/// Retrieving a client is safe from any thread. The clients are already created most of the time,
+/// so they are looked up behind a read lock, and the lock is only held exclusively while a client
+/// is created, by the shard of its device.
 pub struct ComputeRuntime<Device, Server: ComputeServer, Channel> {
-    clients: spin::Mutex<Option<HashMap<Device, ComputeClient<Server, Channel>>>>,
+    shards: [Clients<Device, Server, Channel>; SHARDS],
 }
 
 /// The kind of execution to be performed.
@@ -40,34 +51,35 @@ where
     /// Create a new compute.
     pub const fn new() -> Self {
         Self {
-            clients: spin::Mutex::new(None),
+            shards: [const { spin::RwLock::new(None) }; SHARDS],
         }
     }
 
     /// Get the compute client for the given device.
     ///
-    /// Provide the init function to create a new client if it isn't already initialized.
+    /// Provide the init function to create a new client if it isn't already initialized. It is
+    /// called at most once per device, even when many threads request the client at once.
     pub fn client<Init>(&self, device: &Device, init: Init) -> ComputeClient<Server, Channel>
     where
         Init: Fn() -> ComputeClient<Server, Channel>,
     {
-        let mut clients = self.clients.lock();
+        let shard = self.shard(device);
 
-        if clients.is_none() {
-            Self::register_inner(device, init(), &mut clients);
+        if let Some(client) = shard
+            .read()
+            .as_ref()
+            .and_then(|clients| clients.get(device))
+        {
+            return client.clone();
         }
 
-        match clients.deref_mut() {
-            Some(clients) => match clients.get(device) {
-                Some(client) => client.clone(),
-                None => {
-                    let client = init();
-                    clients.insert(device.clone(), client.clone());
-                    client
-                }
-            },
-            _ => unreachable!(),
-        }
+        // Another thread may have created the client between the locks.
+        shard
+            .write()
+            .get_or_insert_with(HashMap::new)
+            .entry(device.clone())
+            .or_insert_with(init)
+            .clone()
     }
 
     /// Register the compute client for the given device.
@@ -81,26 +93,18 @@ where
     ///
     /// If a client is already registered for the given device.
     pub fn register(&self, device: &Device, client: ComputeClient<Server, Channel>) {
-        let mut clients = self.clients.lock();
+        let mut clients = self.shard(device).write();
+        let clients = clients.get_or_insert_with(HashMap::new);
 
-        Self::register_inner(device, client, &mut clients);
-    }
-
-    fn register_inner(
-        device: &Device,
-        client: ComputeClient<Server, Channel>,
-        clients: &mut Option<HashMap<Device, ComputeClient<Server, Channel>>>,
-    ) {
-        if clients.is_none() {
-            *clients = Some(HashMap::new());
+        if clients.contains_key(device) {
+            panic!("Client already created for device {:?}", device);
         }
 
-        if let Some(clients) = clients {
-            if clients.contains_key(device) {
-                panic!("Client already created for device {:?}", device);
-            }
+        clients.insert(device.clone(), client);
+    }
 
-            clients.insert(device.clone(), client);
-        }
+    fn shard(&self, device: &Device) -> &Clients<Device, Server, Channel> {
+        let hash = DefaultHashBuilder::default().hash_one(device);
+        &self.shards[hash as usize % SHARDS]
     }
 }
@@ -0,0 +1,70 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// A flag shared between a [client](crate::client::ComputeClient) and the code that may abort its
+/// work, such as a UI or a request handler.
+///
+/// Once the token is cancelled, the kernels launched by the clients holding it are dropped
+/// instead of being submitted to the server, so a long sequence of launches stops at the next
+/// one without tearing down the device. [try_execute](crate::client::ComputeClient::try_execute)
+/// returns the cancellation, and the launches dropped by
+/// [execute](crate::client::ComputeClient::execute) are counted by the token.
+///
+/// The kernels already submitted still run to completion: the token lives on the host, so a
+/// kernel that must stop early has to poll a flag of its own, written to one of its buffers.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    dropped: Arc<AtomicUsize>,
+}
+
+/// Error returned when a kernel isn't launched because the
+/// [cancellation token](CancellationToken) of the client was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl core::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The kernel launch was dropped, the client was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+impl CancellationToken {
+    /// Create a token that isn't cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the launches of the clients holding this token or any of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// The number of launches dropped since the token was cancelled.
+    pub fn dropped_launches(&self) -> usize {
+        self.dropped.load(Ordering::Acquire)
+    }
+
+    /// Allow the clients holding this token to launch kernels again, and reset the count of the
+    /// [dropped launches](Self::dropped_launches).
+    pub fn reset(&self) {
+        self.dropped.store(0, Ordering::Release);
+        self.cancelled.store(false, Ordering::Release);
+    }
+
+    /// Count a launch dropped because the token was cancelled, returning the cancellation.
+    pub(crate) fn check(&self) -> Result<(), Cancelled> {
+        if !self.is_cancelled() {
+            return Ok(());
+        }
+        self.dropped.fetch_add(1, Ordering::AcqRel);
+        Err(Cancelled)
+    }
+}
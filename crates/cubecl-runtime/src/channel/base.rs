@@ -2,7 +2,8 @@ use core::future::Future;
 use cubecl_common::benchmark::TimestampsResult;
 
 use crate::{
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    memory_management::OutOfMemoryError,
+    server::{Binding, ComputeServer, CubeCount, DeviceLost, Fence, Handle, Priority, Stream},
     storage::BindingResource,
     ExecutionMode,
 };
@@ -14,15 +15,50 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     /// Given a binding, returns owned resource as bytes
     fn read(&self, binding: Binding) -> impl Future<Output = Vec<u8>> + Send;
 
+    /// Given multiple bindings, returns the owned resources as bytes, in the same order
+    fn read_many(&self, bindings: Vec<Binding>) -> impl Future<Output = Vec<Vec<u8>>> + Send;
+
+    /// Given a binding, returns `len` bytes of the resource starting at `offset` as owned bytes
+    fn read_range(
+        &self,
+        binding: Binding,
+        offset: u64,
+        len: u64,
+    ) -> impl Future<Output = Vec<u8>> + Send;
+
+    /// Given a binding, overwrites the bytes at its start with `data`
+    fn write(&self, binding: Binding, data: &[u8]);
+
+    /// Given a binding, sets all its bytes to zero
+    fn clear(&self, binding: Binding);
+
+    /// Given a binding, repeats `pattern` over its bytes
+    fn fill(&self, binding: Binding, pattern: &[u8]);
+
     /// Given a resource handle, return the storage resource.
     fn get_resource(&self, binding: Binding) -> BindingResource<Server>;
 
     /// Given a resource as bytes, stores it and returns the resource handle
     fn create(&self, data: &[u8]) -> Handle;
 
+    /// Given a resource as bytes, starts storing it asynchronously and returns the resource handle
+    /// with the fence signaled once the copy is completed
+    fn create_async(&self, data: &[u8]) -> (Handle, Fence);
+
+    /// Wait for the completion of the operation signaling the `fence`
+    fn wait_fence(&self, fence: Fence) -> impl Future<Output = ()> + Send;
+
     /// Reserves `size` bytes in the storage, and returns a handle over them
     fn empty(&self, size: usize) -> Handle;
 
+    /// Reserves `size` bytes in the storage, and returns a handle over them or the error when the
+    /// device is out of memory
+    fn try_empty(&self, size: usize) -> Result<Handle, OutOfMemoryError>;
+
+    /// Grows the resource of the handle to `size` bytes, or returns `None` when the server can't
+    /// grow resources
+    fn grow(&self, handle: Handle, size: usize) -> Option<Handle>;
+
     /// Executes the `kernel` over the given `bindings`.
     ///
     /// # Safety
@@ -34,8 +70,30 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
         count: CubeCount,
         bindings: Vec<Binding>,
         mode: ExecutionMode,
+        stream: Stream,
     );
 
+    /// Remove the compiled `kernel` from the cache of the server.
+    fn evict(&self, kernel: Server::Kernel);
+
+    /// Compile the kernels ahead of their execution.
+    fn prepare(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>);
+
+    /// Create a new stream to execute kernels on.
+    fn create_stream(&self) -> Stream;
+
+    /// Create a new stream on the server, whose kernels are scheduled with the `priority`.
+    fn create_stream_with_priority(&self, priority: Priority) -> Stream {
+        let _ = priority;
+        self.create_stream()
+    }
+
+    /// Record an event on the `stream`, returning a fence signaled once its kernels are completed.
+    fn record_event(&self, stream: Stream) -> Fence;
+
+    /// Make the kernels executed next on the `stream` wait on the `fence`.
+    fn wait_event(&self, stream: Stream, fence: Fence);
+
     /// Flush outstanding work of the server.
     fn flush(&self);
 
@@ -55,4 +113,10 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
 
     /// Disable collecting timestamps.
     fn disable_timestamps(&self);
+
+    /// The error of the device when it was lost.
+    fn device_lost(&self) -> Option<DeviceLost>;
+
+    /// Request a new device to replace a lost one.
+    fn recover(&self) -> Result<(), DeviceLost>;
 }
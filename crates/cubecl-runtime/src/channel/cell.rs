@@ -1,5 +1,8 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, CubeCount, Handle};
+use crate::memory_management::OutOfMemoryError;
+use crate::server::{
+    Binding, ComputeServer, CubeCount, DeviceLost, Fence, Handle, Priority, Stream,
+};
 use crate::storage::BindingResource;
 use crate::ExecutionMode;
 use alloc::sync::Arc;
@@ -52,6 +55,34 @@ where
         future.await
     }
 
+    async fn read_many(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
+        let future = {
+            let mut server = self.server.borrow_mut();
+            server.read_many(bindings)
+        };
+        future.await
+    }
+
+    async fn read_range(&self, binding: Binding, offset: u64, len: u64) -> Vec<u8> {
+        let future = {
+            let mut server = self.server.borrow_mut();
+            server.read_range(binding, offset, len)
+        };
+        future.await
+    }
+
+    fn write(&self, binding: Binding, data: &[u8]) {
+        self.server.borrow_mut().write(binding, data)
+    }
+
+    fn clear(&self, binding: Binding) {
+        self.server.borrow_mut().clear(binding)
+    }
+
+    fn fill(&self, binding: Binding, pattern: &[u8]) {
+        self.server.borrow_mut().fill(binding, pattern)
+    }
+
     fn get_resource(&self, binding: Binding) -> BindingResource<Server> {
         self.server.borrow_mut().get_resource(binding)
     }
@@ -60,20 +91,67 @@ where
         self.server.borrow_mut().create(resource)
     }
 
+    fn create_async(&self, data: &[u8]) -> (Handle, Fence) {
+        self.server.borrow_mut().create_async(data)
+    }
+
+    async fn wait_fence(&self, fence: Fence) {
+        let fut = {
+            let mut server = self.server.borrow_mut();
+            server.wait_fence(fence)
+        };
+        fut.await
+    }
+
     fn empty(&self, size: usize) -> Handle {
         self.server.borrow_mut().empty(size)
     }
 
+    fn try_empty(&self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        self.server.borrow_mut().try_empty(size)
+    }
+
+    fn grow(&self, handle: Handle, size: usize) -> Option<Handle> {
+        self.server.borrow_mut().grow(handle, size)
+    }
+
     unsafe fn execute(
         &self,
         kernel_description: Server::Kernel,
         count: CubeCount,
         bindings: Vec<Binding>,
         kind: ExecutionMode,
+        stream: Stream,
     ) {
         self.server
             .borrow_mut()
-            .execute(kernel_description, count, bindings, kind)
+            .execute(kernel_description, count, bindings, kind, stream)
+    }
+
+    fn evict(&self, kernel: Server::Kernel) {
+        self.server.borrow_mut().evict(kernel)
+    }
+
+    fn prepare(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>) {
+        self.server.borrow_mut().prepare(kernels)
+    }
+
+    fn create_stream(&self) -> Stream {
+        self.server.borrow_mut().create_stream()
+    }
+
+    fn create_stream_with_priority(&self, priority: Priority) -> Stream {
+        self.server
+            .borrow_mut()
+            .create_stream_with_priority(priority)
+    }
+
+    fn record_event(&self, stream: Stream) -> Fence {
+        self.server.borrow_mut().record_event(stream)
+    }
+
+    fn wait_event(&self, stream: Stream, fence: Fence) {
+        self.server.borrow_mut().wait_event(stream, fence)
     }
 
     fn flush(&self) {
@@ -107,6 +185,14 @@ where
     fn disable_timestamps(&self) {
         self.server.borrow_mut().disable_timestamps();
     }
+
+    fn device_lost(&self) -> Option<DeviceLost> {
+        self.server.borrow_mut().device_lost()
+    }
+
+    fn recover(&self) -> Result<(), DeviceLost> {
+        self.server.borrow_mut().recover()
+    }
 }
 
 /// This is unsafe, since no concurrency is supported by the `RefCell` channel.
@@ -4,8 +4,8 @@ use cubecl_common::benchmark::TimestampsResult;
 
 use super::ComputeChannel;
 use crate::{
-    memory_management::MemoryUsage,
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    memory_management::{MemoryUsage, OutOfMemoryError},
+    server::{Binding, ComputeServer, CubeCount, DeviceLost, Fence, Handle, Priority, Stream},
     storage::BindingResource,
     ExecutionMode,
 };
@@ -36,16 +36,37 @@ where
     Server: ComputeServer,
 {
     Read(Binding, Callback<Vec<u8>>),
+    ReadMany(Vec<Binding>, Callback<Vec<Vec<u8>>>),
+    ReadRange(Binding, u64, u64, Callback<Vec<u8>>),
+    Write(Binding, Vec<u8>),
+    Clear(Binding),
+    Fill(Binding, Vec<u8>),
     GetResource(Binding, Callback<BindingResource<Server>>),
     Create(Vec<u8>, Callback<Handle>),
+    CreateAsync(Vec<u8>, Callback<(Handle, Fence)>),
+    WaitFence(Fence, Callback<()>),
     Empty(usize, Callback<Handle>),
-    ExecuteKernel((Server::Kernel, CubeCount, ExecutionMode), Vec<Binding>),
+    TryEmpty(usize, Callback<Result<Handle, OutOfMemoryError>>),
+    Grow(Handle, usize, Callback<Option<Handle>>),
+    ExecuteKernel(
+        (Server::Kernel, CubeCount, ExecutionMode),
+        Vec<Binding>,
+        Stream,
+    ),
+    Evict(Server::Kernel),
+    Prepare(Vec<(Server::Kernel, ExecutionMode)>),
+    CreateStream(Callback<Stream>),
+    CreateStreamWithPriority(Priority, Callback<Stream>),
+    RecordEvent(Stream, Callback<Fence>),
+    WaitEvent(Stream, Fence),
     Flush,
     SyncElapsed(Callback<TimestampsResult>),
     Sync(Callback<()>),
     GetMemoryUsage(Callback<MemoryUsage>),
     EnableTimestamps,
     DisableTimestamps,
+    DeviceLost(Callback<Option<DeviceLost>>),
+    Recover(Callback<Result<(), DeviceLost>>),
 }
 
 impl<Server> MpscComputeChannel<Server>
@@ -66,6 +87,23 @@ where
                             let data = server.read(binding).await;
                             callback.send(data).await.unwrap();
                         }
+                        Message::ReadMany(bindings, callback) => {
+                            let data = server.read_many(bindings).await;
+                            callback.send(data).await.unwrap();
+                        }
+                        Message::ReadRange(binding, offset, len, callback) => {
+                            let data = server.read_range(binding, offset, len).await;
+                            callback.send(data).await.unwrap();
+                        }
+                        Message::Write(binding, data) => {
+                            server.write(binding, &data);
+                        }
+                        Message::Clear(binding) => {
+                            server.clear(binding);
+                        }
+                        Message::Fill(binding, pattern) => {
+                            server.fill(binding, &pattern);
+                        }
                         Message::GetResource(binding, callback) => {
                             let data = server.get_resource(binding);
                             callback.send(data).await.unwrap();
@@ -74,13 +112,48 @@ where
                             let handle = server.create(&data);
                             callback.send(handle).await.unwrap();
                         }
+                        Message::CreateAsync(data, callback) => {
+                            let result = server.create_async(&data);
+                            callback.send(result).await.unwrap();
+                        }
+                        Message::WaitFence(fence, callback) => {
+                            server.wait_fence(fence).await;
+                            callback.send(()).await.unwrap();
+                        }
                         Message::Empty(size, callback) => {
                             let handle = server.empty(size);
                             callback.send(handle).await.unwrap();
                         }
-                        Message::ExecuteKernel(kernel, bindings) => unsafe {
-                            server.execute(kernel.0, kernel.1, bindings, kernel.2);
+                        Message::TryEmpty(size, callback) => {
+                            let handle = server.try_empty(size);
+                            callback.send(handle).await.unwrap();
+                        }
+                        Message::Grow(handle, size, callback) => {
+                            let handle = server.grow(handle, size);
+                            callback.send(handle).await.unwrap();
+                        }
+                        Message::ExecuteKernel(kernel, bindings, stream) => unsafe {
+                            server.execute(kernel.0, kernel.1, bindings, kernel.2, stream);
                         },
+                        Message::Evict(kernel) => {
+                            server.evict(kernel);
+                        }
+                        Message::Prepare(kernels) => {
+                            server.prepare(kernels);
+                        }
+                        Message::CreateStream(callback) => {
+                            callback.send(server.create_stream()).await.unwrap();
+                        }
+                        Message::CreateStreamWithPriority(priority, callback) => {
+                            let stream = server.create_stream_with_priority(priority);
+                            callback.send(stream).await.unwrap();
+                        }
+                        Message::RecordEvent(stream, callback) => {
+                            callback.send(server.record_event(stream)).await.unwrap();
+                        }
+                        Message::WaitEvent(stream, fence) => {
+                            server.wait_event(stream, fence);
+                        }
                         Message::SyncElapsed(callback) => {
                             let duration = server.sync_elapsed().await;
                             callback.send(duration).await.unwrap();
@@ -101,6 +174,12 @@ where
                         Message::DisableTimestamps => {
                             server.disable_timestamps();
                         }
+                        Message::DeviceLost(callback) => {
+                            callback.send(server.device_lost()).await.unwrap();
+                        }
+                        Message::Recover(callback) => {
+                            callback.send(server.recover()).await.unwrap();
+                        }
                     };
                 }
             });
@@ -131,6 +210,47 @@ where
         handle_response(response.recv().await)
     }
 
+    async fn read_many(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
+        let sender = self.state.sender.clone();
+        let (callback, response) = async_channel::unbounded();
+        sender
+            .send(Message::ReadMany(bindings, callback))
+            .await
+            .unwrap();
+        handle_response(response.recv().await)
+    }
+
+    async fn read_range(&self, binding: Binding, offset: u64, len: u64) -> Vec<u8> {
+        let sender = self.state.sender.clone();
+        let (callback, response) = async_channel::unbounded();
+        sender
+            .send(Message::ReadRange(binding, offset, len, callback))
+            .await
+            .unwrap();
+        handle_response(response.recv().await)
+    }
+
+    fn write(&self, binding: Binding, data: &[u8]) {
+        self.state
+            .sender
+            .send_blocking(Message::Write(binding, data.to_vec()))
+            .unwrap();
+    }
+
+    fn clear(&self, binding: Binding) {
+        self.state
+            .sender
+            .send_blocking(Message::Clear(binding))
+            .unwrap();
+    }
+
+    fn fill(&self, binding: Binding, pattern: &[u8]) {
+        self.state
+            .sender
+            .send_blocking(Message::Fill(binding, pattern.to_vec()))
+            .unwrap();
+    }
+
     fn get_resource(&self, binding: Binding) -> BindingResource<Server> {
         let (callback, response) = async_channel::unbounded();
 
@@ -153,6 +273,27 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn create_async(&self, data: &[u8]) -> (Handle, Fence) {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::CreateAsync(data.to_vec(), callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    async fn wait_fence(&self, fence: Fence) {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send(Message::WaitFence(fence, callback))
+            .await
+            .unwrap();
+        handle_response(response.recv().await)
+    }
+
     fn empty(&self, size: usize) -> Handle {
         let (callback, response) = async_channel::unbounded();
         self.state
@@ -163,16 +304,89 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn try_empty(&self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::TryEmpty(size, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn grow(&self, handle: Handle, size: usize) -> Option<Handle> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::Grow(handle, size, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
         count: CubeCount,
         bindings: Vec<Binding>,
         kind: ExecutionMode,
+        stream: Stream,
     ) {
         self.state
             .sender
-            .send_blocking(Message::ExecuteKernel((kernel, count, kind), bindings))
+            .send_blocking(Message::ExecuteKernel(
+                (kernel, count, kind),
+                bindings,
+                stream,
+            ))
+            .unwrap()
+    }
+
+    fn evict(&self, kernel: Server::Kernel) {
+        self.state
+            .sender
+            .send_blocking(Message::Evict(kernel))
+            .unwrap()
+    }
+
+    fn prepare(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>) {
+        self.state
+            .sender
+            .send_blocking(Message::Prepare(kernels))
+            .unwrap()
+    }
+
+    fn create_stream(&self) -> Stream {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::CreateStream(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn create_stream_with_priority(&self, priority: Priority) -> Stream {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::CreateStreamWithPriority(priority, callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn record_event(&self, stream: Stream) -> Fence {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::RecordEvent(stream, callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn wait_event(&self, stream: Stream, fence: Fence) {
+        self.state
+            .sender
+            .send_blocking(Message::WaitEvent(stream, fence))
             .unwrap()
     }
 
@@ -222,6 +436,26 @@ where
             .send_blocking(Message::DisableTimestamps)
             .unwrap();
     }
+
+    fn device_lost(&self) -> Option<DeviceLost> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::DeviceLost(callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn recover(&self) -> Result<(), DeviceLost> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::Recover(callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
 }
 
 fn handle_response<Response, Err: core::fmt::Debug>(response: Result<Response, Err>) -> Response {
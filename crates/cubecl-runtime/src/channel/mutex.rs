@@ -1,5 +1,8 @@
 use super::ComputeChannel;
-use crate::server::{Binding, ComputeServer, CubeCount, Handle};
+use crate::memory_management::OutOfMemoryError;
+use crate::server::{
+    Binding, ComputeServer, CubeCount, DeviceLost, Fence, Handle, Priority, Stream,
+};
 use crate::storage::BindingResource;
 use crate::ExecutionMode;
 use alloc::sync::Arc;
@@ -47,6 +50,34 @@ where
         fut.await
     }
 
+    async fn read_many(&self, bindings: Vec<Binding>) -> Vec<Vec<u8>> {
+        let fut = {
+            let mut server = self.server.lock();
+            server.read_many(bindings)
+        };
+        fut.await
+    }
+
+    async fn read_range(&self, binding: Binding, offset: u64, len: u64) -> Vec<u8> {
+        let fut = {
+            let mut server = self.server.lock();
+            server.read_range(binding, offset, len)
+        };
+        fut.await
+    }
+
+    fn write(&self, binding: Binding, data: &[u8]) {
+        self.server.lock().write(binding, data)
+    }
+
+    fn clear(&self, binding: Binding) {
+        self.server.lock().clear(binding)
+    }
+
+    fn fill(&self, binding: Binding, pattern: &[u8]) {
+        self.server.lock().fill(binding, pattern)
+    }
+
     fn get_resource(&self, binding: Binding) -> BindingResource<Server> {
         self.server.lock().get_resource(binding)
     }
@@ -55,18 +86,65 @@ where
         self.server.lock().create(data)
     }
 
+    fn create_async(&self, data: &[u8]) -> (Handle, Fence) {
+        self.server.lock().create_async(data)
+    }
+
+    async fn wait_fence(&self, fence: Fence) {
+        let fut = {
+            let mut server = self.server.lock();
+            server.wait_fence(fence)
+        };
+        fut.await
+    }
+
     fn empty(&self, size: usize) -> Handle {
         self.server.lock().empty(size)
     }
 
+    fn try_empty(&self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        self.server.lock().try_empty(size)
+    }
+
+    fn grow(&self, handle: Handle, size: usize) -> Option<Handle> {
+        self.server.lock().grow(handle, size)
+    }
+
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
         count: CubeCount,
         handles: Vec<Binding>,
         kind: ExecutionMode,
+        stream: Stream,
     ) {
-        self.server.lock().execute(kernel, count, handles, kind)
+        self.server
+            .lock()
+            .execute(kernel, count, handles, kind, stream)
+    }
+
+    fn evict(&self, kernel: Server::Kernel) {
+        self.server.lock().evict(kernel)
+    }
+
+    fn prepare(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>) {
+        self.server.lock().prepare(kernels)
+    }
+
+    fn create_stream(&self) -> Stream {
+        self.server.lock().create_stream()
+    }
+
+    fn create_stream_with_priority(&self, priority: Priority) -> Stream {
+        self.server.lock().create_stream_with_priority(priority)
+    }
+
+    fn record_event(&self, stream: Stream) -> Fence {
+        self.server.lock().record_event(stream)
+    }
+
+    fn wait_event(&self, stream: Stream, fence: Fence) {
+        self.server.lock().wait_event(stream, fence)
     }
 
     fn flush(&self) {
@@ -104,4 +182,12 @@ where
     fn disable_timestamps(&self) {
         self.server.lock().disable_timestamps();
     }
+
+    fn device_lost(&self) -> Option<DeviceLost> {
+        self.server.lock().device_lost()
+    }
+
+    fn recover(&self) -> Result<(), DeviceLost> {
+        self.server.lock().recover()
+    }
 }
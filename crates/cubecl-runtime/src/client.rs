@@ -1,28 +1,226 @@
 use core::future::Future;
 
 use crate::{
+    cancellation::{CancellationToken, Cancelled},
     channel::ComputeChannel,
-    memory_management::MemoryUsage,
-    server::{Binding, ComputeServer, CubeCount, Handle},
+    memory_management::{MemoryTagUsage, MemoryTags, MemoryUsage, OutOfMemoryError},
+    server::{Binding, ComputeServer, CubeCount, DeviceLost, Fence, Handle, Priority, Stream},
     storage::BindingResource,
     DeviceProperties, ExecutionMode,
 };
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::time::Duration;
 use cubecl_common::benchmark::TimestampsResult;
+use hashbrown::HashSet;
 
 /// The ComputeClient is the entry point to require tasks from the ComputeServer.
 /// It should be obtained for a specific device via the Compute struct.
+///
+/// # Thread safety
+///
+/// The client is cheap to clone, and its clones can be used from many threads at once when its
+/// channel is `Send + Sync`, as with the mutex and mpsc channels. Every operation reaching the
+/// server, including the allocations of the memory pools and the lookups of the compiled kernels,
+/// is serialized by the channel, so launches from different threads are executed in the order
+/// they acquire it. The state shared by the clones, such as the launch hooks, is behind locks
+/// that are only held exclusively while it's modified.
 #[derive(Debug)]
 pub struct ComputeClient<Server: ComputeServer, Channel> {
     channel: Channel,
     state: Arc<ComputeClientState<Server>>,
+    deterministic: bool,
+    zero_initialized: bool,
+    profile_launches: bool,
+    cancellation: Option<CancellationToken>,
+    timeout: Option<Duration>,
+    memory_tag: Option<Arc<str>>,
+    stream: Stream,
 }
 
 #[derive(new, Debug)]
 struct ComputeClientState<Server: ComputeServer> {
     properties: DeviceProperties<Server::Feature>,
     timestamp_lock: async_lock::Mutex<()>,
+    #[new(default)]
+    hooks: spin::RwLock<LaunchHooks>,
+    /// The launches not known to be completed yet, only recorded with completion hooks or a
+    /// timeout.
+    #[new(default)]
+    pending: Arc<spin::Mutex<Vec<Launch>>>,
+    /// Watches the syncs of the clients with a [timeout](ComputeClient::with_timeout).
+    #[cfg(watchdog)]
+    #[new(default)]
+    watchdog: crate::watchdog::Watchdog,
+    /// The timeout of a sync that couldn't return it, returned by the next fallible operation.
+    #[new(default)]
+    timed_out: spin::Mutex<Option<DeviceLost>>,
+    #[new(default)]
+    memory_tags: Arc<MemoryTags>,
+    /// The stream of each [priority](ComputeClient::with_priority), created on first use.
+    #[new(default)]
+    priority_streams: spin::Mutex<BTreeMap<Priority, Stream>>,
+    /// The hash of the kernels [validated](ComputeClient::validate_once) on the device.
+    #[new(default)]
+    validated: spin::Mutex<HashSet<u64>>,
+    #[new(default)]
+    out_of_memory_hooks: spin::Mutex<OutOfMemoryHooks>,
+    /// The threads [recording](ComputeClient::record_kernels) their launches, innermost last.
+    #[cfg(feature = "std")]
+    #[new(default)]
+    recordings: Recordings<Server>,
+    /// Whether any thread is recording, so launches don't lock the recordings otherwise.
+    #[cfg(feature = "std")]
+    #[new(default)]
+    recording: core::sync::atomic::AtomicBool,
+}
+
+/// The threads recording their launches.
+#[cfg(feature = "std")]
+struct Recordings<Server: ComputeServer>(spin::Mutex<Vec<Recording<Server::Kernel>>>);
+
+#[cfg(feature = "std")]
+impl<Server: ComputeServer> Default for Recordings<Server> {
+    fn default() -> Self {
+        Self(spin::Mutex::new(Vec::new()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Server: ComputeServer> core::fmt::Debug for Recordings<Server> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.0.lock().iter()).finish()
+    }
+}
+
+/// The kernels launched by a thread while it records, `None` when the recording is suspended
+/// and the kernels are executed.
+#[cfg(feature = "std")]
+struct Recording<Kernel> {
+    thread: std::thread::ThreadId,
+    kernels: Option<Vec<(Kernel, ExecutionMode)>>,
+}
+
+#[cfg(feature = "std")]
+impl<Kernel> core::fmt::Debug for Recording<Kernel> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Recording")
+            .field("thread", &self.thread)
+            .field("kernels", &self.kernels.as_ref().map(Vec::len))
+            .finish()
+    }
+}
+
+/// A kernel launched by a [client](ComputeClient), given to its
+/// [launch hooks](ComputeClient::on_launch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Launch {
+    /// The readable name of the kernel.
+    pub kernel: String,
+    /// The number of cubes dispatched, unknown when the count is read from a buffer.
+    pub cube_count: Option<(u32, u32, u32)>,
+    /// Whether the kernel is launched with bound checks.
+    pub mode: ExecutionMode,
+    /// The stream the kernel is executed on.
+    pub stream: Stream,
+}
+
+type LaunchHook = Arc<dyn Fn(&Launch) + Send + Sync>;
+type CompleteHook = Arc<dyn Fn(&Launch, Option<Duration>) + Send + Sync>;
+
+#[derive(Default)]
+struct LaunchHooks {
+    launch: Vec<LaunchHook>,
+    complete: Vec<CompleteHook>,
+}
+
+impl core::fmt::Debug for LaunchHooks {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LaunchHooks")
+            .field("launch", &self.launch.len())
+            .field("complete", &self.complete.len())
+            .finish()
+    }
+}
+
+/// The failed allocation given to the [out of memory hooks](ComputeClient::on_out_of_memory),
+/// with access to the resources of the client so they can be spilled to the host.
+pub struct OutOfMemoryContext<'a> {
+    error: &'a OutOfMemoryError,
+    read: &'a dyn Fn(Binding) -> Vec<u8>,
+}
+
+impl OutOfMemoryContext<'_> {
+    /// The error of the failed allocation.
+    pub fn error(&self) -> &OutOfMemoryError {
+        self.error
+    }
+
+    /// Reads the content of a resource, for example to copy it to the host before freeing it.
+    pub fn read(&self, binding: Binding) -> Vec<u8> {
+        (self.read)(binding)
+    }
+
+    /// Frees a resource by dropping its handle, and returns whether it was the last handle over
+    /// it, in which case its memory is released before the allocation is retried.
+    pub fn free(&self, handle: Handle) -> bool {
+        handle.can_mut()
+    }
+}
+
+type OutOfMemoryHook = Box<dyn FnMut(&mut OutOfMemoryContext<'_>) -> bool + Send>;
+
+#[derive(Default)]
+struct OutOfMemoryHooks(Vec<OutOfMemoryHook>);
+
+impl core::fmt::Debug for OutOfMemoryHooks {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OutOfMemoryHooks")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// Ends the innermost recording of its thread when dropped, even when the recorded function
+/// panics.
+#[cfg(feature = "std")]
+struct RecordingGuard<'a, Server: ComputeServer> {
+    state: &'a ComputeClientState<Server>,
+    thread: std::thread::ThreadId,
+    popped: bool,
+}
+
+#[cfg(feature = "std")]
+impl<Server: ComputeServer> RecordingGuard<'_, Server> {
+    fn pop(mut self) -> Option<Vec<(Server::Kernel, ExecutionMode)>> {
+        self.popped = true;
+        self.remove()
+    }
+
+    fn remove(&self) -> Option<Vec<(Server::Kernel, ExecutionMode)>> {
+        let mut recordings = self.state.recordings.0.lock();
+        let index = recordings
+            .iter()
+            .rposition(|recording| recording.thread == self.thread)?;
+        let recording = recordings.remove(index);
+        self.state.recording.store(
+            !recordings.is_empty(),
+            core::sync::atomic::Ordering::Release,
+        );
+        recording.kernels
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Server: ComputeServer> Drop for RecordingGuard<'_, Server> {
+    fn drop(&mut self) {
+        if !self.popped {
+            self.remove();
+        }
+    }
 }
 
 impl<S, C> Clone for ComputeClient<S, C>
@@ -34,6 +232,13 @@ where
         Self {
             channel: self.channel.clone(),
             state: self.state.clone(),
+            deterministic: self.deterministic,
+            zero_initialized: self.zero_initialized,
+            profile_launches: self.profile_launches,
+            cancellation: self.cancellation.clone(),
+            timeout: self.timeout,
+            memory_tag: self.memory_tag.clone(),
+            stream: self.stream,
         }
     }
 }
@@ -49,12 +254,186 @@ where
         Self {
             channel,
             state: Arc::new(state),
+            deterministic: false,
+            zero_initialized: cfg!(feature = "zero-initialize"),
+            profile_launches: false,
+            cancellation: None,
+            timeout: None,
+            memory_tag: None,
+            stream: Stream::default(),
         }
     }
 
+    /// Create a new stream on the server, see [with_stream](Self::with_stream).
+    pub fn create_stream(&self) -> Stream {
+        self.channel.create_stream()
+    }
+
+    /// Execute the kernels of this client and its clones on the given `stream`.
+    ///
+    /// Kernels on different streams can run concurrently, so independent kernel chains should use
+    /// their own stream. A chain depending on the results of another waits on an
+    /// [event](Self::event) of the other chain's client.
+    pub fn with_stream(mut self, stream: Stream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Execute the kernels of this client and its clones on the stream of the `priority`, shared
+    /// by the clients of the device with the same priority.
+    ///
+    /// The [normal](Priority::Normal) priority is the one of the default stream.
+    pub fn with_priority(self, priority: Priority) -> Self {
+        let stream = match priority {
+            Priority::Normal => Stream::default(),
+            _ => *self
+                .state
+                .priority_streams
+                .lock()
+                .entry(priority)
+                .or_insert_with(|| self.channel.create_stream_with_priority(priority)),
+        };
+        self.with_stream(stream)
+    }
+
+    /// The stream the kernels of this client are executed on.
+    pub fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    /// Record an event on the stream of this client, returning a fence signaled once the kernels
+    /// executed on it so far are completed.
+    pub fn event(&self) -> Fence {
+        self.channel.record_event(self.stream)
+    }
+
+    /// Make the kernels executed next by this client wait on the `fence`.
+    pub fn wait(&self, fence: Fence) {
+        self.channel.wait_event(self.stream, fence)
+    }
+
+    /// Drop the kernels launched by this client and its clones once the `token` is
+    /// [cancelled](CancellationToken::cancel).
+    ///
+    /// The token is checked before each launch, so a long sequence of launches stops at the next
+    /// one, and the results it was writing are left incomplete. The dropped launches are returned
+    /// as an error by [try_execute](Self::try_execute) and counted by the
+    /// [token](CancellationToken::dropped_launches).
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Whether the [cancellation token](Self::with_cancellation) of this client was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
+    /// Watch the syncs of this client and its clones, reporting the kernels that didn't complete
+    /// within the `timeout`.
+    ///
+    /// Desktops without a GPU watchdog freeze while a kernel runs, so an accidental infinite loop
+    /// is otherwise only noticed when the application stops responding. Once a sync exceeds the
+    /// timeout, the kernels launched since the previous sync are logged as errors along with
+    /// their cube count, and [try_sync](Self::try_sync) returns them as an error after
+    /// [recovering](Self::recover) the device when it was lost meanwhile.
+    ///
+    /// The timeout is only watched on platforms with threads.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Attribute the resources allocated by this client and its clones to the `tag`, such as the
+    /// name of a model layer, reported with its peak usage by [memory_tags](Self::memory_tags).
+    ///
+    /// A resource is accounted for its requested size until every handle over it is dropped.
+    pub fn with_memory_tag(mut self, tag: impl Into<String>) -> Self {
+        self.memory_tag = Some(Arc::from(tag.into()));
+        self
+    }
+
+    /// Enable or disable the deterministic mode for this client and its clones.
+    ///
+    /// In deterministic mode, library kernels avoid operations whose results depend on the
+    /// scheduling of the device, such as atomic accumulations of floating point values whose
+    /// rounding depends on their order, and autotune doesn't benchmark anything, always selecting
+    /// the same operation. This makes runs on the same device reproducible at a performance cost.
+    /// Atomic accumulations of integers are exact in any order, so they are kept.
+    pub fn deterministic(mut self, enabled: bool) -> Self {
+        self.deterministic = enabled;
+        self
+    }
+
+    /// Whether the client is in [deterministic mode](Self::deterministic).
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Enable or disable the zero initialization of the resources reserved by this client and its
+    /// clones.
+    ///
+    /// Reserved memory is usually reused from resources that were dropped, so it holds the values
+    /// of unrelated operations. When enabled, [empty](Self::empty) resources are cleared before
+    /// any kernel uses them, which makes reads of values never written reproducible, and doesn't
+    /// leak data between users of the device. Growing a resource then copies it to a cleared
+    /// resource instead of growing it in place.
+    ///
+    /// It is enabled by default with the `zero-initialize` feature.
+    pub fn zero_initialized(mut self, enabled: bool) -> Self {
+        self.zero_initialized = enabled;
+        self
+    }
+
+    /// Whether the client [zero initializes](Self::zero_initialized) the resources it reserves.
+    pub fn is_zero_initialized(&self) -> bool {
+        self.zero_initialized
+    }
+
+    /// Register a hook called with each kernel launched by this client and its clones, before it
+    /// is submitted to the server.
+    ///
+    /// Hooks are called in the order they were registered, on the thread launching the kernel.
+    pub fn on_launch(&self, hook: impl Fn(&Launch) + Send + Sync + 'static) {
+        self.state.hooks.write().launch.push(Arc::new(hook));
+    }
+
+    /// Register a hook called with each kernel launched by this client and its clones once it is
+    /// completed.
+    ///
+    /// Completion is only observed when the client [syncs](Self::sync), which calls the hook for
+    /// every kernel launched since the previous sync, without a duration. With
+    /// [profiled launches](Self::profile_launches), the hook is instead called right after the
+    /// kernel completes, with the time elapsed since its launch.
+    pub fn on_complete(&self, hook: impl Fn(&Launch, Option<Duration>) + Send + Sync + 'static) {
+        self.state.hooks.write().complete.push(Arc::new(hook));
+    }
+
+    /// Enable or disable the profiling of the kernels launched by this client.
+    ///
+    /// When enabled and [completion hooks](Self::on_complete) are registered, each launch waits
+    /// for the kernel to complete, which serializes the work of the device, so it's only meant
+    /// for development. On platforms that can't block, such as wasm, the kernels are reported at
+    /// the next sync instead.
+    pub fn profile_launches(mut self, enabled: bool) -> Self {
+        self.profile_launches = enabled;
+        self
+    }
+
     /// Given a binding, returns owned resource as bytes.
     pub async fn read_async(&self, binding: Binding) -> Vec<u8> {
-        self.channel.read(binding).await
+        crate::trace_future!(self.channel.read(binding), "read").await
+    }
+
+    /// Given a binding, returns owned resource as bytes, or the error of the device when it was
+    /// [lost](Self::device_lost).
+    pub fn try_read(&self, binding: Binding) -> Result<Vec<u8>, DeviceLost> {
+        self.check_device()?;
+        let data = self.read(binding);
+        self.check_device()?;
+        Ok(data)
     }
 
     /// Given a binding, returns owned resource as bytes.
@@ -62,7 +441,86 @@ where
     /// # Remarks
     /// Panics if the read operation fails.
     pub fn read(&self, binding: Binding) -> Vec<u8> {
-        cubecl_common::reader::read_sync(self.channel.read(binding))
+        cubecl_common::reader::read_sync(crate::trace_future!(self.channel.read(binding), "read"))
+    }
+
+    /// Given multiple bindings, returns the owned resources as bytes, in the same order.
+    ///
+    /// Every read is staged at once, paying for a single synchronization instead of one per
+    /// binding.
+    pub async fn read_many_async(&self, bindings: &[Binding]) -> Vec<Vec<u8>> {
+        crate::trace_future!(
+            self.channel.read_many(bindings.to_vec()),
+            "read",
+            count = bindings.len()
+        )
+        .await
+    }
+
+    /// Given multiple bindings, returns the owned resources as bytes, in the same order.
+    ///
+    /// Every read is staged at once, paying for a single synchronization instead of one per
+    /// binding.
+    ///
+    /// # Remarks
+    /// Panics if the read operation fails.
+    pub fn read_many(&self, bindings: &[Binding]) -> Vec<Vec<u8>> {
+        cubecl_common::reader::read_sync(crate::trace_future!(
+            self.channel.read_many(bindings.to_vec()),
+            "read",
+            count = bindings.len()
+        ))
+    }
+
+    /// Given a handle, returns `len` bytes of the resource starting `offset` bytes after the start
+    /// of the handle, without reading back the rest of the allocation.
+    pub async fn read_range_async(&self, handle: &Handle, offset: u64, len: u64) -> Vec<u8> {
+        crate::trace_future!(
+            self.channel
+                .read_range(handle.clone().binding(), offset, len),
+            "read",
+            size = len
+        )
+        .await
+    }
+
+    /// Given a handle, returns `len` bytes of the resource starting `offset` bytes after the start
+    /// of the handle, without reading back the rest of the allocation.
+    ///
+    /// # Remarks
+    /// Panics if the range is out of bounds of the handle.
+    pub fn read_range(&self, handle: &Handle, offset: u64, len: u64) -> Vec<u8> {
+        cubecl_common::reader::read_sync(crate::trace_future!(
+            self.channel
+                .read_range(handle.clone().binding(), offset, len),
+            "read",
+            size = len
+        ))
+    }
+
+    /// Overwrites the bytes of the resource starting `offset` bytes after the start of the handle
+    /// with `data`, keeping the rest of the allocation untouched.
+    ///
+    /// The write is ordered after the kernels already executed, and before the next ones.
+    pub fn write_range(&self, handle: &Handle, offset: u64, data: &[u8]) {
+        self.channel
+            .write(handle.clone().offset_start(offset).binding(), data)
+    }
+
+    /// Repeats `pattern` over the bytes of the resource, the last repetition being truncated when
+    /// the size of the resource isn't a multiple of its length.
+    ///
+    /// The resource is filled on the device without uploading its content, so zeroing an
+    /// accumulator is cheaper than writing a buffer of zeros. The fill is ordered like a
+    /// [write](Self::write_range).
+    pub fn fill(&self, handle: &Handle, pattern: &[u8]) {
+        assert!(!pattern.is_empty(), "The pattern of a fill can't be empty");
+
+        if pattern.iter().all(|byte| *byte == 0) {
+            self.channel.clear(handle.clone().binding())
+        } else {
+            self.channel.fill(handle.clone().binding(), pattern)
+        }
     }
 
     /// Given a resource handle, returns the storage resource.
@@ -72,20 +530,151 @@ where
 
     /// Given a resource, stores it and returns the resource handle.
     pub fn create(&self, data: &[u8]) -> Handle {
-        self.channel.create(data)
+        let _span = crate::trace_span!("create", size = data.len());
+        self.tag(self.channel.create(data), data.len())
+    }
+
+    /// Given a resource, starts storing it on a dedicated transfer queue and returns the resource
+    /// handle along with a fence signaled once the copy is completed.
+    ///
+    /// The copy overlaps with the kernels already submitted, so uploading the inputs of the next
+    /// batch doesn't stall the computation of the current one. The next kernel executed waits on
+    /// the fence, and [wait_fence](Self::wait_fence) waits for it on the host.
+    pub fn create_async(&self, data: &[u8]) -> (Handle, Fence) {
+        let _span = crate::trace_span!("create", size = data.len());
+        let (handle, fence) = self.channel.create_async(data);
+        (self.tag(handle, data.len()), fence)
+    }
+
+    /// Wait for the completion of the operation signaling the `fence`.
+    pub async fn wait_fence(&self, fence: Fence) {
+        self.channel.wait_fence(fence).await
     }
 
     /// Reserves `size` bytes in the storage, and returns a handle over them.
+    ///
+    /// # Panics
+    ///
+    /// When the device is out of memory, after the [hooks](Self::on_out_of_memory) failed to
+    /// recover.
     pub fn empty(&self, size: usize) -> Handle {
-        self.channel.empty(size)
+        self.try_empty(size).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them, or the error of the
+    /// allocation when the device is out of memory.
+    ///
+    /// The error carries the requested size and the memory usage of the pools, after the
+    /// [hooks](Self::on_out_of_memory) failed to recover.
+    pub fn try_empty(&self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        let _span = crate::trace_span!("empty", size);
+        let handle = self.tag(self.reserve(size)?, size);
+        if self.zero_initialized {
+            self.channel.clear(handle.clone().binding());
+        }
+        Ok(handle)
+    }
+
+    /// Register a hook called when an [allocation](Self::try_empty) of this client or its clones
+    /// fails because the device is out of memory.
+    ///
+    /// The hook is called after the memory that isn't used anymore was released, with a
+    /// [context](OutOfMemoryContext) that can read and free resources, for example to spill them
+    /// to the host. It returns whether it released memory, so the allocation is retried. Hooks are
+    /// called in the order they were registered until the allocation succeeds.
+    ///
+    /// The hooks run on the thread allocating, and an allocation made by a hook doesn't call them
+    /// again.
+    pub fn on_out_of_memory(
+        &self,
+        hook: impl FnMut(&mut OutOfMemoryContext<'_>) -> bool + Send + 'static,
+    ) {
+        self.state.out_of_memory_hooks.lock().0.push(Box::new(hook));
+    }
+
+    /// Reserves `size` bytes, calling the out of memory hooks until the allocation succeeds.
+    fn reserve(&self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        let mut error = match self.channel.try_empty(size) {
+            Ok(handle) => return Ok(handle),
+            Err(error) => error,
+        };
+
+        // Hooks are taken out while running, so they can allocate without being called again.
+        let mut hooks = core::mem::take(&mut self.state.out_of_memory_hooks.lock().0);
+        let read = |binding| self.read(binding);
+        let mut result = None;
+        for hook in hooks.iter_mut() {
+            let mut context = OutOfMemoryContext {
+                error: &error,
+                read: &read,
+            };
+            if !hook(&mut context) {
+                continue;
+            }
+
+            match self.channel.try_empty(size) {
+                Ok(handle) => {
+                    result = Some(handle);
+                    break;
+                }
+                Err(err) => error = err,
+            }
+        }
+        let mut state = self.state.out_of_memory_hooks.lock();
+        hooks.append(&mut state.0);
+        state.0 = hooks;
+
+        result.ok_or(error)
+    }
+
+    /// Grows the resource of the handle to `size` bytes, keeping its content, and returns the
+    /// handle over the whole grown resource.
+    ///
+    /// On servers supporting growable memory, the resource is moved once to a growable allocation
+    /// and later extended in place, so a buffer growing at every step isn't reallocated and copied
+    /// each time. The other servers copy the content to a new resource.
+    pub fn grow(&self, handle: Handle, size: usize) -> Handle {
+        let _span = crate::trace_span!("grow", size);
+        // The grown bytes of a resource aren't cleared, so it is copied to a cleared resource.
+        if !self.zero_initialized {
+            if let Some(grown) = self.channel.grow(handle.clone(), size) {
+                return self.tag(grown, size);
+            }
+        }
+
+        let data = self.read(handle.binding());
+        let grown = self.empty(size);
+        self.write_range(&grown, 0, &data[..Ord::min(data.len(), size)]);
+        grown
+    }
+
+    fn tag(&self, mut handle: Handle, size: usize) -> Handle {
+        handle.size = Some(size as u64);
+        if let Some(tag) = &self.memory_tag {
+            let allocation = self.state.memory_tags.allocate(tag, size as u64);
+            handle.tag = Some(Arc::new(allocation));
+        }
+        handle
     }
 
     /// Executes the `kernel` over the given `bindings`.
+    ///
+    /// The launch is dropped when the client was [cancelled](Self::with_cancellation), which
+    /// [try_execute](Self::try_execute) returns as an error.
     pub fn execute(&self, kernel: Server::Kernel, count: CubeCount, bindings: Vec<Binding>) {
-        unsafe {
-            self.channel
-                .execute(kernel, count, bindings, ExecutionMode::Checked)
-        }
+        let result = unsafe { self.execute_mode(kernel, count, bindings, ExecutionMode::Checked) };
+        log_cancelled(result);
+    }
+
+    /// Executes the `kernel` over the given `bindings`, or returns the cancellation without
+    /// launching it when the client was [cancelled](Self::with_cancellation).
+    pub fn try_execute(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+    ) -> Result<(), Cancelled> {
+        unsafe { self.execute_mode(kernel, count, bindings, ExecutionMode::Checked) }
     }
 
     /// Executes the `kernel` over the given `bindings` without performing any bound checks.
@@ -99,8 +688,183 @@ where
         count: CubeCount,
         bindings: Vec<Binding>,
     ) {
+        log_cancelled(self.execute_mode(kernel, count, bindings, ExecutionMode::Unchecked));
+    }
+
+    /// Executes the `kernel` over the given `bindings` without performing any bound checks, or
+    /// returns the cancellation without launching it when the client was
+    /// [cancelled](Self::with_cancellation).
+    ///
+    /// # Safety
+    ///
+    /// Without checks, the out-of-bound reads and writes can happen.
+    pub unsafe fn try_execute_unchecked(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+    ) -> Result<(), Cancelled> {
+        self.execute_mode(kernel, count, bindings, ExecutionMode::Unchecked)
+    }
+
+    unsafe fn execute_mode(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Vec<Binding>,
+        mode: ExecutionMode,
+    ) -> Result<(), Cancelled> {
+        if let Some(token) = &self.cancellation {
+            token.check()?;
+        }
+        #[cfg(feature = "std")]
+        let Some(kernel) = self.record(kernel, mode) else {
+            return Ok(());
+        };
+
+        let (launch_hooks, has_complete_hooks) = {
+            let hooks = self.state.hooks.read();
+            (hooks.launch.clone(), !hooks.complete.is_empty())
+        };
+        let tracked = has_complete_hooks || self.timeout.is_some();
+        if launch_hooks.is_empty() && !tracked {
+            self.channel
+                .execute(kernel, count, bindings, mode, self.stream);
+            return Ok(());
+        }
+
+        let launch = Launch {
+            kernel: Server::kernel_name(&kernel),
+            cube_count: match count {
+                CubeCount::Static(x, y, z) => Some((x, y, z)),
+                CubeCount::Dynamic(_) => None,
+            },
+            mode,
+            stream: self.stream,
+        };
+        // Hooks are called without holding the lock, so they can register other hooks.
+        for hook in launch_hooks.iter() {
+            hook(&launch);
+        }
+
+        #[cfg(feature = "std")]
+        let start = cubecl_common::stub::Instant::now();
         self.channel
-            .execute(kernel, count, bindings, ExecutionMode::Unchecked)
+            .execute(kernel, count, bindings, mode, self.stream);
+        if !tracked {
+            return Ok(());
+        }
+
+        if has_complete_hooks
+            && self.profile_launches
+            && cubecl_common::reader::try_read_sync(self.channel.sync()).is_some()
+        {
+            #[cfg(feature = "std")]
+            let duration = Some(start.elapsed());
+            #[cfg(not(feature = "std"))]
+            let duration = None;
+            // The launches pending before are completed as well.
+            self.complete_launches();
+            let hooks = self.state.hooks.read().complete.clone();
+            for hook in hooks.iter() {
+                hook(&launch, duration);
+            }
+        } else {
+            self.state.pending.lock().push(launch);
+        }
+
+        Ok(())
+    }
+
+    /// Call the completion hooks for the launches pending until the last sync.
+    fn complete_launches(&self) {
+        let pending = core::mem::take(&mut *self.state.pending.lock());
+        let hooks = self.state.hooks.read().complete.clone();
+        for launch in pending.iter() {
+            for hook in hooks.iter() {
+                hook(launch, None);
+            }
+        }
+    }
+
+    /// Remove the compiled `kernel` from the cache of the server, so it's compiled again on its
+    /// next execution.
+    pub fn evict(&self, kernel: Server::Kernel) {
+        self.channel.evict(kernel);
+    }
+
+    /// Compile the `kernels` ahead of their execution, concurrently when the server can.
+    pub fn prepare(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>) {
+        if !kernels.is_empty() {
+            self.channel.prepare(kernels);
+        }
+    }
+
+    /// Run `func`, returning the kernels it launches on the current thread instead of executing
+    /// them, so they can be [prepared](Self::prepare) together.
+    ///
+    /// Everything else reaches the server as usual, so the outputs of the kernels are left
+    /// uninitialized and reading them returns garbage. Launches from other threads are executed.
+    #[cfg(feature = "std")]
+    pub fn record_kernels(&self, func: impl FnOnce()) -> Vec<(Server::Kernel, ExecutionMode)> {
+        let recording = self.push_recording(Some(Vec::new()));
+        func();
+        recording.pop().unwrap_or_default()
+    }
+
+    /// Run `func` executing the kernels it launches, even while the current thread records.
+    #[cfg(feature = "std")]
+    pub(crate) fn without_recording<T>(&self, func: impl FnOnce() -> T) -> T {
+        let recording = self.push_recording(None);
+        let output = func();
+        recording.pop();
+        output
+    }
+
+    #[cfg(feature = "std")]
+    fn push_recording(
+        &self,
+        kernels: Option<Vec<(Server::Kernel, ExecutionMode)>>,
+    ) -> RecordingGuard<'_, Server> {
+        let thread = std::thread::current().id();
+        let mut recordings = self.state.recordings.0.lock();
+        recordings.push(Recording { thread, kernels });
+        self.state
+            .recording
+            .store(true, core::sync::atomic::Ordering::Release);
+
+        RecordingGuard {
+            state: &self.state,
+            thread,
+            popped: false,
+        }
+    }
+
+    /// Record the kernel when the current thread records, returning it otherwise.
+    #[cfg(feature = "std")]
+    fn record(&self, kernel: Server::Kernel, mode: ExecutionMode) -> Option<Server::Kernel> {
+        if !self
+            .state
+            .recording
+            .load(core::sync::atomic::Ordering::Acquire)
+        {
+            return Some(kernel);
+        }
+
+        let thread = std::thread::current().id();
+        let mut recordings = self.state.recordings.0.lock();
+        match recordings
+            .iter_mut()
+            .rev()
+            .find(|recording| recording.thread == thread)
+            .and_then(|recording| recording.kernels.as_mut())
+        {
+            Some(kernels) => {
+                kernels.push((kernel, mode));
+                None
+            }
+            None => Some(kernel),
+        }
     }
 
     /// Flush all outstanding commands.
@@ -109,13 +873,100 @@ where
     }
 
     /// Wait for the completion of every task in the server.
+    ///
+    /// When the sync exceeds the [timeout](Self::with_timeout), the next fallible operation, such
+    /// as [try_sync](Self::try_sync) or [try_read](Self::try_read), returns the timeout error.
     pub async fn sync(&self) {
-        self.channel.sync().await
+        let watchdog = self.arm_watchdog();
+        crate::trace_future!(self.channel.sync(), "sync").await;
+        self.keep_timeout(self.disarm_watchdog(watchdog));
+        self.complete_launches();
+    }
+
+    /// Wait for the completion of every task in the server, or return the error of the device
+    /// when it was [lost](Self::device_lost).
+    pub async fn try_sync(&self) -> Result<(), DeviceLost> {
+        self.check_device()?;
+        let watchdog = self.arm_watchdog();
+        crate::trace_future!(self.channel.sync(), "sync").await;
+        let timeout = self.disarm_watchdog(watchdog);
+        self.complete_launches();
+        if let Some(timeout) = timeout {
+            if self.channel.device_lost().is_some() {
+                self.recover()?;
+            }
+            return Err(timeout);
+        }
+        self.check_device()
     }
 
     /// Wait for the completion of every task in the server.
+    ///
+    /// The timeout error is kept like with [sync](Self::sync).
     pub async fn sync_elapsed(&self) -> TimestampsResult {
-        self.channel.sync_elapsed().await
+        let watchdog = self.arm_watchdog();
+        let result = crate::trace_future!(self.channel.sync_elapsed(), "sync").await;
+        self.keep_timeout(self.disarm_watchdog(watchdog));
+        self.complete_launches();
+        result
+    }
+
+    #[cfg(watchdog)]
+    fn arm_watchdog(&self) -> Option<crate::watchdog::WatchedSync> {
+        let timeout = self.timeout?;
+        Some(self.state.watchdog.arm(timeout, self.state.pending.clone()))
+    }
+
+    #[cfg(not(watchdog))]
+    fn arm_watchdog(&self) -> Option<()> {
+        None
+    }
+
+    #[cfg(watchdog)]
+    fn disarm_watchdog(&self, sync: Option<crate::watchdog::WatchedSync>) -> Option<DeviceLost> {
+        self.state.watchdog.disarm(sync?)
+    }
+
+    #[cfg(not(watchdog))]
+    fn disarm_watchdog(&self, _sync: Option<()>) -> Option<DeviceLost> {
+        None
+    }
+
+    /// Keep the timeout of a sync that can't return it for the next fallible operation.
+    fn keep_timeout(&self, timeout: Option<DeviceLost>) {
+        if let Some(timeout) = timeout {
+            *self.state.timed_out.lock() = Some(timeout);
+        }
+    }
+
+    /// The error of the device when it was lost, for instance after a driver reset or a hang.
+    ///
+    /// The operations of a lost device can't complete, so the client has to
+    /// [recover](Self::recover) before being used again.
+    pub fn device_lost(&self) -> Option<DeviceLost> {
+        self.channel.device_lost()
+    }
+
+    /// Request a new device to replace the [lost](Self::device_lost) one, returning the error of
+    /// the lost device when the server can't recover.
+    ///
+    /// Every handle created before the loss is invalid afterward and the kernels are compiled
+    /// again, so the inputs must be uploaded again. Nothing happens when the device isn't lost.
+    pub fn recover(&self) -> Result<(), DeviceLost> {
+        self.channel.recover()?;
+        // The launches of the lost device never complete.
+        self.state.pending.lock().clear();
+        Ok(())
+    }
+
+    fn check_device(&self) -> Result<(), DeviceLost> {
+        if let Some(timeout) = self.state.timed_out.lock().take() {
+            return Err(timeout);
+        }
+        match self.channel.device_lost() {
+            Some(lost) => Err(lost),
+            None => Ok(()),
+        }
     }
 
     /// Get the features supported by the compute server.
@@ -123,11 +974,40 @@ where
         &self.state.properties
     }
 
+    /// Run the validation of a kernel, identified by the hash of its id, unless it already
+    /// succeeded on the device. Validations can then be as slow as compiling the kernel, since
+    /// they only run on its first launch.
+    pub fn validate_once<E>(
+        &self,
+        kernel: u64,
+        validate: impl FnOnce() -> Result<(), E>,
+    ) -> Result<(), E> {
+        if self.state.validated.lock().contains(&kernel) {
+            return Ok(());
+        }
+
+        validate()?;
+        self.state.validated.lock().insert(kernel);
+
+        Ok(())
+    }
+
     /// Get the current memory usage of this client.
     pub fn memory_usage(&self) -> MemoryUsage {
         self.channel.memory_usage()
     }
 
+    /// Get the memory usage of each [tag](Self::with_memory_tag) of this client and its clones.
+    pub fn memory_tags(&self) -> BTreeMap<String, MemoryTagUsage> {
+        self.state.memory_tags.usages()
+    }
+
+    /// Restart the tracking of the peak usage of the [tags](Self::with_memory_tag) from their
+    /// current usage, e.g. to measure each step of a training loop separately.
+    pub fn reset_memory_peaks(&self) {
+        self.state.memory_tags.reset_peaks()
+    }
+
     /// When executing operation within the profile scope, you can call
     /// [sync_elapsed](Self::sync_elapsed) safely even in multithreaded workloads.
     /// Creates a profiling scope that enables safe timing measurements in concurrent contexts.
@@ -181,3 +1061,11 @@ where
         self.channel.enable_timestamps();
     }
 }
+
+/// Log the launches dropped by a [cancelled](ComputeClient::with_cancellation) client, which are
+/// counted by its [token](CancellationToken::dropped_launches).
+fn log_cancelled(result: Result<(), Cancelled>) {
+    if let Err(cancelled) = result {
+        log::debug!("{cancelled}");
+    }
+}
@@ -7,11 +7,45 @@ use std::collections::BTreeSet;
 pub struct DeviceProperties<Feature: Ord + Copy> {
     set: alloc::collections::BTreeSet<Feature>,
     memory: MemoryDeviceProperties,
+    hardware: HardwareProperties,
+}
+
+/// Limits of the device, as reported by the graphics API.
+///
+/// Kernel authors and autotune can use them to size tiles and cubes correctly per device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardwareProperties {
+    /// The maximal size of the shared memory of a cube, in bytes.
+    pub max_shared_memory_size: usize,
+    /// The maximal number of units in each dimension of a cube.
+    pub max_cube_dim: (u32, u32, u32),
+    /// The maximal number of units in a cube.
+    pub max_units_per_cube: u32,
+    /// The maximal number of cubes in each dimension of a dispatch.
+    pub max_cube_count: (u32, u32, u32),
+    /// The maximal number of buffers that can be bound to a single kernel.
+    pub max_bindings: u32,
+    /// The maximal size of a single buffer binding, in bytes.
+    pub max_buffer_size: u64,
+    /// The number of units in a subcube, when the device has a fixed one.
+    pub subcube_size: Option<u32>,
+    /// The number of multiprocessors of the device, when known.
+    pub num_multiprocessors: Option<u32>,
+    /// The maximal number of units resident on a multiprocessor at once, when known.
+    pub max_units_per_multiprocessor: Option<u32>,
+    /// The maximal number of cubes resident on a multiprocessor at once, when known.
+    pub max_cubes_per_multiprocessor: Option<u32>,
+    /// The number of registers of a multiprocessor, shared by its resident units, when known.
+    pub registers_per_multiprocessor: Option<u32>,
 }
 
 impl<Feature: Ord + Copy> DeviceProperties<Feature> {
-    /// Create a new feature set with the given features and memory properties.
-    pub fn new(features: &[Feature], memory_props: MemoryDeviceProperties) -> Self {
+    /// Create a new feature set with the given features, memory and hardware properties.
+    pub fn new(
+        features: &[Feature],
+        memory_props: MemoryDeviceProperties,
+        hardware_props: HardwareProperties,
+    ) -> Self {
         let mut set = BTreeSet::new();
         for feature in features {
             set.insert(*feature);
@@ -20,6 +54,7 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
         DeviceProperties {
             set,
             memory: memory_props,
+            hardware: hardware_props,
         }
     }
 
@@ -28,6 +63,11 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
         self.set.contains(&feature)
     }
 
+    /// The [features](Feature) supported by the runtime, in order.
+    pub fn features(&self) -> impl Iterator<Item = &Feature> {
+        self.set.iter()
+    }
+
     /// Register a [feature](Feature) supported by the compute server.
     ///
     /// This should only be used by a [runtime](Runtime) when initializing a device.
@@ -39,4 +79,9 @@ impl<Feature: Ord + Copy> DeviceProperties<Feature> {
     pub fn memory_properties(&self) -> &MemoryDeviceProperties {
         &self.memory
     }
+
+    /// The hardware properties of this client.
+    pub fn hardware_properties(&self) -> &HardwareProperties {
+        &self.hardware
+    }
 }
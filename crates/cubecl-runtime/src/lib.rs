@@ -10,6 +10,8 @@ extern crate derive_new;
 
 mod id;
 
+/// Cooperative cancellation of kernel launches.
+pub mod cancellation;
 /// Compute channel module.
 pub mod channel;
 /// Compute client module.
@@ -26,6 +28,8 @@ pub mod server;
 pub mod storage;
 
 mod feature_set;
+#[cfg(watchdog)]
+mod watchdog;
 
 mod base;
 pub use base::*;
@@ -34,3 +38,9 @@ pub use cubecl_common::benchmark;
 pub use feature_set::*;
 /// Debugging utilities.
 pub mod debug;
+/// Kernel sources overridden during development.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+pub mod overrides;
+#[cfg(remote)]
+pub mod remote;
+pub mod trace;
@@ -1,6 +1,7 @@
 /// Amount of memory in use by this allocator
 /// and statistics on how much memory is reserved and
 /// wasted in total.
+#[derive(Debug, Clone)]
 pub struct MemoryUsage {
     /// The number of allocations currently active.
     pub number_allocs: u64,
@@ -75,6 +76,38 @@ impl std::fmt::Display for MemoryUsage {
     }
 }
 
+/// Error returned when an allocation fails because the device is out of memory, after every
+/// [hook](OutOfMemoryHook) failed to recover from it.
+#[derive(Debug, Clone)]
+pub struct OutOfMemoryError {
+    /// The size of the allocation in bytes.
+    pub requested: u64,
+    /// The memory usage of the pools when the allocation failed.
+    pub usage: MemoryUsage,
+}
+
+impl std::fmt::Display for OutOfMemoryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(
+            f,
+            "Out of memory when allocating {}.",
+            bytes_format(self.requested)
+        )?;
+        write!(f, "{}", self.usage)
+    }
+}
+
+impl std::error::Error for OutOfMemoryError {}
+
+/// Hook called by the [memory management](super::MemoryManagement::on_out_of_memory) when an
+/// allocation fails because the device is out of memory.
+///
+/// The hook runs in the server and can release the memory it owns, for example by clearing a
+/// cache, and returns whether it did so the allocation is retried. Spilling resources to the host
+/// is done with the [hooks of the client](crate::client::ComputeClient::on_out_of_memory), which
+/// can read them.
+pub type OutOfMemoryHook = Box<dyn FnMut(&OutOfMemoryError) -> bool + Send>;
+
 /// The managed tensor buffer handle that points to some memory segment.
 /// It should not contain actual data.
 pub trait MemoryHandle<Binding>: Clone + Send + Sync + core::fmt::Debug {
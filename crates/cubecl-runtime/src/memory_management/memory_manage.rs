@@ -1,12 +1,16 @@
 use std::collections::BTreeSet;
 
 use super::{
-    memory_pool::{ExclusiveMemoryPool, MemoryPool, SliceBinding, SliceHandle, SlicedPool},
+    memory_pool::{
+        ExclusiveMemoryPool, GrowablePool, MemoryPool, SliceBinding, SliceHandle, SlicedPool,
+    },
     MemoryConfiguration, MemoryDeviceProperties, MemoryLock, MemoryPoolOptions, MemoryUsage,
-    PoolType,
+    OutOfMemoryError, OutOfMemoryHook, PoolType,
 };
 use crate::storage::{ComputeStorage, StorageHandle};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
 
 enum DynamicPool {
     Sliced(SlicedPool),
@@ -54,14 +58,18 @@ impl MemoryPool for DynamicPool {
         storage: &mut Storage,
         size: u64,
         locked: Option<&MemoryLock>,
-    ) -> SliceHandle {
+    ) -> Option<SliceHandle> {
         match self {
             DynamicPool::Sliced(m) => m.reserve(storage, size, locked),
             DynamicPool::Exclusive(m) => m.reserve(storage, size, locked),
         }
     }
 
-    fn alloc<Storage: ComputeStorage>(&mut self, storage: &mut Storage, size: u64) -> SliceHandle {
+    fn alloc<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Option<SliceHandle> {
         match self {
             DynamicPool::Sliced(m) => m.alloc(storage, size),
             DynamicPool::Exclusive(m) => m.alloc(storage, size),
@@ -87,20 +95,85 @@ impl MemoryPool for DynamicPool {
             DynamicPool::Exclusive(m) => m.cleanup(storage, alloc_nr),
         }
     }
+
+    fn release_free<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        match self {
+            DynamicPool::Sliced(m) => m.release_free(storage),
+            DynamicPool::Exclusive(m) => m.release_free(storage),
+        }
+    }
 }
 
 /// Reserves and keeps track of chunks of memory in the storage, and slices upon these chunks.
+///
+/// The pools can be [shared](Self::into_shared) by the servers of the same device, so memory
+/// released by one server is reused by the others instead of each reserving its own pages.
 pub struct MemoryManagement<Storage> {
+    state: State<Storage>,
+}
+
+enum State<Storage> {
+    Owned(MemoryState<Storage>),
+    Shared(Arc<spin::Mutex<MemoryState<Storage>>>),
+}
+
+/// The state of the pools, locked when the state is shared.
+enum StateGuard<'a, Storage> {
+    Owned(&'a mut MemoryState<Storage>),
+    Shared(spin::MutexGuard<'a, MemoryState<Storage>>),
+}
+
+impl<Storage> Deref for StateGuard<'_, Storage> {
+    type Target = MemoryState<Storage>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            StateGuard::Owned(state) => state,
+            StateGuard::Shared(state) => state,
+        }
+    }
+}
+
+impl<Storage> DerefMut for StateGuard<'_, Storage> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            StateGuard::Owned(state) => state,
+            StateGuard::Shared(state) => state,
+        }
+    }
+}
+
+/// The [storage](MemoryManagement::storage) of the memory management, locked while it is
+/// borrowed when the pools are shared.
+pub struct StorageGuard<'a, Storage>(StateGuard<'a, Storage>);
+
+impl<Storage> Deref for StorageGuard<'_, Storage> {
+    type Target = Storage;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.storage
+    }
+}
+
+impl<Storage> DerefMut for StorageGuard<'_, Storage> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0.storage
+    }
+}
+
+struct MemoryState<Storage> {
     pools: Vec<DynamicPool>,
+    growable: GrowablePool,
     storage: Storage,
     alloc_reserve_count: u64,
+    out_of_memory_hooks: Vec<OutOfMemoryHook>,
 }
 
 fn round_up_to_multiple(value: u64, multiple: u64) -> u64 {
     ((value + multiple - 1) / multiple) * multiple
 }
 
-impl<Storage: ComputeStorage> MemoryManagement<Storage> {
+impl<Storage: ComputeStorage> MemoryState<Storage> {
     /// Creates the options from device limits.
     pub fn from_configuration(
         storage: Storage,
@@ -225,7 +298,8 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                 };
 
                 for _ in 0..options.chunk_num_prealloc {
-                    pool.alloc(&mut storage, options.page_size);
+                    pool.alloc(&mut storage, options.page_size)
+                        .expect("Not enough memory to preallocate the pool pages");
                 }
 
                 pool
@@ -236,8 +310,10 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
 
         Self {
             pools,
+            growable: GrowablePool::new(),
             storage,
             alloc_reserve_count: 0,
+            out_of_memory_hooks: Vec::new(),
         }
     }
 
@@ -246,6 +322,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         for pool in self.pools.iter_mut() {
             pool.cleanup(&mut self.storage, self.alloc_reserve_count);
         }
+        self.growable.cleanup(&mut self.storage);
     }
 
     /// Returns the storage from the specified binding
@@ -253,6 +330,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         self.pools
             .iter()
             .find_map(|p| p.get(&binding))
+            .or_else(|| self.growable.get(&binding))
             .expect("No handle found in memory pools")
             .clone()
     }
@@ -273,22 +351,120 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             Some(offset) => handle.offset_end(offset),
             None => handle,
         };
-        self.storage().get(&handle)
+        self.storage.get(&handle)
     }
 
     /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
+    ///
+    /// # Panics
+    ///
+    /// When the device is out of memory, see [try_reserve](Self::try_reserve).
     pub fn reserve(&mut self, size: u64, exclude: Option<&MemoryLock>) -> SliceHandle {
+        self.try_reserve(size, exclude)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to
+    /// it.
+    ///
+    /// When the storage is out of memory, the pages that aren't used are deallocated and the
+    /// allocation is retried. Then each [hook](Self::on_out_of_memory) is called in the order they
+    /// were registered, and the allocation is retried after every hook that released memory. The
+    /// error is returned once all of them failed.
+    pub fn try_reserve(
+        &mut self,
+        size: u64,
+        exclude: Option<&MemoryLock>,
+    ) -> Result<SliceHandle, OutOfMemoryError> {
         // If this happens every nanosecond, counts overflows after 585 years, so not worth thinking too
         // hard about overflow here.
         self.alloc_reserve_count += 1;
 
         // Find first pool where size <= p.max_alloc with a binary search.
         let pool_ind = self.pools.partition_point(|p| size > p.max_alloc_size());
-        let pool = &mut self.pools[pool_ind];
-        if pool.max_alloc_size() < size {
-            panic!("No memory pool big enough to reserve {size} bytes.");
+        if pool_ind == self.pools.len() {
+            log::warn!("No memory pool big enough to reserve {size} bytes.");
+            return Err(self.out_of_memory_error(size));
         }
-        pool.reserve(&mut self.storage, size, exclude)
+
+        self.recover(size, |this| {
+            this.pools[pool_ind].reserve(&mut this.storage, size, exclude)
+        })
+    }
+
+    /// Register a hook called when an allocation fails because the device is out of memory.
+    ///
+    /// Hooks are called in the order they were registered, see
+    /// [try_reserve](Self::try_reserve).
+    pub fn on_out_of_memory(&mut self, hook: OutOfMemoryHook) {
+        self.out_of_memory_hooks.push(hook);
+    }
+
+    /// Calls `allocate` until it succeeds, releasing memory in between.
+    fn recover(
+        &mut self,
+        size: u64,
+        mut allocate: impl FnMut(&mut Self) -> Option<SliceHandle>,
+    ) -> Result<SliceHandle, OutOfMemoryError> {
+        if let Some(handle) = allocate(self) {
+            return Ok(handle);
+        }
+
+        self.release_free();
+        if let Some(handle) = allocate(self) {
+            return Ok(handle);
+        }
+
+        // Hooks are taken out while running, so they can't be called recursively.
+        let mut hooks = core::mem::take(&mut self.out_of_memory_hooks);
+        let mut handle = None;
+        for hook in hooks.iter_mut() {
+            if !hook(&self.out_of_memory_error(size)) {
+                continue;
+            }
+
+            self.release_free();
+            handle = allocate(self);
+            if handle.is_some() {
+                break;
+            }
+        }
+        hooks.append(&mut self.out_of_memory_hooks);
+        self.out_of_memory_hooks = hooks;
+
+        handle.ok_or_else(|| self.out_of_memory_error(size))
+    }
+
+    /// Deallocates the memory that isn't used anymore.
+    fn release_free(&mut self) {
+        for pool in self.pools.iter_mut() {
+            pool.release_free(&mut self.storage);
+        }
+        self.growable.cleanup(&mut self.storage);
+    }
+
+    fn out_of_memory_error(&self, size: u64) -> OutOfMemoryError {
+        OutOfMemoryError {
+            requested: size,
+            usage: self.memory_usage(),
+        }
+    }
+
+    /// Allocates `size` bytes of storage that can [grow](MemoryManagement::grow) in place, or
+    /// returns `None` when the storage doesn't support growable allocations.
+    ///
+    /// Growable allocations aren't part of the pools, and their storage is deallocated on the
+    /// next [cleanup](MemoryManagement::cleanup) after their handle is dropped.
+    pub fn reserve_growable(&mut self, size: u64) -> Option<SliceHandle> {
+        self.growable.reserve(&mut self.storage, size)
+    }
+
+    /// Grows the allocation of the binding to `size` bytes in place, keeping its content.
+    ///
+    /// Returns false when the binding isn't a [growable allocation](Self::reserve_growable) or
+    /// when the storage can't grow it in place.
+    pub fn grow(&mut self, binding: SliceBinding, size: u64) -> bool {
+        self.growable.grow(&mut self.storage, &binding, size)
     }
 
     /// Bypass the memory allocation algorithm to allocate data directly.
@@ -299,11 +475,13 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
     pub fn alloc(&mut self, size: u64) -> SliceHandle {
         // Find first pool where size <= p.max_alloc with a binary search.
         let pool_ind = self.pools.partition_point(|p| size > p.max_alloc_size());
-        let pool = &mut self.pools[pool_ind];
-        if pool.max_alloc_size() < size {
+        if pool_ind == self.pools.len() {
             panic!("No memory pool big enough to alloc {size} bytes.");
         }
-        pool.alloc(&mut self.storage, size)
+        self.recover(size, |this| {
+            this.pools[pool_ind].alloc(&mut this.storage, size)
+        })
+        .unwrap_or_else(|err| panic!("{err}"))
     }
 
     /// Bypass the memory allocation algorithm to deallocate data directly.
@@ -315,7 +493,164 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         // Can't dealloc slices.
     }
 
-    /// Fetch the storage used by the memory manager.
+    /// Get the current memory usage.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.pools
+            .iter()
+            .map(|x| x.get_memory_usage())
+            .chain([self.growable.get_memory_usage()])
+            .fold(
+                MemoryUsage {
+                    number_allocs: 0,
+                    bytes_in_use: 0,
+                    bytes_padding: 0,
+                    bytes_reserved: 0,
+                },
+                |m1, m2| m1.combine(m2),
+            )
+    }
+}
+
+impl<Storage: ComputeStorage> MemoryManagement<Storage> {
+    /// Creates the options from device limits.
+    pub fn from_configuration(
+        storage: Storage,
+        properties: MemoryDeviceProperties,
+        config: MemoryConfiguration,
+    ) -> Self {
+        Self {
+            state: State::Owned(MemoryState::from_configuration(storage, properties, config)),
+        }
+    }
+
+    /// Creates a new instance using the given storage, merging_strategy strategy and slice strategy.
+    pub fn new(storage: Storage, pools: Vec<MemoryPoolOptions>, memory_alignment: u64) -> Self {
+        Self {
+            state: State::Owned(MemoryState::new(storage, pools, memory_alignment)),
+        }
+    }
+
+    /// Share the pools, so the memory management can be [shared](Self::share) with the other
+    /// servers of the same device.
+    ///
+    /// The servers sharing the pools must execute their work in the order of their allocations,
+    /// for instance on the same queue, since a slice released by one server can be reserved by
+    /// another one right away.
+    pub fn into_shared(self) -> Self {
+        match self.state {
+            State::Owned(state) => Self {
+                state: State::Shared(Arc::new(spin::Mutex::new(state))),
+            },
+            State::Shared(_) => self,
+        }
+    }
+
+    /// Whether the pools are [shared](Self::into_shared).
+    pub fn is_shared(&self) -> bool {
+        matches!(self.state, State::Shared(_))
+    }
+
+    /// Another memory management over the same pools and storage, or `None` when the pools
+    /// aren't [shared](Self::into_shared).
+    pub fn share(&self) -> Option<Self> {
+        match &self.state {
+            State::Owned(_) => None,
+            State::Shared(state) => Some(Self {
+                state: State::Shared(state.clone()),
+            }),
+        }
+    }
+
+    /// Cleanup allocations in pools that are deemed unnecessary.
+    pub fn cleanup(&mut self) {
+        self.state().cleanup()
+    }
+
+    /// Returns the storage from the specified binding
+    pub fn get(&mut self, binding: SliceBinding) -> StorageHandle {
+        self.state().get(binding)
+    }
+
+    /// Returns the resource from the storage at the specified handle
+    pub fn get_resource(
+        &mut self,
+        binding: SliceBinding,
+        offset_start: Option<u64>,
+        offset_end: Option<u64>,
+    ) -> Storage::Resource {
+        self.state().get_resource(binding, offset_start, offset_end)
+    }
+
+    /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
+    ///
+    /// # Panics
+    ///
+    /// When the device is out of memory, see [try_reserve](Self::try_reserve).
+    pub fn reserve(&mut self, size: u64, exclude: Option<&MemoryLock>) -> SliceHandle {
+        self.state().reserve(size, exclude)
+    }
+
+    /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to
+    /// it.
+    ///
+    /// When the storage is out of memory, the pages that aren't used are deallocated and the
+    /// allocation is retried. Then each [hook](Self::on_out_of_memory) is called in the order they
+    /// were registered, and the allocation is retried after every hook that released memory. The
+    /// error is returned once all of them failed.
+    pub fn try_reserve(
+        &mut self,
+        size: u64,
+        exclude: Option<&MemoryLock>,
+    ) -> Result<SliceHandle, OutOfMemoryError> {
+        self.state().try_reserve(size, exclude)
+    }
+
+    /// Register a hook called when an allocation fails because the device is out of memory.
+    ///
+    /// Hooks are called in the order they were registered, see
+    /// [try_reserve](Self::try_reserve). The hooks of shared pools are called on the allocations
+    /// of every server sharing them.
+    pub fn on_out_of_memory(&mut self, hook: OutOfMemoryHook) {
+        self.state().on_out_of_memory(hook)
+    }
+
+    /// Allocates `size` bytes of storage that can [grow](MemoryManagement::grow) in place, or
+    /// returns `None` when the storage doesn't support growable allocations.
+    ///
+    /// Growable allocations aren't part of the pools, and their storage is deallocated on the
+    /// next [cleanup](MemoryManagement::cleanup) after their handle is dropped.
+    pub fn reserve_growable(&mut self, size: u64) -> Option<SliceHandle> {
+        self.state().reserve_growable(size)
+    }
+
+    /// Grows the allocation of the binding to `size` bytes in place, keeping its content.
+    ///
+    /// Returns false when the binding isn't a [growable allocation](Self::reserve_growable) or
+    /// when the storage can't grow it in place.
+    pub fn grow(&mut self, binding: SliceBinding, size: u64) -> bool {
+        self.state().grow(binding, size)
+    }
+
+    /// Bypass the memory allocation algorithm to allocate data directly.
+    ///
+    /// # Notes
+    ///
+    /// Can be useful for servers that want specific control over memory.
+    pub fn alloc(&mut self, size: u64) -> SliceHandle {
+        self.state().alloc(size)
+    }
+
+    /// Bypass the memory allocation algorithm to deallocate data directly.
+    ///
+    /// # Notes
+    ///
+    /// Can be useful for servers that want specific control over memory.
+    pub fn dealloc(&mut self, binding: SliceBinding) {
+        self.state().dealloc(binding)
+    }
+
+    /// Fetch the storage used by the memory manager, locked until the guard is dropped when the
+    /// pools are [shared](Self::into_shared).
     ///
     /// # Notes
     ///
@@ -325,27 +660,30 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
     ///
     /// This is useful if you need to time the deallocations based on async computation, or to
     /// change the mode of storage for different reasons.
-    pub fn storage(&mut self) -> &mut Storage {
-        &mut self.storage
+    pub fn storage(&mut self) -> StorageGuard<'_, Storage> {
+        StorageGuard(self.state())
     }
 
-    /// Get the current memory usage.
+    /// Get the current memory usage, of every server sharing the pools when they are
+    /// [shared](Self::into_shared).
     pub fn memory_usage(&self) -> MemoryUsage {
-        self.pools.iter().map(|x| x.get_memory_usage()).fold(
-            MemoryUsage {
-                number_allocs: 0,
-                bytes_in_use: 0,
-                bytes_padding: 0,
-                bytes_reserved: 0,
-            },
-            |m1, m2| m1.combine(m2),
-        )
+        match &self.state {
+            State::Owned(state) => state.memory_usage(),
+            State::Shared(state) => state.lock().memory_usage(),
+        }
     }
 
     /// Print out a report of the current memory usage.
     pub fn print_memory_usage(&self) {
         log::info!("{}", self.memory_usage());
     }
+
+    fn state(&mut self) -> StateGuard<'_, Storage> {
+        match &mut self.state {
+            State::Owned(state) => StateGuard::Owned(state),
+            State::Shared(state) => StateGuard::Shared(state.lock()),
+        }
+    }
 }
 
 impl<Storage> core::fmt::Debug for MemoryManagement<Storage> {
@@ -363,9 +701,146 @@ impl<Storage> core::fmt::Debug for MemoryManagement<Storage> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{memory_management::MemoryManagement, storage::BytesStorage};
+    use crate::{
+        memory_management::MemoryManagement,
+        storage::{BytesStorage, StorageId},
+    };
+    use alloc::sync::Arc;
+    use hashbrown::HashMap;
+    use std::sync::Mutex;
+
+    /// Bytes storage running out of memory after `capacity` bytes.
+    #[derive(Default)]
+    struct LimitedStorage {
+        storage: BytesStorage,
+        capacity: u64,
+        allocations: HashMap<StorageId, u64>,
+    }
+
+    impl LimitedStorage {
+        fn new(capacity: u64) -> Self {
+            Self {
+                capacity,
+                ..Default::default()
+            }
+        }
+    }
+
+    impl ComputeStorage for LimitedStorage {
+        type Resource = <BytesStorage as ComputeStorage>::Resource;
+        const ALIGNMENT: u64 = BytesStorage::ALIGNMENT;
+
+        fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+            self.storage.get(handle)
+        }
+
+        fn alloc(&mut self, size: u64) -> StorageHandle {
+            self.try_alloc(size).expect("Out of memory")
+        }
+
+        fn try_alloc(&mut self, size: u64) -> Option<StorageHandle> {
+            if self.allocations.values().sum::<u64>() + size > self.capacity {
+                return None;
+            }
+            let handle = self.storage.alloc(size);
+            self.allocations.insert(handle.id, size);
+            Some(handle)
+        }
+
+        fn dealloc(&mut self, id: StorageId) {
+            self.allocations.remove(&id);
+            self.storage.dealloc(id);
+        }
+
+        // Growable allocations are regular ones that can't grow in place.
+        fn alloc_growable(&mut self, size: u64) -> Option<StorageHandle> {
+            self.try_alloc(size)
+        }
+    }
+
+    fn exclusive_pools(page_sizes: &[u64]) -> Vec<MemoryPoolOptions> {
+        page_sizes
+            .iter()
+            .map(|&page_size| MemoryPoolOptions {
+                page_size,
+                chunk_num_prealloc: 0,
+                pool_type: PoolType::ExclusivePages,
+                dealloc_period: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn out_of_memory_error_has_the_requested_size_and_usage() {
+        let mut memory_management =
+            MemoryManagement::new(LimitedStorage::new(1024), exclusive_pools(&[1024]), 32);
+
+        let _handle = memory_management.reserve(512, None);
+        let error = memory_management.try_reserve(256, None).unwrap_err();
+
+        assert_eq!(error.requested, 256);
+        assert_eq!(error.usage.number_allocs, 1);
+        assert_eq!(error.usage.bytes_in_use, 512);
+    }
+
+    #[test]
+    fn out_of_memory_releases_free_pages_before_failing() {
+        let mut memory_management =
+            MemoryManagement::new(LimitedStorage::new(1024), exclusive_pools(&[512, 1024]), 32);
+
+        let handle = memory_management.reserve(512, None);
+        drop(handle);
+
+        assert!(memory_management.try_reserve(1024, None).is_ok());
+    }
+
+    #[test]
+    fn out_of_memory_hook_releases_memory_before_retrying() {
+        let mut memory_management =
+            MemoryManagement::new(LimitedStorage::new(1024), exclusive_pools(&[1024]), 32);
+        let cache = Arc::new(Mutex::new(Some(memory_management.reserve(1024, None))));
+
+        let cache_hook = cache.clone();
+        memory_management.on_out_of_memory(Box::new(|_| false));
+        memory_management.on_out_of_memory(Box::new(move |error| {
+            assert_eq!(error.requested, 512);
+            cache_hook.lock().unwrap().take().is_some()
+        }));
+
+        assert!(memory_management.try_reserve(512, None).is_ok());
+        assert!(cache.lock().unwrap().is_none());
+    }
 
     // Test pools with slices.
+    #[test]
+    fn shared_pools_reuse_the_memory_released_by_another_server() {
+        let mut first =
+            MemoryManagement::new(LimitedStorage::new(1024), exclusive_pools(&[1024]), 32)
+                .into_shared();
+        let mut second = first.share().unwrap();
+
+        let handle = first.reserve(1024, None);
+        assert!(second.try_reserve(512, None).is_err());
+        drop(handle);
+        let handle = second.reserve(512, None);
+
+        assert_eq!(first.memory_usage().bytes_in_use, 512);
+        assert_eq!(first.memory_usage().bytes_reserved, 1024);
+        assert_eq!(
+            first.get(handle.clone().binding()).id,
+            second.get(handle.binding()).id
+        );
+    }
+
+    #[test]
+    fn owned_pools_are_not_shared() {
+        let memory_management = MemoryManagement::new(BytesStorage::default(), vec![], 32);
+
+        assert!(!memory_management.is_shared());
+        assert!(memory_management.share().is_none());
+        assert!(memory_management.into_shared().is_shared());
+    }
+
     #[test]
     fn test_handle_mutability() {
         let mut memory_management = MemoryManagement::from_configuration(
@@ -383,6 +858,41 @@ mod tests {
         assert!(handle.can_mut(), "Handle should be mut when only one ref.");
     }
 
+    #[test]
+    fn growable_alloc_is_none_without_storage_support() {
+        let mut memory_management = MemoryManagement::new(BytesStorage::default(), vec![], 32);
+
+        assert!(memory_management.reserve_growable(512).is_none());
+    }
+
+    #[test]
+    fn growable_alloc_does_not_grow_without_storage_support() {
+        let mut memory_management = MemoryManagement::new(LimitedStorage::new(2048), vec![], 32);
+
+        let handle = memory_management.reserve_growable(512).unwrap();
+
+        assert!(memory_management.grow(handle.clone().binding(), 256));
+        assert!(!memory_management.grow(handle.binding(), 1024));
+    }
+
+    #[test]
+    fn pool_alloc_does_not_grow() {
+        let mut memory_management = MemoryManagement::new(
+            BytesStorage::default(),
+            vec![MemoryPoolOptions {
+                page_size: 1024,
+                chunk_num_prealloc: 0,
+                pool_type: PoolType::ExclusivePages,
+                dealloc_period: None,
+            }],
+            32,
+        );
+
+        let handle = memory_management.reserve(512, None);
+
+        assert!(!memory_management.grow(handle.binding(), 1024));
+    }
+
     #[test]
     fn alloc_two_chunks_on_one_page() {
         let page_size = 2048;
@@ -40,16 +40,25 @@ pub trait MemoryPool {
 
     fn get(&self, binding: &SliceBinding) -> Option<&StorageHandle>;
 
+    /// Returns `None` when the storage is out of memory.
     fn reserve<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
         size: u64,
         locked: Option<&MemoryLock>,
-    ) -> SliceHandle;
+    ) -> Option<SliceHandle>;
 
-    fn alloc<Storage: ComputeStorage>(&mut self, storage: &mut Storage, size: u64) -> SliceHandle;
+    /// Returns `None` when the storage is out of memory.
+    fn alloc<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Option<SliceHandle>;
 
     fn get_memory_usage(&self) -> MemoryUsage;
 
     fn cleanup<Storage: ComputeStorage>(&mut self, storage: &mut Storage, alloc_nr: u64);
+
+    /// Deallocates every page that isn't used, to recover from an allocation failure.
+    fn release_free<Storage: ComputeStorage>(&mut self, storage: &mut Storage);
 }
@@ -82,12 +82,12 @@ impl MemoryPool for ExclusiveMemoryPool {
         storage: &mut Storage,
         size: u64,
         exclude: Option<&MemoryLock>,
-    ) -> SliceHandle {
+    ) -> Option<SliceHandle> {
         let page = self.get_free_page(exclude);
         let slice_id = if let Some(page) = page {
             page
         } else {
-            *self.alloc(storage, self.max_page_size).id()
+            *self.alloc(storage, self.max_page_size)?.id()
         };
 
         let padding = calculate_padding(size, self.alignment);
@@ -96,11 +96,15 @@ impl MemoryPool for ExclusiveMemoryPool {
         // get a page with a size > size, so this is ok to do.
         slice.storage.utilization = StorageUtilization { offset: 0, size };
         slice.padding = padding;
-        slice.handle.clone()
+        Some(slice.handle.clone())
     }
 
-    fn alloc<Storage: ComputeStorage>(&mut self, storage: &mut Storage, size: u64) -> SliceHandle {
-        let storage = storage.alloc(size);
+    fn alloc<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Option<SliceHandle> {
+        let storage = storage.try_alloc(size)?;
         self.ring_buffer.push(storage.id);
 
         let handle = SliceHandle::new();
@@ -117,7 +121,7 @@ impl MemoryPool for ExclusiveMemoryPool {
             },
         );
         self.slices.insert(slice_id, slice);
-        handle_slice
+        Some(handle_slice)
     }
 
     fn get_memory_usage(&self) -> MemoryUsage {
@@ -168,6 +172,27 @@ impl MemoryPool for ExclusiveMemoryPool {
             })
             .collect();
 
+        self.dealloc_pages(storage, deallocations);
+    }
+
+    fn release_free<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        let deallocations: HashSet<_> = self
+            .pages
+            .iter()
+            .filter(|(_, page)| self.slices[&page.slice_id].is_free())
+            .map(|(storage_id, _)| *storage_id)
+            .collect();
+
+        self.dealloc_pages(storage, deallocations);
+    }
+}
+
+impl ExclusiveMemoryPool {
+    fn dealloc_pages<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        deallocations: HashSet<StorageId>,
+    ) {
         // Perform any deallocations if necessary.
         if !deallocations.is_empty() {
             for storage_id in deallocations.iter() {
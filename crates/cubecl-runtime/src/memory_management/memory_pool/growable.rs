@@ -0,0 +1,83 @@
+use super::{Slice, SliceBinding, SliceHandle, SliceId};
+use crate::{
+    memory_management::MemoryUsage,
+    storage::{ComputeStorage, StorageHandle},
+};
+use hashbrown::HashMap;
+
+/// A pool of allocations that each own their storage, and grow in place instead of being
+/// reallocated.
+///
+/// The allocations aren't reused, their storage is deallocated once their handle is dropped.
+pub(crate) struct GrowablePool {
+    slices: HashMap<SliceId, Slice>,
+}
+
+impl GrowablePool {
+    pub(crate) fn new() -> Self {
+        Self {
+            slices: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn get(&self, binding: &SliceBinding) -> Option<&StorageHandle> {
+        self.slices.get(binding.id()).map(|slice| &slice.storage)
+    }
+
+    /// Allocates `size` bytes of growable storage, or returns `None` when the storage doesn't
+    /// support it.
+    pub(crate) fn reserve<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Option<SliceHandle> {
+        let storage = storage.alloc_growable(size)?;
+        let slice = Slice::new(storage, SliceHandle::new(), 0);
+        let handle = slice.handle.clone();
+        self.slices.insert(slice.id(), slice);
+
+        Some(handle)
+    }
+
+    /// Grows the allocation of the binding to `size` bytes, returning false when it isn't part of
+    /// the pool or when the storage can't grow it in place.
+    pub(crate) fn grow<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        binding: &SliceBinding,
+        size: u64,
+    ) -> bool {
+        let Some(slice) = self.slices.get_mut(binding.id()) else {
+            return false;
+        };
+
+        if size > slice.storage.size() && !storage.grow(slice.storage.id, size) {
+            return false;
+        }
+        slice.storage.utilization.size = size;
+
+        true
+    }
+
+    pub(crate) fn get_memory_usage(&self) -> MemoryUsage {
+        let size = self.slices.values().map(|slice| slice.storage.size()).sum();
+
+        MemoryUsage {
+            number_allocs: self.slices.len() as u64,
+            bytes_in_use: size,
+            bytes_padding: 0,
+            bytes_reserved: size,
+        }
+    }
+
+    /// Deallocates the storage of the allocations that aren't used anymore.
+    pub(crate) fn cleanup<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        self.slices.retain(|_, slice| {
+            if slice.is_free() {
+                storage.dealloc(slice.storage.id);
+                return false;
+            }
+            true
+        });
+    }
+}
@@ -3,11 +3,13 @@ mod ring;
 
 mod base;
 mod exclusive_pool;
+mod growable;
 mod handle;
 mod sliced_pool;
 
 pub(crate) use base::*;
 pub(crate) use exclusive_pool::*;
+pub(crate) use growable::*;
 pub(crate) use handle::*;
 pub(crate) use ring::*;
 pub(crate) use sliced_pool::*;
@@ -99,17 +99,21 @@ impl MemoryPool for SlicedPool {
         storage: &mut Storage,
         size: u64,
         locked: Option<&MemoryLock>,
-    ) -> SliceHandle {
+    ) -> Option<SliceHandle> {
         let slice = self.get_free_slice(size, locked);
 
         match slice {
-            Some(slice) => slice.clone(),
+            Some(slice) => Some(slice.clone()),
             None => self.alloc(storage, size),
         }
     }
 
-    fn alloc<Storage: ComputeStorage>(&mut self, storage: &mut Storage, size: u64) -> SliceHandle {
-        let storage_id = self.create_page(storage, self.page_size);
+    fn alloc<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        size: u64,
+    ) -> Option<SliceHandle> {
+        let storage_id = self.create_page(storage, self.page_size)?;
         self.recently_added_pages.push(storage_id);
         self.recently_allocated_size += self.page_size;
 
@@ -139,7 +143,7 @@ impl MemoryPool for SlicedPool {
             page.slices.insert(extra_slice_offset, extra_slice_id);
         }
 
-        handle_slice
+        Some(handle_slice)
     }
 
     fn get_memory_usage(&self) -> MemoryUsage {
@@ -160,6 +164,10 @@ impl MemoryPool for SlicedPool {
     fn cleanup<Storage: ComputeStorage>(&mut self, _storage: &mut Storage, _alloc_nr: u64) {
         // This pool doesn't do any shrinking currently.
     }
+
+    fn release_free<Storage: ComputeStorage>(&mut self, _storage: &mut Storage) {
+        // This pool doesn't do any shrinking currently.
+    }
 }
 
 impl SlicedPool {
@@ -228,8 +236,8 @@ impl SlicedPool {
         &mut self,
         storage: &mut Storage,
         size: u64,
-    ) -> StorageId {
-        let storage = storage.alloc(self.page_size);
+    ) -> Option<StorageId> {
+        let storage = storage.try_alloc(self.page_size)?;
 
         let id = storage.id;
         self.ring.push_page(id);
@@ -237,7 +245,7 @@ impl SlicedPool {
         self.pages.insert(id, MemoryPage::new(HashMap::new()));
         self.storage_index.insert(id, size);
 
-        id
+        Some(id)
     }
 }
 
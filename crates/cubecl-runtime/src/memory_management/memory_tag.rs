@@ -0,0 +1,66 @@
+use alloc::{collections::BTreeMap, string::String, sync::Arc};
+
+/// The memory used by the allocations of a [tag](crate::client::ComputeClient::with_memory_tag).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryTagUsage {
+    /// The bytes of the allocations of the tag whose handles are still alive.
+    pub bytes_in_use: u64,
+    /// The most bytes in use at once since the peaks were last
+    /// [reset](crate::client::ComputeClient::reset_memory_peaks).
+    pub bytes_peak: u64,
+    /// The number of allocations made with the tag.
+    pub allocations: u64,
+}
+
+/// The usage of every memory tag of a client, shared with the handles of their allocations.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryTags {
+    usages: spin::Mutex<BTreeMap<Arc<str>, MemoryTagUsage>>,
+}
+
+impl MemoryTags {
+    /// Record an allocation of `size` bytes, released once the returned value is dropped.
+    pub(crate) fn allocate(self: &Arc<Self>, tag: &Arc<str>, size: u64) -> TaggedAllocation {
+        let mut usages = self.usages.lock();
+        let usage = usages.entry(tag.clone()).or_default();
+        usage.bytes_in_use += size;
+        usage.bytes_peak = usage.bytes_peak.max(usage.bytes_in_use);
+        usage.allocations += 1;
+
+        TaggedAllocation {
+            tags: self.clone(),
+            tag: tag.clone(),
+            size,
+        }
+    }
+
+    pub(crate) fn usages(&self) -> BTreeMap<String, MemoryTagUsage> {
+        self.usages
+            .lock()
+            .iter()
+            .map(|(tag, usage)| (String::from(tag.as_ref()), *usage))
+            .collect()
+    }
+
+    pub(crate) fn reset_peaks(&self) {
+        for usage in self.usages.lock().values_mut() {
+            usage.bytes_peak = usage.bytes_in_use;
+        }
+    }
+}
+
+/// An allocation attributed to a tag until every handle over it is dropped.
+#[derive(Debug)]
+pub(crate) struct TaggedAllocation {
+    tags: Arc<MemoryTags>,
+    tag: Arc<str>,
+    size: u64,
+}
+
+impl Drop for TaggedAllocation {
+    fn drop(&mut self) {
+        if let Some(usage) = self.tags.usages.lock().get_mut(&self.tag) {
+            usage.bytes_in_use -= self.size;
+        }
+    }
+}
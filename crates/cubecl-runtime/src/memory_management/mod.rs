@@ -2,9 +2,12 @@ pub(crate) mod memory_pool;
 
 mod base;
 mod memory_lock;
+mod memory_tag;
 
 pub use base::*;
 pub use memory_lock::*;
+pub use memory_tag::MemoryTagUsage;
+pub(crate) use memory_tag::{MemoryTags, TaggedAllocation};
 
 /// Dynamic memory management strategy.
 mod memory_manage;
@@ -68,6 +71,28 @@ impl Default for MemoryConfiguration {
     }
 }
 
+/// The host memory used to stage the transfers between the host and the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StagingConfiguration {
+    /// Transfers go through pageable memory allocated for each transfer, which the driver copies
+    /// to page-locked memory before the device can access it.
+    Pageable,
+    /// Transfers go through page-locked host memory kept between transfers, allocated in chunks
+    /// of at least `chunk_size` bytes.
+    Pinned {
+        /// The minimum amount of bytes allocated at once.
+        chunk_size: u64,
+    },
+}
+
+impl Default for StagingConfiguration {
+    fn default() -> Self {
+        StagingConfiguration::Pinned {
+            chunk_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
 /// Properties of the device related to allocation.
 #[derive(Debug, Clone)]
 pub struct MemoryDeviceProperties {
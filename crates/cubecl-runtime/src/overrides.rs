@@ -0,0 +1,170 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// The environment variable naming the directory of the overridden kernel sources.
+pub const OVERRIDES_DIR_VAR: &str = "CUBECL_KERNEL_OVERRIDES";
+
+/// How often the overridden sources are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Kernel sources edited by hand during development, loaded from the directory given by the
+/// `CUBECL_KERNEL_OVERRIDES` environment variable.
+///
+/// The source of a kernel is read from `<dir>/<name>.<extension>`, where `name` is the name of
+/// its kernel id, as shown in the debug logs, and `extension` the language of the backend,
+/// such as `wgsl` or `cu`. The generated source of every kernel compiled without an override is
+/// written to `<dir>/<name>.generated.<extension>`, to be copied and edited.
+///
+/// Servers [poll](Self::changed) the overrides and compile again the kernels whose override was
+/// created, modified or removed, so the edits apply to the running program without rebuilding
+/// it.
+#[derive(Debug)]
+pub struct KernelOverrides {
+    dir: PathBuf,
+    extension: &'static str,
+    /// The modification time of the override of each compiled kernel, `None` without override.
+    watched: HashMap<String, Option<SystemTime>>,
+    polled: Instant,
+}
+
+impl KernelOverrides {
+    /// The overrides of the directory given by the environment, `None` when it isn't set.
+    pub fn from_env(extension: &'static str) -> Option<Self> {
+        let dir = std::env::var_os(OVERRIDES_DIR_VAR)?;
+        Some(Self::new(dir, extension))
+    }
+
+    /// The overrides of the directory, for the sources with the extension.
+    pub fn new(dir: impl Into<PathBuf>, extension: &'static str) -> Self {
+        let dir = dir.into();
+        if let Err(err) = fs::create_dir_all(&dir) {
+            log::warn!("Can't create the kernel overrides directory {dir:?}: {err}");
+        }
+
+        Self {
+            dir,
+            extension,
+            watched: HashMap::new(),
+            polled: Instant::now(),
+        }
+    }
+
+    /// The source overriding the `generated` source of the kernel, `None` when the generated
+    /// source should be compiled.
+    pub fn source(&mut self, name: &str, generated: &str) -> Option<String> {
+        let path = self.path(name);
+        self.watched.insert(name.to_string(), modified(&path));
+
+        match fs::read_to_string(&path) {
+            Ok(source) => {
+                log::info!("Compiling {name} from {path:?}");
+                Some(source)
+            }
+            Err(_) => {
+                let generated_path = self.path(&format!("{name}.generated"));
+                if let Err(err) = fs::write(&generated_path, generated) {
+                    log::warn!("Can't write the generated source to {generated_path:?}: {err}");
+                }
+                None
+            }
+        }
+    }
+
+    /// The names of the kernels whose override changed since they were compiled, checked at most
+    /// every 250 milliseconds.
+    ///
+    /// The kernels are no longer watched until they are compiled again.
+    pub fn changed(&mut self) -> Vec<String> {
+        if self.polled.elapsed() < POLL_INTERVAL {
+            return Vec::new();
+        }
+        self.polled = Instant::now();
+
+        let mut changed = Vec::new();
+        self.watched.retain(|name, compiled| {
+            let path = self.dir.join(format!("{name}.{}", self.extension));
+            if modified(&path) == *compiled {
+                return true;
+            }
+            log::info!("The override of {name} changed, it will be compiled again");
+            changed.push(name.clone());
+            false
+        });
+
+        changed
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.{}", self.extension))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(test: &str) -> KernelOverrides {
+        let dir =
+            std::env::temp_dir().join(format!("cubecl-overrides-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut overrides = KernelOverrides::new(dir, "wgsl");
+        // The first call polls.
+        overrides.polled = Instant::now() - POLL_INTERVAL;
+        overrides
+    }
+
+    #[test]
+    fn generated_source_is_written_without_override() {
+        let mut overrides = overrides("generated");
+
+        assert_eq!(overrides.source("kernel", "generated"), None);
+
+        let generated = fs::read_to_string(overrides.dir.join("kernel.generated.wgsl"));
+        assert_eq!(generated.unwrap(), "generated");
+    }
+
+    #[test]
+    fn override_replaces_the_generated_source() {
+        let mut overrides = overrides("override");
+        fs::write(overrides.dir.join("kernel.wgsl"), "edited").unwrap();
+
+        assert_eq!(
+            overrides.source("kernel", "generated").as_deref(),
+            Some("edited")
+        );
+    }
+
+    #[test]
+    fn changed_overrides_are_reported_once() {
+        let mut overrides = overrides("changed");
+        let path = overrides.dir.join("kernel.wgsl");
+
+        overrides.source("kernel", "generated");
+        overrides.source("other", "generated");
+        assert!(overrides.changed().is_empty());
+
+        fs::write(&path, "edited").unwrap();
+        overrides.polled -= POLL_INTERVAL;
+        assert_eq!(overrides.changed(), ["kernel"]);
+
+        // The kernel isn't watched until it's compiled again.
+        fs::remove_file(&path).unwrap();
+        overrides.polled -= POLL_INTERVAL;
+        assert!(overrides.changed().is_empty());
+
+        overrides.source("kernel", "generated");
+        fs::write(&path, "edited").unwrap();
+        overrides.polled -= POLL_INTERVAL;
+        assert_eq!(overrides.changed(), ["kernel"]);
+    }
+}
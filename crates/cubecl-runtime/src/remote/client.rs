@@ -0,0 +1,82 @@
+use super::{read_message, write_message, LaunchDescriptor, RemoteError, Request, Response};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::io::{Read, Write};
+
+/// A client sending its [requests](Request) to a [remote server](super::RemoteServer) over a
+/// stream, such as a [TcpStream](std::net::TcpStream) connected to its
+/// [listener](super::RemoteServer::listen).
+#[derive(Debug)]
+pub struct RemoteClient<Stream> {
+    stream: Stream,
+}
+
+impl<Stream: Read + Write> RemoteClient<Stream> {
+    /// A client sending its requests over the stream.
+    pub fn new(stream: Stream) -> Self {
+        Self { stream }
+    }
+
+    /// Create the buffer with the name holding the data.
+    pub fn create(&mut self, name: impl Into<String>, data: &[u8]) -> Result<(), RemoteError> {
+        self.done(Request::Create {
+            name: name.into(),
+            data: data.to_vec(),
+        })
+    }
+
+    /// Create the uninitialized buffer with the name of `size` bytes.
+    pub fn empty(&mut self, name: impl Into<String>, size: usize) -> Result<(), RemoteError> {
+        self.done(Request::Empty {
+            name: name.into(),
+            size,
+        })
+    }
+
+    /// The bytes of the buffer with the name, once the kernels writing it are done.
+    pub fn read(&mut self, name: impl Into<String>) -> Result<Vec<u8>, RemoteError> {
+        match self.request(Request::Read { name: name.into() })? {
+            Response::Data(data) => Ok(data),
+            response => Err(unexpected(response)),
+        }
+    }
+
+    /// Release the buffer with the name.
+    pub fn free(&mut self, name: impl Into<String>) -> Result<(), RemoteError> {
+        self.done(Request::Free { name: name.into() })
+    }
+
+    /// Launch the kernel described.
+    pub fn launch(&mut self, descriptor: LaunchDescriptor) -> Result<(), RemoteError> {
+        self.done(Request::Launch(descriptor))
+    }
+
+    /// Wait for the launched kernels to be done.
+    pub fn sync(&mut self) -> Result<(), RemoteError> {
+        self.done(Request::Sync)
+    }
+
+    /// Send the request, returning the response of the server or its error.
+    pub fn request(&mut self, request: Request) -> Result<Response, RemoteError> {
+        write_message(&mut self.stream, request)?;
+        // The server is trusted with the size of its responses.
+        match read_message(&mut self.stream, usize::MAX)? {
+            Some(Response::Error(err)) => Err(err),
+            Some(response) => Ok(response),
+            None => Err(RemoteError::Transport(
+                "The server closed the connection".to_string(),
+            )),
+        }
+    }
+
+    fn done(&mut self, request: Request) -> Result<(), RemoteError> {
+        match self.request(request)? {
+            Response::Done => Ok(()),
+            response => Err(unexpected(response)),
+        }
+    }
+}
+
+fn unexpected(response: Response) -> RemoteError {
+    RemoteError::Transport(format!("Unexpected response {response:?}"))
+}
@@ -0,0 +1,230 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// A launch of a kernel registered in a [remote server](super::RemoteServer), serializable to be
+/// sent by another process.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LaunchDescriptor {
+    /// The name the kernel is registered under.
+    pub kernel: String,
+    /// The number of cubes dispatched along x, y and z.
+    pub cube_count: (u32, u32, u32),
+    /// The number of units of each cube along x, y and z.
+    pub cube_dim: (u32, u32, u32),
+    /// The names of the buffers given to the kernel, in order.
+    pub bindings: Vec<String>,
+    /// The scalars given to the kernel, in order.
+    pub scalars: Vec<Scalar>,
+}
+
+impl LaunchDescriptor {
+    /// A launch of the kernel without arguments.
+    pub fn new(
+        kernel: impl Into<String>,
+        cube_count: (u32, u32, u32),
+        cube_dim: (u32, u32, u32),
+    ) -> Self {
+        Self {
+            kernel: kernel.into(),
+            cube_count,
+            cube_dim,
+            bindings: Vec::new(),
+            scalars: Vec::new(),
+        }
+    }
+
+    /// Add the buffer with the name after the other bindings.
+    pub fn binding(mut self, name: impl Into<String>) -> Self {
+        self.bindings.push(name.into());
+        self
+    }
+
+    /// Add the scalar after the other scalars.
+    pub fn scalar(mut self, scalar: Scalar) -> Self {
+        self.scalars.push(scalar);
+        self
+    }
+}
+
+/// A scalar argument of a [launch](LaunchDescriptor).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    /// An unsigned 32-bit integer.
+    U32(u32),
+    /// A signed 32-bit integer.
+    I32(i32),
+    /// A 32-bit float.
+    F32(f32),
+}
+
+/// A request sent to a [remote server](super::RemoteServer).
+///
+/// Buffers are named by the clients. The names are shared by every client of the server, so
+/// cooperating processes can exchange buffers without copying them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Request {
+    /// Create the buffer holding the data, replacing the buffer with the same name.
+    Create {
+        /// The name of the buffer.
+        name: String,
+        /// The bytes of the buffer, sent as the binary payload of the message.
+        #[serde(skip)]
+        data: Vec<u8>,
+    },
+    /// Create the uninitialized buffer of `size` bytes, replacing the buffer with the same name.
+    Empty {
+        /// The name of the buffer.
+        name: String,
+        /// The size of the buffer in bytes.
+        size: usize,
+    },
+    /// Read the bytes of the buffer, once the kernels writing it are done.
+    Read {
+        /// The name of the buffer.
+        name: String,
+    },
+    /// Release the buffer.
+    Free {
+        /// The name of the buffer.
+        name: String,
+    },
+    /// Launch a kernel.
+    Launch(LaunchDescriptor),
+    /// Wait for the launched kernels to be done.
+    Sync,
+}
+
+/// The response of a [remote server](super::RemoteServer) to a [request](Request).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Response {
+    /// The request is done.
+    Done,
+    /// The bytes of the buffer that was read, sent as the binary payload of the message.
+    Data(#[serde(skip)] Vec<u8>),
+    /// The request failed.
+    Error(RemoteError),
+}
+
+/// Error of a request to a [remote server](super::RemoteServer).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum RemoteError {
+    /// No kernel is registered under the name.
+    UnknownKernel(String),
+    /// No buffer has the name.
+    UnknownBuffer(String),
+    /// An argument of the launch doesn't match the kernel.
+    InvalidArgument(String),
+    /// No buffer of the requested size can be allocated.
+    OutOfMemory(String),
+    /// The request panicked when executed.
+    Panic(String),
+    /// The request or its response couldn't be exchanged.
+    Transport(String),
+}
+
+impl core::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownKernel(name) => write!(f, "No kernel named {name} is registered"),
+            Self::UnknownBuffer(name) => write!(f, "No buffer named {name}"),
+            Self::InvalidArgument(reason) => write!(f, "Invalid argument: {reason}"),
+            Self::OutOfMemory(reason) => write!(f, "Out of memory: {reason}"),
+            Self::Panic(reason) => write!(f, "The request panicked: {reason}"),
+            Self::Transport(reason) => write!(f, "The request couldn't be exchanged: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+impl From<io::Error> for RemoteError {
+    fn from(err: io::Error) -> Self {
+        Self::Transport(err.to_string())
+    }
+}
+
+/// A message exchanged with a [remote server](super::RemoteServer), whose buffer bytes are sent
+/// as a binary payload after its JSON header.
+pub(crate) trait Message: Serialize + DeserializeOwned {
+    /// The bytes sent as the payload of the message, if it has any.
+    fn payload(&mut self) -> Option<&mut Vec<u8>>;
+}
+
+impl Message for Request {
+    fn payload(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            Request::Create { data, .. } => Some(data),
+            _ => None,
+        }
+    }
+}
+
+impl Message for Response {
+    fn payload(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            Response::Data(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// Write the message as the length in bytes of its JSON header, as a little-endian `u32`, and of
+/// its payload, as a little-endian `u64`, followed by the header and the payload.
+pub(crate) fn write_message<T: Message>(writer: &mut impl Write, mut message: T) -> io::Result<()> {
+    let payload = message.payload().map(core::mem::take).unwrap_or_default();
+    let header = serde_json::to_vec(&message)?;
+    let header_len = u32::try_from(header.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "The message is too large"))?;
+    writer.write_all(&header_len.to_le_bytes())?;
+    writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+    writer.write_all(&header)?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+/// Read a message [written](write_message) to the stream, `None` once the stream is closed.
+///
+/// Messages larger than `max_size` bytes, header and payload included, are rejected with an
+/// [InvalidData](io::ErrorKind::InvalidData) error before anything is allocated for them.
+pub(crate) fn read_message<T: Message>(
+    reader: &mut impl Read,
+    max_size: usize,
+) -> io::Result<Option<T>> {
+    let mut lengths = [0; 12];
+    match reader.read_exact(&mut lengths) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let header_len = u32::from_le_bytes(lengths[..4].try_into().unwrap()) as u64;
+    let payload_len = u64::from_le_bytes(lengths[4..].try_into().unwrap());
+    let size = header_len.saturating_add(payload_len);
+    if size > max_size as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The message of {size} bytes is larger than the maximum of {max_size} bytes"),
+        ));
+    }
+
+    let mut header = alloc::vec![0; header_len as usize];
+    reader.read_exact(&mut header)?;
+    let mut message: T = serde_json::from_slice(&header)?;
+
+    let mut payload = alloc::vec![0; payload_len as usize];
+    reader.read_exact(&mut payload)?;
+    match message.payload() {
+        Some(data) => *data = payload,
+        None if payload.is_empty() => {}
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The message doesn't have a payload",
+            ))
+        }
+    }
+
+    Ok(Some(message))
+}
@@ -0,0 +1,15 @@
+//! Execution of kernels on behalf of other processes, so several processes share the device of
+//! a single GPU server process.
+//!
+//! Clients describe their launches with serializable [descriptors](LaunchDescriptor), naming
+//! the kernel registered in the [server](RemoteServer) and the buffers it created on their
+//! behalf. Requests are exchanged over any stream, such as a TCP or Unix socket, as
+//! length-prefixed JSON headers followed by the bytes of the buffers they carry.
+
+mod client;
+mod descriptor;
+mod server;
+
+pub use client::*;
+pub use descriptor::*;
+pub use server::*;
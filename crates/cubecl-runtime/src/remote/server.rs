@@ -0,0 +1,310 @@
+use super::{
+    read_message, write_message, LaunchDescriptor, RemoteError, Request, Response, Scalar,
+};
+use crate::{
+    channel::ComputeChannel,
+    client::ComputeClient,
+    server::{ComputeServer, CubeCount, Handle},
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The default [maximum size](RemoteServer::with_max_frame_size) of the messages and buffers of
+/// a [remote server](RemoteServer), 256 MiB.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 256 << 20;
+
+/// Launch a kernel registered in a [remote server](RemoteServer) with the resolved arguments of
+/// a [descriptor](LaunchDescriptor).
+pub type RemoteLauncher<Server, Channel> =
+    fn(&ComputeClient<Server, Channel>, &RemoteLaunch<'_>) -> Result<(), RemoteError>;
+
+/// A buffer created on behalf of the clients.
+#[derive(Debug)]
+struct NamedBuffer {
+    handle: Handle,
+    size: usize,
+}
+
+/// A server executing the [requests](Request) of other processes on the device of its client.
+///
+/// Kernels are registered by name, with a [launcher](RemoteLauncher) giving the resolved
+/// arguments to the generated `launch` function of the kernel:
+///
+/// ```ignore
+/// let mut server = RemoteServer::new(client);
+/// server.register("scale", |client, launch| {
+///     scale::launch::<WgpuRuntime>(
+///         client,
+///         launch.cube_count(),
+///         CubeDim::new(launch.cube_dim().0, launch.cube_dim().1, launch.cube_dim().2),
+///         unsafe { ArrayArg::from_raw_parts::<f32>(launch.handle(0)?, launch.size(0)? / 4, 1) },
+///         ScalarArg::new(launch.f32(0)?),
+///     );
+///     Ok(())
+/// });
+/// server.listen(&TcpListener::bind("127.0.0.1:7878")?)?;
+/// ```
+pub struct RemoteServer<Server: ComputeServer, Channel> {
+    client: ComputeClient<Server, Channel>,
+    kernels: HashMap<String, RemoteLauncher<Server, Channel>>,
+    buffers: Mutex<HashMap<String, NamedBuffer>>,
+    max_frame_size: usize,
+}
+
+impl<Server, Channel> core::fmt::Debug for RemoteServer<Server, Channel>
+where
+    Server: ComputeServer,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RemoteServer")
+            .field("kernels", &self.kernels.keys().collect::<Vec<_>>())
+            .field(
+                "buffers",
+                &self
+                    .buffers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .len(),
+            )
+            .field("max_frame_size", &self.max_frame_size)
+            .finish()
+    }
+}
+
+impl<Server, Channel> RemoteServer<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    /// A server executing the requests with the client, without any registered kernel.
+    pub fn new(client: ComputeClient<Server, Channel>) -> Self {
+        Self {
+            client,
+            kernels: HashMap::new(),
+            buffers: Mutex::new(HashMap::new()),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    /// Reject the messages and the buffers larger than `bytes`, which defaults to
+    /// [DEFAULT_MAX_FRAME_SIZE].
+    ///
+    /// A client sending a larger message is disconnected before anything is allocated for it.
+    pub fn with_max_frame_size(mut self, bytes: usize) -> Self {
+        self.max_frame_size = bytes;
+        self
+    }
+
+    /// Register the kernel under the name, replacing the kernel registered under the same name.
+    pub fn register(&mut self, name: impl Into<String>, launcher: RemoteLauncher<Server, Channel>) {
+        self.kernels.insert(name.into(), launcher);
+    }
+
+    /// Execute the request.
+    ///
+    /// A panic while executing the request is returned as a [panic error](RemoteError::Panic),
+    /// so it doesn't take the server down for the other clients.
+    pub fn execute(&self, request: Request) -> Response {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.try_execute(request)))
+            .unwrap_or_else(|payload| {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Err(RemoteError::Panic(message))
+            })
+            .unwrap_or_else(Response::Error)
+    }
+
+    fn try_execute(&self, request: Request) -> Result<Response, RemoteError> {
+        match request {
+            Request::Create { name, data } => {
+                self.check_size(data.len())?;
+                let handle = self.client.create(&data);
+                self.insert(name, handle, data.len());
+                Ok(Response::Done)
+            }
+            Request::Empty { name, size } => {
+                self.check_size(size)?;
+                let handle = self
+                    .client
+                    .try_empty(size)
+                    .map_err(|err| RemoteError::OutOfMemory(err.to_string()))?;
+                self.insert(name, handle, size);
+                Ok(Response::Done)
+            }
+            Request::Read { name } => self
+                .handle(&name)
+                .map(|(handle, _)| Response::Data(self.client.read(handle.binding()))),
+            Request::Free { name } => match self.buffers().remove(&name) {
+                Some(_) => Ok(Response::Done),
+                None => Err(RemoteError::UnknownBuffer(name)),
+            },
+            Request::Launch(descriptor) => self.launch(&descriptor).map(|_| Response::Done),
+            Request::Sync => {
+                cubecl_common::future::block_on(self.client.sync());
+                Ok(Response::Done)
+            }
+        }
+    }
+
+    /// Execute the requests read from the stream, writing their responses to it, until the
+    /// stream is closed.
+    pub fn serve(&self, mut stream: impl Read + Write) -> io::Result<()> {
+        while let Some(request) = read_message::<Request>(&mut stream, self.max_frame_size)? {
+            let response = self.execute(request);
+            write_message(&mut stream, response)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serve every connection accepted by the listener on its own thread, until accepting a
+    /// connection fails.
+    pub fn listen(&self, listener: &TcpListener) -> io::Result<()>
+    where
+        Self: Sync,
+    {
+        std::thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                scope.spawn(move || {
+                    if let Err(err) = self.serve(stream) {
+                        log::warn!("Remote connection closed: {err}");
+                    }
+                });
+            }
+
+            Ok(())
+        })
+    }
+
+    fn launch(&self, descriptor: &LaunchDescriptor) -> Result<(), RemoteError> {
+        let launcher = self
+            .kernels
+            .get(&descriptor.kernel)
+            .ok_or_else(|| RemoteError::UnknownKernel(descriptor.kernel.clone()))?;
+        let buffers = descriptor
+            .bindings
+            .iter()
+            .map(|name| self.handle(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let launch = RemoteLaunch {
+            descriptor,
+            buffers,
+        };
+
+        launcher(&self.client, &launch)
+    }
+
+    /// Buffers larger than a message couldn't be read back by the clients.
+    fn check_size(&self, size: usize) -> Result<(), RemoteError> {
+        match size > self.max_frame_size {
+            true => Err(RemoteError::InvalidArgument(format!(
+                "The buffer of {size} bytes is larger than the maximum of {} bytes",
+                self.max_frame_size
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// The buffers, still usable after a panic while they were locked since every update of
+    /// the map is a single insertion or removal.
+    fn buffers(&self) -> MutexGuard<'_, HashMap<String, NamedBuffer>> {
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn insert(&self, name: String, handle: Handle, size: usize) {
+        let buffer = NamedBuffer { handle, size };
+        self.buffers().insert(name, buffer);
+    }
+
+    fn handle(&self, name: &str) -> Result<(Handle, usize), RemoteError> {
+        let buffers = self.buffers();
+        let buffer = buffers
+            .get(name)
+            .ok_or_else(|| RemoteError::UnknownBuffer(name.to_string()))?;
+        Ok((buffer.handle.clone(), buffer.size))
+    }
+}
+
+/// The arguments of a [descriptor](LaunchDescriptor), with its buffers resolved to their
+/// handles.
+#[derive(Debug)]
+pub struct RemoteLaunch<'a> {
+    descriptor: &'a LaunchDescriptor,
+    buffers: Vec<(Handle, usize)>,
+}
+
+impl RemoteLaunch<'_> {
+    /// The number of cubes dispatched.
+    pub fn cube_count(&self) -> CubeCount {
+        let (x, y, z) = self.descriptor.cube_count;
+        CubeCount::Static(x, y, z)
+    }
+
+    /// The number of units of each cube along x, y and z.
+    pub fn cube_dim(&self) -> (u32, u32, u32) {
+        self.descriptor.cube_dim
+    }
+
+    /// The handle of the buffer bound at the position.
+    pub fn handle(&self, position: usize) -> Result<&Handle, RemoteError> {
+        self.buffer(position).map(|(handle, _)| handle)
+    }
+
+    /// The size in bytes of the buffer bound at the position.
+    pub fn size(&self, position: usize) -> Result<usize, RemoteError> {
+        self.buffer(position).map(|(_, size)| *size)
+    }
+
+    /// The scalar at the position.
+    pub fn scalar(&self, position: usize) -> Result<Scalar, RemoteError> {
+        self.descriptor
+            .scalars
+            .get(position)
+            .copied()
+            .ok_or_else(|| {
+                RemoteError::InvalidArgument(format!("The launch has no scalar at {position}"))
+            })
+    }
+
+    /// The `u32` scalar at the position.
+    pub fn u32(&self, position: usize) -> Result<u32, RemoteError> {
+        match self.scalar(position)? {
+            Scalar::U32(value) => Ok(value),
+            _ => Err(invalid_scalar(position, "a u32")),
+        }
+    }
+
+    /// The `i32` scalar at the position.
+    pub fn i32(&self, position: usize) -> Result<i32, RemoteError> {
+        match self.scalar(position)? {
+            Scalar::I32(value) => Ok(value),
+            _ => Err(invalid_scalar(position, "an i32")),
+        }
+    }
+
+    /// The `f32` scalar at the position.
+    pub fn f32(&self, position: usize) -> Result<f32, RemoteError> {
+        match self.scalar(position)? {
+            Scalar::F32(value) => Ok(value),
+            _ => Err(invalid_scalar(position, "an f32")),
+        }
+    }
+
+    fn buffer(&self, position: usize) -> Result<&(Handle, usize), RemoteError> {
+        self.buffers.get(position).ok_or_else(|| {
+            RemoteError::InvalidArgument(format!("The launch has no binding at {position}"))
+        })
+    }
+}
+
+fn invalid_scalar(position: usize, expected: &str) -> RemoteError {
+    RemoteError::InvalidArgument(format!("The scalar at {position} should be {expected}"))
+}
@@ -1,13 +1,13 @@
 use crate::{
     memory_management::{
         memory_pool::{SliceBinding, SliceHandle},
-        MemoryHandle, MemoryUsage,
+        MemoryHandle, MemoryUsage, OutOfMemoryError, TaggedAllocation,
     },
     storage::{BindingResource, ComputeStorage},
     ExecutionMode,
 };
-use alloc::vec::Vec;
-use core::{fmt::Debug, future::Future};
+use alloc::{boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use core::{fmt::Debug, future::Future, pin::Pin};
 use cubecl_common::benchmark::TimestampsResult;
 
 /// The compute server is responsible for handling resources and computations over resources.
@@ -25,18 +25,128 @@ where
     /// The type of the features supported by the server.
     type Feature: Ord + Copy + Debug + Send + Sync;
 
+    /// A readable name of the kernel, given to the
+    /// [launch hooks](crate::client::ComputeClient::on_launch).
+    fn kernel_name(_kernel: &Self::Kernel) -> String {
+        String::from(core::any::type_name::<Self::Kernel>())
+    }
+
     /// Given a handle, returns the owned resource as bytes.
     fn read(&mut self, binding: Binding) -> impl Future<Output = Vec<u8>> + Send + 'static;
 
+    /// Given multiple handles, returns the owned resources as bytes, in the same order.
+    ///
+    /// Servers should stage every read at once to pay for a single synchronization.
+    fn read_many(
+        &mut self,
+        bindings: Vec<Binding>,
+    ) -> impl Future<Output = Vec<Vec<u8>>> + Send + 'static {
+        // Boxing the futures ends their borrow of the server, since they are `'static`.
+        let futures = bindings
+            .into_iter()
+            .map(|binding| {
+                Box::pin(self.read(binding)) as Pin<Box<dyn Future<Output = Vec<u8>> + Send>>
+            })
+            .collect::<Vec<_>>();
+
+        async move {
+            let mut data = Vec::with_capacity(futures.len());
+            for future in futures {
+                data.push(future.await);
+            }
+            data
+        }
+    }
+
+    /// Given a resource handle, returns `len` bytes of the resource starting `offset` bytes after
+    /// the start of its binding.
+    fn read_range(
+        &mut self,
+        binding: Binding,
+        offset: u64,
+        len: u64,
+    ) -> impl Future<Output = Vec<u8>> + Send + 'static {
+        let size = self.binding_size(&binding);
+        assert!(
+            offset + len <= size,
+            "Range of {len} bytes at offset {offset} is out of bounds for a binding of {size} bytes"
+        );
+        self.read(binding.range(offset, size - offset - len))
+    }
+
+    /// Given a resource handle, returns the size in bytes of its binding.
+    fn binding_size(&mut self, binding: &Binding) -> u64;
+
+    /// Given a resource handle, overwrites the bytes at the start of its binding with `data`.
+    ///
+    /// The write is ordered after the kernels already executed on the resource.
+    fn write(&mut self, binding: Binding, data: &[u8]);
+
+    /// Given a resource handle, sets all the bytes of its binding to zero.
+    ///
+    /// The clear is ordered like a [write](ComputeServer::write). Servers able to fill memory on
+    /// the device override it, the others write a buffer of zeros.
+    fn clear(&mut self, binding: Binding) {
+        let size = self.binding_size(&binding);
+        self.write(binding, &vec![0; size as usize]);
+    }
+
+    /// Given a resource handle, repeats `pattern` over the bytes of its binding, the last
+    /// repetition being truncated.
+    ///
+    /// The fill is ordered like a [write](ComputeServer::write). Servers able to fill memory on
+    /// the device override it, the others write the repeated pattern.
+    fn fill(&mut self, binding: Binding, pattern: &[u8]) {
+        let size = self.binding_size(&binding);
+        let data = pattern
+            .iter()
+            .copied()
+            .cycle()
+            .take(size as usize)
+            .collect::<Vec<_>>();
+        self.write(binding, &data);
+    }
+
     /// Given a resource handle, returns the storage resource.
     fn get_resource(&mut self, binding: Binding) -> BindingResource<Self>;
 
     /// Given a resource as bytes, stores it and returns the memory handle.
     fn create(&mut self, data: &[u8]) -> Handle;
 
+    /// Given a resource as bytes, starts storing it on a dedicated transfer queue and returns the
+    /// memory handle along with a [fence](Fence) signaled once the copy is completed.
+    ///
+    /// The copy can overlap with the kernels already submitted, and the next kernel executed waits
+    /// on the fence before starting. Servers without a transfer queue fall back to
+    /// [create](ComputeServer::create).
+    fn create_async(&mut self, data: &[u8]) -> (Handle, Fence) {
+        (self.create(data), Fence::default())
+    }
+
+    /// Wait for the completion of the operation signaling the `fence`.
+    fn wait_fence(&mut self, fence: Fence) -> impl Future<Output = ()> + Send + 'static {
+        let _ = fence;
+        self.sync()
+    }
+
     /// Reserves `size` bytes in the storage, and returns a handle over them.
     fn empty(&mut self, size: usize) -> Handle;
 
+    /// Reserves `size` bytes in the storage, and returns a handle over them, or the error of the
+    /// allocation when the device is out of memory.
+    fn try_empty(&mut self, size: usize) -> Result<Handle, OutOfMemoryError>;
+
+    /// Grows the resource of the handle to `size` bytes, keeping its content, and returns the
+    /// handle over the whole grown resource.
+    ///
+    /// Servers supporting growable memory move the resource once to a growable allocation, then
+    /// extend it in place on the next calls. Returns `None` when the server can't grow resources,
+    /// and the caller has to copy the content to a new resource.
+    fn grow(&mut self, handle: Handle, size: usize) -> Option<Handle> {
+        let _ = (handle, size);
+        None
+    }
+
     /// Executes the `kernel` over the given memory `handles`.
     ///
     /// Kernels have mutable access to every resource they are given
@@ -51,8 +161,53 @@ where
         count: CubeCount,
         bindings: Vec<Binding>,
         kind: ExecutionMode,
+        stream: Stream,
     );
 
+    /// Remove the compiled `kernel` from the cache of the server, so it's compiled again on its
+    /// next execution.
+    ///
+    /// Servers that can't release compiled kernels keep them cached.
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let _ = kernel;
+    }
+
+    /// Compile the `kernels` ahead of their execution, so they are launched without compiling.
+    ///
+    /// The kernels are independent, so servers able to compile on many threads compile them
+    /// concurrently. Servers compiling on execution ignore them.
+    fn prepare(&mut self, kernels: Vec<(Self::Kernel, ExecutionMode)>) {
+        let _ = kernels;
+    }
+
+    /// Create a new [stream](Stream) to execute kernels on.
+    ///
+    /// Servers executing every kernel in order return the default stream.
+    fn create_stream(&mut self) -> Stream {
+        Stream::default()
+    }
+
+    /// Create a new [stream](Stream) whose kernels are scheduled with the `priority`, relative to
+    /// the kernels of the other streams.
+    ///
+    /// Servers without prioritized scheduling return a [new stream](Self::create_stream).
+    fn create_stream_with_priority(&mut self, priority: Priority) -> Stream {
+        let _ = priority;
+        self.create_stream()
+    }
+
+    /// Record an event on the `stream`, returning a [fence](Fence) signaled once the kernels
+    /// executed on it so far are completed.
+    fn record_event(&mut self, stream: Stream) -> Fence {
+        let _ = stream;
+        Fence::default()
+    }
+
+    /// Make the kernels executed next on the `stream` wait on the `fence`.
+    fn wait_event(&mut self, stream: Stream, fence: Fence) {
+        let _ = (stream, fence);
+    }
+
     /// Flush all outstanding tasks in the server.
     fn flush(&mut self);
 
@@ -72,6 +227,74 @@ where
 
     /// Disable collecting timestamps.
     fn disable_timestamps(&mut self);
+
+    /// The error of the device when it was lost, for instance after a driver reset, in which case
+    /// the operations of the server can't complete anymore.
+    fn device_lost(&mut self) -> Option<DeviceLost> {
+        None
+    }
+
+    /// Request a new device to replace a [lost](ComputeServer::device_lost) one, which
+    /// invalidates every resource and compiled kernel of the server.
+    ///
+    /// Servers that can't request their device again return the error of the lost device.
+    fn recover(&mut self) -> Result<(), DeviceLost> {
+        match self.device_lost() {
+            Some(lost) => Err(lost),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error returned when the device of a server is lost, after a driver reset, a hang or a fatal
+/// error of a kernel. The resources and compiled kernels of the server are lost with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceLost {
+    /// The reason reported by the driver.
+    pub reason: String,
+}
+
+impl core::fmt::Display for DeviceLost {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The device was lost: {}", self.reason)
+    }
+}
+
+impl std::error::Error for DeviceLost {}
+
+/// A fence signaled once an asynchronous operation of the server is completed.
+///
+/// The identifier is only meaningful to the server that created the fence.
+#[derive(new, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fence {
+    /// The identifier of the fence in the server.
+    pub id: u64,
+}
+
+/// A stream of the server, executing its kernels in order.
+///
+/// Kernels executed on different streams can run concurrently on devices that support it.
+/// Dependencies between streams are expressed by waiting on an
+/// [event](ComputeServer::record_event) of the other stream.
+#[derive(new, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stream {
+    /// The identifier of the stream in the server, the default stream is `0`.
+    pub id: u64,
+}
+
+/// The priority of the kernels of a [stream](ComputeServer::create_stream_with_priority).
+///
+/// Devices schedule the pending kernels of the higher priority streams first, so latency
+/// critical work isn't delayed by the background work submitted before it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Work whose latency doesn't matter, such as preprocessing.
+    Background,
+    /// The priority of the default stream.
+    #[default]
+    Normal,
+    /// Latency critical work, such as interactive inference.
+    Interactive,
 }
 
 /// Server handle containing the [memory handle](MemoryManagement::Handle).
@@ -83,6 +306,12 @@ pub struct Handle {
     pub offset_start: Option<u64>,
     /// Memory offset in bytes.
     pub offset_end: Option<u64>,
+    /// The tag the allocation is attributed to until every clone of the handle is dropped.
+    #[new(default)]
+    pub(crate) tag: Option<Arc<TaggedAllocation>>,
+    /// The size in bytes of the allocation, known when the handle was created by the client.
+    #[new(default)]
+    pub(crate) size: Option<u64>,
 }
 
 impl Handle {
@@ -119,7 +348,25 @@ pub struct Binding {
     pub offset_end: Option<u64>,
 }
 
+impl Binding {
+    /// Skip `start` more bytes at the start of the binding and `end` more bytes at its end.
+    pub fn range(mut self, start: u64, end: u64) -> Self {
+        self.offset_start = Some(self.offset_start.unwrap_or(0) + start);
+        self.offset_end = Some(self.offset_end.unwrap_or(0) + end);
+        self
+    }
+}
+
 impl Handle {
+    /// The number of bytes between the offsets of the handle, or `None` when the handle wasn't
+    /// created by a [client](crate::client::ComputeClient) and its size is unknown.
+    pub fn size(&self) -> Option<u64> {
+        let size = self.size?;
+        let offsets = self.offset_start.unwrap_or(0) + self.offset_end.unwrap_or(0);
+
+        Some(size.saturating_sub(offsets))
+    }
+
     /// If the tensor handle can be reused inplace.
     pub fn can_mut(&self) -> bool {
         self.memory.can_mut()
@@ -143,6 +390,8 @@ impl Clone for Handle {
             memory: self.memory.clone(),
             offset_start: self.offset_start,
             offset_end: self.offset_end,
+            tag: self.tag.clone(),
+            size: self.size,
         }
     }
 }
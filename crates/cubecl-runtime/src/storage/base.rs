@@ -77,8 +77,33 @@ pub trait ComputeStorage: Send {
     /// Allocates `size` units of memory and returns a handle to it
     fn alloc(&mut self, size: u64) -> StorageHandle;
 
+    /// Allocates `size` units of memory and returns a handle to it, or returns `None` when the
+    /// device is out of memory.
+    ///
+    /// Storages that can't detect it fall back to [alloc](ComputeStorage::alloc).
+    fn try_alloc(&mut self, size: u64) -> Option<StorageHandle> {
+        Some(self.alloc(size))
+    }
+
     /// Deallocates the memory pointed by the given storage id.
     fn dealloc(&mut self, id: StorageId);
+
+    /// Allocates `size` units of memory that can later [grow](ComputeStorage::grow) in place, or
+    /// returns `None` when the storage doesn't support growable allocations.
+    fn alloc_growable(&mut self, size: u64) -> Option<StorageHandle> {
+        let _ = size;
+        None
+    }
+
+    /// Grows the [growable allocation](ComputeStorage::alloc_growable) to `size` units of memory,
+    /// keeping its address and content.
+    ///
+    /// Returns false when the allocation can't grow in place, the memory manager then falls back
+    /// to a new allocation.
+    fn grow(&mut self, id: StorageId, size: u64) -> bool {
+        let _ = (id, size);
+        false
+    }
 }
 
 /// Access to the underlying resource for a given binding.
@@ -101,3 +126,11 @@ impl<Server: ComputeServer> BindingResource<Server> {
         &self.resource
     }
 }
+
+impl<Server: ComputeServer> core::fmt::Debug for BindingResource<Server> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BindingResource")
+            .field("binding", &self.binding)
+            .finish_non_exhaustive()
+    }
+}
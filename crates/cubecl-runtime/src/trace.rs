@@ -0,0 +1,55 @@
+//! Structured events of the runtime activity, emitted with [tracing](https://docs.rs/tracing)
+//! when the `tracing` feature is enabled.
+//!
+//! Compilations, pipeline creations, launches, synchronizations, reads and allocations are
+//! recorded as spans at the `debug` level under the `cubecl` target, carrying the kernel names
+//! and the sizes involved. A subscriber measures their durations, for instance
+//! `tracing_subscriber::fmt().with_span_events(FmtSpan::CLOSE)` logs each span with the time
+//! spent in it. Without the feature, the macros expand to nothing and their arguments aren't
+//! evaluated.
+
+#[cfg(feature = "tracing")]
+pub use tracing;
+
+/// Enter a `debug` span until the end of the scope, e.g.
+/// `let _span = trace_span!("compile", kernel = %name);`.
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! trace_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        $crate::trace::tracing::debug_span!(target: "cubecl", $name $(, $($fields)*)?).entered()
+    };
+}
+
+/// Enter a `debug` span until the end of the scope, e.g.
+/// `let _span = trace_span!("compile", kernel = %name);`.
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        ()
+    };
+}
+
+/// Instrument a future with a `debug` span, entered each time the future is polled, e.g.
+/// `trace_future!(channel.sync(), "sync").await`.
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! trace_future {
+    ($future:expr, $name:literal $(, $($fields:tt)*)?) => {
+        $crate::trace::tracing::Instrument::instrument(
+            $future,
+            $crate::trace::tracing::debug_span!(target: "cubecl", $name $(, $($fields)*)?),
+        )
+    };
+}
+
+/// Instrument a future with a `debug` span, entered each time the future is polled, e.g.
+/// `trace_future!(channel.sync(), "sync").await`.
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! trace_future {
+    ($future:expr, $($args:tt)*) => {
+        $future
+    };
+}
@@ -5,8 +5,14 @@ use crate::{
 use core::{fmt::Display, hash::Hash};
 use hashbrown::HashMap;
 
+#[cfg(autotune_persistent_cache)]
+use super::TuneResults;
+
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, string::ToString};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
 
 /// A local tuner allows to create a tuner for a specific key that can be different from the server
 /// key.
@@ -54,6 +60,19 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
         S: ComputeServer + 'static,
         C: ComputeChannel<S> + 'static,
     {
+        // Benchmarks aren't reproducible, so the operation is selected without them.
+        if client.is_deterministic() {
+            let key = autotune_operation_set.key();
+            let num_candidates = autotune_operation_set.autotunables().len();
+            let fastest_index = autotune_operation_set
+                .benchmark_order(&key, num_candidates)
+                .into_iter()
+                .find(|index| autotune_operation_set.should_run(&key, *index))
+                .unwrap_or(0);
+
+            return autotune_operation_set.fastest(fastest_index).execute();
+        }
+
         // We avoid locking in write mode when possible.
         //
         // This makes us potentially check the cache twice, but allows to avoid
@@ -84,14 +103,7 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
 
         // If we are not able to get a tuner for the given ID, we have to create one.
         if should_init {
-            let mut state = self.state.write();
-            let map = state.get_or_insert_with(Default::default);
-
-            if !map.contains_key(id) {
-                let name = self.name.replace("::", "-");
-                let tuner = Tuner::new(&name, &id.to_string());
-                map.insert(id.clone(), tuner);
-            };
+            self.init_tuner(id);
         }
 
         // When loading the first time the result of an autotune set, we need to verify the checksum.
@@ -142,6 +154,88 @@ impl<AK: AutotuneKey + 'static, ID: Hash + PartialEq + Eq + Clone + Display> Loc
         unreachable!();
     }
 
+    /// Benchmark every candidate of the provided [autotune operation set](AutotuneOperationSet)
+    /// and register the fastest one, without executing it.
+    ///
+    /// Meant to produce pre-tuned results offline, which can then be
+    /// [exported](Self::export_results).
+    pub fn tune_offline<S, C, Out: Send + 'static>(
+        &self,
+        id: &ID,
+        client: &ComputeClient<S, C>,
+        autotune_operation_set: Box<dyn AutotuneOperationSet<AK, Out>>,
+    ) where
+        S: ComputeServer + 'static,
+        C: ComputeChannel<S> + 'static,
+    {
+        self.init_tuner(id);
+
+        let mut result = None;
+        if let Some(state) = self.state.read().as_ref() {
+            if let Some(tuner) = state.get(id) {
+                result = Some(tuner.execute_autotune_exhaustive(autotune_operation_set, client));
+            }
+        }
+
+        if let Some(result) = result {
+            let mut state = self.state.write();
+            let map = state.get_or_insert_with(Default::default);
+
+            if let Some(tuner) = map.get_mut(id) {
+                tuner.register_result(result);
+            }
+        }
+    }
+
+    #[cfg(autotune_persistent_cache)]
+    /// Export the results tuned for the given ID.
+    pub fn export_results(&self, id: &ID) -> TuneResults<AK> {
+        let results = self
+            .state
+            .read()
+            .as_ref()
+            .and_then(|state| state.get(id).map(|tuner| tuner.export_results()))
+            .unwrap_or_default();
+
+        TuneResults::new(self.name(), id.to_string(), results)
+    }
+
+    #[cfg(autotune_persistent_cache)]
+    /// Import results, usually [exported](Self::export_results) offline, for the given ID.
+    pub fn import_results(&self, id: &ID, results: TuneResults<AK>) {
+        if results.name != self.name() {
+            log::warn!(
+                "Importing tune results of {} into {}",
+                results.name,
+                self.name()
+            );
+        }
+
+        self.init_tuner(id);
+
+        let mut state = self.state.write();
+        let map = state.get_or_insert_with(Default::default);
+
+        if let Some(tuner) = map.get_mut(id) {
+            tuner.import_results(results.results);
+        }
+    }
+
+    fn init_tuner(&self, id: &ID) {
+        let mut state = self.state.write();
+        let map = state.get_or_insert_with(Default::default);
+
+        if !map.contains_key(id) {
+            let tuner = Tuner::new(&self.name(), &id.to_string());
+            map.insert(id.clone(), tuner);
+        };
+    }
+
+    /// The name of the tuner, used to name its results and its cache.
+    pub fn name(&self) -> String {
+        self.name.replace("::", "-")
+    }
+
     /// Return the autotune result given a key.
     pub fn autotune_result(&self, id: &ID, key: &AK) -> TuneCacheResult {
         if let Some(state) = self.state.read().as_ref() {
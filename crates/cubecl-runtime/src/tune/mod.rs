@@ -1,5 +1,8 @@
 mod local;
 mod operation;
+#[cfg(autotune_persistent_cache)]
+mod results;
+mod search_space;
 mod tune_benchmark;
 mod tune_cache;
 mod tuner;
@@ -8,6 +11,9 @@ mod util;
 pub use crate::tune_with;
 pub use local::*;
 pub use operation::*;
+#[cfg(autotune_persistent_cache)]
+pub use results::*;
+pub use search_space::*;
 pub use tune_benchmark::*;
 pub use tune_cache::*;
 pub use tuner::*;
@@ -39,6 +39,25 @@ pub trait AutotuneOperationSet<K: Send + Sync + 'static, Output = ()>: Send + Sy
     fn should_run(&self, key: &K, index: usize) -> bool {
         true
     }
+
+    /// The order in which the candidate operations are benchmarked, most promising first.
+    ///
+    /// In [deterministic mode](crate::client::ComputeClient::deterministic), the first operation
+    /// of that order is always selected, without benchmarking.
+    ///
+    /// Indices that aren't returned are not benchmarked. Operation sets built from a
+    /// [search space](crate::tune::SearchSpace) can use
+    /// [its prior](crate::tune::SearchSpace::benchmark_order).
+    #[allow(unused)]
+    fn benchmark_order(&self, key: &K, num_candidates: usize) -> Vec<usize> {
+        (0..num_candidates).collect()
+    }
+
+    /// Stop benchmarking after the given number of consecutive candidates didn't improve the
+    /// fastest one. All candidates are benchmarked when `None`.
+    fn early_stopping(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Contains operation to run and inputs on which to run it
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::AutotuneKey;
+
+/// The version of the [tune results](TuneResults) file format.
+pub const TUNE_RESULTS_VERSION: u32 = 1;
+
+/// The fastest operation found for a single autotune key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TuneResult<K> {
+    /// The autotune key.
+    pub key: K,
+    /// The checksum of the [operation set](super::AutotuneOperationSet) that was tuned.
+    pub checksum: String,
+    /// The index of the fastest operation.
+    pub fastest_index: usize,
+}
+
+/// Autotune results of a [local tuner](super::LocalTuner) on a device, usually produced offline.
+///
+/// Production deployments can ship those results with their application and
+/// [import](super::LocalTuner::import_results) them at startup instead of tuning at the first
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "K: AutotuneKey")]
+pub struct TuneResults<K> {
+    /// The version of the file format.
+    pub version: u32,
+    /// The name of the tuner.
+    pub name: String,
+    /// The device the results were produced on.
+    pub device_id: String,
+    /// The tuned results.
+    pub results: Vec<TuneResult<K>>,
+}
+
+impl<K: AutotuneKey> TuneResults<K> {
+    /// Create tune results with the current file format version.
+    pub fn new(name: String, device_id: String, results: Vec<TuneResult<K>>) -> Self {
+        Self {
+            version: TUNE_RESULTS_VERSION,
+            name,
+            device_id,
+            results,
+        }
+    }
+
+    /// Save the results as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let path = path.as_ref();
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(path, data)
+    }
+
+    /// Load results saved with [save](Self::save).
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        let data = fs::read_to_string(path)?;
+        let results: Self = serde_json::from_str(&data)?;
+
+        if results.version != TUNE_RESULTS_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Unsupported tune results version {}, expected {}",
+                    results.version, TUNE_RESULTS_VERSION
+                ),
+            ));
+        }
+
+        Ok(results)
+    }
+}
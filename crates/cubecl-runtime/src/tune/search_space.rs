@@ -0,0 +1,174 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// A point of a [search space](SearchSpace), assigning a value to each parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TunePoint {
+    values: Vec<(&'static str, u32)>,
+}
+
+impl TunePoint {
+    /// The value of the given parameter.
+    ///
+    /// # Panics
+    ///
+    /// If the parameter isn't part of the search space.
+    pub fn get(&self, name: &str) -> u32 {
+        self.values
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("Unknown tune parameter {name}"))
+    }
+
+    /// All parameters with their values, in declaration order.
+    pub fn values(&self) -> &[(&'static str, u32)] {
+        &self.values
+    }
+}
+
+type Constraint = Box<dyn Fn(&TunePoint) -> bool + Send + Sync>;
+type Prior = Box<dyn Fn(&TunePoint) -> f32 + Send + Sync>;
+
+/// A grid of tunable parameters, such as tile sizes, vectorization factors or cube dimensions.
+///
+/// Library authors declare the parameters with their candidate values, along with constraints
+/// that remove invalid combinations and an optional prior that ranks the candidates from the most
+/// to the least promising. The prior is usually chosen per device class from the
+/// [device properties](crate::DeviceProperties).
+///
+/// Combined with [early stopping](SearchSpace::early_stopping), the tuner benchmarks the most
+/// promising candidates first and stops when it can't find a faster one.
+#[derive(Default)]
+pub struct SearchSpace {
+    params: Vec<(&'static str, Vec<u32>)>,
+    constraints: Vec<Constraint>,
+    prior: Option<Prior>,
+    patience: Option<usize>,
+}
+
+impl core::fmt::Debug for SearchSpace {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SearchSpace")
+            .field("params", &self.params)
+            .field("num_constraints", &self.constraints.len())
+            .field("prior", &self.prior.is_some())
+            .field("patience", &self.patience)
+            .finish()
+    }
+}
+
+impl SearchSpace {
+    /// Create an empty search space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter with its candidate values.
+    pub fn param(mut self, name: &'static str, values: impl IntoIterator<Item = u32>) -> Self {
+        self.params.push((name, values.into_iter().collect()));
+        self
+    }
+
+    /// Only keep the points that satisfy the given constraint.
+    pub fn constraint(
+        mut self,
+        constraint: impl Fn(&TunePoint) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.constraints.push(Box::new(constraint));
+        self
+    }
+
+    /// Rank the points with the given heuristic, higher scores being benchmarked first.
+    pub fn prior(mut self, prior: impl Fn(&TunePoint) -> f32 + Send + Sync + 'static) -> Self {
+        self.prior = Some(Box::new(prior));
+        self
+    }
+
+    /// Stop benchmarking after `patience` consecutive candidates didn't improve the fastest one.
+    pub fn early_stopping(mut self, patience: usize) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// The early stopping patience, if any.
+    pub fn patience(&self) -> Option<usize> {
+        self.patience
+    }
+
+    /// All the valid points of the search space, in declaration order.
+    pub fn points(&self) -> Vec<TunePoint> {
+        let mut points = Vec::from([TunePoint { values: Vec::new() }]);
+
+        for (name, values) in self.params.iter() {
+            points = points
+                .into_iter()
+                .flat_map(|point| {
+                    values.iter().map(move |value| {
+                        let mut point = point.clone();
+                        point.values.push((*name, *value));
+                        point
+                    })
+                })
+                .collect();
+        }
+
+        points
+            .into_iter()
+            .filter(|point| self.constraints.iter().all(|constraint| constraint(point)))
+            .collect()
+    }
+
+    /// The order in which the given points should be benchmarked, according to the prior.
+    ///
+    /// Points with the same score keep their declaration order.
+    pub fn benchmark_order(&self, points: &[TunePoint]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+
+        if let Some(prior) = &self.prior {
+            let scores: Vec<f32> = points.iter().map(prior).collect();
+            order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        }
+
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_space_is_the_cartesian_product_of_params() {
+        let space = SearchSpace::new()
+            .param("tile", [16, 32])
+            .param("vectorization", [1, 2, 4]);
+
+        let points = space.points();
+
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0].values(), &[("tile", 16), ("vectorization", 1)]);
+        assert_eq!(points[5].values(), &[("tile", 32), ("vectorization", 4)]);
+    }
+
+    #[test]
+    fn search_space_constraints_remove_points() {
+        let space = SearchSpace::new()
+            .param("tile", [16, 32, 64])
+            .param("vectorization", [1, 2, 4])
+            .constraint(|point| point.get("tile") * point.get("vectorization") <= 64);
+
+        assert_eq!(space.points().len(), 6);
+    }
+
+    #[test]
+    fn search_space_prior_orders_benchmarks() {
+        let space = SearchSpace::new()
+            .param("tile", [16, 32, 64])
+            .prior(|point| if point.get("tile") == 32 { 1.0 } else { 0.0 });
+
+        let points = space.points();
+
+        assert_eq!(space.benchmark_order(&points), vec![1, 0, 2]);
+    }
+}
@@ -13,6 +13,8 @@ use std_imports::*;
 #[cfg(autotune_persistent_cache)]
 use serde::{Deserialize, Serialize};
 
+#[cfg(autotune_persistent_cache)]
+use super::TuneResult;
 use super::{AutotuneKey, AutotuneOperationSet};
 use hashbrown::HashMap;
 
@@ -183,6 +185,28 @@ impl<K: AutotuneKey> TuneCache<K> {
         );
     }
 
+    pub(crate) fn persistent_results(&self) -> Vec<TuneResult<K>> {
+        self.persistent_cache
+            .iter()
+            .map(|(key, entry)| TuneResult {
+                key: key.clone(),
+                checksum: entry.checksum.clone(),
+                fastest_index: entry.fastest_index,
+            })
+            .collect()
+    }
+
+    pub(crate) fn persistent_import(&mut self, result: TuneResult<K>) {
+        self.in_memory_cache.insert(
+            result.key.clone(),
+            CacheEntry {
+                checksum_checked: false,
+                fastest_index: result.fastest_index,
+            },
+        );
+        self.persistent_cache_insert(result.key, result.checksum, result.fastest_index);
+    }
+
     /// Load the persistent cache data from disk
     pub(crate) fn load(&mut self) -> Result<(), io::Error> {
         let file_path = self.get_persistent_cache_file_path();
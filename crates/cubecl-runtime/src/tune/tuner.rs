@@ -6,7 +6,7 @@ use core::{any::Any, mem::ManuallyDrop};
 use cubecl_common::stub::Duration;
 
 #[cfg(all(not(target_family = "wasm"), feature = "std"))]
-use std::panic::resume_unwind;
+use std::panic::{resume_unwind, AssertUnwindSafe};
 
 use alloc::boxed::Box;
 use alloc::string::ToString;
@@ -18,6 +18,8 @@ use crate::client::ComputeClient;
 use crate::server::ComputeServer;
 use crate::tune::{AutotuneOperation, AutotuneOperationSet, TuneBenchmark, TuneCache};
 
+#[cfg(autotune_persistent_cache)]
+use super::TuneResult;
 use super::{AutotuneKey, TuneCacheResult};
 
 /// An error that occurred during benchmarking. If other benches succeeded, ignore this bench and
@@ -61,6 +63,17 @@ impl<K: AutotuneKey> Tuner<K> {
 
     /// Registers the [results](AutotuneResult) from [execute_autotune()](Self::execute_autotune).
     pub fn register_autotune<Out: Send>(&mut self, result: AutotuneResult<K, Out>) -> Out {
+        let set = self.register_result(result);
+        AutotuneOperation::execute(set)
+    }
+
+    /// Registers the [results](AutotuneResult) without executing the fastest operation.
+    ///
+    /// Returns the fastest operation.
+    pub fn register_result<Out: Send>(
+        &mut self,
+        result: AutotuneResult<K, Out>,
+    ) -> Box<dyn AutotuneOperation<Out>> {
         self.tune_cache
             .cache_insert(result.key.clone(), result.fastest_index);
 
@@ -71,9 +84,26 @@ impl<K: AutotuneKey> Tuner<K> {
                 .persistent_cache_insert(result.key, checksum, result.fastest_index);
             self.tune_cache.save();
         }
-        let op = result.set.fastest(result.fastest_index);
 
-        AutotuneOperation::execute(op)
+        result.set.fastest(result.fastest_index)
+    }
+
+    #[cfg(autotune_persistent_cache)]
+    /// Export the tuned results, to be shared with other devices of the same kind.
+    pub fn export_results(&self) -> Vec<TuneResult<K>> {
+        self.tune_cache.persistent_results()
+    }
+
+    #[cfg(autotune_persistent_cache)]
+    /// Import tuned results, usually produced offline by [export_results](Self::export_results).
+    ///
+    /// The checksums of the imported results are validated before their first use, so results
+    /// produced with different operation sets are ignored.
+    pub fn import_results(&mut self, results: impl IntoIterator<Item = TuneResult<K>>) {
+        for result in results {
+            self.tune_cache.persistent_import(result);
+        }
+        self.tune_cache.save();
     }
 
     #[cfg(autotune_persistent_cache)]
@@ -91,13 +121,44 @@ impl<K: AutotuneKey> Tuner<K> {
         set: Box<dyn AutotuneOperationSet<K, Out>>,
         client: &ComputeClient<S, C>,
     ) -> AutotuneResult<K, Out>
+    where
+        S: ComputeServer + 'static,
+        C: ComputeChannel<S> + 'static,
+    {
+        self.autotune(set, client, false)
+    }
+
+    /// Benchmark every candidate of the operation set, ignoring its
+    /// [benchmark order](AutotuneOperationSet::benchmark_order) and
+    /// [early stopping](AutotuneOperationSet::early_stopping).
+    ///
+    /// Used when tuning offline, where finding the fastest operation matters more than the
+    /// time spent tuning.
+    pub fn execute_autotune_exhaustive<S, C, Out: Send + 'static>(
+        &self,
+        set: Box<dyn AutotuneOperationSet<K, Out>>,
+        client: &ComputeClient<S, C>,
+    ) -> AutotuneResult<K, Out>
+    where
+        S: ComputeServer + 'static,
+        C: ComputeChannel<S> + 'static,
+    {
+        self.autotune(set, client, true)
+    }
+
+    fn autotune<S, C, Out: Send + 'static>(
+        &self,
+        set: Box<dyn AutotuneOperationSet<K, Out>>,
+        client: &ComputeClient<S, C>,
+        exhaustive: bool,
+    ) -> AutotuneResult<K, Out>
     where
         S: ComputeServer + 'static,
         C: ComputeChannel<S> + 'static,
     {
         let (send, rec) = async_channel::bounded(1);
 
-        self.start_autotuning(send, set.as_ref(), client);
+        self.start_autotuning(send, set.as_ref(), client, exhaustive);
 
         if let Ok(msg) = rec.try_recv() {
             AutotuneResult {
@@ -119,19 +180,43 @@ impl<K: AutotuneKey> Tuner<K> {
         sender: Sender<AutotuneMessage<K>>,
         set: &dyn AutotuneOperationSet<K, Out>,
         client: &ComputeClient<S, C>,
+        exhaustive: bool,
     ) {
         let key = set.key();
         log::info!("Tuning {key}");
 
-        let autotunables: Vec<_> = set
-            .autotunables()
-            .into_iter()
-            .enumerate()
-            .map(|(index, op)| (index, op, set.should_run(&key, index)))
-            .collect();
-        let client = client.clone();
+        let mut autotunables: Vec<_> = set.autotunables().into_iter().map(Some).collect();
+        let (order, patience) = if exhaustive {
+            ((0..autotunables.len()).collect(), None)
+        } else {
+            (
+                set.benchmark_order(&key, autotunables.len()),
+                set.early_stopping(),
+            )
+        };
+
+        // Benchmark the candidates in the requested order, the others are skipped.
+        let mut ordered = Vec::with_capacity(autotunables.len());
+        for index in order {
+            if let Some(op) = autotunables.get_mut(index).and_then(Option::take) {
+                ordered.push((index, op, set.should_run(&key, index)));
+            }
+        }
+        for (index, op) in autotunables.into_iter().enumerate() {
+            if let Some(op) = op {
+                ordered.push((index, op, false));
+            }
+        }
+        let autotunables = ordered;
+
+        // Compiling the candidates can take longer than benchmarking them, so their kernels are
+        // compiled together, concurrently when the server can, instead of on their first run.
+        #[cfg(all(feature = "std", not(target_family = "wasm")))]
+        Self::prepare(&autotunables, client);
 
-        spawn_benchmark_task(async move {
+        let benchmark_client = client.clone();
+        let task = async move {
+            let client = benchmark_client;
             #[derive(new, Debug)]
             struct BenchResult {
                 name: String,
@@ -140,16 +225,33 @@ impl<K: AutotuneKey> Tuner<K> {
             }
 
             let mut bench_results = Vec::with_capacity(autotunables.len());
+            let mut fastest = Duration::MAX;
+            let mut num_without_improvement = 0;
 
             for (index, op, should_run) in autotunables.into_iter() {
+                let stopped = patience.is_some_and(|patience| num_without_improvement >= patience);
+                if should_run && stopped {
+                    log::info!("Early stopping, skipping {}", op.name());
+                }
+
                 let name = op.name().to_string();
-                let result = Self::run_benchmark(op, &client, should_run)
+                let result = Self::run_benchmark(op, &client, should_run && !stopped)
                     .await
                     .map(|durations| {
                         log::info!("Name: {name} => {}", durations);
                         BenchResult::new(name, index, BenchmarkComputations::new(&durations))
                     });
 
+                if should_run && !stopped {
+                    match &result {
+                        Ok(result) if result.computation.median < fastest => {
+                            fastest = result.computation.median;
+                            num_without_improvement = 0;
+                        }
+                        _ => num_without_improvement += 1,
+                    }
+                }
+
                 bench_results.push(result);
             }
 
@@ -200,7 +302,40 @@ impl<K: AutotuneKey> Tuner<K> {
             sender
                 .try_send(AutotuneMessage { key, fastest_index })
                 .expect("Autotune results channel closed");
-        });
+        };
+
+        // The candidates are benchmarked even when tuning a nested operation of a recorded one.
+        #[cfg(all(feature = "std", not(target_family = "wasm")))]
+        client.without_recording(|| spawn_benchmark_task(task));
+        #[cfg(not(all(feature = "std", not(target_family = "wasm"))))]
+        spawn_benchmark_task(task);
+    }
+
+    /// Compile the kernels of the candidates that are benchmarked, recorded from a run of each.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn prepare<S: ComputeServer, C: ComputeChannel<S>, Out>(
+        autotunables: &[(usize, Box<dyn AutotuneOperation<Out>>, bool)],
+        client: &ComputeClient<S, C>,
+    ) {
+        let mut kernels = Vec::new();
+        for (_, op, should_run) in autotunables {
+            if !should_run {
+                continue;
+            }
+
+            // The candidates failing here fail again when benchmarked, where it's handled.
+            let op = op.clone();
+            let recorded = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                client.record_kernels(|| {
+                    AutotuneOperation::execute(op);
+                })
+            }));
+            if let Ok(recorded) = recorded {
+                kernels.extend(recorded);
+            }
+        }
+
+        client.prepare(kernels);
     }
 
     async fn run_benchmark<S: ComputeServer, C: ComputeChannel<S>, Out>(
@@ -0,0 +1,171 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Condvar, Mutex, MutexGuard, Once, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{client::Launch, server::DeviceLost};
+
+/// The launches not known to be completed yet, reported when a sync exceeds its timeout.
+pub(crate) type PendingLaunches = Arc<spin::Mutex<alloc::vec::Vec<Launch>>>;
+
+/// Reports the kernels still running when a sync of a client exceeds its timeout.
+///
+/// The syncs of a client and its clones are watched by a single thread, started by the first sync
+/// and waiting for the nearest deadline, so it reports kernels stuck in an infinite loop even when
+/// the sync never returns. The report is only built once a timeout expired.
+pub(crate) struct Watchdog {
+    shared: Arc<Shared>,
+    started: Once,
+}
+
+#[derive(Default)]
+struct Shared {
+    syncs: Mutex<Syncs>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct Syncs {
+    next_id: u64,
+    armed: BTreeMap<u64, ArmedSync>,
+    stopped: bool,
+}
+
+struct ArmedSync {
+    timeout: Duration,
+    deadline: Instant,
+    state: SyncState,
+}
+
+enum SyncState {
+    Waiting(PendingLaunches),
+    Expired(DeviceLost),
+}
+
+/// A sync watched by the [watchdog](Watchdog), until it's [disarmed](Watchdog::disarm).
+pub(crate) struct WatchedSync(u64);
+
+impl Watchdog {
+    /// Start the timeout of a sync waiting for the `pending` launches.
+    pub(crate) fn arm(&self, timeout: Duration, pending: PendingLaunches) -> WatchedSync {
+        self.started.call_once(|| {
+            let shared = self.shared.clone();
+            std::thread::Builder::new()
+                .name("cubecl-watchdog".into())
+                .spawn(move || watch(&shared))
+                .expect("The watchdog thread should start");
+        });
+
+        let mut syncs = self.shared.lock();
+        let id = syncs.next_id;
+        syncs.next_id += 1;
+        syncs.armed.insert(
+            id,
+            ArmedSync {
+                timeout,
+                deadline: Instant::now() + timeout,
+                state: SyncState::Waiting(pending),
+            },
+        );
+        self.shared.changed.notify_one();
+
+        WatchedSync(id)
+    }
+
+    /// Stop watching a sync once it completed, returning the timeout error when it expired.
+    ///
+    /// The sync is removed under the same lock the watchdog expires it with, so a timeout
+    /// expiring right after the sync completed is either reported here or not at all.
+    pub(crate) fn disarm(&self, sync: WatchedSync) -> Option<DeviceLost> {
+        match self.shared.lock().armed.remove(&sync.0)?.state {
+            SyncState::Expired(error) => Some(error),
+            SyncState::Waiting(_) => None,
+        }
+    }
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self {
+            shared: Default::default(),
+            started: Once::new(),
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.changed.notify_one();
+    }
+}
+
+impl core::fmt::Debug for Watchdog {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Watchdog")
+            .field("armed", &self.shared.lock().armed.len())
+            .finish()
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Syncs> {
+        self.syncs.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Expire the syncs exceeding their timeout until the watchdog is dropped.
+fn watch(shared: &Shared) {
+    let mut syncs = shared.lock();
+
+    while !syncs.stopped {
+        let now = Instant::now();
+        let mut next_deadline = None;
+
+        for sync in syncs.armed.values_mut() {
+            let SyncState::Waiting(pending) = &sync.state else {
+                continue;
+            };
+            if sync.deadline > now {
+                next_deadline = Some(
+                    next_deadline.map_or(sync.deadline, |next: Instant| next.min(sync.deadline)),
+                );
+                continue;
+            }
+
+            let error = DeviceLost {
+                reason: report(sync.timeout, &pending.lock()),
+            };
+            log::error!("{}", error.reason);
+            sync.state = SyncState::Expired(error);
+        }
+
+        syncs = match next_deadline {
+            Some(deadline) => {
+                shared
+                    .changed
+                    .wait_timeout(syncs, deadline.saturating_duration_since(now))
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            None => shared
+                .changed
+                .wait(syncs)
+                .unwrap_or_else(PoisonError::into_inner),
+        };
+    }
+}
+
+fn report(timeout: Duration, launches: &[Launch]) -> alloc::string::String {
+    let mut reason = alloc::format!(
+        "The kernels didn't complete within {timeout:?}, the stuck kernels are among:"
+    );
+    for launch in launches {
+        match launch.cube_count {
+            Some((x, y, z)) => reason += &alloc::format!("\n  - {} ({x}, {y}, {z})", launch.kernel),
+            None => reason += &alloc::format!("\n  - {} (dynamic)", launch.kernel),
+        }
+    }
+    reason
+}
@@ -6,7 +6,8 @@ use cubecl_runtime::memory_management::{
 };
 use cubecl_runtime::storage::BytesStorage;
 use cubecl_runtime::tune::{AutotuneOperationSet, LocalTuner};
-use cubecl_runtime::{ComputeRuntime, DeviceProperties};
+use cubecl_runtime::{ComputeRuntime, DeviceProperties, HardwareProperties};
+use std::time::Duration;
 
 /// The dummy device.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -28,6 +29,26 @@ pub fn autotune_execute(
 }
 
 pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServer>> {
+    init_client_with_server(DummyServer::new)
+}
+
+/// A client whose allocations fail once `bytes` are in use.
+pub fn init_client_with_memory_limit(bytes: u64) -> DummyClient {
+    init_client_with_server(|memory_management| {
+        DummyServer::new(memory_management).with_memory_limit(bytes)
+    })
+}
+
+/// A client whose syncs block for the `delay`, as if kernels were still running.
+pub fn init_client_with_sync_delay(delay: Duration) -> DummyClient {
+    init_client_with_server(|memory_management| {
+        DummyServer::new(memory_management).with_sync_delay(delay)
+    })
+}
+
+fn init_client_with_server(
+    server: impl FnOnce(MemoryManagement<BytesStorage>) -> DummyServer,
+) -> DummyClient {
     let storage = BytesStorage::default();
     let mem_properties = MemoryDeviceProperties {
         max_page_size: 1024 * 1024 * 512,
@@ -38,9 +59,28 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
         mem_properties.clone(),
         MemoryConfiguration::default(),
     );
-    let server = DummyServer::new(memory_management);
+    let server = server(memory_management);
     let channel = MutexComputeChannel::new(server);
-    ComputeClient::new(channel, DeviceProperties::new(&[], mem_properties))
+    ComputeClient::new(
+        channel,
+        DeviceProperties::new(
+            &[],
+            mem_properties,
+            HardwareProperties {
+                max_shared_memory_size: 32768,
+                max_cube_dim: (1024, 1024, 64),
+                max_units_per_cube: 1024,
+                max_cube_count: (u16::MAX as u32, u16::MAX as u32, u16::MAX as u32),
+                max_bindings: u32::MAX,
+                max_buffer_size: 1024 * 1024 * 512,
+                subcube_size: Some(32),
+                num_multiprocessors: Some(16),
+                max_units_per_multiprocessor: Some(2048),
+                max_cubes_per_multiprocessor: Some(32),
+                registers_per_multiprocessor: Some(65536),
+            },
+        ),
+    )
 }
 
 pub fn client(device: &DummyDevice) -> DummyClient {
@@ -1,15 +1,15 @@
 use cubecl_runtime::{TimestampsError, TimestampsResult};
 use std::future::Future;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use super::DummyKernel;
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{MemoryUsage, OutOfMemoryError};
 use cubecl_runtime::server::CubeCount;
-use cubecl_runtime::storage::{BindingResource, ComputeStorage};
+use cubecl_runtime::storage::{BindingResource, BytesResource, ComputeStorage};
 use cubecl_runtime::{
     memory_management::MemoryManagement,
-    server::{Binding, ComputeServer, Handle},
+    server::{Binding, ComputeServer, Handle, Priority, Stream},
     storage::BytesStorage,
     ExecutionMode,
 };
@@ -20,6 +20,32 @@ use cubecl_runtime::{
 pub struct DummyServer {
     memory_management: MemoryManagement<BytesStorage>,
     timestamps: KernelTimestamps,
+    /// The buffers of the dispatches, reused so dispatching doesn't allocate.
+    bind_resources: Vec<BindingResource<Self>>,
+    resources: KernelResources,
+    /// The priority of each stream created after the default one.
+    streams: Vec<Priority>,
+    /// The number of bytes in use after which allocations fail as if the device was out of
+    /// memory.
+    memory_limit: Option<u64>,
+    /// The time each sync blocks, as if kernels were still running.
+    sync_delay: Duration,
+}
+
+/// The names of the kernels prepared by the dummy servers, in order.
+pub static PREPARED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// The allocation of the resources given to the kernels, empty between dispatches.
+#[derive(Default)]
+struct KernelResources(Vec<&'static BytesResource>);
+
+// The resources are only borrowed during a dispatch, the vector is empty otherwise.
+unsafe impl Send for KernelResources {}
+
+impl core::fmt::Debug for KernelResources {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("KernelResources")
+    }
 }
 
 #[derive(Debug)]
@@ -49,12 +75,33 @@ impl ComputeServer for DummyServer {
     type Storage = BytesStorage;
     type Feature = ();
 
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        format!("{kernel:?}")
+    }
+
     fn read(&mut self, binding: Binding) -> impl Future<Output = Vec<u8>> + 'static {
-        let bytes_handle = self.memory_management.get(binding.memory);
-        let bytes = self.memory_management.storage().get(&bytes_handle);
+        let bytes = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
         async move { bytes.read().to_vec() }
     }
 
+    fn binding_size(&mut self, binding: &Binding) -> u64 {
+        let handle = self.memory_management.get(binding.memory.clone());
+        handle.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
+
+    fn write(&mut self, binding: Binding, data: &[u8]) {
+        let bytes = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        bytes.write()[..data.len()].copy_from_slice(data);
+    }
+
     fn get_resource(&mut self, binding: Binding) -> BindingResource<Self> {
         let handle = self.memory_management.get(binding.clone().memory);
         BindingResource::new(binding, self.memory_management.storage().get(&handle))
@@ -79,21 +126,53 @@ impl ComputeServer for DummyServer {
         )
     }
 
+    fn try_empty(&mut self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        let usage = self.memory_management.memory_usage();
+        if let Some(limit) = self.memory_limit {
+            if usage.bytes_in_use + size as u64 > limit {
+                return Err(OutOfMemoryError {
+                    requested: size as u64,
+                    usage,
+                });
+            }
+        }
+        let memory = self.memory_management.try_reserve(size as u64, None)?;
+        Ok(Handle::new(memory, None, None))
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
         _count: CubeCount,
         bindings: Vec<Binding>,
         _mode: ExecutionMode,
+        _stream: Stream,
     ) {
-        let bind_resources = bindings
-            .into_iter()
-            .map(|binding| self.get_resource(binding))
-            .collect::<Vec<_>>();
+        let mut bind_resources = core::mem::take(&mut self.bind_resources);
+        bind_resources.extend(
+            bindings
+                .into_iter()
+                .map(|binding| self.get_resource(binding)),
+        );
 
-        let mut resources: Vec<_> = bind_resources.iter().map(|x| x.resource()).collect();
+        let mut resources = recycle(core::mem::take(&mut self.resources.0));
+        resources.extend(bind_resources.iter().map(|x| x.resource()));
 
         kernel.compute(&mut resources);
+
+        self.resources.0 = recycle(resources);
+        bind_resources.clear();
+        self.bind_resources = bind_resources;
+    }
+
+    fn prepare(&mut self, kernels: Vec<(Self::Kernel, ExecutionMode)>) {
+        let names = kernels.iter().map(|(kernel, _)| Self::kernel_name(kernel));
+        PREPARED.lock().unwrap().extend(names);
+    }
+
+    fn create_stream_with_priority(&mut self, priority: Priority) -> Stream {
+        self.streams.push(priority);
+        Stream::new(self.streams.len() as u64)
     }
 
     fn flush(&mut self) {
@@ -102,6 +181,7 @@ impl ComputeServer for DummyServer {
 
     #[allow(clippy::manual_async_fn)]
     fn sync(&mut self) -> impl Future<Output = ()> + 'static {
+        std::thread::sleep(self.sync_delay);
         async move {}
     }
 
@@ -137,6 +217,29 @@ impl DummyServer {
         Self {
             memory_management,
             timestamps: KernelTimestamps::Disabled,
+            bind_resources: Vec::new(),
+            resources: KernelResources::default(),
+            streams: Vec::new(),
+            memory_limit: None,
+            sync_delay: Duration::ZERO,
         }
     }
+
+    /// Make the allocations fail once `bytes` are in use.
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Make each sync block for the `delay`, as if kernels were still running.
+    pub fn with_sync_delay(mut self, delay: Duration) -> Self {
+        self.sync_delay = delay;
+        self
+    }
+}
+
+/// Empty the resources, keeping their allocation for resources of another lifetime.
+fn recycle<'a>(mut resources: Vec<&BytesResource>) -> Vec<&'a BytesResource> {
+    resources.clear();
+    resources.into_iter().map(|_| unreachable!()).collect()
 }
@@ -1,4 +1,8 @@
-use std::{thread::sleep, time::Duration};
+use std::{
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
 
 use cubecl_runtime::storage::BytesResource;
 
@@ -20,6 +24,14 @@ pub struct CacheTestSlowOn3;
 #[derive(Debug)]
 pub struct ParameteredKernel;
 
+/// Records its index each time it's computed, taking `delay_ms` to do so.
+#[derive(Debug)]
+pub struct CountingKernel {
+    pub index: usize,
+    pub delay_ms: u64,
+    pub executed: Arc<Mutex<Vec<usize>>>,
+}
+
 impl DummyKernel for DummyElementwiseAdditionSlowWrong {
     fn compute(&self, inputs: &mut [&BytesResource]) {
         // Slow and wrong on purpose, for tests
@@ -111,3 +123,11 @@ impl DummyKernel for ParameteredKernel {
         }
     }
 }
+
+impl DummyKernel for CountingKernel {
+    fn compute(&self, _inputs: &mut [&BytesResource]) {
+        // This is an artificial kernel designed for counting the benchmarked candidates only
+        self.executed.lock().unwrap().push(self.index);
+        sleep(Duration::from_millis(self.delay_ms));
+    }
+}
@@ -1,16 +1,16 @@
 #[cfg(autotune_persistent_cache)]
 use rand::{distributions::Alphanumeric, Rng};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[cfg(autotune_persistent_cache)]
 use cubecl_runtime::tune::compute_checksum;
 use cubecl_runtime::{
     server::Binding,
-    tune::{AutotuneOperation, AutotuneOperationSet},
+    tune::{AutotuneOperation, AutotuneOperationSet, SearchSpace},
 };
 
 use crate::dummy::{
-    CacheTestFastOn3, CacheTestSlowOn3, DummyClient, DummyElementwiseAddition,
+    CacheTestFastOn3, CacheTestSlowOn3, CountingKernel, DummyClient, DummyElementwiseAddition,
     DummyElementwiseMultiplication, DummyElementwiseMultiplicationSlowWrong,
     OneKernelAutotuneOperation,
 };
@@ -170,6 +170,65 @@ impl AutotuneOperationSet<String> for CacheTestAutotuneOperationSet {
     }
 }
 
+/// One candidate per point of the search space, each taking the given delay and recording its
+/// executions.
+pub struct SearchSpaceAutotuneOperationSet {
+    client: DummyClient,
+    key: String,
+    space: SearchSpace,
+    delays_ms: Vec<u64>,
+    pub executed: Arc<Mutex<Vec<usize>>>,
+}
+
+impl SearchSpaceAutotuneOperationSet {
+    #[allow(dead_code)]
+    pub fn new(client: DummyClient, key: &str, space: SearchSpace, delays_ms: Vec<u64>) -> Self {
+        Self {
+            client,
+            key: format!("{}-{}", "search_space", key),
+            space,
+            delays_ms,
+            executed: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl AutotuneOperationSet<String> for SearchSpaceAutotuneOperationSet {
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    fn autotunables(&self) -> Vec<Box<dyn AutotuneOperation>> {
+        (0..self.space.points().len())
+            .map(|index| {
+                let kernel = CountingKernel {
+                    index,
+                    delay_ms: self.delays_ms[index],
+                    executed: self.executed.clone(),
+                };
+                Box::new(OneKernelAutotuneOperation::new(
+                    Arc::new(kernel),
+                    self.client.clone(),
+                    Vec::new(),
+                    Vec::new(),
+                )) as Box<dyn AutotuneOperation>
+            })
+            .collect()
+    }
+
+    fn fastest(self: Box<Self>, fastest_index: usize) -> Box<dyn AutotuneOperation> {
+        self.autotunables()[fastest_index].clone()
+    }
+
+    fn benchmark_order(&self, _key: &String, _num_candidates: usize) -> Vec<usize> {
+        self.space.benchmark_order(&self.space.points())
+    }
+
+    fn early_stopping(&self) -> Option<usize> {
+        self.space.patience()
+    }
+}
+
 pub fn log_shape_input_key(shapes: &[Vec<usize>]) -> String {
     let mut hash = String::new();
     let lhs = &shapes[0];
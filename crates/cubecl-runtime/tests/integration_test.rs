@@ -1,22 +1,57 @@
 mod dummy;
 
-use std::sync::Arc;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::dummy::autotune_execute;
 use crate::dummy::TEST_TUNER;
-use crate::dummy::{client, DummyDevice, DummyElementwiseAddition};
+use crate::dummy::{
+    client, init_client, init_client_with_memory_limit, init_client_with_sync_delay, DummyDevice,
+    DummyElementwiseAddition, PREPARED,
+};
 
 #[cfg(autotune_persistent_cache)]
 use crate::dummy::{TUNER_DEVICE_ID, TUNER_PREFIX};
 
-use cubecl_runtime::server::CubeCount;
-use cubecl_runtime::ComputeRuntime;
+use cubecl_runtime::cancellation::{CancellationToken, Cancelled};
+use cubecl_runtime::client::Launch;
+use cubecl_runtime::server::{CubeCount, Handle, Priority, Stream};
+use cubecl_runtime::tune::SearchSpace;
+use cubecl_runtime::{ComputeRuntime, ExecutionMode};
 
 #[allow(unused)]
 use serial_test::serial;
 
 type Runtime = ComputeRuntime<DummyDevice, dummy::DummyServer, dummy::DummyChannel>;
 
+/// Counts the allocations of each thread, to check the hot paths don't allocate.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
 #[test]
 fn created_resource_is_the_same_when_read() {
     let client = client(&DummyDevice);
@@ -28,6 +63,108 @@ fn created_resource_is_the_same_when_read() {
     assert_eq!(resource, obtained_resource)
 }
 
+#[test]
+fn created_async_resource_is_the_same_when_read() {
+    let client = client(&DummyDevice);
+    let resource = Vec::from([0, 1, 2]);
+    let (handle, fence) = client.create_async(&resource);
+
+    cubecl_common::reader::read_sync(client.wait_fence(fence));
+    let obtained_resource = client.read(handle.binding());
+
+    assert_eq!(resource, obtained_resource)
+}
+
+#[test]
+fn created_resources_are_the_same_when_read_together() {
+    let client = client(&DummyDevice);
+    let resources = [Vec::from([0, 1, 2]), Vec::from([3, 4])];
+    let bindings = resources
+        .iter()
+        .map(|resource| client.create(resource).binding())
+        .collect::<Vec<_>>();
+
+    let obtained_resources = client.read_many(&bindings);
+
+    assert_eq!(obtained_resources, resources)
+}
+
+#[test]
+fn read_range_returns_only_the_requested_bytes() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2, 3, 4, 5]);
+
+    let obtained_resource = client.read_range(&handle, 2, 3);
+
+    assert_eq!(obtained_resource, Vec::from([2, 3, 4]))
+}
+
+#[test]
+fn write_range_overwrites_only_the_given_bytes() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2, 3, 4, 5]);
+
+    client.write_range(&handle, 1, &[9, 8]);
+
+    assert_eq!(client.read(handle.binding()), Vec::from([0, 9, 8, 3, 4, 5]))
+}
+
+#[test]
+fn fill_repeats_the_pattern_over_the_resource() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2, 3, 4]);
+
+    client.fill(&handle, &[7, 8]);
+
+    assert_eq!(client.read(handle.binding()), Vec::from([7, 8, 7, 8, 7]))
+}
+
+#[test]
+fn grow_keeps_the_content_of_the_resource() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2]);
+
+    let handle = client.grow(handle, 5);
+    let obtained_resource = client.read(handle.binding());
+
+    assert_eq!(obtained_resource.len(), 5);
+    assert_eq!(obtained_resource[..3], [0, 1, 2]);
+}
+
+#[test]
+fn try_empty_returns_the_requested_size_when_out_of_memory() {
+    let client = client(&DummyDevice);
+    let size = 1024 * 1024 * 1024 * 1024;
+
+    let error = client.try_empty(size).unwrap_err();
+
+    assert_eq!(error.requested, size as u64);
+}
+
+#[test]
+fn out_of_memory_hook_spills_a_resource_to_the_host_before_retrying() {
+    let client = init_client_with_memory_limit(1024);
+    let cached = Arc::new(Mutex::new(Some(client.create(&[1; 1024]))));
+    let spilled = Arc::new(Mutex::new(Vec::new()));
+
+    let (cached_hook, spilled_hook) = (cached.clone(), spilled.clone());
+    client.on_out_of_memory(|_| false);
+    client.on_out_of_memory(move |context| {
+        assert_eq!(context.error().requested, 512);
+        let Some(handle) = cached_hook.lock().unwrap().take() else {
+            return false;
+        };
+        *spilled_hook.lock().unwrap() = context.read(handle.clone().binding());
+        context.free(handle)
+    });
+
+    let handle = client.try_empty(512).unwrap();
+
+    assert!(cached.lock().unwrap().is_none());
+    assert_eq!(*spilled.lock().unwrap(), [1; 1024]);
+    assert_eq!(client.read(handle.binding()).len(), 512);
+}
+
 #[test]
 fn empty_allocates_memory() {
     let client = client(&DummyDevice);
@@ -38,6 +175,26 @@ fn empty_allocates_memory() {
     assert_eq!(empty_resource.len(), 4);
 }
 
+#[test]
+fn zero_initialized_empty_is_cleared_when_memory_is_reused() {
+    let client = client(&DummyDevice).zero_initialized(true);
+    core::mem::drop(client.create(&[1, 2, 3, 4]));
+
+    let handle = client.empty(4);
+
+    assert_eq!(client.read(handle.binding()), Vec::from([0, 0, 0, 0]));
+}
+
+#[test]
+fn zero_initialized_grow_clears_the_new_bytes() {
+    let client = client(&DummyDevice).zero_initialized(true);
+    let handle = client.create(&[1, 2, 3]);
+
+    let handle = client.grow(handle, 5);
+
+    assert_eq!(client.read(handle.binding()), Vec::from([1, 2, 3, 0, 0]));
+}
+
 #[test]
 fn execute_elementwise_addition() {
     let client = client(&DummyDevice);
@@ -56,6 +213,395 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test]
+fn execute_elementwise_addition_on_stream() {
+    let client = client(&DummyDevice);
+    let stream = client.create_stream();
+    let client_stream = client.clone().with_stream(stream);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client_stream.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+    client.wait(client_stream.event());
+
+    let obtained_resource = client.read(out.binding());
+
+    assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
+}
+
+#[test]
+fn clients_of_the_same_priority_share_their_stream() {
+    let client = init_client();
+
+    let interactive = client.clone().with_priority(Priority::Interactive);
+    let background = client.clone().with_priority(Priority::Background);
+    let normal = client.clone().with_priority(Priority::Normal);
+
+    assert_eq!(normal.stream(), Stream::default());
+    assert_ne!(interactive.stream(), Stream::default());
+    assert_ne!(interactive.stream(), background.stream());
+    assert_eq!(
+        background.with_priority(Priority::Interactive).stream(),
+        interactive.stream()
+    );
+}
+
+#[test]
+fn execute_compiled_kernel_does_not_allocate() {
+    let client = init_client();
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let kernel: Arc<dyn dummy::DummyKernel> = Arc::new(DummyElementwiseAddition);
+    let bindings = || {
+        vec![
+            lhs.clone().binding(),
+            rhs.clone().binding(),
+            out.clone().binding(),
+        ]
+    };
+
+    // The buffers of the server grow on the first dispatch.
+    client.execute(kernel.clone(), CubeCount::Static(1, 1, 1), bindings());
+    let (kernel, bindings) = (kernel.clone(), bindings());
+    let before = allocations();
+    client.execute(kernel, CubeCount::Static(1, 1, 1), bindings);
+
+    assert_eq!(allocations() - before, 0);
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn client_clones_launch_concurrently_from_many_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<dummy::DummyClient>();
+
+    let client = init_client();
+    let launches = Arc::new(AtomicUsize::new(0));
+    let launches_hook = launches.clone();
+    client.on_launch(move |_| {
+        launches_hook.fetch_add(1, Ordering::Relaxed);
+    });
+
+    let threads = (0..8u8)
+        .map(|thread| {
+            let client = client.clone().with_memory_tag(format!("thread {thread}"));
+            std::thread::spawn(move || {
+                for i in 0..100u8 {
+                    let lhs = client.create(&[thread, i, 1]);
+                    let rhs = client.create(&[i, thread, 2]);
+                    let out = client.empty(3);
+
+                    client.execute(
+                        Arc::new(DummyElementwiseAddition),
+                        CubeCount::Static(1, 1, 1),
+                        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+                    );
+
+                    assert_eq!(client.read(out.binding()), [thread + i, i + thread, 3]);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(launches.load(Ordering::Relaxed), 800);
+    assert_eq!(client.memory_tags().len(), 8);
+}
+
+#[test]
+fn runtime_creates_each_client_once_from_many_threads() {
+    static INITS: AtomicUsize = AtomicUsize::new(0);
+    let runtime = ComputeRuntime::<usize, dummy::DummyServer, dummy::DummyChannel>::new();
+
+    std::thread::scope(|scope| {
+        for thread in 0..16 {
+            let runtime = &runtime;
+            scope.spawn(move || {
+                for i in 0..64 {
+                    let client = runtime.client(&((thread + i) % 32), || {
+                        INITS.fetch_add(1, Ordering::Relaxed);
+                        init_client()
+                    });
+                    let handle = client.create(&[i as u8]);
+                    assert_eq!(client.read(handle.binding()), [i as u8]);
+                }
+            });
+        }
+    });
+
+    assert_eq!(INITS.load(Ordering::Relaxed), 32);
+}
+
+#[test]
+fn recorded_kernels_are_returned_instead_of_executed() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.create(&[0, 0, 0]);
+    let bindings = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let kernels = client.record_kernels(|| {
+        client.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            bindings.clone(),
+        );
+    });
+
+    assert_eq!(kernels.len(), 1);
+    assert_eq!(kernels[0].1, ExecutionMode::Checked);
+    assert_eq!(client.read(out.clone().binding()), Vec::from([0, 0, 0]));
+
+    // Launches after the recording are executed again.
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        bindings,
+    );
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn recorded_kernels_only_include_the_launches_of_the_recording_thread() {
+    let client = client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.create(&[0, 0, 0]);
+    let bindings = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let kernels = client.record_kernels(|| {
+        let client = client.clone();
+        std::thread::spawn(move || {
+            client.execute(
+                Arc::new(DummyElementwiseAddition),
+                CubeCount::Static(1, 1, 1),
+                bindings,
+            );
+        })
+        .join()
+        .unwrap();
+    });
+
+    assert!(kernels.is_empty());
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn healthy_device_is_neither_lost_nor_recovered() {
+    let client = client(&DummyDevice);
+    let handle = client.create(&[0, 1, 2]);
+
+    assert_eq!(client.device_lost(), None);
+    assert_eq!(cubecl_common::reader::read_sync(client.try_sync()), Ok(()));
+    assert_eq!(client.recover(), Ok(()));
+    assert_eq!(client.try_read(handle.binding()), Ok(Vec::from([0, 1, 2])));
+}
+
+#[test]
+fn sync_within_timeout_succeeds() {
+    let client = init_client().with_timeout(Duration::from_secs(60));
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.clone().binding()],
+    );
+
+    assert_eq!(cubecl_common::reader::read_sync(client.try_sync()), Ok(()));
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn sync_exceeding_timeout_reports_pending_kernels() {
+    let client = init_client_with_sync_delay(Duration::from_millis(200))
+        .with_timeout(Duration::from_millis(20));
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let add = || {
+        client.execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(3, 1, 1),
+            vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding(),
+            ],
+        )
+    };
+
+    add();
+    let error = cubecl_common::reader::read_sync(client.try_sync()).unwrap_err();
+    assert!(error.reason.contains("within 20ms"), "{}", error.reason);
+    assert!(
+        error.reason.contains("DummyElementwiseAddition (3, 1, 1)"),
+        "{}",
+        error.reason
+    );
+
+    // A sync can't return the error, so the next fallible operation does.
+    add();
+    cubecl_common::reader::read_sync(client.sync());
+    assert!(client.try_read(out.clone().binding()).is_err());
+    assert_eq!(client.try_read(out.binding()), Ok(Vec::from([4, 5, 6])));
+}
+
+#[test]
+fn cancelled_client_drops_its_launches() {
+    let token = CancellationToken::new();
+    let client = client(&DummyDevice).with_cancellation(token.clone());
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.create(&[0, 0, 0]);
+    let add = |out: &Handle| {
+        client.clone().execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding(),
+            ],
+        )
+    };
+
+    token.cancel();
+    add(&out);
+    assert!(client.is_cancelled());
+    assert_eq!(
+        client.try_execute(
+            Arc::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding()
+            ],
+        ),
+        Err(Cancelled)
+    );
+    assert_eq!(token.dropped_launches(), 2);
+    assert_eq!(client.read(out.clone().binding()), Vec::from([0, 0, 0]));
+
+    token.reset();
+    assert_eq!(token.dropped_launches(), 0);
+    add(&out);
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn launch_hooks_receive_the_kernel_and_its_config() {
+    let client = init_client();
+    let launches = Arc::new(Mutex::new(Vec::new()));
+    let launches_hook = launches.clone();
+    client.on_launch(move |launch| launches_hook.lock().unwrap().push(launch.clone()));
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 2, 3),
+        vec![lhs.binding(), rhs.binding(), out.binding()],
+    );
+
+    assert_eq!(
+        *launches.lock().unwrap(),
+        vec![Launch {
+            kernel: "DummyElementwiseAddition".to_string(),
+            cube_count: Some((1, 2, 3)),
+            mode: ExecutionMode::Checked,
+            stream: client.stream(),
+        }]
+    );
+}
+
+#[test]
+fn complete_hooks_are_called_on_sync() {
+    let client = init_client();
+    let completed = Arc::new(Mutex::new(Vec::new()));
+    let completed_hook = completed.clone();
+    client.on_complete(move |launch, duration| {
+        completed_hook
+            .lock()
+            .unwrap()
+            .push((launch.kernel.clone(), duration))
+    });
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.binding()],
+    );
+    assert!(completed.lock().unwrap().is_empty());
+
+    cubecl_common::reader::read_sync(client.sync());
+    assert_eq!(
+        *completed.lock().unwrap(),
+        vec![("DummyElementwiseAddition".to_string(), None)]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn profiled_launches_are_completed_with_their_duration() {
+    let client = init_client().profile_launches(true);
+    let durations = Arc::new(Mutex::new(Vec::new()));
+    let durations_hook = durations.clone();
+    client.on_complete(move |_, duration| durations_hook.lock().unwrap().push(duration));
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        vec![lhs.binding(), rhs.binding(), out.binding()],
+    );
+
+    let durations = durations.lock().unwrap();
+    assert_eq!(durations.len(), 1);
+    assert!(durations[0].is_some());
+}
+
+#[test]
+fn memory_tags_track_the_peak_usage() {
+    let client = init_client();
+    let layer = client.clone().with_memory_tag("layer");
+    let first = layer.create(&[0, 1, 2, 3]);
+    let second = layer.empty(8);
+    let untagged = client.empty(16);
+
+    let usage = layer.memory_tags()["layer"];
+    assert_eq!(usage.bytes_in_use, 12);
+    assert_eq!(usage.bytes_peak, 12);
+    assert_eq!(usage.allocations, 2);
+
+    core::mem::drop((second, untagged));
+    let usage = client.memory_tags()["layer"];
+    assert_eq!(usage.bytes_in_use, 4);
+    assert_eq!(usage.bytes_peak, 12);
+
+    client.reset_memory_peaks();
+    assert_eq!(client.memory_tags()["layer"].bytes_peak, 4);
+    core::mem::drop(first);
+    assert_eq!(client.memory_tags()["layer"].bytes_in_use, 0);
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "std")]
@@ -79,6 +625,31 @@ fn autotune_basic_addition_execution() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]));
 }
 
+#[test]
+#[serial]
+#[cfg(feature = "std")]
+fn autotune_prepares_the_candidates_before_benchmarking_them() {
+    TEST_TUNER.clear();
+    PREPARED.lock().unwrap().clear();
+    let client = client(&DummyDevice);
+
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let addition_autotune_kernel =
+        dummy::AdditionAutotuneOperationSet::new(client.clone(), shapes, handles);
+    autotune_execute(&client, Box::new(addition_autotune_kernel));
+
+    let prepared = PREPARED.lock().unwrap().clone();
+    assert_eq!(prepared.len(), 2);
+    assert!(prepared[0].contains("DummyElementwiseAddition"));
+    assert!(prepared[1].contains("DummyElementwiseAdditionSlowWrong"));
+    assert_eq!(client.read(out.binding()), Vec::from([4, 5, 6]));
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "std")]
@@ -102,6 +673,68 @@ fn autotune_basic_multiplication_execution() {
     assert_eq!(obtained_resource, Vec::from([0, 4, 8]));
 }
 
+/// The candidates in the order they were first executed.
+#[cfg(feature = "std")]
+fn first_executions(executed: &[usize]) -> Vec<usize> {
+    let mut order = Vec::new();
+    for index in executed {
+        if !order.contains(index) {
+            order.push(*index);
+        }
+    }
+    order
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "std")]
+fn autotune_benchmarks_the_candidates_in_prior_order() {
+    TEST_TUNER.clear();
+    let client = client(&DummyDevice);
+
+    let space = SearchSpace::new()
+        .param("tile", [8, 16, 32, 64])
+        .prior(|point| match point.get("tile") {
+            32 => 3.0,
+            8 => 2.0,
+            64 => 1.0,
+            _ => 0.0,
+        });
+    let set =
+        dummy::SearchSpaceAutotuneOperationSet::new(client.clone(), "prior", space, vec![0; 4]);
+    let executed = set.executed.clone();
+    autotune_execute(&client, Box::new(set));
+
+    assert_eq!(first_executions(&executed.lock().unwrap()), [2, 0, 3, 1]);
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "std")]
+fn autotune_stops_early_without_improvement() {
+    TEST_TUNER.clear();
+    let client = client(&DummyDevice);
+
+    let space = SearchSpace::new()
+        .param("tile", [8, 16, 32, 64, 128])
+        .prior(|point| if point.get("tile") == 32 { 1.0 } else { 0.0 })
+        .early_stopping(2);
+    // The first benchmarked candidate is the fastest one, so the two next ones don't improve it.
+    let set = dummy::SearchSpaceAutotuneOperationSet::new(
+        client.clone(),
+        "early_stopping",
+        space,
+        vec![5, 5, 0, 5, 5],
+    );
+    let executed = set.executed.clone();
+    autotune_execute(&client, Box::new(set));
+
+    let executed = executed.lock().unwrap();
+    assert_eq!(first_executions(&executed), [2, 0, 1]);
+    // The fastest candidate is executed once tuned.
+    assert_eq!(executed.last(), Some(&2));
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "std")]
@@ -299,3 +932,69 @@ fn autotune_cache_different_checksums_return_a_cache_miss() {
     // so CacheTestSlowOn3 (but faster on 4) should be used, returning rhs
     assert_eq!(obtained_resource, Vec::from([5, 6, 7, 8]));
 }
+
+#[test]
+#[serial]
+#[cfg(autotune_persistent_cache)]
+fn autotune_offline_results_are_reused_after_import() {
+    TEST_TUNER.clear();
+    let client = client(&DummyDevice);
+    let device_id = TUNER_DEVICE_ID.to_string();
+
+    // in this test both shapes [1,3] and [1,4] end up with the same key name
+    // which is 'cache_test-1,4'
+    let shapes_1 = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let lhs_1 = client.create(&[0, 1, 2]);
+    let rhs_1 = client.create(&[4, 4, 4]);
+    let out_1 = client.empty(3);
+    let handles_1 = vec![lhs_1.binding(), rhs_1.binding(), out_1.binding()];
+    let cache_test_autotune_kernel_1 =
+        dummy::CacheTestAutotuneOperationSet::new(client.clone(), shapes_1, handles_1);
+    TEST_TUNER.tune_offline(&device_id, &client, Box::new(cache_test_autotune_kernel_1));
+
+    let results = TEST_TUNER.export_results(&device_id);
+    let path = std::env::temp_dir().join("cubecl-autotune-offline-results.json");
+    results.save(&path).unwrap();
+    let loaded = cubecl_runtime::tune::TuneResults::<String>::load(&path).unwrap();
+    assert_eq!(results, loaded);
+
+    TEST_TUNER.clear();
+    TEST_TUNER.import_results(&device_id, loaded);
+
+    let shapes_2 = vec![vec![1, 4], vec![1, 4], vec![1, 4]];
+    let lhs_2 = client.create(&[0, 1, 2, 3]);
+    let rhs_2 = client.create(&[5, 6, 7, 8]);
+    let out_2 = client.empty(4);
+    let handles_2 = vec![lhs_2.binding(), rhs_2.binding(), out_2.clone().binding()];
+    let cache_test_autotune_kernel_2 =
+        dummy::CacheTestAutotuneOperationSet::new(client.clone(), shapes_2, handles_2);
+    autotune_execute(&client, Box::new(cache_test_autotune_kernel_2));
+
+    let obtained_resource = client.read(out_2.binding());
+
+    // Imported result should be hit, so CacheTestFastOn3 should be used, returning lhs
+    assert_eq!(obtained_resource, Vec::from([0, 1, 2, 3]));
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "std")]
+fn autotune_deterministic_selects_the_first_operation() {
+    TEST_TUNER.clear();
+    let client = client(&DummyDevice).deterministic(true);
+
+    let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let multiplication_autotune_kernel =
+        dummy::MultiplicationAutotuneOperationSet::new(client.clone(), shapes, handles);
+    autotune_execute(&client, Box::new(multiplication_autotune_kernel));
+
+    let obtained_resource = client.read(out.binding());
+
+    // The slow kernel comes first, even though it isn't the fastest
+    assert_eq!(obtained_resource, Vec::from([0, 1, 2]));
+}
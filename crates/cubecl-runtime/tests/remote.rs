@@ -0,0 +1,194 @@
+// The dummy runtime is shared with the integration tests.
+#[allow(dead_code)]
+mod dummy;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+
+use crate::dummy::{init_client, DummyClient, DummyElementwiseAddition};
+use cubecl_runtime::remote::{
+    LaunchDescriptor, RemoteClient, RemoteError, RemoteLaunch, RemoteServer, Request, Response,
+    Scalar,
+};
+
+fn addition(client: &DummyClient, launch: &RemoteLaunch<'_>) -> Result<(), RemoteError> {
+    let bindings = (0..3)
+        .map(|position| {
+            launch
+                .handle(position)
+                .map(|handle| handle.clone().binding())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    client.execute(
+        Arc::new(DummyElementwiseAddition),
+        launch.cube_count(),
+        bindings,
+    );
+    Ok(())
+}
+
+fn server() -> RemoteServer<dummy::DummyServer, dummy::DummyChannel> {
+    let mut server = RemoteServer::new(init_client());
+    server.register("addition", addition);
+    server
+}
+
+/// Connect to a server listening on its own thread.
+fn connect() -> impl Fn() -> RemoteClient<TcpStream> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || server().listen(&listener));
+
+    move || RemoteClient::new(TcpStream::connect(address).unwrap())
+}
+
+fn addition_of(lhs: &str, rhs: &str, out: &str) -> LaunchDescriptor {
+    LaunchDescriptor::new("addition", (1, 1, 1), (3, 1, 1))
+        .binding(lhs)
+        .binding(rhs)
+        .binding(out)
+}
+
+#[test]
+fn descriptors_are_serialized() {
+    let descriptor = addition_of("lhs", "rhs", "out").scalar(Scalar::F32(0.5));
+
+    let json = serde_json::to_string(&descriptor).unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<LaunchDescriptor>(&json).unwrap(),
+        descriptor
+    );
+}
+
+#[test]
+fn requests_are_executed() {
+    let server = server();
+
+    server.execute(Request::Create {
+        name: "lhs".into(),
+        data: vec![0, 1, 2],
+    });
+    server.execute(Request::Create {
+        name: "rhs".into(),
+        data: vec![4, 4, 4],
+    });
+    server.execute(Request::Empty {
+        name: "out".into(),
+        size: 3,
+    });
+    let launched = server.execute(Request::Launch(addition_of("lhs", "rhs", "out")));
+
+    assert_eq!(launched, Response::Done);
+    assert_eq!(
+        server.execute(Request::Read { name: "out".into() }),
+        Response::Data(vec![4, 5, 6])
+    );
+}
+
+#[test]
+fn clients_share_the_buffers_of_the_server() {
+    let connect = connect();
+    let mut producer = connect();
+    let mut consumer = connect();
+
+    producer.create("lhs", &[0, 1, 2]).unwrap();
+    producer.create("rhs", &[4, 4, 4]).unwrap();
+    consumer.empty("out", 3).unwrap();
+    consumer.launch(addition_of("lhs", "rhs", "out")).unwrap();
+    consumer.sync().unwrap();
+
+    assert_eq!(producer.read("out").unwrap(), vec![4, 5, 6]);
+}
+
+#[test]
+fn unknown_kernels_and_buffers_are_errors() {
+    let mut client = connect()();
+    client.create("buffer", &[1]).unwrap();
+
+    let unknown_kernel = client.launch(LaunchDescriptor::new("missing", (1, 1, 1), (1, 1, 1)));
+    let unknown_buffer = client.launch(addition_of("buffer", "buffer", "missing"));
+    client.free("buffer").unwrap();
+    let freed = client.read("buffer");
+
+    assert_eq!(
+        unknown_kernel,
+        Err(RemoteError::UnknownKernel("missing".into()))
+    );
+    assert_eq!(
+        unknown_buffer,
+        Err(RemoteError::UnknownBuffer("missing".into()))
+    );
+    assert_eq!(freed, Err(RemoteError::UnknownBuffer("buffer".into())));
+}
+
+#[test]
+fn buffers_are_sent_as_binary_payloads() {
+    let mut client = connect()();
+    let data = (0..=255).collect::<Vec<u8>>();
+
+    client.create("buffer", &data).unwrap();
+
+    assert_eq!(client.read("buffer").unwrap(), data);
+}
+
+#[test]
+fn oversized_messages_are_rejected_before_allocating() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || server().with_max_frame_size(1024).listen(&listener));
+
+    // A header announcing a payload of 4 GiB.
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(&2u32.to_le_bytes()).unwrap();
+    stream.write_all(&(4u64 << 30).to_le_bytes()).unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+
+    let too_large =
+        RemoteClient::new(TcpStream::connect(address).unwrap()).create("large", &[0; 2048]);
+    let mut client = RemoteClient::new(TcpStream::connect(address).unwrap());
+    client.create("small", &[1; 16]).unwrap();
+
+    assert!(response.is_empty(), "The connection should be closed");
+    assert!(matches!(too_large, Err(RemoteError::Transport(_))));
+    assert_eq!(client.read("small").unwrap(), vec![1; 16]);
+}
+
+#[test]
+fn oversized_buffers_are_rejected() {
+    let server = server().with_max_frame_size(1024);
+
+    let response = server.execute(Request::Empty {
+        name: "huge".into(),
+        size: usize::MAX,
+    });
+
+    assert!(matches!(
+        response,
+        Response::Error(RemoteError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn panics_are_returned_without_stopping_the_server() {
+    let mut server = server();
+    server.register("panic", |_, _| panic!("Invalid launch"));
+
+    let panicked = server.execute(Request::Launch(LaunchDescriptor::new(
+        "panic",
+        (1, 1, 1),
+        (1, 1, 1),
+    )));
+    let created = server.execute(Request::Create {
+        name: "buffer".into(),
+        data: vec![1, 2],
+    });
+
+    assert_eq!(
+        panicked,
+        Response::Error(RemoteError::Panic("Invalid launch".into()))
+    );
+    assert_eq!(created, Response::Done);
+}
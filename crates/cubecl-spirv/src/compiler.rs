@@ -9,8 +9,8 @@ use std::{
 };
 
 use cubecl_core::{
-    ir::{HybridAllocator, KernelDefinition, LocalAllocator},
-    Compiler, ExecutionMode,
+    ir::{HybridAllocator, KernelDefinition, LocalAllocator, SourceLocation},
+    CompilationOptions, Compiler, ExecutionMode,
 };
 use rspirv::{
     dr::{Builder, InsertPoint, Instruction, Module, Operand},
@@ -100,7 +100,11 @@ impl<T: SpirvTarget> DerefMut for SpirvCompiler<T> {
 impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
     type Representation = SpirvKernel;
 
-    fn compile(kernel: KernelDefinition, mode: ExecutionMode) -> Self::Representation {
+    fn compile(
+        kernel: KernelDefinition,
+        _options: &CompilationOptions,
+        mode: ExecutionMode,
+    ) -> Self::Representation {
         let num_bindings = kernel.inputs.len() + kernel.outputs.len() + kernel.named.len();
         let (module, optimizer) = Self {
             mode,
@@ -121,10 +125,6 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
     fn local_allocator() -> impl LocalAllocator {
         HybridAllocator::default()
     }
-
-    fn max_shared_memory_size() -> usize {
-        32768
-    }
 }
 
 impl<Target: SpirvTarget> Debug for SpirvCompiler<Target> {
@@ -291,4 +291,17 @@ impl<Target: SpirvTarget> SpirvCompiler<Target> {
             self.name(var, name);
         }
     }
+
+    /// Attach the source location to the following instructions with `OpLine`.
+    pub fn debug_line(&mut self, location: SourceLocation) {
+        let file = match self.state.debug_files.get(&location.file) {
+            Some(file) => *file,
+            None => {
+                let file = self.string(location.file.clone());
+                self.state.debug_files.insert(location.file, file);
+                file
+            }
+        };
+        self.line(file, location.line, location.column);
+    }
 }
@@ -31,6 +31,8 @@ pub trait TargetExtensions<T: SpirvTarget> {
     fn s_clamp(b: &mut SpirvCompiler<T>, ty: Word, input: Word, min: Word, max: Word, out: Word);
     fn magnitude(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn normalize(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn pack_half_2x16(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn unpack_half_2x16(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
 }
 
 mod glcompute {
@@ -163,5 +165,13 @@ mod glcompute {
         fn normalize(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
             ext_op(b, ty, out, GLSLstd450Normalize, [input]);
         }
+
+        fn pack_half_2x16(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450PackHalf2x16, [input]);
+        }
+
+        fn unpack_half_2x16(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            ext_op(b, ty, out, GLSLstd450UnpackHalf2x16, [input]);
+        }
     }
 }
@@ -21,6 +21,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             Operation::Subcube(subcube) => self.compile_subcube(subcube),
             Operation::Synchronization(sync) => self.compile_sync(sync),
             Operation::CoopMma(cmma) => self.compile_cmma(cmma),
+            Operation::Call(_) => unreachable!("Device functions are always inlined for SPIR-V"),
+            Operation::Location(location) => self.debug_line(location),
         }
     }
 
@@ -354,12 +356,83 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             Operator::ShiftRight(op) => self.compile_binary_op(op, |b, _, ty, lhs, rhs, out| {
                 b.shift_right_logical(ty, Some(out), lhs, rhs).unwrap();
             }),
+            Operator::RotateLeft(op) => {
+                self.compile_binary_op(op, |b, out_ty, ty, lhs, rhs, out| {
+                    b.compile_rotate(&out_ty, ty, lhs, rhs, out, true);
+                })
+            }
+            Operator::RotateRight(op) => {
+                self.compile_binary_op(op, |b, out_ty, ty, lhs, rhs, out| {
+                    b.compile_rotate(&out_ty, ty, lhs, rhs, out, false);
+                })
+            }
+            Operator::BitfieldExtract(op) => {
+                let input = self.compile_variable(op.input);
+                let out = self.compile_variable(op.out);
+                let out_ty = out.item();
+
+                let input = self.read_as(&input, &out_ty);
+                let (offset, count) = self.compile_bitfield_range(&out_ty, op.offset, op.count);
+                let out_id = self.write_id(&out);
+
+                let ty = out_ty.id(self);
+
+                match out_ty.elem() {
+                    Elem::Int(_, true) => {
+                        self.bit_field_s_extract(ty, Some(out_id), input, offset, count)
+                    }
+                    _ => self.bit_field_u_extract(ty, Some(out_id), input, offset, count),
+                }
+                .unwrap();
+                self.write(&out, out_id);
+            }
+            Operator::BitfieldInsert(op) => {
+                let base = self.compile_variable(op.base);
+                let insert = self.compile_variable(op.insert);
+                let out = self.compile_variable(op.out);
+                let out_ty = out.item();
+
+                let base = self.read_as(&base, &out_ty);
+                let insert = self.read_as(&insert, &out_ty);
+                let (offset, count) = self.compile_bitfield_range(&out_ty, op.offset, op.count);
+                let out_id = self.write_id(&out);
+
+                let ty = out_ty.id(self);
+
+                self.bit_field_insert(ty, Some(out_id), base, insert, offset, count)
+                    .unwrap();
+                self.write(&out, out_id);
+            }
             Operator::Bitcast(op) => self.compile_unary_op(op, |b, _, ty, input, out| {
                 b.bitcast(ty, Some(out), input).unwrap();
             }),
+            Operator::PackF16(op) => self.compile_unary_op(op, |b, _, ty, input, out| {
+                T::pack_half_2x16(b, ty, input, out);
+            }),
+            Operator::UnpackF16(op) => self.compile_unary_op(op, |b, _, ty, input, out| {
+                T::unpack_half_2x16(b, ty, input, out);
+            }),
+            Operator::PackBf16(op) => self.compile_unary_op(op, |b, _, ty, input, out| {
+                b.compile_pack_bf16(ty, input, out);
+            }),
+            Operator::UnpackBf16(op) => self.compile_unary_op(op, |b, _, ty, input, out| {
+                b.compile_unpack_bf16(ty, input, out);
+            }),
             Operator::Erf(op) => self.compile_unary_op_cast(op, |b, out_ty, ty, input, out| {
                 b.compile_erf(out_ty, ty, input, out);
             }),
+            Operator::Erfc(op) => self.compile_unary_op_cast(op, |b, out_ty, ty, input, out| {
+                b.compile_erfc(out_ty, ty, input, out);
+            }),
+            Operator::Expm1(op) => self.compile_unary_op_cast(op, |b, out_ty, ty, input, out| {
+                b.compile_expm1(out_ty, ty, input, out);
+            }),
+            Operator::Tgamma(op) => self.compile_unary_op_cast(op, |b, out_ty, ty, input, out| {
+                b.compile_tgamma(out_ty, ty, input, out);
+            }),
+            Operator::Lgamma(op) => self.compile_unary_op_cast(op, |b, out_ty, ty, input, out| {
+                b.compile_lgamma(out_ty, ty, input, out);
+            }),
 
             // Extension functions
             Operator::Normalize(op) => {
@@ -467,6 +540,26 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 self.write(&out, out_id);
             }
 
+            Operator::SaturatingAdd(op) => {
+                self.compile_binary_op(op, |b, out_ty, ty, lhs, rhs, out| {
+                    b.compile_saturating(&out_ty, ty, lhs, rhs, out, false);
+                })
+            }
+            Operator::SaturatingSub(op) => {
+                self.compile_binary_op(op, |b, out_ty, ty, lhs, rhs, out| {
+                    b.compile_saturating(&out_ty, ty, lhs, rhs, out, true);
+                })
+            }
+            // Integer arithmetic wraps around without the `NoSignedWrap` decoration.
+            Operator::WrappingAdd(op) => self.compile_binary_op(op, |b, _, ty, lhs, rhs, out| {
+                b.i_add(ty, Some(out), lhs, rhs).unwrap();
+            }),
+            Operator::WrappingSub(op) => self.compile_binary_op(op, |b, _, ty, lhs, rhs, out| {
+                b.i_sub(ty, Some(out), lhs, rhs).unwrap();
+            }),
+            Operator::WrappingMul(op) => self.compile_binary_op(op, |b, _, ty, lhs, rhs, out| {
+                b.i_mul(ty, Some(out), lhs, rhs).unwrap();
+            }),
             Operator::Max(op) => {
                 self.compile_binary_op(op, |b, out_ty, ty, lhs, rhs, out| match out_ty.elem() {
                     Elem::Int(_, false) => T::u_max(b, ty, lhs, rhs, out),
@@ -718,6 +811,67 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 self.composite_construct(ty, Some(out_id), values).unwrap();
                 self.write(&out, out_id);
             }
+            Operator::MatrixMul(_) | Operator::Transpose(_) | Operator::Determinant(_) => {
+                unreachable!("Matrix operations are unrolled for SPIR-V")
+            }
+            Operator::Swizzle(op) => {
+                let input = self.compile_variable(op.input);
+                let out = self.compile_variable(op.out);
+                let ty = out.item().id(self);
+                let in_id = self.read(&input);
+                let out_id = self.write_id(&out);
+
+                match (input.item(), op.components.as_slice()) {
+                    // Scalars only have one component, which is broadcasted.
+                    (Item::Scalar(_), [_]) => self.copy_object(ty, Some(out_id), in_id),
+                    (Item::Scalar(_), components) => {
+                        let values = vec![in_id; components.len()];
+                        self.composite_construct(ty, Some(out_id), values)
+                    }
+                    (_, [component]) => {
+                        self.composite_extract(ty, Some(out_id), in_id, vec![*component])
+                    }
+                    (_, components) => {
+                        let components = components.to_vec();
+                        self.vector_shuffle(ty, Some(out_id), in_id, in_id, components)
+                    }
+                }
+                .unwrap();
+                self.write(&out, out_id);
+            }
+            Operator::SwizzleAssign(op) => {
+                let input = self.compile_variable(op.input);
+                let out = self.compile_variable(op.out);
+                let out_item = out.item();
+                let ty = out_item.id(self);
+                let in_id = self.read(&input);
+                let current = self.read(&out);
+
+                let value = match (&out_item, input.item()) {
+                    (Item::Scalar(_), _) => in_id,
+                    (Item::Vector(_, size), Item::Vector(..)) => {
+                        // Shuffle the written components from the input, keeping the others.
+                        let size = *size;
+                        let components = (0..size).map(|component| {
+                            op.components
+                                .iter()
+                                .position(|it| *it == component)
+                                .map(|i| size + i as u32)
+                                .unwrap_or(component)
+                        });
+                        self.vector_shuffle(ty, None, current, in_id, components)
+                            .unwrap()
+                    }
+                    _ => op.components.iter().fold(current, |composite, component| {
+                        self.composite_insert(ty, None, in_id, composite, vec![*component])
+                            .unwrap()
+                    }),
+                };
+
+                let out_id = self.write_id(&out);
+                self.copy_object(ty, Some(out_id), value).unwrap();
+                self.write(&out, out_id);
+            }
             Operator::Copy(op) => {
                 let input = self.compile_variable(op.input);
                 let in_index = self.compile_variable(op.in_index);
@@ -739,7 +893,7 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                         .unwrap();
                 }
             }
-            Operator::CopyBulk(op) => {
+            Operator::CopyBulk(op) | Operator::CopyBulkAsync(op) => {
                 self.capabilities.insert(Capability::Addresses);
                 let input = self.compile_variable(op.input);
                 let in_index = self.compile_variable(op.in_index);
@@ -935,4 +1089,399 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
         let pos = erf(self, input);
         self.select(ty, Some(out), cond, neg, pos).unwrap();
     }
+
+    /// The complementary error function, with the Chebyshev fit of Numerical Recipes (fractional
+    /// error under 1.2e-7). Unlike `1 - erf(x)`, the tail for large inputs keeps its precision.
+    fn compile_erfc(&mut self, out_ty: Item, ty: Word, input: Word, out: Word) {
+        let bool = self.bool_ty(&out_ty);
+        let zero = self.const_float(&out_ty, 0.0);
+        let half = self.const_float(&out_ty, 0.5);
+        let one = self.const_float(&out_ty, 1.0);
+        let two = self.const_float(&out_ty, 2.0);
+
+        let abs = self.id();
+        T::f_abs(self, ty, input, abs);
+        let t_0 = self.f_mul(ty, None, half, abs).unwrap();
+        let t_1 = self.f_add(ty, None, one, t_0).unwrap();
+        let t = self.f_div(ty, None, one, t_1).unwrap();
+        let poly = self.horner(
+            &out_ty,
+            ty,
+            t,
+            &[
+                0.17087277,
+                -0.82215223,
+                1.48851587,
+                -1.13520398,
+                0.27886807,
+                -0.18628806,
+                0.09678418,
+                0.37409196,
+                1.00002368,
+                -1.26551223,
+            ],
+        );
+        let square = self.f_mul(ty, None, abs, abs).unwrap();
+        let arg = self.f_sub(ty, None, poly, square).unwrap();
+        let exp = self.id();
+        T::exp(self, ty, arg, exp);
+        let pos = self.f_mul(ty, None, t, exp).unwrap();
+        let neg = self.f_sub(ty, None, two, pos).unwrap();
+
+        let cond = self.f_ord_less_than(bool, None, input, zero).unwrap();
+        self.select(ty, Some(out), cond, neg, pos).unwrap();
+    }
+
+    /// `exp(x) - 1`, with a Taylor polynomial of degree 8 for `|x| < 0.5` where the subtraction
+    /// would cancel the significant digits.
+    fn compile_expm1(&mut self, out_ty: Item, ty: Word, input: Word, out: Word) {
+        let bool = self.bool_ty(&out_ty);
+        let half = self.const_float(&out_ty, 0.5);
+        let one = self.const_float(&out_ty, 1.0);
+
+        let poly = self.horner(
+            &out_ty,
+            ty,
+            input,
+            &[
+                1.0 / 40320.0,
+                1.0 / 5040.0,
+                1.0 / 720.0,
+                1.0 / 120.0,
+                1.0 / 24.0,
+                1.0 / 6.0,
+                0.5,
+                1.0,
+            ],
+        );
+        let small = self.f_mul(ty, None, input, poly).unwrap();
+        let exp = self.id();
+        T::exp(self, ty, input, exp);
+        let large = self.f_sub(ty, None, exp, one).unwrap();
+
+        let abs = self.id();
+        T::f_abs(self, ty, input, abs);
+        let cond = self.f_ord_less_than(bool, None, abs, half).unwrap();
+        self.select(ty, Some(out), cond, small, large).unwrap();
+    }
+
+    /// The gamma function with the Lanczos approximation (g = 7, 9 coefficients), using the
+    /// reflection formula for `x < 0.5`.
+    fn compile_tgamma(&mut self, out_ty: Item, ty: Word, input: Word, out: Word) {
+        let pi = self.const_float(&out_ty, std::f64::consts::PI);
+        let sqrt_two_pi = self.const_float(&out_ty, 2.5066282746310002);
+        let half = self.const_float(&out_ty, 0.5);
+
+        let (reflect, z, a, t) = self.lanczos(&out_ty, ty, input);
+        // t^(z + 0.5) is split in two powers so it doesn't overflow before the result does.
+        let exponent_0 = self.f_add(ty, None, z, half).unwrap();
+        let exponent = self.f_mul(ty, None, exponent_0, half).unwrap();
+        let pow = self.id();
+        T::pow(self, ty, t, exponent, pow);
+        let neg_t = self.f_negate(ty, None, t).unwrap();
+        let exp = self.id();
+        T::exp(self, ty, neg_t, exp);
+        let tmp_0 = self.f_mul(ty, None, pow, exp).unwrap();
+        let tmp_1 = self.f_mul(ty, None, pow, tmp_0).unwrap();
+        let tmp_2 = self.f_mul(ty, None, sqrt_two_pi, tmp_1).unwrap();
+        let gamma = self.f_mul(ty, None, tmp_2, a).unwrap();
+
+        let angle = self.f_mul(ty, None, pi, input).unwrap();
+        let sin = self.id();
+        T::sin(self, ty, angle, sin);
+        let denom = self.f_mul(ty, None, sin, gamma).unwrap();
+        let reflected = self.f_div(ty, None, pi, denom).unwrap();
+
+        self.select(ty, Some(out), reflect, reflected, gamma)
+            .unwrap();
+    }
+
+    /// The logarithm of the absolute value of the gamma function with the Lanczos approximation
+    /// (g = 7, 9 coefficients), using the reflection formula for `x < 0.5`.
+    fn compile_lgamma(&mut self, out_ty: Item, ty: Word, input: Word, out: Word) {
+        let pi = self.const_float(&out_ty, std::f64::consts::PI);
+        let log_sqrt_two_pi = self.const_float(&out_ty, 0.9189385332046728);
+        let half = self.const_float(&out_ty, 0.5);
+
+        let (reflect, z, a, t) = self.lanczos(&out_ty, ty, input);
+        let factor = self.f_add(ty, None, z, half).unwrap();
+        let log_t = self.id();
+        T::log(self, ty, t, log_t);
+        let log_a = self.id();
+        T::log(self, ty, a, log_a);
+        let tmp_0 = self.f_mul(ty, None, factor, log_t).unwrap();
+        let tmp_1 = self.f_add(ty, None, log_sqrt_two_pi, tmp_0).unwrap();
+        let tmp_2 = self.f_sub(ty, None, tmp_1, t).unwrap();
+        let lgamma = self.f_add(ty, None, tmp_2, log_a).unwrap();
+
+        let angle = self.f_mul(ty, None, pi, input).unwrap();
+        let sin = self.id();
+        T::sin(self, ty, angle, sin);
+        let abs_sin = self.id();
+        T::f_abs(self, ty, sin, abs_sin);
+        let ratio = self.f_div(ty, None, pi, abs_sin).unwrap();
+        let log_ratio = self.id();
+        T::log(self, ty, ratio, log_ratio);
+        let reflected = self.f_sub(ty, None, log_ratio, lgamma).unwrap();
+
+        self.select(ty, Some(out), reflect, reflected, lgamma)
+            .unwrap();
+    }
+
+    /// The terms shared by the gamma functions: whether the input is reflected, then `z`, the
+    /// Lanczos series and `t` for the input or its reflection `1 - x`.
+    fn lanczos(&mut self, out_ty: &Item, ty: Word, input: Word) -> (Word, Word, Word, Word) {
+        const COEFFICIENTS: [f64; 9] = [
+            0.9999999999998099,
+            676.5203681218851,
+            -1259.1392167224028,
+            771.3234287776531,
+            -176.6150291621406,
+            12.507343278686905,
+            -0.13857109526572012,
+            9.984369578019572e-6,
+            1.5056327351493116e-7,
+        ];
+
+        let bool = self.bool_ty(out_ty);
+        let half = self.const_float(out_ty, 0.5);
+        let one = self.const_float(out_ty, 1.0);
+        let g = self.const_float(out_ty, 7.5);
+
+        let reflect = self.f_ord_less_than(bool, None, input, half).unwrap();
+        let reflected = self.f_sub(ty, None, one, input).unwrap();
+        let x = self.select(ty, None, reflect, reflected, input).unwrap();
+        let z = self.f_sub(ty, None, x, one).unwrap();
+
+        let mut a = self.const_float(out_ty, COEFFICIENTS[0]);
+        for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+            let coefficient = self.const_float(out_ty, *coefficient);
+            let offset = self.const_float(out_ty, i as f64);
+            let denom = self.f_add(ty, None, z, offset).unwrap();
+            let term = self.f_div(ty, None, coefficient, denom).unwrap();
+            a = self.f_add(ty, None, a, term).unwrap();
+        }
+        let t = self.f_add(ty, None, z, g).unwrap();
+
+        (reflect, z, a, t)
+    }
+
+    /// Evaluate the polynomial with the `coefficients` of decreasing degree at `x`.
+    /// Saturate the integer sum or difference by comparing `lhs` with the bounds offset by `rhs`,
+    /// so the overflowing result is never selected.
+    fn compile_saturating(
+        &mut self,
+        out_ty: &Item,
+        ty: Word,
+        lhs: Word,
+        rhs: Word,
+        out: Word,
+        sub: bool,
+    ) {
+        let bool = self.bool_ty(out_ty);
+        let (min, max) = self.int_bounds(out_ty);
+
+        if let Elem::Int(_, false) = out_ty.elem() {
+            let result = if sub {
+                let difference = self.i_sub(ty, None, lhs, rhs).unwrap();
+                let underflow = self.u_less_than(bool, None, lhs, rhs).unwrap();
+                self.select(ty, Some(out), underflow, min, difference)
+            } else {
+                let sum = self.i_add(ty, None, lhs, rhs).unwrap();
+                let bound = self.i_sub(ty, None, max, rhs).unwrap();
+                let overflow = self.u_greater_than(bool, None, lhs, bound).unwrap();
+                self.select(ty, Some(out), overflow, max, sum)
+            };
+            result.unwrap();
+            return;
+        }
+
+        // Adding a positive or subtracting a negative value can only overflow the maximum.
+        let zero = self.static_cast(ConstVal::Bit32(0), &Elem::Int(32, true), out_ty);
+        let (result, upper_bound, lower_bound, increases) = if sub {
+            let difference = self.i_sub(ty, None, lhs, rhs).unwrap();
+            let upper_bound = self.i_add(ty, None, max, rhs).unwrap();
+            let lower_bound = self.i_add(ty, None, min, rhs).unwrap();
+            let increases = self.s_less_than(bool, None, rhs, zero).unwrap();
+            (difference, upper_bound, lower_bound, increases)
+        } else {
+            let sum = self.i_add(ty, None, lhs, rhs).unwrap();
+            let upper_bound = self.i_sub(ty, None, max, rhs).unwrap();
+            let lower_bound = self.i_sub(ty, None, min, rhs).unwrap();
+            let increases = self.s_greater_than(bool, None, rhs, zero).unwrap();
+            (sum, upper_bound, lower_bound, increases)
+        };
+        let overflow = self.s_greater_than(bool, None, lhs, upper_bound).unwrap();
+        let underflow = self.s_less_than(bool, None, lhs, lower_bound).unwrap();
+        let saturated_max = self.select(ty, None, overflow, max, result).unwrap();
+        let saturated_min = self.select(ty, None, underflow, min, result).unwrap();
+        self.select(ty, Some(out), increases, saturated_max, saturated_min)
+            .unwrap();
+    }
+
+    /// Rotate with two logical shifts, the amount taken modulo the bit width since larger shifts
+    /// are undefined.
+    fn compile_rotate(
+        &mut self,
+        out_ty: &Item,
+        ty: Word,
+        value: Word,
+        amount: Word,
+        out: Word,
+        left: bool,
+    ) {
+        let (mask, width) = match out_ty.elem() {
+            Elem::Int(64, _) => (ConstVal::Bit64(63), ConstVal::Bit64(64)),
+            _ => (ConstVal::Bit32(31), ConstVal::Bit32(32)),
+        };
+        let elem = out_ty.elem();
+        let mask = self.static_cast(mask, &elem, out_ty);
+        let width = self.static_cast(width, &elem, out_ty);
+
+        let shift = self.bitwise_and(ty, None, amount, mask).unwrap();
+        let complement = self.i_sub(ty, None, width, shift).unwrap();
+        let complement = self.bitwise_and(ty, None, complement, mask).unwrap();
+        let (forward, backward) = if left {
+            (
+                self.shift_left_logical(ty, None, value, shift).unwrap(),
+                self.shift_right_logical(ty, None, value, complement)
+                    .unwrap(),
+            )
+        } else {
+            (
+                self.shift_right_logical(ty, None, value, shift).unwrap(),
+                self.shift_left_logical(ty, None, value, complement)
+                    .unwrap(),
+            )
+        };
+        self.bitwise_or(ty, Some(out), forward, backward).unwrap();
+    }
+
+    /// The scalar offset and count of a bitfield, clamped to the bit width like in WGSL since
+    /// bits outside of the value are undefined.
+    fn compile_bitfield_range(
+        &mut self,
+        out_ty: &Item,
+        offset: core::Variable,
+        count: core::Variable,
+    ) -> (Word, Word) {
+        let u32_item = Item::Scalar(Elem::Int(32, false));
+        let offset = self.compile_variable(offset);
+        let count = self.compile_variable(count);
+        let offset = self.read_as(&offset, &u32_item);
+        let count = self.read_as(&count, &u32_item);
+
+        let ty = u32_item.id(self);
+        let width = match out_ty.elem() {
+            Elem::Int(64, _) => 64,
+            _ => 32,
+        };
+        let width = self.const_u32(width);
+
+        let clamped_offset = self.id();
+        T::u_min(self, ty, offset, width, clamped_offset);
+        let remaining = self.i_sub(ty, None, width, clamped_offset).unwrap();
+        let clamped_count = self.id();
+        T::u_min(self, ty, count, remaining, clamped_count);
+
+        (clamped_offset, clamped_count)
+    }
+
+    /// The smallest and largest values of an integer item.
+    fn int_bounds(&mut self, out_ty: &Item) -> (Word, Word) {
+        let elem = out_ty.elem();
+        let (min, max) = match elem {
+            Elem::Int(64, true) => (i64::MIN as u64, i64::MAX as u64),
+            Elem::Int(64, false) => (0, u64::MAX),
+            Elem::Int(_, true) => (i32::MIN as u32 as u64, i32::MAX as u64),
+            Elem::Int(_, false) => (0, u32::MAX as u64),
+            elem => unreachable!("{elem} isn't an integer"),
+        };
+        let (min, max) = match elem {
+            Elem::Int(64, _) => (ConstVal::Bit64(min), ConstVal::Bit64(max)),
+            _ => (ConstVal::Bit32(min as u32), ConstVal::Bit32(max as u32)),
+        };
+        (
+            self.static_cast(min, &elem, out_ty),
+            self.static_cast(max, &elem, out_ty),
+        )
+    }
+
+    /// Round the two `f32` to `bf16` to nearest even by adding half of the dropped bits, with the
+    /// parity of the kept bits breaking the ties. NaNs are kept quiet, since the rounding could
+    /// carry them to infinity.
+    fn compile_pack_bf16(&mut self, ty: Word, input: Word, out: Word) {
+        let vec = Item::Vector(Elem::Int(32, false), 2);
+        let vec_ty = vec.id(self);
+        let bool = self.bool_ty(&vec);
+        let one = self.const_uint(&vec, 1);
+        let sixteen = self.const_uint(&vec, 16);
+        let half = self.const_uint(&vec, 0x7fff);
+        let abs_mask = self.const_uint(&vec, 0x7fffffff);
+        let infinity = self.const_uint(&vec, 0x7f800000);
+        let quiet = self.const_uint(&vec, 0x40);
+
+        let bits = self.bitcast(vec_ty, None, input).unwrap();
+        let truncated = self
+            .shift_right_logical(vec_ty, None, bits, sixteen)
+            .unwrap();
+        let parity = self.bitwise_and(vec_ty, None, truncated, one).unwrap();
+        let biased = self.i_add(vec_ty, None, bits, half).unwrap();
+        let biased = self.i_add(vec_ty, None, biased, parity).unwrap();
+        let rounded = self
+            .shift_right_logical(vec_ty, None, biased, sixteen)
+            .unwrap();
+        let abs = self.bitwise_and(vec_ty, None, bits, abs_mask).unwrap();
+        let is_nan = self.u_greater_than(bool, None, abs, infinity).unwrap();
+        let nan = self.bitwise_or(vec_ty, None, truncated, quiet).unwrap();
+        let halves = self.select(vec_ty, None, is_nan, nan, rounded).unwrap();
+
+        let sixteen = self.const_uint(&Item::Scalar(Elem::Int(32, false)), 16);
+        let low = self.composite_extract(ty, None, halves, [0]).unwrap();
+        let high = self.composite_extract(ty, None, halves, [1]).unwrap();
+        let high = self.shift_left_logical(ty, None, high, sixteen).unwrap();
+        self.bitwise_or(ty, Some(out), low, high).unwrap();
+    }
+
+    fn compile_unpack_bf16(&mut self, ty: Word, input: Word, out: Word) {
+        let scalar = Item::Scalar(Elem::Int(32, false));
+        let scalar_ty = scalar.id(self);
+        let vec_ty = Item::Vector(Elem::Int(32, false), 2).id(self);
+        let sixteen = self.const_uint(&scalar, 16);
+        let high_mask = self.const_uint(&scalar, 0xffff0000);
+
+        let low = self
+            .shift_left_logical(scalar_ty, None, input, sixteen)
+            .unwrap();
+        let high = self.bitwise_and(scalar_ty, None, input, high_mask).unwrap();
+        let bits = self.composite_construct(vec_ty, None, [low, high]).unwrap();
+        self.bitcast(ty, Some(out), bits).unwrap();
+    }
+
+    fn horner(&mut self, out_ty: &Item, ty: Word, x: Word, coefficients: &[f64]) -> Word {
+        let mut acc = self.const_float(out_ty, coefficients[0]);
+        for coefficient in &coefficients[1..] {
+            let coefficient = self.const_float(out_ty, *coefficient);
+            let mul = self.f_mul(ty, None, acc, x).unwrap();
+            acc = self.f_add(ty, None, mul, coefficient).unwrap();
+        }
+        acc
+    }
+
+    fn const_float(&mut self, out_ty: &Item, val: f64) -> Word {
+        self.static_cast(ConstVal::Bit64(val.to_bits()), &Elem::Float(64), out_ty)
+    }
+
+    fn const_uint(&mut self, out_ty: &Item, val: u32) -> Word {
+        self.static_cast(ConstVal::Bit32(val), &Elem::Int(32, false), out_ty)
+    }
+
+    fn bool_ty(&mut self, out_ty: &Item) -> Word {
+        match out_ty {
+            Item::Scalar(_) => Item::Scalar(Elem::Bool),
+            Item::Vector(_, factor) => Item::Vector(Elem::Bool, *factor),
+            _ => unreachable!(),
+        }
+        .id(self)
+    }
 }
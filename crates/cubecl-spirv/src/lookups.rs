@@ -21,6 +21,7 @@ pub struct LookupTables {
 
     pub const_arrays: Vec<ConstArray>,
     pub shared_memories: HashMap<u16, Array>,
+    pub dynamic_shared_memory: u32,
     pub local_arrays: HashMap<(u16, u8), Array>,
     pub matrices: HashMap<(u16, u8), Matrix>,
 
@@ -43,6 +44,7 @@ pub struct LookupTables {
     pub loops: VecDeque<Loop>,
 
     pub debug_types: HashSet<Word>,
+    pub debug_files: HashMap<String, Word>,
 }
 
 #[derive(Clone, Debug)]
@@ -134,6 +136,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
             })
             .collect();
 
+        self.state.dynamic_shared_memory = kernel.dynamic_shared_memory;
+
         let cube_dims = [kernel.cube_dim.x, kernel.cube_dim.y, kernel.cube_dim.z];
         self.state.cube_dims = cube_dims.iter().map(|dim| self.const_u32(*dim)).collect();
         self.state.cube_size = self.const_u32(cube_dims.iter().product());
@@ -23,6 +23,8 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 self.control_barrier(scope_exec, scope_mem, semantics)
                     .unwrap();
             }
+            // Asynchronous copies are performed right away.
+            Synchronization::CopyAsyncCommit | Synchronization::CopyAsyncWait { .. } => {}
         }
     }
 }
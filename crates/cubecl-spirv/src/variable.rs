@@ -540,8 +540,25 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 let id = self.state.const_arrays[id as usize].id;
                 Variable::ConstantArray(id, item, length)
             }
-            core::Variable::SharedMemory { id, item, length } => {
+            core::Variable::SharedMemory {
+                id,
+                item,
+                length,
+                dynamic,
+            } => {
                 let item = self.compile_item(item);
+                let length = match dynamic {
+                    true => {
+                        let length = self.state.dynamic_shared_memory / item.size();
+                        assert!(
+                            length > 0,
+                            "The dynamic shared memory of {} bytes can't hold a single {item}",
+                            self.state.dynamic_shared_memory
+                        );
+                        length
+                    }
+                    false => length,
+                };
                 let id = if let Some(arr) = self.state.shared_memories.get(&id) {
                     arr.id
                 } else {
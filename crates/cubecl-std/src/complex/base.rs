@@ -0,0 +1,90 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// A complex number, stored as a line of two elements holding the real and imaginary parts.
+///
+/// Global buffers and shared memories of complex numbers are arrays of lines of size 2, e.g.
+/// `Array<Line<F>>` launched with a vectorization of 2 or `SharedMemory::new_lined(size, 2)`,
+/// whose elements are read with [from_line](Complex::from_line) and written with
+/// [to_line](Complex::to_line). The interleaved layout is the one of `[..., n, 2]` tensors.
+#[derive(CubeType, Copy, Clone)]
+pub struct Complex<F: Float> {
+    /// The real part followed by the imaginary part.
+    pub value: Line<F>,
+}
+
+#[cube]
+impl<F: Float> Complex<F> {
+    /// Create a complex number from its real and imaginary parts.
+    pub fn new(re: F, im: F) -> Self {
+        let mut value = Line::empty(2u32);
+        value[0] = re;
+        value[1] = im;
+        Complex::<F> { value }
+    }
+
+    /// Read a complex number from a line of size 2.
+    pub fn from_line(value: Line<F>) -> Self {
+        Complex::<F> { value }
+    }
+
+    /// The line of size 2 to write the complex number to a buffer.
+    pub fn to_line(self) -> Line<F> {
+        self.value
+    }
+
+    /// The real part.
+    pub fn re(&self) -> F {
+        self.value[0]
+    }
+
+    /// The imaginary part.
+    pub fn im(&self) -> F {
+        self.value[1]
+    }
+
+    /// The sum of two complex numbers.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, other: Self) -> Self {
+        Complex::<F> {
+            value: self.value + other.value,
+        }
+    }
+
+    /// The difference of two complex numbers.
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(self, other: Self) -> Self {
+        Complex::<F> {
+            value: self.value - other.value,
+        }
+    }
+
+    /// The product of two complex numbers.
+    #[allow(clippy::should_implement_trait)]
+    pub fn mul(self, other: Self) -> Self {
+        let a = self.re();
+        let b = self.im();
+        let c = other.re();
+        let d = other.im();
+        Complex::<F>::new(a * c - b * d, a * d + b * c)
+    }
+
+    /// Multiply both parts by a real number.
+    pub fn scale(self, factor: F) -> Self {
+        Complex::<F> {
+            value: self.value * Line::new(factor),
+        }
+    }
+
+    /// The complex conjugate.
+    pub fn conj(self) -> Self {
+        Complex::<F>::new(self.re(), F::new(0.0) - self.im())
+    }
+
+    /// The modulus `sqrt(re² + im²)`.
+    pub fn abs(self) -> F {
+        let a = self.re();
+        let b = self.im();
+        F::sqrt(a * a + b * b)
+    }
+}
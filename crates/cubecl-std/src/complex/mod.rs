@@ -0,0 +1,6 @@
+mod base;
+
+pub use base::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
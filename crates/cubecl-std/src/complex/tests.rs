@@ -0,0 +1,100 @@
+#![allow(missing_docs)]
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::Complex;
+
+#[cube(launch_unchecked)]
+fn complex_kernel(
+    lhs: &Array<Line<f32>>,
+    rhs: &Array<Line<f32>>,
+    products: &mut Array<Line<f32>>,
+    sums: &mut Array<Line<f32>>,
+    moduli: &mut Array<f32>,
+) {
+    let a = Complex::<f32>::from_line(lhs[ABSOLUTE_POS]);
+    let b = Complex::<f32>::from_line(rhs[ABSOLUTE_POS]);
+
+    products[ABSOLUTE_POS] = a.mul(b.conj()).to_line();
+    sums[ABSOLUTE_POS] = a.add(b).sub(Complex::new(1.0, 1.0)).scale(2.0).to_line();
+    moduli[ABSOLUTE_POS] = a.abs();
+}
+
+/// Reverse the 4 complex numbers of the cube through shared memory, conjugating them.
+#[cube(launch_unchecked)]
+fn complex_shared_kernel(input: &Array<Line<f32>>, output: &mut Array<Line<f32>>) {
+    let mut shared = SharedMemory::<f32>::new_lined(4, 2u32);
+    let value = Complex::<f32>::from_line(input[UNIT_POS]);
+    shared[UNIT_POS] = value.conj().to_line();
+    sync_units();
+
+    let reversed = Complex::<f32>::from_line(shared[3 - UNIT_POS]);
+    output[UNIT_POS] = reversed.to_line();
+}
+
+fn assert_approx(actual: &[f32], expected: &[f32]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-5, "{actual:?} != {expected:?}");
+    }
+}
+
+pub fn test_complex_arithmetic<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs = [1.0, 2.0, -3.0, 0.5, 3.0, -4.0];
+    let rhs = [3.0, -1.0, 0.0, 2.0, 1.0, 1.0];
+    let lhs_handle = client.create(f32::as_bytes(&lhs));
+    let rhs_handle = client.create(f32::as_bytes(&rhs));
+    let products = client.empty(lhs.len() * core::mem::size_of::<f32>());
+    let sums = client.empty(lhs.len() * core::mem::size_of::<f32>());
+    let moduli = client.empty(lhs.len() / 2 * core::mem::size_of::<f32>());
+
+    unsafe {
+        complex_kernel::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(3, 1, 1),
+            ArrayArg::from_raw_parts(&lhs_handle, 3, 2),
+            ArrayArg::from_raw_parts(&rhs_handle, 3, 2),
+            ArrayArg::from_raw_parts(&products, 3, 2),
+            ArrayArg::from_raw_parts(&sums, 3, 2),
+            ArrayArg::from_raw_parts(&moduli, 3, 1),
+        )
+    };
+
+    // (1 + 2i)(3 + i) = 1 + 7i, (-3 + 0.5i)(-2i) = 1 + 6i, (3 - 4i)(1 - i) = -1 - 7i.
+    assert_approx(
+        f32::from_bytes(&client.read(products.binding())),
+        &[1.0, 7.0, 1.0, 6.0, -1.0, -7.0],
+    );
+    assert_approx(
+        f32::from_bytes(&client.read(sums.binding())),
+        &[6.0, 0.0, -8.0, 3.0, 6.0, -8.0],
+    );
+    assert_approx(
+        f32::from_bytes(&client.read(moduli.binding())),
+        &[5.0f32.sqrt(), 9.25f32.sqrt(), 5.0],
+    );
+}
+
+pub fn test_complex_shared_memory<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let input = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    let input_handle = client.create(f32::as_bytes(&input));
+    let output = client.empty(input.len() * core::mem::size_of::<f32>());
+
+    unsafe {
+        complex_shared_kernel::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(4, 1, 1),
+            ArrayArg::from_raw_parts(&input_handle, 4, 2),
+            ArrayArg::from_raw_parts(&output, 4, 2),
+        )
+    };
+
+    assert_eq!(
+        f32::from_bytes(&client.read(output.binding())),
+        [7.0, -8.0, 5.0, -6.0, 3.0, -4.0, 1.0, -2.0]
+    );
+}
@@ -0,0 +1,157 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// A signed fixed-point number in the Q format, with `BITS` bits of which `FRAC` are fractional,
+/// e.g. `Fixed<16, 15>` for Q15 values in `[-1, 1)`.
+///
+/// The number is stored as an `i32` holding `value * 2^FRAC`, and its arithmetic is lowered to
+/// integer operations that saturate to the range of `BITS` bits instead of wrapping, so the
+/// results are bit-exact on every backend. `BITS` is at most 16, so that products fit in 32 bits
+/// without the 64-bit integers WGSL lacks.
+#[derive(CubeType, Copy, Clone)]
+pub struct Fixed<const BITS: u32, const FRAC: u32> {
+    /// The raw representation, `value * 2^FRAC`.
+    pub bits: i32,
+}
+
+impl<const BITS: u32, const FRAC: u32> Fixed<BITS, FRAC> {
+    /// The largest raw representation.
+    pub const MAX_BITS: i32 = (1 << (BITS - 1)) - 1;
+    /// The smallest raw representation.
+    pub const MIN_BITS: i32 = -(1 << (BITS - 1));
+    /// The raw representation of one, which saturates when there are no integer bits.
+    pub const ONE_BITS: i32 = 1 << FRAC;
+
+    /// Create a number from its raw representation, which must be in the range of `BITS` bits.
+    #[allow(unused_variables)]
+    pub fn from_bits(bits: i32) -> Self {
+        cubecl::unexpanded!()
+    }
+
+    /// Expand function of [from_bits](Self::from_bits).
+    pub fn __expand_from_bits(
+        _context: &mut CubeContext,
+        bits: ExpandElementTyped<i32>,
+    ) -> FixedExpand<BITS, FRAC> {
+        assert!(
+            (2..=16).contains(&BITS) && FRAC < BITS,
+            "Fixed-point numbers should have between 2 and 16 bits with at least one integer bit, \
+             got {BITS} bits with {FRAC} fractional bits"
+        );
+        FixedExpand { bits }
+    }
+}
+
+#[cube]
+impl<const BITS: u32, const FRAC: u32> Fixed<BITS, FRAC> {
+    /// Convert an integer, saturating it to the range of the format.
+    pub fn from_int(value: i32) -> Self {
+        // The integer part of the largest value is rounded down, so the integer above it is
+        // kept to saturate the product, e.g. 128 to 32767 in Q8.8 and 1 to 32767 in Q15.
+        let min = i32::new(comptime!((Fixed::<BITS, FRAC>::MIN_BITS >> FRAC) as i64));
+        let max = i32::new(comptime!(
+            ((Fixed::<BITS, FRAC>::MAX_BITS >> FRAC) + 1) as i64
+        ));
+        let one = i32::new(comptime!(Fixed::<BITS, FRAC>::ONE_BITS as i64));
+        Self::saturate(Self::clamp_bits(value, min, max) * one)
+    }
+
+    /// Convert a float, rounding it to the nearest representable value, ties to even, and
+    /// saturating it to the range of the format.
+    pub fn from_float<F: Float>(value: F) -> Self {
+        let scale = F::new(comptime!((1u32 << FRAC) as f32));
+        let min = F::new(comptime!(Fixed::<BITS, FRAC>::MIN_BITS as f32));
+        let max = F::new(comptime!(Fixed::<BITS, FRAC>::MAX_BITS as f32));
+        let scaled = value * scale;
+        // The rounding of ties differs between backends, so they are rounded to even explicitly.
+        let floor = F::floor(scaled);
+        let fraction = scaled - floor;
+        let odd = floor - F::new(2.0) * F::floor(floor * F::new(0.5));
+        let up = select(
+            fraction > F::new(0.5),
+            F::new(1.0),
+            select(fraction < F::new(0.5), F::new(0.0), odd),
+        );
+        let rounded = F::clamp(floor + up, min, max);
+        Self::from_bits(i32::cast_from(rounded))
+    }
+
+    /// Convert to a float. The conversion is exact for `f32`.
+    pub fn to_float<F: Float>(self) -> F {
+        let scale = F::new(comptime!(1.0 / (1u32 << FRAC) as f32));
+        F::cast_from(self.bits) * scale
+    }
+
+    /// The sum, saturated to the range of the format.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, other: Self) -> Self {
+        Self::saturate(self.bits + other.bits)
+    }
+
+    /// The difference, saturated to the range of the format.
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(self, other: Self) -> Self {
+        Self::saturate(self.bits - other.bits)
+    }
+
+    /// The product, rounded to the nearest representable value, ties away from zero, and
+    /// saturated to the range of the format.
+    #[allow(clippy::should_implement_trait)]
+    pub fn mul(self, other: Self) -> Self {
+        let product = self.bits * other.bits;
+        Self::saturate(Self::shift_round(product))
+    }
+
+    /// The quotient, rounded toward zero and saturated to the range of the format.
+    ///
+    /// A division by zero saturates to the largest or smallest value according to the sign of
+    /// the numerator, and returns zero for a zero numerator.
+    #[allow(clippy::should_implement_trait)]
+    pub fn div(self, other: Self) -> Self {
+        let divisor_is_zero = other.bits == 0;
+        // The divisor is never zero, the result of a division by zero being selected below.
+        let divisor = select(divisor_is_zero, 1i32, other.bits);
+        let one = i32::new(comptime!(Fixed::<BITS, FRAC>::ONE_BITS as i64));
+        let quotient = (self.bits * one) / divisor;
+        let infinity = select(
+            self.bits < 0,
+            comptime!(Fixed::<BITS, FRAC>::MIN_BITS),
+            select(
+                self.bits > 0,
+                comptime!(Fixed::<BITS, FRAC>::MAX_BITS),
+                0i32,
+            ),
+        );
+        Self::saturate(select(divisor_is_zero, infinity, quotient))
+    }
+
+    /// The negation, saturated to the range of the format.
+    #[allow(clippy::should_implement_trait)]
+    pub fn neg(self) -> Self {
+        Self::saturate(0i32 - self.bits)
+    }
+
+    /// The absolute value, saturated to the range of the format.
+    pub fn abs(self) -> Self {
+        Self::saturate(i32::abs(self.bits))
+    }
+
+    fn saturate(bits: i32) -> Self {
+        let min = i32::new(comptime!(Fixed::<BITS, FRAC>::MIN_BITS as i64));
+        let max = i32::new(comptime!(Fixed::<BITS, FRAC>::MAX_BITS as i64));
+        Self::from_bits(Self::clamp_bits(bits, min, max))
+    }
+
+    fn clamp_bits(value: i32, min: i32, max: i32) -> i32 {
+        select(value < min, min, select(value > max, max, value))
+    }
+
+    /// Divide by `2^FRAC`, rounding ties away from zero. The shift is applied to the unsigned
+    /// magnitude since right shifts of negative integers aren't arithmetic on every backend, and
+    /// WGSL only shifts by unsigned amounts.
+    fn shift_round(value: i32) -> i32 {
+        let magnitude = u32::cast_from(i32::abs(value));
+        let shifted = i32::cast_from((magnitude + comptime!((1u32 << FRAC) >> 1)) >> FRAC);
+        select(value < 0, 0i32 - shifted, shifted)
+    }
+}
@@ -0,0 +1,87 @@
+#![allow(missing_docs)]
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::Fixed;
+
+type Q8 = Fixed<16, 8>;
+type Q15 = Fixed<16, 15>;
+
+#[cube(launch_unchecked)]
+fn fixed_kernel(
+    lhs: &Array<f32>,
+    rhs: &Array<f32>,
+    sums: &mut Array<i32>,
+    differences: &mut Array<i32>,
+    products: &mut Array<i32>,
+    quotients: &mut Array<i32>,
+    negations: &mut Array<i32>,
+    integers: &mut Array<i32>,
+    fractions: &mut Array<i32>,
+    ties: &mut Array<i32>,
+    floats: &mut Array<f32>,
+) {
+    let a = Q8::from_float::<f32>(lhs[ABSOLUTE_POS]);
+    let b = Q8::from_float::<f32>(rhs[ABSOLUTE_POS]);
+
+    sums[ABSOLUTE_POS] = a.add(b).bits;
+    differences[ABSOLUTE_POS] = a.sub(b).bits;
+    products[ABSOLUTE_POS] = a.mul(b).bits;
+    quotients[ABSOLUTE_POS] = a.div(b).bits;
+    negations[ABSOLUTE_POS] = a.neg().bits;
+    integers[ABSOLUTE_POS] = Q8::from_int(i32::cast_from(ABSOLUTE_POS) * 100 - 200).bits;
+    fractions[ABSOLUTE_POS] = Q15::from_int(i32::cast_from(ABSOLUTE_POS) - 2).bits;
+    ties[ABSOLUTE_POS] = Q8::from_float::<f32>((f32::cast_from(ABSOLUTE_POS) - 1.5) / 256.0).bits;
+    floats[ABSOLUTE_POS] = a.mul(b).to_float::<f32>();
+}
+
+pub fn test_fixed_arithmetic<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs = [1.5, 100.0, -0.01, -200.0, -1.0 / 256.0];
+    let rhs = [2.25, 50.0, 0.0, -0.5, 0.5];
+    let lhs_handle = client.create(f32::as_bytes(&lhs));
+    let rhs_handle = client.create(f32::as_bytes(&rhs));
+    let outputs: [_; 9] =
+        core::array::from_fn(|_| client.empty(lhs.len() * core::mem::size_of::<i32>()));
+    let arg = |handle| unsafe { ArrayArg::from_raw_parts(handle, lhs.len(), 1) };
+
+    unsafe {
+        fixed_kernel::launch_unchecked::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(lhs.len() as u32, 1, 1),
+            arg(&lhs_handle),
+            arg(&rhs_handle),
+            arg(&outputs[0]),
+            arg(&outputs[1]),
+            arg(&outputs[2]),
+            arg(&outputs[3]),
+            arg(&outputs[4]),
+            arg(&outputs[5]),
+            arg(&outputs[6]),
+            arg(&outputs[7]),
+            arg(&outputs[8]),
+        )
+    };
+    let read =
+        |index: usize| i32::from_bytes(&client.read(outputs[index].clone().binding())).to_vec();
+
+    // -0.01 rounds to -3 / 256 and -200 saturates to -128.
+    assert_eq!(read(0), [960, 32767, -3, -32768, 127]);
+    assert_eq!(read(1), [-192, 12800, -3, -32640, -129]);
+    // Ties round away from zero: -1 / 256 * 0.5 gives -1 / 256.
+    assert_eq!(read(2), [864, 32767, 0, 16384, -1]);
+    // Divisions round toward zero, and a division by zero saturates by the sign of the numerator.
+    assert_eq!(read(3), [170, 512, -32768, 32767, -2]);
+    assert_eq!(read(4), [-384, -25600, 3, 32767, 1]);
+    // Integers saturate after the conversion, to the largest value instead of its integer part.
+    assert_eq!(read(5), [-32768, -25600, 0, 25600, 32767]);
+    assert_eq!(read(6), [-32768, -32768, 0, 32767, 32767]);
+    // Ties of -1.5, -0.5, 0.5, 1.5 and 2.5 / 256 round to even.
+    assert_eq!(read(7), [-2, 0, 0, 2, 2]);
+    assert_eq!(
+        f32::from_bytes(&client.read(outputs[8].clone().binding())),
+        [3.375, 32767.0 / 256.0, 0.0, 64.0, -1.0 / 256.0]
+    );
+}
@@ -0,0 +1,18 @@
+/// Complex numbers.
+pub mod complex;
+/// Fixed-point numbers.
+pub mod fixed;
+/// Persistent kernels, whose cubes claim work items until there are none left.
+pub mod persistent;
+/// Software pipelining of tiled loops.
+pub mod pipeline;
+/// Work queues on the device, to which kernels push follow-up work.
+pub mod queue;
+/// Layout operations on tensors.
+pub mod tensor;
+mod tests;
+
+pub use complex::Complex;
+pub use fixed::Fixed;
+pub use pipeline::PipelineTask;
+pub use queue::WorkQueue;
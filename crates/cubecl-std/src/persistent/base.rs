@@ -0,0 +1,35 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// Claim the next work item of a persistent kernel for the current cube, from a counter shared
+/// by all cubes, which must be zero at launch.
+///
+/// The cubes claim the items in increasing order, each as soon as it is done with its previous
+/// one, which balances work items of uneven costs. Every unit of the cube gets the same item, so
+/// a loop over the claimed items is uniform and can synchronize the cube, even though the
+/// uniformity check of the compiler can't prove it through shared memory.
+///
+/// # Example
+///
+/// ```ignore
+/// loop {
+///     let item = next_work_item(counter);
+///     if item >= num_items {
+///         break;
+///     }
+///     // Process the item with the whole cube.
+/// }
+/// ```
+#[cube]
+pub fn next_work_item(counter: &Array<AtomicU32>) -> u32 {
+    let mut claimed = SharedMemory::<u32>::new(1);
+    if UNIT_POS == 0 {
+        claimed[0] = AtomicU32::add(&counter[0], 1);
+    }
+    sync_units();
+
+    let item = claimed[0];
+    // The item of the cube isn't overwritten by its next claim before every unit has read it.
+    sync_units();
+    item
+}
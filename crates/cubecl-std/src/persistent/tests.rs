@@ -0,0 +1,66 @@
+#![allow(missing_docs)]
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::next_work_item;
+
+/// Rotate each block of `CUBE_DIM` elements through shared memory, one block per work item.
+#[cube(launch_unchecked)]
+fn rotate_blocks_kernel(input: &Array<u32>, output: &mut Array<u32>, counter: &Array<AtomicU32>) {
+    let mut block = SharedMemory::<u32>::new(32);
+    let num_items = input.len() / CUBE_DIM;
+
+    loop {
+        let item = next_work_item(counter);
+        if item >= num_items {
+            break;
+        }
+
+        let offset = item * CUBE_DIM;
+        block[UNIT_POS] = input[offset + UNIT_POS];
+        sync_units();
+        output[offset + UNIT_POS] = block[(UNIT_POS + 1) % block.len()];
+        sync_units();
+    }
+}
+
+pub fn test_persistent_work_items<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let cube_dim = CubeDim::new(32, 1, 1);
+    let num_items = 37;
+    let input: Vec<u32> = (0..num_items * 32).collect();
+    let input_handle = client.create(u32::as_bytes(&input));
+    let expected: Vec<u32> = input
+        .chunks(32)
+        .flat_map(|block| block.iter().cycle().skip(1).take(32).copied())
+        .collect();
+
+    // More items than cubes, so each cube claims several of them.
+    for cube_count in [1, 5] {
+        let output = client.empty(input.len() * core::mem::size_of::<u32>());
+        let counter = client.create(u32::as_bytes(&[0]));
+
+        unsafe {
+            rotate_blocks_kernel::launch_unchecked::<R>(
+                &client,
+                CubeCount::Static(cube_count, 1, 1),
+                cube_dim,
+                ArrayArg::from_raw_parts(&input_handle, input.len(), 1),
+                ArrayArg::from_raw_parts(&output, input.len(), 1),
+                ArrayArg::from_raw_parts(&counter, 1, 1),
+            )
+        };
+
+        assert_eq!(
+            u32::from_bytes(&client.read(output.binding())),
+            expected,
+            "{cube_count} cubes"
+        );
+        // Each cube claims one item past the last before stopping.
+        assert_eq!(
+            u32::from_bytes(&client.read(counter.binding())),
+            [num_items + cube_count]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+/// The work of a software pipelined loop over tiles, driven by [pipeline()].
+///
+/// Each tile is first loaded into a stage in shared memory, then computed on once every unit
+/// finished loading it.
+#[cube]
+pub trait PipelineTask<E: CubePrimitive>: 'static + Send + Sync {
+    /// What the loads read from, e.g. the global inputs.
+    type Input: CubeType;
+    /// What the computations update, e.g. the accumulators.
+    type State: CubeType;
+
+    /// Load the tile into the stage, cooperatively with the other units of the cube.
+    ///
+    /// The load should use [copy_bulk_async](cubecl::prelude::copy_bulk_async()) so it overlaps
+    /// with the computation on the previous tiles, but can be any write to the stage.
+    fn load(input: &Self::Input, tile: u32, stage: &mut SliceMut<E>);
+
+    /// Compute on the tile, whose stage has been loaded by every unit of the cube.
+    fn compute(state: &mut Self::State, tile: u32, stage: &Slice<E>);
+}
+
+/// Run `P` over `num_tiles` tiles with `STAGES` shared memory buffers of `stage_size` elements,
+/// loading the next `STAGES - 1` tiles while computing on the current one. Two stages give
+/// double buffering, and a single stage runs the loads and computations one after the other.
+///
+/// The prologue starts loading the first tiles, and the tiles past the end are skipped by the
+/// last iterations, so `num_tiles` can be any number of tiles. It must be the same for every unit
+/// of the cube, since the stages are separated by [sync_units()].
+#[cube]
+pub fn pipeline<E: CubePrimitive, P: PipelineTask<E>, const STAGES: u32>(
+    input: &P::Input,
+    state: &mut P::State,
+    num_tiles: u32,
+    #[comptime] stage_size: u32,
+) {
+    let mut buffers = SharedMemory::<E>::new(comptime!(STAGES * stage_size));
+
+    // Every iteration commits one group of copies, even when there's nothing left to load, so
+    // the group of a tile is always its index.
+    #[unroll]
+    for tile in 0..comptime!(STAGES - 1) {
+        if tile < num_tiles {
+            let start = tile * stage_size;
+            P::load(input, tile, buffers.slice_mut(start, start + stage_size));
+        }
+        copy_async_commit();
+    }
+
+    for tile in 0..num_tiles {
+        // The stage of the next tile was released by the computation of the previous one.
+        let next = tile + comptime!(STAGES - 1);
+        if next < num_tiles {
+            let start = (next % comptime!(STAGES)) * stage_size;
+            P::load(input, next, buffers.slice_mut(start, start + stage_size));
+        }
+        copy_async_commit();
+
+        copy_async_wait(comptime!(STAGES - 1));
+        sync_units();
+
+        let start = (tile % comptime!(STAGES)) * stage_size;
+        P::compute(state, tile, buffers.slice(start, start + stage_size));
+        sync_units();
+    }
+}
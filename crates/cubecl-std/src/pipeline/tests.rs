@@ -0,0 +1,106 @@
+#![allow(missing_docs)]
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{pipeline, PipelineTask};
+
+const STAGE_SIZE: u32 = 4;
+
+#[derive(CubeType)]
+struct TileSum {
+    value: f32,
+}
+
+/// Every unit sums a different element of each tile, so the elements loaded by the other units
+/// must be visible.
+struct TileSumTask;
+
+#[cube]
+impl PipelineTask<f32> for TileSumTask {
+    type Input = Array<f32>;
+    type State = TileSum;
+
+    fn load(input: &Array<f32>, tile: u32, stage: &mut SliceMut<f32>) {
+        let start = tile * STAGE_SIZE;
+        copy_bulk_async(
+            input.slice(start, start + STAGE_SIZE),
+            UNIT_POS,
+            stage,
+            UNIT_POS,
+            1u32,
+        );
+    }
+
+    fn compute(state: &mut TileSum, tile: u32, stage: &Slice<f32>) {
+        state.value += stage[(UNIT_POS + tile) % STAGE_SIZE];
+    }
+}
+
+#[cube]
+fn tile_sum<const STAGES: u32>(input: &Array<f32>, output: &mut Array<f32>) {
+    let mut state = TileSum { value: 0.0 };
+    pipeline::<f32, TileSumTask, STAGES>(input, &mut state, input.len() / STAGE_SIZE, STAGE_SIZE);
+    output[UNIT_POS] = state.value;
+}
+
+#[cube(launch_unchecked)]
+fn tile_sum_single_kernel(input: &Array<f32>, output: &mut Array<f32>) {
+    tile_sum::<1>(input, output);
+}
+
+#[cube(launch_unchecked)]
+fn tile_sum_double_kernel(input: &Array<f32>, output: &mut Array<f32>) {
+    tile_sum::<2>(input, output);
+}
+
+#[cube(launch_unchecked)]
+fn tile_sum_triple_kernel(input: &Array<f32>, output: &mut Array<f32>) {
+    tile_sum::<3>(input, output);
+}
+
+pub fn test_pipeline_stages<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let stage_size = STAGE_SIZE as usize;
+
+    // Fewer tiles than stages leave the last stages of the prologue empty.
+    for num_tiles in [1, 2, 5] {
+        let input: Vec<f32> = (0..num_tiles * stage_size).map(|i| i as f32).collect();
+        let expected: Vec<f32> = (0..stage_size)
+            .map(|unit| {
+                (0..num_tiles)
+                    .map(|tile| input[tile * stage_size + (unit + tile) % stage_size])
+                    .sum()
+            })
+            .collect();
+        let input_handle = client.create(f32::as_bytes(&input));
+
+        for stages in 1..=3 {
+            let output = client.empty(stage_size * core::mem::size_of::<f32>());
+            let cube_count = CubeCount::Static(1, 1, 1);
+            let cube_dim = CubeDim::new(STAGE_SIZE, 1, 1);
+            let input_arg = unsafe { ArrayArg::from_raw_parts(&input_handle, input.len(), 1) };
+            let output_arg = unsafe { ArrayArg::from_raw_parts(&output, stage_size, 1) };
+
+            unsafe {
+                match stages {
+                    1 => tile_sum_single_kernel::launch_unchecked::<R>(
+                        &client, cube_count, cube_dim, input_arg, output_arg,
+                    ),
+                    2 => tile_sum_double_kernel::launch_unchecked::<R>(
+                        &client, cube_count, cube_dim, input_arg, output_arg,
+                    ),
+                    _ => tile_sum_triple_kernel::launch_unchecked::<R>(
+                        &client, cube_count, cube_dim, input_arg, output_arg,
+                    ),
+                }
+            };
+
+            assert_eq!(
+                f32::from_bytes(&client.read(output.binding())),
+                expected,
+                "{num_tiles} tiles with {stages} stages"
+            );
+        }
+    }
+}
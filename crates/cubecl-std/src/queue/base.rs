@@ -0,0 +1,127 @@
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+use cubecl_core as cubecl;
+
+/// The value of the empty slots of a [work queue](WorkQueue), returned by [pop_work] once every
+/// item is finished. It can't be used as a work item.
+pub const NO_WORK: u32 = u32::MAX;
+
+/// A queue of work items on the device, to which kernels push follow-up work consumed by a
+/// persistent kernel without going back to the host.
+///
+/// The queue is made of two buffers: the `items`, where each item pushed gets its own slot, and
+/// the `state`, holding the next slot to pop (the head), the next slot to push (the tail) and the
+/// number of items pushed but not finished yet. Slots aren't reused, so the capacity must hold every item pushed during
+/// the lifetime of the queue, including the initial ones.
+pub struct WorkQueue {
+    /// The slots of the items, [empty](NO_WORK) until an item is pushed into them.
+    pub items: Handle,
+    /// The head, tail and pending count of the queue.
+    pub state: Handle,
+    capacity: usize,
+}
+
+impl WorkQueue {
+    /// Create a queue of the given capacity, holding the initial items.
+    pub fn new<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        capacity: usize,
+        initial: &[u32],
+    ) -> Self {
+        assert!(
+            initial.len() <= capacity,
+            "The initial items don't fit in the capacity of the queue"
+        );
+        let mut items = vec![NO_WORK; capacity];
+        items[..initial.len()].copy_from_slice(initial);
+        let num_initial = initial.len() as u32;
+
+        Self {
+            items: client.create(u32::as_bytes(&items)),
+            state: client.create(u32::as_bytes(&[0, num_initial, num_initial])),
+            capacity,
+        }
+    }
+
+    /// The items argument of a kernel using the queue.
+    pub fn items_arg<R: Runtime>(&self) -> ArrayArg<'_, R> {
+        unsafe { ArrayArg::from_raw_parts(&self.items, self.capacity, 1) }
+    }
+
+    /// The state argument of a kernel using the queue.
+    pub fn state_arg<R: Runtime>(&self) -> ArrayArg<'_, R> {
+        unsafe { ArrayArg::from_raw_parts(&self.state, 3, 1) }
+    }
+
+    /// The number of items pushed into the queue so far, including the initial ones.
+    pub fn num_pushed<R: Runtime>(&self, client: &ComputeClient<R::Server, R::Channel>) -> u32 {
+        u32::from_bytes(&client.read(self.state.clone().binding()))[1]
+    }
+}
+
+/// Push a follow-up work item into the [queue](WorkQueue), from any unit.
+///
+/// Must be called while processing a popped item, before it is [finished](finish_work), so the
+/// queue can't look done while the new item isn't in it yet. The item is carried by value: data
+/// written elsewhere by the unit isn't guaranteed to be visible to the cube that pops it.
+#[cube]
+pub fn push_work(items: &Array<AtomicU32>, state: &Array<AtomicU32>, item: u32) {
+    AtomicU32::add(&state[2], 1);
+    let slot = AtomicU32::add(&state[1], 1);
+    AtomicU32::store(&items[slot], item);
+}
+
+/// Pop the next work item of the [queue](WorkQueue) for the current cube, waiting for it to be
+/// pushed when the queue is momentarily empty.
+///
+/// Every unit of the cube gets the same item, or [NO_WORK] once every item pushed is finished, at
+/// which point no more work can appear. Since cubes wait on each other, the consumer must be a
+/// persistent kernel with no more cubes than the device can run at once.
+///
+/// # Example
+///
+/// ```ignore
+/// loop {
+///     let item = pop_work(items, state);
+///     if item == NO_WORK {
+///         break;
+///     }
+///     // Process the item with the whole cube, pushing its follow-up work.
+///     finish_work(state);
+/// }
+/// ```
+#[cube]
+pub fn pop_work(items: &Array<AtomicU32>, state: &Array<AtomicU32>) -> u32 {
+    let mut popped = SharedMemory::<u32>::new(1);
+    if UNIT_POS == 0 {
+        let slot = AtomicU32::add(&state[0], 1);
+        let mut item = NO_WORK;
+
+        loop {
+            if slot < items.len() {
+                item = AtomicU32::load(&items[slot]);
+            }
+            if item != NO_WORK || AtomicU32::load(&state[2]) == 0 {
+                break;
+            }
+        }
+
+        popped[0] = item;
+    }
+    sync_units();
+
+    let item = popped[0];
+    // The item of the cube isn't overwritten by its next pop before every unit has read it.
+    sync_units();
+    item
+}
+
+/// Mark the item popped by the cube as finished, once all of its follow-up work is pushed.
+#[cube]
+pub fn finish_work(state: &Array<AtomicU32>) {
+    // The pushes of every unit are counted before the item stops being pending.
+    sync_storage();
+    if UNIT_POS == 0 {
+        AtomicU32::sub(&state[2], 1);
+    }
+}
@@ -0,0 +1,58 @@
+#![allow(missing_docs)]
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+use super::{finish_work, pop_work, push_work, WorkQueue, NO_WORK};
+
+/// Visit the nodes of a complete binary tree stored as a heap, starting from the root at 1, each
+/// node pushing its children.
+#[cube(launch_unchecked)]
+fn visit_tree_kernel(items: &Array<AtomicU32>, state: &Array<AtomicU32>, visited: &mut Array<u32>) {
+    loop {
+        let node = pop_work(items, state);
+        if node == NO_WORK {
+            break;
+        }
+
+        if UNIT_POS == 0 {
+            visited[node] += node;
+        }
+        let child = node * 2 + UNIT_POS;
+        if UNIT_POS < 2 && child < visited.len() {
+            push_work(items, state, child);
+        }
+
+        finish_work(state);
+    }
+}
+
+pub fn test_work_queue_tree<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let num_nodes = 1000;
+    let expected: Vec<u32> = (0..num_nodes as u32).collect();
+
+    for cube_count in [1, 4] {
+        let queue = WorkQueue::new::<R>(&client, num_nodes, &[1]);
+        let visited = client.create(u32::as_bytes(&vec![0; num_nodes]));
+
+        unsafe {
+            visit_tree_kernel::launch_unchecked::<R>(
+                &client,
+                CubeCount::Static(cube_count, 1, 1),
+                CubeDim::new(32, 1, 1),
+                queue.items_arg(),
+                queue.state_arg(),
+                ArrayArg::from_raw_parts(&visited, num_nodes, 1),
+            )
+        };
+
+        // Each node is visited exactly once.
+        assert_eq!(
+            u32::from_bytes(&client.read(visited.binding())),
+            expected,
+            "{cube_count} cubes"
+        );
+        assert_eq!(queue.num_pushed::<R>(&client), num_nodes as u32 - 1);
+    }
+}
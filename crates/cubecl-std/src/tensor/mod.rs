@@ -0,0 +1,6 @@
+mod permute;
+
+pub use permute::*;
+
+#[cfg(feature = "export_tests")]
+pub mod tests;
@@ -0,0 +1,231 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, CubeCount};
+
+/// Side of the square tiles transposed in shared memory.
+const TILE_SIZE: u32 = 32;
+/// Number of rows of the tile read or written by the units of a cube at once.
+const BLOCK_ROWS: u32 = 8;
+
+/// Copy the input into the contiguous output of the same shape, where the dimension `dim_row` of
+/// the input is its most contiguous one.
+///
+/// Each cube transposes a tile of the `dim_row` and the last dimensions in shared memory, so
+/// both the reads and the writes are coalesced. The rows of the tile are padded by one element to
+/// avoid bank conflicts when reading its columns.
+#[cube(launch_unchecked)]
+fn permute_tiled_kernel<E: CubePrimitive>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    #[comptime] dim_row: u32,
+) {
+    let rank = output.rank();
+    let dim_col = rank - 1;
+    let num_rows = output.shape(dim_row);
+    let num_cols = output.shape(dim_col);
+
+    // The other dimensions are flattened in the cube position.
+    let mut batch = CUBE_POS_Z;
+    let mut offset_input = 0;
+    let mut offset_output = 0;
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+
+        if dim != dim_row && dim != dim_col {
+            let coordinate = batch % output.shape(dim);
+            batch /= output.shape(dim);
+            offset_input += coordinate * input.stride(dim);
+            offset_output += coordinate * output.stride(dim);
+        }
+    }
+
+    let stride = TILE_SIZE + 1;
+    let mut tile = SharedMemory::<E>::new(TILE_SIZE * stride);
+    let tile_row = CUBE_POS_Y * TILE_SIZE;
+    let tile_col = CUBE_POS_X * TILE_SIZE;
+
+    #[unroll]
+    for j in 0..TILE_SIZE / BLOCK_ROWS {
+        let local_col = UNIT_POS_Y + j * BLOCK_ROWS;
+        let row = tile_row + UNIT_POS_X;
+        let col = tile_col + local_col;
+
+        if row < num_rows && col < num_cols {
+            tile[local_col * stride + UNIT_POS_X] =
+                input[offset_input + row * input.stride(dim_row) + col * input.stride(dim_col)];
+        }
+    }
+
+    sync_units();
+
+    #[unroll]
+    for j in 0..TILE_SIZE / BLOCK_ROWS {
+        let local_row = UNIT_POS_Y + j * BLOCK_ROWS;
+        let row = tile_row + local_row;
+        let col = tile_col + UNIT_POS_X;
+
+        if row < num_rows && col < num_cols {
+            output[offset_output + row * output.stride(dim_row) + col] =
+                tile[UNIT_POS_X * stride + local_row];
+        }
+    }
+}
+
+/// Copy the input into the contiguous output of the same shape, where the last dimension of the
+/// input is its most contiguous one, so the reads of consecutive units are already coalesced.
+#[cube(launch_unchecked)]
+fn permute_copy_kernel<E: CubePrimitive>(input: &Tensor<E>, output: &mut Tensor<E>) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let mut offset_input = 0;
+    for dim in 0..output.rank() {
+        let coordinate = ABSOLUTE_POS / output.stride(dim) % output.shape(dim);
+        offset_input += coordinate * input.stride(dim);
+    }
+
+    output[ABSOLUTE_POS] = input[offset_input];
+}
+
+/// Transpose the last two dimensions of a tensor into the contiguous output.
+pub fn transpose_2d<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    output: TensorHandleRef<'_, R>,
+) {
+    let rank = input.shape.len();
+    assert!(rank >= 2, "Can't transpose a tensor of rank {rank}");
+
+    let mut axes: Vec<usize> = (0..rank).collect();
+    axes.swap(rank - 2, rank - 1);
+
+    permute::<R, E>(client, input, &axes, output)
+}
+
+/// Permute the dimensions of a tensor into the contiguous output, whose dimension `i` is the
+/// dimension `axes[i]` of the input.
+pub fn permute<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<'_, R>,
+    axes: &[usize],
+    output: TensorHandleRef<'_, R>,
+) {
+    let rank = input.shape.len();
+    let mut sorted = axes.to_vec();
+    sorted.sort();
+    assert!(
+        sorted.iter().copied().eq(0..rank),
+        "The axes {axes:?} aren't a permutation of the {rank} dimensions"
+    );
+
+    let shape: Vec<usize> = axes.iter().map(|axis| input.shape[*axis]).collect();
+    let strides: Vec<usize> = axes.iter().map(|axis| input.strides[*axis]).collect();
+    assert_eq!(
+        output.shape, shape,
+        "The output should have the permuted shape"
+    );
+    assert_eq!(
+        output.strides,
+        contiguous_strides(&shape),
+        "The output should be contiguous"
+    );
+
+    let num_elems: usize = shape.iter().product();
+    if num_elems == 0 {
+        return;
+    }
+    let permuted = unsafe { TensorHandleRef::<R>::from_raw_parts(input.handle, &strides, &shape) };
+
+    let dim_row = match tiled_dim(&shape, &strides) {
+        Some(dim_row) => dim_row,
+        None => {
+            let cube_dim = CubeDim::default();
+            let cube_count = calculate_cube_count_elemwise(num_elems, cube_dim);
+
+            unsafe {
+                permute_copy_kernel::launch_unchecked::<E, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    permuted.as_tensor_arg(1),
+                    output.as_tensor_arg(1),
+                );
+            }
+            return;
+        }
+    };
+
+    let num_batches: usize = shape
+        .iter()
+        .enumerate()
+        .filter(|(dim, _)| *dim != dim_row && *dim != rank - 1)
+        .map(|(_, shape)| shape)
+        .product();
+    let cube_count = CubeCount::Static(
+        (shape[rank - 1] as u32).div_ceil(TILE_SIZE),
+        (shape[dim_row] as u32).div_ceil(TILE_SIZE),
+        num_batches as u32,
+    );
+
+    unsafe {
+        permute_tiled_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            CubeDim::new(TILE_SIZE, BLOCK_ROWS, 1),
+            permuted.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            dim_row as u32,
+        );
+    }
+}
+
+/// The strides of a contiguous tensor of the given shape.
+pub fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for dim in (0..shape.len().saturating_sub(1)).rev() {
+        strides[dim] = strides[dim + 1] * shape[dim + 1];
+    }
+    strides
+}
+
+/// The dimension to transpose with the last one, which is the most contiguous dimension of the
+/// input, or `None` when it's already the last one.
+fn tiled_dim(shape: &[usize], strides: &[usize]) -> Option<usize> {
+    let rank = shape.len();
+    // Dimensions of size 1 are never iterated, so their stride doesn't matter.
+    let dim_row = (0..rank.saturating_sub(1))
+        .filter(|dim| shape[*dim] > 1 && strides[*dim] > 0)
+        .min_by_key(|dim| strides[*dim])?;
+
+    match shape[rank - 1] == 1 || strides[rank - 1] <= strides[dim_row] {
+        true => None,
+        false => Some(dim_row),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transposed_matrix_is_tiled() {
+        assert_eq!(tiled_dim(&[8, 4, 2], &[8, 1, 4]), Some(1));
+    }
+
+    #[test]
+    fn contiguous_last_dim_is_copied() {
+        assert_eq!(tiled_dim(&[8, 4, 2], &[2, 16, 1]), None);
+    }
+
+    #[test]
+    fn contiguous_strides_are_row_major() {
+        assert_eq!(contiguous_strides(&[2, 3, 4]), [12, 4, 1]);
+        assert_eq!(contiguous_strides(&[]), [0usize; 0]);
+    }
+
+    #[test]
+    fn unit_dims_are_ignored() {
+        assert_eq!(tiled_dim(&[1, 4, 2], &[1, 1, 4]), Some(1));
+        assert_eq!(tiled_dim(&[4, 2, 1], &[2, 1, 8]), None);
+    }
+}
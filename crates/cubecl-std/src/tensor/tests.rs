@@ -0,0 +1,84 @@
+#![allow(missing_docs)]
+
+use cubecl_core::prelude::*;
+
+use super::{contiguous_strides, permute, transpose_2d};
+
+/// The contiguous data of the permutation of the contiguous `data`.
+fn permute_cpu(data: &[f32], shape: &[usize], axes: &[usize]) -> Vec<f32> {
+    let strides = contiguous_strides(shape);
+    let shape_out: Vec<usize> = axes.iter().map(|axis| shape[*axis]).collect();
+
+    (0..data.len())
+        .map(|index| {
+            let mut remaining = index;
+            let mut offset = 0;
+            for dim in (0..shape.len()).rev() {
+                offset += remaining % shape_out[dim] * strides[axes[dim]];
+                remaining /= shape_out[dim];
+            }
+            data[offset]
+        })
+        .collect()
+}
+
+fn test_permute<R: Runtime>(shape: Vec<usize>, axes: &[usize], device: &R::Device) {
+    let client = R::client(device);
+    let data: Vec<f32> = (0..shape.iter().product::<usize>())
+        .map(|i| i as f32)
+        .collect();
+    let input = client.create(f32::as_bytes(&data));
+    let strides = contiguous_strides(&shape);
+    let shape_out: Vec<usize> = axes.iter().map(|axis| shape[*axis]).collect();
+    let strides_out = contiguous_strides(&shape_out);
+    let output = client.empty(data.len() * core::mem::size_of::<f32>());
+
+    unsafe {
+        permute::<R, f32>(
+            &client,
+            TensorHandleRef::from_raw_parts(&input, &strides, &shape),
+            axes,
+            TensorHandleRef::from_raw_parts(&output, &strides_out, &shape_out),
+        );
+    }
+
+    let actual = client.read(output.binding());
+    assert_eq!(f32::from_bytes(&actual), permute_cpu(&data, &shape, axes));
+}
+
+pub fn test_transpose_2d<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let shape = [3, 60, 70];
+    let data: Vec<f32> = (0..3 * 60 * 70).map(|i| i as f32).collect();
+    let input = client.create(f32::as_bytes(&data));
+    let strides = contiguous_strides(&shape);
+    let shape_out = [3, 70, 60];
+    let strides_out = contiguous_strides(&shape_out);
+    let output = client.empty(data.len() * core::mem::size_of::<f32>());
+
+    unsafe {
+        transpose_2d::<R, f32>(
+            &client,
+            TensorHandleRef::from_raw_parts(&input, &strides, &shape),
+            TensorHandleRef::from_raw_parts(&output, &strides_out, &shape_out),
+        );
+    }
+
+    let actual = client.read(output.binding());
+    assert_eq!(
+        f32::from_bytes(&actual),
+        permute_cpu(&data, &shape, &[0, 2, 1])
+    );
+}
+
+pub fn test_permute_last_dim<R: Runtime>(device: &R::Device) {
+    test_permute::<R>(vec![5, 33, 40], &[2, 0, 1], device);
+}
+
+pub fn test_permute_batches<R: Runtime>(device: &R::Device) {
+    test_permute::<R>(vec![2, 3, 4, 40], &[1, 3, 0, 2], device);
+}
+
+pub fn test_permute_keep_last_dim<R: Runtime>(device: &R::Device) {
+    test_permute::<R>(vec![6, 7, 8], &[1, 0, 2], device);
+}
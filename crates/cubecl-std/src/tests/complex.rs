@@ -0,0 +1,19 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_complex {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_complex_arithmetic() {
+            cubecl_std::complex::tests::test_complex_arithmetic::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_complex_shared_memory() {
+            cubecl_std::complex::tests::test_complex_shared_memory::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+    };
+}
@@ -0,0 +1,12 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_fixed {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_fixed_arithmetic() {
+            cubecl_std::fixed::tests::test_fixed_arithmetic::<TestRuntime>(&Default::default())
+        }
+    };
+}
@@ -0,0 +1,23 @@
+mod complex;
+mod fixed;
+mod persistent;
+mod pipeline;
+mod queue;
+mod tensor;
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_all {
+    () => {
+        mod stdlib {
+            use super::*;
+
+            cubecl_std::testgen_complex!();
+            cubecl_std::testgen_fixed!();
+            cubecl_std::testgen_persistent!();
+            cubecl_std::testgen_pipeline!();
+            cubecl_std::testgen_queue!();
+            cubecl_std::testgen_tensor!();
+        }
+    };
+}
@@ -0,0 +1,14 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_persistent {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_persistent_work_items() {
+            cubecl_std::persistent::tests::test_persistent_work_items::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+    };
+}
@@ -0,0 +1,12 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_pipeline {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_pipeline_stages() {
+            cubecl_std::pipeline::tests::test_pipeline_stages::<TestRuntime>(&Default::default())
+        }
+    };
+}
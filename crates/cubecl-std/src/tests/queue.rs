@@ -0,0 +1,12 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_queue {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_work_queue_tree() {
+            cubecl_std::queue::tests::test_work_queue_tree::<TestRuntime>(&Default::default())
+        }
+    };
+}
@@ -0,0 +1,29 @@
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_tensor {
+    () => {
+        use super::*;
+
+        #[test]
+        pub fn test_transpose_2d() {
+            cubecl_std::tensor::tests::test_transpose_2d::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_permute_last_dim() {
+            cubecl_std::tensor::tests::test_permute_last_dim::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_permute_batches() {
+            cubecl_std::tensor::tests::test_permute_batches::<TestRuntime>(&Default::default())
+        }
+
+        #[test]
+        pub fn test_permute_keep_last_dim() {
+            cubecl_std::tensor::tests::test_permute_keep_last_dim::<TestRuntime>(
+                &Default::default(),
+            )
+        }
+    };
+}
@@ -0,0 +1,192 @@
+use core::{marker::PhantomData, ops::Range};
+
+use bytemuck::Pod;
+use cubecl_common::rand::{Rng, SeedableRng, StdRng};
+use cubecl_runtime::{
+    channel::ComputeChannel,
+    client::ComputeClient,
+    server::{ComputeServer, Handle},
+};
+use num_traits::{NumCast, ToPrimitive};
+
+use crate::Tolerance;
+
+/// A test running a kernel against a reference implementation on the CPU.
+///
+/// The inputs are filled with uniformly random values, and the outputs are zeroed both on the
+/// device and for the reference. Each case uses a new seed derived from [seed](Self::seed), which
+/// is printed on failure so the case can be reproduced.
+#[derive(Debug, Clone)]
+pub struct GoldenTest<E> {
+    inputs: Vec<(usize, Range<f64>)>,
+    outputs: Vec<usize>,
+    cases: u64,
+    seed: u64,
+    tolerance: Tolerance,
+    _element: PhantomData<E>,
+}
+
+impl<E: Pod + NumCast> Default for GoldenTest<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Pod + NumCast> GoldenTest<E> {
+    /// Create a test without inputs or outputs, running 4 cases with the default tolerance.
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            cases: 4,
+            seed: 0,
+            tolerance: Tolerance::default(),
+            _element: PhantomData,
+        }
+    }
+
+    /// Add an input of `len` elements sampled in `range`.
+    ///
+    /// Sampled values are converted to the element type, so integers are truncated.
+    pub fn input(mut self, len: usize, range: Range<f64>) -> Self {
+        assert!(range.start < range.end, "Input range must not be empty");
+        self.inputs.push((len, range));
+        self
+    }
+
+    /// Add an output of `len` elements.
+    pub fn output(mut self, len: usize) -> Self {
+        self.outputs.push(len);
+        self
+    }
+
+    /// Set the number of randomized cases to run.
+    pub fn cases(mut self, cases: u64) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Set the seed of the first case.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the tolerance used to compare the outputs.
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Run each case, launching the kernel with `launch` and computing the expected outputs with
+    /// `reference`.
+    ///
+    /// `launch` receives the handles of the inputs and outputs in the order they were added, and
+    /// `reference` receives their values.
+    ///
+    /// # Panics
+    ///
+    /// When an output value isn't within the tolerance of its expected value.
+    pub fn run<Server: ComputeServer, Channel: ComputeChannel<Server>>(
+        &self,
+        client: &ComputeClient<Server, Channel>,
+        launch: impl Fn(&ComputeClient<Server, Channel>, &[Handle], &[Handle]),
+        reference: impl Fn(&[Vec<E>], &mut [Vec<E>]),
+    ) {
+        for case in 0..self.cases {
+            let seed = self.seed.wrapping_add(case);
+            let inputs = self.generate_inputs(seed);
+            let mut expected = self.zeroed_outputs();
+
+            let input_handles = inputs
+                .iter()
+                .map(|input| client.create(bytemuck::cast_slice(input)))
+                .collect::<Vec<_>>();
+            let output_handles = expected
+                .iter()
+                .map(|output| client.create(bytemuck::cast_slice(output)))
+                .collect::<Vec<_>>();
+
+            launch(client, &input_handles, &output_handles);
+            reference(&inputs, &mut expected);
+
+            for (output, (handle, expected)) in output_handles.iter().zip(expected).enumerate() {
+                let actual = client.read(handle.clone().binding());
+                let actual = bytemuck::cast_slice(&actual);
+
+                self.compare(actual, &expected).unwrap_or_else(|message| {
+                    panic!("Case {case} (seed {seed}), output {output}: {message}")
+                });
+            }
+        }
+    }
+
+    fn generate_inputs(&self, seed: u64) -> Vec<Vec<E>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        self.inputs
+            .iter()
+            .map(|(len, range)| {
+                (0..*len)
+                    .map(|_| {
+                        let value = rng.gen_range(range.clone());
+                        E::from(value).unwrap_or_else(|| {
+                            panic!("Sampled value {value} doesn't fit the element type")
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn zeroed_outputs(&self) -> Vec<Vec<E>> {
+        let zero = E::from(0).unwrap();
+        self.outputs.iter().map(|len| vec![zero; *len]).collect()
+    }
+
+    fn compare(&self, actual: &[E], expected: &[E]) -> Result<(), String> {
+        if actual.len() != expected.len() {
+            return Err(format!(
+                "Output has {} values, expected {}",
+                actual.len(),
+                expected.len()
+            ));
+        }
+
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            let (a, e) = (to_f64(a), to_f64(e));
+            if !self.tolerance.accepts(a, e) {
+                return Err(format!(
+                    "Values differ more than the tolerance: index={i} actual={a}, expected={e}, \
+                     difference={}, tolerance=({})",
+                    (a - e).abs(),
+                    self.tolerance
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn to_f64<E: ToPrimitive>(value: &E) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn golden_inputs_are_reproducible() {
+        let test = GoldenTest::<f32>::new()
+            .input(16, -1.0..1.0)
+            .input(4, 2.0..3.0);
+
+        let inputs = test.generate_inputs(7);
+        assert_eq!(inputs, test.generate_inputs(7));
+        assert_ne!(inputs, test.generate_inputs(8));
+        assert!(inputs[0].iter().all(|value| (-1.0..1.0).contains(value)));
+        assert!(inputs[1].iter().all(|value| (2.0..3.0).contains(value)));
+    }
+}
@@ -0,0 +1,36 @@
+//! Test utilities for the kernels written with CubeCL.
+//!
+//! A [golden test](GoldenTest) compares a kernel against a CPU implementation over randomized
+//! inputs. The test is written once, generic over the runtime, and called with the client of each
+//! enabled runtime:
+//!
+//! ```ignore
+//! pub fn test_double<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+//!     GoldenTest::<f32>::new()
+//!         .input(64, -10.0..10.0)
+//!         .output(64)
+//!         .run(
+//!             &client,
+//!             |client, inputs, outputs| unsafe {
+//!                 kernel_double::launch_unchecked::<R>(
+//!                     client,
+//!                     CubeCount::Static(1, 1, 1),
+//!                     CubeDim::new(64, 1, 1),
+//!                     ArrayArg::from_raw_parts(&inputs[0], 64, 1),
+//!                     ArrayArg::from_raw_parts(&outputs[0], 64, 1),
+//!                 )
+//!             },
+//!             |inputs, outputs| {
+//!                 for (input, output) in inputs[0].iter().zip(outputs[0].iter_mut()) {
+//!                     *output = input * 2.0;
+//!                 }
+//!             },
+//!         );
+//! }
+//! ```
+
+mod golden;
+mod tolerance;
+
+pub use golden::*;
+pub use tolerance::*;
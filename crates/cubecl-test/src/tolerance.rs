@@ -0,0 +1,92 @@
+use core::fmt::Display;
+
+/// The difference allowed between a kernel output and its expected value.
+///
+/// A value passes when it is within the absolute tolerance or within the relative tolerance of
+/// the expected value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Values must be equal.
+    pub fn exact() -> Self {
+        Self::absolute(0.0)
+    }
+
+    /// Values may differ by at most `epsilon`.
+    pub fn absolute(epsilon: f64) -> Self {
+        Self {
+            absolute: epsilon,
+            relative: 0.0,
+        }
+    }
+
+    /// Values may differ by at most `epsilon` times the expected value.
+    pub fn relative(epsilon: f64) -> Self {
+        Self {
+            absolute: 0.0,
+            relative: epsilon,
+        }
+    }
+
+    /// Whether the actual value is close enough to the expected one.
+    ///
+    /// `NaN` is only accepted where `NaN` is expected.
+    pub fn accepts(&self, actual: f64, expected: f64) -> bool {
+        if actual.is_nan() || expected.is_nan() {
+            return actual.is_nan() && expected.is_nan();
+        }
+        if actual == expected {
+            return true;
+        }
+
+        let difference = (actual - expected).abs();
+        difference <= self.absolute || difference <= self.relative * expected.abs()
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 1e-5,
+            relative: 1e-3,
+        }
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "absolute={}, relative={}", self.absolute, self.relative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerance_accepts_absolute_or_relative() {
+        let tolerance = Tolerance {
+            absolute: 0.1,
+            relative: 0.01,
+        };
+
+        assert!(tolerance.accepts(1.05, 1.0));
+        assert!(tolerance.accepts(101.0, 100.0));
+        assert!(!tolerance.accepts(1.2, 1.0));
+        assert!(!tolerance.accepts(103.0, 100.0));
+    }
+
+    #[test]
+    fn tolerance_matches_nan_only_with_nan() {
+        let tolerance = Tolerance::default();
+
+        assert!(tolerance.accepts(f64::NAN, f64::NAN));
+        assert!(!tolerance.accepts(f64::NAN, 1.0));
+        assert!(!tolerance.accepts(1.0, f64::NAN));
+        assert!(tolerance.accepts(f64::INFINITY, f64::INFINITY));
+    }
+}
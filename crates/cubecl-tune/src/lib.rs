@@ -0,0 +1,139 @@
+//! Tune the operations selected by autotune ahead of time on the local device, so production
+//! deployments can ship the results instead of tuning at the first launch.
+//!
+//! Run it with the runtimes to tune, writing the results of each runtime in a directory:
+//!
+//! ```sh
+//! cargo run -p cubecl-tune --release --features cuda -- --output tune
+//! ```
+//!
+//! Every tuner writes its results to `<output>/<runtime>/<tuner>.json`. Applications load them
+//! with [TuneResults::load](cubecl::tune::TuneResults::load) and import them with
+//! [import_tune_results](cubecl::linalg::tune::import_tune_results) and
+//! [fft::import_tune_results](cubecl::fft::import_tune_results).
+
+use cubecl::fft::{fft_tune_offline, FftDirection};
+use cubecl::linalg::attention;
+use cubecl::linalg::sparse::{spmm_tune_offline, CsrMatrix};
+use cubecl::linalg::tensor::{histogram_tune_offline, TensorHandle};
+use cubecl::linalg::tune::export_tune_results;
+use cubecl::prelude::*;
+use cubecl::tune::TuneResults;
+
+/// The number of values of each head of the attentions.
+pub const ATTENTION_HEAD_DIM: usize = 64;
+/// The number of columns of the sparse matrices and of rows of the dense matrices they multiply.
+pub const SPMM_DEPTH: usize = 4096;
+/// The number of columns of the dense matrices multiplied by the sparse matrices.
+pub const SPMM_COLUMNS: usize = 64;
+
+/// The workloads to tune.
+#[derive(Debug, Clone)]
+pub struct TuneOptions {
+    /// The lengths of the signals of the FFTs, which should be powers of two.
+    pub fft_lengths: Vec<usize>,
+    /// The numbers of bins of the histograms.
+    pub histogram_bins: Vec<usize>,
+    /// The sequence lengths of the queries and keys of the attentions, with heads of
+    /// [ATTENTION_HEAD_DIM] values.
+    pub attention_lengths: Vec<usize>,
+    /// The numbers of nonzero values per row of the sparse matrices multiplied by dense matrices
+    /// of [SPMM_COLUMNS] columns.
+    pub spmm_row_lengths: Vec<usize>,
+    /// The number of values of each workload, split in signals of the given length for the FFTs,
+    /// in sequences for the attentions and in rows for the sparse matrices.
+    pub size: usize,
+}
+
+impl Default for TuneOptions {
+    fn default() -> Self {
+        Self {
+            fft_lengths: vec![256, 1024, 4096],
+            histogram_bins: vec![64, 256, 1024],
+            attention_lengths: vec![128, 512, 2048],
+            spmm_row_lengths: vec![4, 32, 256],
+            size: 1 << 20,
+        }
+    }
+}
+
+/// The results of the tuners on a runtime.
+#[derive(Debug)]
+pub struct RuntimeResults {
+    /// The name of the runtime.
+    pub runtime: &'static str,
+    /// The results of each tuner.
+    pub results: Vec<TuneResults<String>>,
+}
+
+/// Benchmark every candidate of the tunable operations for the workloads on the device, and
+/// return the results of each tuner.
+pub fn tune<R: Runtime>(device: &R::Device, options: &TuneOptions) -> RuntimeResults {
+    let client = R::client(device);
+
+    for n in options.fft_lengths.iter().copied() {
+        let num_signals = usize::max(options.size / n, 1);
+        let input = TensorHandle::<R, f32>::zeros(&client, vec![num_signals, n, 2]);
+
+        for direction in [FftDirection::Forward, FftDirection::Inverse] {
+            fft_tune_offline::<R, f32>(&client, input.as_ref(), direction);
+        }
+    }
+
+    let values = TensorHandle::<R, f32>::zeros(&client, vec![options.size]);
+    for num_bins in options.histogram_bins.iter().copied() {
+        histogram_tune_offline::<R, f32>(&client, values.as_ref(), num_bins, 0.0, 1.0);
+    }
+
+    for seq in options.attention_lengths.iter().copied() {
+        let num_batches = usize::max(options.size / (seq * ATTENTION_HEAD_DIM), 1);
+        let shape = vec![num_batches, seq, ATTENTION_HEAD_DIM];
+        let input = TensorHandle::<R, f32>::zeros(&client, shape.clone());
+        let out = TensorHandle::<R, f32>::empty(&client, shape);
+
+        for causal in [false, true] {
+            attention::tune_offline::<R, f32>(
+                &client,
+                input.as_ref(),
+                input.as_ref(),
+                input.as_ref(),
+                out.as_ref(),
+                causal,
+            );
+        }
+    }
+
+    let dense = TensorHandle::<R, f32>::zeros(&client, vec![SPMM_DEPTH, SPMM_COLUMNS]);
+    for row_length in options.spmm_row_lengths.iter().copied() {
+        let sparse = sparse_matrix::<R>(&client, options.size, row_length);
+        spmm_tune_offline(&client, &sparse, dense.as_ref());
+    }
+
+    let mut results = export_tune_results::<R>(&client);
+    results.extend(cubecl::fft::export_tune_results::<R>(&client));
+
+    RuntimeResults {
+        runtime: R::name(),
+        results,
+    }
+}
+
+/// A sparse matrix of about `size` nonzero values, spread evenly over the columns with
+/// `row_length` values per row.
+fn sparse_matrix<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    size: usize,
+    row_length: usize,
+) -> CsrMatrix<R, f32> {
+    let row_length = usize::min(row_length, SPMM_DEPTH);
+    let rows = usize::max(size / row_length, 1);
+    let stride = SPMM_DEPTH / row_length;
+
+    let row_offsets: Vec<u32> = (0..=rows).map(|row| (row * row_length) as u32).collect();
+    let column_indices: Vec<u32> = (0..rows)
+        .flat_map(|row| (0..row_length).map(move |i| (i * stride + row % stride) as u32))
+        .collect();
+    let values = vec![0.0; rows * row_length];
+
+    CsrMatrix::from_host(client, SPMM_DEPTH, &row_offsets, &column_indices, &values)
+}
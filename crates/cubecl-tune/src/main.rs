@@ -0,0 +1,150 @@
+use cubecl_tune::{RuntimeResults, TuneOptions};
+use std::fmt;
+use std::path::Path;
+
+const USAGE: &str = "Usage: cubecl-tune --output DIR [--fft LENGTH,...] [--histogram BINS,...] \
+                     [--attention LENGTH,...] [--spmm ROW_LENGTH,...] [--size VALUES]";
+
+/// An error ending the command.
+enum Error {
+    /// The arguments are invalid.
+    Args(String),
+    /// The results can't be written.
+    Output { path: String, reason: String },
+    /// No runtime feature is enabled.
+    NoRuntime,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Args(reason) => write!(f, "{reason}\n{USAGE}"),
+            Error::Output { path, reason } => {
+                write!(f, "Can't write the tune results to {path}: {reason}")
+            }
+            Error::NoRuntime => {
+                f.write_str("Nothing was tuned, enable the features of the runtimes to tune")
+            }
+        }
+    }
+}
+
+// The error returned by `main` is printed with its debug representation.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+struct Args {
+    output: String,
+    // Unused when no runtime feature is enabled.
+    #[allow(dead_code)]
+    options: TuneOptions,
+}
+
+fn parse_sizes(arg: &str, value: &str) -> Result<Vec<usize>, Error> {
+    value
+        .split(',')
+        .map(|size| match size.parse::<usize>() {
+            Ok(size) if size > 0 => Ok(size),
+            _ => Err(Error::Args(format!("Invalid size {size} for {arg}"))),
+        })
+        .collect()
+}
+
+fn parse_args() -> Result<Args, Error> {
+    let mut output = None;
+    let mut options = TuneOptions::default();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| Error::Args(format!("Missing value for {arg}")))
+        };
+        match arg.as_str() {
+            "--output" => output = Some(value()?),
+            "--fft" => {
+                options.fft_lengths = parse_sizes(&arg, &value()?)?;
+                if let Some(n) = options.fft_lengths.iter().find(|n| !n.is_power_of_two()) {
+                    return Err(Error::Args(format!(
+                        "The FFT length {n} isn't a power of two"
+                    )));
+                }
+            }
+            "--histogram" => options.histogram_bins = parse_sizes(&arg, &value()?)?,
+            "--attention" => options.attention_lengths = parse_sizes(&arg, &value()?)?,
+            "--spmm" => options.spmm_row_lengths = parse_sizes(&arg, &value()?)?,
+            "--size" => options.size = parse_sizes(&arg, &value()?)?[0],
+            _ => return Err(Error::Args(format!("Unknown argument {arg}"))),
+        }
+    }
+
+    let output = output.ok_or_else(|| Error::Args("Missing --output".to_string()))?;
+
+    Ok(Args { output, options })
+}
+
+/// Write the results of each tuner in the directory of the runtime.
+fn write_results(output: &str, tuned: RuntimeResults) -> Result<(), Error> {
+    let dir = Path::new(output).join(tuned.runtime);
+
+    for results in tuned.results {
+        let path = dir.join(format!("{}.json", results.name));
+        results.save(&path).map_err(|err| Error::Output {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[allow(unused_mut, clippy::vec_init_then_push)]
+fn main() -> Result<(), Error> {
+    let args = parse_args()?;
+
+    let mut tuned = Vec::new();
+
+    #[cfg(feature = "wgpu")]
+    tuned.push(cubecl_tune::tune::<cubecl::wgpu::WgpuRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "wgpu-spirv")]
+    tuned.push(cubecl_tune::tune::<
+        cubecl::wgpu::WgpuRuntime<cubecl::wgpu::spirv::VkSpirvCompiler>,
+    >(&Default::default(), &args.options));
+    #[cfg(feature = "cuda")]
+    tuned.push(cubecl_tune::tune::<cubecl::cuda::CudaRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "hip")]
+    tuned.push(cubecl_tune::tune::<cubecl::hip::HipRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "opencl")]
+    tuned.push(cubecl_tune::tune::<cubecl::opencl::OpenClRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+    #[cfg(feature = "vulkan")]
+    tuned.push(cubecl_tune::tune::<cubecl::vulkan::VulkanRuntime>(
+        &Default::default(),
+        &args.options,
+    ));
+
+    if tuned.is_empty() {
+        return Err(Error::NoRuntime);
+    }
+
+    for tuned in tuned {
+        write_results(&args.output, tuned)?;
+    }
+
+    Ok(())
+}
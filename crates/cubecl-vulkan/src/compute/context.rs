@@ -0,0 +1,87 @@
+use ash::{khr::push_descriptor, vk};
+
+/// The Vulkan objects shared by the server and the storage, destroyed once both are dropped.
+pub struct VkContext {
+    pub(crate) entry: ash::Entry,
+    pub(crate) instance: ash::Instance,
+    pub(crate) physical_device: vk::PhysicalDevice,
+    pub(crate) device: ash::Device,
+    pub(crate) push_descriptor: push_descriptor::Device,
+    pub(crate) memory_properties: vk::PhysicalDeviceMemoryProperties,
+    /// The queue families sharing buffers, compute first and transfer second when they differ.
+    pub(crate) queue_families: Vec<u32>,
+}
+
+impl core::fmt::Debug for VkContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("VkContext {{ device: {:?} }}", self.device.handle()).as_str())
+    }
+}
+
+impl VkContext {
+    /// Find a memory type allowed by `type_bits` with all the `flags`.
+    pub(crate) fn memory_type(
+        &self,
+        type_bits: u32,
+        flags: vk::MemoryPropertyFlags,
+    ) -> Option<u32> {
+        let types = &self.memory_properties.memory_types;
+        (0..self.memory_properties.memory_type_count).find(|index| {
+            type_bits & (1 << index) != 0 && types[*index as usize].property_flags.contains(flags)
+        })
+    }
+
+    /// Create a buffer with its own memory allocation, preferring the given memory properties.
+    pub(crate) fn create_buffer(
+        &self,
+        size: u64,
+        usage: vk::BufferUsageFlags,
+        flags: &[vk::MemoryPropertyFlags],
+    ) -> (vk::Buffer, vk::DeviceMemory) {
+        let sharing_mode = match self.queue_families.len() {
+            1 => vk::SharingMode::EXCLUSIVE,
+            _ => vk::SharingMode::CONCURRENT,
+        };
+        let info = vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(usage)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(&self.queue_families);
+
+        unsafe {
+            let buffer = self.device.create_buffer(&info, None).unwrap();
+            let requirements = self.device.get_buffer_memory_requirements(buffer);
+            let memory_type = flags
+                .iter()
+                .find_map(|flags| self.memory_type(requirements.memory_type_bits, *flags))
+                .expect("No memory type is compatible with the buffer");
+            let info = vk::MemoryAllocateInfo::default()
+                .allocation_size(requirements.size)
+                .memory_type_index(memory_type);
+            let memory = self.device.allocate_memory(&info, None).unwrap();
+            self.device.bind_buffer_memory(buffer, memory, 0).unwrap();
+
+            (buffer, memory)
+        }
+    }
+
+    /// Destroy a buffer created with [create_buffer](Self::create_buffer).
+    ///
+    /// # Safety
+    ///
+    /// The buffer can't be used by any pending command.
+    pub(crate) unsafe fn destroy_buffer(&self, buffer: vk::Buffer, memory: vk::DeviceMemory) {
+        self.device.destroy_buffer(buffer, None);
+        self.device.free_memory(memory, None);
+    }
+}
+
+impl Drop for VkContext {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device_wait_idle().ok();
+            self.device.destroy_device(None);
+            self.instance.destroy_instance(None);
+        }
+    }
+}
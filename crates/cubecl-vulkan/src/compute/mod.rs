@@ -0,0 +1,10 @@
+mod context;
+mod queue;
+mod server;
+mod storage;
+
+pub use context::*;
+pub use server::*;
+pub use storage::*;
+
+pub(crate) use queue::*;
@@ -0,0 +1,159 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use ash::vk;
+
+use super::VkContext;
+
+/// A queue with its own command pool, whose submissions are ordered by a timeline semaphore.
+///
+/// Each submission signals the next value of the semaphore, so waiting on another queue or on the
+/// host only requires the value returned by [submit](Self::submit).
+#[derive(Debug)]
+pub(crate) struct TimelineQueue {
+    context: Arc<VkContext>,
+    queue: vk::Queue,
+    pool: vk::CommandPool,
+    pub(crate) semaphore: vk::Semaphore,
+    /// The value signaled by the last submission.
+    pub(crate) submitted: u64,
+    in_flight: VecDeque<(u64, vk::CommandBuffer)>,
+    free: Vec<vk::CommandBuffer>,
+}
+
+impl TimelineQueue {
+    pub(crate) fn new(context: Arc<VkContext>, family: u32, index: u32) -> Self {
+        let device = &context.device;
+        unsafe {
+            let queue = device.get_device_queue(family, index);
+            let info = vk::CommandPoolCreateInfo::default()
+                .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
+                .queue_family_index(family);
+            let pool = device.create_command_pool(&info, None).unwrap();
+            let mut timeline = vk::SemaphoreTypeCreateInfo::default()
+                .semaphore_type(vk::SemaphoreType::TIMELINE)
+                .initial_value(0);
+            let info = vk::SemaphoreCreateInfo::default().push_next(&mut timeline);
+            let semaphore = device.create_semaphore(&info, None).unwrap();
+
+            Self {
+                context,
+                queue,
+                pool,
+                semaphore,
+                submitted: 0,
+                in_flight: VecDeque::new(),
+                free: Vec::new(),
+            }
+        }
+    }
+
+    /// Start recording a new command buffer.
+    pub(crate) fn begin(&mut self) -> vk::CommandBuffer {
+        self.recycle();
+        let device = &self.context.device;
+
+        let command_buffer = self.free.pop().unwrap_or_else(|| {
+            let info = vk::CommandBufferAllocateInfo::default()
+                .command_pool(self.pool)
+                .level(vk::CommandBufferLevel::PRIMARY)
+                .command_buffer_count(1);
+            unsafe { device.allocate_command_buffers(&info).unwrap()[0] }
+        });
+
+        let info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        unsafe {
+            device.begin_command_buffer(command_buffer, &info).unwrap();
+        }
+
+        command_buffer
+    }
+
+    /// Submit a command buffer recorded with [begin](Self::begin) once the given semaphores reach
+    /// their values, and return the value signaled when it completes.
+    pub(crate) fn submit(
+        &mut self,
+        command_buffer: vk::CommandBuffer,
+        waits: &[(vk::Semaphore, u64)],
+    ) -> u64 {
+        let device = &self.context.device;
+        self.submitted += 1;
+
+        let wait_semaphores = waits
+            .iter()
+            .map(|(semaphore, _)| *semaphore)
+            .collect::<Vec<_>>();
+        let wait_values = waits.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+        let wait_stages = vec![vk::PipelineStageFlags::ALL_COMMANDS; waits.len()];
+        let signal_semaphores = [self.semaphore];
+        let signal_values = [self.submitted];
+        let command_buffers = [command_buffer];
+
+        let mut timeline = vk::TimelineSemaphoreSubmitInfo::default()
+            .wait_semaphore_values(&wait_values)
+            .signal_semaphore_values(&signal_values);
+        let info = vk::SubmitInfo::default()
+            .wait_semaphores(&wait_semaphores)
+            .wait_dst_stage_mask(&wait_stages)
+            .command_buffers(&command_buffers)
+            .signal_semaphores(&signal_semaphores)
+            .push_next(&mut timeline);
+
+        unsafe {
+            device.end_command_buffer(command_buffer).unwrap();
+            device
+                .queue_submit(self.queue, &[info], vk::Fence::null())
+                .unwrap();
+        }
+
+        self.in_flight.push_back((self.submitted, command_buffer));
+        self.submitted
+    }
+
+    /// The value of the last completed submission.
+    pub(crate) fn completed(&self) -> u64 {
+        unsafe {
+            self.context
+                .device
+                .get_semaphore_counter_value(self.semaphore)
+                .unwrap()
+        }
+    }
+
+    /// Block until the submission that signals `value` is completed.
+    pub(crate) fn wait(&self, value: u64) {
+        let semaphores = [self.semaphore];
+        let values = [value];
+        let info = vk::SemaphoreWaitInfo::default()
+            .semaphores(&semaphores)
+            .values(&values);
+        unsafe {
+            self.context
+                .device
+                .wait_semaphores(&info, u64::MAX)
+                .unwrap();
+        }
+    }
+
+    /// Reuse the command buffers of the completed submissions.
+    fn recycle(&mut self) {
+        let completed = self.completed();
+        while let Some((value, command_buffer)) = self.in_flight.front() {
+            if *value > completed {
+                break;
+            }
+            self.free.push(*command_buffer);
+            self.in_flight.pop_front();
+        }
+    }
+}
+
+impl Drop for TimelineQueue {
+    fn drop(&mut self) {
+        self.wait(self.submitted);
+        unsafe {
+            self.context.device.destroy_command_pool(self.pool, None);
+            self.context.device.destroy_semaphore(self.semaphore, None);
+        }
+    }
+}
@@ -0,0 +1,639 @@
+use super::{TimelineQueue, VkContext, VulkanResource, VulkanStorage};
+use ash::vk;
+use cubecl_core::compute::DebugInformation;
+use cubecl_core::Feature;
+use cubecl_core::{prelude::*, CompilationOptions, KernelId};
+use cubecl_runtime::debug::{DebugLogger, ProfileLevel};
+use cubecl_runtime::memory_management::{MemoryUsage, OutOfMemoryError};
+use cubecl_runtime::storage::{BindingResource, StorageId};
+use cubecl_runtime::{
+    memory_management::MemoryManagement,
+    server::{self, ComputeServer},
+};
+use cubecl_runtime::{ExecutionMode, TimestampsError, TimestampsResult};
+use cubecl_spirv::{GLCompute, SpirvCompiler};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub type VkSpirvCompiler = SpirvCompiler<GLCompute>;
+
+/// Vulkan compute server.
+///
+/// Kernels are recorded in a command buffer submitted to the compute queue, while uploads and
+/// readbacks are submitted to a transfer queue. The queues only wait for each other on the memory
+/// they share, so uploads can overlap with the kernels already submitted.
+#[derive(Debug)]
+pub struct VulkanServer {
+    context: Arc<VkContext>,
+    memory_management: MemoryManagement<VulkanStorage>,
+    compute: TimelineQueue,
+    transfer: TimelineQueue,
+    /// The command buffer recording the kernels executed since the last flush.
+    recording: Option<vk::CommandBuffer>,
+    /// The storage bound by the recorded kernels.
+    recorded_storage: HashSet<StorageId>,
+    /// The last compute submission using each storage.
+    submitted_storage: HashMap<StorageId, u64>,
+    /// Staging buffers of the uploads, freed once the transfer submission is completed.
+    staging: Vec<(u64, vk::Buffer, vk::DeviceMemory)>,
+    pipelines: HashMap<KernelId, Pipeline>,
+    tasks_count: usize,
+    tasks_max: usize,
+    logger: DebugLogger,
+    timestamps: KernelTimestamps,
+}
+
+#[derive(Debug)]
+struct Pipeline {
+    module: vk::ShaderModule,
+    descriptor_layout: vk::DescriptorSetLayout,
+    layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+#[derive(Debug)]
+enum KernelTimestamps {
+    Inferred { start_time: Instant },
+    Disabled,
+}
+
+impl KernelTimestamps {
+    fn enable(&mut self) {
+        if !matches!(self, Self::Disabled) {
+            return;
+        }
+
+        *self = Self::Inferred {
+            start_time: Instant::now(),
+        };
+    }
+
+    fn disable(&mut self) {
+        *self = Self::Disabled;
+    }
+}
+
+const HOST_MEMORY: vk::MemoryPropertyFlags = vk::MemoryPropertyFlags::from_raw(
+    vk::MemoryPropertyFlags::HOST_VISIBLE.as_raw()
+        | vk::MemoryPropertyFlags::HOST_COHERENT.as_raw(),
+);
+
+impl VulkanServer {
+    /// Create a new Vulkan server.
+    pub(crate) fn new(
+        context: Arc<VkContext>,
+        memory_management: MemoryManagement<VulkanStorage>,
+        compute: TimelineQueue,
+        transfer: TimelineQueue,
+        tasks_max: usize,
+    ) -> Self {
+        let logger = DebugLogger::default();
+        let mut timestamps = KernelTimestamps::Disabled;
+        if logger.profile_level().is_some() {
+            timestamps.enable();
+        }
+
+        Self {
+            context,
+            memory_management,
+            compute,
+            transfer,
+            recording: None,
+            recorded_storage: HashSet::new(),
+            submitted_storage: HashMap::new(),
+            staging: Vec::new(),
+            pipelines: HashMap::new(),
+            tasks_count: 0,
+            tasks_max,
+            logger,
+            timestamps,
+        }
+    }
+
+    /// The resource of a binding, registering its storage as used by the recorded kernels.
+    fn record_resource(&mut self, binding: server::Binding) -> VulkanResource {
+        let storage = self.memory_management.get(binding.memory.clone());
+        self.recorded_storage.insert(storage.id);
+        self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        )
+    }
+
+    /// The compute submission a transfer on the given binding has to wait for.
+    fn compute_dependency(&mut self, binding: &server::Binding) -> Vec<(vk::Semaphore, u64)> {
+        let storage = self.memory_management.get(binding.memory.clone());
+        if self.recorded_storage.contains(&storage.id) {
+            self.flush_compute();
+        }
+
+        match self.submitted_storage.get(&storage.id) {
+            Some(value) if *value > self.compute.completed() => {
+                vec![(self.compute.semaphore, *value)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Start a command buffer on the transfer queue, ordered after the previous transfers.
+    fn begin_transfer(&mut self) -> vk::CommandBuffer {
+        let command_buffer = self.transfer.begin();
+        let barrier = vk::MemoryBarrier::default()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ | vk::AccessFlags::TRANSFER_WRITE);
+        unsafe {
+            self.context.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+        }
+        command_buffer
+    }
+
+    fn read_sync(&mut self, binding: server::Binding) -> Vec<u8> {
+        let waits = self.compute_dependency(&binding);
+        let resource = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        let size = resource.size();
+        if size == 0 {
+            return Vec::new();
+        }
+
+        let (staging, memory) = self.context.create_buffer(
+            size,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            &[
+                HOST_MEMORY | vk::MemoryPropertyFlags::HOST_CACHED,
+                HOST_MEMORY,
+            ],
+        );
+        let command_buffer = self.begin_transfer();
+        let region = vk::BufferCopy::default()
+            .src_offset(resource.offset())
+            .dst_offset(0)
+            .size(size);
+
+        let device = &self.context.device;
+        unsafe {
+            device.cmd_copy_buffer(command_buffer, resource.buffer, staging, &[region]);
+            let value = self.transfer.submit(command_buffer, &waits);
+            self.transfer.wait(value);
+
+            let data = device
+                .map_memory(memory, 0, size, vk::MemoryMapFlags::empty())
+                .unwrap();
+            let result = std::slice::from_raw_parts(data as *const u8, size as usize).to_vec();
+            device.unmap_memory(memory);
+            self.context.destroy_buffer(staging, memory);
+
+            result
+        }
+    }
+
+    fn pipeline(
+        &mut self,
+        kernel: <Self as ComputeServer>::Kernel,
+        mode: ExecutionMode,
+    ) -> KernelId {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        // The size of the dynamic shared memory is baked into the shader, so each size is
+        // compiled into its own pipeline variant.
+        let dynamic_shared_memory = kernel.dynamic_shared_memory();
+        if dynamic_shared_memory > 0 {
+            kernel_id.dynamic_shared_memory(dynamic_shared_memory);
+        }
+
+        if self.pipelines.contains_key(&kernel_id) {
+            return kernel_id;
+        }
+
+        log::debug!("Compiling {}", kernel.name());
+        let mut compiled = kernel.compile(&CompilationOptions::default(), mode);
+        if self.logger.is_activated() {
+            compiled.debug_info = Some(DebugInformation::new(
+                "spv",
+                kernel_id.clone(),
+                kernel.cost(),
+            ));
+        }
+        let compiled = self.logger.debug(compiled);
+        let repr = compiled
+            .repr
+            .expect("Need compiled repr to assemble to spirv");
+        let spirv = repr.assemble();
+
+        let device = &self.context.device;
+        let bindings = (0..repr.num_bindings as u32)
+            .map(|binding| {
+                vk::DescriptorSetLayoutBinding::default()
+                    .binding(binding)
+                    .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                    .descriptor_count(1)
+                    .stage_flags(vk::ShaderStageFlags::COMPUTE)
+            })
+            .collect::<Vec<_>>();
+
+        let pipeline = unsafe {
+            let info = vk::ShaderModuleCreateInfo::default().code(&spirv);
+            let module = device.create_shader_module(&info, None).unwrap();
+            // Bindings are pushed when recording each dispatch instead of allocating descriptor sets.
+            let info = vk::DescriptorSetLayoutCreateInfo::default()
+                .flags(vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR)
+                .bindings(&bindings);
+            let descriptor_layout = device.create_descriptor_set_layout(&info, None).unwrap();
+            let descriptor_layouts = [descriptor_layout];
+            let info = vk::PipelineLayoutCreateInfo::default().set_layouts(&descriptor_layouts);
+            let layout = device.create_pipeline_layout(&info, None).unwrap();
+            let stage = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::COMPUTE)
+                .module(module)
+                .name(c"main");
+            let info = vk::ComputePipelineCreateInfo::default()
+                .stage(stage)
+                .layout(layout);
+            let pipeline = device
+                .create_compute_pipelines(vk::PipelineCache::null(), &[info], None)
+                .map_err(|(_, err)| err)
+                .unwrap()[0];
+
+            Pipeline {
+                module,
+                descriptor_layout,
+                layout,
+                pipeline,
+            }
+        };
+
+        self.pipelines.insert(kernel_id.clone(), pipeline);
+        kernel_id
+    }
+
+    fn execute_task(
+        &mut self,
+        kernel_id: &KernelId,
+        count: CubeCount,
+        bindings: Vec<server::Binding>,
+    ) {
+        let resources = bindings
+            .into_iter()
+            .map(|binding| self.record_resource(binding))
+            .collect::<Vec<_>>();
+        let count = match count {
+            CubeCount::Static(x, y, z) => CubeCount::Static(x, y, z),
+            CubeCount::Dynamic(binding) => {
+                self.record_resource(binding.clone());
+                CubeCount::Dynamic(binding)
+            }
+        };
+
+        let command_buffer = match self.recording {
+            Some(command_buffer) => command_buffer,
+            None => *self.recording.insert(self.compute.begin()),
+        };
+        let pipeline = self.pipelines.get(kernel_id).unwrap();
+        let buffer_infos = resources
+            .iter()
+            .map(|resource| [resource.descriptor()])
+            .collect::<Vec<_>>();
+        let writes = buffer_infos
+            .iter()
+            .enumerate()
+            .map(|(binding, info)| {
+                vk::WriteDescriptorSet::default()
+                    .dst_binding(binding as u32)
+                    .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                    .buffer_info(info)
+            })
+            .collect::<Vec<_>>();
+
+        let device = &self.context.device;
+        unsafe {
+            device.cmd_bind_pipeline(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                pipeline.pipeline,
+            );
+            self.context.push_descriptor.cmd_push_descriptor_set(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                pipeline.layout,
+                0,
+                &writes,
+            );
+
+            match count {
+                CubeCount::Static(x, y, z) => device.cmd_dispatch(command_buffer, x, y, z),
+                // The cube count is read by the device, there's no need to read it back.
+                CubeCount::Dynamic(binding) => {
+                    let resource = self.memory_management.get_resource(
+                        binding.memory,
+                        binding.offset_start,
+                        binding.offset_end,
+                    );
+                    device.cmd_dispatch_indirect(command_buffer, resource.buffer, resource.offset())
+                }
+            }
+
+            // Kernels are ordered like on the other runtimes, each one sees the writes of the
+            // previous ones.
+            let barrier = vk::MemoryBarrier::default()
+                .src_access_mask(vk::AccessFlags::SHADER_WRITE)
+                .dst_access_mask(
+                    vk::AccessFlags::SHADER_READ
+                        | vk::AccessFlags::SHADER_WRITE
+                        | vk::AccessFlags::INDIRECT_COMMAND_READ,
+                );
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::PipelineStageFlags::COMPUTE_SHADER | vk::PipelineStageFlags::DRAW_INDIRECT,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+        }
+
+        self.tasks_count += 1;
+        if self.tasks_count >= self.tasks_max {
+            self.flush_compute();
+        }
+    }
+
+    /// Submit the recorded kernels, after the uploads they might read.
+    fn flush_compute(&mut self) {
+        let Some(command_buffer) = self.recording.take() else {
+            return;
+        };
+
+        let waits = [(self.transfer.semaphore, self.transfer.submitted)];
+        let value = self.compute.submit(command_buffer, &waits);
+        for storage in self.recorded_storage.drain() {
+            self.submitted_storage.insert(storage, value);
+        }
+        self.tasks_count = 0;
+    }
+
+    fn sync_queues(&mut self) {
+        self.flush_compute();
+        self.compute.wait(self.compute.submitted);
+        self.transfer.wait(self.transfer.submitted);
+
+        self.submitted_storage.clear();
+        for (_, buffer, memory) in self.staging.drain(..) {
+            unsafe {
+                self.context.destroy_buffer(buffer, memory);
+            }
+        }
+        self.memory_management.cleanup();
+        self.memory_management.storage().perform_deallocations();
+    }
+
+    fn free_staging(&mut self) {
+        let completed = self.transfer.completed();
+        let context = &self.context;
+        self.staging.retain(|(value, buffer, memory)| {
+            if *value > completed {
+                return true;
+            }
+            unsafe {
+                context.destroy_buffer(*buffer, *memory);
+            }
+            false
+        });
+    }
+}
+
+impl ComputeServer for VulkanServer {
+    type Kernel = Box<dyn CubeTask<VkSpirvCompiler>>;
+    type Storage = VulkanStorage;
+    type Feature = Feature;
+
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.id().name()
+    }
+
+    fn read(&mut self, binding: server::Binding) -> impl Future<Output = Vec<u8>> + 'static {
+        let value = self.read_sync(binding);
+        async { value }
+    }
+
+    fn create(&mut self, data: &[u8]) -> server::Handle {
+        let handle = self.empty(data.len());
+        self.write(handle.clone().binding(), data);
+        handle
+    }
+
+    fn binding_size(&mut self, binding: &server::Binding) -> u64 {
+        let storage = self.memory_management.get(binding.memory.clone());
+        storage.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
+
+    /// Writes are uploads on the transfer queue, waiting on the kernels using the resource.
+    fn write(&mut self, binding: server::Binding, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        self.free_staging();
+
+        let waits = self.compute_dependency(&binding);
+        let resource = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+
+        let size = data.len() as u64;
+        let (staging, memory) =
+            self.context
+                .create_buffer(size, vk::BufferUsageFlags::TRANSFER_SRC, &[HOST_MEMORY]);
+        let command_buffer = self.begin_transfer();
+        let region = vk::BufferCopy::default()
+            .src_offset(0)
+            .dst_offset(resource.offset())
+            .size(size);
+
+        let device = &self.context.device;
+        unsafe {
+            let mapped = device
+                .map_memory(memory, 0, size, vk::MemoryMapFlags::empty())
+                .unwrap();
+            std::ptr::copy_nonoverlapping(data.as_ptr(), mapped as *mut u8, data.len());
+            device.unmap_memory(memory);
+
+            device.cmd_copy_buffer(command_buffer, staging, resource.buffer, &[region]);
+        }
+        let value = self.transfer.submit(command_buffer, &waits);
+        self.staging.push((value, staging, memory));
+    }
+
+    /// Uploads are always done on the transfer queue, the fence is the value its timeline
+    /// semaphore reaches once the copy is completed.
+    fn create_async(&mut self, data: &[u8]) -> (server::Handle, server::Fence) {
+        let handle = self.create(data);
+        (handle, server::Fence::new(self.transfer.submitted))
+    }
+
+    fn wait_fence(&mut self, fence: server::Fence) -> impl Future<Output = ()> + 'static {
+        self.transfer.wait(fence.id);
+        self.free_staging();
+        async {}
+    }
+
+    fn empty(&mut self, size: usize) -> server::Handle {
+        let handle = self.memory_management.reserve(size as u64, None);
+        server::Handle::new(handle, None, None)
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle, OutOfMemoryError> {
+        let handle = self.memory_management.try_reserve(size as u64, None)?;
+        Ok(server::Handle::new(handle, None, None))
+    }
+
+    unsafe fn execute(
+        &mut self,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Vec<server::Binding>,
+        mode: ExecutionMode,
+        _stream: server::Stream,
+    ) {
+        let profile_level = self.logger.profile_level();
+        let profile_info = if profile_level.is_some() {
+            Some((kernel.name(), kernel.id()))
+        } else {
+            None
+        };
+        let count_info = format!("{count:?}");
+
+        let kernel_id = self.pipeline(kernel, mode);
+
+        if let Some(level) = profile_level {
+            self.sync_queues();
+            let start = std::time::SystemTime::now();
+            self.execute_task(&kernel_id, count, bindings);
+            self.sync_queues();
+
+            let (name, kernel_id) = profile_info.unwrap();
+            let info = match level {
+                ProfileLevel::Basic | ProfileLevel::Medium => kernel_id.name(),
+                ProfileLevel::Full => {
+                    format!("{name}: {kernel_id} CubeCount {count_info}")
+                }
+            };
+
+            self.logger
+                .register_profiled(info, start.elapsed().unwrap());
+        } else {
+            self.execute_task(&kernel_id, count, bindings);
+        }
+    }
+
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let kernel_id = kernel.id();
+        let evicted: Vec<_> = self
+            .pipelines
+            .keys()
+            .filter(|id| id.is_variant_of(&kernel_id))
+            .cloned()
+            .collect();
+        if evicted.is_empty() {
+            return;
+        }
+
+        // The commands in flight may still use the pipelines.
+        self.sync_queues();
+        for id in evicted {
+            let pipeline = self.pipelines.remove(&id).unwrap();
+            pipeline.destroy(&self.context.device);
+        }
+    }
+
+    fn flush(&mut self) {
+        self.flush_compute();
+    }
+
+    fn sync(&mut self) -> impl Future<Output = ()> + 'static {
+        self.logger.profile_summary();
+
+        self.sync_queues();
+        async move {}
+    }
+
+    fn sync_elapsed(&mut self) -> impl Future<Output = TimestampsResult> + 'static {
+        self.logger.profile_summary();
+
+        self.sync_queues();
+
+        let duration = match &mut self.timestamps {
+            KernelTimestamps::Inferred { start_time } => {
+                let duration = start_time.elapsed();
+                *start_time = Instant::now();
+                Ok(duration)
+            }
+            KernelTimestamps::Disabled => Err(TimestampsError::Disabled),
+        };
+
+        async move { duration }
+    }
+
+    fn get_resource(&mut self, binding: server::Binding) -> BindingResource<Self> {
+        BindingResource::new(
+            binding.clone(),
+            self.memory_management.get_resource(
+                binding.memory,
+                binding.offset_start,
+                binding.offset_end,
+            ),
+        )
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+
+    fn enable_timestamps(&mut self) {
+        self.timestamps.enable();
+    }
+
+    fn disable_timestamps(&mut self) {
+        if self.logger.profile_level().is_none() {
+            self.timestamps.disable();
+        }
+    }
+}
+
+impl Drop for VulkanServer {
+    fn drop(&mut self) {
+        self.sync_queues();
+
+        for (_, pipeline) in self.pipelines.drain() {
+            pipeline.destroy(&self.context.device);
+        }
+    }
+}
+
+impl Pipeline {
+    /// The commands using the pipeline have to be completed.
+    fn destroy(self, device: &ash::Device) {
+        unsafe {
+            device.destroy_pipeline(self.pipeline, None);
+            device.destroy_pipeline_layout(self.layout, None);
+            device.destroy_descriptor_set_layout(self.descriptor_layout, None);
+            device.destroy_shader_module(self.module, None);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use ash::vk;
+use cubecl_runtime::storage::{ComputeStorage, StorageHandle, StorageId, StorageUtilization};
+use std::{collections::HashMap, sync::Arc};
+
+use super::VkContext;
+
+/// Buffer storage for Vulkan, each allocation is a buffer with its own device memory.
+pub struct VulkanStorage {
+    context: Arc<VkContext>,
+    memory: HashMap<StorageId, (vk::Buffer, vk::DeviceMemory)>,
+    deallocations: Vec<StorageId>,
+}
+
+impl core::fmt::Debug for VulkanStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(format!("VulkanStorage {{ context: {:?} }}", self.context).as_str())
+    }
+}
+
+impl VulkanStorage {
+    /// Create a new storage on the given [context](VkContext).
+    pub fn new(context: Arc<VkContext>) -> Self {
+        Self {
+            context,
+            memory: HashMap::new(),
+            deallocations: Vec::new(),
+        }
+    }
+
+    /// Actually deallocates buffers tagged to be deallocated, the device must be idle.
+    pub fn perform_deallocations(&mut self) {
+        for id in self.deallocations.drain(..) {
+            if let Some((buffer, memory)) = self.memory.remove(&id) {
+                unsafe {
+                    self.context.destroy_buffer(buffer, memory);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for VulkanStorage {
+    fn drop(&mut self) {
+        for (_, (buffer, memory)) in self.memory.drain() {
+            unsafe {
+                self.context.destroy_buffer(buffer, memory);
+            }
+        }
+    }
+}
+
+/// The memory resource that can be allocated for Vulkan.
+#[derive(new, Debug)]
+pub struct VulkanResource {
+    /// The buffer of the allocation.
+    pub buffer: vk::Buffer,
+    offset: u64,
+    size: u64,
+}
+
+impl VulkanResource {
+    /// The descriptor binding the range of the resource.
+    pub fn descriptor(&self) -> vk::DescriptorBufferInfo {
+        vk::DescriptorBufferInfo::default()
+            .buffer(self.buffer)
+            .offset(self.offset)
+            .range(match self.size {
+                0 => vk::WHOLE_SIZE,
+                size => size,
+            })
+    }
+
+    /// Return the buffer size.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Return the buffer offset.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl ComputeStorage for VulkanStorage {
+    type Resource = VulkanResource;
+    // Bindings are also aligned to `minStorageBufferOffsetAlignment` when creating the client.
+    const ALIGNMENT: u64 = 32;
+
+    fn get(&mut self, handle: &StorageHandle) -> Self::Resource {
+        let (buffer, _) = self.memory.get(&handle.id).unwrap();
+
+        VulkanResource::new(*buffer, handle.offset(), handle.size())
+    }
+
+    fn alloc(&mut self, size: u64) -> StorageHandle {
+        let id = StorageId::new();
+        let usage = vk::BufferUsageFlags::STORAGE_BUFFER
+            | vk::BufferUsageFlags::TRANSFER_SRC
+            | vk::BufferUsageFlags::TRANSFER_DST
+            | vk::BufferUsageFlags::INDIRECT_BUFFER;
+        let buffer = self.context.create_buffer(
+            size,
+            usage,
+            &[
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                vk::MemoryPropertyFlags::empty(),
+            ],
+        );
+        self.memory.insert(id, buffer);
+        StorageHandle::new(id, StorageUtilization { offset: 0, size })
+    }
+
+    fn dealloc(&mut self, id: StorageId) {
+        self.deallocations.push(id);
+    }
+}
@@ -0,0 +1,11 @@
+/// A Vulkan physical device, in the order they are enumerated by the instance.
+#[derive(new, Clone, PartialEq, Eq, Default, Hash)]
+pub struct VulkanDevice {
+    pub index: usize,
+}
+
+impl core::fmt::Debug for VulkanDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vulkan({})", self.index)
+    }
+}
@@ -0,0 +1,370 @@
+use std::{ffi::CStr, sync::Arc};
+
+use ash::{
+    khr::{cooperative_matrix, push_descriptor},
+    vk,
+};
+use cubecl_core::{
+    ir::{Elem, FloatKind, IntKind},
+    Feature, MemoryConfiguration, Runtime,
+};
+use cubecl_runtime::{
+    channel::MutexComputeChannel,
+    client::ComputeClient,
+    memory_management::{MemoryDeviceProperties, MemoryManagement},
+    storage::ComputeStorage,
+    ComputeRuntime, DeviceProperties, HardwareProperties,
+};
+
+use crate::{
+    compute::{TimelineQueue, VkContext, VkSpirvCompiler, VulkanServer, VulkanStorage},
+    device::VulkanDevice,
+};
+
+/// The values that control how a Vulkan Runtime will perform its calculations.
+pub struct RuntimeOptions {
+    /// Control the amount of kernels to be recorded into a single command buffer.
+    pub tasks_max: usize,
+    /// Configures the memory management.
+    pub memory_config: MemoryConfiguration,
+}
+
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            tasks_max: 16,
+            memory_config: MemoryConfiguration::default(),
+        }
+    }
+}
+
+/// Runtime that uses Vulkan directly with the SPIR-V compiler, without the validation and
+/// tracking done by wgpu.
+#[derive(Debug)]
+pub struct VulkanRuntime;
+
+type Server = VulkanServer;
+type Channel = MutexComputeChannel<Server>;
+
+static RUNTIME: ComputeRuntime<VulkanDevice, Server, Channel> = ComputeRuntime::new();
+
+/// Initialize a client on the given device with the given options.
+pub fn init(device: &VulkanDevice, options: RuntimeOptions) {
+    let client = create_client(device, options);
+    RUNTIME.register(device, client)
+}
+
+fn create_client(device: &VulkanDevice, options: RuntimeOptions) -> ComputeClient<Server, Channel> {
+    let entry = unsafe { ash::Entry::load().expect("Failed to load the Vulkan library") };
+    // The SPIR-V compiler targets SPIR-V 1.6, which requires Vulkan 1.3.
+    let app_info = vk::ApplicationInfo::default()
+        .application_name(c"CubeCL")
+        .api_version(vk::API_VERSION_1_3);
+    let instance_info = vk::InstanceCreateInfo::default().application_info(&app_info);
+    let instance = unsafe { entry.create_instance(&instance_info, None).unwrap() };
+
+    let physical_devices = unsafe { instance.enumerate_physical_devices().unwrap() };
+    let physical_device = *physical_devices.get(device.index).unwrap_or_else(|| {
+        panic!(
+            "No Vulkan device at index {}, only {} devices were found",
+            device.index,
+            physical_devices.len()
+        )
+    });
+
+    let properties = unsafe { instance.get_physical_device_properties(physical_device) };
+    assert!(
+        properties.api_version >= vk::API_VERSION_1_3,
+        "The Vulkan device {device:?} doesn't support Vulkan 1.3"
+    );
+    log::info!(
+        "Created Vulkan compute server on device {:?} => {:?}",
+        device,
+        properties.device_name_as_c_str().unwrap_or_default()
+    );
+
+    let extensions = unsafe {
+        instance
+            .enumerate_device_extension_properties(physical_device)
+            .unwrap()
+    };
+    let supports = |name: &CStr| {
+        extensions
+            .iter()
+            .any(|extension| extension.extension_name_as_c_str() == Ok(name))
+    };
+    assert!(
+        supports(push_descriptor::NAME),
+        "The Vulkan device {device:?} doesn't support push descriptors"
+    );
+    let has_cmma = supports(cooperative_matrix::NAME);
+
+    let (compute_family, transfer_family) = queue_families(&instance, physical_device);
+    let queue_priorities = [1.0, 1.0];
+    let mut queue_infos = vec![vk::DeviceQueueCreateInfo::default()
+        .queue_family_index(compute_family.0)
+        .queue_priorities(&queue_priorities[..compute_family.1 as usize])];
+    if transfer_family.0 != compute_family.0 {
+        queue_infos.push(
+            vk::DeviceQueueCreateInfo::default()
+                .queue_family_index(transfer_family.0)
+                .queue_priorities(&queue_priorities[..1]),
+        );
+    }
+
+    // Every supported feature is enabled, the compiler only uses the ones it was told about.
+    let mut features_11 = vk::PhysicalDeviceVulkan11Features::default();
+    let mut features_12 = vk::PhysicalDeviceVulkan12Features::default();
+    let mut features_13 = vk::PhysicalDeviceVulkan13Features::default();
+    let mut features_cmma = vk::PhysicalDeviceCooperativeMatrixFeaturesKHR::default();
+    let mut features = vk::PhysicalDeviceFeatures2::default()
+        .push_next(&mut features_11)
+        .push_next(&mut features_12)
+        .push_next(&mut features_13);
+    if has_cmma {
+        features = features.push_next(&mut features_cmma);
+    }
+    unsafe { instance.get_physical_device_features2(physical_device, &mut features) };
+    let device_features = features.features;
+
+    let mut extension_names = vec![push_descriptor::NAME.as_ptr()];
+    if has_cmma {
+        extension_names.push(cooperative_matrix::NAME.as_ptr());
+    }
+    let device_info = vk::DeviceCreateInfo::default()
+        .queue_create_infos(&queue_infos)
+        .enabled_extension_names(&extension_names)
+        .push_next(&mut features);
+    let device_vk = unsafe {
+        instance
+            .create_device(physical_device, &device_info, None)
+            .expect("Failed to create Vulkan device")
+    };
+    assert!(
+        features_12.timeline_semaphore == vk::TRUE,
+        "The Vulkan device {device:?} doesn't support timeline semaphores"
+    );
+
+    let mut queue_families = vec![compute_family.0];
+    if transfer_family.0 != compute_family.0 {
+        queue_families.push(transfer_family.0);
+    }
+    let context = Arc::new(VkContext {
+        push_descriptor: push_descriptor::Device::new(&instance, &device_vk),
+        memory_properties: unsafe {
+            instance.get_physical_device_memory_properties(physical_device)
+        },
+        entry,
+        instance,
+        physical_device,
+        device: device_vk,
+        queue_families,
+    });
+    let compute = TimelineQueue::new(context.clone(), compute_family.0, 0);
+    let transfer = TimelineQueue::new(context.clone(), transfer_family.0, transfer_family.1);
+
+    let mut push_descriptor_properties = vk::PhysicalDevicePushDescriptorPropertiesKHR::default();
+    let mut subgroup_properties = vk::PhysicalDeviceSubgroupProperties::default();
+    let mut properties2 = vk::PhysicalDeviceProperties2::default()
+        .push_next(&mut push_descriptor_properties)
+        .push_next(&mut subgroup_properties);
+    unsafe {
+        context
+            .instance
+            .get_physical_device_properties2(physical_device, &mut properties2)
+    };
+
+    let limits = properties.limits;
+    let hardware_props = HardwareProperties {
+        max_shared_memory_size: limits.max_compute_shared_memory_size as usize,
+        max_cube_dim: (
+            limits.max_compute_work_group_size[0],
+            limits.max_compute_work_group_size[1],
+            limits.max_compute_work_group_size[2],
+        ),
+        max_units_per_cube: limits.max_compute_work_group_invocations,
+        max_cube_count: (
+            limits.max_compute_work_group_count[0],
+            limits.max_compute_work_group_count[1],
+            limits.max_compute_work_group_count[2],
+        ),
+        max_bindings: push_descriptor_properties.max_push_descriptors,
+        max_buffer_size: limits.max_storage_buffer_range as u64,
+        subcube_size: Some(subgroup_properties.subgroup_size),
+        num_multiprocessors: None,
+        max_units_per_multiprocessor: None,
+        max_cubes_per_multiprocessor: None,
+        registers_per_multiprocessor: None,
+    };
+    let mem_properties = MemoryDeviceProperties {
+        max_page_size: limits.max_storage_buffer_range as u64,
+        alignment: VulkanStorage::ALIGNMENT.max(limits.min_storage_buffer_offset_alignment),
+    };
+
+    let storage = VulkanStorage::new(context.clone());
+    let memory_management = MemoryManagement::from_configuration(
+        storage,
+        mem_properties.clone(),
+        options.memory_config,
+    );
+    let server = VulkanServer::new(
+        context.clone(),
+        memory_management,
+        compute,
+        transfer,
+        options.tasks_max,
+    );
+
+    let mut device_props = DeviceProperties::new(&[], mem_properties, hardware_props);
+    let subgroup_operations = vk::SubgroupFeatureFlags::BASIC
+        | vk::SubgroupFeatureFlags::VOTE
+        | vk::SubgroupFeatureFlags::ARITHMETIC
+        | vk::SubgroupFeatureFlags::BALLOT
+        | vk::SubgroupFeatureFlags::SHUFFLE;
+    if subgroup_properties
+        .supported_stages
+        .contains(vk::ShaderStageFlags::COMPUTE)
+        && subgroup_properties
+            .supported_operations
+            .contains(subgroup_operations)
+    {
+        device_props.register_feature(Feature::Subcube);
+    }
+    register_types(
+        &mut device_props,
+        &device_features,
+        &features_11,
+        &features_12,
+    );
+    if has_cmma && features_cmma.cooperative_matrix == vk::TRUE {
+        register_cmma(&mut device_props, &context);
+    }
+
+    ComputeClient::new(MutexComputeChannel::new(server), device_props)
+}
+
+/// The family and number of queues of the compute queue family, and the family and index of the
+/// transfer queue, preferring a family dedicated to transfers.
+fn queue_families(
+    instance: &ash::Instance,
+    physical_device: vk::PhysicalDevice,
+) -> ((u32, u32), (u32, u32)) {
+    let families = unsafe { instance.get_physical_device_queue_family_properties(physical_device) };
+    let compute = families
+        .iter()
+        .position(|family| family.queue_flags.contains(vk::QueueFlags::COMPUTE))
+        .expect("No Vulkan queue supports compute") as u32;
+    let compute_count = families[compute as usize].queue_count.min(2);
+
+    let dedicated = families.iter().position(|family| {
+        family.queue_flags.contains(vk::QueueFlags::TRANSFER)
+            && !family
+                .queue_flags
+                .intersects(vk::QueueFlags::COMPUTE | vk::QueueFlags::GRAPHICS)
+    });
+    let transfer = match dedicated {
+        Some(family) => (family as u32, 0),
+        // Compute queues also support transfers, use a second one if there is one.
+        None => (compute, compute_count - 1),
+    };
+
+    ((compute, compute_count), transfer)
+}
+
+fn register_types(
+    props: &mut DeviceProperties<Feature>,
+    features: &vk::PhysicalDeviceFeatures,
+    features_11: &vk::PhysicalDeviceVulkan11Features,
+    features_12: &vk::PhysicalDeviceVulkan12Features,
+) {
+    let mut supported_types = vec![
+        Elem::UInt,
+        Elem::Int(IntKind::I32),
+        Elem::AtomicInt(IntKind::I32),
+        Elem::AtomicUInt,
+        Elem::Float(FloatKind::F32),
+        Elem::Bool,
+    ];
+    if features_12.shader_float16 == vk::TRUE && features_11.storage_buffer16_bit_access == vk::TRUE
+    {
+        supported_types.push(Elem::Float(FloatKind::F16));
+    }
+    if features.shader_int64 == vk::TRUE {
+        supported_types.push(Elem::Int(IntKind::I64));
+        if features_12.shader_buffer_int64_atomics == vk::TRUE {
+            supported_types.push(Elem::AtomicInt(IntKind::I64));
+        }
+    }
+    if features.shader_float64 == vk::TRUE {
+        supported_types.push(Elem::Float(FloatKind::F64));
+    }
+
+    for ty in supported_types {
+        props.register_feature(Feature::Type(ty));
+    }
+    // Vectors are at most 4 elements wide, so only 2x2 matrices are supported.
+    props.register_feature(Feature::Matrix(2));
+}
+
+fn register_cmma(props: &mut DeviceProperties<Feature>, context: &VkContext) {
+    let cmma = cooperative_matrix::Instance::new(&context.entry, &context.instance);
+    let properties = unsafe {
+        cmma.get_physical_device_cooperative_matrix_properties(context.physical_device)
+            .unwrap()
+    };
+    let sizes = properties
+        .into_iter()
+        .filter(|it| {
+            it.saturating_accumulation == 0
+                && it.result_type == it.c_type
+                && it.scope == vk::ScopeKHR::SUBGROUP
+        })
+        .filter_map(|it| {
+            Some(Feature::Cmma {
+                a: conv_type(it.a_type)?,
+                b: conv_type(it.b_type)?,
+                c: conv_type(it.c_type)?,
+                m: it.m_size as u8,
+                k: it.k_size as u8,
+                n: it.n_size as u8,
+            })
+        });
+    for size in sizes {
+        props.register_feature(size);
+    }
+}
+
+fn conv_type(vk_ty: vk::ComponentTypeKHR) -> Option<Elem> {
+    let ty = match vk_ty {
+        vk::ComponentTypeKHR::FLOAT16 => Elem::Float(FloatKind::F16),
+        vk::ComponentTypeKHR::FLOAT32 => Elem::Float(FloatKind::F32),
+        vk::ComponentTypeKHR::FLOAT64 => Elem::Float(FloatKind::F64),
+        vk::ComponentTypeKHR::SINT32 => Elem::Int(IntKind::I32),
+        vk::ComponentTypeKHR::SINT64 => Elem::Int(IntKind::I64),
+        vk::ComponentTypeKHR::UINT32 => Elem::UInt,
+        _ => None?,
+    };
+    Some(ty)
+}
+
+impl Runtime for VulkanRuntime {
+    type Compiler = VkSpirvCompiler;
+    type Server = VulkanServer;
+
+    type Channel = MutexComputeChannel<VulkanServer>;
+    type Device = VulkanDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        RUNTIME.client(device, move || {
+            create_client(device, RuntimeOptions::default())
+        })
+    }
+
+    fn name() -> &'static str {
+        "vulkan"
+    }
+
+    fn supported_line_sizes() -> &'static [u8] {
+        &[4, 2]
+    }
+}
@@ -9,16 +9,27 @@ use wgpu::{Adapter, ComputePipeline, Device, Queue};
 use crate::WgpuServer;
 
 pub trait WgpuCompiler: Compiler {
+    /// The extension of the files [overriding](cubecl_runtime::overrides) the generated sources,
+    /// `None` when the pipelines aren't created from the source.
+    const SOURCE_EXTENSION: Option<&'static str>;
+
+    /// Compile the kernel for the device. Kernels are compiled concurrently when
+    /// [prepared](ComputeServer::prepare).
     fn compile(
-        server: &mut WgpuServer<Self>,
+        device: &Device,
         kernel: <WgpuServer<Self> as ComputeServer>::Kernel,
         mode: ExecutionMode,
     ) -> CompiledKernel<Self>;
 
+    /// Create the pipeline of the kernel, zero-initializing its workgroup memory before each
+    /// dispatch when requested. The label names the shader and the pipeline in wgpu errors and
+    /// graphics debuggers.
     fn create_pipeline(
-        server: &mut WgpuServer<Self>,
+        device: &Device,
         kernel: CompiledKernel<Self>,
         mode: ExecutionMode,
+        zero_initialize_workgroup_memory: bool,
+        label: &str,
     ) -> Arc<ComputePipeline>;
 
     #[allow(async_fn_in_trait)]
@@ -16,7 +16,7 @@ use cubecl_core::{
     ir::{Elem, FloatKind, IntKind},
     prelude::CompiledKernel,
     server::ComputeServer,
-    ExecutionMode, Feature, Runtime,
+    CompilationOptions, ExecutionMode, Feature, Runtime,
 };
 use cubecl_runtime::{ComputeRuntime, DeviceProperties};
 use wgpu::{
@@ -42,10 +42,15 @@ static RUNTIME: ComputeRuntime<WgpuDevice, Server, MutexComputeChannel<Server>>
     ComputeRuntime::new();
 
 impl WgpuCompiler for SpirvCompiler<GLCompute> {
+    // The pipelines are created from the assembled representation.
+    const SOURCE_EXTENSION: Option<&'static str> = None;
+
     fn create_pipeline(
-        server: &mut WgpuServer<Self>,
+        device: &wgpu::Device,
         kernel: CompiledKernel<Self>,
         _mode: ExecutionMode,
+        zero_initialize_workgroup_memory: bool,
+        label: &str,
     ) -> Arc<ComputePipeline> {
         let repr = kernel
             .repr
@@ -65,60 +70,52 @@ impl WgpuCompiler for SpirvCompiler<GLCompute> {
                 count: None,
             })
             .collect::<Vec<_>>();
-        let layout = server
-            .device
-            .create_bind_group_layout(&BindGroupLayoutDescriptor {
-                label: None,
-                entries: &bindings,
-            });
-        let layout = server
-            .device
-            .create_pipeline_layout(&PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &[&layout],
-                push_constant_ranges: &[],
-            });
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries: &bindings,
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
 
         let module = unsafe {
-            server
-                .device
-                .create_shader_module_spirv(&ShaderModuleDescriptorSpirV {
-                    label: None,
-                    source: Cow::Borrowed(&spirv),
-                })
+            device.create_shader_module_spirv(&ShaderModuleDescriptorSpirV {
+                label: Some(label),
+                source: Cow::Borrowed(&spirv),
+            })
         };
 
         Arc::new(
-            server
-                .device
-                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                    label: None,
-                    layout: Some(&layout),
-                    module: &module,
-                    entry_point: "main",
-                    compilation_options: wgpu::PipelineCompilationOptions {
-                        zero_initialize_workgroup_memory: false,
-                        ..Default::default()
-                    },
-                    cache: None,
-                }),
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                module: &module,
+                entry_point: "main",
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    zero_initialize_workgroup_memory,
+                    ..Default::default()
+                },
+                cache: None,
+            }),
         )
     }
 
     fn compile(
-        server: &mut WgpuServer<Self>,
+        device: &wgpu::Device,
         kernel: <WgpuServer<Self> as ComputeServer>::Kernel,
         mode: ExecutionMode,
     ) -> CompiledKernel<Self> {
         // `wgpu` currently always enables `robustness2` on Vulkan if available, so default to
         // unchecked execution if robustness is enabled and let Vulkan handle it
-        let mode = if is_robust(&server.device) {
+        let mode = if is_robust(device) {
             ExecutionMode::Unchecked
         } else {
             mode
         };
         log::debug!("Compiling {}", kernel.name());
-        let compiled = kernel.compile(mode);
+        let compiled = kernel.compile(&CompilationOptions::default(), mode);
         #[cfg(feature = "spirv-dump")]
         dump_spirv(&compiled, kernel.name(), kernel.id());
         compiled
@@ -295,6 +292,8 @@ fn register_types(props: &mut DeviceProperties<Feature>) {
     for ty in supported_types {
         props.register_feature(Feature::Type(ty));
     }
+    // Vectors are at most 4 elements wide, so only 2x2 matrices are supported.
+    props.register_feature(Feature::Matrix(2));
 }
 
 fn conv_type(vk_ty: ComponentTypeKHR) -> Option<Elem> {
@@ -332,9 +331,12 @@ impl Runtime for WgpuRuntime<VkSpirvCompiler> {
 
     fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
         RUNTIME.client(device, move || {
-            let (adapter, device_wgpu, queue) =
-                future::block_on(create_wgpu_setup::<Vulkan, VkSpirvCompiler>(device));
-            create_client(adapter, device_wgpu, queue, RuntimeOptions::default())
+            let options = RuntimeOptions::default();
+            let (adapter, device_wgpu, queue) = future::block_on(create_wgpu_setup::<
+                Vulkan,
+                VkSpirvCompiler,
+            >(device, &options));
+            create_client(adapter, device_wgpu, queue, options)
         })
     }
 
@@ -368,7 +370,7 @@ pub fn init_sync(device: &WgpuDevice, options: RuntimeOptions) {
 /// Like [`init_sync`], but async, necessary for wasm.
 pub async fn init_async(device: &WgpuDevice, options: RuntimeOptions) {
     let (adapter, device_wgpu, queue) =
-        create_wgpu_setup::<Vulkan, SpirvCompiler<GLCompute>>(device).await;
+        create_wgpu_setup::<Vulkan, SpirvCompiler<GLCompute>>(device, &options).await;
     let client = create_client(adapter, device_wgpu, queue, options);
     RUNTIME.register(device, client)
 }
@@ -1,5 +1,5 @@
 use cubecl_core::ir::{self as cube, ConstantScalarValue, FloatKind, IntKind};
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Variable {
@@ -74,6 +74,9 @@ pub enum Item {
     Vec3(Elem),
     Vec2(Elem),
     Scalar(Elem),
+    /// A line larger than 4 elements, stored as an array of `vec4` parts. The last part is padded
+    /// when the size isn't a multiple of 4, such as for the 9 elements of a 3x3 matrix.
+    Wide(Elem, usize),
 }
 
 #[derive(Debug, Clone)]
@@ -184,11 +187,11 @@ impl Variable {
         *self.item().elem()
     }
 
-    pub fn fmt_cast_to(&self, item: Item) -> String {
-        if self.item() != item {
-            format!("{item}({self})")
-        } else {
-            format!("{self}")
+    pub fn fmt_cast_to(&self, item: Item) -> Cast<&Self> {
+        Cast {
+            value: self,
+            from: self.item(),
+            to: item,
         }
     }
 }
@@ -200,23 +203,118 @@ impl Item {
             Item::Vec3(e) => e,
             Item::Vec2(e) => e,
             Item::Scalar(e) => e,
+            Item::Wide(e, _) => e,
         }
     }
 
+    pub fn size(&self) -> usize {
+        self.elem().size() * self.vectorization_factor()
+    }
+
     pub fn vectorization_factor(&self) -> usize {
         match self {
             Item::Vec4(_) => 4,
             Item::Vec3(_) => 3,
             Item::Vec2(_) => 2,
             Item::Scalar(_) => 1,
+            Item::Wide(_, vectorization) => *vectorization,
+        }
+    }
+
+    pub fn is_wide(&self) -> bool {
+        matches!(self, Item::Wide(..))
+    }
+
+    /// The number of `vec4` parts of a wide item, 0 for other items.
+    pub fn parts(&self) -> usize {
+        match self {
+            Item::Wide(_, vectorization) => vectorization.div_ceil(4),
+            _ => 0,
+        }
+    }
+
+    /// Whether the last `vec4` part of a wide item is padded. Arrays of padded items in storage
+    /// buffers hold their elements packed as scalars, to match the layout of the host data.
+    pub fn is_padded(&self) -> bool {
+        matches!(self, Item::Wide(_, vectorization) if vectorization % 4 != 0)
+    }
+
+    /// The item of the elements of an array in a storage buffer.
+    pub fn storage_item(&self) -> Item {
+        match self.is_padded() {
+            true => Item::Scalar(*self.elem()),
+            false => *self,
+        }
+    }
+
+    /// The item of the same shape with another element.
+    pub fn with_elem(&self, elem: Elem) -> Item {
+        match self {
+            Item::Vec4(_) => Item::Vec4(elem),
+            Item::Vec3(_) => Item::Vec3(elem),
+            Item::Vec2(_) => Item::Vec2(elem),
+            Item::Scalar(_) => Item::Scalar(elem),
+            Item::Wide(_, vectorization) => Item::Wide(elem, *vectorization),
+        }
+    }
+
+    pub fn fmt_cast_to<T: Display>(&self, item: Item, text: T) -> Cast<T> {
+        Cast {
+            value: text,
+            from: *self,
+            to: item,
+        }
+    }
+}
+
+/// A value cast to an item when it is of another item, formatted without an intermediate string.
+pub struct Cast<T> {
+    value: T,
+    from: Item,
+    to: Item,
+}
+
+impl<T: Display> Display for Cast<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.from != self.to {
+            true => write!(f, "{}({})", self.to, self.value),
+            false => self.value.fmt(f),
+        }
+    }
+}
+
+/// Formats with a closure, to compose expressions without an intermediate string.
+pub struct DisplayFn<F>(F);
+
+pub fn display_fn<F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result>(fmt: F) -> DisplayFn<F> {
+    DisplayFn(fmt)
+}
+
+impl<F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result> Display for DisplayFn<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// The left side of an assignment, declaring the local bindings.
+pub struct Left<'a, T>(&'a T);
+
+impl Display for Left<'_, Variable> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Variable::LocalBinding { id, .. } => write!(f, "let _{id}"),
+            var => var.fmt(f),
         }
     }
+}
 
-    pub fn fmt_cast_to(&self, item: Item, text: String) -> String {
-        if *self != item {
-            format!("{item}({text})")
-        } else {
-            text
+impl Display for Left<'_, IndexedVariable> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let var = &self.0.var;
+        match var {
+            Variable::GlobalScalar(_, _, _) => var.fmt_left().fmt(f),
+            var if matches!(var.item(), Item::Scalar(_)) => var.fmt_left().fmt(f),
+            _ => self.0.fmt(f),
         }
     }
 }
@@ -258,14 +356,58 @@ impl Display for Item {
             Item::Vec3(elem) => write!(f, "vec3<{elem}>"),
             Item::Vec2(elem) => write!(f, "vec2<{elem}>"),
             Item::Scalar(elem) => write!(f, "{elem}"),
+            Item::Wide(elem, _) => write!(f, "array<vec4<{elem}>, {}>", self.parts()),
         }
     }
 }
 
-fn format_number(num: f64) -> String {
-    let formatted = format!("{:.34}", num);
-    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
-    trimmed.to_string() + "f"
+/// A float literal without the trailing zeros of its decimals, e.g. `1.5f`.
+struct FloatLiteral(f64);
+
+impl Display for FloatLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut writer = TrimDecimals {
+            f,
+            decimals: false,
+            point: false,
+            zeros: 0,
+        };
+        write!(writer, "{:.34}", self.0)?;
+        f.write_char('f')
+    }
+}
+
+/// Writes a number while holding back the trailing zeros of its decimals, and the decimal point
+/// when only zeros follow it, which are dropped at the end.
+struct TrimDecimals<'a, 'b> {
+    f: &'a mut std::fmt::Formatter<'b>,
+    decimals: bool,
+    point: bool,
+    zeros: usize,
+}
+
+impl std::fmt::Write for TrimDecimals<'_, '_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for c in s.chars() {
+            match c {
+                '.' if !self.decimals => {
+                    self.decimals = true;
+                    self.point = true;
+                }
+                '0' if self.decimals => self.zeros += 1,
+                c => {
+                    if core::mem::take(&mut self.point) {
+                        self.f.write_char('.')?;
+                    }
+                    for _ in 0..core::mem::take(&mut self.zeros) {
+                        self.f.write_char('0')?;
+                    }
+                    self.f.write_char(c)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Display for Variable {
@@ -295,7 +437,7 @@ impl Display for Variable {
                 write!(f, "output_{number}_global")
             }
             Variable::GlobalScalar(number, _, elem) => {
-                write!(f, "scalars_{elem}[{number}]")
+                write!(f, "scalars.{elem}_{number}")
             }
             // We do the conversion in Rust and then render the number to avoid overflow or other
             // precision related problems.
@@ -311,7 +453,7 @@ impl Display for Variable {
                     FloatKind::BF16 => {
                         todo!("Unsupported")
                     }
-                    FloatKind::F32 | FloatKind::F64 => f.write_str(&format_number(*val)),
+                    FloatKind::F32 | FloatKind::F64 => FloatLiteral(*val).fmt(f),
                 },
                 ConstantScalarValue::UInt(val) => write!(f, "{}u", *val as u32),
                 ConstantScalarValue::Bool(val) => write!(f, "{}", val),
@@ -358,37 +500,28 @@ impl Display for IndexedVariable {
         match &self.var {
             Variable::GlobalScalar(_, _, _) => write!(f, "{var}"),
             var if matches!(item, Item::Scalar(_)) => write!(f, "{var}"),
+            var if item.is_wide() => write!(f, "{var}[{}][{}]", index / 4, index % 4),
             var => write!(f, "{var}[{index}]"),
         }
     }
 }
 
 impl Variable {
-    pub fn fmt_left(&self) -> String {
-        match self {
-            Variable::LocalBinding { id, .. } => {
-                format!("let _{id}")
-            }
-            var => format!("{}", var),
-        }
+    pub fn fmt_left(&self) -> Left<'_, Self> {
+        Left(self)
     }
 }
 
 impl IndexedVariable {
-    pub fn fmt_left(&self) -> String {
-        let item = self.var.item();
-        match &self.var {
-            Variable::GlobalScalar(_, _, _) => self.var.fmt_left(),
-            var if matches!(item, Item::Scalar(_)) => var.fmt_left(),
-            _ => format!("{self}"),
-        }
+    pub fn fmt_left(&self) -> Left<'_, Self> {
+        Left(self)
     }
 
-    pub fn fmt_cast(&self, item: Item) -> String {
-        if self.var.item() != item {
-            format!("{item}({self})")
-        } else {
-            format!("{self}")
+    pub fn fmt_cast(self, item: Item) -> Cast<Self> {
+        Cast {
+            from: self.var.item(),
+            value: self,
+            to: item,
         }
     }
 }
@@ -10,7 +10,7 @@ use cubecl_core::{
     ir::{self as cube, HybridAllocator},
     prelude::CompiledKernel,
     server::ComputeServer,
-    Feature,
+    CompilationOptions, Feature,
 };
 use cubecl_runtime::{DeviceProperties, ExecutionMode};
 use wgpu::{ComputePipeline, DeviceDescriptor, ShaderModuleDescriptor};
@@ -25,6 +25,8 @@ pub struct WgslCompiler {
     global_invocation_id: bool,
     workgroup_id: bool,
     subgroup_size: bool,
+    /// Whether the device supports subgroups, otherwise a subgroup is the whole workgroup.
+    subgroups: bool,
     rank: bool,
     id: bool,
     stride: bool,
@@ -36,6 +38,8 @@ pub struct WgslCompiler {
     shared_memories: Vec<SharedMemory>,
     const_arrays: Vec<ConstantArray>,
     local_arrays: Vec<LocalArray>,
+    dynamic_shared_memory: u32,
+    fast_math: bool,
 }
 
 impl core::fmt::Debug for WgslCompiler {
@@ -47,8 +51,15 @@ impl core::fmt::Debug for WgslCompiler {
 impl cubecl_core::Compiler for WgslCompiler {
     type Representation = ComputeShader;
 
-    fn compile(shader: cube::KernelDefinition, _mode: ExecutionMode) -> Self::Representation {
-        let mut compiler = Self::default();
+    fn compile(
+        shader: cube::KernelDefinition,
+        options: &CompilationOptions,
+        _mode: ExecutionMode,
+    ) -> Self::Representation {
+        let mut compiler = Self {
+            subgroups: options.subcube,
+            ..Self::default()
+        };
         compiler.compile_shader(shader)
     }
 
@@ -56,8 +67,16 @@ impl cubecl_core::Compiler for WgslCompiler {
         Self::compile_elem(elem).size()
     }
 
-    fn max_shared_memory_size() -> usize {
-        32768
+    fn packed_scalars() -> bool {
+        true
+    }
+
+    fn device_functions() -> bool {
+        true
+    }
+
+    fn matrix_ops(size: u32) -> bool {
+        matches!(size, 2..=4)
     }
 
     fn local_allocator() -> impl cube::LocalAllocator {
@@ -66,50 +85,54 @@ impl cubecl_core::Compiler for WgslCompiler {
 }
 
 impl WgpuCompiler for WgslCompiler {
+    const SOURCE_EXTENSION: Option<&'static str> = Some("wgsl");
+
     fn create_pipeline(
-        server: &mut WgpuServer<Self>,
+        device: &wgpu::Device,
         kernel: CompiledKernel<Self>,
         mode: ExecutionMode,
+        zero_initialize_workgroup_memory: bool,
+        label: &str,
     ) -> Arc<ComputePipeline> {
         let source = &kernel.source;
         let module = match mode {
-            ExecutionMode::Checked => server.device.create_shader_module(ShaderModuleDescriptor {
-                label: None,
+            ExecutionMode::Checked => device.create_shader_module(ShaderModuleDescriptor {
+                label: Some(label),
                 source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
             }),
             ExecutionMode::Unchecked => unsafe {
-                server
-                    .device
-                    .create_shader_module_unchecked(ShaderModuleDescriptor {
-                        label: None,
-                        source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
-                    })
+                device.create_shader_module_unchecked(ShaderModuleDescriptor {
+                    label: Some(label),
+                    source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
+                })
             },
         };
 
         Arc::new(
-            server
-                .device
-                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                    label: None,
-                    layout: None,
-                    module: &module,
-                    entry_point: "main",
-                    compilation_options: wgpu::PipelineCompilationOptions {
-                        zero_initialize_workgroup_memory: false,
-                        ..Default::default()
-                    },
-                    cache: None,
-                }),
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    zero_initialize_workgroup_memory,
+                    ..Default::default()
+                },
+                cache: None,
+            }),
         )
     }
 
     fn compile(
-        _server: &mut WgpuServer<Self>,
+        device: &wgpu::Device,
         kernel: <WgpuServer<Self> as ComputeServer>::Kernel,
         mode: ExecutionMode,
     ) -> CompiledKernel<Self> {
-        kernel.compile(mode)
+        // Subgroup operations are lowered to workgroup memory when the adapter lacks them.
+        let options = CompilationOptions {
+            subcube: device.features().contains(wgpu::Features::SUBGROUP),
+        };
+        kernel.compile(&options, mode)
     }
 
     async fn request_device(adapter: &wgpu::Adapter) -> (wgpu::Device, wgpu::Queue) {
@@ -162,15 +185,47 @@ fn register_types(props: &mut DeviceProperties<Feature>) {
     for ty in supported_types {
         props.register_feature(Feature::Type(ty));
     }
+    for size in 2..=4 {
+        props.register_feature(Feature::Matrix(size));
+    }
 }
 
 impl WgslCompiler {
     fn compile_shader(&mut self, mut value: cube::KernelDefinition) -> wgsl::ComputeShader {
         self.num_inputs = value.inputs.len();
         self.num_outputs = value.outputs.len();
+        self.dynamic_shared_memory = value.dynamic_shared_memory;
+        self.fast_math = value.fast_math == cube::FastMath::Relaxed;
 
+        let functions: Vec<_> = value
+            .functions
+            .into_iter()
+            .map(|function| self.compile_function(function))
+            .collect();
         let instructions = self.compile_scope(&mut value.body);
-        let extensions = register_extensions(&instructions);
+        let mut extensions = register_extensions(&instructions);
+        for function in functions.iter() {
+            for extension in register_extensions(&function.instructions) {
+                if !extensions.contains(&extension) {
+                    extensions.push(extension);
+                }
+            }
+        }
+
+        let mut named = Vec::with_capacity(value.named.len());
+        let mut scalars = Vec::new();
+
+        for (name, binding) in value.named {
+            match name.strip_prefix("scalars_") {
+                Some(elem) => scalars.push(wgsl::ScalarGroup {
+                    name: elem.to_string(),
+                    elem: Self::compile_elem(binding.item.elem),
+                    size: binding.size.unwrap_or(1),
+                }),
+                None => named.push((name, Self::compile_binding(binding))),
+            }
+        }
+
         let body = wgsl::Body {
             instructions,
             rank: true,
@@ -190,11 +245,8 @@ impl WgslCompiler {
                 .into_iter()
                 .map(Self::compile_binding)
                 .collect(),
-            named: value
-                .named
-                .into_iter()
-                .map(|(name, binding)| (name, Self::compile_binding(binding)))
-                .collect(),
+            named,
+            scalars,
             shared_memories: self.shared_memories.clone(),
             constant_arrays: self.const_arrays.clone(),
             local_arrays: self.local_arrays.clone(),
@@ -209,6 +261,7 @@ impl WgslCompiler {
             workgroup_id: self.workgroup_id || self.workgroup_id_no_axis,
             subgroup_size: self.subgroup_size,
             body,
+            functions,
             extensions,
             num_workgroups_no_axis: self.num_workgroup_no_axis,
             workgroup_id_no_axis: self.workgroup_id_no_axis,
@@ -216,6 +269,29 @@ impl WgslCompiler {
         }
     }
 
+    fn compile_function(&mut self, mut value: cube::FunctionDefinition) -> wgsl::Function {
+        let mut instructions = self.compile_scope(&mut value.body);
+        let num_declarations = instructions
+            .iter()
+            .take_while(|instruction| {
+                matches!(instruction, wgsl::Instruction::DeclareVariable { .. })
+            })
+            .count();
+        let declarations = instructions.drain(..num_declarations).collect();
+
+        wgsl::Function {
+            name: value.name,
+            inputs: value
+                .inputs
+                .into_iter()
+                .map(|input| self.compile_variable(input))
+                .collect(),
+            output: value.output.map(|output| self.compile_variable(output)),
+            declarations,
+            instructions,
+        }
+    }
+
     fn compile_item(item: cube::Item) -> Item {
         let elem = Self::compile_elem(item.elem);
         match item.vectorization.map(|it| it.get()).unwrap_or(1) {
@@ -223,6 +299,7 @@ impl WgslCompiler {
             2 => wgsl::Item::Vec2(elem),
             3 => wgsl::Item::Vec3(elem),
             4 => wgsl::Item::Vec4(elem),
+            8 | 9 | 16 => wgsl::Item::Wide(elem, item.vectorization.unwrap().get() as usize),
             _ => panic!("Unsupported vectorizations scheme {:?}", item.vectorization),
         }
     }
@@ -280,8 +357,25 @@ impl WgslCompiler {
             cube::Variable::ConstantScalar(value) => {
                 wgsl::Variable::ConstantScalar(value, Self::compile_elem(value.elem()))
             }
-            cube::Variable::SharedMemory { id, item, length } => {
+            cube::Variable::SharedMemory {
+                id,
+                item,
+                length,
+                dynamic,
+            } => {
                 let item = Self::compile_item(item);
+                let length = match dynamic {
+                    true => {
+                        let length = self.dynamic_shared_memory / item.size() as u32;
+                        assert!(
+                            length > 0,
+                            "The dynamic shared memory of {} bytes can't hold a single {item}",
+                            self.dynamic_shared_memory
+                        );
+                        length
+                    }
+                    false => length,
+                };
                 if !self.shared_memories.iter().any(|s| s.index == id) {
                     self.shared_memories
                         .push(SharedMemory::new(id, item, length));
@@ -380,10 +474,14 @@ impl WgslCompiler {
                 self.num_workgroup_no_axis = true;
                 wgsl::Variable::NumWorkgroups
             }
-            cube::Variable::SubcubeDim => {
+            cube::Variable::SubcubeDim if self.subgroups => {
                 self.subgroup_size = true;
                 wgsl::Variable::SubgroupSize
             }
+            cube::Variable::SubcubeDim => {
+                self.workgroup_size_no_axis = true;
+                wgsl::Variable::WorkgroupSize
+            }
             cube::Variable::Matrix { .. } => {
                 panic!("Cooperative matrix-multiply and accumulate not supported.")
             }
@@ -421,10 +519,15 @@ impl WgslCompiler {
             });
         }
 
-        processing
-            .operations
-            .into_iter()
-            .for_each(|op| self.compile_operation(&mut instructions, op));
+        for op in processing.operations {
+            let mut compiled = Vec::new();
+            self.compile_operation(&mut compiled, op);
+            instructions.extend(
+                compiled
+                    .into_iter()
+                    .flat_map(wgsl::Instruction::split_parts),
+            );
+        }
 
         instructions
     }
@@ -442,6 +545,18 @@ impl WgslCompiler {
                 self.compile_synchronization(instructions, val)
             }
             cube::Operation::Subcube(op) => self.compile_subgroup(instructions, op),
+            cube::Operation::Call(call) => instructions.push(wgsl::Instruction::Call {
+                name: call.name,
+                args: call
+                    .args
+                    .into_iter()
+                    .map(|arg| self.compile_variable(arg))
+                    .collect(),
+                out: call.out.map(|out| self.compile_variable(out)),
+            }),
+            cube::Operation::Location(location) => instructions.push(wgsl::Instruction::Comment {
+                content: location.to_string(),
+            }),
             cube::Operation::CoopMma(_) => {
                 panic!("Cooperative matrix-multiply and accumulate isn't supported on wgpu.")
             }
@@ -521,6 +636,7 @@ impl WgslCompiler {
             }),
             cube::Branch::Return => instructions.push(wgsl::Instruction::Return),
             cube::Branch::Break => instructions.push(wgsl::Instruction::Break),
+            cube::Branch::Continue => instructions.push(wgsl::Instruction::Continue),
             cube::Branch::RangeLoop(mut range_loop) => {
                 instructions.push(wgsl::Instruction::RangeLoop {
                     i: self.compile_variable(range_loop.i),
@@ -549,6 +665,9 @@ impl WgslCompiler {
             cube::Synchronization::SyncStorage => {
                 instructions.push(wgsl::Instruction::StorageBarrier)
             }
+            // Asynchronous copies are performed right away.
+            cube::Synchronization::CopyAsyncCommit
+            | cube::Synchronization::CopyAsyncWait { .. } => {}
         };
     }
 
@@ -587,8 +706,39 @@ impl WgslCompiler {
         }
     }
 
+    /// Whether the operation writing to `out` is approximated, which is only done for `f32`.
+    fn is_fast_math(&self, out: cube::Variable) -> bool {
+        self.fast_math && out.item().elem() == cube::Elem::Float(cube::FloatKind::F32)
+    }
+
     fn compile_instruction(&mut self, value: cube::Operator) -> wgsl::Instruction {
         match value {
+            cube::Operator::SaturatingAdd(op) => wgsl::Instruction::SaturatingAdd {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::SaturatingSub(op) => wgsl::Instruction::SaturatingSub {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            // Integer arithmetic wraps around in WGSL.
+            cube::Operator::WrappingAdd(op) => wgsl::Instruction::Add {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::WrappingSub(op) => wgsl::Instruction::Sub {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::WrappingMul(op) => wgsl::Instruction::Mul {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Max(op) => wgsl::Instruction::Max {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -644,6 +794,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::Exp(op) if self.is_fast_math(op.out) => wgsl::Instruction::FastExp {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Exp(op) => wgsl::Instruction::Exp {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
@@ -668,6 +822,11 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::Powf(op) if self.is_fast_math(op.out) => wgsl::Instruction::FastPowf {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Powf(op) => wgsl::Instruction::Powf {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -693,6 +852,22 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::Erfc(op) => wgsl::Instruction::Erfc {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Expm1(op) => wgsl::Instruction::Expm1 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Tgamma(op) => wgsl::Instruction::Tgamma {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Lgamma(op) => wgsl::Instruction::Lgamma {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Recip(op) => wgsl::Instruction::Recip {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
@@ -707,6 +882,29 @@ impl WgslCompiler {
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::RotateLeft(op) => wgsl::Instruction::RotateLeft {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::RotateRight(op) => wgsl::Instruction::RotateRight {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::BitfieldExtract(op) => wgsl::Instruction::ExtractBits {
+                input: self.compile_variable(op.input),
+                offset: self.compile_variable(op.offset),
+                count: self.compile_variable(op.count),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::BitfieldInsert(op) => wgsl::Instruction::InsertBits {
+                base: self.compile_variable(op.base),
+                insert: self.compile_variable(op.insert),
+                offset: self.compile_variable(op.offset),
+                count: self.compile_variable(op.count),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::Clamp(op) => wgsl::Instruction::Clamp {
                 input: self.compile_variable(op.input),
                 min_value: self.compile_variable(op.min_value),
@@ -822,6 +1020,22 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::PackF16(op) => wgsl::Instruction::PackF16 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::UnpackF16(op) => wgsl::Instruction::UnpackF16 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::PackBf16(op) => wgsl::Instruction::PackBf16 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::UnpackBf16(op) => wgsl::Instruction::UnpackBf16 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::AtomicAdd(op) => wgsl::Instruction::AtomicAdd {
                 lhs: self.compile_variable(op.lhs),
                 rhs: self.compile_variable(op.rhs),
@@ -874,6 +1088,19 @@ impl WgslCompiler {
                 rhs: self.compile_variable(op.rhs),
                 out: self.compile_variable(op.out),
             },
+            cube::Operator::MatrixMul(op) => wgsl::Instruction::MatrixMul {
+                lhs: self.compile_variable(op.lhs),
+                rhs: self.compile_variable(op.rhs),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Transpose(op) => wgsl::Instruction::Transpose {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Determinant(op) => wgsl::Instruction::Determinant {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(op.out),
+            },
             cube::Operator::InitLine(op) => wgsl::Instruction::VecInit {
                 inputs: op
                     .inputs
@@ -882,19 +1109,31 @@ impl WgslCompiler {
                     .collect(),
                 out: self.compile_variable(op.out),
             },
-            cube::Operator::Copy(op) => wgsl::Instruction::Copy {
+            cube::Operator::Swizzle(op) => wgsl::Instruction::Swizzle {
                 input: self.compile_variable(op.input),
-                in_index: self.compile_variable(op.in_index),
+                components: op.components,
                 out: self.compile_variable(op.out),
-                out_index: self.compile_variable(op.out_index),
             },
-            cube::Operator::CopyBulk(op) => wgsl::Instruction::CopyBulk {
+            cube::Operator::SwizzleAssign(op) => wgsl::Instruction::SwizzleAssign {
+                input: self.compile_variable(op.input),
+                components: op.components,
+                out: self.compile_variable(op.out),
+            },
+            cube::Operator::Copy(op) => wgsl::Instruction::Copy {
                 input: self.compile_variable(op.input),
                 in_index: self.compile_variable(op.in_index),
                 out: self.compile_variable(op.out),
                 out_index: self.compile_variable(op.out_index),
-                len: op.len,
             },
+            cube::Operator::CopyBulk(op) | cube::Operator::CopyBulkAsync(op) => {
+                wgsl::Instruction::CopyBulk {
+                    input: self.compile_variable(op.input),
+                    in_index: self.compile_variable(op.in_index),
+                    out: self.compile_variable(op.out),
+                    out_index: self.compile_variable(op.out_index),
+                    len: op.len,
+                }
+            }
         }
     }
 
@@ -946,6 +1185,33 @@ fn register_extensions(instructions: &[wgsl::Instruction]) -> Vec<wgsl::Extensio
             wgsl::Instruction::Erf { input, out: _ } => {
                 register_extension(wgsl::Extension::Erf(input.item()));
             }
+            wgsl::Instruction::Erfc { input, out: _ } => {
+                register_extension(wgsl::Extension::Erfc(input.item()));
+            }
+            wgsl::Instruction::Expm1 { input, out: _ } => {
+                register_extension(wgsl::Extension::Expm1(input.item()));
+            }
+            wgsl::Instruction::Tgamma { input, out: _ } => {
+                register_extension(wgsl::Extension::Tgamma(input.item()));
+            }
+            wgsl::Instruction::Lgamma { input, out: _ } => {
+                register_extension(wgsl::Extension::Lgamma(input.item()));
+            }
+            wgsl::Instruction::PackBf16 { .. } => {
+                register_extension(wgsl::Extension::PackBf16);
+            }
+            wgsl::Instruction::SaturatingAdd { out, .. } => {
+                register_extension(wgsl::Extension::SaturatingAdd(out.item()));
+            }
+            wgsl::Instruction::SaturatingSub { out, .. } => {
+                register_extension(wgsl::Extension::SaturatingSub(out.item()));
+            }
+            wgsl::Instruction::RotateLeft { out, .. } => {
+                register_extension(wgsl::Extension::RotateLeft(out.item()));
+            }
+            wgsl::Instruction::RotateRight { out, .. } => {
+                register_extension(wgsl::Extension::RotateRight(out.item()));
+            }
             #[cfg(target_os = "macos")]
             wgsl::Instruction::Tanh { input, out: _ } => {
                 register_extension(wgsl::Extension::SafeTanh(input.item()))
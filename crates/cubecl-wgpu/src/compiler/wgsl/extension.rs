@@ -1,4 +1,4 @@
-use super::base::Item;
+use super::base::{Elem, Item};
 use std::fmt::Display;
 
 /// Not all functions are native to WGSL, so this struct allows to support more functions.
@@ -8,6 +8,15 @@ pub enum Extension {
     PowfPrimitive(Item),
     Powf(Item),
     Erf(Item),
+    Erfc(Item),
+    Expm1(Item),
+    Tgamma(Item),
+    Lgamma(Item),
+    PackBf16,
+    SaturatingAdd(Item),
+    SaturatingSub(Item),
+    RotateLeft(Item),
+    RotateRight(Item),
     #[cfg(target_os = "macos")]
     SafeTanh(Item),
 }
@@ -19,6 +28,15 @@ impl Display for Extension {
             Extension::PowfPrimitive(elem) => format_powf_primitive(f, elem),
             Extension::Powf(elem) => format_powf(f, elem),
             Extension::Erf(elem) => format_erf(f, elem),
+            Extension::Erfc(elem) => format_erfc(f, elem),
+            Extension::Expm1(elem) => format_expm1(f, elem),
+            Extension::Tgamma(elem) => format_tgamma(f, elem),
+            Extension::Lgamma(elem) => format_lgamma(f, elem),
+            Extension::PackBf16 => format_pack_bf16(f),
+            Extension::SaturatingAdd(item) => format_saturating(f, "saturating_add", item),
+            Extension::SaturatingSub(item) => format_saturating(f, "saturating_sub", item),
+            Extension::RotateLeft(item) => format_rotate(f, "rotate_left", item),
+            Extension::RotateRight(item) => format_rotate(f, "rotate_right", item),
             #[cfg(target_os = "macos")]
             Extension::SafeTanh(elem) => format_safe_tanh(f, elem),
         }
@@ -71,6 +89,7 @@ fn powf_scalar(lhs: {elem}, rhs: {elem}) -> {elem} {{
 }}
 "
         ),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
     }
 }
 
@@ -149,6 +168,7 @@ fn powf(lhs: {elem}, rhs: {elem}) -> {elem} {{
 }}
 "
         ),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
     }
 }
 
@@ -229,9 +249,272 @@ fn erf(x: {ty}) -> {ty} {{
 }}
                 "
         ),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
     }
 }
 
+fn format_erfc(f: &mut core::fmt::Formatter<'_>, ty: &Item) -> core::fmt::Result {
+    let elem = ty.elem();
+    write!(
+        f,
+        "
+/// The complementary error function, with a fractional error under 1.2×10−7 everywhere:
+/// Numerical Recipes, section 6.2, Chebyshev fitting.
+///
+/// Computing 1 - erf(x) would lose every digit for large x.
+fn erfc_scalar(x: {elem}) -> {elem} {{
+    let z = abs(x);
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let tmp = -1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+        + t * (-0.82215223 + t * 0.17087277))))))));
+    let res = t * exp(-z * z + tmp);
+
+    if (x < 0.0) {{
+        return 2.0 - res;
+    }}
+
+    return res;
+}}
+"
+    )?;
+
+    format_vectorized(f, "erfc", ty)
+}
+
+fn format_expm1(f: &mut core::fmt::Formatter<'_>, ty: &Item) -> core::fmt::Result {
+    let elem = ty.elem();
+    write!(
+        f,
+        "
+/// exp(x) - 1, with a Taylor polynomial of degree 8 near zero where the subtraction would cancel
+/// every significant digit.
+fn expm1_scalar(x: {elem}) -> {elem} {{
+    if (abs(x) < 0.5) {{
+        return x * (1.0 + x * (0.5 + x * (1.0 / 6.0 + x * (1.0 / 24.0 + x * (1.0 / 120.0
+            + x * (1.0 / 720.0 + x * (1.0 / 5040.0 + x * (1.0 / 40320.0))))))));
+    }}
+
+    return exp(x) - 1.0;
+}}
+"
+    )?;
+
+    format_vectorized(f, "expm1", ty)
+}
+
+/// The Lanczos series with g = 7 and 9 coefficients, for z = x - 1.
+fn format_lanczos_sum(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(
+        "0.99999999999980993
+        + 676.5203681218851 / (z + 1.0)
+        - 1259.1392167224028 / (z + 2.0)
+        + 771.32342877765313 / (z + 3.0)
+        - 176.61502916214059 / (z + 4.0)
+        + 12.507343278686905 / (z + 5.0)
+        - 0.13857109526572012 / (z + 6.0)
+        + 9.9843695780195716e-6 / (z + 7.0)
+        + 1.5056327351493116e-7 / (z + 8.0)",
+    )
+}
+
+fn format_tgamma(f: &mut core::fmt::Formatter<'_>, ty: &Item) -> core::fmt::Result {
+    let elem = ty.elem();
+    write!(
+        f,
+        "
+/// The gamma function with the Lanczos approximation: https://en.wikipedia.org/wiki/Lanczos_approximation
+///
+/// Only valid for x ≥ 0.5, smaller inputs use the reflection formula.
+fn tgamma_positive_scalar(x: {elem}) -> {elem} {{
+    let z = x - 1.0;
+    let a = "
+    )?;
+    format_lanczos_sum(f)?;
+    write!(
+        f,
+        ";
+    let t = z + 7.5;
+    // t^(z + 0.5) is split in two powers so it doesn't overflow before the result does.
+    let p = pow(t, 0.5 * (z + 0.5));
+
+    return 2.5066282746310002 * p * (p * exp(-t)) * a;
+}}
+
+fn tgamma_scalar(x: {elem}) -> {elem} {{
+    if (x < 0.5) {{
+        let pi = 3.14159265358979323846;
+        return pi / (sin(pi * x) * tgamma_positive_scalar(1.0 - x));
+    }}
+
+    return tgamma_positive_scalar(x);
+}}
+"
+    )?;
+
+    format_vectorized(f, "tgamma", ty)
+}
+
+fn format_lgamma(f: &mut core::fmt::Formatter<'_>, ty: &Item) -> core::fmt::Result {
+    let elem = ty.elem();
+    write!(
+        f,
+        "
+/// The logarithm of the absolute value of the gamma function with the Lanczos approximation:
+/// https://en.wikipedia.org/wiki/Lanczos_approximation
+///
+/// Only valid for x ≥ 0.5, smaller inputs use the reflection formula.
+fn lgamma_positive_scalar(x: {elem}) -> {elem} {{
+    let z = x - 1.0;
+    let a = "
+    )?;
+    format_lanczos_sum(f)?;
+    write!(
+        f,
+        ";
+    let t = z + 7.5;
+
+    return 0.91893853320467274 + (z + 0.5) * log(t) - t + log(a);
+}}
+
+fn lgamma_scalar(x: {elem}) -> {elem} {{
+    if (x < 0.5) {{
+        let pi = 3.14159265358979323846;
+        return log(pi / abs(sin(pi * x))) - lgamma_positive_scalar(1.0 - x);
+    }}
+
+    return lgamma_positive_scalar(x);
+}}
+"
+    )?;
+
+    format_vectorized(f, "lgamma", ty)
+}
+
+/// Round the `f32` to `bf16` to nearest even by adding half of the dropped bits, with the parity
+/// of the kept bits breaking the ties. NaNs are kept quiet, since the rounding could carry them
+/// to infinity.
+fn format_pack_bf16(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(
+        "
+fn pack_bf16(x: vec2<f32>) -> u32 {
+    let bits = bitcast<vec2<u32>>(x);
+    let rounded = (bits + 0x7fffu + ((bits >> vec2(16u)) & vec2(1u))) >> vec2(16u);
+    let is_nan = (bits & vec2(0x7fffffffu)) > vec2(0x7f800000u);
+    let halves = select(rounded, (bits >> vec2(16u)) | vec2(0x40u), is_nan);
+    return halves.x | (halves.y << 16u);
+}
+",
+    )
+}
+
+/// The name of a function specialized for an item, since WGSL doesn't have overloads.
+pub fn specialized_name(name: &str, item: &Item) -> String {
+    match item {
+        Item::Vec4(elem) => format!("{name}_vec4_{elem}"),
+        Item::Vec3(elem) => format!("{name}_vec3_{elem}"),
+        Item::Vec2(elem) => format!("{name}_vec2_{elem}"),
+        Item::Scalar(elem) => format!("{name}_{elem}"),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
+    }
+}
+
+/// Saturate the integer sum or difference by comparing `lhs` with the bounds offset by `rhs`, so
+/// the overflowing result is never selected.
+fn format_saturating(
+    f: &mut core::fmt::Formatter<'_>,
+    name: &str,
+    item: &Item,
+) -> core::fmt::Result {
+    let function = specialized_name(name, item);
+    let body = match (name, item.elem()) {
+        ("saturating_add", Elem::I32) => format!(
+            "let max = {item}(2147483647);
+    let min = {item}(-2147483647 - 1);
+    let sum = lhs + rhs;
+    return select(select(sum, min, lhs < min - rhs), select(sum, max, lhs > max - rhs), rhs > {item}(0));"
+        ),
+        ("saturating_sub", Elem::I32) => format!(
+            "let max = {item}(2147483647);
+    let min = {item}(-2147483647 - 1);
+    let difference = lhs - rhs;
+    return select(select(difference, max, lhs > max + rhs), select(difference, min, lhs < min + rhs), rhs > {item}(0));"
+        ),
+        ("saturating_add", Elem::U32) => format!(
+            "let max = {item}(4294967295u);
+    return select(lhs + rhs, max, lhs > max - rhs);"
+        ),
+        ("saturating_sub", Elem::U32) => format!("return select(lhs - rhs, {item}(0u), lhs < rhs);"),
+        (name, elem) => panic!("{name} isn't supported for {elem}"),
+    };
+
+    write!(
+        f,
+        "
+fn {function}(lhs: {item}, rhs: {item}) -> {item} {{
+    {body}
+}}
+"
+    )
+}
+
+/// Apply the scalar function `{name}_scalar` to each element of the item.
+/// Rotate the bits with two shifts of the unsigned bits, so the right shift is logical for signed
+/// integers too. The amount is taken modulo the bit width, since larger shifts aren't defined.
+fn format_rotate(f: &mut core::fmt::Formatter<'_>, name: &str, item: &Item) -> core::fmt::Result {
+    let function = specialized_name(name, item);
+    let bits = match item {
+        Item::Vec4(_) => Item::Vec4(Elem::U32),
+        Item::Vec3(_) => Item::Vec3(Elem::U32),
+        Item::Vec2(_) => Item::Vec2(Elem::U32),
+        Item::Scalar(_) => Item::Scalar(Elem::U32),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
+    };
+    let (forward, backward) = match name {
+        "rotate_left" => ("<<", ">>"),
+        _ => (">>", "<<"),
+    };
+
+    write!(
+        f,
+        "
+fn {function}(value: {item}, amount: u32) -> {item} {{
+    let bits = bitcast<{bits}>(value);
+    let shift = amount % 32u;
+    return bitcast<{item}>((bits {forward} {bits}(shift)) | (bits {backward} {bits}((32u - shift) % 32u)));
+}}
+"
+    )
+}
+
+fn format_vectorized(f: &mut core::fmt::Formatter<'_>, name: &str, ty: &Item) -> core::fmt::Result {
+    let (constructor, factor) = match ty {
+        Item::Vec4(_) => ("vec4", 4),
+        Item::Vec3(_) => ("vec3", 3),
+        Item::Vec2(_) => ("vec2", 2),
+        Item::Scalar(_) => {
+            return write!(
+                f,
+                "
+fn {name}(x: {ty}) -> {ty} {{
+    return {name}_scalar(x);
+}}
+"
+            )
+        }
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
+    };
+
+    write!(
+        f,
+        "\nfn {name}(x: {ty}) -> {ty} {{\n    return {constructor}(\n"
+    )?;
+    for i in 0..factor {
+        writeln!(f, "        {name}_scalar(x[{i}]),")?;
+    }
+    f.write_str("    );\n}\n")
+}
+
 #[cfg(target_os = "macos")]
 fn format_safe_tanh(f: &mut core::fmt::Formatter<'_>, item: &Item) -> core::fmt::Result {
     let elem = item.elem();
@@ -295,5 +578,6 @@ fn safe_tanh(x: {item}) -> {item} {{
 }}
 "
         ),
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
     }
 }
@@ -1,8 +1,8 @@
 use super::{
-    base::{Item, Variable},
-    Elem, Subgroup,
+    base::{display_fn, Item, Variable},
+    specialized_name, Elem, Subgroup,
 };
-use std::fmt::Display;
+use std::{f32::consts::LOG2_E, fmt::Display};
 
 /// All instructions that can be used in a WGSL compute shader.
 #[derive(Debug, Clone)]
@@ -54,6 +54,15 @@ pub enum Instruction {
     },
     Return,
     Break,
+    Continue,
+    Call {
+        name: String,
+        args: Vec<Variable>,
+        out: Option<Variable>,
+    },
+    Comment {
+        content: String,
+    },
     WorkgroupBarrier,
     StorageBarrier,
     // Index handles casting to correct local variable.
@@ -101,6 +110,11 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    /// An `exp` computed with `exp2`, for kernels with relaxed fast math.
+    FastExp {
+        input: Variable,
+        out: Variable,
+    },
     Log {
         input: Variable,
         out: Variable,
@@ -126,6 +140,13 @@ pub enum Instruction {
         rhs: Variable,
         out: Variable,
     },
+    /// A `powf` without the extension handling negative bases and zero exponents, for kernels with
+    /// relaxed fast math.
+    FastPowf {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
     Sqrt {
         input: Variable,
         out: Variable,
@@ -134,6 +155,22 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Erfc {
+        input: Variable,
+        out: Variable,
+    },
+    Expm1 {
+        input: Variable,
+        out: Variable,
+    },
+    Tgamma {
+        input: Variable,
+        out: Variable,
+    },
+    Lgamma {
+        input: Variable,
+        out: Variable,
+    },
     Recip {
         input: Variable,
         out: Variable,
@@ -265,6 +302,55 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    PackF16 {
+        input: Variable,
+        out: Variable,
+    },
+    SaturatingAdd {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    SaturatingSub {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    RotateLeft {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    RotateRight {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    ExtractBits {
+        input: Variable,
+        offset: Variable,
+        count: Variable,
+        out: Variable,
+    },
+    InsertBits {
+        base: Variable,
+        insert: Variable,
+        offset: Variable,
+        count: Variable,
+        out: Variable,
+    },
+    UnpackF16 {
+        input: Variable,
+        out: Variable,
+    },
+    PackBf16 {
+        input: Variable,
+        out: Variable,
+    },
+    UnpackBf16 {
+        input: Variable,
+        out: Variable,
+    },
     AtomicLoad {
         input: Variable,
         out: Variable,
@@ -341,6 +427,29 @@ pub enum Instruction {
         inputs: Vec<Variable>,
         out: Variable,
     },
+    MatrixMul {
+        lhs: Variable,
+        rhs: Variable,
+        out: Variable,
+    },
+    Transpose {
+        input: Variable,
+        out: Variable,
+    },
+    Determinant {
+        input: Variable,
+        out: Variable,
+    },
+    Swizzle {
+        input: Variable,
+        components: Vec<u32>,
+        out: Variable,
+    },
+    SwizzleAssign {
+        input: Variable,
+        components: Vec<u32>,
+        out: Variable,
+    },
     Copy {
         input: Variable,
         in_index: Variable,
@@ -448,18 +557,8 @@ impl Display for Instruction {
                 out,
                 out_index,
             } => {
-                let rhs = match input {
-                    Variable::Slice { .. } => {
-                        format!("(*{input}_ptr)[{in_index} + {input}_offset]")
-                    }
-                    _ => format!("{input}[{in_index}]"),
-                };
-                let lhs = match out {
-                    Variable::Slice { .. } => {
-                        format!("(*{out}_ptr)[{out_index} + {out}_offset]")
-                    }
-                    _ => format!("{out}[{out_index}]"),
-                };
+                let lhs = CopyElement(out, out_index, None);
+                let rhs = CopyElement(input, in_index, None);
                 writeln!(f, "{lhs} = {rhs};")
             }
             Instruction::CopyBulk {
@@ -470,18 +569,8 @@ impl Display for Instruction {
                 len,
             } => {
                 for i in 0..*len {
-                    let rhs = match input {
-                        Variable::Slice { .. } => {
-                            format!("(*{input}_ptr)[{in_index} + {input}_offset + {i}]")
-                        }
-                        _ => format!("{input}[{in_index} + {i}]"),
-                    };
-                    let lhs = match out {
-                        Variable::Slice { .. } => {
-                            format!("(*{out}_ptr)[{out_index} + {out}_offset + {i}]")
-                        }
-                        _ => format!("{out}[{out_index} + {i}]"),
-                    };
+                    let lhs = CopyElement(out, out_index, Some(i));
+                    let rhs = CopyElement(input, in_index, Some(i));
                     writeln!(f, "{lhs} = {rhs};")?;
                 }
                 Ok(())
@@ -490,18 +579,37 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = {lhs} % {rhs};")
             }
+            // Integers adjust the truncated remainder of `%`, since going through `f32` isn't
+            // exact for large values.
+            Instruction::Remainder { lhs, rhs, out } if lhs.elem() == Elem::I32 => {
+                let item = out.item();
+                let zero = display_fn(|f| write!(f, "{item}(0)"));
+                let rhs = display_fn(|f| write!(f, "{item}({rhs})"));
+                let rem = display_fn(|f| write!(f, "{lhs} % {rhs}"));
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = select({rem}, {rem} + {rhs}, {rem} != {zero} & (({rem} < {zero}) != ({rhs} < {zero})));"
+                )
+            }
+            Instruction::Remainder { lhs, rhs, out } if lhs.elem() == Elem::U32 => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {lhs} % {rhs};")
+            }
             Instruction::Remainder { lhs, rhs, out } => {
                 let f_type = match lhs.item() {
                     Item::Vec4(_) => Item::Vec4(Elem::F32),
                     Item::Vec3(_) => Item::Vec3(Elem::F32),
                     Item::Vec2(_) => Item::Vec2(Elem::F32),
                     Item::Scalar(_) => Item::Scalar(Elem::F32),
+                    Item::Wide(_, vectorization) => Item::Wide(Elem::F32, vectorization),
                 };
                 let ty = lhs.item();
                 let lhs = lhs.fmt_cast_to(f_type);
                 let rhs = rhs.fmt_cast_to(f_type);
                 let out = out.fmt_left();
-                let floor = f_type.fmt_cast_to(ty, format!("floor({lhs} / {rhs})"));
+                let floor =
+                    f_type.fmt_cast_to(ty, display_fn(|f| write!(f, "floor({lhs} / {rhs})")));
                 writeln!(f, "{out} = {lhs} - {rhs} * {floor};")
             }
             Instruction::Sub { lhs, rhs, out } => {
@@ -553,6 +661,14 @@ impl Display for Instruction {
                     writeln!(f, "{out} = powf({lhs}, {rhs});")
                 }
             }
+            Instruction::FastPowf { lhs, rhs, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = exp2({rhs} * log2({lhs}));")
+            }
+            Instruction::FastExp { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = exp2({input} * {LOG2_E});")
+            }
             Instruction::Sqrt { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = sqrt({input});")
@@ -582,6 +698,22 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = erf({input});")
             }
+            Instruction::Erfc { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = erfc({input});")
+            }
+            Instruction::Expm1 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = expm1({input});")
+            }
+            Instruction::Tgamma { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = tgamma({input});")
+            }
+            Instruction::Lgamma { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = lgamma({input});")
+            }
             Instruction::Recip { input, out } => {
                 let out = out.fmt_left();
                 write!(f, "{out} = 1.0 / {input};")
@@ -635,10 +767,10 @@ impl Display for Instruction {
                 inclusive,
                 instructions,
             } => {
-                let increment = step
-                    .as_ref()
-                    .map(|step| format!("{i} += {step}"))
-                    .unwrap_or_else(|| format!("{i}++"));
+                let increment = display_fn(|f| match step {
+                    Some(step) => write!(f, "{i} += {step}"),
+                    None => write!(f, "{i}++"),
+                });
                 let cmp = if *inclusive { "<=" } else { "<" };
                 let i_ty = i.item();
 
@@ -745,12 +877,32 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
             }
             Instruction::Return => f.write_str("return;\n"),
             Instruction::Break => f.write_str("break;\n"),
+            Instruction::Continue => f.write_str("continue;\n"),
+            Instruction::Comment { content } => writeln!(f, "// {content}"),
+            Instruction::Call { name, args, out } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match out {
+                    Some(out) => {
+                        let out = out.fmt_left();
+                        writeln!(f, "{out} = {name}({args});")
+                    }
+                    None => writeln!(f, "{name}({args});"),
+                }
+            }
             Instruction::WorkgroupBarrier => f.write_str("workgroupBarrier();\n"),
             Instruction::StorageBarrier => f.write_str("storageBarrier();\n"),
             Instruction::Length { var, out } => {
                 let out = out.fmt_left();
                 match var {
                     Variable::Slice { .. } => writeln!(f, "{out} = {var}_length;"),
+                    _ if var.item().is_padded() => {
+                        let size = var.item().vectorization_factor();
+                        writeln!(f, "{out} = arrayLength(&{var}) / {size}u;")
+                    }
                     _ => writeln!(f, "{out} = arrayLength(&{var});"),
                 }
             }
@@ -773,13 +925,16 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = {lhs} ^ {rhs};")
             }
+            // WGSL only shifts by unsigned amounts.
             Instruction::ShiftLeft { lhs, rhs, out } => {
+                let amount = rhs.fmt_cast_to(rhs.item().with_elem(Elem::U32));
                 let out = out.fmt_left();
-                writeln!(f, "{out} = {lhs} << {rhs};")
+                writeln!(f, "{out} = {lhs} << {amount};")
             }
             Instruction::ShiftRight { lhs, rhs, out } => {
+                let amount = rhs.fmt_cast_to(rhs.item().with_elem(Elem::U32));
                 let out = out.fmt_left();
-                writeln!(f, "{out} = {lhs} >> {rhs};")
+                writeln!(f, "{out} = {lhs} >> {amount};")
             }
             Instruction::Round { input, out } => {
                 let out = out.fmt_left();
@@ -799,6 +954,68 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
                 let out = out.fmt_left();
                 writeln!(f, "{out} = bitcast<{elem}>({input});")
             }
+            Instruction::SaturatingAdd { lhs, rhs, out } => {
+                let name = specialized_name("saturating_add", &out.item());
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {name}({lhs}, {rhs});")
+            }
+            Instruction::SaturatingSub { lhs, rhs, out } => {
+                let name = specialized_name("saturating_sub", &out.item());
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {name}({lhs}, {rhs});")
+            }
+            Instruction::RotateLeft { lhs, rhs, out } => {
+                let name = specialized_name("rotate_left", &out.item());
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {name}({lhs}, {rhs});")
+            }
+            Instruction::RotateRight { lhs, rhs, out } => {
+                let name = specialized_name("rotate_right", &out.item());
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {name}({lhs}, {rhs});")
+            }
+            Instruction::ExtractBits {
+                input,
+                offset,
+                count,
+                out,
+            } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = extractBits({input}, {offset}, {count});")
+            }
+            Instruction::InsertBits {
+                base,
+                insert,
+                offset,
+                count,
+                out,
+            } => {
+                let insert = insert.fmt_cast_to(out.item());
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = insertBits({base}, {insert}, {offset}, {count});"
+                )
+            }
+            Instruction::PackF16 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = pack2x16float({input});")
+            }
+            Instruction::UnpackF16 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = unpack2x16float({input});")
+            }
+            Instruction::PackBf16 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = pack_bf16({input});")
+            }
+            Instruction::UnpackBf16 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(
+                    f,
+                    "{out} = bitcast<vec2<f32>>(vec2({input} << 16u, {input} & 0xffff0000u));"
+                )
+            }
             Instruction::AtomicLoad { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = atomicLoad({input});")
@@ -882,14 +1099,145 @@ for (var {i}: {i_ty} = {start}; {i} {cmp} {end}; {increment}) {{
             }
             Instruction::VecInit { inputs, out } => {
                 let item = out.item();
-                let inputs = inputs.iter().map(|var| var.to_string()).collect::<Vec<_>>();
+                let mut inputs = inputs.iter().map(|var| var.to_string()).collect::<Vec<_>>();
+                if let Item::Wide(elem, _) = item {
+                    inputs.resize(4 * item.parts(), format!("{elem}(0)"));
+                    inputs = inputs
+                        .chunks(4)
+                        .map(|part| format!("vec4<{elem}>({})", part.join(", ")))
+                        .collect();
+                }
+                let out = out.fmt_left();
+                writeln!(f, "{out} = {item}({});", inputs.join(", "))
+            }
+            Instruction::MatrixMul { lhs, rhs, out } => {
+                let lhs_matrix = matrix(lhs);
+                if rhs.item() == lhs.item() {
+                    store_matrix(format!("{lhs_matrix} * {}", matrix(rhs)), out, f)
+                } else {
+                    let out = out.fmt_left();
+                    writeln!(f, "{out} = {lhs_matrix} * {rhs};")
+                }
+            }
+            Instruction::Transpose { input, out } => {
+                store_matrix(format!("transpose({})", matrix(input)), out, f)
+            }
+            Instruction::Determinant { input, out } => {
                 let out = out.fmt_left();
-                writeln!(f, "{out} = {item}({})", inputs.join(", "))
+                writeln!(f, "{out} = determinant({});", matrix(input))
+            }
+            Instruction::Swizzle {
+                input,
+                components,
+                out,
+            } => swizzle(input, components, out, f),
+            Instruction::SwizzleAssign {
+                input,
+                components,
+                out,
+            } => {
+                // WGSL can't assign to a swizzle, so each component is written on its own.
+                for (i, component) in components.iter().enumerate() {
+                    let out = out.index(*component as usize);
+                    writeln!(f, "{} = {};", out.fmt_left(), input.index(i))?;
+                }
+                Ok(())
             }
         }
     }
 }
 
+/// Matrices are stored column-major in lines, as a `vec4` for 2x2 matrices and as `vec4` parts
+/// for 3x3 and 4x4 matrices.
+fn matrix(var: &Variable) -> String {
+    match var.item() {
+        Item::Vec4(elem) => format!("mat2x2<{elem}>({var}.xy, {var}.zw)"),
+        Item::Wide(elem, 9) => format!(
+            "mat3x3<{elem}>({var}[0].xyz, vec3<{elem}>({var}[0].w, {var}[1].xy), \
+             vec3<{elem}>({var}[1].zw, {var}[2].x))"
+        ),
+        Item::Wide(elem, 16) => {
+            format!("mat4x4<{elem}>({var}[0], {var}[1], {var}[2], {var}[3])")
+        }
+        item => {
+            panic!("{item} can't be used as a matrix, WGSL only supports 2x2, 3x3 and 4x4 matrices")
+        }
+    }
+}
+
+fn store_matrix(
+    value: String,
+    out: &Variable,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let item = out.item();
+    let columns = |matrix: &str| {
+        match item {
+        Item::Vec4(_) => format!("{matrix}[0], {matrix}[1]"),
+        // The columns of 3 elements are repacked into padded `vec4` parts.
+        Item::Wide(elem, 9) => format!(
+            "vec4<{elem}>({matrix}[0], {matrix}[1].x), vec4<{elem}>({matrix}[1].yz, {matrix}[2].xy), \
+             vec4<{elem}>({matrix}[2].z, {elem}(0), {elem}(0), {elem}(0))"
+        ),
+        _ => (0..4)
+            .map(|i| format!("{matrix}[{i}]"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+    };
+
+    match out {
+        Variable::LocalBinding { .. } => {
+            let matrix = format!("{out}_matrix");
+            writeln!(f, "let {matrix} = {value};")?;
+            writeln!(f, "{} = {item}({});", out.fmt_left(), columns(&matrix))
+        }
+        _ => {
+            writeln!(f, "{{")?;
+            writeln!(f, "let matrix = {value};")?;
+            writeln!(f, "{out} = {item}({});", columns("matrix"))?;
+            writeln!(f, "}}")
+        }
+    }
+}
+
+fn swizzle(
+    input: &Variable,
+    components: &[u32],
+    out: &Variable,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let item = out.item();
+    let out = out.fmt_left();
+
+    if let Item::Vec2(_) | Item::Vec3(_) | Item::Vec4(_) = input.item() {
+        if !item.is_wide() {
+            let swizzle = components
+                .iter()
+                .map(|component| ['x', 'y', 'z', 'w'][*component as usize])
+                .collect::<String>();
+            return writeln!(f, "{out} = {input}.{swizzle};");
+        }
+    }
+
+    let values = components
+        .iter()
+        .map(|component| input.index(*component as usize).to_string())
+        .collect::<Vec<_>>();
+
+    match item {
+        Item::Scalar(_) => writeln!(f, "{out} = {};", values[0]),
+        Item::Wide(elem, _) => {
+            let parts = values
+                .chunks(4)
+                .map(|part| format!("vec4<{elem}>({})", part.join(", ")))
+                .collect::<Vec<_>>();
+            writeln!(f, "{out} = {item}({});", parts.join(", "))
+        }
+        _ => writeln!(f, "{out} = {item}({});", values.join(", ")),
+    }
+}
+
 fn comparison(
     lhs: &Variable,
     rhs: &Variable,
@@ -953,6 +1301,7 @@ fn comparison(
             }
             _ => panic!("Can only compare a scalar when the output is a scalar"),
         },
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
     }
 }
 
@@ -1012,7 +1361,7 @@ fn index(
     rhs: &Variable,
     out: &Variable,
     offset: Option<Variable>,
-) -> core::fmt::Result {
+) -> std::fmt::Result {
     let is_scalar = match lhs {
         Variable::Local { item, .. } => item.vectorization_factor() == 1,
         Variable::LocalBinding { item, .. } => item.vectorization_factor() == 1,
@@ -1031,15 +1380,17 @@ fn index(
             Some(offset) => {
                 let value = lhs
                     .item()
-                    .fmt_cast_to(item, format!("{lhs}[{rhs}+{offset}]"));
+                    .fmt_cast_to(item, display_fn(|f| write!(f, "{lhs}[{rhs}+{offset}]")));
                 writeln!(f, "{out} = {value};")
             }
             None => {
                 if is_scalar {
-                    let value = lhs.item().fmt_cast_to(item, format!("{lhs}"));
+                    let value = lhs.fmt_cast_to(item);
                     writeln!(f, "{out} = {value};")
                 } else {
-                    let value = lhs.item().fmt_cast_to(item, format!("{lhs}[{rhs}]"));
+                    let value = lhs
+                        .item()
+                        .fmt_cast_to(item, display_fn(|f| write!(f, "{lhs}[{rhs}]")));
                     writeln!(f, "{out} = {value};")
                 }
             }
@@ -1065,7 +1416,7 @@ fn index_assign(
     rhs: &Variable,
     out: &Variable,
     offset: Option<Variable>,
-) -> core::fmt::Result {
+) -> std::fmt::Result {
     match lhs.item() {
         Item::Vec4(elem) => {
             let item = Item::Scalar(elem);
@@ -1115,6 +1466,7 @@ fn index_assign(
                     Item::Vec3(_) => Item::Vec3(elem_out),
                     Item::Vec2(_) => Item::Vec2(elem_out),
                     Item::Scalar(_) => Item::Scalar(elem_out),
+                    Item::Wide(_, vectorization) => Item::Wide(elem_out, vectorization),
                 };
                 let rhs = rhs.fmt_cast_to(casting_type);
                 writeln!(f, "{out}[{lhs}] = {rhs};")
@@ -1128,7 +1480,7 @@ fn index_assign(
                     let casting_type = Item::Scalar(*item_out.elem());
                     write!(f, "{out}[{lhs}] = vec{vectorization_factor}(")?;
                     for i in 0..vectorization_factor {
-                        f.write_str(&rhs.index(i).fmt_cast(casting_type))?;
+                        write!(f, "{}", rhs.index(i).fmt_cast(casting_type))?;
 
                         if i < vectorization_factor - 1 {
                             f.write_str(",")?;
@@ -1141,5 +1493,23 @@ fn index_assign(
                 }
             }
         }
+        Item::Wide(..) => unreachable!("Wide items are split into vec4 parts"),
+    }
+}
+
+/// An element copied from or into an array, `i` elements after the index.
+struct CopyElement<'a>(&'a Variable, &'a Variable, Option<u32>);
+
+impl Display for CopyElement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let CopyElement(var, index, i) = self;
+        match var {
+            Variable::Slice { .. } => write!(f, "(*{var}_ptr)[{index} + {var}_offset")?,
+            _ => write!(f, "{var}[{index}")?,
+        }
+        match i {
+            Some(i) => write!(f, " + {i}]"),
+            None => f.write_str("]"),
+        }
     }
 }
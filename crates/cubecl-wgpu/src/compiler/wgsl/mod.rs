@@ -4,6 +4,7 @@ mod compiler;
 mod extension;
 mod instructions;
 mod shader;
+mod split;
 mod subgroup;
 
 pub(crate) use base::*;
@@ -1,4 +1,4 @@
-use super::{Body, Extension, Item, Variable};
+use super::{display_fn, Body, Elem, Extension, Instruction, Item, Variable};
 use cubecl_core::{ir::CubeDim, CompilerRepresentation};
 use std::fmt::Display;
 
@@ -68,11 +68,59 @@ impl LocalArray {
     }
 }
 
+/// A device function, whose parameters are copied into the locals used by its body.
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Variable>,
+    pub output: Option<Variable>,
+    pub declarations: Vec<Instruction>,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fn {}(", self.name)?;
+        for (i, input) in self.inputs.iter().enumerate() {
+            write!(f, "p_{i}: {}, ", input.item())?;
+        }
+        f.write_str(")")?;
+        if let Some(output) = &self.output {
+            write!(f, " -> {}", output.item())?;
+        }
+        f.write_str(" {\n")?;
+
+        for declaration in self.declarations.iter() {
+            write!(f, "{declaration}")?;
+        }
+        for (i, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "{input} = p_{i};")?;
+        }
+        for instruction in self.instructions.iter() {
+            write!(f, "{instruction}")?;
+        }
+        if let Some(output) = &self.output {
+            writeln!(f, "return {output};")?;
+        }
+
+        f.write_str("}\n\n")
+    }
+}
+
+/// Scalars of the same element type, declared as fields of the uniform `Scalars` struct.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScalarGroup {
+    pub name: String,
+    pub elem: Elem,
+    pub size: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct ComputeShader {
     pub inputs: Vec<Binding>,
     pub outputs: Vec<Binding>,
     pub named: Vec<(String, Binding)>,
+    pub scalars: Vec<ScalarGroup>,
     pub shared_memories: Vec<SharedMemory>,
     pub constant_arrays: Vec<ConstantArray>,
     pub local_arrays: Vec<LocalArray>,
@@ -87,6 +135,7 @@ pub struct ComputeShader {
     pub workgroup_id_no_axis: bool,
     pub workgroup_size_no_axis: bool,
     pub body: Body,
+    pub functions: Vec<Function>,
     pub extensions: Vec<Extension>,
 }
 
@@ -96,11 +145,14 @@ impl Display for ComputeShader {
         Self::format_bindings(f, "output", &self.outputs, self.inputs.len())?;
 
         for (i, (name, binding)) in self.named.iter().enumerate() {
-            Self::format_binding(
+            Self::format_binding(f, name, binding, self.inputs.len() + self.outputs.len() + i)?;
+        }
+
+        if !self.scalars.is_empty() {
+            Self::format_scalars(
                 f,
-                name.as_str(),
-                binding,
-                self.inputs.len() + self.outputs.len() + i,
+                &self.scalars,
+                self.inputs.len() + self.outputs.len() + self.named.len(),
             )?;
         }
 
@@ -125,6 +177,10 @@ impl Display for ComputeShader {
             f.write_str(");\n\n")?;
         }
 
+        for function in self.functions.iter() {
+            write!(f, "{function}")?;
+        }
+
         write!(
             f,
             "const WORKGROUP_SIZE_X = {}u;
@@ -214,7 +270,7 @@ impl ComputeShader {
         for (i, binding) in bindings.iter().enumerate() {
             Self::format_binding(
                 f,
-                format!("{prefix}_{i}_global").as_str(),
+                display_fn(|f| write!(f, "{prefix}_{i}_global")),
                 binding,
                 num_entry + i,
             )?;
@@ -223,27 +279,53 @@ impl ComputeShader {
         Ok(())
     }
 
-    fn format_binding(
+    /// All scalars are packed into a single uniform struct. Each scalar is a field aligned on its own
+    /// size, which matches the [uniform layout](cubecl_core::UniformLayout) used at launch.
+    fn format_scalars(
         f: &mut core::fmt::Formatter<'_>,
-        name: &str,
-        binding: &Binding,
+        scalars: &[ScalarGroup],
         num_entry: usize,
     ) -> core::fmt::Result {
-        let ty = match binding.size {
-            Some(size) => format!("array<{}, {}>", binding.item, size),
-            None => format!("array<{}>", binding.item),
-        };
+        f.write_str("struct Scalars {\n")?;
+        for group in scalars {
+            for i in 0..group.size {
+                writeln!(f, "    {}_{i}: {},", group.name, group.elem)?;
+            }
+        }
+        f.write_str("}\n\n")?;
 
         write!(
             f,
             "@group(0)
 @binding({})
-var<{}, {}> {}: {};
+var<uniform> scalars: Scalars;
 \n",
-            num_entry, binding.location, binding.visibility, name, ty
+            num_entry
+        )
+    }
+
+    fn format_binding(
+        f: &mut core::fmt::Formatter<'_>,
+        name: impl Display,
+        binding: &Binding,
+        num_entry: usize,
+    ) -> core::fmt::Result {
+        write!(
+            f,
+            "@group(0)
+@binding({})
+var<{}, {}> {}: array<{}",
+            num_entry,
+            binding.location,
+            binding.visibility,
+            name,
+            binding.item.storage_item()
         )?;
+        if let Some(size) = binding.size {
+            write!(f, ", {size}")?;
+        }
 
-        Ok(())
+        f.write_str(">;\n\n")
     }
 }
 
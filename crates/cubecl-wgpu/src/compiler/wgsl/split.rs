@@ -0,0 +1,485 @@
+use super::{Elem, Instruction, Item, Subgroup, Variable};
+
+/// Applies the mapping to every variable of an elementwise instruction, returning [None] for
+/// other instructions.
+macro_rules! map_elementwise {
+    ($instruction:expr, $map:expr, [$($variant:ident { $($field:ident),* }),* $(,)?]) => {
+        match $instruction {
+            $(Instruction::$variant { $($field),* } => Some(Instruction::$variant {
+                $($field: $map($field)),*
+            }),)*
+            _ => None,
+        }
+    };
+}
+
+impl Instruction {
+    /// Split an instruction working on [wide items](Item::Wide) into one instruction per `vec4`
+    /// part, since WGSL doesn't have vectors larger than 4 elements.
+    ///
+    /// Wide values are stored as arrays of `vec4`, so elementwise instructions are applied to
+    /// each part while scalars are broadcasted. Accessing a single element of a wide value
+    /// indexes the part holding it. Padded values are gathered from and scattered to the packed
+    /// elements of storage buffers.
+    pub fn split_parts(self) -> Vec<Instruction> {
+        match self {
+            Instruction::Index { lhs, rhs, out } if is_packed(&lhs) => {
+                let item = lhs.item();
+                let mut instructions = declare_binding(&out);
+                for k in 0..item.parts() {
+                    let elements = (4 * k..4 * k + 4)
+                        .map(|e| match e < item.vectorization_factor() {
+                            true => packed_element(&lhs, &rhs, e),
+                            false => format!("{}(0)", item.elem()),
+                        })
+                        .collect::<Vec<_>>();
+                    instructions.push(Instruction::Assign {
+                        input: part_expr(
+                            format!("vec4<{}>({})", item.elem(), elements.join(", ")),
+                            *item.elem(),
+                        ),
+                        out: part(&out, k),
+                    });
+                }
+                instructions
+            }
+            Instruction::IndexAssign { lhs, rhs, out } if is_packed(&out) => {
+                let item = out.item();
+                (0..item.vectorization_factor())
+                    .map(|e| Instruction::Assign {
+                        input: match rhs.item().is_wide() {
+                            true => scalar_expr(format!("{rhs}[{}][{}]", e / 4, e % 4), &item),
+                            false => rhs.clone(),
+                        },
+                        out: scalar_expr(packed_element(&out, &lhs, e), &item),
+                    })
+                    .collect()
+            }
+            Instruction::Index { lhs, rhs, out } if is_array(&lhs) && out.item().is_wide() => {
+                let mut instructions = declare_binding(&out);
+                for k in 0..out.item().parts() {
+                    instructions.push(Instruction::Assign {
+                        input: part_expr(format!("{}[{k}]", element(&lhs, &rhs)), lhs.elem()),
+                        out: part(&out, k),
+                    });
+                }
+                instructions
+            }
+            Instruction::Index { lhs, rhs, out } if !is_array(&lhs) && lhs.item().is_wide() => {
+                vec![Instruction::Index {
+                    lhs: part_expr(format!("{lhs}[{rhs} / 4u]"), lhs.elem()),
+                    rhs: index_in_part(&rhs),
+                    out,
+                }]
+            }
+            Instruction::IndexAssign { lhs, rhs, out }
+                if is_array(&out) && out.item().is_wide() =>
+            {
+                (0..out.item().parts())
+                    .map(|k| Instruction::Assign {
+                        input: part(&rhs, k),
+                        out: part_expr(format!("{}[{k}]", element(&out, &lhs)), out.elem()),
+                    })
+                    .collect()
+            }
+            Instruction::IndexAssign { lhs, rhs, out }
+                if !is_array(&out) && out.item().is_wide() =>
+            {
+                vec![Instruction::IndexAssign {
+                    lhs: index_in_part(&lhs),
+                    rhs,
+                    out: part_expr(format!("{out}[{lhs} / 4u]"), out.elem()),
+                }]
+            }
+            instruction => {
+                // The output is the last field of every elementwise instruction.
+                let mut parts = 0;
+                let mut out = None;
+                let elementwise = split_elementwise(&instruction, |var: &Variable| {
+                    parts = usize::max(parts, var.item().parts());
+                    out = Some(var.clone());
+                    var.clone()
+                });
+
+                if elementwise.is_none() && !handles_wide_items(&instruction) {
+                    assert!(
+                        !variables(&instruction)
+                            .iter()
+                            .any(|var| var.item().is_wide()),
+                        "Line sizes above 4 aren't supported by WGSL for {instruction:?}"
+                    );
+                }
+                if parts == 0 {
+                    return vec![instruction];
+                }
+
+                let mut instructions = out.map(|out| declare_binding(&out)).unwrap_or_default();
+                for k in 0..parts {
+                    instructions.extend(split_elementwise(&instruction, |var: &Variable| {
+                        part(var, k)
+                    }));
+                }
+                instructions
+            }
+        }
+    }
+}
+
+fn split_elementwise(
+    instruction: &Instruction,
+    mut map: impl FnMut(&Variable) -> Variable,
+) -> Option<Instruction> {
+    map_elementwise!(
+        instruction,
+        map,
+        [
+            Assign { input, out },
+            Add { lhs, rhs, out },
+            Sub { lhs, rhs, out },
+            Mul { lhs, rhs, out },
+            Div { lhs, rhs, out },
+            Modulo { lhs, rhs, out },
+            Remainder { lhs, rhs, out },
+            Max { lhs, rhs, out },
+            SaturatingAdd { lhs, rhs, out },
+            SaturatingSub { lhs, rhs, out },
+            RotateLeft { lhs, rhs, out },
+            RotateRight { lhs, rhs, out },
+            Min { lhs, rhs, out },
+            Powf { lhs, rhs, out },
+            FastPowf { lhs, rhs, out },
+            And { lhs, rhs, out },
+            Or { lhs, rhs, out },
+            BitwiseAnd { lhs, rhs, out },
+            BitwiseOr { lhs, rhs, out },
+            BitwiseXor { lhs, rhs, out },
+            ShiftLeft { lhs, rhs, out },
+            ShiftRight { lhs, rhs, out },
+            Equal { lhs, rhs, out },
+            NotEqual { lhs, rhs, out },
+            Lower { lhs, rhs, out },
+            LowerEqual { lhs, rhs, out },
+            Greater { lhs, rhs, out },
+            GreaterEqual { lhs, rhs, out },
+            Not { input, out },
+            Negate { input, out },
+            Abs { input, out },
+            Exp { input, out },
+            FastExp { input, out },
+            Log { input, out },
+            Log1p { input, out },
+            Cos { input, out },
+            Sin { input, out },
+            Tanh { input, out },
+            Sqrt { input, out },
+            Erf { input, out },
+            Erfc { input, out },
+            Expm1 { input, out },
+            Tgamma { input, out },
+            Lgamma { input, out },
+            Recip { input, out },
+            Round { input, out },
+            Floor { input, out },
+            Ceil { input, out },
+            Bitcast { input, out },
+            Fma { a, b, c, out },
+            ExtractBits {
+                input,
+                offset,
+                count,
+                out
+            },
+            InsertBits {
+                base,
+                insert,
+                offset,
+                count,
+                out
+            },
+            Clamp {
+                input,
+                min_value,
+                max_value,
+                out
+            },
+            Select {
+                cond,
+                then,
+                or_else,
+                out
+            },
+        ]
+    )
+}
+
+/// Whether an instruction that isn't split writes wide items itself.
+fn handles_wide_items(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::DeclareVariable { .. }
+        | Instruction::VecInit { .. }
+        | Instruction::MatrixMul { .. }
+        | Instruction::Transpose { .. }
+        | Instruction::Determinant { .. }
+        | Instruction::Length { .. }
+        | Instruction::Slice { .. }
+        | Instruction::Call { .. } => true,
+        // Whole values are copied, which doesn't work for the packed elements of storage buffers.
+        Instruction::Copy { input, out, .. } | Instruction::CopyBulk { input, out, .. } => {
+            !is_packed(input) && !is_packed(out)
+        }
+        _ => false,
+    }
+}
+
+/// The variables read or written by an instruction, without the ones of nested instructions
+/// which are split on their own.
+fn variables(instruction: &Instruction) -> Vec<&Variable> {
+    match instruction {
+        Instruction::Max { lhs, rhs, out }
+        | Instruction::Min { lhs, rhs, out }
+        | Instruction::Add { lhs, rhs, out }
+        | Instruction::Index { lhs, rhs, out }
+        | Instruction::IndexAssign { lhs, rhs, out }
+        | Instruction::Modulo { lhs, rhs, out }
+        | Instruction::Sub { lhs, rhs, out }
+        | Instruction::Mul { lhs, rhs, out }
+        | Instruction::Div { lhs, rhs, out }
+        | Instruction::Powf { lhs, rhs, out }
+        | Instruction::FastPowf { lhs, rhs, out }
+        | Instruction::Equal { lhs, rhs, out }
+        | Instruction::Lower { lhs, rhs, out }
+        | Instruction::Greater { lhs, rhs, out }
+        | Instruction::LowerEqual { lhs, rhs, out }
+        | Instruction::GreaterEqual { lhs, rhs, out }
+        | Instruction::NotEqual { lhs, rhs, out }
+        | Instruction::And { lhs, rhs, out }
+        | Instruction::Or { lhs, rhs, out }
+        | Instruction::BitwiseOr { lhs, rhs, out }
+        | Instruction::BitwiseAnd { lhs, rhs, out }
+        | Instruction::BitwiseXor { lhs, rhs, out }
+        | Instruction::ShiftLeft { lhs, rhs, out }
+        | Instruction::ShiftRight { lhs, rhs, out }
+        | Instruction::Remainder { lhs, rhs, out }
+        | Instruction::SaturatingAdd { lhs, rhs, out }
+        | Instruction::SaturatingSub { lhs, rhs, out }
+        | Instruction::RotateLeft { lhs, rhs, out }
+        | Instruction::RotateRight { lhs, rhs, out }
+        | Instruction::AtomicSwap { lhs, rhs, out }
+        | Instruction::AtomicAdd { lhs, rhs, out }
+        | Instruction::AtomicSub { lhs, rhs, out }
+        | Instruction::AtomicMax { lhs, rhs, out }
+        | Instruction::AtomicMin { lhs, rhs, out }
+        | Instruction::AtomicAnd { lhs, rhs, out }
+        | Instruction::AtomicOr { lhs, rhs, out }
+        | Instruction::AtomicXor { lhs, rhs, out }
+        | Instruction::Dot { lhs, rhs, out }
+        | Instruction::MatrixMul { lhs, rhs, out } => vec![lhs, rhs, out],
+        Instruction::Assign { input, out }
+        | Instruction::Abs { input, out }
+        | Instruction::Exp { input, out }
+        | Instruction::FastExp { input, out }
+        | Instruction::Log { input, out }
+        | Instruction::Log1p { input, out }
+        | Instruction::Cos { input, out }
+        | Instruction::Sin { input, out }
+        | Instruction::Tanh { input, out }
+        | Instruction::Sqrt { input, out }
+        | Instruction::Erf { input, out }
+        | Instruction::Erfc { input, out }
+        | Instruction::Expm1 { input, out }
+        | Instruction::Tgamma { input, out }
+        | Instruction::Lgamma { input, out }
+        | Instruction::Recip { input, out }
+        | Instruction::Not { input, out }
+        | Instruction::Round { input, out }
+        | Instruction::Floor { input, out }
+        | Instruction::Ceil { input, out }
+        | Instruction::Bitcast { input, out }
+        | Instruction::PackF16 { input, out }
+        | Instruction::UnpackF16 { input, out }
+        | Instruction::PackBf16 { input, out }
+        | Instruction::UnpackBf16 { input, out }
+        | Instruction::AtomicLoad { input, out }
+        | Instruction::AtomicStore { input, out }
+        | Instruction::Negate { input, out }
+        | Instruction::Magnitude { input, out }
+        | Instruction::Normalize { input, out }
+        | Instruction::Transpose { input, out }
+        | Instruction::Determinant { input, out }
+        | Instruction::Swizzle { input, out, .. }
+        | Instruction::SwizzleAssign { input, out, .. } => vec![input, out],
+        Instruction::Fma { a, b, c, out } => vec![a, b, c, out],
+        Instruction::Select {
+            cond,
+            then,
+            or_else,
+            out,
+        } => vec![cond, then, or_else, out],
+        Instruction::Clamp {
+            input,
+            min_value,
+            max_value,
+            out,
+        } => vec![input, min_value, max_value, out],
+        Instruction::ExtractBits {
+            input,
+            offset,
+            count,
+            out,
+        } => vec![input, offset, count, out],
+        Instruction::InsertBits {
+            base,
+            insert,
+            offset,
+            count,
+            out,
+        } => vec![base, insert, offset, count, out],
+        Instruction::AtomicCompareExchangeWeak {
+            lhs,
+            cmp,
+            value,
+            out,
+        } => vec![lhs, cmp, value, out],
+        Instruction::Copy {
+            input,
+            in_index,
+            out,
+            out_index,
+        }
+        | Instruction::CopyBulk {
+            input,
+            in_index,
+            out,
+            out_index,
+            ..
+        } => vec![input, in_index, out, out_index],
+        Instruction::Slice {
+            input,
+            start,
+            end,
+            out,
+        } => vec![input, start, end, out],
+        Instruction::Stride { dim, out, .. } | Instruction::Shape { dim, out, .. } => {
+            vec![dim, out]
+        }
+        Instruction::Length { var, out } => vec![var, out],
+        Instruction::DeclareVariable { var } => vec![var],
+        Instruction::VecInit { inputs, out } => inputs.iter().chain([out]).collect(),
+        Instruction::Call { args, out, .. } => args.iter().chain(out).collect(),
+        Instruction::If { cond, .. } | Instruction::IfElse { cond, .. } => vec![cond],
+        Instruction::Switch { value, cases, .. } => [value]
+            .into_iter()
+            .chain(cases.iter().map(|(case, _)| case))
+            .collect(),
+        Instruction::RangeLoop {
+            i,
+            start,
+            end,
+            step,
+            ..
+        } => [i, start, end].into_iter().chain(step).collect(),
+        Instruction::Subgroup(subgroup) => match subgroup {
+            Subgroup::Elect { out } => vec![out],
+            Subgroup::Broadcast { lhs, rhs, out } => vec![lhs, rhs, out],
+            Subgroup::All { input, out }
+            | Subgroup::Any { input, out }
+            | Subgroup::Sum { input, out }
+            | Subgroup::Prod { input, out }
+            | Subgroup::Min { input, out }
+            | Subgroup::Max { input, out } => vec![input, out],
+        },
+        Instruction::Loop { .. }
+        | Instruction::Return
+        | Instruction::Break
+        | Instruction::Continue
+        | Instruction::Comment { .. }
+        | Instruction::WorkgroupBarrier
+        | Instruction::StorageBarrier => Vec::new(),
+    }
+}
+
+/// Local bindings are declared with `let`, which can't be assigned part by part, so wide bindings
+/// are declared as variables before their parts are written.
+fn declare_binding(out: &Variable) -> Vec<Instruction> {
+    match out {
+        Variable::LocalBinding { .. } if out.item().is_wide() => {
+            vec![Instruction::DeclareVariable { var: out.clone() }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The part `k` of a wide value, other values being broadcasted to every part.
+fn part(var: &Variable, k: usize) -> Variable {
+    match var.item() {
+        Item::Wide(elem, _) => part_expr(format!("{var}[{k}]"), elem),
+        _ => var.clone(),
+    }
+}
+
+fn part_expr(name: String, elem: Elem) -> Variable {
+    Variable::Named {
+        name,
+        item: Item::Vec4(elem),
+        is_array: false,
+    }
+}
+
+fn index_in_part(index: &Variable) -> Variable {
+    Variable::Named {
+        name: format!("{index} % 4u"),
+        item: Item::Scalar(Elem::U32),
+        is_array: false,
+    }
+}
+
+fn scalar_expr(name: String, item: &Item) -> Variable {
+    Variable::Named {
+        name,
+        item: Item::Scalar(*item.elem()),
+        is_array: false,
+    }
+}
+
+/// Whether the variable is an array of a storage buffer holding padded items as packed scalars.
+fn is_packed(var: &Variable) -> bool {
+    match var {
+        Variable::GlobalInputArray(..) | Variable::GlobalOutputArray(..) => var.item().is_padded(),
+        Variable::Slice { item, .. } => {
+            assert!(
+                !item.is_padded(),
+                "Slices of lines of {} elements aren't supported by WGSL",
+                item.vectorization_factor()
+            );
+            false
+        }
+        _ => false,
+    }
+}
+
+/// The element `e` of the packed item of a storage buffer at the given index.
+fn packed_element(array: &Variable, index: &Variable, e: usize) -> String {
+    let size = array.item().vectorization_factor();
+    format!("{array}[{index} * {size}u + {e}u]")
+}
+
+/// The element of an array at the given index.
+fn element(array: &Variable, index: &Variable) -> String {
+    match array {
+        Variable::Slice { .. } => format!("(*{array}_ptr)[{index} + {array}_offset]"),
+        _ => format!("{array}[{index}]"),
+    }
+}
+
+fn is_array(var: &Variable) -> bool {
+    match var {
+        Variable::GlobalInputArray(..)
+        | Variable::GlobalOutputArray(..)
+        | Variable::SharedMemory(..)
+        | Variable::ConstantArray(..)
+        | Variable::LocalArray(..)
+        | Variable::Slice { .. } => true,
+        Variable::Named { is_array, .. } => *is_array,
+        _ => false,
+    }
+}
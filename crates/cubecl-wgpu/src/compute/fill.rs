@@ -0,0 +1,60 @@
+use wgpu::ComputePipeline;
+
+const WORKGROUP_SIZE: u32 = 256;
+/// The most workgroups dispatched along a dimension.
+const MAX_WORKGROUPS: u32 = 65535;
+
+/// Repeats the words of the pattern over the output, indexed along two dimensions of workgroups
+/// so that large buffers don't exceed the dispatch limits.
+const FILL_SHADER: &str = r#"
+@group(0) @binding(0)
+var<storage, read_write> output: array<u32>;
+
+@group(0) @binding(1)
+var<storage, read> pattern: array<u32>;
+
+@compute @workgroup_size(256, 1, 1)
+fn main(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    let index = global_id.y * num_workgroups.x * 256u + global_id.x;
+    if index < arrayLength(&output) {
+        output[index] = pattern[index % arrayLength(&pattern)];
+    }
+}
+"#;
+
+pub(crate) fn create_fill_pipeline(device: &wgpu::Device) -> ComputePipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("fill"),
+        source: wgpu::ShaderSource::Wgsl(FILL_SHADER.into()),
+    });
+
+    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("fill"),
+        layout: None,
+        module: &module,
+        entry_point: "main",
+        compilation_options: Default::default(),
+        cache: None,
+    })
+}
+
+/// The pattern repeated until it spans a whole number of words.
+pub(crate) fn word_pattern(pattern: &[u8]) -> Vec<u8> {
+    let mut bytes = pattern.to_vec();
+    while bytes.len() % 4 != 0 {
+        bytes.extend_from_slice(pattern);
+    }
+    bytes
+}
+
+/// The workgroups to dispatch to fill `words` words.
+pub(crate) fn fill_workgroups(words: u64) -> (u32, u32) {
+    let workgroups = words.div_ceil(WORKGROUP_SIZE as u64);
+    let x = Ord::min(workgroups, MAX_WORKGROUPS as u64);
+    let y = workgroups.div_ceil(x.max(1));
+
+    (x as u32, y as u32)
+}
@@ -1,3 +1,5 @@
+mod fill;
+mod pipeline;
 pub(super) mod poll;
 mod server;
 mod storage;
@@ -0,0 +1,65 @@
+use std::sync::RwLock;
+
+use alloc::sync::Arc;
+use core::hash::BuildHasher;
+use cubecl_core::KernelId;
+use hashbrown::{hash_map::DefaultHashBuilder, HashMap};
+use wgpu::ComputePipeline;
+
+/// The number of shards of the cache, so the kernels of different shards are looked up without
+/// contending on the same lock.
+const SHARDS: usize = 16;
+
+/// The compiled pipelines of a server, by kernel.
+///
+/// Looking up a pipeline only takes a read lock on the shard of its kernel, and the shard is only
+/// locked exclusively while a pipeline is inserted or evicted, which happens once per kernel
+/// variant.
+#[derive(Debug, Default)]
+pub(crate) struct PipelineCache {
+    shards: [RwLock<HashMap<KernelId, Arc<ComputePipeline>>>; SHARDS],
+    hasher: DefaultHashBuilder,
+}
+
+impl PipelineCache {
+    /// The pipeline of the kernel, when it was already compiled.
+    pub(crate) fn get(&self, kernel_id: &KernelId) -> Option<Arc<ComputePipeline>> {
+        self.shard(kernel_id)
+            .read()
+            .unwrap()
+            .get(kernel_id)
+            .cloned()
+    }
+
+    /// Cache the compiled pipeline of the kernel.
+    pub(crate) fn insert(&self, kernel_id: KernelId, pipeline: Arc<ComputePipeline>) {
+        self.shard(&kernel_id)
+            .write()
+            .unwrap()
+            .insert(kernel_id, pipeline);
+    }
+
+    /// Remove every variant of the kernel, compiled with a different mode or shared memory size.
+    pub(crate) fn evict(&self, kernel_id: &KernelId) {
+        // The variants hash differently, so they can be in any shard.
+        for shard in self.shards.iter() {
+            shard
+                .write()
+                .unwrap()
+                .retain(|id, _| !id.is_variant_of(kernel_id));
+        }
+    }
+
+    /// Remove every pipeline of the kernels with the name, whatever their variant.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn evict_named(&self, name: &str) {
+        for shard in self.shards.iter() {
+            shard.write().unwrap().retain(|id, _| id.name() != name);
+        }
+    }
+
+    fn shard(&self, kernel_id: &KernelId) -> &RwLock<HashMap<KernelId, Arc<ComputePipeline>>> {
+        let hash = self.hasher.hash_one(kernel_id);
+        &self.shards[hash as usize % SHARDS]
+    }
+}
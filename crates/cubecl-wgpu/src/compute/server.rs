@@ -1,21 +1,37 @@
-use std::{future::Future, marker::PhantomData, num::NonZero, pin::Pin, time::Duration};
+use std::{
+    future::Future, marker::PhantomData, num::NonZero, panic::AssertUnwindSafe, pin::Pin,
+    sync::Mutex, time::Duration,
+};
 
+use super::fill::{create_fill_pipeline, fill_workgroups, word_pattern};
+use super::pipeline::PipelineCache;
 use super::poll::WgpuPoll;
 use super::WgpuStorage;
 use crate::compiler::base::WgpuCompiler;
 use alloc::sync::Arc;
-use cubecl_common::future;
+use cubecl_common::reader;
 use cubecl_core::{compute::DebugInformation, prelude::*, server::Handle, Feature, KernelId};
+#[cfg(not(target_family = "wasm"))]
+use cubecl_runtime::overrides::KernelOverrides;
 use cubecl_runtime::{
     debug::{DebugLogger, ProfileLevel},
-    memory_management::{MemoryHandle, MemoryLock, MemoryManagement},
-    server::{self, ComputeServer},
+    memory_management::{
+        MemoryHandle, MemoryLock, MemoryManagement, OutOfMemoryError, StagingConfiguration,
+    },
+    server::{self, ComputeServer, DeviceLost},
     storage::{BindingResource, ComputeStorage},
     ExecutionMode, TimestampsError, TimestampsResult,
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use web_time::Instant;
-use wgpu::{CommandEncoder, ComputePass, ComputePipeline, QuerySet, QuerySetDescriptor, QueryType};
+use wgpu::{
+    util::{DeviceExt, StagingBelt},
+    CommandEncoder, ComputePass, ComputePipeline, QuerySet, QuerySetDescriptor, QueryType,
+};
+
+/// The stream of the [interactive](server::Priority::Interactive) tasks, submitted without
+/// batching.
+const INTERACTIVE_STREAM: server::Stream = server::Stream { id: 1 };
 
 /// Wgpu compute server.
 #[derive(Debug)]
@@ -26,16 +42,70 @@ pub struct WgpuServer<C: WgpuCompiler> {
     encoder: CommandEncoder,
     current_pass: Option<ComputePass<'static>>,
     tasks_count: usize,
-    pipelines: HashMap<KernelId, Arc<ComputePipeline>>,
+    pipelines: PipelineCache,
+    /// The pipeline of the fills, created on the first fill.
+    fill_pipeline: Option<Arc<ComputePipeline>>,
     tasks_max: usize,
     logger: DebugLogger,
     poll: WgpuPoll,
     storage_locked: MemoryLock,
+    /// The submissions of the asynchronous uploads since the last sync.
+    fences: HashMap<u64, wgpu::SubmissionIndex>,
+    fence_count: u64,
     duration_profiled: Option<Duration>,
     timestamps: KernelTimestamps,
+    /// The staging memory of the uploads, kept between submissions when staging is pinned.
+    staging_belt: Option<StagingBelt>,
+    /// The staging buffers of the reads, kept between reads when staging is pinned.
+    read_staging: Option<ReadStaging>,
+    /// The sources overridden during development.
+    #[cfg(not(target_family = "wasm"))]
+    overrides: Option<KernelOverrides>,
+    /// The resources of the bind group of the dispatch, reused so dispatching a compiled kernel
+    /// doesn't allocate.
+    bind_resources: Vec<BindingResource<Self>>,
+    /// The entries of the bind group of the dispatch, empty between dispatches.
+    bind_entries: Vec<wgpu::BindGroupEntry<'static>>,
+    /// Set by the device lost callback of the device.
+    lost: Arc<Mutex<Option<DeviceLost>>>,
+    recovery: Option<DeviceRecovery<C>>,
     _compiler: PhantomData<C>,
 }
 
+/// Creates the server again on a new device, once its device is lost.
+struct DeviceRecovery<C: WgpuCompiler>(Box<dyn Fn() -> WgpuServer<C> + Send>);
+
+impl<C: WgpuCompiler> core::fmt::Debug for DeviceRecovery<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("DeviceRecovery")
+    }
+}
+
+#[derive(Debug)]
+struct ReadStaging {
+    chunk_size: u64,
+    free: Arc<Mutex<Vec<wgpu::Buffer>>>,
+}
+
+impl ReadStaging {
+    fn reserve(&self, device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+        let mut free = self.free.lock().unwrap();
+        match free.iter().position(|buffer| buffer.size() >= size) {
+            Some(index) => free.swap_remove(index),
+            None => create_read_buffer(device, u64::max(size, self.chunk_size)),
+        }
+    }
+}
+
+fn create_read_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
 #[derive(Debug)]
 enum KernelTimestamps {
     Native { query_set: QuerySet, init: bool },
@@ -72,6 +142,72 @@ impl KernelTimestamps {
     }
 }
 
+/// Empty the entries of a bind group, keeping their allocation for entries of another lifetime.
+fn recycle_entries<'a>(
+    mut entries: Vec<wgpu::BindGroupEntry<'_>>,
+) -> Vec<wgpu::BindGroupEntry<'a>> {
+    entries.clear();
+    // Collecting an empty vector into elements of the same layout reuses its allocation.
+    entries.into_iter().map(|_| unreachable!()).collect()
+}
+
+/// The id of the pipeline of a kernel, which differs between the execution modes and sizes of
+/// dynamic shared memory.
+fn pipeline_id<C: WgpuCompiler>(
+    kernel: &<WgpuServer<C> as ComputeServer>::Kernel,
+    mode: ExecutionMode,
+) -> KernelId {
+    let mut kernel_id = kernel.id();
+    kernel_id.mode(mode);
+
+    // The size of the dynamic shared memory is baked into the shader, so each size is
+    // compiled into its own pipeline variant.
+    let dynamic_shared_memory = kernel.dynamic_shared_memory();
+    if dynamic_shared_memory > 0 {
+        kernel_id.dynamic_shared_memory(dynamic_shared_memory);
+    }
+
+    kernel_id
+}
+
+fn compile_pipeline<C: WgpuCompiler>(
+    device: &wgpu::Device,
+    kernel: <WgpuServer<C> as ComputeServer>::Kernel,
+    mode: ExecutionMode,
+    kernel_id: &KernelId,
+    logger: Option<&mut DebugLogger>,
+    #[cfg(not(target_family = "wasm"))] overrides: Option<&mut KernelOverrides>,
+) -> Arc<ComputePipeline> {
+    let _span = cubecl_runtime::trace_span!("pipeline", kernel = %kernel_id.name());
+    // Checked kernels never read what previous workgroups left in the workgroup memory.
+    let zero_initialize_workgroup_memory =
+        matches!(mode, ExecutionMode::Checked) || kernel.zero_initialize_shared_memory();
+    let logger = logger.filter(|logger| logger.is_activated());
+    let cost = logger.as_ref().and_then(|_| kernel.cost());
+    let mut compile = <C as WgpuCompiler>::compile(device, kernel, mode);
+    #[cfg(not(target_family = "wasm"))]
+    if let Some(source) =
+        overrides.and_then(|overrides| overrides.source(&kernel_id.name(), &compile.source))
+    {
+        compile.source = source;
+    }
+
+    let compile = match logger {
+        Some(logger) => {
+            compile.debug_info = Some(DebugInformation::new("wgsl", kernel_id.clone(), cost));
+            logger.debug(compile)
+        }
+        None => compile,
+    };
+    C::create_pipeline(
+        device,
+        compile,
+        mode,
+        zero_initialize_workgroup_memory,
+        &kernel_id.name(),
+    )
+}
+
 fn create_encoder(device: &wgpu::Device) -> CommandEncoder {
     device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
         label: Some("CubeCL Command Encoder"),
@@ -85,6 +221,7 @@ impl<C: WgpuCompiler> WgpuServer<C> {
         device: Arc<wgpu::Device>,
         queue: Arc<wgpu::Queue>,
         tasks_max: usize,
+        staging_config: StagingConfiguration,
     ) -> Self {
         let logger = DebugLogger::default();
         let mut timestamps = KernelTimestamps::Disabled;
@@ -93,6 +230,24 @@ impl<C: WgpuCompiler> WgpuServer<C> {
             timestamps.enable(&device);
         }
 
+        let lost = Arc::new(Mutex::new(None));
+        let lost_callback = lost.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            // The device is dropped along with the server, for instance when recovering.
+            if matches!(
+                reason,
+                wgpu::DeviceLostReason::Dropped | wgpu::DeviceLostReason::ReplacedCallback
+            ) {
+                return;
+            }
+            let reason = match message.is_empty() {
+                true => format!("{reason:?}"),
+                false => message,
+            };
+            log::error!("The wgpu device was lost: {reason}");
+            *lost_callback.lock().unwrap() = Some(DeviceLost { reason });
+        });
+
         Self {
             memory_management,
             device: device.clone(),
@@ -101,42 +256,82 @@ impl<C: WgpuCompiler> WgpuServer<C> {
             current_pass: None,
             tasks_count: 0,
             storage_locked: MemoryLock::default(),
-            pipelines: HashMap::new(),
+            fences: HashMap::new(),
+            fence_count: 0,
+            pipelines: PipelineCache::default(),
+            fill_pipeline: None,
             tasks_max,
             logger,
             poll: WgpuPoll::new(device.clone()),
             duration_profiled: None,
             timestamps,
+            staging_belt: match staging_config {
+                StagingConfiguration::Pinned { chunk_size } => Some(StagingBelt::new(chunk_size)),
+                StagingConfiguration::Pageable => None,
+            },
+            read_staging: match staging_config {
+                StagingConfiguration::Pinned { chunk_size } => Some(ReadStaging {
+                    chunk_size,
+                    free: Arc::new(Mutex::new(Vec::new())),
+                }),
+                StagingConfiguration::Pageable => None,
+            },
+            #[cfg(not(target_family = "wasm"))]
+            overrides: C::SOURCE_EXTENSION.and_then(KernelOverrides::from_env),
+            bind_resources: Vec::new(),
+            bind_entries: Vec::new(),
+            lost,
+            recovery: None,
             _compiler: PhantomData,
         }
     }
 
+    /// Let the server [recover](ComputeServer::recover) from the loss of its device by replacing
+    /// itself with the server created by `recovery`.
+    pub(crate) fn with_recovery(mut self, recovery: impl Fn() -> Self + Send + 'static) -> Self {
+        self.recovery = Some(DeviceRecovery(Box::new(recovery)));
+        self
+    }
+
     fn pipeline(
         &mut self,
         kernel: <Self as ComputeServer>::Kernel,
         mode: ExecutionMode,
     ) -> Arc<ComputePipeline> {
-        let mut kernel_id = kernel.id();
-        kernel_id.mode(mode);
-
-        if let Some(pipeline) = self.pipelines.get(&kernel_id) {
-            return pipeline.clone();
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(overrides) = &mut self.overrides {
+            for name in overrides.changed() {
+                self.pipelines.evict_named(&name);
+            }
         }
 
-        let mut compile = <C as WgpuCompiler>::compile(self, kernel, mode);
-
-        if self.logger.is_activated() {
-            compile.debug_info = Some(DebugInformation::new("wgsl", kernel_id.clone()));
+        let kernel_id = pipeline_id(&kernel, mode);
+        if let Some(pipeline) = self.pipelines.get(&kernel_id) {
+            return pipeline;
         }
 
-        let compile = self.logger.debug(compile);
-        let pipeline = C::create_pipeline(self, compile, mode);
-
-        self.pipelines.insert(kernel_id.clone(), pipeline.clone());
+        let pipeline = compile_pipeline::<C>(
+            &self.device,
+            kernel,
+            mode,
+            &kernel_id,
+            Some(&mut self.logger),
+            #[cfg(not(target_family = "wasm"))]
+            self.overrides.as_mut(),
+        );
+        self.pipelines.insert(kernel_id, pipeline.clone());
 
         pipeline
     }
 
+    /// Stop the operations of a lost device with the error of the loss, instead of a validation
+    /// error of wgpu.
+    fn check_device(&self) {
+        if let Some(lost) = self.lost.lock().unwrap().as_ref() {
+            panic!("{lost}");
+        }
+    }
+
     fn clear_compute_pass(&mut self) {
         self.current_pass = None;
     }
@@ -147,48 +342,96 @@ impl<C: WgpuCompiler> WgpuServer<C> {
         offset: u64,
         size: u64,
     ) -> impl Future<Output = Vec<u8>> + 'static {
-        let staging_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size,
-            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let fut = self.read_wgpu_buffers(&[(buffer, offset, size)]);
+        async move { fut.await.remove(0) }
+    }
 
-        self.encoder
-            .copy_buffer_to_buffer(buffer, offset, &staging_buffer, 0, size);
+    /// Read the `(buffer, offset, size)` ranges with a single submission and a single poll.
+    fn read_wgpu_buffers(
+        &mut self,
+        ranges: &[(&wgpu::Buffer, u64, u64)],
+    ) -> impl Future<Output = Vec<Vec<u8>>> + 'static {
+        self.check_device();
+        let staging_buffers = ranges
+            .iter()
+            .map(|(buffer, offset, size)| {
+                // Copies and mapped ranges have to be 4 bytes aligned, which buffers are padded to.
+                let align = wgpu::COPY_BUFFER_ALIGNMENT;
+                let skip = offset % align;
+                let mapped_size = (skip + size).div_ceil(align) * align;
+                let staging_buffer = match &self.read_staging {
+                    Some(staging) => staging.reserve(&self.device, mapped_size),
+                    None => create_read_buffer(&self.device, mapped_size),
+                };
+
+                self.encoder.copy_buffer_to_buffer(
+                    buffer,
+                    offset - skip,
+                    &staging_buffer,
+                    0,
+                    mapped_size,
+                );
+                (staging_buffer, mapped_size, skip, *size)
+            })
+            .collect::<Vec<_>>();
+        let free = self
+            .read_staging
+            .as_ref()
+            .map(|staging| staging.free.clone());
 
         // Flush all commands to the queue, so GPU gets started on copying to the staging buffer.
         self.flush();
 
-        let (sender, receiver) = async_channel::bounded(1);
-        staging_buffer
-            .slice(..)
-            .map_async(wgpu::MapMode::Read, move |v| {
-                sender
-                    .try_send(v)
-                    .expect("Unable to send buffer slice result to async channel.");
-            });
+        let (sender, receiver) = async_channel::bounded(staging_buffers.len());
+        for (staging_buffer, mapped_size, _, _) in staging_buffers.iter() {
+            let sender = sender.clone();
+            staging_buffer
+                .slice(..*mapped_size)
+                .map_async(wgpu::MapMode::Read, move |v| {
+                    sender
+                        .try_send(v)
+                        .expect("Unable to send buffer slice result to async channel.");
+                });
+        }
         let poll = self.poll.start_polling();
+        let lost = self.lost.clone();
         async move {
-            receiver
-                .recv()
-                .await
-                .expect("Unable to receive buffer slice result.")
-                .expect("Failed to map buffer");
+            for _ in 0..staging_buffers.len() {
+                let mapped = receiver
+                    .recv()
+                    .await
+                    .expect("Unable to receive buffer slice result.");
+                // Mapping fails once the device is lost, which is the error worth reporting.
+                if let Err(err) = mapped {
+                    match lost.lock().unwrap().as_ref() {
+                        Some(lost) => panic!("{lost}"),
+                        None => panic!("Failed to map buffer: {err}"),
+                    }
+                }
+            }
             // Can stop polling now.
             drop(poll);
 
-            let result = {
-                let data = staging_buffer.slice(..).get_mapped_range();
-                bytemuck::cast_slice(&data).to_vec()
-            };
-            staging_buffer.unmap();
-            result
+            staging_buffers
+                .into_iter()
+                .map(|(staging_buffer, mapped_size, skip, size)| {
+                    let result = {
+                        let data = staging_buffer.slice(..mapped_size).get_mapped_range();
+                        bytemuck::cast_slice(&data[skip as usize..(skip + size) as usize]).to_vec()
+                    };
+                    staging_buffer.unmap();
+                    if let Some(free) = &free {
+                        free.lock().unwrap().push(staging_buffer);
+                    }
+                    result
+                })
+                .collect()
         }
     }
 
     fn sync_queue(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
         self.flush();
+        self.fences.clear();
 
         #[cfg(target_family = "wasm")]
         {
@@ -296,6 +539,30 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
     type Storage = WgpuStorage;
     type Feature = Feature;
 
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.id().name()
+    }
+
+    fn device_lost(&mut self) -> Option<DeviceLost> {
+        self.lost.lock().unwrap().clone()
+    }
+
+    fn recover(&mut self) -> Result<(), DeviceLost> {
+        let Some(lost) = self.device_lost() else {
+            return Ok(());
+        };
+        let Some(recovery) = self.recovery.take() else {
+            return Err(lost);
+        };
+
+        log::warn!("{lost}, requesting a new device");
+        // The new server starts with empty pipeline and memory caches.
+        let mut server = (recovery.0)();
+        server.recovery = Some(recovery);
+        *self = server;
+        Ok(())
+    }
+
     fn read(&mut self, binding: server::Binding) -> impl Future<Output = Vec<u8>> + Send + 'static {
         let rb = self.get_resource(binding);
         let resource = rb.resource();
@@ -303,6 +570,25 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         self.read_wgpu_buffer(&resource.buffer, resource.offset(), resource.size())
     }
 
+    fn read_many(
+        &mut self,
+        bindings: Vec<server::Binding>,
+    ) -> impl Future<Output = Vec<Vec<u8>>> + Send + 'static {
+        let resources = bindings
+            .into_iter()
+            .map(|binding| self.get_resource(binding))
+            .collect::<Vec<_>>();
+        let ranges = resources
+            .iter()
+            .map(|binding| {
+                let resource = binding.resource();
+                (&*resource.buffer, resource.offset(), resource.size())
+            })
+            .collect::<Vec<_>>();
+        self.clear_compute_pass();
+        self.read_wgpu_buffers(&ranges)
+    }
+
     fn get_resource(&mut self, binding: server::Binding) -> BindingResource<Self> {
         // Keep track of any buffer that might be used in the wgpu queue, as we cannot copy into them
         // after they have any outstanding compute work. Calling get_resource repeatedly
@@ -350,16 +636,201 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
 
             let resource = self.memory_management.storage().get(&resource_handle);
 
-            // Write to the staging buffer. Next queue submission this will copy the data to the GPU.
-            self.queue
-                .write_buffer_with(&resource.buffer, resource.offset(), len)
-                .expect("Failed to write to staging buffer.")[0..data.len()]
-                .copy_from_slice(data);
+            if self.staging_belt.is_some() {
+                // Copy from the staging belt, recorded after the tasks of the current encoder.
+                self.clear_compute_pass();
+                let staging_belt = self.staging_belt.as_mut().unwrap();
+                staging_belt.write_buffer(
+                    &mut self.encoder,
+                    &resource.buffer,
+                    resource.offset(),
+                    len,
+                    &self.device,
+                )[0..data.len()]
+                    .copy_from_slice(data);
+            } else {
+                // Write to the staging buffer. Next queue submission this will copy the data to the GPU.
+                self.queue
+                    .write_buffer_with(&resource.buffer, resource.offset(), len)
+                    .expect("Failed to write to staging buffer.")[0..data.len()]
+                    .copy_from_slice(data);
+            }
         }
 
         Handle::new(memory, None, None)
     }
 
+    fn binding_size(&mut self, binding: &server::Binding) -> u64 {
+        let handle = self.memory_management.get(binding.memory.clone());
+        handle.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
+
+    /// Buffer copies are done in units of 4 bytes, so the offset and the size of the data have to
+    /// be aligned on them.
+    fn write(&mut self, binding: server::Binding, data: &[u8]) {
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let rb = self.get_resource(binding);
+        let resource = rb.resource();
+        assert!(
+            resource.offset() % align == 0 && data.len() as u64 % align == 0,
+            "Writes have to be aligned to {align} bytes"
+        );
+        let Some(len) = NonZero::new(data.len() as u64) else {
+            return;
+        };
+
+        // The copy is recorded after the tasks of the current encoder, so they don't see the data.
+        self.clear_compute_pass();
+        match self.staging_belt.as_mut() {
+            Some(staging_belt) => {
+                staging_belt
+                    .write_buffer(
+                        &mut self.encoder,
+                        &resource.buffer,
+                        resource.offset(),
+                        len,
+                        &self.device,
+                    )
+                    .copy_from_slice(data);
+            }
+            None => {
+                let staging_buffer =
+                    self.device
+                        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                            label: Some("Write Staging Buffer"),
+                            contents: data,
+                            usage: wgpu::BufferUsages::COPY_SRC,
+                        });
+                self.encoder.copy_buffer_to_buffer(
+                    &staging_buffer,
+                    0,
+                    &resource.buffer,
+                    resource.offset(),
+                    len.get(),
+                );
+            }
+        }
+    }
+
+    /// Buffers are cleared in units of 4 bytes, so the size of the binding is rounded up, staying
+    /// within the buffer.
+    fn clear(&mut self, binding: server::Binding) {
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let rb = self.get_resource(binding);
+        let resource = rb.resource();
+        assert!(
+            resource.offset() % align == 0,
+            "Clears have to be aligned to {align} bytes"
+        );
+        let size = Ord::min(
+            resource.size().next_multiple_of(align),
+            resource.buffer.size() - resource.offset(),
+        );
+
+        // The clear is recorded after the tasks of the current encoder, like writes.
+        self.clear_compute_pass();
+        self.encoder
+            .clear_buffer(&resource.buffer, resource.offset(), Some(size));
+    }
+
+    /// Wgpu exposes a single queue, so the upload is submitted right away on its own instead of
+    /// at the start of the next submission. The copy then runs while the kernels submitted before
+    /// are still executing, and the queue ordering makes the next kernel wait on it.
+    /// The pattern is repeated by a small kernel, so the size of the binding has to be a multiple
+    /// of 4 bytes, like writes.
+    fn fill(&mut self, binding: server::Binding, pattern: &[u8]) {
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        let rb = self.get_resource(binding);
+        let resource = rb.resource();
+        assert!(
+            resource.size() % align == 0,
+            "Fills have to be aligned to {align} bytes"
+        );
+        let words = resource.size() / align;
+        if words == 0 {
+            return;
+        }
+
+        let device = &self.device;
+        let pipeline = self
+            .fill_pipeline
+            .get_or_insert_with(|| Arc::new(create_fill_pipeline(device)))
+            .clone();
+        let pattern = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("fill pattern"),
+                contents: &word_pattern(pattern),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: resource.as_wgpu_bind_resource(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: pattern.as_entire_binding(),
+                },
+            ],
+        });
+
+        // The fill gets its own pass, without timestamps, ordered after the current tasks.
+        self.clear_compute_pass();
+        let (x, y) = fill_workgroups(words);
+        let mut pass = self
+            .encoder
+            .begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("fill"),
+                timestamp_writes: None,
+            });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(x, y, 1);
+        core::mem::drop(pass);
+
+        self.tasks_count += 1;
+        if self.tasks_count >= self.tasks_max {
+            self.flush();
+        }
+    }
+
+    fn create_async(&mut self, data: &[u8]) -> (server::Handle, server::Fence) {
+        let handle = self.create(data);
+        // Uploads from the staging belt are recorded in the encoder, so it has to be submitted.
+        if self.staging_belt.is_some() {
+            self.flush();
+        }
+        let index = self.queue.submit([]);
+
+        let id = self.fence_count;
+        self.fence_count += 1;
+        self.fences.insert(id, index);
+
+        (handle, server::Fence::new(id))
+    }
+
+    fn wait_fence(&mut self, fence: server::Fence) -> impl Future<Output = ()> + Send + 'static {
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = fence;
+            self.sync_queue()
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            // Fences are only removed once synced, so a missing fence is already signaled.
+            if let Some(index) = self.fences.remove(&fence.id) {
+                self.device
+                    .poll(wgpu::MaintainBase::WaitForSubmissionIndex(index));
+            }
+            async {}
+        }
+    }
+
     fn empty(&mut self, size: usize) -> server::Handle {
         server::Handle::new(
             self.memory_management.reserve(size as u64, None),
@@ -368,13 +839,21 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         )
     }
 
+    fn try_empty(&mut self, size: usize) -> Result<server::Handle, OutOfMemoryError> {
+        let memory = self.memory_management.try_reserve(size as u64, None)?;
+        Ok(server::Handle::new(memory, None, None))
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
         count: CubeCount,
         bindings: Vec<server::Binding>,
         mode: ExecutionMode,
+        stream: server::Stream,
     ) {
+        self.check_device();
+
         // Check for any profiling work to be done before execution.
         let profile_level = self.logger.profile_level();
         let profile_info = if profile_level.is_some() {
@@ -385,7 +864,7 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
 
         if profile_level.is_some() {
             let fut = self.sync_queue_elapsed();
-            if let Ok(duration) = future::block_on(fut) {
+            if let Some(Ok(duration)) = reader::try_read_sync(fut) {
                 if let Some(profiled) = &mut self.duration_profiled {
                     *profiled += duration;
                 } else {
@@ -400,23 +879,28 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
 
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
-        let resources: Vec<_> = bindings
-            .iter()
-            .map(|binding| self.get_resource(binding.clone()))
-            .collect();
-        let entries = &resources
-            .iter()
-            .enumerate()
-            .map(|(i, r)| wgpu::BindGroupEntry {
-                binding: i as u32,
-                resource: r.resource().as_wgpu_bind_resource(),
-            })
-            .collect::<Vec<_>>();
+        let mut resources = core::mem::take(&mut self.bind_resources);
+        resources.extend(
+            bindings
+                .iter()
+                .map(|binding| self.get_resource(binding.clone())),
+        );
+        let mut entries = recycle_entries(core::mem::take(&mut self.bind_entries));
+        entries.extend(
+            resources
+                .iter()
+                .enumerate()
+                .map(|(i, r)| wgpu::BindGroupEntry {
+                    binding: i as u32,
+                    resource: r.resource().as_wgpu_bind_resource(),
+                }),
+        );
         let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: None,
             layout: &group_layout,
-            entries,
+            entries: &entries,
         });
+        self.bind_entries = recycle_entries(entries);
 
         // First resolve the dispatch buffer if needed. The weird ordering is because the lifetime of this
         // needs to be longer than the compute pass, so we can't do this just before dispatching.
@@ -470,7 +954,10 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             }
         }
 
-        if self.tasks_count >= self.tasks_max {
+        resources.clear();
+        self.bind_resources = resources;
+
+        if self.tasks_count >= self.tasks_max || stream == INTERACTIVE_STREAM {
             self.flush();
         }
 
@@ -479,7 +966,8 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
             let (name, kernel_id) = profile_info.unwrap();
 
             // Execute the task.
-            if let Ok(duration) = future::block_on(self.sync_queue_elapsed()) {
+            // Profiling is skipped on wasm when the queue can't be synced without blocking.
+            if let Some(Ok(duration)) = reader::try_read_sync(self.sync_queue_elapsed()) {
                 if let Some(profiled) = &mut self.duration_profiled {
                     *profiled += duration;
                 } else {
@@ -487,13 +975,7 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
                 }
 
                 let info = match level {
-                    ProfileLevel::Basic | ProfileLevel::Medium => {
-                        if let Some(val) = name.split("<").next() {
-                            val.split("::").last().unwrap_or(name).to_string()
-                        } else {
-                            name.to_string()
-                        }
-                    }
+                    ProfileLevel::Basic | ProfileLevel::Medium => kernel_id.name(),
                     ProfileLevel::Full => {
                         format!("{name}: {kernel_id} CubeCount {count:?}")
                     }
@@ -503,12 +985,80 @@ impl<C: WgpuCompiler> ComputeServer for WgpuServer<C> {
         }
     }
 
+    fn evict(&mut self, kernel: Self::Kernel) {
+        let kernel_id = kernel.id();
+        // wgpu keeps the pipelines alive until the commands using them are completed.
+        self.pipelines.evict(&kernel_id);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn prepare(&mut self, kernels: Vec<(Self::Kernel, ExecutionMode)>) {
+        // The logged sources are written in order, and the overrides are read by the server.
+        if self.logger.is_activated() || self.overrides.is_some() {
+            for (kernel, mode) in kernels {
+                self.pipeline(kernel, mode);
+            }
+            return;
+        }
+
+        let mut ids = HashSet::new();
+        let kernels = kernels
+            .into_iter()
+            .filter_map(|(kernel, mode)| {
+                let kernel_id = pipeline_id(&kernel, mode);
+                let compiled = self.pipelines.get(&kernel_id).is_some();
+                (!compiled && ids.insert(kernel_id.clone())).then_some((kernel, mode, kernel_id))
+            })
+            .collect::<Vec<_>>();
+        let threads = std::thread::available_parallelism()
+            .map_or(1, usize::from)
+            .min(kernels.len());
+        let queue = Mutex::new(kernels.into_iter());
+        let (device, pipelines) = (&self.device, &self.pipelines);
+
+        // Each pipeline is cached as soon as it's created, the device being shared by the threads.
+        std::thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let next = queue.lock().unwrap().next();
+                    let Some((kernel, mode, kernel_id)) = next else {
+                        break;
+                    };
+
+                    // The kernels failing to compile fail again when executed, where it's handled.
+                    let pipeline = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                        compile_pipeline::<C>(device, kernel, mode, &kernel_id, None, None)
+                    }));
+                    if let Ok(pipeline) = pipeline {
+                        pipelines.insert(kernel_id, pipeline);
+                    }
+                });
+            }
+        });
+    }
+
+    /// The tasks are submitted to a single queue, so the interactive streams are submitted right
+    /// away instead of waiting for their batch to fill. The other priorities use the default
+    /// stream.
+    fn create_stream_with_priority(&mut self, priority: server::Priority) -> server::Stream {
+        match priority {
+            server::Priority::Interactive => INTERACTIVE_STREAM,
+            server::Priority::Normal | server::Priority::Background => server::Stream::default(),
+        }
+    }
+
     fn flush(&mut self) {
         // End the current compute pass.
         self.clear_compute_pass();
         let new_encoder = create_encoder(&self.device);
         let encoder = std::mem::replace(&mut self.encoder, new_encoder);
+        if let Some(staging_belt) = &mut self.staging_belt {
+            staging_belt.finish();
+        }
         self.queue.submit([encoder.finish()]);
+        if let Some(staging_belt) = &mut self.staging_belt {
+            staging_belt.recall();
+        }
 
         self.tasks_count = 0;
         self.storage_locked.clear_locked();
@@ -25,7 +25,10 @@ mod tests {
     pub type TestRuntime = crate::WgpuRuntime<crate::WgslCompiler>;
 
     cubecl_core::testgen_all!();
+    cubecl_fft::testgen_all!();
+    cubecl_image::testgen_all!();
     cubecl_linalg::testgen_all!();
+    cubecl_std::testgen_all!();
 }
 
 #[cfg(all(test, feature = "spirv"))]
@@ -33,5 +36,8 @@ mod tests_spirv {
     pub type TestRuntime = crate::WgpuRuntime<crate::spirv::VkSpirvCompiler>;
 
     cubecl_core::testgen_all!();
+    cubecl_fft::testgen_all!();
+    cubecl_image::testgen_all!();
     cubecl_linalg::testgen_all!();
+    cubecl_std::testgen_all!();
 }
@@ -6,19 +6,22 @@ use crate::{
     AutoGraphicsApi, GraphicsApi, WgpuDevice,
 };
 use alloc::sync::Arc;
+use async_lock::OnceCell;
 use cubecl_common::future;
 use cubecl_core::{Feature, Runtime};
-pub use cubecl_runtime::memory_management::MemoryConfiguration;
-use cubecl_runtime::DeviceProperties;
+pub use cubecl_runtime::memory_management::{MemoryConfiguration, StagingConfiguration};
+use cubecl_runtime::{DeviceProperties, HardwareProperties};
 use cubecl_runtime::{channel::MutexComputeChannel, client::ComputeClient, ComputeRuntime};
 use cubecl_runtime::{
     memory_management::{MemoryDeviceProperties, MemoryManagement},
     storage::ComputeStorage,
 };
+use std::sync::Mutex;
 
 /// Runtime that uses the [wgpu] crate with the wgsl compiler. This is used in the Wgpu backend.
 /// For advanced configuration, use [`init_sync`] to pass in runtime options or to select a
-/// specific graphics API.
+/// specific graphics API. On wasm, the device must be initialized with [`init_async`] before
+/// requesting a client.
 #[derive(Debug)]
 pub struct WgpuRuntime<C: WgpuCompiler = WgslCompiler>(PhantomData<C>);
 
@@ -46,9 +49,21 @@ impl Runtime for WgpuRuntime<WgslCompiler> {
 
     fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
         RUNTIME.client(device, move || {
-            let (adapter, device_wgpu, queue) =
-                future::block_on(create_wgpu_setup::<AutoGraphicsApi, WgslCompiler>(device));
-            create_client(adapter, device_wgpu, queue, RuntimeOptions::default())
+            // Requesting a device can't block on wasm, the client has to be registered upfront.
+            #[cfg(target_family = "wasm")]
+            panic!(
+                "The wgpu device {device:?} isn't initialized, call `init_async` first on wasm."
+            );
+
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let options = RuntimeOptions::default();
+                let (adapter, device_wgpu, queue) =
+                    future::block_on(create_wgpu_setup::<AutoGraphicsApi, WgslCompiler>(
+                        device, &options,
+                    ));
+                create_client(adapter, device_wgpu, queue, options)
+            }
         })
     }
 
@@ -67,6 +82,41 @@ pub struct RuntimeOptions {
     pub tasks_max: usize,
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// Configures the host memory staging the transfers.
+    pub staging_config: StagingConfiguration,
+    /// The backends whose adapters are searched, in order of preference. When empty, only the
+    /// backend of the [graphics API](GraphicsApi) is searched.
+    ///
+    /// Set from a comma-separated list in the `CUBECL_BACKEND` environment variable, e.g.
+    /// `vulkan,gl`.
+    pub backends: Vec<wgpu::Backend>,
+    /// Whether integrated or discrete GPUs are preferred for the
+    /// [best available](WgpuDevice::BestAvailable) device.
+    ///
+    /// Set from the `CUBECL_WGPU_POWER_PREFERENCE` environment variable, either `low-power` or
+    /// `high-performance`.
+    pub power_preference: wgpu::PowerPreference,
+    /// Only select adapters whose name contains this text, ignoring case.
+    ///
+    /// Set from the `CUBECL_WGPU_ADAPTER` environment variable.
+    pub adapter_name: Option<String>,
+    /// Use the fallback adapter of wgpu, a software rasterizer such as lavapipe or llvmpipe, when
+    /// no adapter of the device is found. This lets machines without a GPU, like CI containers,
+    /// run kernels.
+    ///
+    /// Set when the `CUBECL_WGPU_FALLBACK_ADAPTER` environment variable is `1` or `true`.
+    pub fallback_adapter: bool,
+    /// Share the device and the memory pools with the other clients of the same
+    /// [device](WgpuDevice), instead of each client reserving its own pools, e.g. for several
+    /// models served by one process.
+    ///
+    /// The pools are created with the [memory configuration](Self::memory_config) of the first
+    /// client of the device. The clients submit each task right away and upload through
+    /// pageable staging, so the memory released by a client is only reused by another one after
+    /// the work using it is submitted to the shared queue.
+    ///
+    /// Set when the `CUBECL_WGPU_SHARED_MEMORY` environment variable is `1` or `true`.
+    pub shared_memory: bool,
 }
 
 impl Default for RuntimeOptions {
@@ -83,13 +133,76 @@ impl Default for RuntimeOptions {
             Err(_) => DEFAULT_MAX_TASKS,
         };
 
+        let backends = match std::env::var("CUBECL_BACKEND") {
+            Ok(value) => value
+                .split(',')
+                .map(|name| {
+                    parse_backend(name.trim()).expect(
+                        "CUBECL_BACKEND should be a list of vulkan, metal, dx12, gl or webgpu.",
+                    )
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let power_preference = match std::env::var("CUBECL_WGPU_POWER_PREFERENCE") {
+            Ok(value) => match value.to_lowercase().as_str() {
+                "low-power" => wgpu::PowerPreference::LowPower,
+                "high-performance" => wgpu::PowerPreference::HighPerformance,
+                _ => panic!(
+                    "CUBECL_WGPU_POWER_PREFERENCE should be either low-power or high-performance."
+                ),
+            },
+            Err(_) => wgpu::PowerPreference::None,
+        };
+
         Self {
             tasks_max,
             memory_config: MemoryConfiguration::default(),
+            staging_config: StagingConfiguration::default(),
+            backends,
+            power_preference,
+            adapter_name: std::env::var("CUBECL_WGPU_ADAPTER").ok(),
+            fallback_adapter: matches!(
+                std::env::var("CUBECL_WGPU_FALLBACK_ADAPTER").as_deref(),
+                Ok("1" | "true")
+            ),
+            shared_memory: matches!(
+                std::env::var("CUBECL_WGPU_SHARED_MEMORY").as_deref(),
+                Ok("1" | "true")
+            ),
         }
     }
 }
 
+fn backends_mask(backends: &[wgpu::Backend]) -> wgpu::Backends {
+    backends
+        .iter()
+        .fold(wgpu::Backends::empty(), |mask, backend| {
+            mask | (*backend).into()
+        })
+}
+
+/// Whether kernels can run on the adapter, downlevel adapters like some GL ones lacking compute
+/// shaders.
+fn supports_compute(adapter: &wgpu::Adapter) -> bool {
+    adapter
+        .get_downlevel_capabilities()
+        .flags
+        .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+}
+
+fn parse_backend(name: &str) -> Option<wgpu::Backend> {
+    match name.to_lowercase().as_str() {
+        "vulkan" => Some(wgpu::Backend::Vulkan),
+        "metal" => Some(wgpu::Backend::Metal),
+        "dx12" => Some(wgpu::Backend::Dx12),
+        "gl" | "opengl" => Some(wgpu::Backend::Gl),
+        "webgpu" => Some(wgpu::Backend::BrowserWebGpu),
+        _ => None,
+    }
+}
+
 pub fn init_existing_device(
     adapter: Arc<wgpu::Adapter>,
     device: Arc<wgpu::Device>,
@@ -110,22 +223,107 @@ pub fn init_sync<G: GraphicsApi>(device: &WgpuDevice, options: RuntimeOptions) {
 
 /// Like [`init_sync`], but async, necessary for wasm.
 pub async fn init_async<G: GraphicsApi>(device: &WgpuDevice, options: RuntimeOptions) {
-    let (adapter, device_wgpu, queue) = create_wgpu_setup::<G, WgslCompiler>(device).await;
+    let (adapter, device_wgpu, queue) =
+        create_wgpu_setup::<G, WgslCompiler>(device, &options).await;
     let client = create_client(adapter, device_wgpu, queue, options);
     RUNTIME.register(device, client)
 }
 
 pub async fn create_wgpu_setup<G: GraphicsApi, C: WgpuCompiler>(
     device: &WgpuDevice,
+    options: &RuntimeOptions,
 ) -> (Arc<wgpu::Adapter>, Arc<wgpu::Device>, Arc<wgpu::Queue>) {
-    let (device_wgpu, queue, adapter) = select_device::<G, C>(device).await;
+    let (device_wgpu, queue, adapter) = match options.shared_memory {
+        true => select_shared_device::<G, C>(device, options).await,
+        false => {
+            let (device_wgpu, queue, adapter) = select_device::<G, C>(device, options).await;
+            (Arc::new(device_wgpu), Arc::new(queue), adapter)
+        }
+    };
 
     log::info!(
         "Created wgpu compute server on device {:?} => {:?}",
         device,
         adapter.get_info()
     );
-    (Arc::new(adapter), Arc::new(device_wgpu), Arc::new(queue))
+    (Arc::new(adapter), device_wgpu, queue)
+}
+
+/// A device shared by the clients of a [device](WgpuDevice), see
+/// [RuntimeOptions::shared_memory].
+struct SharedDevice {
+    device: WgpuDevice,
+    /// The device and its queue, requested by the first client while the others wait for it.
+    wgpu: Arc<OnceCell<(Arc<wgpu::Device>, Arc<wgpu::Queue>)>>,
+    /// The pools of the device, created with the first client.
+    memory_management: Option<MemoryManagement<WgpuStorage>>,
+}
+
+static SHARED_DEVICES: Mutex<Vec<SharedDevice>> = Mutex::new(Vec::new());
+
+/// Like [select_device], but reusing the wgpu device when a client of the same device already
+/// requested it.
+///
+/// Devices are matched by their [id](WgpuDevice) and not by the info of their adapter, which is
+/// the same for identical GPUs.
+async fn select_shared_device<G: GraphicsApi, C: WgpuCompiler>(
+    device: &WgpuDevice,
+    options: &RuntimeOptions,
+) -> (Arc<wgpu::Device>, Arc<wgpu::Queue>, wgpu::Adapter) {
+    #[cfg(target_family = "wasm")]
+    let adapter = select_adapter::<G>(device, options).await;
+
+    #[cfg(not(target_family = "wasm"))]
+    let adapter = select_adapter::<G>(device, options);
+
+    let wgpu = {
+        let mut shared_devices = SHARED_DEVICES.lock().unwrap();
+        match shared_devices
+            .iter()
+            .find(|shared| &shared.device == device)
+        {
+            Some(shared) => shared.wgpu.clone(),
+            None => {
+                let wgpu = Arc::new(OnceCell::new());
+                shared_devices.push(SharedDevice {
+                    device: device.clone(),
+                    wgpu: wgpu.clone(),
+                    memory_management: None,
+                });
+                wgpu
+            }
+        }
+    };
+
+    // The cell is held across the request, so concurrent clients don't each create a device.
+    let (device_wgpu, queue) = wgpu
+        .get_or_init(|| async {
+            let (device_wgpu, queue) = C::request_device(&adapter).await;
+            (Arc::new(device_wgpu), Arc::new(queue))
+        })
+        .await
+        .clone();
+    (device_wgpu, queue, adapter)
+}
+
+/// The memory management of the device, shared with the other clients of the device when it is
+/// a [shared device](select_shared_device).
+fn shared_memory_management(
+    device: &Arc<wgpu::Device>,
+    memory_config: MemoryConfiguration,
+) -> Option<MemoryManagement<WgpuStorage>> {
+    let mut shared_devices = SHARED_DEVICES.lock().unwrap();
+    let shared = shared_devices.iter_mut().find(|shared| {
+        shared
+            .wgpu
+            .get()
+            .is_some_and(|(shared, _)| Arc::ptr_eq(shared, device))
+    })?;
+    let memory_management = shared.memory_management.get_or_insert_with(|| {
+        init_memory_management(device.clone(), memory_properties(device), memory_config)
+            .into_shared()
+    });
+    memory_management.share()
 }
 
 pub fn create_client<C: WgpuCompiler>(
@@ -135,26 +333,75 @@ pub fn create_client<C: WgpuCompiler>(
     options: RuntimeOptions,
 ) -> ComputeClient<WgpuServer<C>, MutexComputeChannel<WgpuServer<C>>> {
     let limits = device_wgpu.limits();
-    let mem_props = MemoryDeviceProperties {
-        max_page_size: limits.max_storage_buffer_binding_size as u64,
-        alignment: WgpuStorage::ALIGNMENT.max(limits.min_storage_buffer_offset_alignment as u64),
+    let mem_props = memory_properties(&device_wgpu);
+
+    let server = match options
+        .shared_memory
+        .then(|| shared_memory_management(&device_wgpu, options.memory_config.clone()))
+        .flatten()
+    {
+        // Submitting each task keeps the order of the work of the clients on the shared queue.
+        Some(memory_management) => WgpuServer::new(
+            memory_management,
+            device_wgpu.clone(),
+            queue,
+            1,
+            StagingConfiguration::Pageable,
+        ),
+        None => create_server::<C>(
+            device_wgpu.clone(),
+            queue,
+            options.memory_config.clone(),
+            options.tasks_max,
+            options.staging_config,
+        ),
+    };
+    // Requesting a device can't block on wasm, so the server can't recover there.
+    #[cfg(not(target_family = "wasm"))]
+    let server = {
+        let adapter = adapter.clone();
+        let memory_config = options.memory_config;
+        let tasks_max = options.tasks_max;
+        let staging_config = options.staging_config;
+        server.with_recovery(move || {
+            let (device, queue) = future::block_on(C::request_device(&adapter));
+            create_server(
+                Arc::new(device),
+                Arc::new(queue),
+                memory_config.clone(),
+                tasks_max,
+                staging_config,
+            )
+        })
     };
-
-    let memory_management = init_memory_management(
-        device_wgpu.clone(),
-        mem_props.clone(),
-        options.memory_config,
-    );
-    let server = WgpuServer::new(
-        memory_management,
-        device_wgpu.clone(),
-        queue,
-        options.tasks_max,
-    );
     let channel = MutexComputeChannel::new(server);
 
     let features = adapter.features();
-    let mut device_props = DeviceProperties::new(&[], mem_props);
+    let hardware_props = HardwareProperties {
+        max_shared_memory_size: limits.max_compute_workgroup_storage_size as usize,
+        max_cube_dim: (
+            limits.max_compute_workgroup_size_x,
+            limits.max_compute_workgroup_size_y,
+            limits.max_compute_workgroup_size_z,
+        ),
+        max_units_per_cube: limits.max_compute_invocations_per_workgroup,
+        max_cube_count: (
+            limits.max_compute_workgroups_per_dimension,
+            limits.max_compute_workgroups_per_dimension,
+            limits.max_compute_workgroups_per_dimension,
+        ),
+        max_bindings: limits.max_storage_buffers_per_shader_stage,
+        max_buffer_size: limits.max_storage_buffer_binding_size as u64,
+        // The subgroup size is only fixed when the adapter reports a single one.
+        subcube_size: (limits.min_subgroup_size != 0
+            && limits.min_subgroup_size == limits.max_subgroup_size)
+            .then_some(limits.min_subgroup_size),
+        num_multiprocessors: None,
+        max_units_per_multiprocessor: None,
+        max_cubes_per_multiprocessor: None,
+        registers_per_multiprocessor: None,
+    };
+    let mut device_props = DeviceProperties::new(&[], mem_props, hardware_props);
     if features.contains(wgpu::Features::SUBGROUP) {
         device_props.register_feature(Feature::Subcube);
     }
@@ -162,15 +409,40 @@ pub fn create_client<C: WgpuCompiler>(
     ComputeClient::new(channel, device_props)
 }
 
-/// Select the wgpu device and queue based on the provided [device](WgpuDevice).
+fn memory_properties(device: &wgpu::Device) -> MemoryDeviceProperties {
+    let limits = device.limits();
+    MemoryDeviceProperties {
+        max_page_size: limits.max_storage_buffer_binding_size as u64,
+        // Scalars are bound as uniforms, so every binding must satisfy both offset alignments.
+        alignment: WgpuStorage::ALIGNMENT
+            .max(limits.min_storage_buffer_offset_alignment as u64)
+            .max(limits.min_uniform_buffer_offset_alignment as u64),
+    }
+}
+
+fn create_server<C: WgpuCompiler>(
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    memory_config: MemoryConfiguration,
+    tasks_max: usize,
+    staging_config: StagingConfiguration,
+) -> WgpuServer<C> {
+    let memory_management =
+        init_memory_management(device.clone(), memory_properties(&device), memory_config);
+    WgpuServer::new(memory_management, device, queue, tasks_max, staging_config)
+}
+
+/// Select the wgpu device and queue based on the provided [device](WgpuDevice) and the adapter
+/// preferences of the [options](RuntimeOptions).
 pub async fn select_device<G: GraphicsApi, C: WgpuCompiler>(
     device: &WgpuDevice,
+    options: &RuntimeOptions,
 ) -> (wgpu::Device, wgpu::Queue, wgpu::Adapter) {
     #[cfg(target_family = "wasm")]
-    let adapter = select_adapter::<G>(device).await;
+    let adapter = select_adapter::<G>(device, options).await;
 
     #[cfg(not(target_family = "wasm"))]
-    let adapter = select_adapter::<G>(device);
+    let adapter = select_adapter::<G>(device, options);
 
     let (device, queue) = C::request_device(&adapter).await;
 
@@ -178,132 +450,188 @@ pub async fn select_device<G: GraphicsApi, C: WgpuCompiler>(
 }
 
 #[cfg(target_family = "wasm")]
-async fn select_adapter<G: GraphicsApi>(_device: &WgpuDevice) -> wgpu::Adapter {
-    let instance = wgpu::Instance::default();
+async fn select_adapter<G: GraphicsApi>(
+    _device: &WgpuDevice,
+    options: &RuntimeOptions,
+) -> wgpu::Adapter {
+    // Adapters can't be enumerated on wasm, so only the preferences of the request are applied.
+    let instance = match options.backends.is_empty() {
+        true => wgpu::Instance::default(),
+        false => wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: backends_mask(&options.backends),
+            ..Default::default()
+        }),
+    };
+    let request = |force_fallback_adapter| {
+        instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: options.power_preference,
+            force_fallback_adapter,
+            compatible_surface: None,
+        })
+    };
 
-    instance
-        .request_adapter(&wgpu::RequestAdapterOptionsBase::default())
-        .await
-        .unwrap()
+    match request(false).await {
+        Some(adapter) => adapter,
+        None if options.fallback_adapter => request(true)
+            .await
+            .expect("No adapter found, even the fallback adapter"),
+        None => panic!("No adapter found"),
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn select_adapter<G: GraphicsApi>(device: &WgpuDevice) -> wgpu::Adapter {
-    use wgpu::DeviceType;
-
-    let instance = wgpu::Instance::default();
-    let mut adapters_other = Vec::new();
-    let mut adapters = Vec::new();
-
-    instance
-        .enumerate_adapters(G::backend().into())
-        .into_iter()
-        .for_each(|adapter| {
-            let device_type = adapter.get_info().device_type;
+fn select_adapter<G: GraphicsApi>(device: &WgpuDevice, options: &RuntimeOptions) -> wgpu::Adapter {
+    let mut found = Vec::new();
+    if let Some(adapter) = find_adapter::<G>(device, options, &mut found) {
+        return adapter;
+    }
 
-            if let DeviceType::Other = device_type {
-                adapters_other.push(adapter);
-                return;
-            }
+    let backends = match options.backends.is_empty() {
+        true => vec![G::backend()],
+        false => options.backends.clone(),
+    };
+    let name = match &options.adapter_name {
+        Some(name) => format!(" named {name:?}"),
+        None => String::new(),
+    };
+    panic!(
+        "No adapter{name} found for device {device:?} with backends {backends:?}, adapters {:?}",
+        found
+    );
+}
 
-            let is_same_type = match device {
-                WgpuDevice::DiscreteGpu(_) => device_type == DeviceType::DiscreteGpu,
-                WgpuDevice::IntegratedGpu(_) => device_type == DeviceType::IntegratedGpu,
-                WgpuDevice::VirtualGpu(_) => device_type == DeviceType::VirtualGpu,
-                WgpuDevice::Cpu => device_type == DeviceType::Cpu,
-                WgpuDevice::BestAvailable => true,
-                WgpuDevice::Existing(_) => {
-                    unreachable!("Cannot select an adapter for an existing device.")
-                }
-            };
-
-            if is_same_type {
-                adapters.push(adapter);
-            }
-        });
+/// The info of the adapter that [init_sync] would select for the device, or `None` when no
+/// adapter matches, without creating the device.
+///
+/// Useful to probe whether wgpu can run kernels on the machine before committing to it.
+#[cfg(not(target_family = "wasm"))]
+pub fn adapter_info<G: GraphicsApi>(
+    device: &WgpuDevice,
+    options: &RuntimeOptions,
+) -> Option<wgpu::AdapterInfo> {
+    find_adapter::<G>(device, options, &mut Vec::new()).map(|adapter| adapter.get_info())
+}
 
-    fn select(
-        num: usize,
-        error: &str,
-        mut adapters: Vec<wgpu::Adapter>,
-        mut adapters_other: Vec<wgpu::Adapter>,
-    ) -> wgpu::Adapter {
-        if adapters.len() <= num {
-            if adapters_other.len() <= num {
-                panic!(
-                    "{}, adapters {:?}, other adapters {:?}",
-                    error,
-                    adapters
-                        .into_iter()
-                        .map(|adapter| adapter.get_info())
-                        .collect::<Vec<_>>(),
-                    adapters_other
-                        .into_iter()
-                        .map(|adapter| adapter.get_info())
-                        .collect::<Vec<_>>(),
-                );
-            }
+/// Find the adapter of the device, collecting the info of the adapters seen along the way.
+#[cfg(not(target_family = "wasm"))]
+fn find_adapter<G: GraphicsApi>(
+    device: &WgpuDevice,
+    options: &RuntimeOptions,
+    found: &mut Vec<wgpu::AdapterInfo>,
+) -> Option<wgpu::Adapter> {
+    let instance = wgpu::Instance::default();
+    let backends = match options.backends.is_empty() {
+        true => vec![G::backend()],
+        false => options.backends.clone(),
+    };
 
-            return adapters_other.remove(num);
+    // The first backend with a matching adapter is used.
+    for backend in backends.iter() {
+        let adapters = instance
+            .enumerate_adapters((*backend).into())
+            .into_iter()
+            .filter(|adapter| match &options.adapter_name {
+                Some(name) => adapter
+                    .get_info()
+                    .name
+                    .to_lowercase()
+                    .contains(&name.to_lowercase()),
+                None => true,
+            })
+            .filter(supports_compute)
+            .collect::<Vec<_>>();
+        found.extend(adapters.iter().map(|adapter| adapter.get_info()));
+
+        if let Some(adapter) = select_adapter_of(device, adapters, options.power_preference) {
+            log::info!("Using adapter {:?}", adapter.get_info());
+            return Some(adapter);
         }
+    }
 
-        adapters.remove(num)
+    if options.fallback_adapter {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: backends_mask(&backends),
+            ..Default::default()
+        });
+        let adapter = future::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: options.power_preference,
+            force_fallback_adapter: true,
+            compatible_surface: None,
+        }));
+
+        if let Some(adapter) = adapter.filter(supports_compute) {
+            log::warn!("Using the fallback adapter {:?}", adapter.get_info());
+            return Some(adapter);
+        }
     }
 
-    let adapter = match device {
-        WgpuDevice::DiscreteGpu(num) => select(
-            *num,
-            "No Discrete GPU device found",
-            adapters,
-            adapters_other,
-        ),
-        WgpuDevice::IntegratedGpu(num) => select(
-            *num,
-            "No Integrated GPU device found",
-            adapters,
-            adapters_other,
-        ),
-        WgpuDevice::VirtualGpu(num) => select(
-            *num,
-            "No Virtual GPU device found",
-            adapters,
-            adapters_other,
-        ),
-        WgpuDevice::Cpu => select(0, "No CPU device found", adapters, adapters_other),
-        WgpuDevice::BestAvailable => {
-            let mut most_performant_adapter = None;
-            let mut current_score = -1;
-
-            adapters
-                .into_iter()
-                .chain(adapters_other)
-                .for_each(|adapter| {
-                    let info = adapter.get_info();
-                    let score = match info.device_type {
-                        DeviceType::DiscreteGpu => 5,
-                        DeviceType::Other => 4, // Let's be optimistic with the Other device, it's
-                        // often a Discrete Gpu.
-                        DeviceType::IntegratedGpu => 3,
-                        DeviceType::VirtualGpu => 2,
-                        DeviceType::Cpu => 1,
-                    };
-
-                    if score > current_score {
-                        most_performant_adapter = Some(adapter);
-                        current_score = score;
-                    }
-                });
+    None
+}
 
-            if let Some(adapter) = most_performant_adapter {
-                adapter
-            } else {
-                panic!("No adapter found for graphics API {:?}", G::default());
+/// Select the adapter of the device among the adapters of a backend.
+#[cfg(not(target_family = "wasm"))]
+fn select_adapter_of(
+    device: &WgpuDevice,
+    adapters: Vec<wgpu::Adapter>,
+    power_preference: wgpu::PowerPreference,
+) -> Option<wgpu::Adapter> {
+    use wgpu::DeviceType;
+
+    let (mut adapters_other, mut adapters): (Vec<_>, Vec<_>) = adapters
+        .into_iter()
+        .partition(|adapter| adapter.get_info().device_type == DeviceType::Other);
+
+    adapters.retain(|adapter| {
+        let device_type = adapter.get_info().device_type;
+        match device {
+            WgpuDevice::DiscreteGpu(_) => device_type == DeviceType::DiscreteGpu,
+            WgpuDevice::IntegratedGpu(_) => device_type == DeviceType::IntegratedGpu,
+            WgpuDevice::VirtualGpu(_) => device_type == DeviceType::VirtualGpu,
+            WgpuDevice::Cpu => device_type == DeviceType::Cpu,
+            WgpuDevice::BestAvailable => true,
+            WgpuDevice::Existing(_) => {
+                unreachable!("Cannot select an adapter for an existing device.")
             }
         }
-        WgpuDevice::Existing(_) => unreachable!("Cannot select an adapter for an existing device."),
+    });
+
+    let mut select = |num: usize| {
+        if num < adapters.len() {
+            Some(adapters.remove(num))
+        } else if num < adapters_other.len() {
+            Some(adapters_other.remove(num))
+        } else {
+            None
+        }
     };
 
-    log::info!("Using adapter {:?}", adapter.get_info());
-
-    adapter
+    match device {
+        WgpuDevice::DiscreteGpu(num)
+        | WgpuDevice::IntegratedGpu(num)
+        | WgpuDevice::VirtualGpu(num) => select(*num),
+        WgpuDevice::Cpu => select(0),
+        WgpuDevice::BestAvailable => adapters
+            .into_iter()
+            .chain(adapters_other)
+            .enumerate()
+            // The first adapter wins among the ones with the same score.
+            .max_by_key(|(index, adapter)| {
+                let score = match adapter.get_info().device_type {
+                    DeviceType::IntegratedGpu
+                        if power_preference == wgpu::PowerPreference::LowPower =>
+                    {
+                        6
+                    }
+                    DeviceType::DiscreteGpu => 5,
+                    DeviceType::Other => 4, // Let's be optimistic with the Other device, it's
+                    // often a Discrete Gpu.
+                    DeviceType::IntegratedGpu => 3,
+                    DeviceType::VirtualGpu => 2,
+                    DeviceType::Cpu => 1,
+                };
+                (score, usize::MAX - index)
+            })
+            .map(|(_, adapter)| adapter),
+        WgpuDevice::Existing(_) => unreachable!("Cannot select an adapter for an existing device."),
+    }
 }
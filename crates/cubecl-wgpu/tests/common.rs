@@ -2,7 +2,7 @@ use cubecl_core::{
     client::ComputeClient,
     prelude::{ArrayArg, TensorArg},
     server::Handle,
-    Compiler, ExecutionMode, Kernel, Runtime,
+    CompilationOptions, Compiler, ExecutionMode, Kernel, Runtime,
 };
 use cubecl_wgpu::{WgpuDevice, WgpuRuntime, WgslCompiler};
 
@@ -38,6 +38,7 @@ pub fn array(tensor: &Handle) -> ArrayArg<'_, TestRuntime> {
 pub fn compile(kernel: impl Kernel) -> String {
     <<TestRuntime as Runtime>::Compiler as Compiler>::compile(
         kernel.define(),
+        &CompilationOptions::default(),
         ExecutionMode::Checked,
     )
     .to_string()
@@ -0,0 +1,331 @@
+use std::num::NonZero;
+
+use cubecl_common::rand::{Rng, SeedableRng, StdRng};
+use cubecl_core::{
+    ir::{
+        BinaryOperator, Branch, ConstantScalarValue, Elem, FloatKind, If, IfElse, IntKind, Item,
+        Loop, Operator, RangeLoop, Scope, Select, UnaryOperator, Variable,
+    },
+    prelude::{KernelBuilder, KernelDefinition},
+    CubeDim, KernelSettings,
+};
+
+/// The deepest nesting of branches and loops.
+const MAX_DEPTH: u8 = 3;
+const MAX_STATEMENTS: usize = 8;
+const LOOP_COUNT: u32 = 4;
+
+const ELEMS: [Elem; 3] = [
+    Elem::Float(FloatKind::F32),
+    Elem::Int(IntKind::I32),
+    Elem::UInt,
+];
+const VECTORIZATIONS: [u8; 5] = [1, 2, 3, 4, 8];
+
+/// Generate a random kernel reading from its inputs and writing to its output.
+///
+/// The kernel is valid IR, as the frontend could produce it: operands share the item of their
+/// output, conditions are scalar booleans, and loops always terminate.
+pub fn random_kernel(seed: u64) -> KernelDefinition {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut builder = KernelBuilder::default();
+
+    let inputs = (0..rng.gen_range(1..=2))
+        .map(|_| {
+            let item = random_item(&mut rng);
+            (builder.input_array(item).into(), item)
+        })
+        .collect();
+    let output_item = random_item(&mut rng);
+    let output = builder.output_array(output_item).into();
+    let scalars = (0..rng.gen_range(0..=2))
+        .map(|_| builder.scalar(ELEMS[rng.gen_range(0..ELEMS.len())]).into())
+        .collect();
+
+    let mut generator = ScopeGenerator {
+        rng,
+        inputs,
+        scalars,
+        output: (output, output_item),
+        visible: Vec::new(),
+    };
+    generator.scope(&mut builder.context.scope.borrow_mut(), 0);
+
+    builder.build(KernelSettings::default().cube_dim(CubeDim::new(16, 1, 1)))
+}
+
+fn random_item(rng: &mut StdRng) -> Item {
+    let elem = ELEMS[rng.gen_range(0..ELEMS.len())];
+    item(elem, VECTORIZATIONS[rng.gen_range(0..VECTORIZATIONS.len())])
+}
+
+fn item(elem: Elem, vectorization: u8) -> Item {
+    Item::vectorized(elem, NonZero::new(vectorization).filter(|v| v.get() > 1))
+}
+
+struct ScopeGenerator {
+    rng: StdRng,
+    inputs: Vec<(Variable, Item)>,
+    scalars: Vec<Variable>,
+    output: (Variable, Item),
+    /// The locals declared in each enclosing scope.
+    visible: Vec<Vec<Variable>>,
+}
+
+impl ScopeGenerator {
+    fn scope(&mut self, scope: &mut Scope, depth: u8) {
+        self.visible.push(Vec::new());
+
+        for _ in 0..self.rng.gen_range(1..=MAX_STATEMENTS) {
+            match self.rng.gen_range(0..10) {
+                0..=3 => self.arithmetic(scope),
+                4 => {
+                    self.compare(scope);
+                }
+                5 => self.cast(scope),
+                6 => self.store(scope),
+                _ if depth < MAX_DEPTH => self.branch(scope, depth),
+                _ => self.store(scope),
+            }
+        }
+
+        self.visible.pop();
+    }
+
+    /// A value of the item, either a visible local or scalar argument, or a new local assigned
+    /// from an input or a constant.
+    fn value(&mut self, scope: &mut Scope, item: Item) -> Variable {
+        let existing = self
+            .visible
+            .iter()
+            .flatten()
+            .chain(self.scalars.iter())
+            .filter(|variable| variable.item() == item)
+            .copied()
+            .collect::<Vec<_>>();
+        if !existing.is_empty() && self.rng.gen_bool(0.5) {
+            return existing[self.rng.gen_range(0..existing.len())];
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .filter(|(_, input)| *input == item)
+            .map(|(input, _)| *input)
+            .collect::<Vec<_>>();
+
+        let out = self.local(scope, item);
+        if !inputs.is_empty() && self.rng.gen_bool(0.5) {
+            let input = inputs[self.rng.gen_range(0..inputs.len())];
+            scope.register(Operator::Index(BinaryOperator {
+                lhs: input,
+                rhs: Variable::AbsolutePos,
+                out,
+            }));
+        } else {
+            scope.register(Operator::Assign(UnaryOperator {
+                input: self.constant(item.elem()),
+                out,
+            }));
+        }
+        out
+    }
+
+    fn local(&mut self, scope: &mut Scope, item: Item) -> Variable {
+        let local = scope.create_local(item);
+        self.visible.last_mut().unwrap().push(local);
+        local
+    }
+
+    fn constant(&mut self, elem: Elem) -> Variable {
+        let value = match elem {
+            Elem::Float(kind) => ConstantScalarValue::Float(self.rng.gen_range(-8.0..8.0), kind),
+            Elem::Int(kind) => ConstantScalarValue::Int(self.rng.gen_range(-8..8), kind),
+            Elem::UInt => ConstantScalarValue::UInt(self.rng.gen_range(0..8)),
+            Elem::Bool => ConstantScalarValue::Bool(self.rng.gen_bool(0.5)),
+            Elem::AtomicInt(_) | Elem::AtomicUInt => unreachable!("Atomics aren't generated"),
+        };
+        Variable::ConstantScalar(value)
+    }
+
+    fn arithmetic(&mut self, scope: &mut Scope) {
+        let item = random_item(&mut self.rng);
+        let lhs = self.value(scope, item);
+
+        let operator = if item.elem() == ELEMS[0] && self.rng.gen_bool(0.4) {
+            let unary = UnaryOperator {
+                input: lhs,
+                out: self.local(scope, item),
+            };
+            match self.rng.gen_range(0..8) {
+                0 => Operator::Abs(unary),
+                1 => Operator::Exp(unary),
+                2 => Operator::Cos(unary),
+                3 => Operator::Sqrt(unary),
+                4 => Operator::Floor(unary),
+                5 => Operator::Tanh(unary),
+                6 => Operator::Recip(unary),
+                _ => Operator::Neg(unary),
+            }
+        } else {
+            let binary = BinaryOperator {
+                lhs,
+                rhs: self.value(scope, item),
+                out: self.local(scope, item),
+            };
+            match self.rng.gen_range(0..7) {
+                0 => Operator::Add(binary),
+                1 => Operator::Sub(binary),
+                2 => Operator::Mul(binary),
+                3 => Operator::Div(binary),
+                4 => Operator::Max(binary),
+                5 => Operator::Min(binary),
+                _ => Operator::Modulo(binary),
+            }
+        };
+
+        scope.register(operator);
+    }
+
+    fn compare(&mut self, scope: &mut Scope) -> Variable {
+        let item = random_item(&mut self.rng);
+        let vectorization = item.vectorization.map(|v| v.get()).unwrap_or(1);
+        let binary = BinaryOperator {
+            lhs: self.value(scope, item),
+            rhs: self.value(scope, item),
+            out: self.local(scope, self::item(Elem::Bool, vectorization)),
+        };
+        let out = binary.out;
+
+        scope.register(match self.rng.gen_range(0..6) {
+            0 => Operator::Equal(binary),
+            1 => Operator::NotEqual(binary),
+            2 => Operator::Lower(binary),
+            3 => Operator::LowerEqual(binary),
+            4 => Operator::Greater(binary),
+            _ => Operator::GreaterEqual(binary),
+        });
+        out
+    }
+
+    fn cast(&mut self, scope: &mut Scope) {
+        let input = random_item(&mut self.rng);
+        let vectorization = input.vectorization.map(|v| v.get()).unwrap_or(1);
+        let elem = ELEMS[self.rng.gen_range(0..ELEMS.len())];
+
+        let cast = UnaryOperator {
+            input: self.value(scope, input),
+            out: self.local(scope, item(elem, vectorization)),
+        };
+        scope.register(Operator::Assign(cast));
+    }
+
+    fn store(&mut self, scope: &mut Scope) {
+        let (output, item) = self.output;
+        let value = self.value(scope, item);
+
+        scope.register(Operator::IndexAssign(BinaryOperator {
+            lhs: Variable::AbsolutePos,
+            rhs: value,
+            out: output,
+        }));
+    }
+
+    /// A scalar boolean, selected from a comparison of lines when needed.
+    fn condition(&mut self, scope: &mut Scope) -> Variable {
+        let cond = self.compare(scope);
+        if cond.item().vectorization.is_none() {
+            return cond;
+        }
+
+        let index = self
+            .rng
+            .gen_range(0..cond.item().vectorization.unwrap().get() as u32);
+        let out = self.local(scope, Item::new(Elem::Bool));
+        scope.register(Operator::Index(BinaryOperator {
+            lhs: cond,
+            rhs: index.into(),
+            out,
+        }));
+        out
+    }
+
+    fn branch(&mut self, scope: &mut Scope, depth: u8) {
+        match self.rng.gen_range(0..5) {
+            0 => {
+                let cond = self.condition(scope);
+                let mut child = scope.child();
+                self.scope(&mut child, depth + 1);
+                scope.register(Branch::If(Box::new(If { cond, scope: child })));
+            }
+            1 => {
+                let cond = self.condition(scope);
+                let mut scope_if = scope.child();
+                let mut scope_else = scope.child();
+                self.scope(&mut scope_if, depth + 1);
+                self.scope(&mut scope_else, depth + 1);
+                scope.register(Branch::IfElse(Box::new(IfElse {
+                    cond,
+                    scope_if,
+                    scope_else,
+                })));
+            }
+            2 => {
+                let mut child = scope.child();
+                let i = child.create_local_undeclared(Item::new(Elem::UInt));
+                self.visible.push(vec![i]);
+                self.scope(&mut child, depth + 1);
+                self.visible.pop();
+                scope.register(Branch::RangeLoop(Box::new(RangeLoop {
+                    i,
+                    start: 0u32.into(),
+                    end: LOOP_COUNT.into(),
+                    step: None,
+                    inclusive: self.rng.gen_bool(0.5),
+                    scope: child,
+                })));
+            }
+            3 => {
+                // Count the iterations so that the loop breaks.
+                let count = self.local(scope, Item::new(Elem::UInt));
+                scope.register(Operator::Assign(UnaryOperator {
+                    input: 0u32.into(),
+                    out: count,
+                }));
+
+                let mut child = scope.child();
+                let done = child.create_local(Item::new(Elem::Bool));
+                child.register(Operator::GreaterEqual(BinaryOperator {
+                    lhs: count,
+                    rhs: LOOP_COUNT.into(),
+                    out: done,
+                }));
+                let mut exit = child.child();
+                exit.register(Branch::Break);
+                child.register(Branch::If(Box::new(If {
+                    cond: done,
+                    scope: exit,
+                })));
+                child.register(Operator::Add(BinaryOperator {
+                    lhs: count,
+                    rhs: 1u32.into(),
+                    out: count,
+                }));
+
+                self.scope(&mut child, depth + 1);
+                scope.register(Branch::Loop(Box::new(Loop { scope: child })));
+            }
+            _ => {
+                let item = random_item(&mut self.rng);
+                let cond = self.condition(scope);
+                let select = Select {
+                    cond,
+                    then: self.value(scope, item),
+                    or_else: self.value(scope, item),
+                    out: self.local(scope, item),
+                };
+                scope.register(Branch::Select(select));
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Compile random kernels to WGSL and validate the result with naga.
+//!
+//! Set `CUBECL_FUZZ_CASES` to run more cases, and `CUBECL_FUZZ_SEED` to reproduce a single
+//! failing case.
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use cubecl_core::{CompilationOptions, Compiler, ExecutionMode};
+use cubecl_wgpu::WgslCompiler;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+mod generator;
+
+const DEFAULT_CASES: u64 = 256;
+
+#[test]
+fn random_kernels_compile_to_valid_wgsl() {
+    let seeds = match env_u64("CUBECL_FUZZ_SEED") {
+        Some(seed) => seed..seed + 1,
+        None => 0..env_u64("CUBECL_FUZZ_CASES").unwrap_or(DEFAULT_CASES),
+    };
+
+    let failures = seeds
+        .filter_map(|seed| {
+            check(seed)
+                .err()
+                .map(|error| format!("seed {seed}: {error}"))
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} kernels failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Compile the kernel generated from the seed, returning the reason it is invalid.
+fn check(seed: u64) -> Result<(), String> {
+    let kernel = generator::random_kernel(seed);
+    let source = catch_unwind(AssertUnwindSafe(|| {
+        WgslCompiler::compile(
+            kernel,
+            &CompilationOptions::default(),
+            ExecutionMode::Checked,
+        )
+        .to_string()
+    }))
+    .map_err(|panic| format!("compilation panicked: {}", panic_message(&panic)))?;
+
+    let module = naga::front::wgsl::parse_str(&source)
+        .map_err(|error| format!("{}\n{source}", error.emit_to_string(&source)))?;
+    Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(&module)
+        .map_err(|error| format!("{}\n{source}", error.emit_to_string(&source)))?;
+
+    Ok(())
+}
+
+fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> String {
+    panic
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| {
+            panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+        })
+        .unwrap_or_default()
+}
+
+fn env_u64(name: &str) -> Option<u64> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+}
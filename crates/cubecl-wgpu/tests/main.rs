@@ -1,7 +1,10 @@
 use common::*;
 use cubecl_core as cubecl;
-use cubecl_core::{prelude::*, CubeCount, CubeDim};
+use cubecl_core::ir::{FastMath, Item};
+use cubecl_core::{prelude::*, CompilationOptions, Compiler, CubeCount, CubeDim, ExecutionMode};
+use cubecl_wgpu::WgslCompiler;
 use pretty_assertions::assert_eq;
+use std::num::NonZero;
 
 mod common;
 
@@ -137,3 +140,185 @@ pub fn constant_array() {
     let expected = include_str!("constant_array.wgsl").replace("\r\n", "\n");
     assert_eq!(compile(kernel), expected);
 }
+
+#[cube]
+fn exp_powf<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    output[0] = F::exp(input[0]) + F::powf(input[0], input[1]);
+}
+
+#[test]
+pub fn fast_math_approximates_exp_and_powf() {
+    let compile = |fast_math| {
+        let mut builder = KernelBuilder::default();
+        let item = Item::new(f32::as_elem());
+        let input = builder.input_array(item);
+        let output = builder.output_array(item);
+        exp_powf::expand::<f32>(&mut builder.context, input.into(), output.into());
+
+        let definition = builder.build(KernelSettings::default().fast_math(fast_math));
+        <WgslCompiler as Compiler>::compile(
+            definition,
+            &CompilationOptions::default(),
+            ExecutionMode::Checked,
+        )
+        .to_string()
+    };
+
+    let strict = compile(FastMath::Strict);
+    assert!(strict.contains("exp(") && strict.contains("fn powf"));
+
+    let relaxed = compile(FastMath::Relaxed);
+    assert!(!relaxed.contains("exp(") && !relaxed.contains("fn powf"));
+    assert!(relaxed.contains("exp2("));
+}
+
+#[derive(CubeType, Clone, Copy)]
+#[expand(buffer)]
+pub struct Particle {
+    #[expand(line_size = 3)]
+    pos: Line<f32>,
+    #[expand(line_size = 3)]
+    vel: Line<f32>,
+    id: u32,
+}
+
+#[cube]
+fn struct_array_step(particles: &mut StructArray<Particle>) {
+    let mut particle = particles.read(UNIT_POS);
+    particle.pos += particle.vel;
+    particles.write(UNIT_POS, particle);
+}
+
+/// Structs are stored as `u32` words following the WGSL layout: `pos` in the words 0 to 2, `vel`
+/// in the words 4 to 6 after the padding of its `vec3` alignment, and `id` in the word 7, for a
+/// stride of 8 words.
+#[test]
+pub fn struct_array_layout() {
+    let mut builder = KernelBuilder::default();
+    let particles = builder.output_array(Item::new(u32::as_elem()));
+    struct_array_step::expand(&mut builder.context, particles.into());
+
+    let definition = builder.build(KernelSettings::default());
+    let kernel = <WgslCompiler as Compiler>::compile(
+        definition,
+        &CompilationOptions::default(),
+        ExecutionMode::Checked,
+    )
+    .to_string();
+    let expected = include_str!("struct_array.wgsl").replace("\r\n", "\n");
+    assert_eq!(kernel, expected);
+}
+
+#[cube]
+fn matrix_ops<F: Float>(
+    matrix: &Array<Line<F>>,
+    vector: &Array<Line<F>>,
+    product: &mut Array<Line<F>>,
+    transformed: &mut Array<Line<F>>,
+    determinant: &mut Array<F>,
+) {
+    let m = matrix[0];
+    product[0] = m.matrix_mul(m.transpose());
+    transformed[0] = m.matrix_mul(vector[0]);
+    determinant[0] = m.determinant();
+}
+
+/// 3x3 matrices are stored in padded `vec4` parts in the kernel, and as packed scalars in the
+/// storage buffers.
+#[test]
+pub fn matrix_3x3_compiles_to_valid_wgsl() {
+    let mut builder = KernelBuilder::default();
+    builder.context.matrix_ops = <WgslCompiler as Compiler>::matrix_ops;
+    let matrix = Item::vectorized(f32::as_elem(), NonZero::new(9));
+    let vector = Item::vectorized(f32::as_elem(), NonZero::new(3));
+    let inputs = (builder.input_array(matrix), builder.input_array(vector));
+    let outputs = (
+        builder.output_array(matrix),
+        builder.output_array(vector),
+        builder.output_array(Item::new(f32::as_elem())),
+    );
+    matrix_ops::expand::<f32>(
+        &mut builder.context,
+        inputs.0.into(),
+        inputs.1.into(),
+        outputs.0.into(),
+        outputs.1.into(),
+        outputs.2.into(),
+    );
+
+    let definition = builder.build(KernelSettings::default());
+    let kernel = <WgslCompiler as Compiler>::compile(
+        definition,
+        &CompilationOptions::default(),
+        ExecutionMode::Checked,
+    )
+    .to_string();
+
+    validate(&kernel);
+    assert!(kernel.contains("mat3x3<f32>("));
+    assert!(kernel.contains("input_0_global: array<f32>"));
+}
+
+#[cube]
+fn subcube_sum_line<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    output[UNIT_POS] = cubecl_core::prelude::subcube_sum(input[UNIT_POS]);
+}
+
+/// Instructions that can't be split in `vec4` parts reject lines of more than 4 elements instead
+/// of generating invalid WGSL.
+#[test]
+#[should_panic(expected = "Line sizes above 4 aren't supported by WGSL")]
+pub fn wide_lines_are_rejected_by_unsplittable_instructions() {
+    let mut builder = KernelBuilder::default();
+    let line = Item::vectorized(f32::as_elem(), NonZero::new(8));
+    let input = builder.input_array(line);
+    let output = builder.output_array(line);
+    subcube_sum_line::expand::<f32>(&mut builder.context, input.into(), output.into());
+
+    let definition = builder.build(KernelSettings::default());
+    <WgslCompiler as Compiler>::compile(
+        definition,
+        &CompilationOptions::default(),
+        ExecutionMode::Checked,
+    );
+}
+
+#[cube]
+fn line_wide<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+    let mut line = input[UNIT_POS] + input[UNIT_POS];
+    line[5] = input[UNIT_POS][1];
+    output[UNIT_POS] = line;
+}
+
+/// Lines of 16 elements aren't in the supported line sizes of WGSL, which skips them in the
+/// runtime tests, but kernels using them are split in `vec4` parts.
+#[test]
+pub fn wide_lines_compile_to_valid_wgsl() {
+    let mut builder = KernelBuilder::default();
+    let line = Item::vectorized(f32::as_elem(), NonZero::new(16));
+    let input = builder.input_array(line);
+    let output = builder.output_array(line);
+    line_wide::expand::<f32>(&mut builder.context, input.into(), output.into());
+
+    let definition = builder.build(KernelSettings::default());
+    let kernel = <WgslCompiler as Compiler>::compile(
+        definition,
+        &CompilationOptions::default(),
+        ExecutionMode::Checked,
+    )
+    .to_string();
+
+    validate(&kernel);
+    assert!(kernel.contains("array<array<vec4<f32>, 4>>"));
+}
+
+fn validate(kernel: &str) {
+    let module = naga::front::wgsl::parse_str(kernel)
+        .unwrap_or_else(|error| panic!("{}\n{kernel}", error.emit_to_string(kernel)));
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .unwrap_or_else(|error| panic!("{}\n{kernel}", error.emit_to_string(kernel)));
+}
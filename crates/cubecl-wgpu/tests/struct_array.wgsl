@@ -0,0 +1,81 @@
+@group(0)
+@binding(0)
+var<storage, read_write> output_0_global: array<u32>;
+
+@group(0)
+@binding(1)
+var<storage, read_write> info: array<u32>;
+
+const WORKGROUP_SIZE_X = 16u;
+const WORKGROUP_SIZE_Y = 16u;
+const WORKGROUP_SIZE_Z = 1u;
+
+@compute
+@workgroup_size(16, 16, 1)
+fn main(
+    @builtin(local_invocation_index) local_idx: u32,
+) {let rank: u32 = info[0];
+var l_0_0: u32;
+var l_0_1: vec3<f32>;
+var l_0_2: u32;
+var l_0_3: u32;
+var l_0_4: f32;
+var l_0_5: vec3<f32>;
+var l_0_6: u32;
+l_0_0 = local_idx * 8u;
+l_0_2 = l_0_0 + 0u;
+l_0_3 = output_0_global[l_0_2];
+l_0_4 = bitcast<f32>(l_0_3);
+l_0_1[0u] = l_0_4;
+l_0_3 = l_0_0 + 1u;
+l_0_2 = output_0_global[l_0_3];
+l_0_4 = bitcast<f32>(l_0_2);
+l_0_1[1u] = l_0_4;
+l_0_3 = l_0_0 + 2u;
+l_0_2 = output_0_global[l_0_3];
+l_0_4 = bitcast<f32>(l_0_2);
+l_0_1[2u] = l_0_4;
+l_0_3 = l_0_0 + 4u;
+l_0_2 = output_0_global[l_0_3];
+l_0_4 = bitcast<f32>(l_0_2);
+l_0_5[0u] = l_0_4;
+l_0_3 = l_0_0 + 5u;
+l_0_2 = output_0_global[l_0_3];
+l_0_4 = bitcast<f32>(l_0_2);
+l_0_5[1u] = l_0_4;
+l_0_3 = l_0_0 + 6u;
+l_0_2 = output_0_global[l_0_3];
+l_0_4 = bitcast<f32>(l_0_2);
+l_0_5[2u] = l_0_4;
+l_0_2 = l_0_0 + 7u;
+l_0_6 = output_0_global[l_0_2];
+l_0_3 = l_0_6;
+l_0_1 = l_0_1 + l_0_5;
+l_0_6 = local_idx * 8u;
+l_0_4 = l_0_1[0u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 0u;
+output_0_global[l_0_0] = l_0_2;
+l_0_4 = l_0_1[1u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 1u;
+output_0_global[l_0_0] = l_0_2;
+l_0_4 = l_0_1[2u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 2u;
+output_0_global[l_0_0] = l_0_2;
+l_0_4 = l_0_5[0u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 4u;
+output_0_global[l_0_0] = l_0_2;
+l_0_4 = l_0_5[1u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 5u;
+output_0_global[l_0_0] = l_0_2;
+l_0_4 = l_0_5[2u];
+l_0_2 = bitcast<u32>(l_0_4);
+l_0_0 = l_0_6 + 6u;
+output_0_global[l_0_0] = l_0_2;
+l_0_2 = l_0_6 + 7u;
+output_0_global[l_0_2] = l_0_3;
+}
\ No newline at end of file
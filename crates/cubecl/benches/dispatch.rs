@@ -0,0 +1,105 @@
+use cubecl::prelude::*;
+use cubecl_runtime::server::Handle;
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{run_benchmark, Benchmark};
+use cubecl::future;
+
+#[cube(launch)]
+fn execute<F: Float>(input: &Array<F>, output: &mut Array<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + F::new(1.0);
+    }
+}
+
+/// Launches tiny kernels from many threads at once, so the duration is dominated by the dispatch
+/// path of the runtime instead of the execution of the kernels.
+///
+/// Each sample makes the same number of launches whatever the number of threads, so the
+/// durations compare between runs.
+impl<R: Runtime, E: Float> Benchmark for DispatchBench<R, E> {
+    type Args = Vec<(Handle, Handle)>;
+
+    fn prepare(&self) -> Self::Args {
+        (0..self.threads)
+            .map(|_| {
+                let input = self.client.empty(self.len * core::mem::size_of::<E>());
+                let output = self.client.empty(self.len * core::mem::size_of::<E>());
+                (input, output)
+            })
+            .collect()
+    }
+
+    fn execute(&self, args: Self::Args) {
+        std::thread::scope(|scope| {
+            for (input, output) in args.iter() {
+                let client = self.client.clone();
+                scope.spawn(move || {
+                    for _ in 0..self.launches {
+                        execute::launch::<E, R>(
+                            &client,
+                            CubeCount::Static(1, 1, 1),
+                            CubeDim::new(self.len as u32, 1, 1),
+                            unsafe { ArrayArg::from_raw_parts(input, self.len, 1) },
+                            unsafe { ArrayArg::from_raw_parts(output, self.len, 1) },
+                        )
+                    }
+                });
+            }
+        });
+    }
+
+    fn num_samples(&self) -> usize {
+        20
+    }
+
+    fn options(&self) -> Option<String> {
+        Some(format!("{} launches", self.threads * self.launches))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "dispatch-{}-{}-{}threads",
+            R::name(),
+            E::as_elem(),
+            self.threads
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+struct DispatchBench<R: Runtime, E> {
+    threads: usize,
+    launches: usize,
+    len: usize,
+    client: ComputeClient<R::Server, R::Channel>,
+    _e: PhantomData<E>,
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime, E: Float>(device: R::Device, threads: usize) {
+    let client = R::client(&device);
+
+    let bench = DispatchBench::<R, E> {
+        threads,
+        launches: 4096 / threads,
+        len: 64,
+        client,
+        _e: PhantomData,
+    };
+    println!("{}", run_benchmark(bench));
+}
+
+fn main() {
+    for threads in [1, 2, 8, 16] {
+        #[cfg(feature = "cuda")]
+        run::<cubecl::cuda::CudaRuntime, f32>(Default::default(), threads);
+        #[cfg(feature = "wgpu")]
+        run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default(), threads);
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::OnceLock;
+
+use cubecl_core::Runtime;
+
+#[cfg(feature = "cuda")]
+use cubecl_cuda::{CudaDevice, CudaRuntime};
+#[cfg(feature = "wgpu")]
+use cubecl_wgpu::{AutoGraphicsApi, RuntimeOptions, WgpuDevice, WgpuRuntime};
+
+/// The device of the backend selected by [AutoRuntime].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AutoDevice {
+    /// A CUDA device, selected when the CUDA driver finds a GPU.
+    #[cfg(feature = "cuda")]
+    Cuda(CudaDevice),
+    /// A wgpu device, either a GPU through Vulkan, Metal or DirectX, or a CPU adapter.
+    #[cfg(feature = "wgpu")]
+    Wgpu(WgpuDevice),
+}
+
+/// Work generic over the runtime, run by [AutoRuntime] on the selected backend.
+///
+/// The runtimes have different server and device types, so they can't be hidden behind a single
+/// [Runtime], instead the task is monomorphized for each enabled backend and dispatched at run
+/// time.
+pub trait RuntimeTask {
+    /// The result of the task.
+    type Output;
+
+    /// Run the task with the runtime `R` on the device.
+    fn run<R: Runtime>(self, device: &R::Device) -> Self::Output;
+}
+
+/// Selects the best backend available on the machine among the enabled runtimes, so
+/// applications don't need `cfg`-gated selection trees.
+///
+/// In order of preference:
+///
+/// 1. CUDA, when the driver is installed and finds at least one device.
+/// 2. wgpu with the best adapter of the platform API (Vulkan, Metal or DirectX), GPUs first.
+/// 3. wgpu with the fallback adapter, a software rasterizer running on the CPU.
+///
+/// The selection is made once and cached for the lifetime of the process.
+///
+/// # Example
+///
+/// ```ignore
+/// struct Launch;
+///
+/// impl RuntimeTask for Launch {
+///     type Output = ();
+///
+///     fn run<R: Runtime>(self, device: &R::Device) {
+///         let client = R::client(device);
+///         // ...
+///     }
+/// }
+///
+/// AutoRuntime::run(Launch);
+/// ```
+pub struct AutoRuntime;
+
+impl AutoRuntime {
+    /// The device of the selected backend, `None` when no enabled backend can run kernels.
+    pub fn device() -> Option<AutoDevice> {
+        static DEVICE: OnceLock<Option<AutoDevice>> = OnceLock::new();
+
+        DEVICE.get_or_init(select_device).clone()
+    }
+
+    /// The name of the selected runtime, `None` when no enabled backend can run kernels.
+    pub fn name() -> Option<&'static str> {
+        match Self::device()? {
+            #[cfg(feature = "cuda")]
+            AutoDevice::Cuda(_) => Some(CudaRuntime::name()),
+            #[cfg(feature = "wgpu")]
+            AutoDevice::Wgpu(_) => Some(WgpuRuntime::name()),
+        }
+    }
+
+    /// Run the task on the selected backend.
+    ///
+    /// # Panics
+    ///
+    /// When no enabled backend can run kernels.
+    pub fn run<T: RuntimeTask>(task: T) -> T::Output {
+        match Self::device().expect("No backend available to run kernels") {
+            #[cfg(feature = "cuda")]
+            AutoDevice::Cuda(device) => task.run::<CudaRuntime>(&device),
+            #[cfg(feature = "wgpu")]
+            AutoDevice::Wgpu(device) => task.run::<WgpuRuntime>(&device),
+        }
+    }
+}
+
+fn select_device() -> Option<AutoDevice> {
+    #[cfg(feature = "cuda")]
+    if CudaDevice::count() > 0 {
+        return Some(AutoDevice::Cuda(CudaDevice::new(0)));
+    }
+
+    #[cfg(feature = "wgpu")]
+    {
+        let options = RuntimeOptions::default();
+        let device = WgpuDevice::BestAvailable;
+        if cubecl_wgpu::adapter_info::<AutoGraphicsApi>(&device, &options).is_some() {
+            return Some(AutoDevice::Wgpu(device));
+        }
+
+        // The client of the fallback adapter is registered here, the default options of the
+        // runtime only use it when asked through the environment.
+        let options = RuntimeOptions {
+            fallback_adapter: true,
+            ..options
+        };
+        let device = WgpuDevice::Cpu;
+        if cubecl_wgpu::adapter_info::<AutoGraphicsApi>(&device, &options).is_some() {
+            cubecl_wgpu::init_sync::<AutoGraphicsApi>(&device, options);
+            return Some(AutoDevice::Wgpu(device));
+        }
+    }
+
+    None
+}
@@ -9,5 +9,25 @@ pub use cubecl_cuda as cuda;
 #[cfg(feature = "hip")]
 pub use cubecl_hip as hip;
 
+#[cfg(feature = "opencl")]
+pub use cubecl_opencl as opencl;
+
+#[cfg(feature = "vulkan")]
+pub use cubecl_vulkan as vulkan;
+
+#[cfg(feature = "fft")]
+pub use cubecl_fft as fft;
+
+#[cfg(feature = "image")]
+pub use cubecl_image as image;
+
 #[cfg(feature = "linalg")]
 pub use cubecl_linalg as linalg;
+
+#[cfg(feature = "stdlib")]
+pub use cubecl_std as std;
+
+#[cfg(all(any(feature = "cuda", feature = "wgpu"), not(target_family = "wasm")))]
+mod auto;
+#[cfg(all(any(feature = "cuda", feature = "wgpu"), not(target_family = "wasm")))]
+pub use auto::*;
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use cubecl_core::backend::{
+    CompilationOptions, Compiler, CompilerRepresentation, Elem, ExecutionMode, HybridAllocator,
+    KernelDefinition, LocalAllocator,
+};
+
+/// Compiles the kernels to the text of their IR.
+///
+/// A backend translates the scope of the kernel to its own language instead, and declares the
+/// capabilities of its language with the optional methods of [Compiler], such as
+/// [device functions](Compiler::device_functions).
+#[derive(Clone, Debug, Default)]
+pub struct TemplateCompiler;
+
+/// A compiled kernel, displayed as its source.
+#[derive(Debug)]
+pub struct TemplateKernel {
+    /// The text of the IR of the kernel.
+    pub source: String,
+}
+
+impl Display for TemplateKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl CompilerRepresentation for TemplateKernel {
+    fn shared_memory_size(&self) -> usize {
+        // The template doesn't allocate shared memory.
+        0
+    }
+}
+
+impl Compiler for TemplateCompiler {
+    type Representation = TemplateKernel;
+
+    fn compile(
+        kernel: KernelDefinition,
+        _options: &CompilationOptions,
+        _mode: ExecutionMode,
+    ) -> Self::Representation {
+        TemplateKernel {
+            source: kernel.body.to_string(),
+        }
+    }
+
+    fn elem_size(elem: Elem) -> usize {
+        elem.size()
+    }
+
+    fn local_allocator() -> impl LocalAllocator {
+        HybridAllocator::default()
+    }
+}
@@ -0,0 +1,15 @@
+//! A template of a CubeCL backend, only built on the stable [backend](cubecl_core::backend)
+//! interface.
+//!
+//! The kernels are compiled to the text of their IR and the buffers are stored in host memory,
+//! so every part of the integration runs without a device. A new backend copies this crate and
+//! replaces the [compiler](TemplateCompiler) with the translation to its language, and the
+//! [server](TemplateServer) with the allocations and dispatches of its device.
+
+mod compiler;
+mod runtime;
+mod server;
+
+pub use compiler::*;
+pub use runtime::*;
+pub use server::*;
@@ -0,0 +1,81 @@
+use cubecl_core::backend::{
+    BytesStorage, ComputeClient, ComputeRuntime, DeviceProperties, Elem, Feature,
+    HardwareProperties, MemoryConfiguration, MemoryDeviceProperties, MemoryManagement,
+    MutexComputeChannel, Runtime,
+};
+use cubecl_core::ir::{FloatKind, IntKind};
+
+use crate::{TemplateCompiler, TemplateServer};
+
+/// The device of the template, a backend usually enumerates its adapters here.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct TemplateDevice;
+
+/// The runtime of the template backend.
+#[derive(Debug)]
+pub struct TemplateRuntime;
+
+type Server = TemplateServer;
+type Channel = MutexComputeChannel<Server>;
+
+static RUNTIME: ComputeRuntime<TemplateDevice, Server, Channel> = ComputeRuntime::new();
+
+impl Runtime for TemplateRuntime {
+    type Compiler = TemplateCompiler;
+    type Server = Server;
+    type Channel = Channel;
+    type Device = TemplateDevice;
+
+    fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
+        RUNTIME.client(device, create_client)
+    }
+
+    fn name() -> &'static str {
+        "template"
+    }
+
+    fn supported_line_sizes() -> &'static [u8] {
+        &[4, 2, 1]
+    }
+}
+
+fn create_client() -> ComputeClient<Server, Channel> {
+    // The properties are queried from the device by a backend.
+    let memory_properties = MemoryDeviceProperties {
+        max_page_size: 1024 * 1024 * 512,
+        alignment: 32,
+    };
+    let hardware_properties = HardwareProperties {
+        max_shared_memory_size: 32768,
+        max_cube_dim: (1024, 1024, 64),
+        max_units_per_cube: 1024,
+        max_cube_count: (u16::MAX as u32, u16::MAX as u32, u16::MAX as u32),
+        max_bindings: u32::MAX,
+        max_buffer_size: 1024 * 1024 * 512,
+        subcube_size: None,
+        num_multiprocessors: None,
+        max_units_per_multiprocessor: None,
+        max_cubes_per_multiprocessor: None,
+        registers_per_multiprocessor: None,
+    };
+    let memory_management = MemoryManagement::from_configuration(
+        BytesStorage::default(),
+        memory_properties.clone(),
+        MemoryConfiguration::default(),
+    );
+
+    // Kernels only get the features registered here, e.g. the subcube operations are lowered to
+    // shared memory without `Feature::Subcube`.
+    let mut device_properties = DeviceProperties::new(&[], memory_properties, hardware_properties);
+    for elem in [
+        Elem::Float(FloatKind::F32),
+        Elem::Int(IntKind::I32),
+        Elem::UInt,
+        Elem::Bool,
+    ] {
+        device_properties.register_feature(Feature::Type(elem));
+    }
+
+    let server = TemplateServer::new(memory_management);
+    ComputeClient::new(MutexComputeChannel::new(server), device_properties)
+}
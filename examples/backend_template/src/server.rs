@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Instant;
+
+use cubecl_core::backend::{
+    Binding, BindingResource, BytesStorage, CompilationOptions, ComputeServer, ComputeStorage,
+    CubeCount, CubeTask, ExecutionMode, Feature, Handle, KernelId, MemoryManagement, MemoryUsage,
+    OutOfMemoryError, Stream, TimestampsError, TimestampsResult,
+};
+
+use crate::TemplateCompiler;
+
+/// The server of the template, storing the buffers in host memory.
+///
+/// The kernels are compiled once per [id](KernelId) and cached, but not executed: a backend
+/// binds the resources of the bindings and dispatches the compiled kernel on its device.
+#[derive(Debug)]
+pub struct TemplateServer {
+    memory_management: MemoryManagement<BytesStorage>,
+    /// The source of each compiled kernel, where a backend keeps its compiled modules.
+    kernels: HashMap<KernelId, String>,
+    timestamps: Option<Instant>,
+}
+
+impl TemplateServer {
+    /// A server allocating its buffers with the memory management.
+    pub fn new(memory_management: MemoryManagement<BytesStorage>) -> Self {
+        Self {
+            memory_management,
+            kernels: HashMap::new(),
+            timestamps: None,
+        }
+    }
+
+    /// Compile the kernel, unless it was already compiled in this mode.
+    fn compile(&mut self, kernel: &dyn CubeTask<TemplateCompiler>, mode: ExecutionMode) {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        // The device doesn't register `Feature::Subcube`, so subcube operations are lowered.
+        let options = CompilationOptions { subcube: false };
+        self.kernels
+            .entry(kernel_id)
+            .or_insert_with(|| kernel.compile(&options, mode).source);
+    }
+}
+
+impl ComputeServer for TemplateServer {
+    type Kernel = Box<dyn CubeTask<TemplateCompiler>>;
+    type Storage = BytesStorage;
+    type Feature = Feature;
+
+    fn kernel_name(kernel: &Self::Kernel) -> String {
+        kernel.name().to_string()
+    }
+
+    fn read(&mut self, binding: Binding) -> impl Future<Output = Vec<u8>> + Send + 'static {
+        let bytes = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        let data = bytes.read().to_vec();
+        async move { data }
+    }
+
+    fn binding_size(&mut self, binding: &Binding) -> u64 {
+        let handle = self.memory_management.get(binding.memory.clone());
+        handle.size() - binding.offset_start.unwrap_or(0) - binding.offset_end.unwrap_or(0)
+    }
+
+    fn write(&mut self, binding: Binding, data: &[u8]) {
+        let bytes = self.memory_management.get_resource(
+            binding.memory,
+            binding.offset_start,
+            binding.offset_end,
+        );
+        bytes.write()[..data.len()].copy_from_slice(data);
+    }
+
+    fn get_resource(&mut self, binding: Binding) -> BindingResource<Self> {
+        let handle = self.memory_management.get(binding.clone().memory);
+        BindingResource::new(binding, self.memory_management.storage().get(&handle))
+    }
+
+    fn create(&mut self, data: &[u8]) -> Handle {
+        let handle = self.empty(data.len());
+        self.write(handle.clone().binding(), data);
+        handle
+    }
+
+    fn empty(&mut self, size: usize) -> Handle {
+        Handle::new(
+            self.memory_management.reserve(size as u64, None),
+            None,
+            None,
+        )
+    }
+
+    fn try_empty(&mut self, size: usize) -> Result<Handle, OutOfMemoryError> {
+        let memory = self.memory_management.try_reserve(size as u64, None)?;
+        Ok(Handle::new(memory, None, None))
+    }
+
+    unsafe fn execute(
+        &mut self,
+        kernel: Self::Kernel,
+        _count: CubeCount,
+        bindings: Vec<Binding>,
+        mode: ExecutionMode,
+        _stream: Stream,
+    ) {
+        self.compile(kernel.as_ref(), mode);
+
+        // A backend dispatches the compiled kernel with these resources, `count` cubes of
+        // `cube_dim` units.
+        for binding in bindings {
+            let _resource = self.get_resource(binding);
+        }
+    }
+
+    fn prepare(&mut self, kernels: Vec<(Self::Kernel, ExecutionMode)>) {
+        // A backend with a thread safe compiler compiles the kernels in parallel here.
+        for (kernel, mode) in kernels {
+            self.compile(kernel.as_ref(), mode);
+        }
+    }
+
+    fn flush(&mut self) {
+        // The kernels aren't executed, there's nothing to submit.
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn sync(&mut self) -> impl Future<Output = ()> + Send + 'static {
+        async move {}
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn sync_elapsed(&mut self) -> impl Future<Output = TimestampsResult> + Send + 'static {
+        let duration = match &mut self.timestamps {
+            Some(start) => {
+                let duration = start.elapsed();
+                *start = Instant::now();
+                Ok(duration)
+            }
+            None => Err(TimestampsError::Disabled),
+        };
+
+        async move { duration }
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        self.memory_management.memory_usage()
+    }
+
+    fn enable_timestamps(&mut self) {
+        self.timestamps.get_or_insert_with(Instant::now);
+    }
+
+    fn disable_timestamps(&mut self) {
+        self.timestamps = None;
+    }
+}
@@ -0,0 +1,60 @@
+use backend_template::{TemplateCompiler, TemplateDevice, TemplateRuntime};
+use cubecl_core as cubecl;
+use cubecl_core::backend::{CompilationOptions, Runtime};
+use cubecl_core::prelude::*;
+
+#[cube(launch)]
+fn add_one(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + 1.0;
+    }
+}
+
+fn launch(
+    client: &ComputeClient<
+        <TemplateRuntime as Runtime>::Server,
+        <TemplateRuntime as Runtime>::Channel,
+    >,
+) {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(4 * core::mem::size_of::<f32>());
+
+    add_one::launch::<TemplateRuntime>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new(4, 1, 1),
+        unsafe { ArrayArg::from_raw_parts(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts(&output, 4, 1) },
+    );
+}
+
+#[test]
+fn buffers_are_written_and_read() {
+    let client = TemplateRuntime::client(&TemplateDevice);
+    let handle = client.create(f32::as_bytes(&[1.0, 2.0]));
+
+    let bytes = client.read(handle.binding());
+
+    assert_eq!(f32::from_bytes(&bytes), [1.0, 2.0]);
+}
+
+#[test]
+fn kernels_are_compiled_to_their_ir() {
+    let client = TemplateRuntime::client(&TemplateDevice);
+
+    let kernels = client.record_kernels(|| launch(&client));
+
+    assert_eq!(kernels.len(), 1);
+    let (kernel, mode) = &kernels[0];
+    let compiled: CompiledKernel<TemplateCompiler> =
+        kernel.compile(&CompilationOptions::default(), *mode);
+    assert!(compiled.source.contains(" + 1f32"), "{}", compiled.source);
+}
+
+#[test]
+fn kernels_are_launched() {
+    let client = TemplateRuntime::client(&TemplateDevice);
+
+    launch(&client);
+    cubecl_core::future::block_on(client.sync());
+}
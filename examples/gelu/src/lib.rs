@@ -1,4 +1,5 @@
 use cubecl::prelude::*;
+use cubecl::server::Handle;
 
 #[cube(launch_unchecked)]
 /// A [Line] represents a contiguous series of elements where SIMD operations may be available.
@@ -20,6 +21,25 @@ fn gelu_scalar<F: Float>(x: Line<F>) -> Line<F> {
 
 pub fn launch<R: Runtime>(device: &R::Device) {
     let client = R::client(device);
+    let output_handle = launch_gelu::<R>(&client);
+
+    let bytes = client.read(output_handle.binding());
+    let output = f32::from_bytes(&bytes);
+
+    // Should be [-0.1587,  0.0000,  0.8413,  5.0000]
+    println!("Executed gelu with runtime {:?} => {output:?}", R::name());
+}
+
+/// Like [launch], but reads the output without blocking, which is necessary on wasm.
+pub async fn launch_async<R: Runtime>(device: &R::Device) -> Vec<f32> {
+    let client = R::client(device);
+    let output_handle = launch_gelu::<R>(&client);
+
+    let bytes = client.read_async(output_handle.binding()).await;
+    f32::from_bytes(&bytes).to_vec()
+}
+
+fn launch_gelu<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> Handle {
     let input = &[-1., 0., 1., 5.];
     let vectorization = 4;
     let output_handle = client.empty(input.len() * core::mem::size_of::<f32>());
@@ -27,7 +47,7 @@ pub fn launch<R: Runtime>(device: &R::Device) {
 
     unsafe {
         gelu_array::launch_unchecked::<f32, R>(
-            &client,
+            client,
             CubeCount::Static(1, 1, 1),
             CubeDim::new(input.len() as u32 / vectorization, 1, 1),
             ArrayArg::from_raw_parts(&input_handle, input.len(), vectorization as u8),
@@ -35,9 +55,5 @@ pub fn launch<R: Runtime>(device: &R::Device) {
         )
     };
 
-    let bytes = client.read(output_handle.binding());
-    let output = f32::from_bytes(&bytes);
-
-    // Should be [-0.1587,  0.0000,  0.8413,  5.0000]
-    println!("Executed gelu with runtime {:?} => {output:?}", R::name());
+    output_handle
 }
@@ -0,0 +1,23 @@
+//! Launch the gelu kernel from a web page with WebGPU.
+//!
+//! Build the package with `wasm-pack build --target web examples/gelu_web`, then serve the
+//! `examples/gelu_web` directory with any static file server and open `index.html` in a browser
+//! that supports WebGPU.
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use cubecl::wgpu::{init_async, AutoGraphicsApi, RuntimeOptions, WgpuDevice, WgpuRuntime};
+    use wasm_bindgen::prelude::*;
+
+    /// Initialize the device and execute gelu on it.
+    ///
+    /// The device can't be requested synchronously in the browser, so it has to be initialized
+    /// with [init_async] before any client is created.
+    #[wasm_bindgen]
+    pub async fn run() -> Vec<f32> {
+        let device = WgpuDevice::default();
+        init_async::<AutoGraphicsApi>(&device, RuntimeOptions::default()).await;
+
+        gelu::launch_async::<WgpuRuntime>(&device).await
+    }
+}